@@ -38,10 +38,18 @@ pub const PLAN_INSIGHTS_NOTICE_FAST_PATH_CLUSTERS_OPTIMIZE_DURATION: Config<Dura
     "Enable plan insights fast path clusters calculation if the optimize step took less than this duration.",
 );
 
+/// The maximum number of locally optimized `SELECT` plans to cache. Zero disables the cache.
+pub const PEEK_PLAN_CACHE_MAX_ENTRIES: Config<usize> = Config::new(
+    "peek_plan_cache_max_entries",
+    1024,
+    "The maximum number of optimized SELECT plans to cache. Zero disables the cache.",
+);
+
 /// Adds the full set of all compute `Config`s.
 pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
     configs
         .add(&ALLOW_USER_SESSIONS)
         .add(&ENABLE_STATEMENT_LIFECYCLE_LOGGING)
         .add(&PLAN_INSIGHTS_NOTICE_FAST_PATH_CLUSTERS_OPTIMIZE_DURATION)
+        .add(&PEEK_PLAN_CACHE_MAX_ENTRIES)
 }
@@ -10,22 +10,24 @@
 //! Coordinator bookkeeping for active compute sinks.
 
 use std::cmp::Ordering;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::iter;
+use std::sync::Arc;
 
 use anyhow::anyhow;
 use itertools::Itertools;
 use mz_adapter_types::connection::ConnectionId;
 use mz_compute_client::protocol::response::SubscribeBatch;
-use mz_controller_types::ClusterId;
+use mz_controller_types::{ClusterId, ReplicaId};
 use mz_expr::compare_columns;
 use mz_ore::cast::CastFrom;
 use mz_ore::now::EpochMillis;
 use mz_repr::adt::numeric;
-use mz_repr::{Datum, GlobalId, IntoRowIterator, Row, Timestamp};
+use mz_repr::{Datum, Diff, GlobalId, IntoRowIterator, Row, RowIterator, RowRef, Timestamp};
 use mz_sql::plan::SubscribeOutput;
 use timely::progress::Antichain;
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, oneshot, OwnedSemaphorePermit, Semaphore};
 
 use crate::coord::peek::PeekResponseUnary;
 use crate::{AdapterError, ExecuteResponse};
@@ -87,6 +89,9 @@ pub enum ActiveComputeSinkRetireReason {
     /// The compute sink was forcibly terminated because an object it depended on
     /// was dropped.
     DependencyDropped(String),
+    /// The compute sink was forcibly terminated because its results could
+    /// not be delivered as fast as they were produced.
+    FellBehind,
 }
 
 /// A description of an active subscribe from coord's perspective
@@ -112,8 +117,16 @@ pub struct ActiveSubscribe {
     pub start_time: EpochMillis,
     /// How to present the subscribe's output.
     pub output: SubscribeOutput,
+    /// Channel on which to hand the results of a shared dataflow to the task
+    /// delivering them to the client, if the subscribe shares a dataflow. See
+    /// [`ActiveSubscribe::spawn_fan_out`].
+    pub fan_out: Option<mpsc::Sender<Arc<SubscribeBatch>>>,
 }
 
+/// The number of batches of results that can be queued for the task delivering
+/// the results of a shared dataflow to a subscribe.
+const FAN_OUT_QUEUE_CAPACITY: usize = 1024;
+
 impl ActiveSubscribe {
     /// Initializes the subscription.
     ///
@@ -362,6 +375,87 @@ impl ActiveSubscribe {
         batch.upper.is_empty()
     }
 
+    /// Spawns a task that formats the results of a shared dataflow and
+    /// delivers them to the client, so that the coordinator does not need to
+    /// do so for every subscribe sharing the dataflow. Batches are handed to
+    /// the task with [`ActiveSubscribe::fan_out_response`].
+    ///
+    /// At most `max_buffered_rows` rows the client has not yet read are
+    /// buffered. If the client falls further behind, the task terminates the
+    /// subscribe.
+    pub fn spawn_fan_out(&mut self, max_buffered_rows: usize) {
+        let (tx, mut rx) = mpsc::channel::<Arc<SubscribeBatch>>(FAN_OUT_QUEUE_CAPACITY);
+        let (formatted_tx, mut formatted_rx) = mpsc::unbounded_channel();
+        let mut formatter = ActiveSubscribe {
+            conn_id: self.conn_id.clone(),
+            cluster_id: self.cluster_id,
+            depends_on: BTreeSet::new(),
+            channel: formatted_tx,
+            emit_progress: self.emit_progress,
+            as_of: self.as_of,
+            arity: self.arity,
+            start_time: self.start_time,
+            output: self.output.clone(),
+            fan_out: None,
+        };
+        let client = self.channel.clone();
+        let budget = Arc::new(Semaphore::new(
+            max_buffered_rows.min(Semaphore::MAX_PERMITS),
+        ));
+
+        mz_ore::task::spawn(|| "subscribe fan-out", async move {
+            while let Some(batch) = rx.recv().await {
+                let finished = formatter.process_response(Arc::unwrap_or_clone(batch));
+                while let Ok(response) = formatted_rx.try_recv() {
+                    let response = match response {
+                        PeekResponseUnary::Rows(rows) => {
+                            let count = u32::try_from(rows.count()).unwrap_or(u32::MAX);
+                            match Arc::clone(&budget).try_acquire_many_owned(count) {
+                                Ok(permit) => PeekResponseUnary::Rows(Box::new(BufferedRows {
+                                    rows,
+                                    _permit: permit,
+                                })),
+                                Err(_) => {
+                                    let _ = client
+                                        .send(PeekResponseUnary::Error(FELL_BEHIND_MESSAGE.into()));
+                                    return;
+                                }
+                            }
+                        }
+                        response => response,
+                    };
+                    if client.send(response).is_err() {
+                        return;
+                    }
+                }
+                if finished {
+                    return;
+                }
+            }
+        });
+        self.fan_out = Some(tx);
+    }
+
+    /// Hands a batch of results from a shared dataflow to the task spawned by
+    /// [`ActiveSubscribe::spawn_fan_out`].
+    ///
+    /// Returns the reason to retire the subscribe with, if it is finished or
+    /// the task cannot keep up with the dataflow.
+    pub fn fan_out_response(
+        &self,
+        batch: Arc<SubscribeBatch>,
+    ) -> Option<ActiveComputeSinkRetireReason> {
+        let finished = batch.updates.is_err() || batch.upper.is_empty();
+        let tx = self.fan_out.as_ref().expect("subscribe shares a dataflow");
+        match tx.try_send(batch) {
+            Ok(()) if finished => Some(ActiveComputeSinkRetireReason::Finished),
+            Ok(()) => None,
+            // The task has already terminated the subscribe.
+            Err(TrySendError::Closed(_)) => Some(ActiveComputeSinkRetireReason::Finished),
+            Err(TrySendError::Full(_)) => Some(ActiveComputeSinkRetireReason::FellBehind),
+        }
+    }
+
     /// Retires the subscribe with the specified reason.
     ///
     /// This method must be called on every subscribe before it is dropped. It
@@ -374,6 +468,9 @@ impl ActiveSubscribe {
             ActiveComputeSinkRetireReason::DependencyDropped(d) => PeekResponseUnary::Error(
                 format!("subscribe has been terminated because underlying {d} was dropped"),
             ),
+            ActiveComputeSinkRetireReason::FellBehind => {
+                PeekResponseUnary::Error(FELL_BEHIND_MESSAGE.into())
+            }
         };
         self.send(message);
     }
@@ -387,6 +484,367 @@ impl ActiveSubscribe {
     }
 }
 
+/// The error reported to subscribes that are terminated because their results
+/// could not be delivered as fast as they were produced.
+const FELL_BEHIND_MESSAGE: &str = "subscribe has been terminated because it fell too far behind";
+
+/// Rows delivered to the client of a subscribe sharing a dataflow, which count
+/// against the rows buffered for the subscribe until the client has read them.
+#[derive(Debug)]
+struct BufferedRows {
+    rows: Box<dyn RowIterator + Send + Sync>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl RowIterator for BufferedRows {
+    fn next(&mut self) -> Option<&RowRef> {
+        self.rows.next()
+    }
+
+    fn peek(&mut self) -> Option<&RowRef> {
+        self.rows.peek()
+    }
+
+    fn count(&self) -> usize {
+        self.rows.count()
+    }
+}
+
+/// Identifies the subscribes that can share a dataflow.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SharedSubscribeKey {
+    /// The cluster on which the dataflow runs.
+    pub cluster_id: ClusterId,
+    /// The replica the dataflow targets, if any.
+    pub replica_id: Option<ReplicaId>,
+    /// The subscribed relation.
+    pub from: GlobalId,
+}
+
+/// The subscribe dataflows whose results are fanned out to several subscribes.
+#[derive(Debug, Default)]
+pub struct SharedSubscribes {
+    /// The shared dataflows, keyed by the ID of the dataflow's sink.
+    dataflows: BTreeMap<GlobalId, SharedSubscribe>,
+    /// The most recently installed dataflow for each key, which is the one
+    /// later subscribes attach to.
+    by_key: BTreeMap<SharedSubscribeKey, GlobalId>,
+    /// The dataflow each subscribe receives its results from.
+    by_sink: BTreeMap<GlobalId, GlobalId>,
+}
+
+impl SharedSubscribes {
+    /// Registers the dataflow installed for the subscribe `sink_id` at `as_of`,
+    /// which later subscribes with the same `key` can attach to.
+    pub fn insert(&mut self, key: SharedSubscribeKey, sink_id: GlobalId, as_of: Timestamp) {
+        self.by_key.insert(key.clone(), sink_id);
+        self.by_sink.insert(sink_id, sink_id);
+        self.dataflows
+            .insert(sink_id, SharedSubscribe::new(key, sink_id, as_of));
+    }
+
+    /// Returns the dataflow a subscribe with `key` at `as_of` can attach to, if any.
+    pub fn attachable(&self, key: &SharedSubscribeKey, as_of: Timestamp) -> Option<GlobalId> {
+        self.by_key
+            .get(key)
+            .filter(|dataflow_id| self.dataflows[*dataflow_id].can_attach(as_of))
+            .copied()
+    }
+
+    /// Attaches the subscribe `sink_id` at `as_of` to the dataflow `dataflow_id`.
+    ///
+    /// If `snapshot_pending` is set, the subscribe's snapshot is computed by a
+    /// dataflow of its own, exported under `sink_id`, whose results must be
+    /// passed to [`SharedSubscribes::process_response`] as well.
+    ///
+    /// Returns the results to deliver to the subscribe right away, if the
+    /// dataflow's results have already passed `as_of`.
+    pub fn attach(
+        &mut self,
+        dataflow_id: GlobalId,
+        sink_id: GlobalId,
+        as_of: Timestamp,
+        snapshot_pending: bool,
+    ) -> Option<Arc<SubscribeBatch>> {
+        self.by_sink.insert(sink_id, dataflow_id);
+        self.dataflows
+            .get_mut(&dataflow_id)
+            .expect("known to exist")
+            .attach(sink_id, as_of, snapshot_pending)
+    }
+
+    /// Reports whether the results for `id` must be passed to
+    /// [`SharedSubscribes::process_response`].
+    pub fn contains(&self, id: &GlobalId) -> bool {
+        self.dataflows.contains_key(id) || self.by_sink.contains_key(id)
+    }
+
+    /// Returns the dataflow the subscribe `sink_id` receives results from, if
+    /// it is attached to a shared dataflow.
+    pub fn dataflow_id(&self, sink_id: &GlobalId) -> Option<GlobalId> {
+        self.by_sink.get(sink_id).copied()
+    }
+
+    /// Detaches the subscribe `sink_id`.
+    ///
+    /// Returns the IDs of the dataflows that are no longer needed: the
+    /// subscribe's snapshot dataflow, if it is still running, and the shared
+    /// dataflow, if no subscribe receives its results anymore. Returns `None`
+    /// if the subscribe is not attached to a shared dataflow.
+    pub fn detach(&mut self, sink_id: &GlobalId) -> Option<Vec<GlobalId>> {
+        let dataflow_id = self.by_sink.remove(sink_id)?;
+        let shared = self
+            .dataflows
+            .get_mut(&dataflow_id)
+            .expect("known to exist");
+
+        let mut to_drop = Vec::new();
+        if shared.detach(sink_id) {
+            to_drop.push(*sink_id);
+        }
+        if shared.is_empty() {
+            let shared = self.dataflows.remove(&dataflow_id).expect("known to exist");
+            if self.by_key.get(&shared.key) == Some(&dataflow_id) {
+                self.by_key.remove(&shared.key);
+            }
+            if !to_drop.contains(&dataflow_id) {
+                to_drop.push(dataflow_id);
+            }
+        }
+        Some(to_drop)
+    }
+
+    /// Processes a batch of results for `id`, which is either a shared
+    /// dataflow or the snapshot dataflow of an attached subscribe.
+    ///
+    /// Returns the batches to deliver to each of the subscribes, and the
+    /// snapshot dataflow that has completed and can be dropped, if any.
+    /// Subscribes receiving the same results share a batch.
+    pub fn process_response(
+        &mut self,
+        id: GlobalId,
+        batch: SubscribeBatch,
+    ) -> (
+        Vec<(GlobalId, Arc<SubscribeBatch>)>,
+        Option<(ClusterId, GlobalId)>,
+    ) {
+        // The subscribe a dataflow was installed for might have detached
+        // already, so look up dataflows first.
+        let dataflow_id = if self.dataflows.contains_key(&id) {
+            id
+        } else {
+            self.by_sink[&id]
+        };
+        let shared = self
+            .dataflows
+            .get_mut(&dataflow_id)
+            .expect("known to exist");
+        if id == dataflow_id {
+            (shared.process_batch(batch), None)
+        } else {
+            let (batches, completed) = shared.process_snapshot_batch(id, batch);
+            let completed = completed.then_some((shared.key.cluster_id, id));
+            (batches, completed)
+        }
+    }
+}
+
+/// A subscribe dataflow whose results are fanned out to several subscribes.
+///
+/// A later subscribe can attach if the dataflow has not yet emitted updates
+/// beyond its as-of. Its snapshot, if requested, is computed by a dataflow of
+/// its own that stops after the subscribe's as-of, so the coordinator does not
+/// need to keep the contents of the subscribed relation. Once the snapshot is
+/// complete and the dataflow's results have passed the subscribe's as-of, the
+/// subscribe receives all following updates.
+#[derive(Debug)]
+struct SharedSubscribe {
+    /// Identifies the subscribes that can attach to this dataflow.
+    key: SharedSubscribeKey,
+    /// The as-of of the dataflow.
+    as_of: Timestamp,
+    /// The subscribes receiving the dataflow's results.
+    subscribers: BTreeSet<GlobalId>,
+    /// Subscribes waiting for their snapshot or for the dataflow's results to
+    /// pass their as-of.
+    pending: BTreeMap<GlobalId, PendingSubscribe>,
+    /// The upper frontier of the results received so far.
+    upper: Antichain<Timestamp>,
+}
+
+/// A subscribe attached to a [`SharedSubscribe`] that does not yet receive the
+/// dataflow's results.
+#[derive(Debug)]
+struct PendingSubscribe {
+    /// The as-of of the subscribe.
+    as_of: Timestamp,
+    /// Whether the subscribe's snapshot dataflow is still running.
+    snapshot_pending: bool,
+    /// The dataflow's updates beyond `as_of` received while the subscribe was
+    /// pending.
+    ///
+    /// These only cover the time it takes to compute the snapshot, as the
+    /// dataflow's results cannot be beyond `as_of` when the subscribe attaches.
+    buffered: Vec<(Timestamp, Row, Diff)>,
+}
+
+impl SharedSubscribe {
+    /// Creates the bookkeeping for a dataflow installed for the subscribe
+    /// `sink_id` at `as_of`.
+    fn new(key: SharedSubscribeKey, sink_id: GlobalId, as_of: Timestamp) -> Self {
+        SharedSubscribe {
+            key,
+            as_of,
+            subscribers: BTreeSet::from([sink_id]),
+            pending: BTreeMap::new(),
+            upper: Antichain::from_elem(as_of),
+        }
+    }
+
+    /// Reports whether a subscribe at `as_of` can attach to this dataflow.
+    fn can_attach(&self, as_of: Timestamp) -> bool {
+        self.as_of <= as_of && self.upper.less_equal(&as_of.step_forward())
+    }
+
+    /// Attaches the subscribe `sink_id` at `as_of`.
+    fn attach(
+        &mut self,
+        sink_id: GlobalId,
+        as_of: Timestamp,
+        snapshot_pending: bool,
+    ) -> Option<Arc<SubscribeBatch>> {
+        assert!(self.can_attach(as_of), "cannot attach at {as_of}");
+        let pending = PendingSubscribe {
+            as_of,
+            snapshot_pending,
+            buffered: Vec::new(),
+        };
+        self.pending.insert(sink_id, pending);
+        self.activate_pending().pop().map(|(_, batch)| batch)
+    }
+
+    /// Detaches the subscribe `sink_id`.
+    ///
+    /// Returns whether the subscribe's snapshot dataflow is still running.
+    fn detach(&mut self, sink_id: &GlobalId) -> bool {
+        self.subscribers.remove(sink_id);
+        self.pending
+            .remove(sink_id)
+            .map_or(false, |pending| pending.snapshot_pending)
+    }
+
+    /// Reports whether no subscribe receives results from this dataflow anymore.
+    fn is_empty(&self) -> bool {
+        self.subscribers.is_empty() && self.pending.is_empty()
+    }
+
+    /// Processes a batch of results from the dataflow.
+    ///
+    /// Returns the batches to deliver to each of the subscribes.
+    fn process_batch(&mut self, batch: SubscribeBatch) -> Vec<(GlobalId, Arc<SubscribeBatch>)> {
+        if batch.updates.is_err() {
+            let batch = Arc::new(batch);
+            return self
+                .subscribers
+                .iter()
+                .chain(self.pending.keys())
+                .map(|id| (*id, Arc::clone(&batch)))
+                .collect();
+        }
+
+        for pending in self.pending.values_mut() {
+            let updates = batch.updates.as_ref().expect("checked above");
+            pending.buffered.extend(
+                updates
+                    .iter()
+                    .filter(|(time, _, _)| *time > pending.as_of)
+                    .cloned(),
+            );
+        }
+        self.upper = batch.upper.clone();
+
+        // The subscribers share the batch, and each one clones its updates off
+        // the coordinator's main loop.
+        let batch = Arc::new(batch);
+        let mut batches: Vec<_> = self
+            .subscribers
+            .iter()
+            .map(|id| (*id, Arc::clone(&batch)))
+            .collect();
+        batches.extend(self.activate_pending());
+
+        batches
+    }
+
+    /// Processes a batch of results from the snapshot dataflow of the pending
+    /// subscribe `sink_id`.
+    ///
+    /// Returns the batches to deliver to the subscribe, and whether the
+    /// snapshot is complete.
+    fn process_snapshot_batch(
+        &mut self,
+        sink_id: GlobalId,
+        batch: SubscribeBatch,
+    ) -> (Vec<(GlobalId, Arc<SubscribeBatch>)>, bool) {
+        let Some(pending) = self.pending.get_mut(&sink_id) else {
+            // The snapshot has already been delivered and the snapshot
+            // dataflow dropped, but some of its results were still in flight.
+            return (Vec::new(), false);
+        };
+        if !pending.snapshot_pending {
+            return (Vec::new(), false);
+        }
+        if batch.updates.is_err() {
+            // Forward the error, which retires the subscribe.
+            return (vec![(sink_id, Arc::new(batch))], false);
+        }
+        if batch.upper.less_equal(&pending.as_of) {
+            return (Vec::new(), false);
+        }
+
+        // The snapshot is complete. The snapshot dataflow stops after the
+        // subscribe's as-of, so its upper is replaced by the time after the
+        // as-of, to not report the subscribe as finished.
+        pending.snapshot_pending = false;
+        let batch = SubscribeBatch {
+            lower: batch.lower,
+            upper: Antichain::from_elem(pending.as_of.step_forward()),
+            updates: batch.updates,
+        };
+        let mut batches = vec![(sink_id, Arc::new(batch))];
+        batches.extend(self.activate_pending());
+        (batches, true)
+    }
+
+    /// Activates the pending subscribes whose snapshot is complete and whose
+    /// as-of the dataflow's results have passed, delivering the updates
+    /// buffered for them.
+    fn activate_pending(&mut self) -> Vec<(GlobalId, Arc<SubscribeBatch>)> {
+        let ready: Vec<_> = self
+            .pending
+            .iter()
+            .filter(|(_id, pending)| {
+                !pending.snapshot_pending && !self.upper.less_equal(&pending.as_of)
+            })
+            .map(|(id, _pending)| *id)
+            .collect();
+
+        let mut batches = Vec::new();
+        for id in ready {
+            let pending = self.pending.remove(&id).expect("known to exist");
+            self.subscribers.insert(id);
+
+            let batch = SubscribeBatch {
+                lower: Antichain::from_elem(pending.as_of),
+                upper: self.upper.clone(),
+                updates: Ok(pending.buffered),
+            };
+            batches.push((id, Arc::new(batch)));
+        }
+        batches
+    }
+}
+
 /// A description of an active copy to sink from the coordinator's perspective.
 #[derive(Debug)]
 pub struct ActiveCopyTo {
@@ -428,6 +886,9 @@ impl ActiveCopyTo {
             ActiveComputeSinkRetireReason::DependencyDropped(d) => Err(AdapterError::Unstructured(
                 anyhow!("copy has been terminated because underlying {d} was dropped"),
             )),
+            ActiveComputeSinkRetireReason::FellBehind => Err(AdapterError::Unstructured(anyhow!(
+                "copy has been terminated because it fell too far behind"
+            ))),
         };
         let _ = self.tx.send(message);
     }
@@ -561,6 +561,7 @@ impl CatalogState {
                         create_sql: None,
                         desc: table.desc.clone(),
                         defaults: vec![Expr::null(); table.desc.arity()],
+                        checks: Vec::new(),
                         conn_id: None,
                         resolved_ids: ResolvedIds(BTreeSet::new()),
                         custom_logical_compaction_window: table.is_retained_metrics_object.then(
@@ -940,9 +941,7 @@ impl CatalogState {
                 }
                 builtin_table_updates
             }
-            StateUpdateKind::Database(database) => {
-                vec![self.pack_database_update(&database.id, diff)]
-            }
+            StateUpdateKind::Database(database) => self.pack_database_update(&database.id, diff),
             StateUpdateKind::Schema(schema) => {
                 let db_spec = schema.database_id.into();
                 vec![self.pack_schema_update(&db_spec, &schema.id, diff)]
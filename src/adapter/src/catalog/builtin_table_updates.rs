@@ -19,14 +19,16 @@ use mz_catalog::builtin::{
     MZ_AWS_PRIVATELINK_CONNECTIONS, MZ_BASE_TYPES, MZ_CLUSTERS, MZ_CLUSTER_REPLICAS,
     MZ_CLUSTER_REPLICA_METRICS, MZ_CLUSTER_REPLICA_SIZES, MZ_CLUSTER_REPLICA_STATUSES,
     MZ_CLUSTER_SCHEDULES, MZ_COLUMNS, MZ_COMMENTS, MZ_CONNECTIONS, MZ_DATABASES,
-    MZ_DEFAULT_PRIVILEGES, MZ_EGRESS_IPS, MZ_FUNCTIONS, MZ_HISTORY_RETENTION_STRATEGIES,
-    MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_INTERNAL_CLUSTER_REPLICAS, MZ_KAFKA_CONNECTIONS,
-    MZ_KAFKA_SINKS, MZ_KAFKA_SOURCES, MZ_LIST_TYPES, MZ_MAP_TYPES, MZ_MATERIALIZED_VIEWS,
-    MZ_MATERIALIZED_VIEW_REFRESH_STRATEGIES, MZ_MYSQL_SOURCE_TABLES, MZ_OBJECT_DEPENDENCIES,
-    MZ_OPERATORS, MZ_POSTGRES_SOURCES, MZ_POSTGRES_SOURCE_TABLES, MZ_PSEUDO_TYPES, MZ_ROLES,
-    MZ_ROLE_MEMBERS, MZ_ROLE_PARAMETERS, MZ_SCHEMAS, MZ_SECRETS, MZ_SESSIONS, MZ_SINKS, MZ_SOURCES,
-    MZ_SSH_TUNNEL_CONNECTIONS, MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS, MZ_SYSTEM_PRIVILEGES,
-    MZ_TABLES, MZ_TYPES, MZ_TYPE_PG_METADATA, MZ_VIEWS, MZ_WEBHOOKS_SOURCES,
+    MZ_DATABASE_PARAMETERS, MZ_DEFAULT_PRIVILEGES, MZ_EGRESS_IPS, MZ_FUNCTIONS,
+    MZ_HISTORY_RETENTION_STRATEGIES, MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_INTERNAL_CLUSTER_REPLICAS,
+    MZ_KAFKA_CONNECTIONS, MZ_KAFKA_SINKS, MZ_KAFKA_SOURCES, MZ_LIST_TYPES, MZ_MAP_TYPES,
+    MZ_MATERIALIZED_VIEWS, MZ_MATERIALIZED_VIEW_REFRESH_STRATEGIES, MZ_MYSQL_SOURCE_TABLES,
+    MZ_OBJECT_DEPENDENCIES, MZ_OPERATORS, MZ_POSTGRES_SOURCES, MZ_POSTGRES_SOURCE_TABLES,
+    MZ_PSEUDO_TYPES, MZ_ROLES, MZ_ROLE_MEMBERS, MZ_ROLE_PARAMETERS, MZ_SCHEMAS, MZ_SECRETS,
+    MZ_SESSIONS, MZ_SINKS, MZ_SOURCES, MZ_SSH_TUNNEL_CONNECTIONS,
+    MZ_STORAGE_SHARD_BATCH_SIZES_HISTOGRAM, MZ_STORAGE_SHARD_READERS, MZ_STORAGE_SHARD_USAGE,
+    MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS, MZ_SYSTEM_PRIVILEGES, MZ_TABLES, MZ_TYPES,
+    MZ_TYPE_PG_METADATA, MZ_VIEWS, MZ_WEBHOOKS_SOURCES,
 };
 use mz_catalog::config::AwsPrincipalContext;
 use mz_catalog::memory::error::{Error, ErrorKind};
@@ -44,6 +46,9 @@ use mz_expr::MirScalarExpr;
 use mz_orchestrator::{CpuLimit, DiskLimit, MemoryLimit, ServiceProcessMetrics};
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
+use mz_ore::now::EpochMillis;
+use mz_persist_client::usage::{ShardReaderReport, ShardUsageReport};
+use mz_persist_client::ShardId;
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::jsonb::Jsonb;
@@ -127,11 +132,11 @@ impl CatalogState {
         &self,
         database_id: &DatabaseId,
         diff: Diff,
-    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+    ) -> Vec<BuiltinTableUpdate<&'static BuiltinTable>> {
         let database = self.get_database(database_id);
         let row = self.pack_privilege_array_row(database.privileges());
         let privileges = row.unpack_first();
-        BuiltinTableUpdate {
+        let database_update = BuiltinTableUpdate {
             id: &*MZ_DATABASES,
             row: Row::pack_slice(&[
                 Datum::String(&database.id.to_string()),
@@ -141,7 +146,35 @@ impl CatalogState {
                 privileges,
             ]),
             diff,
+        };
+        let mut updates = vec![database_update];
+
+        let session_vars_reference =
+            SessionVars::new_unchecked(&mz_build_info::DUMMY_BUILD_INFO, SYSTEM_USER.clone());
+
+        for (name, val) in &database.vars.map {
+            let result = session_vars_reference
+                .inspect(name)
+                .and_then(|var| var.check(val.borrow()));
+            let Ok(formatted_val) = result else {
+                // Note: all variables should have been validated by this point, so we
+                // shouldn't ever hit this.
+                tracing::error!(?name, ?val, ?result, "found invalid database default var");
+                continue;
+            };
+
+            updates.push(BuiltinTableUpdate {
+                id: &*MZ_DATABASE_PARAMETERS,
+                row: Row::pack_slice(&[
+                    Datum::String(&database.id.to_string()),
+                    Datum::String(name),
+                    Datum::String(&formatted_val),
+                ]),
+                diff,
+            });
         }
+
+        updates
     }
 
     pub(super) fn pack_schema_update(
@@ -262,7 +295,7 @@ impl CatalogState {
         let cluster = &self.clusters_by_id[&id];
         let row = self.pack_privilege_array_row(cluster.privileges());
         let privileges = row.unpack_first();
-        let (size, disk, replication_factor, azs) = match &cluster.config.variant {
+        let (size, disk, replication_factor, azs, autoscale) = match &cluster.config.variant {
             ClusterVariant::Managed(config) => (
                 Some(config.size.as_str()),
                 Some(config.disk),
@@ -272,8 +305,9 @@ impl CatalogState {
                 } else {
                     Some(config.availability_zones.clone())
                 },
+                config.autoscale.as_ref(),
             ),
-            ClusterVariant::Unmanaged => (None, None, None, None),
+            ClusterVariant::Unmanaged => (None, None, None, None, None),
         };
 
         let mut row = Row::default();
@@ -293,6 +327,14 @@ impl CatalogState {
         } else {
             packer.push(Datum::Null);
         }
+        packer.extend([
+            autoscale
+                .map(|autoscale| autoscale.min_size.as_str())
+                .into(),
+            autoscale
+                .map(|autoscale| autoscale.max_size.as_str())
+                .into(),
+        ]);
 
         let mut updates = Vec::new();
 
@@ -303,11 +345,12 @@ impl CatalogState {
         });
 
         if let ClusterVariant::Managed(managed_config) = &cluster.config.variant {
-            let row = match managed_config.schedule {
+            let row = match &managed_config.schedule {
                 ClusterSchedule::Manual => Row::pack_slice(&[
                     Datum::String(&id.to_string()),
                     Datum::String("manual"),
                     Datum::Null,
+                    Datum::Null,
                 ]),
                 ClusterSchedule::Refresh {
                     rehydration_time_estimate,
@@ -315,10 +358,23 @@ impl CatalogState {
                     Datum::String(&id.to_string()),
                     Datum::String("on-refresh"),
                     Datum::Interval(
-                        Interval::from_duration(&rehydration_time_estimate)
+                        Interval::from_duration(rehydration_time_estimate)
                             .expect("planning ensured that this is convertible back to Interval"),
                     ),
+                    Datum::Null,
                 ]),
+                ClusterSchedule::OffDuring { windows } => {
+                    let windows: Vec<_> = windows.iter().map(|w| w.to_string()).collect();
+                    let mut row = Row::default();
+                    let mut packer = row.packer();
+                    packer.extend([
+                        Datum::String(&id.to_string()),
+                        Datum::String("off-during"),
+                        Datum::Null,
+                    ]);
+                    packer.push_list(windows.iter().map(|w| Datum::String(w)));
+                    row
+                }
             };
             updates.push(BuiltinTableUpdate {
                 id: &*MZ_CLUSTER_SCHEDULES,
@@ -1232,13 +1288,16 @@ impl CatalogState {
         let mut updates = vec![];
         match &sink.connection {
             StorageSinkConnection::Kafka(KafkaSinkConnection {
-                topic: topic_name, ..
+                topic: topic_name,
+                delivery_guarantee,
+                ..
             }) => {
                 updates.push(BuiltinTableUpdate {
                     id: &*MZ_KAFKA_SINKS,
                     row: Row::pack_slice(&[
                         Datum::String(&id.to_string()),
                         Datum::String(topic_name.as_str()),
+                        Datum::String(delivery_guarantee.as_str()),
                     ]),
                     diff,
                 });
@@ -1682,6 +1741,88 @@ impl CatalogState {
         BuiltinTableUpdate { id, row, diff }
     }
 
+    pub fn pack_storage_shard_usage_updates(
+        &self,
+        shard_id: &ShardId,
+        report: &ShardUsageReport,
+        collection_timestamp: EpochMillis,
+        diff: Diff,
+    ) -> Vec<BuiltinTableUpdate<&'static BuiltinTable>> {
+        let shard_id = shard_id.to_string();
+        let ShardUsageReport {
+            live_bytes,
+            rollup_bytes,
+            pending_gc_bytes,
+            garbage_bytes,
+            batch_sizes,
+            compaction_debt_batches,
+            compaction_debt_bytes,
+            readers,
+        } = report;
+        let mut updates = vec![BuiltinTableUpdate {
+            id: &*MZ_STORAGE_SHARD_USAGE,
+            row: Row::pack_slice(&[
+                Datum::String(&shard_id),
+                Datum::UInt64(*live_bytes),
+                Datum::UInt64(*rollup_bytes),
+                Datum::UInt64(*pending_gc_bytes),
+                Datum::UInt64(*garbage_bytes),
+                Datum::UInt64(batch_sizes.values().sum()),
+                Datum::UInt64(*compaction_debt_batches),
+                Datum::UInt64(*compaction_debt_bytes),
+                Datum::TimestampTz(
+                    mz_ore::now::to_datetime(collection_timestamp)
+                        .try_into()
+                        .expect("must fit"),
+                ),
+            ]),
+            diff,
+        }];
+        updates.extend(
+            batch_sizes
+                .iter()
+                .map(|(size_bytes, count)| BuiltinTableUpdate {
+                    id: &*MZ_STORAGE_SHARD_BATCH_SIZES_HISTOGRAM,
+                    row: Row::pack_slice(&[
+                        Datum::String(&shard_id),
+                        Datum::UInt64(*size_bytes),
+                        Datum::UInt64(*count),
+                    ]),
+                    diff,
+                }),
+        );
+        updates.extend(readers.iter().map(|reader| {
+            let ShardReaderReport {
+                reader_id,
+                critical,
+                since,
+                hostname,
+                purpose,
+                last_heartbeat_timestamp_ms,
+                lease_duration_ms,
+            } = reader;
+            let kind = if *critical { "critical" } else { "leased" };
+            let last_heartbeat_at = last_heartbeat_timestamp_ms.map(|ts| {
+                Datum::TimestampTz(mz_ore::now::to_datetime(ts).try_into().expect("must fit"))
+            });
+            BuiltinTableUpdate {
+                id: &*MZ_STORAGE_SHARD_READERS,
+                row: Row::pack_slice(&[
+                    Datum::String(&shard_id),
+                    Datum::String(reader_id),
+                    Datum::String(kind),
+                    since.map_or(Datum::Null, |ts| Datum::MzTimestamp(ts.into())),
+                    Datum::String(hostname),
+                    Datum::String(purpose),
+                    last_heartbeat_at.unwrap_or(Datum::Null),
+                    lease_duration_ms.map_or(Datum::Null, Datum::UInt64),
+                ]),
+                diff,
+            }
+        }));
+        updates
+    }
+
     pub fn pack_egress_ip_update(
         &self,
         ip: &Ipv4Addr,
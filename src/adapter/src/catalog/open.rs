@@ -1075,6 +1075,7 @@ fn add_new_builtin_clusters_migration(
                         logging: default_logging_config(),
                         optimizer_feature_overrides: Default::default(),
                         schedule: Default::default(),
+                        autoscale: None,
                     }),
                 },
             )?;
@@ -1343,6 +1344,7 @@ mod builtin_migration_tests {
                         .with_column("a", ScalarType::Int32.nullable(true))
                         .with_key(vec![0]),
                     defaults: vec![Expr::null(); 1],
+                    checks: Vec::new(),
                     conn_id: None,
                     resolved_ids: ResolvedIds(BTreeSet::new()),
                     custom_logical_compaction_window: None,
@@ -1383,6 +1385,7 @@ mod builtin_migration_tests {
                         resolved_ids: ResolvedIds(resolved_ids),
                         cluster_id: ClusterId::User(1),
                         non_null_assertions: vec![],
+                        append_only: false,
                         custom_logical_compaction_window: None,
                         refresh_schedule: None,
                         initial_as_of: None,
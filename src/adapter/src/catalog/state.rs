@@ -813,6 +813,7 @@ impl CatalogState {
                 create_sql: Some(table.create_sql),
                 desc: table.desc,
                 defaults: table.defaults,
+                checks: table.checks,
                 conn_id: None,
                 resolved_ids,
                 custom_logical_compaction_window: custom_logical_compaction_window
@@ -919,6 +920,7 @@ impl CatalogState {
                     resolved_ids,
                     cluster_id: materialized_view.cluster_id,
                     non_null_assertions: materialized_view.non_null_assertions,
+                    append_only: materialized_view.append_only,
                     custom_logical_compaction_window: materialized_view.compaction_window,
                     refresh_schedule: materialized_view.refresh_schedule,
                     initial_as_of,
@@ -42,7 +42,7 @@ use mz_sql::session::user::{MZ_SUPPORT_ROLE_ID, MZ_SYSTEM_ROLE_ID};
 use mz_sql::session::vars::Value as VarValue;
 use mz_sql::session::vars::{OwnedVarInput, Var, TXN_WAL_TABLES};
 use mz_sql::{rbac, DEFAULT_SCHEMA};
-use mz_sql_parser::ast::{QualifiedReplica, Value};
+use mz_sql_parser::ast::{QualifiedReplica, RawClusterName, Statement, Value};
 use mz_storage_client::controller::StorageController;
 use mz_storage_types::controller::TxnWalTablesImpl;
 use std::collections::{BTreeMap, BTreeSet};
@@ -53,7 +53,9 @@ use crate::catalog::{
     object_type_to_audit_object_type, system_object_type_to_audit_object_type, BuiltinTableUpdate,
     Catalog, CatalogState, UpdatePrivilegeVariant,
 };
+use crate::coord::cluster_autoscaling::AutoscalingDecision;
 use crate::coord::cluster_scheduling::SchedulingDecision;
+use crate::coord::replica_health::ReplicaHealth;
 use crate::coord::ConnMeta;
 use crate::util::ResultExt;
 use crate::AdapterError;
@@ -65,12 +67,21 @@ pub enum Op {
         value: Option<Value>,
         window: CompactionWindow,
     },
+    AlterSetCluster {
+        id: GlobalId,
+        cluster: ClusterId,
+    },
     AlterRole {
         id: RoleId,
         name: String,
         attributes: RoleAttributes,
         vars: RoleVars,
     },
+    AlterDatabase {
+        id: DatabaseId,
+        name: String,
+        vars: RoleVars,
+    },
     CreateDatabase {
         name: String,
         owner_id: RoleId,
@@ -163,6 +174,22 @@ pub enum Op {
         name: String,
         config: ClusterConfig,
     },
+    /// Records a resize of a cluster by the autoscaler in the audit log.
+    RecordClusterAutoscaling {
+        id: ClusterId,
+        name: String,
+        decision: AutoscalingDecision,
+    },
+    /// Records a change of the health or the hydration status of a cluster replica in the audit
+    /// log.
+    RecordReplicaHealth {
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+        old_health: ReplicaHealth,
+        new_health: ReplicaHealth,
+        hydrated: bool,
+        last_heartbeat: Option<EpochMillis>,
+    },
     UpdateItem {
         id: GlobalId,
         name: QualifiedItemName,
@@ -262,6 +289,11 @@ pub enum ReplicaCreateDropReason {
     /// The automated cluster scheduling initiated the replica create or drop, e.g., a
     /// materialized view is needing a refresh on a SCHEDULE ON REFRESH cluster.
     ClusterScheduling(Vec<SchedulingDecision>),
+    /// The replica health subsystem replaced a replica that was crash looping, i.e., that went
+    /// from ready to not ready too many times within the configured window.
+    CrashLoop,
+    /// The cluster autoscaler resized the cluster.
+    Autoscaling(AutoscalingDecision),
 }
 
 impl ReplicaCreateDropReason {
@@ -277,12 +309,15 @@ impl ReplicaCreateDropReason {
                 CreateOrDropClusterReplicaReasonV1::Schedule,
                 Some(scheduling_decisions),
             ),
+            ReplicaCreateDropReason::CrashLoop | ReplicaCreateDropReason::Autoscaling(_) => {
+                (CreateOrDropClusterReplicaReasonV1::System, None)
+            }
         };
         (
             reason,
             scheduling_policies
                 .as_ref()
-                .map(SchedulingDecision::reasons_to_audit_log_reasons),
+                .and_then(SchedulingDecision::reasons_to_audit_log_reasons),
         )
     }
 }
@@ -607,6 +642,60 @@ impl Catalog {
 
                 Self::log_update(state, &id);
             }
+            Op::AlterSetCluster { id, cluster } => {
+                let entry = state.get_entry(&id);
+                let name = entry.name().clone();
+                let old_cluster = entry.item().cluster_id();
+
+                let mut new_entry = entry.clone();
+                let CatalogItem::MaterializedView(mview) = &mut new_entry.item else {
+                    return Err(AdapterError::Catalog(Error::new(ErrorKind::Internal(
+                        "planner should have rejected invalid alter set cluster item type"
+                            .to_string(),
+                    ))));
+                };
+                mview.cluster_id = cluster;
+                new_entry
+                    .item
+                    .update_sql(|ast| match ast {
+                        Statement::CreateMaterializedView(stmt) => {
+                            stmt.in_cluster = Some(RawClusterName::Resolved(cluster.to_string()));
+                            Ok(())
+                        }
+                        _ => Err(()),
+                    })
+                    .map_err(|_| {
+                        AdapterError::Catalog(Error::new(ErrorKind::Internal(
+                            "materialized view create_sql must be a CREATE MATERIALIZED VIEW"
+                                .to_string(),
+                        )))
+                    })?;
+
+                if Self::should_audit_log_item(new_entry.item()) {
+                    let full_name = Self::full_name_detail(
+                        &state.resolve_full_name(&name, session.map(|session| session.conn_id())),
+                    );
+                    CatalogState::add_to_audit_log(
+                        &state.system_configuration,
+                        oracle_write_ts,
+                        session,
+                        tx,
+                        audit_events,
+                        EventType::Alter,
+                        catalog_type_to_audit_object_type(new_entry.item().typ()),
+                        EventDetails::AlterSetClusterV1(mz_audit_log::AlterSetClusterV1 {
+                            id: id.to_string(),
+                            name: full_name,
+                            old_cluster: old_cluster.map(|cluster| cluster.to_string()),
+                            new_cluster: Some(cluster.to_string()),
+                        }),
+                    )?;
+                }
+
+                tx.update_item(id, new_entry.into())?;
+
+                Self::log_update(state, &id);
+            }
             Op::AlterRole {
                 id,
                 name,
@@ -636,6 +725,32 @@ impl Catalog {
 
                 info!("update role {name} ({id})");
             }
+            Op::AlterDatabase { id, name, vars } => {
+                let mut existing_database = state.get_database(&id).clone();
+                if id.is_system() {
+                    return Err(AdapterError::Catalog(Error::new(
+                        ErrorKind::ReadOnlyDatabase(existing_database.name),
+                    )));
+                }
+                existing_database.vars = vars;
+                tx.update_database(id, existing_database.into())?;
+
+                CatalogState::add_to_audit_log(
+                    &state.system_configuration,
+                    oracle_write_ts,
+                    session,
+                    tx,
+                    audit_events,
+                    EventType::Alter,
+                    ObjectType::Database,
+                    EventDetails::IdNameV1(mz_audit_log::IdNameV1 {
+                        id: id.to_string(),
+                        name: name.clone(),
+                    }),
+                )?;
+
+                info!("update database {name} ({id})");
+            }
             Op::CreateDatabase { name, owner_id } => {
                 let database_owner_privileges = vec![rbac::owner_privilege(
                     mz_sql::catalog::ObjectType::Database,
@@ -1856,6 +1971,55 @@ impl Catalog {
                 tx.update_cluster(id, cluster.into())?;
                 info!("update cluster {}", name);
             }
+            Op::RecordClusterAutoscaling { id, name, decision } => {
+                CatalogState::add_to_audit_log(
+                    &state.system_configuration,
+                    oracle_write_ts,
+                    session,
+                    tx,
+                    audit_events,
+                    EventType::Alter,
+                    ObjectType::Cluster,
+                    EventDetails::AutoscaleClusterV1(mz_audit_log::AutoscaleClusterV1 {
+                        cluster_id: id.to_string(),
+                        cluster_name: name,
+                        old_size: decision.old_size,
+                        new_size: decision.new_size,
+                        trigger: decision.trigger.as_kebab_case_str().to_string(),
+                    }),
+                )?;
+            }
+            Op::RecordReplicaHealth {
+                cluster_id,
+                replica_id,
+                old_health,
+                new_health,
+                hydrated,
+                last_heartbeat,
+            } => {
+                let cluster = state.get_cluster(cluster_id);
+                let replica = state.get_cluster_replica(cluster_id, replica_id);
+                CatalogState::add_to_audit_log(
+                    &state.system_configuration,
+                    oracle_write_ts,
+                    session,
+                    tx,
+                    audit_events,
+                    EventType::Alter,
+                    ObjectType::ClusterReplica,
+                    EventDetails::ReplicaHealthV1(mz_audit_log::ReplicaHealthV1 {
+                        cluster_id: cluster_id.to_string(),
+                        cluster_name: cluster.name.clone(),
+                        replica_id: replica_id.to_string(),
+                        replica_name: replica.name.clone(),
+                        old_health: old_health.as_kebab_case_str().to_string(),
+                        new_health: new_health.as_kebab_case_str().to_string(),
+                        reason: new_health.reason(),
+                        hydrated,
+                        last_heartbeat,
+                    }),
+                )?;
+            }
             Op::UpdateItem { id, name, to_item } => {
                 let mut entry = state.get_entry(&id).clone();
                 entry.name = name.clone();
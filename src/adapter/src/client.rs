@@ -164,6 +164,7 @@ impl Client {
         let conn_id = session.conn_id().clone();
         let secret_key = session.secret_key();
         let uuid = session.uuid();
+        let client_ip = session.client_ip();
         let application_name = session.application_name().into();
         let notice_tx = session.retain_notice_transmitter();
 
@@ -187,6 +188,7 @@ impl Client {
             conn_id: conn_id.clone(),
             secret_key,
             uuid,
+            client_ip,
             application_name,
             notice_tx,
         });
@@ -227,6 +229,23 @@ impl Client {
                 tracing::error!("failed to set peristed default, {err:?}");
             }
         }
+
+        // Override system defaults with the defaults of the session's database, unless the role
+        // has its own default.
+        if let Ok(database) = catalog.resolve_database(session.vars().database()) {
+            let role_vars = &catalog.get_role(&role_id).vars.map;
+            let vars_mut = session.vars_mut();
+            for (name, val) in &database.vars.map {
+                if role_vars.contains_key(name) {
+                    continue;
+                }
+                if let Err(err) = vars_mut.set_default(name, val.borrow()) {
+                    // Note: erroring here is unexpected, but we don't want to panic if somehow our
+                    // assumptions are wrong.
+                    tracing::error!("failed to set peristed database default, {err:?}");
+                }
+            }
+        }
         session
             .vars_mut()
             .end_transaction(EndTransactionAction::Commit);
@@ -338,6 +357,7 @@ Issue a SQL query to get started. Need help?
         self.send(Command::CancelRequest {
             conn_id,
             secret_key,
+            requested_at: Instant::now(),
         });
     }
 
@@ -353,6 +373,7 @@ Issue a SQL query to get started. Need help?
             conn_id,
             user: SUPPORT_USER.name.clone(),
             external_metadata_rx: None,
+            client_ip: None,
         });
         let mut session_client = self.startup(session).await?;
 
@@ -416,6 +437,31 @@ Issue a SQL query to get started. Need help?
         response
     }
 
+    /// Returns the role that a client presenting a verified TLS certificate
+    /// with the given names logs in as, according to the
+    /// `tls_client_cert_role_mapping` system variable.
+    pub async fn map_client_certificate(
+        &self,
+        common_name: Option<String>,
+        subject_alt_names: Vec<String>,
+    ) -> Result<String, AdapterError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Command::MapClientCertificate {
+            common_name,
+            subject_alt_names,
+            tx,
+        });
+        rx.await.expect("sender dropped")
+    }
+
+    /// Returns the SCRAM verifier of the password of the named role, if the
+    /// role exists and has a password.
+    pub async fn scram_verifier(&self, role_name: String) -> Option<String> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Command::GetScramVerifier { role_name, tx });
+        rx.await.expect("sender dropped")
+    }
+
     #[instrument(level = "debug")]
     fn send(&self, cmd: Command) {
         self.inner_cmd_tx
@@ -726,6 +772,21 @@ impl SessionClient {
         columns: Vec<usize>,
         rows: Vec<Row>,
         ctx_extra: ExecuteContextExtra,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let result = self.stage_rows(id, columns, rows).await;
+        self.retire_execute(ctx_extra, (&result).into());
+        result
+    }
+
+    /// Like [`SessionClient::insert_rows`], but does not retire the statement.
+    ///
+    /// This allows a single `COPY FROM` to add its rows to the transaction in
+    /// several batches as they arrive.
+    pub async fn stage_rows(
+        &mut self,
+        id: GlobalId,
+        columns: Vec<usize>,
+        rows: Vec<Row>,
     ) -> Result<ExecuteResponse, AdapterError> {
         // TODO: Remove this clone once we always have the session. It's currently needed because
         // self.session returns a mut ref, so we can't call it twice.
@@ -739,16 +800,13 @@ impl SessionClient {
         // Build an optimizer for this VIEW.
         let mut optimizer = optimize::view::Optimizer::new(optimizer_config, None);
 
-        let result: Result<_, AdapterError> =
-            mz_sql::plan::plan_copy_from(&pcx, &conn_catalog, id, columns, rows)
-                .err_into()
-                .and_then(|values| optimizer.optimize(values).err_into())
-                .and_then(|values| {
-                    // Copied rows must always be constants.
-                    Coordinator::insert_constant(&catalog, self.session(), id, values.into_inner())
-                });
-        self.retire_execute(ctx_extra, (&result).into());
-        result
+        mz_sql::plan::plan_copy_from(&pcx, &conn_catalog, id, columns, rows)
+            .err_into()
+            .and_then(|values| optimizer.optimize(values).err_into())
+            .and_then(|values| {
+                // Copied rows must always be constants.
+                Coordinator::insert_constant(&catalog, self.session(), id, values.into_inner())
+            })
     }
 
     /// Gets the current value of all system variables.
@@ -862,6 +920,8 @@ impl SessionClient {
                 Command::GetWebhook { .. } => typ = Some("webhook"),
                 Command::Startup { .. }
                 | Command::CatalogSnapshot { .. }
+                | Command::MapClientCertificate { .. }
+                | Command::GetScramVerifier { .. }
                 | Command::Commit { .. }
                 | Command::CancelRequest { .. }
                 | Command::PrivilegedCancelRequest { .. }
@@ -904,6 +964,7 @@ impl SessionClient {
                     cancelled = true;
                     inner_client.send(Command::PrivilegedCancelRequest {
                         conn_id: conn_id.clone(),
+                        requested_at: Instant::now(),
                     });
                 }
             };
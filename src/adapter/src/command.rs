@@ -9,12 +9,15 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::future::Future;
+use std::net::IpAddr;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Instant;
 
 use derivative::Derivative;
 use enum_kinds::EnumKind;
 use futures::future::BoxFuture;
+use futures::Stream;
 use mz_adapter_types::connection::{ConnectionId, ConnectionIdType};
 use mz_compute_types::ComputeInstanceId;
 use mz_ore::collections::CollectionExt;
@@ -60,6 +63,7 @@ pub enum Command {
         conn_id: ConnectionId,
         secret_key: u32,
         uuid: Uuid,
+        client_ip: Option<IpAddr>,
         application_name: String,
         notice_tx: mpsc::UnboundedSender<AdapterNotice>,
     },
@@ -80,10 +84,14 @@ pub enum Command {
     CancelRequest {
         conn_id: ConnectionIdType,
         secret_key: u32,
+        /// When the cancellation was requested.
+        requested_at: Instant,
     },
 
     PrivilegedCancelRequest {
         conn_id: ConnectionId,
+        /// When the cancellation was requested.
+        requested_at: Instant,
     },
 
     GetWebhook {
@@ -93,6 +101,17 @@ pub enum Command {
         tx: oneshot::Sender<Result<AppendWebhookResponse, AppendWebhookError>>,
     },
 
+    MapClientCertificate {
+        common_name: Option<String>,
+        subject_alt_names: Vec<String>,
+        tx: oneshot::Sender<Result<String, AdapterError>>,
+    },
+
+    GetScramVerifier {
+        role_name: String,
+        tx: oneshot::Sender<Option<String>>,
+    },
+
     GetSystemVars {
         conn_id: ConnectionId,
         tx: oneshot::Sender<Result<GetVariablesResponse, AdapterError>>,
@@ -142,6 +161,8 @@ impl Command {
             | Command::CatalogSnapshot { .. }
             | Command::PrivilegedCancelRequest { .. }
             | Command::GetWebhook { .. }
+            | Command::MapClientCertificate { .. }
+            | Command::GetScramVerifier { .. }
             | Command::Terminate { .. }
             | Command::GetSystemVars { .. }
             | Command::SetSystemVars { .. }
@@ -160,6 +181,8 @@ impl Command {
             | Command::CatalogSnapshot { .. }
             | Command::PrivilegedCancelRequest { .. }
             | Command::GetWebhook { .. }
+            | Command::MapClientCertificate { .. }
+            | Command::GetScramVerifier { .. }
             | Command::Terminate { .. }
             | Command::GetSystemVars { .. }
             | Command::SetSystemVars { .. }
@@ -180,6 +203,8 @@ pub struct Response<T> {
 
 pub type RowsFuture = Pin<Box<dyn Future<Output = PeekResponseUnary> + Send>>;
 
+pub type RowsStream = Pin<Box<dyn Stream<Item = PeekResponseUnary> + Send + Sync>>;
+
 /// The response to [`Client::startup`](crate::Client::startup).
 #[derive(Derivative)]
 #[derivative(Debug)]
@@ -326,7 +351,10 @@ pub enum ExecuteResponse {
     /// The temporary objects associated with the session have been discarded.
     DiscardedTemp,
     /// All state associated with the session has been discarded.
-    DiscardedAll,
+    DiscardedAll {
+        /// Session parameters that changed because they were reset.
+        params: BTreeMap<&'static str, String>,
+    },
     /// The requested object was dropped.
     DroppedObject(ObjectType),
     /// The requested objects were dropped.
@@ -355,6 +383,11 @@ pub enum ExecuteResponse {
     Raised,
     /// The requested objects were reassigned.
     ReassignOwned,
+    /// All session variables were reset to their defaults.
+    ResetAllVariables {
+        /// Session parameters that changed because they were reset.
+        params: BTreeMap<&'static str, String>,
+    },
     /// The requested privilege was revoked.
     RevokedPrivilege,
     /// The requested role was revoked.
@@ -372,6 +405,14 @@ pub enum ExecuteResponse {
         #[derivative(Debug = "ignore")]
         rows: Box<dyn RowIterator + Send + Sync>,
     },
+    /// Like `SendingRows`, but the rows are delivered in batches by the
+    /// specified stream, which only produces a batch when it is polled.
+    SendingRowsStreaming {
+        #[derivative(Debug = "ignore")]
+        rows: RowsStream,
+        instance_id: ComputeInstanceId,
+        strategy: StatementExecutionStrategy,
+    },
     /// The specified variable was set to a new value.
     SetVariable {
         name: String,
@@ -399,8 +440,6 @@ pub enum ExecuteResponse {
     },
     /// The specified number of rows were updated in the requested table.
     Updated(usize),
-    /// A connection was validated.
-    ValidatedConnection,
 }
 
 impl TryFrom<&Statement<Raw>> for ExecuteResponse {
@@ -487,7 +526,7 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             ExecuteResponseKind::DeclaredCursor => Ok(ExecuteResponse::DeclaredCursor),
             ExecuteResponseKind::Deleted => Err(()),
             ExecuteResponseKind::DiscardedTemp => Ok(ExecuteResponse::DiscardedTemp),
-            ExecuteResponseKind::DiscardedAll => Ok(ExecuteResponse::DiscardedAll),
+            ExecuteResponseKind::DiscardedAll => Err(()),
             ExecuteResponseKind::DroppedObject => Err(()),
             ExecuteResponseKind::DroppedOwned => Ok(ExecuteResponse::DroppedOwned),
             ExecuteResponseKind::EmptyQuery => Ok(ExecuteResponse::EmptyQuery),
@@ -500,6 +539,7 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             ExecuteResponseKind::ReassignOwned => Ok(ExecuteResponse::ReassignOwned),
             ExecuteResponseKind::RevokedPrivilege => Ok(ExecuteResponse::RevokedPrivilege),
             ExecuteResponseKind::RevokedRole => Ok(ExecuteResponse::RevokedRole),
+            ExecuteResponseKind::ResetAllVariables => Err(()),
             ExecuteResponseKind::SendingRows => Err(()),
             ExecuteResponseKind::SetVariable => Err(()),
             ExecuteResponseKind::StartedTransaction => Ok(ExecuteResponse::StartedTransaction),
@@ -507,8 +547,8 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             ExecuteResponseKind::TransactionCommitted => Err(()),
             ExecuteResponseKind::TransactionRolledBack => Err(()),
             ExecuteResponseKind::Updated => Err(()),
-            ExecuteResponseKind::ValidatedConnection => Ok(ExecuteResponse::ValidatedConnection),
             ExecuteResponseKind::SendingRowsImmediate => Err(()),
+            ExecuteResponseKind::SendingRowsStreaming => Err(()),
         }
     }
 }
@@ -545,7 +585,7 @@ impl ExecuteResponse {
             DeclaredCursor => Some("DECLARE CURSOR".into()),
             Deleted(n) => Some(format!("DELETE {}", n)),
             DiscardedTemp => Some("DISCARD TEMP".into()),
-            DiscardedAll => Some("DISCARD ALL".into()),
+            DiscardedAll { .. } => Some("DISCARD ALL".into()),
             DroppedObject(o) => Some(format!("DROP {o}")),
             DroppedOwned => Some("DROP OWNED".into()),
             EmptyQuery => None,
@@ -567,15 +607,14 @@ impl ExecuteResponse {
             ReassignOwned => Some("REASSIGN OWNED".into()),
             RevokedPrivilege => Some("REVOKE".into()),
             RevokedRole => Some("REVOKE ROLE".into()),
-            SendingRows { .. } | SendingRowsImmediate { .. } => None,
-            SetVariable { reset: true, .. } => Some("RESET".into()),
+            SendingRows { .. } | SendingRowsImmediate { .. } | SendingRowsStreaming { .. } => None,
+            ResetAllVariables { .. } | SetVariable { reset: true, .. } => Some("RESET".into()),
             SetVariable { reset: false, .. } => Some("SET".into()),
             StartedTransaction { .. } => Some("BEGIN".into()),
             Subscribing { .. } => None,
             TransactionCommitted { .. } => Some("COMMIT".into()),
             TransactionRolledBack { .. } => Some("ROLLBACK".into()),
             Updated(n) => Some(format!("UPDATE {}", n)),
-            ValidatedConnection => Some("VALIDATE CONNECTION".into()),
         }
     }
 
@@ -606,6 +645,7 @@ impl ExecuteResponse {
             AlterDefaultPrivileges => &[AlteredDefaultPrivileges],
             AlterSetCluster => &[AlteredObject],
             AlterRole => &[AlteredRole],
+            AlterDatabase => &[AlteredObject],
             AlterSystemSet | AlterSystemReset | AlterSystemResetAll => {
                 &[AlteredSystemConfiguration]
             }
@@ -640,6 +680,7 @@ impl ExecuteResponse {
                 ExecuteResponseKind::CopyTo,
                 SendingRows,
                 SendingRowsImmediate,
+                SendingRowsStreaming,
             ],
             Execute | ReadThenWrite => &[
                 Deleted,
@@ -660,10 +701,11 @@ impl ExecuteResponse {
             PlanKind::SetVariable | ResetVariable | PlanKind::SetTransaction => {
                 &[ExecuteResponseKind::SetVariable]
             }
+            PlanKind::ResetAllVariables => &[ExecuteResponseKind::ResetAllVariables],
             PlanKind::Subscribe => &[Subscribing, ExecuteResponseKind::CopyTo],
             StartTransaction => &[StartedTransaction],
             SideEffectingFunc => &[SendingRows, SendingRowsImmediate],
-            ValidateConnection => &[ExecuteResponseKind::ValidatedConnection],
+            ValidateConnection => &[SendingRowsImmediate],
         }
     }
 }
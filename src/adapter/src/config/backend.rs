@@ -31,6 +31,7 @@ impl SystemParameterBackend {
             conn_id,
             user: SYSTEM_USER.name.clone(),
             external_metadata_rx: None,
+            client_ip: None,
         });
         let session_client = client.startup(session).await?;
         Ok(Self { session_client })
@@ -91,10 +91,12 @@ use mz_adapter_types::connection::ConnectionId;
 use mz_build_info::BuildInfo;
 use mz_catalog::config::{AwsPrincipalContext, ClusterReplicaSizeMap};
 use mz_catalog::memory::objects::{
-    CatalogEntry, CatalogItem, ClusterReplicaProcessStatus, Connection, DataSourceDesc, Source,
+    CatalogEntry, CatalogItem, ClusterReplicaProcessStatus, ClusterVariantManaged, Connection,
+    DataSourceDesc, Source,
 };
 use mz_cloud_resources::{CloudResourceController, VpcEndpointConfig, VpcEndpointEvent};
 use mz_compute_client::controller::error::InstanceMissing;
+use mz_compute_client::protocol::response::PeekResponse;
 use mz_compute_types::dataflows::DataflowDescription;
 use mz_compute_types::plan::Plan;
 use mz_compute_types::ComputeInstanceId;
@@ -113,7 +115,8 @@ use mz_ore::task::{spawn, JoinHandle};
 use mz_ore::thread::JoinHandleExt;
 use mz_ore::tracing::{OpenTelemetryContext, TracingHandle};
 use mz_ore::{soft_assert_or_log, soft_panic_or_log, stack};
-use mz_persist_client::usage::{ShardsUsageReferenced, StorageUsageClient};
+use mz_persist_client::usage::{ShardUsageReport, ShardsUsageReferenced, StorageUsageClient};
+use mz_persist_client::ShardId;
 use mz_repr::explain::{ExplainConfig, ExplainFormat};
 use mz_repr::global_id::TransientIdGen;
 use mz_repr::role_id::RoleId;
@@ -149,16 +152,20 @@ use tracing::{debug, info, info_span, span, warn, Instrument, Level, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 use uuid::Uuid;
 
-use crate::active_compute_sink::ActiveComputeSink;
+use crate::active_compute_sink::{ActiveComputeSink, SharedSubscribes};
 use crate::catalog::{BuiltinTableUpdate, Catalog};
 use crate::client::{Client, Handle};
 use crate::command::{Command, ExecuteResponse};
 use crate::config::{SynchronizedParameters, SystemParameterFrontend, SystemParameterSyncConfig};
 use crate::coord::appends::{Deferred, GroupCommitPermit, PendingWriteTxn};
+use crate::coord::cluster_autoscaling::PendingResize;
 use crate::coord::cluster_scheduling::SchedulingDecision;
 use crate::coord::id_bundle::CollectionIdBundle;
-use crate::coord::peek::PendingPeek;
+use crate::coord::peek::{PagedPeek, PeekResultCache, PendingPeek};
+use crate::coord::plan_cache::{PeekPlanCache, PeekPlanCacheKey};
 use crate::coord::read_policy::ReadHoldsInner;
+use crate::coord::replica_health::{CrashLoopReplacements, ReplicaHealthTracker};
+use crate::coord::statistics::CollectionStatistics;
 use crate::coord::timeline::{TimelineContext, TimelineState};
 use crate::coord::timestamp_selection::{TimestampContext, TimestampDetermination};
 use crate::error::AdapterError;
@@ -173,7 +180,7 @@ use crate::optimize::{self, Optimize, OptimizerConfig};
 use crate::session::{EndTransactionAction, Session};
 use crate::statement_logging::{StatementEndedExecutionReason, StatementLifecycleEvent};
 use crate::util::{ClientTransmitter, CompletedClientTransmitter, ResultExt};
-use crate::webhook::{WebhookAppenderInvalidator, WebhookConcurrencyLimiter};
+use crate::webhook::{WebhookAppenderInvalidator, WebhookConcurrencyLimiter, WebhookRowLimit};
 use crate::{flags, AdapterNotice, ReadHolds, TimestampProvider};
 use mz_catalog::builtin::BUILTINS;
 use mz_catalog::durable::OpenableDurableCatalogState;
@@ -193,16 +200,25 @@ pub(crate) mod timestamp_selection;
 
 mod appends;
 mod catalog_serving;
+pub mod cluster_autoscaling;
 pub mod cluster_scheduling;
 mod command_handler;
 pub mod consistency;
 mod ddl;
 mod indexes;
 mod message_handler;
+mod plan_cache;
 mod privatelink_status;
 pub mod read_policy;
+pub mod replica_health;
 mod sequencer;
+mod shard_usage;
 mod sql;
+mod statistics;
+
+/// The interval at which the coordinator renews the compute leases of transient work owned by
+/// active connections. Must be comfortably shorter than `compute_transient_dataflow_lease`.
+const COMPUTE_LEASE_RENEWAL_INTERVAL: Duration = Duration::from_secs(10);
 
 #[derive(Debug)]
 pub enum Message<T = mz_repr::Timestamp> {
@@ -231,10 +247,19 @@ pub enum Message<T = mz_repr::Timestamp> {
     CancelPendingPeeks {
         conn_id: ConnectionId,
     },
+    /// Issues the peek for the next page of a paged peek.
+    PeekPage {
+        peek: PagedPeek,
+        sender: oneshot::Sender<PeekResponse>,
+    },
     LinearizeReads,
     StorageUsageSchedule,
     StorageUsageFetch,
     StorageUsageUpdate(ShardsUsageReferenced),
+    CollectionStatisticsFetch,
+    CollectionStatisticsUpdate(BTreeMap<GlobalId, CollectionStatistics>),
+    StorageShardUsageFetch,
+    StorageShardUsageUpdate(BTreeMap<ShardId, ShardUsageReport>),
 
     /// Performs any cleanup and logging actions necessary for
     /// finalizing a statement execution.
@@ -284,9 +309,26 @@ pub enum Message<T = mz_repr::Timestamp> {
         span: Span,
         stage: ExplainTimestampStage,
     },
+    AlterSetClusterStageReady {
+        ctx: ExecuteContext,
+        span: Span,
+        stage: AlterSetClusterStage,
+    },
+    AlterClusterStageReady {
+        ctx: ExecuteContext,
+        span: Span,
+        stage: AlterClusterStage,
+    },
     DrainStatementLog,
     PrivateLinkVpcEndpointEvents(Vec<VpcEndpointEvent>),
     CheckSchedulingPolicies,
+    /// Renews the compute leases of transient work owned by active connections.
+    RenewComputeLeases,
+    /// Replaces a crash looping replica once the backoff that delayed its replacement expired.
+    ReplaceCrashLoopingReplica {
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+    },
 
     /// Scheduling policy decisions about turning clusters On/Off.
     /// `Vec<(policy name, Vec of decisions by the policy)>`
@@ -307,6 +349,8 @@ impl Message {
                 Command::CancelRequest { .. } => "command-cancel_request",
                 Command::PrivilegedCancelRequest { .. } => "command-privileged_cancel_request",
                 Command::GetWebhook { .. } => "command-get_webhook",
+                Command::MapClientCertificate { .. } => "command-map_client_certificate",
+                Command::GetScramVerifier { .. } => "command-get_scram_verifier",
                 Command::GetSystemVars { .. } => "command-get_system_vars",
                 Command::SetSystemVars { .. } => "command-set_system_vars",
                 Command::Terminate { .. } => "command-terminate",
@@ -325,10 +369,15 @@ impl Message {
             Message::DropReadHolds(_) => "drop_read_holds",
             Message::ClusterEvent(_) => "cluster_event",
             Message::CancelPendingPeeks { .. } => "cancel_pending_peeks",
+            Message::PeekPage { .. } => "peek_page",
             Message::LinearizeReads => "linearize_reads",
             Message::StorageUsageSchedule => "storage_usage_schedule",
             Message::StorageUsageFetch => "storage_usage_fetch",
             Message::StorageUsageUpdate(_) => "storage_usage_update",
+            Message::CollectionStatisticsFetch => "collection_statistics_fetch",
+            Message::CollectionStatisticsUpdate(_) => "collection_statistics_update",
+            Message::StorageShardUsageFetch => "storage_shard_usage_fetch",
+            Message::StorageShardUsageUpdate(_) => "storage_shard_usage_update",
             Message::RetireExecute { .. } => "retire_execute",
             Message::ExecuteSingleStatementTransaction { .. } => {
                 "execute_single_statement_transaction"
@@ -342,10 +391,14 @@ impl Message {
             }
             Message::SubscribeStageReady { .. } => "subscribe_stage_ready",
             Message::SecretStageReady { .. } => "secret_stage_ready",
+            Message::AlterSetClusterStageReady { .. } => "alter_set_cluster_stage_ready",
+            Message::AlterClusterStageReady { .. } => "alter_cluster_stage_ready",
             Message::DrainStatementLog => "drain_statement_log",
             Message::AlterConnectionValidationReady(..) => "alter_connection_validation_ready",
             Message::PrivateLinkVpcEndpointEvents(_) => "private_link_vpc_endpoint_events",
             Message::CheckSchedulingPolicies => "check_scheduling_policies",
+            Message::RenewComputeLeases => "renew_compute_leases",
+            Message::ReplaceCrashLoopingReplica { .. } => "replace_crash_looping_replica",
             Message::SchedulingDecisions { .. } => "scheduling_decision",
         }
     }
@@ -409,6 +462,8 @@ pub struct CopyToContext {
     pub format: S3SinkFormat,
     /// Approximate max file size of each uploaded file.
     pub max_file_size: u64,
+    /// Whether to write a manifest listing the uploaded files.
+    pub manifest: bool,
     /// Number of batches the output of the COPY TO will be partitioned into
     /// to distribute the load across workers deterministically.
     /// This is only an option since it's not set when CopyToContext is instantiated
@@ -427,6 +482,9 @@ pub struct PeekStageLinearizeTimestamp {
     /// An optional context set iff the state machine is initiated from
     /// sequencing an EXPLAIN for this statement.
     explain_ctx: ExplainContext,
+    /// The key of the statement in the peek plan cache, if it is eligible
+    /// for caching.
+    plan_cache_key: Option<PeekPlanCacheKey>,
 }
 
 #[derive(Debug)]
@@ -441,6 +499,9 @@ pub struct PeekStageRealTimeRecency {
     /// An optional context set iff the state machine is initiated from
     /// sequencing an EXPLAIN for this statement.
     explain_ctx: ExplainContext,
+    /// The key of the statement in the peek plan cache, if it is eligible
+    /// for caching.
+    plan_cache_key: Option<PeekPlanCacheKey>,
 }
 
 #[derive(Debug)]
@@ -456,6 +517,9 @@ pub struct PeekStageTimestampReadHold {
     /// An optional context set iff the state machine is initiated from
     /// sequencing an EXPLAIN for this statement.
     explain_ctx: ExplainContext,
+    /// The key of the statement in the peek plan cache, if it is eligible
+    /// for caching.
+    plan_cache_key: Option<PeekPlanCacheKey>,
 }
 
 #[derive(Debug)]
@@ -470,6 +534,9 @@ pub struct PeekStageOptimize {
     /// An optional context set iff the state machine is initiated from
     /// sequencing an EXPLAIN for this statement.
     explain_ctx: ExplainContext,
+    /// The key of the statement in the peek plan cache, if it is eligible
+    /// for caching.
+    plan_cache_key: Option<PeekPlanCacheKey>,
 }
 
 #[derive(Debug)]
@@ -782,6 +849,59 @@ pub struct AlterSecret {
     plan: plan::AlterSecretPlan,
 }
 
+#[derive(Debug)]
+pub enum AlterSetClusterStage {
+    Optimize(AlterSetClusterOptimize),
+    Ship(AlterSetClusterShip),
+    WaitForHydration(AlterSetClusterWaitForHydration),
+}
+
+#[derive(Debug)]
+pub struct AlterSetClusterOptimize {
+    validity: PlanValidity,
+    plan: plan::AlterSetClusterPlan,
+    /// The cluster the materialized view is being moved away from.
+    old_cluster: ComputeInstanceId,
+}
+
+#[derive(Debug)]
+pub struct AlterSetClusterShip {
+    validity: PlanValidity,
+    plan: plan::AlterSetClusterPlan,
+    old_cluster: ComputeInstanceId,
+    global_mir_plan: optimize::materialized_view::GlobalMirPlan,
+    global_lir_plan: optimize::materialized_view::GlobalLirPlan,
+}
+
+#[derive(Debug)]
+pub struct AlterSetClusterWaitForHydration {
+    validity: PlanValidity,
+    plan: plan::AlterSetClusterPlan,
+    old_cluster: ComputeInstanceId,
+    optimized_plan: DataflowDescription<OptimizedMirRelationExpr>,
+    physical_plan: DataflowDescription<Plan>,
+    df_meta: DataflowMetainfo,
+}
+
+#[derive(Debug)]
+pub enum AlterClusterStage {
+    WaitForHydrated(AlterClusterWaitForHydrated),
+}
+
+#[derive(Debug)]
+pub struct AlterClusterWaitForHydrated {
+    validity: PlanValidity,
+    cluster_id: ClusterId,
+    /// The configuration of the cluster when the new replicas were created.
+    config: ClusterVariantManaged,
+    /// The configuration to install once the new replicas have caught up.
+    new_config: ClusterVariantManaged,
+    /// The new replicas, in the order of the managed replica names they take over.
+    pending_replicas: Vec<ReplicaId>,
+    /// The point in time at which to give up waiting for the new replicas.
+    deadline: Instant,
+}
+
 /// An enum describing which cluster to run a statement on.
 ///
 /// One example usage would be that if a query depends only on system tables, we might
@@ -1224,6 +1344,9 @@ pub struct ExecuteContext {
     internal_cmd_tx: mpsc::UnboundedSender<Message>,
     session: Session,
     extra: ExecuteContextExtra,
+    /// Whether this execution produces the rows of a cursor, which are
+    /// consumed incrementally by `FETCH`.
+    is_cursor: bool,
 }
 
 impl ExecuteContext {
@@ -1254,9 +1377,20 @@ impl ExecuteContext {
             session,
             extra,
             internal_cmd_tx,
+            is_cursor: false,
         }
     }
 
+    /// Marks this execution as producing the rows of a cursor.
+    pub(crate) fn set_cursor(&mut self) {
+        self.is_cursor = true;
+    }
+
+    /// Reports whether this execution produces the rows of a cursor.
+    pub(crate) fn is_cursor(&self) -> bool {
+        self.is_cursor
+    }
+
     /// By calling this function, the caller takes responsibility for
     /// dealing with the instance of `ExecuteContextExtra`. This is
     /// intended to support protocols (like `COPY FROM`) that involve
@@ -1278,6 +1412,7 @@ impl ExecuteContext {
             internal_cmd_tx,
             session,
             extra,
+            is_cursor: _,
         } = self;
         (tx, internal_cmd_tx, session, extra)
     }
@@ -1290,6 +1425,7 @@ impl ExecuteContext {
             internal_cmd_tx,
             session,
             extra,
+            is_cursor: _,
         } = self;
         let reason = if extra.is_trivial() {
             None
@@ -1562,12 +1698,21 @@ pub struct Coordinator {
     pending_peeks: BTreeMap<Uuid, PendingPeek>,
     /// A map from client connection ids to a set of all pending peeks for that client.
     client_pending_peeks: BTreeMap<ConnectionId, BTreeMap<Uuid, ClusterId>>,
+    /// The results of recent fast-path peeks, for reuse by identical peeks at the same
+    /// timestamp.
+    peek_result_cache: PeekResultCache,
+    /// Statistics about the contents of persisted collections, for use by the optimizer.
+    collection_statistics: BTreeMap<GlobalId, CollectionStatistics>,
+    /// The most recently reported usage of storage shards, and when it was collected.
+    storage_shard_usage: BTreeMap<ShardId, (ShardUsageReport, EpochMillis)>,
 
     /// A map from client connection ids to pending linearize read transaction.
     pending_linearize_read_txns: BTreeMap<ConnectionId, PendingReadTxn>,
 
     /// A map from the compute sink ID to it's state description.
     active_compute_sinks: BTreeMap<GlobalId, ActiveComputeSink>,
+    /// Subscribe dataflows shared by several subscribes.
+    shared_subscribes: SharedSubscribes,
     /// A map from active webhooks to their invalidation handle.
     active_webhooks: BTreeMap<GlobalId, WebhookAppenderInvalidator>,
     /// A map from connection ids to a watch channel that is set to `true` if the connection
@@ -1580,6 +1725,9 @@ pub struct Coordinator {
     write_lock_wait_group: VecDeque<Deferred>,
     /// Pending writes waiting for a group commit.
     pending_writes: Vec<PendingWriteTxn>,
+    /// The timestamp of the latest group commit that wrote to each user table,
+    /// used to validate the reads of read-write transactions.
+    table_write_timestamps: BTreeMap<GlobalId, Timestamp>,
     /// For the realtime timeline, an explicit SELECT or INSERT on a table will bump the
     /// table's timestamps, but there are cases where timestamps are not bumped but
     /// we expect the closed timestamps to advance (`AS OF X`, SUBSCRIBing views over
@@ -1621,6 +1769,9 @@ pub struct Coordinator {
     metrics: Metrics,
     /// Optimizer metrics.
     optimizer_metrics: OptimizerMetrics,
+    /// Locally optimized plans of recently executed `SELECT` statements.
+    #[derivative(Debug = "ignore")]
+    peek_plan_cache: Arc<Mutex<PeekPlanCache>>,
 
     /// Tracing handle.
     tracing_handle: TracingHandle,
@@ -1630,6 +1781,8 @@ pub struct Coordinator {
 
     /// Limit for how many concurrent webhook requests we allow.
     webhook_concurrency_limit: WebhookConcurrencyLimiter,
+    /// Limit for how many rows a single webhook request can append.
+    webhook_row_limit: WebhookRowLimit,
 
     /// Optional config for the Postgres-backed timestamp oracle. This is
     /// _required_ when `postgres` is configured using the `timestamp_oracle`
@@ -1639,11 +1792,20 @@ pub struct Coordinator {
     /// Periodically asks cluster scheduling policies to make their decisions.
     check_cluster_scheduling_policies_interval: tokio::time::Interval,
 
+    /// Periodically renews the compute leases of transient work owned by active connections.
+    renew_compute_leases_interval: tokio::time::Interval,
+
     /// This keeps the last On/Off decision for each cluster and each scheduling policy.
     /// (Clusters that have been dropped or are otherwise out of scope for automatic scheduling are
     /// periodically cleaned up from this Map.)
     cluster_scheduling_decisions: BTreeMap<ClusterId, BTreeMap<&'static str, SchedulingDecision>>,
 
+    /// The time at which the autoscaler last resized each autoscaled cluster, or first saw it.
+    cluster_autoscaling_last_resizes: BTreeMap<ClusterId, EpochMillis>,
+
+    /// The resizes by the autoscaler that wait for the new replicas to catch up.
+    cluster_autoscaling_pending_resizes: BTreeMap<ClusterId, PendingResize>,
+
     /// Tracks the state associated with the currently installed watchsets.
     installed_watch_sets: BTreeMap<WatchSetId, (ConnectionId, WatchSetResponse)>,
 
@@ -1653,6 +1815,12 @@ pub struct Coordinator {
     /// Tracks the statuses of all cluster replicas.
     cluster_replica_statuses: ClusterReplicaStatuses,
 
+    /// Tracks the health of cluster replicas.
+    replica_health: BTreeMap<(ClusterId, ReplicaId), ReplicaHealthTracker>,
+
+    /// Tracks the replacements of crash looping replicas, to back off from replacing them.
+    crash_loop_replacements: CrashLoopReplacements,
+
     /// Whether or not to start controllers in read-only mode. This is only
     /// meant for use during development of read-only clusters and 0dt upgrades
     /// and should go away once we have proper orchestration during upgrades.
@@ -2354,6 +2522,7 @@ impl Coordinator {
                         internal_view_id,
                         mv.desc.iter_names().cloned().collect(),
                         mv.non_null_assertions.clone(),
+                        mv.append_only,
                         mv.refresh_schedule.clone(),
                         debug_name,
                         optimizer_config.clone(),
@@ -2728,6 +2897,8 @@ impl Coordinator {
             });
 
             self.schedule_storage_usage_collection().await;
+            self.schedule_collection_statistics_refresh();
+            self.schedule_storage_shard_usage_report();
             self.spawn_privatelink_vpc_endpoints_watch_task();
             self.spawn_statement_logging_task();
             flags::tracing_config(self.catalog.system_config()).apply(&self.tracing_handle);
@@ -2827,6 +2998,11 @@ impl Coordinator {
                     _ = self.check_cluster_scheduling_policies_interval.tick() => {
                         Message::CheckSchedulingPolicies
                     },
+                    // `tick()` on `Interval` is cancel-safe:
+                    // https://docs.rs/tokio/1.19.2/tokio/time/struct.Interval.html#cancel-safety
+                    _ = self.renew_compute_leases_interval.tick() => {
+                        Message::RenewComputeLeases
+                    },
 
                     // Process the idle metric at the lowest priority to sample queue non-idle time.
                     // `recv()` on `Receiver` is cancellation safe:
@@ -3349,6 +3525,9 @@ pub fn serve(
                 .cluster_check_scheduling_policies_interval(),
         );
         check_scheduling_policies_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut renew_compute_leases_interval =
+            tokio::time::interval(COMPUTE_LEASE_RENEWAL_INTERVAL);
+        renew_compute_leases_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
         if let Some(config) = pg_timestamp_oracle_config.as_ref() {
             // Apply settings from system vars as early as possible because some
@@ -3382,6 +3561,8 @@ pub fn serve(
                     })
                     .expect("failed to initialize storage_controller");
 
+                let webhook_row_limit =
+                    WebhookRowLimit::new(catalog.system_config().webhook_request_row_limit());
                 let catalog = Arc::new(catalog);
 
                 let caching_secrets_reader = CachingSecretsReader::new(secrets_controller.reader());
@@ -3400,13 +3581,18 @@ pub fn serve(
                     txn_read_holds: Default::default(),
                     pending_peeks: BTreeMap::new(),
                     client_pending_peeks: BTreeMap::new(),
+                    peek_result_cache: PeekResultCache::default(),
+                    collection_statistics: BTreeMap::new(),
+                    storage_shard_usage: BTreeMap::new(),
                     pending_linearize_read_txns: BTreeMap::new(),
                     active_compute_sinks: BTreeMap::new(),
+                    shared_subscribes: SharedSubscribes::default(),
                     active_webhooks: BTreeMap::new(),
                     staged_cancellation: BTreeMap::new(),
                     write_lock: Arc::new(tokio::sync::Mutex::new(())),
                     write_lock_wait_group: VecDeque::new(),
                     pending_writes: Vec::new(),
+                    table_write_timestamps: BTreeMap::new(),
                     advance_timelines_interval,
                     secrets_controller,
                     caching_secrets_reader,
@@ -3417,15 +3603,22 @@ pub fn serve(
                     segment_client,
                     metrics,
                     optimizer_metrics,
+                    peek_plan_cache: Default::default(),
                     tracing_handle,
                     statement_logging: StatementLogging::new(coord_now.clone()),
                     webhook_concurrency_limit,
+                    webhook_row_limit,
                     pg_timestamp_oracle_config,
                     check_cluster_scheduling_policies_interval: check_scheduling_policies_interval,
+                    renew_compute_leases_interval,
                     cluster_scheduling_decisions: BTreeMap::new(),
+                    cluster_autoscaling_last_resizes: BTreeMap::new(),
+                    cluster_autoscaling_pending_resizes: BTreeMap::new(),
                     installed_watch_sets: BTreeMap::new(),
                     connection_watch_sets: BTreeMap::new(),
                     cluster_replica_statuses: ClusterReplicaStatuses::new(),
+                    replica_health: BTreeMap::new(),
+                    crash_loop_replacements: CrashLoopReplacements::default(),
                     read_only_controllers,
                 };
                 let bootstrap = handle.block_on(async {
@@ -9,7 +9,7 @@
 
 //! Logic and types for all appends executed by the [`Coordinator`].
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -30,9 +30,9 @@ use tracing::{debug_span, warn, Instrument, Span};
 
 use crate::catalog::BuiltinTableUpdate;
 use crate::coord::{Coordinator, Message, PendingTxn, PlanValidity};
-use crate::session::{Session, WriteOp};
+use crate::session::{EndTransactionAction, Session, WriteOp};
 use crate::util::{CompletedClientTransmitter, ResultExt};
-use crate::ExecuteContext;
+use crate::{AdapterError, ExecuteContext};
 
 /// An operation that is deferred while waiting for a lock.
 #[derive(Debug)]
@@ -71,6 +71,9 @@ pub(crate) enum PendingWriteTxn {
         writes: Vec<WriteOp>,
         /// Holds the coordinator's write lock.
         write_lock_guard: Option<OwnedMutexGuard<()>>,
+        /// The reads of a read-write transaction, validated before its
+        /// writes are applied.
+        reads: Option<TxnReads>,
         /// Inner transaction.
         pending_txn: PendingTxn,
     },
@@ -81,6 +84,13 @@ pub(crate) enum PendingWriteTxn {
     },
 }
 
+/// The tables a read-write transaction read, and the timestamp it read them at.
+#[derive(Debug)]
+pub(crate) struct TxnReads {
+    pub timestamp: Timestamp,
+    pub table_ids: BTreeSet<GlobalId>,
+}
+
 impl PendingWriteTxn {
     fn take_write_lock(&mut self) -> Option<OwnedMutexGuard<()>> {
         match self {
@@ -290,6 +300,8 @@ impl Coordinator {
         let mut appends: BTreeMap<GlobalId, Vec<(Row, Diff)>> = BTreeMap::new();
         let mut responses = Vec::with_capacity(self.pending_writes.len());
         let mut notifies = Vec::new();
+        // User tables written by the transactions of this group commit.
+        let mut written_tables = BTreeSet::new();
 
         for pending_write_txn in pending_writes {
             match pending_write_txn {
@@ -297,6 +309,7 @@ impl Coordinator {
                     span: _,
                     writes,
                     write_lock_guard: _,
+                    reads,
                     pending_txn:
                         PendingTxn {
                             ctx,
@@ -304,6 +317,26 @@ impl Coordinator {
                             action,
                         },
                 } => {
+                    // A read-write transaction must not have missed a write to
+                    // a table it read, either from an earlier group commit or
+                    // from an earlier transaction in this one.
+                    let conflict = reads.is_some_and(|reads| {
+                        reads.table_ids.iter().any(|id| {
+                            written_tables.contains(id)
+                                || self
+                                    .table_write_timestamps
+                                    .get(id)
+                                    .is_some_and(|ts| *ts > reads.timestamp)
+                        })
+                    });
+                    if conflict {
+                        responses.push(CompletedClientTransmitter::new(
+                            ctx,
+                            Err(AdapterError::ReadWriteTransactionConflict),
+                            EndTransactionAction::Rollback,
+                        ));
+                        continue;
+                    }
                     for WriteOp { id, rows } in writes {
                         // If the table that some write was targeting has been deleted while the
                         // write was waiting, then the write will be ignored and we respond to the
@@ -312,6 +345,7 @@ impl Coordinator {
                         // write before the delete without violating any consistency guarantees.
                         if self.catalog().try_get_entry(&id).is_some() {
                             appends.entry(id).or_default().extend(rows);
+                            written_tables.insert(id);
                         }
                     }
                     if let Some(id) = ctx.extra().contents() {
@@ -335,6 +369,9 @@ impl Coordinator {
             }
         }
 
+        for id in written_tables {
+            self.table_write_timestamps.insert(id, timestamp);
+        }
         for (_, updates) in &mut appends {
             differential_dataflow::consolidation::consolidate(updates);
         }
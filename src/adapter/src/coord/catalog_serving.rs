@@ -18,6 +18,7 @@
 //!
 //! [`mz_catalog_server`]: https://materialize.com/docs/sql/show-clusters/#mz_catalog_server-system-cluster
 
+use mz_controller_types::ClusterId;
 use mz_expr::CollectionPlan;
 use mz_repr::GlobalId;
 use mz_sql::catalog::SessionCatalog;
@@ -94,6 +95,7 @@ pub fn auto_run_on_catalog_server<'a, 's, 'p>(
         | Plan::InspectShard(_)
         | Plan::SetVariable(_)
         | Plan::ResetVariable(_)
+        | Plan::ResetAllVariables
         | Plan::SetTransaction(_)
         | Plan::StartTransaction(_)
         | Plan::CommitTransaction(_)
@@ -123,6 +125,7 @@ pub fn auto_run_on_catalog_server<'a, 's, 'p>(
         | Plan::AlterSystemReset(_)
         | Plan::AlterSystemResetAll(_)
         | Plan::AlterRole(_)
+        | Plan::AlterDatabase(_)
         | Plan::AlterOwner(_)
         | Plan::Declare(_)
         | Plan::Fetch(_)
@@ -239,3 +242,42 @@ pub fn check_cluster_restrictions(
         Ok(())
     }
 }
+
+/// Checks whether the current role is allowed to run `plan` on `cluster`, according to the role's
+/// `allowed_clusters` parameter.
+///
+/// System clusters are always allowed, so that queries which are automatically routed to the
+/// [`MZ_CATALOG_SERVER_CLUSTER`] keep working.
+pub fn check_role_cluster_restrictions(
+    cluster_id: ClusterId,
+    cluster_name: &str,
+    catalog: &impl SessionCatalog,
+    session: &Session,
+    plan: &Plan,
+) -> Result<(), AdapterError> {
+    let allowed_clusters = session.vars().allowed_clusters();
+    if allowed_clusters.is_empty() || cluster_id.is_system() {
+        return Ok(());
+    }
+
+    // Only plans that run some computation on the cluster are restricted.
+    if !matches!(
+        plan,
+        Plan::Select(_) | Plan::Subscribe(_) | Plan::ReadThenWrite(_)
+    ) {
+        return Ok(());
+    }
+
+    if allowed_clusters
+        .iter()
+        .any(|allowed| allowed.as_str() == cluster_name)
+    {
+        Ok(())
+    } else {
+        let role = catalog.get_role(catalog.active_role_id());
+        Err(AdapterError::UnallowedClusterForRole {
+            role: role.name().to_string(),
+            cluster: cluster_name.to_string(),
+        })
+    }
+}
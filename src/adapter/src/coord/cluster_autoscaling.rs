@@ -0,0 +1,588 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Automatic resizing of managed clusters that have `AUTOSCALE MIN SIZE` and `AUTOSCALE MAX SIZE`
+//! set.
+//!
+//! The autoscaler runs next to the cluster scheduling policies. For each autoscaled cluster it
+//! looks at the memory utilization of the cluster's replica processes and at how far the write
+//! frontiers of the cluster's dataflows lag behind the current time:
+//! - If any process uses more than `cluster_autoscaling_scale_up_memory_percent` of its memory
+//!   limit, or any dataflow lags by more than `cluster_autoscaling_max_frontier_lag`, the cluster
+//!   is grown to the next larger size.
+//! - If all processes use less than `cluster_autoscaling_scale_down_memory_percent` of their
+//!   memory limit and no dataflow is lagging, the cluster is shrunk to the next smaller size.
+//!
+//! Frontier lag is only taken into account once all replicas of the cluster have hydrated, as
+//! hydrating replicas lag by design. Until then, the autoscaler only grows clusters under memory
+//! pressure, and never shrinks them.
+//!
+//! Sizes are ordered by their total memory limit, and then by their total number of workers, and
+//! only sizes between the cluster's bounds are considered. Resizes are graceful, like
+//! `ALTER CLUSTER .. WITH (GRACEFUL)`: the replicas of the new size are created next to the
+//! existing ones, and only take over once they have hydrated and caught up. If they don't within
+//! `cluster_graceful_reconfiguration_timeout`, or if the cluster is altered in the meantime, the
+//! resize is abandoned and the new replicas are dropped. A cluster is resized at most once per
+//! `cluster_autoscaling_cooldown`, counted from the end of the previous resize. Every resize is
+//! recorded in the audit log, along with its trigger.
+
+use std::time::{Duration, Instant};
+
+use itertools::Itertools;
+use mz_catalog::config::ClusterReplicaSizeMap;
+use mz_catalog::memory::objects::{ClusterVariant, ClusterVariantManaged};
+use mz_controller::clusters::ReplicaAllocation;
+use mz_controller_types::{ClusterId, ReplicaId};
+use mz_ore::now::EpochMillis;
+use mz_repr::role_id::RoleId;
+use mz_sql::catalog::CatalogCluster;
+use mz_sql::plan::ClusterAutoscale;
+use tracing::{debug, info, warn};
+
+use crate::catalog::ReplicaCreateDropReason;
+use crate::coord::Coordinator;
+use crate::error::AdapterError;
+
+/// What made the autoscaler resize a cluster.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutoscalingTrigger {
+    /// A replica process used more of its memory limit than allowed by
+    /// `cluster_autoscaling_scale_up_memory_percent`.
+    MemoryPressure,
+    /// The write frontier of a dataflow lagged behind the current time by more than
+    /// `cluster_autoscaling_max_frontier_lag`.
+    FrontierLag,
+    /// All replica processes used less of their memory limit than
+    /// `cluster_autoscaling_scale_down_memory_percent`, and no dataflow was lagging.
+    LowMemory,
+}
+
+impl AutoscalingTrigger {
+    /// Returns the trigger as a kebab-case string.
+    pub fn as_kebab_case_str(&self) -> &'static str {
+        match self {
+            AutoscalingTrigger::MemoryPressure => "memory-pressure",
+            AutoscalingTrigger::FrontierLag => "frontier-lag",
+            AutoscalingTrigger::LowMemory => "low-memory",
+        }
+    }
+
+    /// Whether the trigger grows the cluster, as opposed to shrinking it.
+    fn scales_up(&self) -> bool {
+        match self {
+            AutoscalingTrigger::MemoryPressure | AutoscalingTrigger::FrontierLag => true,
+            AutoscalingTrigger::LowMemory => false,
+        }
+    }
+}
+
+/// A resize of a cluster by the autoscaler, along with its trigger.
+#[derive(Clone, Debug)]
+pub struct AutoscalingDecision {
+    pub old_size: String,
+    pub new_size: String,
+    pub trigger: AutoscalingTrigger,
+}
+
+/// A resize of a cluster by the autoscaler that waits for the new replicas to catch up.
+#[derive(Debug)]
+pub(crate) struct PendingResize {
+    /// The configuration of the cluster when the new replicas were created.
+    config: ClusterVariantManaged,
+    /// The configuration to install once the new replicas have caught up.
+    new_config: ClusterVariantManaged,
+    decision: AutoscalingDecision,
+    /// The new replicas, in the order of the managed replica names they take over.
+    pending_replicas: Vec<ReplicaId>,
+    /// The point in time at which to give up waiting for the new replicas.
+    deadline: Instant,
+}
+
+/// The load of a cluster, as observed by the autoscaler.
+#[derive(Clone, Debug)]
+struct AutoscalingObservation {
+    /// The highest memory utilization of any replica process, in percent of its memory limit, or
+    /// `None` if it is not known for all processes.
+    max_memory_percent: Option<u64>,
+    /// The largest lag of any dataflow write frontier behind the current time, or `None` if not
+    /// all replicas have hydrated yet.
+    max_frontier_lag: Option<Duration>,
+}
+
+/// The settings that determine when the autoscaler resizes a cluster.
+#[derive(Clone, Debug)]
+struct AutoscalingThresholds {
+    scale_up_memory_percent: u64,
+    scale_down_memory_percent: u64,
+    max_frontier_lag: Duration,
+}
+
+/// Decides whether a cluster with the given load should be resized, and why.
+fn autoscaling_trigger(
+    observation: &AutoscalingObservation,
+    thresholds: &AutoscalingThresholds,
+) -> Option<AutoscalingTrigger> {
+    let lagging = observation
+        .max_frontier_lag
+        .map(|lag| lag > thresholds.max_frontier_lag);
+    match observation.max_memory_percent {
+        Some(percent) if percent >= thresholds.scale_up_memory_percent => {
+            Some(AutoscalingTrigger::MemoryPressure)
+        }
+        _ if lagging == Some(true) => Some(AutoscalingTrigger::FrontierLag),
+        Some(percent)
+            if percent < thresholds.scale_down_memory_percent && lagging == Some(false) =>
+        {
+            Some(AutoscalingTrigger::LowMemory)
+        }
+        _ => None,
+    }
+}
+
+/// The key by which the autoscaler orders replica sizes: the total memory limit, then the total
+/// number of workers, then the name to break ties.
+fn size_order_key<'a>(name: &'a str, allocation: &ReplicaAllocation) -> (u64, usize, &'a str) {
+    let scale = u64::from(allocation.scale);
+    let memory = allocation
+        .memory_limit
+        .map_or(0, |limit| limit.0 .0.saturating_mul(scale));
+    let workers = allocation
+        .workers
+        .saturating_mul(usize::from(allocation.scale));
+    (memory, workers, name)
+}
+
+/// Returns the next larger (if `up`) or smaller enabled size after `current` that lies within
+/// `bounds`, or `None` if the cluster is already at the end of its bounds.
+fn step_size(
+    sizes: &ClusterReplicaSizeMap,
+    bounds: &ClusterAutoscale,
+    current: &str,
+    up: bool,
+) -> Option<String> {
+    let key = |name: &str| {
+        let (name, allocation) = sizes.0.get_key_value(name)?;
+        Some(size_order_key(name, allocation))
+    };
+    let (min, max, current) = (
+        key(&bounds.min_size)?,
+        key(&bounds.max_size)?,
+        key(current)?,
+    );
+    let candidates = sizes
+        .enabled_allocations()
+        .map(|(name, allocation)| size_order_key(name, allocation))
+        .filter(|key| min <= *key && *key <= max);
+    let next = if up {
+        candidates.filter(|key| *key > current).min()
+    } else {
+        candidates.filter(|key| *key < current).max()
+    };
+    next.map(|(_, _, name)| name.to_string())
+}
+
+impl Coordinator {
+    /// Ensures that the `AUTOSCALE` bounds of a managed cluster name valid sizes, and that `size`
+    /// lies within them.
+    pub(crate) fn ensure_valid_cluster_autoscale(
+        &self,
+        size: &String,
+        autoscale: Option<&ClusterAutoscale>,
+        role_id: Option<RoleId>,
+    ) -> Result<(), AdapterError> {
+        let Some(ClusterAutoscale { min_size, max_size }) = autoscale else {
+            return Ok(());
+        };
+        let allowed_sizes = self.catalog().get_role_allowed_cluster_sizes(&role_id);
+        self.catalog
+            .ensure_valid_replica_size(&allowed_sizes, min_size)?;
+        self.catalog
+            .ensure_valid_replica_size(&allowed_sizes, max_size)?;
+
+        let sizes = &self.catalog().cluster_replica_sizes().0;
+        let key = |name: &String| size_order_key(name, &sizes[name]);
+        if key(min_size) > key(max_size) {
+            coord_bail!(
+                "AUTOSCALE MIN SIZE '{min_size}' is larger than AUTOSCALE MAX SIZE '{max_size}'"
+            );
+        }
+        if let Some(allocation) = sizes.get(size) {
+            let size_key = size_order_key(size, allocation);
+            if size_key < key(min_size) || size_key > key(max_size) {
+                coord_bail!(
+                    "SIZE '{size}' is not between AUTOSCALE MIN SIZE '{min_size}' and \
+                    AUTOSCALE MAX SIZE '{max_size}'"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the cluster autoscaler, which resizes autoscaled managed clusters based on the memory
+    /// utilization of their replicas and the frontier lag of their dataflows.
+    #[mz_ore::instrument(level = "debug")]
+    pub(crate) async fn check_autoscaling_policy(&mut self) {
+        let start_time = Instant::now();
+
+        let system_config = self.catalog().system_config();
+        let thresholds = AutoscalingThresholds {
+            scale_up_memory_percent: system_config
+                .cluster_autoscaling_scale_up_memory_percent()
+                .into(),
+            scale_down_memory_percent: system_config
+                .cluster_autoscaling_scale_down_memory_percent()
+                .into(),
+            max_frontier_lag: system_config.cluster_autoscaling_max_frontier_lag(),
+        };
+        let cooldown = system_config.cluster_autoscaling_cooldown();
+        let now = self.now();
+
+        let autoscaled_clusters = self
+            .catalog()
+            .clusters()
+            .filter_map(|cluster| match &cluster.config.variant {
+                ClusterVariant::Managed(config) if config.autoscale.is_some() => {
+                    Some((cluster.id, config.clone()))
+                }
+                _ => None,
+            })
+            .collect_vec();
+
+        // Forget about clusters that are no longer autoscaled.
+        self.cluster_autoscaling_last_resizes
+            .retain(|cluster_id, _| {
+                autoscaled_clusters
+                    .iter()
+                    .any(|(autoscaled_id, _)| autoscaled_id == cluster_id)
+            });
+
+        // Complete or abandon the resizes that wait for their new replicas.
+        let pending_resizes = std::mem::take(&mut self.cluster_autoscaling_pending_resizes);
+        for (cluster_id, resize) in pending_resizes {
+            if let Some(resize) = self.advance_pending_resize(cluster_id, resize, now).await {
+                self.cluster_autoscaling_pending_resizes
+                    .insert(cluster_id, resize);
+            }
+        }
+
+        for (cluster_id, config) in autoscaled_clusters {
+            // Clusters that are turned off have nothing to observe.
+            if config.replication_factor == 0 {
+                continue;
+            }
+            if self
+                .cluster_autoscaling_pending_resizes
+                .contains_key(&cluster_id)
+            {
+                continue;
+            }
+            // We only resize clusters that we have observed for at least a cooldown, so that
+            // clusters aren't resized right after they are created or after envd restarts.
+            let last_resize = *self
+                .cluster_autoscaling_last_resizes
+                .entry(cluster_id)
+                .or_insert(now);
+            if Duration::from_millis(now.saturating_sub(last_resize)) < cooldown {
+                continue;
+            }
+
+            let observation = self.observe_cluster_load(cluster_id, &config, now);
+            debug!(%cluster_id, ?observation, "check_autoscaling_policy");
+            let Some(trigger) = autoscaling_trigger(&observation, &thresholds) else {
+                continue;
+            };
+            let bounds = config.autoscale.as_ref().expect("filtered above");
+            let Some(new_size) = step_size(
+                self.catalog().cluster_replica_sizes(),
+                bounds,
+                &config.size,
+                trigger.scales_up(),
+            ) else {
+                // Already at the end of the cluster's bounds.
+                continue;
+            };
+
+            let decision = AutoscalingDecision {
+                old_size: config.size.clone(),
+                new_size: new_size.clone(),
+                trigger,
+            };
+            let mut new_config = config.clone();
+            new_config.size = new_size;
+            info!(%cluster_id, ?decision, "autoscaling cluster");
+            match self
+                .create_pending_replicas(
+                    None,
+                    cluster_id,
+                    &new_config,
+                    ReplicaCreateDropReason::Autoscaling(decision.clone()),
+                )
+                .await
+            {
+                Ok(pending_replicas) => {
+                    let timeout = self
+                        .catalog()
+                        .system_config()
+                        .cluster_graceful_reconfiguration_timeout();
+                    let resize = PendingResize {
+                        config,
+                        new_config,
+                        decision,
+                        pending_replicas,
+                        deadline: Instant::now() + timeout,
+                    };
+                    self.cluster_autoscaling_pending_resizes
+                        .insert(cluster_id, resize);
+                }
+                Err(e) => {
+                    warn!(%cluster_id, "failed to autoscale cluster: {e}");
+                    // Back off after failed attempts, to not retry them on every tick.
+                    self.cluster_autoscaling_last_resizes
+                        .insert(cluster_id, now);
+                }
+            }
+        }
+
+        self.metrics
+            .cluster_autoscaling_seconds
+            .observe((Instant::now() - start_time).as_secs_f64());
+    }
+
+    /// Lets the new replicas of a pending resize take over once they have caught up, and
+    /// abandons the resize if they can't. Returns the resize if it has to keep waiting.
+    async fn advance_pending_resize(
+        &mut self,
+        cluster_id: ClusterId,
+        resize: PendingResize,
+        now: EpochMillis,
+    ) -> Option<PendingResize> {
+        if self.catalog().try_get_cluster(cluster_id).is_none() {
+            // Dropping the cluster also dropped the new replicas.
+            return None;
+        }
+        let reason = ReplicaCreateDropReason::Autoscaling(resize.decision.clone());
+
+        let abandon = if let Some(conflict) =
+            self.pending_replicas_conflict(cluster_id, &resize.config, &resize.pending_replicas)
+        {
+            Some(conflict)
+        } else if self.replicas_caught_up(cluster_id, &resize.pending_replicas) {
+            let trigger = resize.decision.trigger;
+            match self
+                .finish_pending_replicas(
+                    None,
+                    cluster_id,
+                    &resize.config,
+                    resize.new_config,
+                    resize.pending_replicas.clone(),
+                    reason.clone(),
+                )
+                .await
+            {
+                Ok(()) => {
+                    self.metrics
+                        .cluster_autoscaling_resizes
+                        .with_label_values(&[trigger.as_kebab_case_str()])
+                        .inc();
+                    None
+                }
+                Err(e) => Some(e.to_string()),
+            }
+        } else if Instant::now() >= resize.deadline {
+            Some("timed out waiting for the new replicas to hydrate".into())
+        } else {
+            return Some(resize);
+        };
+
+        if let Some(abandon) = abandon {
+            warn!(%cluster_id, "abandoning autoscaling of cluster: {abandon}");
+            if let Err(e) = self
+                .drop_pending_replicas(None, cluster_id, &resize.pending_replicas, reason)
+                .await
+            {
+                warn!(%cluster_id, "failed to drop new replicas of cluster: {e}");
+            }
+        }
+        self.cluster_autoscaling_last_resizes
+            .insert(cluster_id, now);
+        None
+    }
+
+    /// Collects the memory utilization of the replicas of a cluster, and the frontier lag of its
+    /// dataflows.
+    fn observe_cluster_load(
+        &self,
+        cluster_id: ClusterId,
+        config: &ClusterVariantManaged,
+        now: EpochMillis,
+    ) -> AutoscalingObservation {
+        let cluster = self.catalog().get_cluster(cluster_id);
+
+        // All replicas of a managed cluster have the cluster's size.
+        let memory_limit = self
+            .catalog()
+            .cluster_replica_sizes()
+            .0
+            .get(&config.size)
+            .and_then(|allocation| allocation.memory_limit)
+            .map(|limit| limit.0 .0)
+            .filter(|limit| *limit > 0);
+        let max_memory_percent = memory_limit.and_then(|limit| {
+            let mut max_percent = 0;
+            for replica in cluster.user_replicas() {
+                let metrics = self
+                    .transient_replica_metadata
+                    .get(&replica.replica_id)
+                    .and_then(|metadata| metadata.as_ref())
+                    .and_then(|metadata| metadata.metrics.as_ref())?;
+                for process in metrics {
+                    let percent = process.memory_bytes?.saturating_mul(100) / limit;
+                    max_percent = std::cmp::max(max_percent, percent);
+                }
+            }
+            Some(max_percent)
+        });
+
+        // Replicas lag while they hydrate, which is no reason to grow the cluster.
+        let compute = &self.controller.compute;
+        let hydrated = cluster.replicas().all(|replica| {
+            compute
+                .replica_hydrated(cluster_id, replica.replica_id)
+                .ok()
+                .flatten()
+                .unwrap_or(false)
+        });
+        let max_frontier_lag = hydrated.then(|| {
+            cluster
+                .bound_objects()
+                .iter()
+                .filter_map(|id| compute.collection(cluster_id, *id).ok())
+                .filter_map(|collection| collection.write_frontier().as_option().copied())
+                .map(|frontier| Duration::from_millis(now.saturating_sub(u64::from(frontier))))
+                .max()
+                .unwrap_or_default()
+        });
+
+        AutoscalingObservation {
+            max_memory_percent,
+            max_frontier_lag,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use bytesize::ByteSize;
+    use mz_orchestrator::MemoryLimit;
+
+    use super::*;
+
+    fn allocation(memory_gib: u64, workers: usize, disabled: bool) -> ReplicaAllocation {
+        ReplicaAllocation {
+            memory_limit: Some(MemoryLimit(ByteSize::gib(memory_gib))),
+            cpu_limit: None,
+            disk_limit: None,
+            scale: 1,
+            workers,
+            credits_per_hour: 1.into(),
+            cpu_exclusive: false,
+            disabled,
+            selectors: BTreeMap::new(),
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_autoscaling_trigger() {
+        let thresholds = AutoscalingThresholds {
+            scale_up_memory_percent: 80,
+            scale_down_memory_percent: 30,
+            max_frontier_lag: Duration::from_secs(60),
+        };
+        let observe = |max_memory_percent, lag_secs| AutoscalingObservation {
+            max_memory_percent,
+            max_frontier_lag: Some(Duration::from_secs(lag_secs)),
+        };
+
+        assert_eq!(
+            autoscaling_trigger(&observe(Some(80), 0), &thresholds),
+            Some(AutoscalingTrigger::MemoryPressure)
+        );
+        assert_eq!(
+            autoscaling_trigger(&observe(Some(50), 61), &thresholds),
+            Some(AutoscalingTrigger::FrontierLag)
+        );
+        assert_eq!(
+            autoscaling_trigger(&observe(None, 61), &thresholds),
+            Some(AutoscalingTrigger::FrontierLag)
+        );
+        assert_eq!(
+            autoscaling_trigger(&observe(Some(10), 0), &thresholds),
+            Some(AutoscalingTrigger::LowMemory)
+        );
+        // A lagging cluster is never shrunk, even if it uses little memory.
+        assert_eq!(
+            autoscaling_trigger(&observe(Some(10), 61), &thresholds),
+            Some(AutoscalingTrigger::FrontierLag)
+        );
+        assert_eq!(
+            autoscaling_trigger(&observe(Some(50), 0), &thresholds),
+            None
+        );
+        // Without memory metrics we can't tell whether the cluster is too large.
+        assert_eq!(autoscaling_trigger(&observe(None, 0), &thresholds), None);
+
+        // Until all replicas have hydrated, lag is unknown, so clusters only grow under memory
+        // pressure, and never shrink.
+        let hydrating = |max_memory_percent| AutoscalingObservation {
+            max_memory_percent,
+            max_frontier_lag: None,
+        };
+        assert_eq!(
+            autoscaling_trigger(&hydrating(Some(80)), &thresholds),
+            Some(AutoscalingTrigger::MemoryPressure)
+        );
+        assert_eq!(autoscaling_trigger(&hydrating(Some(10)), &thresholds), None);
+        assert_eq!(autoscaling_trigger(&hydrating(None), &thresholds), None);
+    }
+
+    #[mz_ore::test]
+    fn test_step_size() {
+        let sizes = ClusterReplicaSizeMap(BTreeMap::from([
+            ("small".to_string(), allocation(4, 1, false)),
+            ("medium".to_string(), allocation(8, 2, false)),
+            ("medium-disabled".to_string(), allocation(12, 2, true)),
+            ("large".to_string(), allocation(16, 4, false)),
+            ("xlarge".to_string(), allocation(32, 8, false)),
+        ]));
+        let bounds = ClusterAutoscale {
+            min_size: "small".into(),
+            max_size: "large".into(),
+        };
+
+        assert_eq!(
+            step_size(&sizes, &bounds, "small", true).as_deref(),
+            Some("medium")
+        );
+        assert_eq!(
+            step_size(&sizes, &bounds, "medium", true).as_deref(),
+            Some("large")
+        );
+        assert_eq!(
+            step_size(&sizes, &bounds, "large", false).as_deref(),
+            Some("medium")
+        );
+        // Bounds are respected.
+        assert_eq!(step_size(&sizes, &bounds, "large", true), None);
+        assert_eq!(step_size(&sizes, &bounds, "small", false), None);
+        // Unknown sizes are never stepped from.
+        assert_eq!(step_size(&sizes, &bounds, "huge", false), None);
+    }
+}
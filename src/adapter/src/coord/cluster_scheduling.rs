@@ -12,7 +12,6 @@ use itertools::Itertools;
 use mz_audit_log::SchedulingDecisionsWithReasonsV1;
 use mz_catalog::memory::objects::{CatalogItem, ClusterVariant, ClusterVariantManaged};
 use mz_controller_types::ClusterId;
-use mz_ore::collections::CollectionExt;
 use mz_ore::soft_panic_or_log;
 use mz_repr::adt::interval::Interval;
 use mz_repr::GlobalId;
@@ -21,10 +20,20 @@ use mz_sql::plan::ClusterSchedule;
 use std::time::{Duration, Instant};
 use tracing::{debug, warn};
 
-const POLICIES: &[&str] = &[REFRESH_POLICY_NAME];
-
 const REFRESH_POLICY_NAME: &str = "refresh";
 
+const OFF_DURING_POLICY_NAME: &str = "off_during";
+
+/// Returns the policies that have to make a decision about a cluster with the given schedule
+/// before we act on that cluster.
+fn policies_for_schedule(schedule: &ClusterSchedule) -> &'static [&'static str] {
+    match schedule {
+        ClusterSchedule::Manual => &[],
+        ClusterSchedule::Refresh { .. } => &[REFRESH_POLICY_NAME],
+        ClusterSchedule::OffDuring { .. } => &[OFF_DURING_POLICY_NAME],
+    }
+}
+
 /// A policy's decision for whether it wants a certain cluster to be On, along with its reason.
 /// (Among the reasons there can be settings of the policy as well as other information about the
 /// state of the system.)
@@ -32,6 +41,8 @@ const REFRESH_POLICY_NAME: &str = "refresh";
 pub enum SchedulingDecision {
     /// The reason for the refresh policy for wanting to turn a cluster On or Off.
     Refresh(RefreshDecision),
+    /// The reason for the off during policy for wanting to turn a cluster On or Off.
+    OffDuring(OffDuringDecision),
 }
 
 impl SchedulingDecision {
@@ -39,6 +50,7 @@ impl SchedulingDecision {
     pub fn cluster_on(&self) -> bool {
         match &self {
             SchedulingDecision::Refresh(RefreshDecision { cluster_on, .. }) => cluster_on.clone(),
+            SchedulingDecision::OffDuring(OffDuringDecision { cluster_on }) => cluster_on.clone(),
         }
     }
 }
@@ -54,39 +66,50 @@ pub struct RefreshDecision {
     rehydration_time_estimate: Duration,
 }
 
+#[derive(Clone, Debug)]
+pub struct OffDuringDecision {
+    /// Whether the OFF DURING policy wants a certain cluster to be On, i.e., whether the current
+    /// time is outside all of the cluster's windows.
+    cluster_on: bool,
+}
+
 impl SchedulingDecision {
-    pub fn reasons_to_audit_log_reasons<'a, I>(reasons: I) -> SchedulingDecisionsWithReasonsV1
+    /// Returns the reasons to record in the audit log, or `None` if none of the given decisions
+    /// come from a policy that records its reasons there. (So far, only the refresh policy does.)
+    pub fn reasons_to_audit_log_reasons<'a, I>(
+        reasons: I,
+    ) -> Option<SchedulingDecisionsWithReasonsV1>
     where
         I: IntoIterator<Item = &'a SchedulingDecision>,
     {
-        SchedulingDecisionsWithReasonsV1 {
-            on_refresh: reasons
-                .into_iter()
-                .filter_map(|r| match r {
-                    SchedulingDecision::Refresh(RefreshDecision {
-                        cluster_on,
-                        objects_needing_refresh: mvs_needing_refresh,
-                        rehydration_time_estimate,
-                    }) => {
-                        let mut rehydration_time_estimate_str = String::new();
-                        mz_repr::strconv::format_interval(
-                            &mut rehydration_time_estimate_str,
-                            Interval::from_duration(rehydration_time_estimate).expect(
-                                "planning ensured that this is convertible back to Interval",
-                            ),
-                        );
-                        Some(mz_audit_log::RefreshDecisionWithReasonV1 {
-                            decision: (*cluster_on).into(),
-                            objects_needing_refresh: mvs_needing_refresh
-                                .iter()
-                                .map(|id| id.to_string())
-                                .collect(),
-                            rehydration_time_estimate: rehydration_time_estimate_str,
-                        })
-                    }
-                })
-                .into_element(), // Each policy should have exactly one opinion on each cluster.
-        }
+        // Each policy has at most one opinion on each cluster.
+        let on_refresh = reasons
+            .into_iter()
+            .filter_map(|r| match r {
+                SchedulingDecision::Refresh(RefreshDecision {
+                    cluster_on,
+                    objects_needing_refresh: mvs_needing_refresh,
+                    rehydration_time_estimate,
+                }) => {
+                    let mut rehydration_time_estimate_str = String::new();
+                    mz_repr::strconv::format_interval(
+                        &mut rehydration_time_estimate_str,
+                        Interval::from_duration(rehydration_time_estimate)
+                            .expect("planning ensured that this is convertible back to Interval"),
+                    );
+                    Some(mz_audit_log::RefreshDecisionWithReasonV1 {
+                        decision: (*cluster_on).into(),
+                        objects_needing_refresh: mvs_needing_refresh
+                            .iter()
+                            .map(|id| id.to_string())
+                            .collect(),
+                        rehydration_time_estimate: rehydration_time_estimate_str,
+                    })
+                }
+                SchedulingDecision::OffDuring(_) => None,
+            })
+            .next()?;
+        Some(SchedulingDecisionsWithReasonsV1 { on_refresh })
     }
 }
 
@@ -94,8 +117,9 @@ impl Coordinator {
     #[mz_ore::instrument(level = "debug")]
     /// Call each scheduling policy.
     pub(crate) async fn check_scheduling_policies(&mut self) {
-        // (So far, we have only this one policy.)
         self.check_refresh_policy();
+        self.check_off_during_policy();
+        self.check_autoscaling_policy().await;
     }
 
     /// Runs the `SCHEDULE = ON REFRESH` cluster scheduling policy, which makes cluster On/Off
@@ -110,8 +134,9 @@ impl Coordinator {
         for cluster in self.catalog().clusters() {
             if let ClusterVariant::Managed(ref config) = cluster.config.variant {
                 match config.schedule {
-                    ClusterSchedule::Manual => {
-                        // Nothing to do, user manages this cluster manually.
+                    ClusterSchedule::Manual | ClusterSchedule::OffDuring { .. } => {
+                        // Nothing to do, user manages this cluster manually, or another policy
+                        // is responsible for it.
                     }
                     ClusterSchedule::Refresh {
                         rehydration_time_estimate,
@@ -211,6 +236,53 @@ impl Coordinator {
             .observe((Instant::now() - start_time).as_secs_f64());
     }
 
+    /// Runs the `SCHEDULE = OFF DURING` cluster scheduling policy, which wants a cluster to be Off
+    /// while the current wall-clock time falls into any of the cluster's windows, and On
+    /// otherwise, and sends `Message::SchedulingDecisions` with these decisions.
+    fn check_off_during_policy(&mut self) {
+        let start_time = Instant::now();
+
+        let now = self.now_datetime();
+        let decisions = self
+            .catalog()
+            .clusters()
+            .filter_map(|cluster| {
+                let ClusterVariant::Managed(config) = &cluster.config.variant else {
+                    return None;
+                };
+                let ClusterSchedule::OffDuring { windows } = &config.schedule else {
+                    return None;
+                };
+                let active_windows = windows
+                    .iter()
+                    .filter(|window| window.matches(now))
+                    .map(|window| window.to_string())
+                    .collect_vec();
+                debug!(%cluster.id, %now, ?active_windows, "check_off_during_policy");
+                let cluster_on = active_windows.is_empty();
+                Some((
+                    cluster.id,
+                    SchedulingDecision::OffDuring(OffDuringDecision { cluster_on }),
+                ))
+            })
+            .collect_vec();
+        if let Err(e) = self
+            .internal_cmd_tx
+            .send(Message::SchedulingDecisions(vec![(
+                OFF_DURING_POLICY_NAME,
+                decisions,
+            )]))
+        {
+            // It is not an error for us to be running after `internal_cmd_rx` is dropped.
+            warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+        }
+
+        self.metrics
+            .check_scheduling_policies_seconds
+            .with_label_values(&[OFF_DURING_POLICY_NAME, "main"])
+            .observe((Instant::now() - start_time).as_secs_f64());
+    }
+
     /// Handles `SchedulingDecisions`:
     /// 1. Adds the newly made decisions to `cluster_scheduling_decisions`.
     /// 2. Cleans up old decisions that are for clusters no longer in scope of automated scheduling
@@ -264,6 +336,13 @@ impl Coordinator {
                             cluster_id
                         );
                         self.cluster_scheduling_decisions.remove(&cluster_id);
+                    } else if let Some(decisions) =
+                        self.cluster_scheduling_decisions.get_mut(&cluster_id)
+                    {
+                        // The cluster might have switched to a different schedule, in which case
+                        // we drop the decisions of policies that no longer apply to it.
+                        let policies = policies_for_schedule(&managed_config.schedule);
+                        decisions.retain(|policy, _| policies.contains(policy));
                     }
                 }
             }
@@ -276,16 +355,19 @@ impl Coordinator {
             // to ensure that after an envd restart all policies have a chance to run at least once
             // before we turn off a cluster, to avoid spuriously turning off a cluster and possibly
             // losing a hydrated state.
-            if POLICIES.iter().all(|policy| decisions.contains_key(policy)) {
+            let cluster_config = self
+                .get_managed_cluster_config(cluster_id)
+                .expect("cleaned up non-existing and unmanaged clusters above");
+            if policies_for_schedule(&cluster_config.schedule)
+                .iter()
+                .all(|policy| decisions.contains_key(policy))
+            {
                 // Check whether the cluster's state matches the needed state.
                 // If any policy says On, then we need a replica.
                 let needs_replica = decisions
                     .values()
                     .map(|decision| decision.cluster_on())
                     .contains(&true);
-                let cluster_config = self
-                    .get_managed_cluster_config(cluster_id)
-                    .expect("cleaned up non-existing and unmanaged clusters above");
                 let has_replica = cluster_config.replication_factor > 0; // Is it On?
                 if needs_replica != has_replica {
                     // Turn the cluster On or Off.
@@ -333,7 +415,10 @@ impl Coordinator {
 
     /// Returns the managed config for a cluster. Returns None if the cluster doesn't exist or if
     /// it's an unmanaged cluster.
-    fn get_managed_cluster_config(&self, cluster_id: ClusterId) -> Option<ClusterVariantManaged> {
+    pub(crate) fn get_managed_cluster_config(
+        &self,
+        cluster_id: ClusterId,
+    ) -> Option<ClusterVariantManaged> {
         let cluster = self.catalog().try_get_cluster(cluster_id)?;
         if let ClusterVariant::Managed(managed_config) = cluster.config.variant.clone() {
             Some(managed_config)
@@ -11,10 +11,12 @@
 //! client via some external Materialize API (ex: HTTP and psql).
 
 use differential_dataflow::lattice::Lattice;
-use mz_adapter_types::dyncfgs::ALLOW_USER_SESSIONS;
+use mz_adapter_types::dyncfgs::{ALLOW_USER_SESSIONS, PEEK_PLAN_CACHE_MAX_ENTRIES};
 use mz_sql::session::metadata::SessionMetadata;
 use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
 use std::sync::Arc;
+use std::time::Instant;
 
 use futures::future::LocalBoxFuture;
 use futures::FutureExt;
@@ -59,12 +61,14 @@ use crate::command::{
     CatalogSnapshot, Command, ExecuteResponse, GetVariablesResponse, StartupResponse,
 };
 use crate::coord::appends::{Deferred, PendingWriteTxn};
+use crate::coord::plan_cache::PeekPlanCacheKey;
 use crate::coord::{
     ConnMeta, Coordinator, Message, PendingTxn, PlanValidity, PurifiedStatementReady,
 };
 use crate::error::AdapterError;
 use crate::notice::AdapterNotice;
 use crate::session::{Session, TransactionOps, TransactionStatus};
+use crate::statement_logging::StatementLifecycleEvent;
 use crate::util::{ClientTransmitter, ResultExt};
 use crate::webhook::{
     AppendWebhookResponse, AppendWebhookValidator, WebhookAppender, WebhookAppenderInvalidator,
@@ -89,6 +93,7 @@ impl Coordinator {
                     conn_id,
                     secret_key,
                     uuid,
+                    client_ip,
                     application_name,
                     notice_tx,
                 } => {
@@ -100,6 +105,7 @@ impl Coordinator {
                         conn_id,
                         secret_key,
                         uuid,
+                        client_ip,
                         application_name,
                         notice_tx,
                     )
@@ -123,12 +129,16 @@ impl Coordinator {
                 Command::CancelRequest {
                     conn_id,
                     secret_key,
+                    requested_at,
                 } => {
-                    self.handle_cancel(conn_id, secret_key).await;
+                    self.handle_cancel(conn_id, secret_key, requested_at).await;
                 }
 
-                Command::PrivilegedCancelRequest { conn_id } => {
-                    self.handle_privileged_cancel(conn_id).await;
+                Command::PrivilegedCancelRequest {
+                    conn_id,
+                    requested_at,
+                } => {
+                    self.handle_privileged_cancel(conn_id, requested_at).await;
                 }
 
                 Command::GetWebhook {
@@ -140,6 +150,28 @@ impl Coordinator {
                     self.handle_get_webhook(database, schema, name, tx);
                 }
 
+                Command::MapClientCertificate {
+                    common_name,
+                    subject_alt_names,
+                    tx,
+                } => {
+                    let role = self
+                        .catalog()
+                        .system_config()
+                        .tls_client_cert_role_mapping()
+                        .role(common_name.as_deref(), &subject_alt_names)
+                        .ok_or(AdapterError::UnmappedClientCertificate);
+                    let _ = tx.send(role);
+                }
+
+                Command::GetScramVerifier { role_name, tx } => {
+                    let verifier = self
+                        .catalog()
+                        .try_get_role_by_name(&role_name)
+                        .and_then(|role| role.attributes.scram_verifier.clone());
+                    let _ = tx.send(verifier);
+                }
+
                 Command::GetSystemVars { conn_id, tx } => {
                     let conn = &self.active_conns[&conn_id];
                     let vars = GetVariablesResponse::new(
@@ -211,7 +243,7 @@ impl Coordinator {
                         }
                     };
 
-                    self.sequence_plan(ctx, plan, ResolvedIds(BTreeSet::new()))
+                    self.sequence_plan(ctx, plan, ResolvedIds(BTreeSet::new()), None)
                         .await;
                 }
 
@@ -247,11 +279,12 @@ impl Coordinator {
         conn_id: ConnectionId,
         secret_key: u32,
         uuid: uuid::Uuid,
+        client_ip: Option<IpAddr>,
         application_name: String,
         notice_tx: mpsc::UnboundedSender<AdapterNotice>,
     ) {
         // Early return if successful, otherwise cleanup any possible state.
-        match self.handle_startup_inner(&user, &conn_id).await {
+        match self.handle_startup_inner(&user, &conn_id, client_ip).await {
             Ok(role_id) => {
                 let mut session_defaults = BTreeMap::new();
                 let system_config = self.catalog().state().system_config();
@@ -338,6 +371,7 @@ impl Coordinator {
         &mut self,
         user: &User,
         conn_id: &ConnectionId,
+        client_ip: Option<IpAddr>,
     ) -> Result<RoleId, AdapterError> {
         if self.catalog().try_get_role_by_name(&user.name).is_none() {
             // If the user has made it to this point, that means they have been fully authenticated.
@@ -361,6 +395,18 @@ impl Coordinator {
             return Err(AdapterError::UserSessionsDisallowed);
         }
 
+        // Sessions of system users, and sessions that are not for a network
+        // connection, are not subject to the network policy.
+        if let (true, Some(client_ip)) = (role_id.is_user(), client_ip) {
+            let allowlist = self
+                .catalog()
+                .system_config()
+                .network_policy_ingress_allowlist();
+            if !allowlist.is_empty() && !allowlist.iter().any(|n| n.contains(client_ip)) {
+                return Err(AdapterError::NetworkPolicyDenied(client_ip));
+            }
+        }
+
         self.catalog_mut()
             .create_temporary_schema(conn_id, role_id)?;
         Ok(role_id)
@@ -421,6 +467,7 @@ impl Coordinator {
             let params = portal.parameters.clone();
             let stmt = portal.stmt.clone();
             let logging = Arc::clone(&portal.logging);
+            let is_cursor = portal.is_cursor;
 
             let extra = if let Some(extra) = outer_context {
                 // We are executing in the context of another SQL statement, so we don't
@@ -434,7 +481,11 @@ impl Coordinator {
 
                 ExecuteContextExtra::new(maybe_uuid)
             };
-            let ctx = ExecuteContext::from_parts(tx, self.internal_cmd_tx.clone(), session, extra);
+            let mut ctx =
+                ExecuteContext::from_parts(tx, self.internal_cmd_tx.clone(), session, extra);
+            if is_cursor {
+                ctx.set_cursor();
+            }
             (stmt, ctx, params)
         };
 
@@ -527,6 +578,7 @@ impl Coordinator {
                     | Statement::Execute(_)
                     | Statement::ExplainPlan(_)
                     | Statement::ExplainPushdown(_)
+                    | Statement::ExplainAnalyze(_)
                     | Statement::ExplainTimestamp(_)
                     | Statement::ExplainSinkSchema(_)
                     | Statement::Fetch(_)
@@ -575,6 +627,7 @@ impl Coordinator {
                     | Statement::AlterOwner(_)
                     | Statement::AlterRetainHistory(_)
                     | Statement::AlterRole(_)
+                    | Statement::AlterDatabase(_)
                     | Statement::AlterSecret(_)
                     | Statement::AlterSink(_)
                     | Statement::AlterSource(_)
@@ -812,8 +865,31 @@ impl Coordinator {
             _ => (stmt, resolved_ids),
         };
 
+        // `SELECT`s are looked up in the peek plan cache by their resolved
+        // statement and parameters.
+        let plan_cache_key = match &stmt {
+            Statement::Select(select)
+                if PEEK_PLAN_CACHE_MAX_ENTRIES.get(self.catalog().system_config().dyncfgs())
+                    > 0 =>
+            {
+                Some(PeekPlanCacheKey::new(select.clone(), &params))
+            }
+            _ => None,
+        };
+
         match self.plan_statement(ctx.session(), stmt, &params, &resolved_ids) {
-            Ok(plan) => self.sequence_plan(ctx, plan, resolved_ids).await,
+            Ok(plan) => {
+                if let Some(id) = ctx.extra().contents() {
+                    let now = self.now();
+                    self.record_statement_lifecycle_event(
+                        &id,
+                        &StatementLifecycleEvent::PlanningFinished,
+                        now,
+                    );
+                }
+                self.sequence_plan(ctx, plan, resolved_ids, plan_cache_key)
+                    .await
+            }
             Err(e) => ctx.retire(Err(e)),
         }
     }
@@ -955,7 +1031,12 @@ impl Coordinator {
     /// `ConnectionId` because this method gets called by external clients when
     /// they request to cancel a request.
     #[mz_ore::instrument(level = "debug")]
-    async fn handle_cancel(&mut self, conn_id: ConnectionIdType, secret_key: u32) {
+    async fn handle_cancel(
+        &mut self,
+        conn_id: ConnectionIdType,
+        secret_key: u32,
+        requested_at: Instant,
+    ) {
         if let Some((id_handle, conn_meta)) = self.active_conns.get_key_value(&conn_id) {
             // If the secret key specified by the client doesn't match the
             // actual secret key for the target connection, we treat this as a
@@ -967,14 +1048,24 @@ impl Coordinator {
             // Now that we've verified the secret key, this is a privileged
             // cancellation request. We can upgrade the raw connection ID to a
             // proper `IdHandle`.
-            self.handle_privileged_cancel(id_handle.clone()).await;
+            self.handle_privileged_cancel(id_handle.clone(), requested_at)
+                .await;
         }
     }
 
     /// Unconditionally instructs the dataflow layer to cancel any ongoing,
     /// interactive work for the named `conn_id`.
+    ///
+    /// Peeks are canceled on the replicas, and the one-shot dataflows of slow
+    /// path peeks and compute sinks are dropped, so that replicas stop working
+    /// on them right away. If any work was canceled, the time since
+    /// `requested_at` is recorded as the cancellation latency.
     #[mz_ore::instrument(level = "debug")]
-    pub(crate) async fn handle_privileged_cancel(&mut self, conn_id: ConnectionId) {
+    pub(crate) async fn handle_privileged_cancel(
+        &mut self,
+        conn_id: ConnectionId,
+        requested_at: Instant,
+    ) {
         // Cancel pending writes. There is at most one pending write per session.
         let mut maybe_ctx = None;
         if let Some(idx) = self.pending_writes.iter().position(|pending_write_txn| {
@@ -1011,15 +1102,24 @@ impl Coordinator {
             maybe_ctx = Some(ctx);
         }
 
+        let mut canceled = maybe_ctx.is_some();
         if let Some(ctx) = maybe_ctx {
             ctx.retire(Err(AdapterError::Canceled));
         }
 
-        self.cancel_pending_peeks(&conn_id);
+        canceled |= self.cancel_pending_peeks(&conn_id) > 0;
         self.cancel_pending_watchsets(&conn_id);
-        self.cancel_compute_sinks_for_conn(&conn_id).await;
+        canceled |= self.cancel_compute_sinks_for_conn(&conn_id).await > 0;
         if let Some((tx, _rx)) = self.staged_cancellation.get_mut(&conn_id) {
             let _ = tx.send(true);
+            canceled = true;
+        }
+
+        if canceled {
+            self.metrics
+                .cancellation_seconds
+                .with_label_values(&[])
+                .observe(requested_at.elapsed().as_secs_f64());
         }
     }
 
@@ -1161,6 +1261,7 @@ impl Coordinator {
                 body_format,
                 header_tys,
                 validator,
+                row_limit: coord.webhook_row_limit.clone(),
             })
         }
 
@@ -408,6 +408,14 @@ impl Coordinator {
             .chain(views_to_drop.iter())
             .collect();
 
+        // Cached plans of `SELECT`s that read dropped relations must not outlive them.
+        if !relations_to_drop.is_empty() {
+            self.peek_plan_cache
+                .lock()
+                .expect("poisoned")
+                .invalidate(&relations_to_drop);
+        }
+
         // Clean up any active compute sinks like subscribes or copy to-s that rely on dropped relations or clusters.
         for (sink_id, sink) in &self.active_compute_sinks {
             let cluster_id = sink.cluster_id();
@@ -516,6 +524,8 @@ impl Coordinator {
             active_conns,
             controller,
             cluster_replica_statuses,
+            replica_health,
+            crash_loop_replacements,
             ..
         } = self;
         let catalog = Arc::make_mut(catalog);
@@ -532,6 +542,7 @@ impl Coordinator {
         // TODO(jkosh44) All these builtin table updates should be handled as a builtin source
         // updates elsewhere.
         for (cluster_id, replica_id) in &cluster_replicas_to_drop {
+            replica_health.remove(&(*cluster_id, *replica_id));
             let replica_statuses =
                 cluster_replica_statuses.remove_cluster_replica_statuses(cluster_id, replica_id);
             for (process_id, status) in replica_statuses {
@@ -548,6 +559,8 @@ impl Coordinator {
             }
         }
         for cluster_id in &clusters_to_drop {
+            replica_health.retain(|(id, _), _| id != cluster_id);
+            crash_loop_replacements.remove_cluster(cluster_id);
             let cluster_statuses = cluster_replica_statuses.remove_cluster_statuses(cluster_id);
             for (replica_id, replica_statuses) in cluster_statuses {
                 for (process_id, status) in replica_statuses {
@@ -815,6 +828,7 @@ impl Coordinator {
     fn drop_tables(&mut self, tables: Vec<GlobalId>, ts: Timestamp) {
         for id in &tables {
             self.drop_storage_read_policy(id);
+            self.table_write_timestamps.remove(id);
         }
         self.controller
             .storage
@@ -862,10 +876,16 @@ impl Coordinator {
                 Some(sink) => sink,
             };
 
+            // A subscribe sharing a dataflow only drops the dataflow if it is
+            // the last one receiving its results.
+            let dataflow_ids = match self.shared_subscribes.detach(&sink_id) {
+                Some(dataflow_ids) => dataflow_ids,
+                None => vec![sink_id],
+            };
             by_cluster
                 .entry(sink.cluster_id())
                 .or_default()
-                .push(sink_id);
+                .extend(dataflow_ids);
             by_id.insert(sink_id, sink);
         }
         for (cluster_id, ids) in by_cluster {
@@ -898,10 +918,31 @@ impl Coordinator {
     }
 
     /// Cancels all active compute sinks for the identified connection.
+    ///
+    /// Returns the number of sinks canceled.
     #[mz_ore::instrument(level = "debug")]
-    pub(crate) async fn cancel_compute_sinks_for_conn(&mut self, conn_id: &ConnectionId) {
+    pub(crate) async fn cancel_compute_sinks_for_conn(&mut self, conn_id: &ConnectionId) -> usize {
+        let mut canceled = 0;
+        for sink_id in &self
+            .active_conns
+            .get(conn_id)
+            .expect("must exist for active session")
+            .drop_sinks
+        {
+            let kind = match self.active_compute_sinks.get(sink_id) {
+                Some(ActiveComputeSink::Subscribe(_)) => "subscribe",
+                Some(ActiveComputeSink::CopyTo(_)) => "copy_to",
+                None => continue,
+            };
+            self.metrics
+                .canceled_dataflows
+                .with_label_values(&[kind])
+                .inc();
+            canceled += 1;
+        }
         self.retire_compute_sinks_for_conn(conn_id, ActiveComputeSinkRetireReason::Canceled)
-            .await
+            .await;
+        canceled
     }
 
     /// Retires all active compute sinks for the identified connection with the
@@ -1135,6 +1176,9 @@ impl Coordinator {
             .webhook_concurrent_request_limit();
         self.webhook_concurrency_limit
             .set_limit(webhook_request_limit);
+
+        let webhook_row_limit = self.catalog().system_config().webhook_request_row_limit();
+        self.webhook_row_limit.set(webhook_row_limit);
     }
 
     pub(crate) async fn create_storage_export(
@@ -1410,7 +1454,9 @@ impl Coordinator {
                     | CatalogItem::Func(_) => {}
                 },
                 Op::AlterRole { .. }
+                | Op::AlterDatabase { .. }
                 | Op::AlterRetainHistory { .. }
+                | Op::AlterSetCluster { .. }
                 | Op::UpdatePrivilege { .. }
                 | Op::UpdateDefaultPrivilege { .. }
                 | Op::GrantRole { .. }
@@ -1421,6 +1467,8 @@ impl Coordinator {
                 | Op::UpdateOwner { .. }
                 | Op::RevokeRole { .. }
                 | Op::UpdateClusterConfig { .. }
+                | Op::RecordClusterAutoscaling { .. }
+                | Op::RecordReplicaHealth { .. }
                 | Op::UpdateStorageUsage { .. }
                 | Op::UpdateSystemConfiguration { .. }
                 | Op::ResetSystemConfiguration { .. }
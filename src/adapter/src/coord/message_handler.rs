@@ -24,6 +24,7 @@ use mz_ore::option::OptionExt;
 use mz_ore::task;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_persist_client::usage::ShardsUsageReferenced;
+use mz_persist_client::ShardId;
 use mz_sql::ast::Statement;
 use mz_sql::names::ResolvedIds;
 use mz_sql::pure::PurifiedStatement;
@@ -118,6 +119,9 @@ impl Coordinator {
                 Message::CancelPendingPeeks { conn_id } => {
                     self.cancel_pending_peeks(&conn_id);
                 }
+                Message::PeekPage { peek, sender } => {
+                    self.peek_page(&peek, sender, Default::default());
+                }
                 Message::LinearizeReads => {
                     self.message_linearize_reads().await;
                 }
@@ -130,6 +134,18 @@ impl Coordinator {
                 Message::StorageUsageUpdate(sizes) => {
                     self.storage_usage_update(sizes).await;
                 }
+                Message::CollectionStatisticsFetch => {
+                    self.collection_statistics_fetch().await;
+                }
+                Message::CollectionStatisticsUpdate(stats) => {
+                    self.collection_statistics_update(stats);
+                }
+                Message::StorageShardUsageFetch => {
+                    self.storage_shard_usage_fetch();
+                }
+                Message::StorageShardUsageUpdate(reports) => {
+                    self.storage_shard_usage_update(reports);
+                }
                 Message::RetireExecute {
                     otel_ctx,
                     data,
@@ -197,6 +213,20 @@ impl Coordinator {
                 } => {
                     self.sequence_staged(ctx, span, stage).await;
                 }
+                Message::AlterSetClusterStageReady {
+                    ctx,
+                    span,
+                    stage,
+                } => {
+                    self.sequence_staged(ctx, span, stage).await;
+                }
+                Message::AlterClusterStageReady {
+                    ctx,
+                    span,
+                    stage,
+                } => {
+                    self.sequence_staged(ctx, span, stage).await;
+                }
                 Message::DrainStatementLog => {
                     self.drain_statement_log().await;
                 }
@@ -214,6 +244,17 @@ impl Coordinator {
                 }
                 Message::CheckSchedulingPolicies => {
                     self.check_scheduling_policies().await;
+                    self.check_replica_health().await;
+                }
+                Message::RenewComputeLeases => {
+                    self.message_renew_compute_leases();
+                }
+                Message::ReplaceCrashLoopingReplica {
+                    cluster_id,
+                    replica_id,
+                } => {
+                    self.message_replace_crash_looping_replica(cluster_id, replica_id)
+                        .await;
                 }
                 Message::SchedulingDecisions(decisions) => {
                     self.handle_scheduling_decisions(decisions).await;
@@ -224,14 +265,9 @@ impl Coordinator {
         .boxed_local()
     }
 
-    #[mz_ore::instrument(level = "debug")]
-    pub async fn storage_usage_fetch(&mut self) {
-        let internal_cmd_tx = self.internal_cmd_tx.clone();
-        let client = self.storage_usage_client.clone();
-
-        // Record the currently live shards.
-        let live_shards: BTreeSet<_> = self
-            .controller
+    /// Returns the shards of all currently live storage collections.
+    pub(crate) fn live_storage_shards(&self) -> BTreeSet<ShardId> {
+        self.controller
             .storage
             .active_collection_metadatas()
             .into_iter()
@@ -254,7 +290,16 @@ impl Coordinator {
                 [remap_shard, status_shard, Some(data_shard)].into_iter()
             })
             .filter_map(|shard| shard)
-            .collect();
+            .collect()
+    }
+
+    #[mz_ore::instrument(level = "debug")]
+    pub async fn storage_usage_fetch(&mut self) {
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let client = self.storage_usage_client.clone();
+
+        // Record the currently live shards.
+        let live_shards = self.live_storage_shards();
 
         let collection_metric = self
             .metrics
@@ -373,6 +418,12 @@ impl Coordinator {
             ControllerResponse::PeekResponse(uuid, response, otel_ctx) => {
                 self.send_peek_response(uuid, response, otel_ctx);
             }
+            ControllerResponse::SubscribeResponse(sink_id, response)
+                if self.shared_subscribes.contains(&sink_id) =>
+            {
+                self.process_shared_subscribe_response(sink_id, response)
+                    .await;
+            }
             ControllerResponse::SubscribeResponse(sink_id, response) => {
                 match self.active_compute_sinks.get_mut(&sink_id) {
                     Some(ActiveComputeSink::Subscribe(active_subscribe)) => {
@@ -548,7 +599,7 @@ impl Coordinator {
         };
 
         match plan {
-            Ok((plan, resolved_ids)) => self.sequence_plan(ctx, plan, resolved_ids).await,
+            Ok((plan, resolved_ids)) => self.sequence_plan(ctx, plan, resolved_ids, None).await,
             Err(e) => ctx.retire(Err(e)),
         }
     }
@@ -647,7 +698,7 @@ impl Coordinator {
                         // Write statements never need to track resolved IDs (NOTE: This is not the
                         // same thing as plan dependencies, which we do need to re-validate).
                         let resolved_ids = ResolvedIds(BTreeSet::new());
-                        self.sequence_plan(ready.ctx, ready.plan, resolved_ids)
+                        self.sequence_plan(ready.ctx, ready.plan, resolved_ids, None)
                             .await;
                     }
                 }
@@ -661,6 +712,53 @@ impl Coordinator {
         // here.
     }
 
+    /// Renews the compute leases of all subscribes, COPY TOs, and peeks owned by active
+    /// connections.
+    ///
+    /// Transient work that isn't owned by an active connection is not renewed, and thus
+    /// eventually reaped by the compute controller.
+    #[mz_ore::instrument(level = "debug")]
+    fn message_renew_compute_leases(&mut self) {
+        let mut collections_by_cluster: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        let mut peeks_by_cluster: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for (conn_id, conn) in &self.active_conns {
+            for sink_id in &conn.drop_sinks {
+                if let Some(sink) = self.active_compute_sinks.get(sink_id) {
+                    let collection_ids =
+                        collections_by_cluster.entry(sink.cluster_id()).or_default();
+                    collection_ids.push(*sink_id);
+                    // Shared subscribe dataflows are kept alive by the
+                    // subscribes receiving their results.
+                    if let Some(dataflow_id) = self.shared_subscribes.dataflow_id(sink_id) {
+                        collection_ids.push(dataflow_id);
+                    }
+                }
+            }
+            for (uuid, cluster_id) in self.client_pending_peeks.get(conn_id).into_iter().flatten() {
+                peeks_by_cluster.entry(*cluster_id).or_default().push(*uuid);
+            }
+        }
+
+        let cluster_ids: BTreeSet<_> = collections_by_cluster
+            .keys()
+            .chain(peeks_by_cluster.keys())
+            .copied()
+            .collect();
+        for cluster_id in cluster_ids {
+            let collection_ids = collections_by_cluster
+                .remove(&cluster_id)
+                .unwrap_or_default();
+            let peek_uuids = peeks_by_cluster.remove(&cluster_id).unwrap_or_default();
+            // A cluster could have been dropped, so verify it exists.
+            if self.controller.compute.instance_exists(cluster_id) {
+                self.controller
+                    .compute
+                    .renew_leases(cluster_id, collection_ids, peek_uuids)
+                    .unwrap_or_terminate("cannot fail to renew leases");
+            }
+        }
+    }
+
     #[mz_ore::instrument(level = "debug")]
     async fn message_cluster_event(&mut self, event: ClusterEvent) {
         event!(Level::TRACE, event = format!("{:?}", event));
@@ -772,6 +870,14 @@ impl Coordinator {
                     status: new_replica_status,
                     time: event.time,
                 });
+                self.update_replica_health(
+                    event.cluster_id,
+                    event.replica_id,
+                    old_replica_status,
+                    new_replica_status,
+                    event.time,
+                )
+                .await;
             }
         }
     }
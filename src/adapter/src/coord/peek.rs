@@ -12,7 +12,7 @@
 //! This module determines if a dataflow can be short-cut, by returning constant values
 //! or by reading out of existing arrangements, and implements the appropriate plan.
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fmt;
 use std::num::NonZeroUsize;
 
@@ -28,21 +28,27 @@ use mz_compute_types::ComputeInstanceId;
 use mz_controller_types::ClusterId;
 use mz_expr::explain::{fmt_text_constant_rows, HumanizedExplain, HumanizerMode};
 use mz_expr::{
-    permutation_for_arrangement, EvalError, Id, MirRelationExpr, MirScalarExpr,
-    OptimizedMirRelationExpr, RowSetFinishing,
+    permutation_for_arrangement, BinaryFunc, ColumnOrder, EvalError, Id, MapFilterProject,
+    MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, RowSetFinishing,
 };
 use mz_ore::cast::CastFrom;
+use mz_ore::num::NonNeg;
 use mz_ore::str::{separated, StrExt};
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_repr::explain::text::DisplayText;
 use mz_repr::explain::{CompactScalars, IndexUsageType, PlanRenderingContext, UsedIndexes};
-use mz_repr::{Diff, GlobalId, IntoRowIterator, RelationType, Row, RowCollection, RowIterator};
+use mz_repr::{
+    ColumnType, DatumVec, Diff, GlobalId, IntoRowIterator, RelationType, Row, RowCollection,
+    RowIterator, SortedRowCollectionIter,
+};
 use serde::{Deserialize, Serialize};
 use timely::progress::Timestamp;
-use tokio::sync::oneshot;
+use tokio::sync::{mpsc, oneshot};
 use uuid::Uuid;
 
+use crate::command::RowsStream;
 use crate::coord::timestamp_selection::TimestampDetermination;
+use crate::coord::Message;
 use crate::optimize::OptimizerError;
 use crate::statement_logging::{StatementEndedExecutionReason, StatementExecutionStrategy};
 use crate::util::ResultExt;
@@ -61,6 +67,68 @@ pub(crate) struct PendingPeek {
     pub(crate) is_fast_path: bool,
     pub(crate) limit: Option<usize>,
     pub(crate) offset: usize,
+    /// The key under which to cache the rows of the response, if any.
+    pub(crate) result_cache_key: Option<PeekResultCacheKey>,
+}
+
+/// Identifies the result of a fast-path peek.
+///
+/// The contents of a collection at a timestamp never change, so peeks that read it with the
+/// same plan and finishing at the same timestamp have the same result.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct PeekResultCacheKey {
+    plan: FastPathPlan,
+    timestamp: mz_repr::Timestamp,
+    cluster_id: ClusterId,
+    finishing: RowSetFinishing,
+}
+
+/// The results of recent fast-path peeks, bounded by their total size and evicted in the
+/// order they were inserted.
+///
+/// Entries are never invalidated explicitly: a write to a peeked collection is only visible
+/// at a later timestamp, which is a different key.
+#[derive(Debug, Default)]
+pub(crate) struct PeekResultCache {
+    entries: BTreeMap<PeekResultCacheKey, RowCollection>,
+    /// The keys of `entries`, in the order they were inserted.
+    insertion_order: VecDeque<PeekResultCacheKey>,
+    /// The sum of the byte lengths of the cached rows.
+    size: usize,
+}
+
+impl PeekResultCache {
+    /// Returns the cached rows for `key`, if any.
+    pub(crate) fn get(&self, key: &PeekResultCacheKey) -> Option<&RowCollection> {
+        self.entries.get(key)
+    }
+
+    /// Caches `rows` under `key`, evicting the oldest entries until the cache fits in
+    /// `max_size` bytes.
+    pub(crate) fn insert(&mut self, key: PeekResultCacheKey, rows: RowCollection, max_size: usize) {
+        let len = rows.byte_len();
+        if len > max_size || self.entries.contains_key(&key) {
+            return;
+        }
+        self.insertion_order.push_back(key.clone());
+        self.entries.insert(key, rows);
+        self.size += len;
+        while self.size > max_size {
+            let key = self
+                .insertion_order
+                .pop_front()
+                .expect("size accounts for entries");
+            let evicted = self.entries.remove(&key).expect("known to exist");
+            self.size -= evicted.byte_len();
+        }
+    }
+
+    /// Drops all cached rows.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.insertion_order.clear();
+        self.size = 0;
+    }
 }
 
 /// The response from a `Peek`, with row multiplicities represented in unary.
@@ -264,6 +332,265 @@ pub struct PlannedPeek {
     pub source_ids: BTreeSet<GlobalId>,
 }
 
+/// A fast-path peek into an index for the rows of a cursor, which are read in
+/// pages of bounded size as they are fetched, rather than all at once.
+///
+/// Each page is read by a separate peek at the timestamp of the first one,
+/// which the read holds of the cursor's transaction keep readable. The peek for
+/// a page only returns the rows that do not precede the last row returned so
+/// far in `order_by`, which extends the order of the query to a total order on
+/// rows. Each such peek still scans the index, but neither the replica nor the
+/// coordinator hold on to more than a page of rows at a time.
+#[derive(Clone, Debug)]
+pub(crate) struct PagedPeek {
+    conn_id: ConnectionId,
+    cluster_id: ClusterId,
+    target_replica: Option<ReplicaId>,
+    /// All `GlobalId`s that the peek depend on.
+    depends_on: BTreeSet<GlobalId>,
+    index_id: GlobalId,
+    literal_constraints: Option<Vec<Row>>,
+    map_filter_project: MapFilterProject,
+    timestamp: mz_repr::Timestamp,
+    /// The types of the columns produced by `map_filter_project`.
+    column_types: Vec<ColumnType>,
+    /// The order of the query, followed by all columns.
+    order_by: Vec<ColumnOrder>,
+    /// The projection of the query, applied to the rows of each page.
+    project: Vec<usize>,
+    page_size: usize,
+    max_result_size: u64,
+    max_returned_query_size: Option<u64>,
+    /// The last row returned so far, and how many of its copies were returned.
+    last: Option<(Row, usize)>,
+}
+
+impl PagedPeek {
+    /// Plans reading the results of `plan` in pages of `page_size` rows.
+    ///
+    /// Returns `None` if `plan` is not a fast-path peek into an index, or if
+    /// `finishing` limits the rows of the query.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        plan: &PlannedPeek,
+        finishing: &RowSetFinishing,
+        typ: &RelationType,
+        cluster_id: ClusterId,
+        target_replica: Option<ReplicaId>,
+        page_size: usize,
+        max_result_size: u64,
+        max_returned_query_size: Option<u64>,
+    ) -> Option<Self> {
+        let PeekPlan::FastPath(FastPathPlan::PeekExisting(
+            _coll_id,
+            index_id,
+            literal_constraints,
+            map_filter_project,
+        )) = &plan.plan
+        else {
+            return None;
+        };
+        if finishing.limit.is_some() || finishing.offset > 0 || page_size == 0 {
+            return None;
+        }
+        let timestamp = *plan.determination.timestamp_context.timestamp()?;
+        let order_by = finishing
+            .order_by
+            .iter()
+            .cloned()
+            .chain((0..typ.arity()).map(|column| ColumnOrder {
+                column,
+                desc: false,
+                nulls_last: true,
+            }))
+            .collect();
+        Some(PagedPeek {
+            conn_id: plan.conn_id.clone(),
+            cluster_id,
+            target_replica,
+            depends_on: plan.source_ids.clone(),
+            index_id: *index_id,
+            literal_constraints: literal_constraints.clone(),
+            map_filter_project: (**map_filter_project).clone(),
+            timestamp,
+            column_types: typ.column_types.clone(),
+            order_by,
+            project: finishing.project.clone(),
+            page_size,
+            max_result_size,
+            max_returned_query_size,
+            last: None,
+        })
+    }
+
+    /// The finishing of the peek for the next page, which must also return the
+    /// copies of the last row that were already returned.
+    fn page_finishing(&self) -> RowSetFinishing {
+        let returned = self.last.as_ref().map_or(0, |(_, copies)| *copies);
+        let limit = i64::try_from(self.page_size.saturating_add(returned)).unwrap_or(i64::MAX);
+        RowSetFinishing {
+            order_by: self.order_by.clone(),
+            limit: Some(NonNeg::try_from(limit).expect("known to be non-negative")),
+            offset: 0,
+            project: (0..self.column_types.len()).collect(),
+        }
+    }
+
+    /// The map-filter-project of the peek for the next page, which discards the
+    /// rows that precede the last row returned so far.
+    fn page_map_filter_project(&self) -> Result<mz_expr::SafeMfpPlan, OptimizerError> {
+        let mut map_filter_project = self.map_filter_project.clone();
+        if let Some((last, _)) = &self.last {
+            map_filter_project = map_filter_project.filter([self.not_before(last)]);
+        }
+        mfp_to_safe_plan(map_filter_project)
+    }
+
+    /// Returns a predicate that holds for the rows that do not precede `row`
+    /// in `self.order_by`.
+    ///
+    /// These are the rows that are equal to `row` in all columns of the order,
+    /// or equal to `row` in a prefix of them and after it in the next one.
+    fn not_before(&self, row: &Row) -> MirScalarExpr {
+        let datums = row.unpack();
+        let literal = |column: usize| {
+            MirScalarExpr::literal_ok(
+                datums[column],
+                self.column_types[column].scalar_type.clone(),
+            )
+        };
+        let equal = |column: usize| {
+            if datums[column].is_null() {
+                MirScalarExpr::column(column).call_is_null()
+            } else {
+                MirScalarExpr::column(column).call_binary(literal(column), BinaryFunc::Eq)
+            }
+        };
+        let after = |order: &ColumnOrder| {
+            let column = MirScalarExpr::column(order.column);
+            if datums[order.column].is_null() {
+                if order.nulls_last {
+                    MirScalarExpr::literal_false()
+                } else {
+                    column.call_is_null().not()
+                }
+            } else {
+                let func = if order.desc {
+                    BinaryFunc::Lt
+                } else {
+                    BinaryFunc::Gt
+                };
+                let after = column.clone().call_binary(literal(order.column), func);
+                if order.nulls_last {
+                    after.or(column.call_is_null())
+                } else {
+                    after
+                }
+            }
+        };
+
+        let all_equal = self
+            .order_by
+            .iter()
+            .map(|order| equal(order.column))
+            .fold(MirScalarExpr::literal_true(), MirScalarExpr::and);
+        self.order_by
+            .iter()
+            .enumerate()
+            .map(|(i, order)| {
+                self.order_by[..i]
+                    .iter()
+                    .map(|order| equal(order.column))
+                    .fold(after(order), MirScalarExpr::and)
+            })
+            .fold(all_equal, MirScalarExpr::or)
+    }
+
+    /// Consumes the response to the peek for the next page.
+    ///
+    /// Returns the rows that were not returned before, projected to the
+    /// columns of the query, and whether there are more pages to read.
+    fn next_page(&mut self, rows: RowCollection) -> Result<(Vec<Row>, bool), String> {
+        let finishing = self.page_finishing();
+        let limit = finishing
+            .limit
+            .map(|limit| usize::cast_from(u64::from(limit)))
+            .expect("pages are limited");
+        let mut rows =
+            finishing.finish(rows, self.max_result_size, self.max_returned_query_size)?;
+
+        // The page starts with the copies of the last row that were returned already.
+        let mut skip = self.last.as_ref().map_or(0, |(_, copies)| *copies);
+        let mut read = 0;
+        let mut page = Vec::new();
+        let mut datum_vec = DatumVec::new();
+        while let Some(row) = rows.next() {
+            read += 1;
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            match &mut self.last {
+                Some((last, copies)) if **last == *row => *copies += 1,
+                last => *last = Some((row.to_owned(), 1)),
+            }
+            let datums = datum_vec.borrow_with(row);
+            page.push(Row::pack(self.project.iter().map(|column| datums[*column])));
+        }
+        Ok((page, read == limit))
+    }
+
+    /// Returns the stream of the pages of this peek, whose first page is read
+    /// by the peek that responds to `first_page`.
+    ///
+    /// The peeks for further pages are requested from the coordinator through
+    /// `internal_cmd_tx` as the stream is polled.
+    fn into_pages(
+        self,
+        first_page: oneshot::Receiver<PeekResponse>,
+        internal_cmd_tx: mpsc::UnboundedSender<Message>,
+    ) -> RowsStream {
+        let pages = futures::stream::unfold(Some((self, Some(first_page))), move |state| {
+            let internal_cmd_tx = internal_cmd_tx.clone();
+            async move {
+                let (mut peek, page) = state?;
+                let page = match page {
+                    Some(page) => page,
+                    None => {
+                        let (sender, page) = oneshot::channel();
+                        let message = Message::PeekPage {
+                            peek: peek.clone(),
+                            sender,
+                        };
+                        if internal_cmd_tx.send(message).is_err() {
+                            return Some((PeekResponseUnary::Canceled, None));
+                        }
+                        page
+                    }
+                };
+                let response = match page.await {
+                    Ok(PeekResponse::Rows(rows)) => match peek.next_page(rows) {
+                        Ok((rows, more)) if rows.is_empty() && !more => return None,
+                        Ok((rows, more)) => {
+                            let rows = Box::new(rows.into_row_iter());
+                            return Some((
+                                PeekResponseUnary::Rows(rows),
+                                more.then_some((peek, None)),
+                            ));
+                        }
+                        Err(e) => PeekResponseUnary::Error(e),
+                    },
+                    Ok(PeekResponse::Error(e)) => PeekResponseUnary::Error(e),
+                    Ok(PeekResponse::Canceled) => PeekResponseUnary::Canceled,
+                    Err(e) => PeekResponseUnary::Error(e.to_string()),
+                };
+                Some((response, None))
+            }
+        });
+        Box::pin(pages)
+    }
+}
+
 /// Possible ways in which the coordinator could produce the result for a goal view.
 #[derive(Clone, Debug)]
 pub enum PeekPlan<T = mz_repr::Timestamp> {
@@ -434,7 +761,83 @@ impl FastPathPlan {
     }
 }
 
+/// Constant peek results with at most this many rows are finished on the coordinator's main loop.
+/// Larger results are finished on a separate task.
+pub(crate) const MAX_INLINE_CONSTANT_ROWS: usize = 1024;
+
+/// Consolidates and finishes the rows of a constant peek.
+pub(crate) fn finish_constant_rows(
+    mut rows: Vec<(Row, Diff)>,
+    finishing: &RowSetFinishing,
+    max_result_size: u64,
+    max_returned_query_size: Option<u64>,
+) -> Result<SortedRowCollectionIter, AdapterError> {
+    // Consolidate down the results to get correct totals.
+    consolidate(&mut rows);
+
+    let mut results = Vec::new();
+    for (row, count) in rows {
+        if count < 0 {
+            Err(EvalError::InvalidParameterValue(format!(
+                "Negative multiplicity in constant result: {}",
+                count
+            )))?
+        };
+        if count > 0 {
+            let count = usize::cast_from(
+                u64::try_from(count).expect("known to be positive from check above"),
+            );
+            results.push((
+                row,
+                NonZeroUsize::new(count).expect("known to be non-zero from check above"),
+            ));
+        }
+    }
+    let row_collection = RowCollection::new(&results);
+
+    finishing
+        .finish(row_collection, max_result_size, max_returned_query_size)
+        .map_err(AdapterError::ResultSize)
+}
+
 impl crate::coord::Coordinator {
+    /// Produces the rows of a peek by running `finish` on a blocking task, off the
+    /// coordinator's main loop, and retires the peek's execution once they are ready.
+    fn finish_rows_off_main_loop<F>(
+        &self,
+        ctx_extra: &mut ExecuteContextExtra,
+        name: &'static str,
+        compute_instance: ComputeInstanceId,
+        strategy: StatementExecutionStrategy,
+        finish: F,
+    ) -> crate::ExecuteResponse
+    where
+        F: FnOnce() -> (PeekResponseUnary, StatementEndedExecutionReason) + Send + 'static,
+    {
+        let ctx_extra = std::mem::take(ctx_extra);
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let handle = mz_ore::task::spawn_blocking(
+            || name,
+            move || {
+                let (resp, reason) = finish();
+                // It is not an error for this message to fail to send: the coordinator might be
+                // shutting down.
+                let _ = internal_cmd_tx.send(Message::RetireExecute {
+                    data: ctx_extra,
+                    otel_ctx: OpenTelemetryContext::obtain(),
+                    reason,
+                });
+                resp
+            },
+        );
+        let rows_rx = handle.unwrap_or_else(|e| PeekResponseUnary::Error(e.to_string()));
+        crate::ExecuteResponse::SendingRows {
+            future: Box::pin(rows_rx),
+            instance_id: compute_instance,
+            strategy,
+        }
+    }
+
     /// Implements a peek plan produced by `create_plan` above.
     #[mz_ore::instrument(level = "debug")]
     pub async fn implement_peek_plan(
@@ -456,53 +859,21 @@ impl crate::coord::Coordinator {
         } = plan;
 
         // If the dataflow optimizes to a constant expression, we can immediately return the result.
+        // Large constants don't get here, as they are finished off the coordinator's main loop by
+        // `peek_finish`.
         if let PeekPlan::FastPath(FastPathPlan::Constant(rows, _)) = fast_path {
-            let mut rows = match rows {
+            let rows = match rows {
                 Ok(rows) => rows,
                 Err(e) => return Err(e.into()),
             };
-            // Consolidate down the results to get correct totals.
-            consolidate(&mut rows);
-
-            let mut results = Vec::new();
-            for (row, count) in rows {
-                if count < 0 {
-                    Err(EvalError::InvalidParameterValue(format!(
-                        "Negative multiplicity in constant result: {}",
-                        count
-                    )))?
-                };
-                if count > 0 {
-                    let count = usize::cast_from(
-                        u64::try_from(count).expect("known to be positive from check above"),
-                    );
-                    results.push((
-                        row,
-                        NonZeroUsize::new(count).expect("known to be non-zero from check above"),
-                    ));
-                }
-            }
-            let row_collection = RowCollection::new(&results);
-
-            let (ret, reason) =
-                match finishing.finish(row_collection, max_result_size, max_returned_query_size) {
-                    Ok(rows) => {
-                        let rows_returned = u64::cast_from(rows.count());
-                        (
-                            Ok(Self::send_immediate_rows(rows)),
-                            StatementEndedExecutionReason::Success {
-                                rows_returned: Some(rows_returned),
-                                execution_strategy: Some(StatementExecutionStrategy::Constant),
-                            },
-                        )
-                    }
-                    Err(error) => (
-                        Err(AdapterError::ResultSize(error.clone())),
-                        StatementEndedExecutionReason::Errored { error },
-                    ),
-                };
+            let rows =
+                finish_constant_rows(rows, &finishing, max_result_size, max_returned_query_size)?;
+            let reason = StatementEndedExecutionReason::Success {
+                rows_returned: Some(u64::cast_from(rows.count())),
+                execution_strategy: Some(StatementExecutionStrategy::Constant),
+            };
             self.retire_execution(reason, std::mem::take(ctx_extra));
-            return ret;
+            return Ok(Self::send_immediate_rows(rows));
         }
 
         let timestamp = determination.timestamp_context.timestamp_or_default();
@@ -510,6 +881,59 @@ impl crate::coord::Coordinator {
             self.set_statement_execution_timestamp(id, timestamp)
         }
 
+        // Identical fast-path peeks at the same timestamp can reuse a cached result.
+        let enable_result_cache = self.catalog().system_config().enable_peek_result_cache();
+        if !enable_result_cache {
+            self.peek_result_cache.clear();
+        }
+        let result_cache_key = match &fast_path {
+            PeekPlan::FastPath(plan) if enable_result_cache => determination
+                .timestamp_context
+                .timestamp()
+                .map(|timestamp| PeekResultCacheKey {
+                    plan: plan.clone(),
+                    timestamp: *timestamp,
+                    cluster_id: compute_instance,
+                    finishing: finishing.clone(),
+                }),
+            _ => None,
+        };
+        if let Some(rows) = result_cache_key
+            .as_ref()
+            .and_then(|key| self.peek_result_cache.get(key))
+        {
+            let rows = rows.clone();
+            self.metrics
+                .peek_result_cache_hits
+                .with_label_values(&[])
+                .inc();
+            // Like the response of a peek, the cached rows are finished off the coordinator's
+            // main loop.
+            return Ok(self.finish_rows_off_main_loop(
+                ctx_extra,
+                "finish cached peek",
+                compute_instance,
+                StatementExecutionStrategy::Cached,
+                move || {
+                    let limit = finishing.limit.map(|x| usize::cast_from(u64::from(x)));
+                    let rows_returned = rows.count(finishing.offset, limit);
+                    match finishing.finish(rows, max_result_size, max_returned_query_size) {
+                        Ok(rows) => (
+                            PeekResponseUnary::Rows(Box::new(rows)),
+                            StatementEndedExecutionReason::Success {
+                                rows_returned: Some(u64::cast_from(rows_returned)),
+                                execution_strategy: Some(StatementExecutionStrategy::Cached),
+                            },
+                        ),
+                        Err(error) => (
+                            PeekResponseUnary::Error(error.clone()),
+                            StatementEndedExecutionReason::Errored { error },
+                        ),
+                    }
+                },
+            ));
+        }
+
         // The remaining cases are a peek into a maintained arrangement, or building a dataflow.
         // In both cases we will want to peek, and the main difference is that we might want to
         // build a dataflow and drop it once the peek is issued. The peeks are also constructed
@@ -619,6 +1043,7 @@ impl crate::coord::Coordinator {
                 is_fast_path,
                 limit: finishing.limit.map(|x| usize::cast_from(u64::from(x))),
                 offset: finishing.offset,
+                result_cache_key,
             },
         );
         self.client_pending_peeks
@@ -670,38 +1095,127 @@ impl crate::coord::Coordinator {
         })
     }
 
+    /// Implements `peek`, whose rows are read in pages as they are fetched.
+    pub(crate) fn implement_paged_peek(
+        &mut self,
+        ctx_extra: &mut ExecuteContextExtra,
+        peek: PagedPeek,
+    ) -> ExecuteResponse {
+        if let Some(id) = ctx_extra.contents() {
+            self.set_statement_execution_timestamp(id, peek.timestamp)
+        }
+
+        let (sender, first_page) = oneshot::channel();
+        self.peek_page(&peek, sender, std::mem::take(ctx_extra));
+
+        ExecuteResponse::SendingRowsStreaming {
+            instance_id: peek.cluster_id,
+            rows: peek.into_pages(first_page, self.internal_cmd_tx.clone()),
+            strategy: StatementExecutionStrategy::FastPath,
+        }
+    }
+
+    /// Issues the peek for the next page of `peek`, whose response is sent to `sender`.
+    pub(crate) fn peek_page(
+        &mut self,
+        peek: &PagedPeek,
+        sender: oneshot::Sender<PeekResponse>,
+        ctx_extra: ExecuteContextExtra,
+    ) {
+        let finishing = peek.page_finishing();
+
+        let mut uuid = Uuid::new_v4();
+        while self.pending_peeks.contains_key(&uuid) {
+            uuid = Uuid::new_v4();
+        }
+        self.pending_peeks.insert(
+            uuid,
+            PendingPeek {
+                sender,
+                conn_id: peek.conn_id.clone(),
+                cluster_id: peek.cluster_id,
+                depends_on: peek.depends_on.clone(),
+                ctx_extra,
+                is_fast_path: true,
+                limit: finishing.limit.map(|x| usize::cast_from(u64::from(x))),
+                offset: finishing.offset,
+                result_cache_key: None,
+            },
+        );
+        self.client_pending_peeks
+            .entry(peek.conn_id.clone())
+            .or_default()
+            .insert(uuid, peek.cluster_id);
+
+        let result = peek
+            .page_map_filter_project()
+            .map_err(|e| e.to_string())
+            .and_then(|map_filter_project| {
+                self.controller
+                    .compute
+                    .peek(
+                        peek.cluster_id,
+                        peek.index_id,
+                        peek.literal_constraints.clone(),
+                        uuid,
+                        peek.timestamp,
+                        finishing,
+                        map_filter_project,
+                        peek.target_replica,
+                        PeekTarget::Index { id: peek.index_id },
+                    )
+                    .map_err(|e| e.to_string())
+            });
+        // The index or the cluster can have been dropped since the cursor was
+        // declared, which fails the cursor.
+        if let Err(e) = result {
+            self.send_peek_response(uuid, PeekResponse::Error(e), OpenTelemetryContext::obtain());
+        }
+    }
+
     /// Cancel and remove all pending peeks that were initiated by the client with `conn_id`.
+    ///
+    /// Returns the number of peeks canceled.
     #[mz_ore::instrument(level = "debug")]
-    pub(crate) fn cancel_pending_peeks(&mut self, conn_id: &ConnectionId) {
-        if let Some(uuids) = self.client_pending_peeks.remove(conn_id) {
-            self.metrics
-                .canceled_peeks
-                .with_label_values(&[])
-                .inc_by(u64::cast_from(uuids.len()));
+    pub(crate) fn cancel_pending_peeks(&mut self, conn_id: &ConnectionId) -> usize {
+        let Some(uuids) = self.client_pending_peeks.remove(conn_id) else {
+            return 0;
+        };
+        self.metrics
+            .canceled_peeks
+            .with_label_values(&[])
+            .inc_by(u64::cast_from(uuids.len()));
 
-            let mut inverse: BTreeMap<ComputeInstanceId, BTreeSet<Uuid>> = Default::default();
-            for (uuid, compute_instance) in &uuids {
-                inverse.entry(*compute_instance).or_default().insert(*uuid);
-            }
-            for (compute_instance, uuids) in inverse {
-                // It's possible that this compute instance no longer exists because it was dropped
-                // while the peek was in progress. In this case we ignore the error and move on
-                // because the dataflow no longer exists.
-                // TODO(jkosh44) Dropping a cluster should actively cancel all pending queries.
-                for uuid in uuids {
-                    let _ = self.controller.compute.cancel_peek(compute_instance, uuid);
-                }
+        let mut inverse: BTreeMap<ComputeInstanceId, BTreeSet<Uuid>> = Default::default();
+        for (uuid, compute_instance) in &uuids {
+            inverse.entry(*compute_instance).or_default().insert(*uuid);
+        }
+        for (compute_instance, uuids) in inverse {
+            // It's possible that this compute instance no longer exists because it was dropped
+            // while the peek was in progress. In this case we ignore the error and move on
+            // because the dataflow no longer exists.
+            // TODO(jkosh44) Dropping a cluster should actively cancel all pending queries.
+            for uuid in uuids {
+                let _ = self.controller.compute.cancel_peek(compute_instance, uuid);
             }
+        }
 
-            let peeks = uuids
-                .iter()
-                .filter_map(|(uuid, _)| self.pending_peeks.remove(uuid))
-                .collect::<Vec<_>>();
-            for peek in peeks {
-                self.retire_execution(StatementEndedExecutionReason::Canceled, peek.ctx_extra);
-                let _ = peek.sender.send(PeekResponse::Canceled);
-            }
+        let peeks = uuids
+            .iter()
+            .filter_map(|(uuid, _)| self.pending_peeks.remove(uuid))
+            .collect::<Vec<_>>();
+        // Slow path peeks read from a one-shot dataflow, which the
+        // controller drops once the canceled peek releases its read hold.
+        let dataflows = peeks.iter().filter(|peek| !peek.is_fast_path).count();
+        self.metrics
+            .canceled_dataflows
+            .with_label_values(&["peek"])
+            .inc_by(u64::cast_from(dataflows));
+        for peek in peeks {
+            self.retire_execution(StatementEndedExecutionReason::Canceled, peek.ctx_extra);
+            let _ = peek.sender.send(PeekResponse::Canceled);
         }
+        uuids.len()
     }
 
     pub(crate) fn send_peek_response(
@@ -721,10 +1235,15 @@ impl crate::coord::Coordinator {
             is_fast_path,
             limit,
             offset,
+            result_cache_key,
         }) = self.remove_pending_peek(&uuid)
         {
             let reason = match &response {
                 PeekResponse::Rows(r) => {
+                    if let Some(key) = result_cache_key {
+                        let max_size = self.catalog().system_config().peek_result_cache_size();
+                        self.peek_result_cache.insert(key, r.clone(), max_size);
+                    }
                     let rows_returned = r.count(offset, limit);
                     StatementEndedExecutionReason::Success {
                         rows_returned: Some(u64::cast_from(rows_returned)),
@@ -871,4 +1390,43 @@ mod tests {
             constant_exp2
         );
     }
+
+    #[mz_ore::test]
+    fn test_peek_result_cache() {
+        let key = |timestamp: u64| PeekResultCacheKey {
+            plan: FastPathPlan::PeekPersist(
+                GlobalId::User(1),
+                MapFilterProject::new(1)
+                    .into_plan()
+                    .expect("invalid plan")
+                    .into_nontemporal()
+                    .expect("invalid nontemporal"),
+            ),
+            timestamp: timestamp.into(),
+            cluster_id: ClusterId::User(1),
+            finishing: RowSetFinishing::trivial(1),
+        };
+        let rows = |s: &str| RowCollection::from(&[Row::pack_slice(&[Datum::String(s)])]);
+        let size = rows("aaaa").byte_len();
+
+        let mut cache = PeekResultCache::default();
+        cache.insert(key(1), rows("aaaa"), 2 * size);
+        cache.insert(key(2), rows("bbbb"), 2 * size);
+        assert!(cache.get(&key(1)).is_some());
+        assert!(cache.get(&key(2)).is_some());
+        assert!(cache.get(&key(3)).is_none());
+
+        // Inserting past the size limit evicts the oldest entry.
+        cache.insert(key(3), rows("cccc"), 2 * size);
+        assert!(cache.get(&key(1)).is_none());
+        assert!(cache.get(&key(2)).is_some());
+        assert!(cache.get(&key(3)).is_some());
+
+        // Results that are larger than the cache are not cached.
+        cache.insert(key(4), rows("dddd"), size - 1);
+        assert!(cache.get(&key(4)).is_none());
+
+        cache.clear();
+        assert!(cache.get(&key(2)).is_none());
+    }
 }
@@ -0,0 +1,315 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A cache of optimized plans for `SELECT` statements.
+
+//! Allow usage of `std::collections::HashMap`.
+//! The cache is keyed by SQL statements, which do not implement `Ord`, so we
+//! cannot use `BTreeMap`s. We need to iterate through the map to evict entries,
+//! so we cannot use the `mz_ore` wrapper either.
+#![allow(clippy::disallowed_types)]
+
+use std::collections::{BTreeSet, HashMap};
+use std::hash::Hash;
+
+use mz_controller_types::ClusterId;
+use mz_expr::MirRelationExpr;
+use mz_repr::optimize::OptimizerFeatures;
+use mz_repr::{GlobalId, Row, ScalarType};
+use mz_sql::ast::SelectStatement;
+use mz_sql::names::Aug;
+use mz_sql::plan::Params;
+
+use crate::optimize::peek::{GlobalLirPlan, LocalMirPlan};
+
+/// Identifies the execution of a `SELECT` statement with some parameters.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PeekPlanCacheKey {
+    /// The statement, with its names resolved and its parameters unbound.
+    stmt: SelectStatement<Aug>,
+    /// The types of the statement's parameters.
+    param_types: Vec<ScalarType>,
+    /// The values of the statement's parameters. The optimizer folds them into
+    /// the plan, so plans can't be shared between executions with different
+    /// values.
+    param_values: Row,
+}
+
+impl PeekPlanCacheKey {
+    pub fn new(stmt: SelectStatement<Aug>, params: &Params) -> Self {
+        PeekPlanCacheKey {
+            stmt,
+            param_types: params.types.clone(),
+            param_values: params.datums.clone(),
+        }
+    }
+}
+
+/// A cache of optimized plans for `SELECT` statements, keyed by the statement
+/// and its parameters.
+///
+/// Every entry holds the local MIR plan of a statement, which lets repeated
+/// executions skip HIR ⇒ MIR lowering and local MIR optimization. An entry can
+/// also hold the global plan of its statement, if that is a fast path plan that
+/// depends neither on the chosen timestamp nor on the session. Executions that
+/// target the cluster the global plan was produced for skip optimization
+/// entirely, as long as the catalog hasn't changed since, as any change could
+/// have added or removed indexes.
+///
+/// Entries remember the optimizer features they were produced with and are
+/// not used once those features change. Entries that depend on dropped
+/// objects are evicted by [`PeekPlanCache::invalidate`].
+pub struct PeekPlanCache<K = PeekPlanCacheKey, L = LocalMirPlan, G = GlobalLirPlan> {
+    entries: HashMap<K, CacheEntry<L, G>>,
+    /// A logical clock, used to find the least recently used entry.
+    clock: u64,
+}
+
+struct CacheEntry<L, G> {
+    local: L,
+    global: Option<CachedGlobalPlan<G>>,
+    features: OptimizerFeatures,
+    dependencies: BTreeSet<GlobalId>,
+    last_used: u64,
+}
+
+struct CachedGlobalPlan<G> {
+    plan: G,
+    cluster_id: ClusterId,
+    catalog_revision: u64,
+}
+
+/// A plan returned by [`PeekPlanCache::get`].
+#[derive(Debug)]
+pub enum CachedPeekPlan<L = LocalMirPlan, G = GlobalLirPlan> {
+    /// The local plan of the statement, which still needs global optimization.
+    Local(L),
+    /// The final plan of the statement.
+    Global(G),
+}
+
+impl<K, L, G> Default for PeekPlanCache<K, L, G> {
+    fn default() -> Self {
+        PeekPlanCache {
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, L: Clone, G: Clone> PeekPlanCache<K, L, G> {
+    /// Returns the most optimized plan cached for `key` that was produced with
+    /// `features`. The global plan is only returned if it was produced for
+    /// `cluster_id` at `catalog_revision`.
+    pub fn get(
+        &mut self,
+        key: &K,
+        features: &OptimizerFeatures,
+        cluster_id: ClusterId,
+        catalog_revision: u64,
+    ) -> Option<CachedPeekPlan<L, G>> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+        if &entry.features != features {
+            return None;
+        }
+        entry.last_used = self.clock;
+        match &entry.global {
+            Some(global)
+                if global.cluster_id == cluster_id
+                    && global.catalog_revision == catalog_revision =>
+            {
+                Some(CachedPeekPlan::Global(global.plan.clone()))
+            }
+            _ => Some(CachedPeekPlan::Local(entry.local.clone())),
+        }
+    }
+
+    /// Caches `plan` as the local optimization of `key` with `features`,
+    /// evicting the least recently used entry if the cache holds `max_entries`
+    /// plans.
+    pub fn insert(
+        &mut self,
+        key: K,
+        features: OptimizerFeatures,
+        dependencies: BTreeSet<GlobalId>,
+        plan: L,
+        max_entries: usize,
+    ) {
+        if max_entries == 0 {
+            self.entries.clear();
+            return;
+        }
+        while self.entries.len() >= max_entries && !self.entries.contains_key(&key) {
+            let lru = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+                .expect("cache is not empty");
+            self.entries.remove(&lru);
+        }
+        self.clock += 1;
+        self.entries.insert(
+            key,
+            CacheEntry {
+                local: plan,
+                global: None,
+                features,
+                dependencies,
+                last_used: self.clock,
+            },
+        );
+    }
+
+    /// Caches `plan` as the global optimization of `key` for `cluster_id` at
+    /// `catalog_revision`, if the local optimization of `key` is cached.
+    pub fn insert_global(
+        &mut self,
+        key: &K,
+        plan: G,
+        cluster_id: ClusterId,
+        catalog_revision: u64,
+    ) {
+        if let Some(entry) = self.entries.get_mut(key) {
+            entry.global = Some(CachedGlobalPlan {
+                plan,
+                cluster_id,
+                catalog_revision,
+            });
+        }
+    }
+
+    /// Evicts all plans that depend on any of `dropped`.
+    pub fn invalidate(&mut self, dropped: &BTreeSet<&GlobalId>) {
+        self.entries
+            .retain(|_, entry| !entry.dependencies.iter().any(|id| dropped.contains(id)));
+    }
+}
+
+/// Reports whether `expr` calls any unmaterializable functions. The global
+/// plans of such expressions depend on the timestamp or the session and must
+/// not be cached.
+pub fn contains_unmaterializable(expr: &MirRelationExpr) -> bool {
+    let mut contains = false;
+    expr.visit_scalars(&mut |e| contains = contains || e.contains_unmaterializable());
+    contains
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TestCache = PeekPlanCache<&'static str, &'static str, &'static str>;
+
+    fn get(
+        cache: &mut TestCache,
+        key: &'static str,
+    ) -> Option<CachedPeekPlan<&'static str, &'static str>> {
+        cache.get(&key, &OptimizerFeatures::default(), ClusterId::User(1), 1)
+    }
+
+    fn insert(cache: &mut TestCache, key: &'static str, dependencies: &[u64], max_entries: usize) {
+        let dependencies = dependencies.iter().map(|id| GlobalId::User(*id)).collect();
+        cache.insert(
+            key,
+            OptimizerFeatures::default(),
+            dependencies,
+            key,
+            max_entries,
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_lru_eviction() {
+        let mut cache = TestCache::default();
+        insert(&mut cache, "a", &[], 2);
+        insert(&mut cache, "b", &[], 2);
+        // Using `a` makes `b` the least recently used entry.
+        assert!(matches!(
+            get(&mut cache, "a"),
+            Some(CachedPeekPlan::Local("a"))
+        ));
+        insert(&mut cache, "c", &[], 2);
+        assert!(get(&mut cache, "b").is_none());
+        assert!(matches!(
+            get(&mut cache, "a"),
+            Some(CachedPeekPlan::Local("a"))
+        ));
+        assert!(matches!(
+            get(&mut cache, "c"),
+            Some(CachedPeekPlan::Local("c"))
+        ));
+
+        // Replacing an entry doesn't evict another one.
+        insert(&mut cache, "c", &[], 2);
+        assert!(get(&mut cache, "a").is_some());
+
+        // A maximum of zero disables the cache.
+        insert(&mut cache, "d", &[], 0);
+        assert!(get(&mut cache, "a").is_none());
+        assert!(get(&mut cache, "d").is_none());
+    }
+
+    #[mz_ore::test]
+    fn test_feature_mismatch() {
+        let mut cache = TestCache::default();
+        insert(&mut cache, "a", &[], 2);
+        let mut features = OptimizerFeatures::default();
+        features.enable_eager_delta_joins = !features.enable_eager_delta_joins;
+        assert!(cache.get(&"a", &features, ClusterId::User(1), 1).is_none());
+        assert!(get(&mut cache, "a").is_some());
+    }
+
+    #[mz_ore::test]
+    fn test_global_plan_context() {
+        let mut cache = TestCache::default();
+        // Global plans are only recorded for cached local plans.
+        cache.insert_global(&"a", "global a", ClusterId::User(1), 1);
+        assert!(get(&mut cache, "a").is_none());
+
+        insert(&mut cache, "a", &[], 2);
+        cache.insert_global(&"a", "global a", ClusterId::User(1), 1);
+        assert!(matches!(
+            get(&mut cache, "a"),
+            Some(CachedPeekPlan::Global("global a"))
+        ));
+
+        // Other clusters and catalog revisions fall back to the local plan.
+        let features = OptimizerFeatures::default();
+        assert!(matches!(
+            cache.get(&"a", &features, ClusterId::User(2), 1),
+            Some(CachedPeekPlan::Local("a"))
+        ));
+        assert!(matches!(
+            cache.get(&"a", &features, ClusterId::User(1), 2),
+            Some(CachedPeekPlan::Local("a"))
+        ));
+
+        // Re-inserting the local plan drops the global plan.
+        insert(&mut cache, "a", &[], 2);
+        assert!(matches!(
+            get(&mut cache, "a"),
+            Some(CachedPeekPlan::Local("a"))
+        ));
+    }
+
+    #[mz_ore::test]
+    fn test_invalidate() {
+        let mut cache = TestCache::default();
+        insert(&mut cache, "a", &[1], 3);
+        insert(&mut cache, "b", &[1, 2], 3);
+        insert(&mut cache, "c", &[3], 3);
+        let dropped = GlobalId::User(1);
+        cache.invalidate(&[&dropped].into());
+        assert!(get(&mut cache, "a").is_none());
+        assert!(get(&mut cache, "b").is_none());
+        assert!(get(&mut cache, "c").is_some());
+    }
+}
@@ -0,0 +1,696 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Tracking of cluster replica health, and automatic replacement of crash looping replicas.
+//!
+//! The health of a replica is derived from the status changes that the orchestrator reports for
+//! it, and from the responses the compute controller receives from it. Every transition of a
+//! replica from ready to not ready is counted as a crash. A replica that crashes at least
+//! `cluster_crash_loop_threshold` times within `cluster_crash_loop_window` is considered crash
+//! looping. A ready replica with introspection enabled, which reports frontiers at least once per
+//! introspection interval, is considered unresponsive if the controller hasn't heard from it for
+//! `cluster_replica_heartbeat_timeout`. Replicas also track whether they have hydrated all of
+//! their compute collections.
+//!
+//! Changes of the health or hydration status of a replica are recorded in the audit log, together
+//! with the time of the replica's last heartbeat.
+//!
+//! If `cluster_replace_crash_looping_replicas` is enabled, crash looping replicas of managed
+//! clusters are dropped and recreated with the same name and configuration. A replica that crash
+//! loops again shortly after its replacement is not replaced right away: the controller waits for
+//! `cluster_crash_loop_replacement_backoff` after the first replacement of a replica name, and
+//! doubles that wait with every consecutive replacement, up to
+//! `cluster_crash_loop_replacement_max_backoff`. Replacements are recorded in the audit log like
+//! any other replica drop and create.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use mz_controller::clusters::ClusterStatus;
+use mz_controller_types::{ClusterId, ReplicaId};
+use mz_orchestrator::NotReadyReason;
+use mz_ore::now::EpochMillis;
+use mz_ore::task;
+use mz_sql::plan::{ComputeReplicaConfig, ComputeReplicaIntrospectionConfig};
+use tracing::{info, warn};
+
+use crate::catalog::{self, ReplicaCreateDropReason};
+use crate::coord::{Coordinator, Message};
+use crate::session::Session;
+
+/// The health of a cluster replica.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplicaHealth {
+    /// All processes of the replica are ready.
+    Healthy,
+    /// At least one process of the replica is not ready.
+    Unhealthy {
+        /// The reason reported by the orchestrator, if known.
+        reason: Option<NotReadyReason>,
+    },
+    /// All processes of the replica are ready, but the controller hasn't heard from the replica
+    /// within the heartbeat timeout.
+    Unresponsive,
+    /// The replica went from ready to not ready too often within the crash loop window.
+    CrashLooping,
+}
+
+impl ReplicaHealth {
+    /// Returns the health as a kebab-case string.
+    pub fn as_kebab_case_str(&self) -> &'static str {
+        match self {
+            ReplicaHealth::Healthy => "healthy",
+            ReplicaHealth::Unhealthy { .. } => "unhealthy",
+            ReplicaHealth::Unresponsive => "unresponsive",
+            ReplicaHealth::CrashLooping => "crash-looping",
+        }
+    }
+
+    /// Returns why the replica is unhealthy, if known.
+    pub fn reason(&self) -> Option<String> {
+        match self {
+            ReplicaHealth::Unhealthy { reason } => reason.map(|reason| reason.to_string()),
+            ReplicaHealth::Healthy | ReplicaHealth::Unresponsive | ReplicaHealth::CrashLooping => {
+                None
+            }
+        }
+    }
+}
+
+/// Tracks the health of a single cluster replica.
+#[derive(Debug)]
+pub(crate) struct ReplicaHealthTracker {
+    /// The last status the orchestrator reported for the replica.
+    status: ClusterStatus,
+    /// The times at which the replica went from ready to not ready, oldest first.
+    crashes: VecDeque<DateTime<Utc>>,
+    /// Whether the replica crashed too often within the crash loop window.
+    crash_looping: bool,
+    /// Whether the controller heard from the replica within the heartbeat timeout.
+    responsive: bool,
+    /// Whether the replica has hydrated all of its compute collections.
+    hydrated: bool,
+    /// When the controller last heard from the replica, if it knows about the replica.
+    last_heartbeat: Option<EpochMillis>,
+}
+
+impl Default for ReplicaHealthTracker {
+    fn default() -> Self {
+        // Replicas start out not ready until the orchestrator tells us otherwise.
+        ReplicaHealthTracker {
+            status: ClusterStatus::NotReady(None),
+            crashes: VecDeque::new(),
+            crash_looping: false,
+            responsive: true,
+            hydrated: false,
+            last_heartbeat: None,
+        }
+    }
+}
+
+impl ReplicaHealthTracker {
+    /// Returns the current health of the replica.
+    pub fn health(&self) -> ReplicaHealth {
+        if self.crash_looping {
+            return ReplicaHealth::CrashLooping;
+        }
+        match self.status {
+            ClusterStatus::NotReady(reason) => ReplicaHealth::Unhealthy { reason },
+            ClusterStatus::Ready if !self.responsive => ReplicaHealth::Unresponsive,
+            ClusterStatus::Ready => ReplicaHealth::Healthy,
+        }
+    }
+
+    /// Returns whether the replica has hydrated all of its compute collections.
+    pub fn hydrated(&self) -> bool {
+        self.hydrated
+    }
+
+    /// Returns when the controller last heard from the replica.
+    pub fn last_heartbeat(&self) -> Option<EpochMillis> {
+        self.last_heartbeat
+    }
+
+    /// Records a change of the replica's status at `time` and returns the resulting health.
+    ///
+    /// A `threshold` of zero disables crash loop detection.
+    pub fn observe(
+        &mut self,
+        old_status: ClusterStatus,
+        new_status: ClusterStatus,
+        time: DateTime<Utc>,
+        threshold: usize,
+        window: Duration,
+    ) -> ReplicaHealth {
+        if old_status == ClusterStatus::Ready && new_status != ClusterStatus::Ready {
+            self.crashes.push_back(time);
+        }
+
+        let cutoff = chrono::Duration::from_std(window)
+            .ok()
+            .and_then(|window| time.checked_sub_signed(window));
+        if let Some(cutoff) = cutoff {
+            while self.crashes.front().is_some_and(|crash| *crash < cutoff) {
+                self.crashes.pop_front();
+            }
+        }
+
+        self.crash_looping = threshold > 0 && self.crashes.len() >= threshold;
+        self.status = new_status;
+        self.health()
+    }
+
+    /// Records that the controller last heard from the replica at `last_heartbeat`, and whether
+    /// that is within the heartbeat timeout, and returns the resulting health.
+    pub fn observe_heartbeat(
+        &mut self,
+        last_heartbeat: EpochMillis,
+        responsive: bool,
+    ) -> ReplicaHealth {
+        self.last_heartbeat = Some(last_heartbeat);
+        self.responsive = responsive;
+        self.health()
+    }
+
+    /// Records whether the replica has hydrated all of its compute collections.
+    pub fn observe_hydration(&mut self, hydrated: bool) {
+        self.hydrated = hydrated;
+    }
+}
+
+/// Tracks the replacements of crash looping replicas, to back off from replacing replicas that
+/// crash loop again soon after they were replaced.
+///
+/// Replacements are tracked by cluster and replica name, because a replacement takes over the
+/// name, but not the ID, of the replica it replaces.
+#[derive(Debug, Default)]
+pub(crate) struct CrashLoopReplacements {
+    replacements: BTreeMap<(ClusterId, String), ReplacementHistory>,
+}
+
+/// The replacements of the crash looping replicas of one name.
+#[derive(Debug)]
+struct ReplacementHistory {
+    /// The number of consecutive replacements.
+    consecutive: u32,
+    /// The time of the last replacement.
+    last: DateTime<Utc>,
+}
+
+impl ReplacementHistory {
+    /// Returns the earliest time at which a replica of this name may be replaced again.
+    ///
+    /// The first replacement is followed by a wait of `backoff`, and every consecutive
+    /// replacement doubles the wait, up to `max_backoff`.
+    fn next_allowed(&self, backoff: Duration, max_backoff: Duration) -> DateTime<Utc> {
+        let exponent = self.consecutive.saturating_sub(1).min(31);
+        let wait = backoff.saturating_mul(1 << exponent).min(max_backoff);
+        add_duration(self.last, wait)
+    }
+}
+
+/// Returns `time` plus `duration`, saturating at the maximum representable time.
+fn add_duration(time: DateTime<Utc>, duration: Duration) -> DateTime<Utc> {
+    chrono::Duration::from_std(duration)
+        .ok()
+        .and_then(|duration| time.checked_add_signed(duration))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+impl CrashLoopReplacements {
+    /// Returns how long to wait before the crash looping replica `name` of `cluster_id` may be
+    /// replaced, or `None` if it may be replaced at `now`.
+    pub fn backoff(
+        &self,
+        cluster_id: ClusterId,
+        name: &str,
+        now: DateTime<Utc>,
+        backoff: Duration,
+        max_backoff: Duration,
+    ) -> Option<Duration> {
+        let history = self.replacements.get(&(cluster_id, name.to_string()))?;
+        let next_allowed = history.next_allowed(backoff, max_backoff);
+        (now < next_allowed).then(|| {
+            (next_allowed - now)
+                .to_std()
+                .expect("next_allowed is after now")
+        })
+    }
+
+    /// Records that the crash looping replica `name` of `cluster_id` was replaced at `now`.
+    ///
+    /// The replacement is consecutive to the previous one if it happens within `window` of the
+    /// end of the previous backoff, i.e., if the previous replacement crash looped right away.
+    pub fn record(
+        &mut self,
+        cluster_id: ClusterId,
+        name: String,
+        now: DateTime<Utc>,
+        backoff: Duration,
+        max_backoff: Duration,
+        window: Duration,
+    ) {
+        let key = (cluster_id, name);
+        let consecutive = match self.replacements.get(&key) {
+            Some(history)
+                if now <= add_duration(history.next_allowed(backoff, max_backoff), window) =>
+            {
+                history.consecutive.saturating_add(1)
+            }
+            _ => 1,
+        };
+        self.replacements.insert(
+            key,
+            ReplacementHistory {
+                consecutive,
+                last: now,
+            },
+        );
+    }
+
+    /// Forgets the replacements of the replicas of `cluster_id`.
+    pub fn remove_cluster(&mut self, cluster_id: &ClusterId) {
+        self.replacements.retain(|(id, _), _| id != cluster_id);
+    }
+}
+
+impl Coordinator {
+    /// Updates the health of the specified replica after its status changed from `old_status` to
+    /// `new_status`, and replaces the replica if it started crash looping and automatic
+    /// replacement is enabled.
+    pub(crate) async fn update_replica_health(
+        &mut self,
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+        old_status: ClusterStatus,
+        new_status: ClusterStatus,
+        time: DateTime<Utc>,
+    ) {
+        let system_config = self.catalog().system_config();
+        let threshold = system_config.cluster_crash_loop_threshold();
+        let window = system_config.cluster_crash_loop_window();
+        let replace_enabled = system_config.cluster_replace_crash_looping_replicas();
+
+        let tracker = self
+            .replica_health
+            .entry((cluster_id, replica_id))
+            .or_default();
+        let old_health = tracker.health();
+        let old_hydrated = tracker.hydrated();
+        let new_health = tracker.observe(old_status, new_status, time, threshold, window);
+        if old_health == new_health {
+            return;
+        }
+        self.record_replica_health_change(cluster_id, replica_id, old_health, old_hydrated)
+            .await;
+
+        if new_health == ReplicaHealth::CrashLooping && replace_enabled {
+            self.replace_crash_looping_replica(cluster_id, replica_id, time)
+                .await;
+        }
+    }
+
+    /// Updates the heartbeat and hydration status of all cluster replicas from the state of the
+    /// compute controller.
+    ///
+    /// This method expects to be called periodically.
+    pub(crate) async fn check_replica_health(&mut self) {
+        let timeout = self
+            .catalog()
+            .system_config()
+            .cluster_replica_heartbeat_timeout();
+        let now = self.now();
+
+        // Replicas without introspection only respond when they have work to report on, so their
+        // silence says nothing about their health.
+        let replicas = self
+            .catalog()
+            .clusters()
+            .flat_map(|cluster| {
+                cluster.replicas().map(|replica| {
+                    let introspection = replica.config.compute.logging.interval.is_some();
+                    (cluster.id, replica.replica_id, introspection)
+                })
+            })
+            .collect_vec();
+
+        for (cluster_id, replica_id, introspection) in replicas {
+            let compute = &self.controller.compute;
+            let Ok(Some(last_heartbeat)) = compute.replica_last_heartbeat(cluster_id, replica_id)
+            else {
+                continue;
+            };
+            let hydrated = compute
+                .replica_hydrated(cluster_id, replica_id)
+                .ok()
+                .flatten()
+                .unwrap_or(false);
+            let silence = last_heartbeat.elapsed();
+            let responsive = !introspection || timeout.is_zero() || silence <= timeout;
+            let last_heartbeat =
+                now.saturating_sub(u64::try_from(silence.as_millis()).unwrap_or(u64::MAX));
+
+            let tracker = self
+                .replica_health
+                .entry((cluster_id, replica_id))
+                .or_default();
+            let old_health = tracker.health();
+            let old_hydrated = tracker.hydrated();
+            let new_health = tracker.observe_heartbeat(last_heartbeat, responsive);
+            tracker.observe_hydration(hydrated);
+            if old_health != new_health || old_hydrated != hydrated {
+                self.record_replica_health_change(cluster_id, replica_id, old_health, old_hydrated)
+                    .await;
+            }
+        }
+    }
+
+    /// Reports a change of the health or the hydration status of the specified replica from
+    /// `old_health` and `old_hydrated` to its current state, and records it in the audit log.
+    async fn record_replica_health_change(
+        &mut self,
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+        old_health: ReplicaHealth,
+        old_hydrated: bool,
+    ) {
+        let tracker = &self.replica_health[&(cluster_id, replica_id)];
+        let new_health = tracker.health();
+        let hydrated = tracker.hydrated();
+        let last_heartbeat = tracker.last_heartbeat();
+
+        info!(
+            %cluster_id, %replica_id, ?old_health, ?new_health, old_hydrated, hydrated,
+            "cluster replica health changed",
+        );
+        if old_health != new_health {
+            self.metrics
+                .replica_health_transitions
+                .with_label_values(&[new_health.as_kebab_case_str()])
+                .inc();
+        }
+
+        let op = catalog::Op::RecordReplicaHealth {
+            cluster_id,
+            replica_id,
+            old_health,
+            new_health,
+            hydrated,
+            last_heartbeat,
+        };
+        if let Err(e) = self.catalog_transact(None::<&Session>, vec![op]).await {
+            warn!(%cluster_id, %replica_id, "failed to record replica health change: {e}");
+        }
+    }
+
+    /// Replaces the specified replica if it is still crash looping and automatic replacement is
+    /// still enabled. Called once the backoff that delayed the replacement of the replica
+    /// expired.
+    pub(crate) async fn message_replace_crash_looping_replica(
+        &mut self,
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+    ) {
+        // Dropped replicas are no longer tracked.
+        let crash_looping = self
+            .replica_health
+            .get(&(cluster_id, replica_id))
+            .is_some_and(|tracker| tracker.health() == ReplicaHealth::CrashLooping);
+        let replace_enabled = self
+            .catalog()
+            .system_config()
+            .cluster_replace_crash_looping_replicas();
+        if crash_looping && replace_enabled {
+            let now = self.now_datetime();
+            self.replace_crash_looping_replica(cluster_id, replica_id, now)
+                .await;
+        }
+    }
+
+    /// Drops the specified replica of a managed cluster and creates a new one with the same name
+    /// and configuration in its place.
+    ///
+    /// If a replica of the same name was replaced recently, the replacement is instead retried
+    /// once the backoff expires.
+    async fn replace_crash_looping_replica(
+        &mut self,
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+        now: DateTime<Utc>,
+    ) {
+        // Unmanaged replicas are under the control of the user, so leave them alone.
+        let Some(config) = self.get_managed_cluster_config(cluster_id) else {
+            return;
+        };
+        let cluster = self.catalog().get_cluster(cluster_id);
+        let owner_id = cluster.owner_id;
+        let Some(replica) = cluster.replica(replica_id) else {
+            return;
+        };
+        let name = replica.name.clone();
+
+        let system_config = self.catalog().system_config();
+        let backoff = system_config.cluster_crash_loop_replacement_backoff();
+        let max_backoff = system_config.cluster_crash_loop_replacement_max_backoff();
+        let window = system_config.cluster_crash_loop_window();
+        if let Some(wait) =
+            self.crash_loop_replacements
+                .backoff(cluster_id, &name, now, backoff, max_backoff)
+        {
+            info!(
+                %cluster_id, %replica_id, ?wait,
+                "delaying replacement of crash looping cluster replica",
+            );
+            let internal_cmd_tx = self.internal_cmd_tx.clone();
+            task::spawn(|| "replace_crash_looping_replica", async move {
+                tokio::time::sleep(wait).await;
+                let msg = Message::ReplaceCrashLoopingReplica {
+                    cluster_id,
+                    replica_id,
+                };
+                if internal_cmd_tx.send(msg).is_err() {
+                    // If sending fails, the main thread has shutdown.
+                }
+            });
+            return;
+        }
+
+        let compute = ComputeReplicaConfig {
+            introspection: config.logging.interval.map(|interval| {
+                ComputeReplicaIntrospectionConfig {
+                    debugging: config.logging.log_logging,
+                    interval,
+                }
+            }),
+        };
+
+        let mut ops = vec![catalog::Op::DropObjects(vec![
+            catalog::DropObjectInfo::ClusterReplica((
+                cluster_id,
+                replica_id,
+                ReplicaCreateDropReason::CrashLoop,
+            )),
+        ])];
+        let new_replica_id = match self.catalog_mut().allocate_replica_id(&cluster_id).await {
+            Ok(id) => id,
+            Err(e) => {
+                warn!(%cluster_id, %replica_id, "failed to replace crash looping replica: {e}");
+                return;
+            }
+        };
+        if let Err(e) = self.create_managed_cluster_replica_op(
+            cluster_id,
+            new_replica_id,
+            name.clone(),
+            &compute,
+            &config.size,
+            &mut ops,
+            Some(config.availability_zones.as_ref()),
+            config.disk,
+            owner_id,
+            ReplicaCreateDropReason::CrashLoop,
+        ) {
+            warn!(%cluster_id, %replica_id, "failed to replace crash looping replica: {e}");
+            return;
+        }
+        if let Err(e) = self.catalog_transact(None::<&Session>, ops).await {
+            warn!(%cluster_id, %replica_id, "failed to replace crash looping replica: {e}");
+            return;
+        }
+        self.create_cluster_replicas(&[(cluster_id, new_replica_id)])
+            .await;
+        self.crash_loop_replacements
+            .record(cluster_id, name, now, backoff, max_backoff, window);
+
+        info!(
+            %cluster_id, %replica_id, %new_replica_id,
+            "replaced crash looping cluster replica",
+        );
+        self.metrics.crash_loop_replica_replacements.inc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_replica_health_crash_loop() {
+        let window = Duration::from_secs(60);
+        let at = |secs| DateTime::<Utc>::from_timestamp(secs, 0).unwrap();
+        let ready = ClusterStatus::Ready;
+        let oomed = ClusterStatus::NotReady(Some(NotReadyReason::OomKilled));
+
+        let mut tracker = ReplicaHealthTracker::default();
+        assert_eq!(
+            tracker.observe(oomed, ready, at(0), 2, window),
+            ReplicaHealth::Healthy
+        );
+        assert_eq!(
+            tracker.observe(ready, oomed, at(10), 2, window),
+            ReplicaHealth::Unhealthy {
+                reason: Some(NotReadyReason::OomKilled)
+            }
+        );
+        tracker.observe(oomed, ready, at(20), 2, window);
+        assert_eq!(
+            tracker.observe(ready, oomed, at(30), 2, window),
+            ReplicaHealth::CrashLooping
+        );
+        // Stays crash looping while the crashes are within the window.
+        assert_eq!(
+            tracker.observe(oomed, ready, at(40), 2, window),
+            ReplicaHealth::CrashLooping
+        );
+        // Recovers once the first crash falls out of the window.
+        assert_eq!(
+            tracker.observe(ready, ready, at(80), 2, window),
+            ReplicaHealth::Healthy
+        );
+
+        // A threshold of zero disables crash loop detection.
+        let mut tracker = ReplicaHealthTracker::default();
+        tracker.observe(oomed, ready, at(0), 0, window);
+        assert_eq!(
+            tracker.observe(ready, oomed, at(10), 0, window),
+            ReplicaHealth::Unhealthy {
+                reason: Some(NotReadyReason::OomKilled)
+            }
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_replica_health_heartbeat() {
+        let window = Duration::from_secs(60);
+        let at = |secs| DateTime::<Utc>::from_timestamp(secs, 0).unwrap();
+        let ready = ClusterStatus::Ready;
+        let not_ready = ClusterStatus::NotReady(None);
+
+        let mut tracker = ReplicaHealthTracker::default();
+        assert_eq!(tracker.last_heartbeat(), None);
+        // Missing heartbeats don't matter while the replica isn't ready anyway.
+        assert_eq!(
+            tracker.observe_heartbeat(1_000, false),
+            ReplicaHealth::Unhealthy { reason: None }
+        );
+        assert_eq!(tracker.last_heartbeat(), Some(1_000));
+        assert_eq!(
+            tracker.observe(not_ready, ready, at(0), 2, window),
+            ReplicaHealth::Unresponsive
+        );
+        assert_eq!(
+            tracker.observe_heartbeat(2_000, true),
+            ReplicaHealth::Healthy
+        );
+        assert_eq!(tracker.last_heartbeat(), Some(2_000));
+
+        // Crash loops take precedence over missing heartbeats.
+        tracker.observe(ready, not_ready, at(10), 2, window);
+        tracker.observe(not_ready, ready, at(20), 2, window);
+        tracker.observe(ready, not_ready, at(30), 2, window);
+        assert_eq!(
+            tracker.observe_heartbeat(2_000, false),
+            ReplicaHealth::CrashLooping
+        );
+
+        // Hydration is tracked independently of the health.
+        assert!(!tracker.hydrated());
+        tracker.observe_hydration(true);
+        assert!(tracker.hydrated());
+        assert_eq!(tracker.health(), ReplicaHealth::CrashLooping);
+    }
+
+    #[mz_ore::test]
+    fn test_crash_loop_replacement_backoff() {
+        let backoff = Duration::from_secs(60);
+        let max_backoff = Duration::from_secs(300);
+        let window = Duration::from_secs(600);
+        let at = |secs| DateTime::<Utc>::from_timestamp(secs, 0).unwrap();
+        let cluster_id = ClusterId::User(1);
+
+        let mut replacements = CrashLoopReplacements::default();
+        let wait = |replacements: &CrashLoopReplacements, name, secs| {
+            replacements.backoff(cluster_id, name, at(secs), backoff, max_backoff)
+        };
+        let replace = |replacements: &mut CrashLoopReplacements, name: &str, secs| {
+            replacements.record(
+                cluster_id,
+                name.to_string(),
+                at(secs),
+                backoff,
+                max_backoff,
+                window,
+            )
+        };
+
+        // Replicas that were never replaced can be replaced right away.
+        assert_eq!(wait(&replacements, "r1", 0), None);
+
+        // The first replacement is followed by the initial backoff.
+        replace(&mut replacements, "r1", 0);
+        assert_eq!(wait(&replacements, "r1", 20), Some(Duration::from_secs(40)));
+        assert_eq!(wait(&replacements, "r1", 60), None);
+        // Other replica names and clusters are not affected.
+        assert_eq!(wait(&replacements, "r2", 20), None);
+        assert_eq!(
+            replacements.backoff(ClusterId::User(2), "r1", at(20), backoff, max_backoff),
+            None
+        );
+
+        // Consecutive replacements double the backoff, up to the maximum.
+        replace(&mut replacements, "r1", 60);
+        assert_eq!(
+            wait(&replacements, "r1", 60),
+            Some(Duration::from_secs(120))
+        );
+        replace(&mut replacements, "r1", 180);
+        assert_eq!(
+            wait(&replacements, "r1", 180),
+            Some(Duration::from_secs(240))
+        );
+        replace(&mut replacements, "r1", 420);
+        assert_eq!(
+            wait(&replacements, "r1", 420),
+            Some(Duration::from_secs(300))
+        );
+
+        // A replacement long after the previous backoff expired starts over.
+        replace(&mut replacements, "r1", 2_000);
+        assert_eq!(
+            wait(&replacements, "r1", 2_000),
+            Some(Duration::from_secs(60))
+        );
+
+        // Dropping the cluster forgets its replacements.
+        replacements.remove_cluster(&cluster_id);
+        assert_eq!(wait(&replacements, "r1", 2_000), None);
+    }
+}
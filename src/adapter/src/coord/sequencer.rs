@@ -15,25 +15,28 @@
 use futures::future::LocalBoxFuture;
 use futures::FutureExt;
 use inner::return_if_err;
+use mz_catalog::memory::objects::CatalogItem;
 use mz_expr::{MirRelationExpr, RowSetFinishing};
 use mz_ore::tracing::OpenTelemetryContext;
-use mz_repr::{Diff, GlobalId, RowCollection};
+use mz_repr::{Datum, Diff, GlobalId, Row, RowArena, RowCollection};
 use mz_sql::catalog::CatalogError;
 use mz_sql::names::ResolvedIds;
 use mz_sql::plan::{
-    self, AbortTransactionPlan, CommitTransactionPlan, CreateRolePlan, CreateSourcePlanBundle,
-    FetchPlan, MutationKind, Params, Plan, PlanKind, RaisePlan,
+    self, AbortTransactionPlan, AlterClusterPlanStrategy, CommitTransactionPlan, CreateRolePlan,
+    CreateSourcePlanBundle, FetchPlan, MutationKind, Params, Plan, PlanKind, RaisePlan, TableCheck,
 };
 use mz_sql::rbac;
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql_parser::ast::{Raw, Statement};
 use mz_storage_types::connections::inline::IntoInlineConnection;
+use mz_storage_types::connections::validation::ValidationStatus;
 use std::sync::Arc;
 use tokio::sync::oneshot;
 use tracing::{event, Instrument, Level, Span};
 
 use crate::catalog::Catalog;
 use crate::command::{Command, ExecuteResponse, Response};
+use crate::coord::plan_cache::PeekPlanCacheKey;
 use crate::coord::{catalog_serving, Coordinator, Message, TargetCluster};
 use crate::error::AdapterError;
 use crate::notice::AdapterNotice;
@@ -69,6 +72,7 @@ impl Coordinator {
         mut ctx: ExecuteContext,
         plan: Plan,
         resolved_ids: ResolvedIds,
+        plan_cache_key: Option<PeekPlanCacheKey>,
     ) -> LocalBoxFuture<'_, ()> {
         async move {
             let responses = ExecuteResponse::generated_from(&PlanKind::from(&plan));
@@ -114,6 +118,20 @@ impl Coordinator {
                 }
             }
 
+            if let (Some(cluster_id), Some(cluster_name)) =
+                (target_cluster_id, &target_cluster_name)
+            {
+                if let Err(e) = catalog_serving::check_role_cluster_restrictions(
+                    cluster_id,
+                    cluster_name,
+                    &session_catalog,
+                    ctx.session(),
+                    &plan,
+                ) {
+                    return ctx.retire(Err(e));
+                }
+            }
+
             if let Err(e) = rbac::check_plan(
                 &session_catalog,
                 &self
@@ -253,6 +271,10 @@ impl Coordinator {
                     let result = self.sequence_reset_variable(ctx.session_mut(), plan);
                     ctx.retire(result);
                 }
+                Plan::ResetAllVariables => {
+                    let result = self.sequence_reset_all_variables(ctx.session_mut());
+                    ctx.retire(result);
+                }
                 Plan::SetTransaction(plan) => {
                     let result = self.sequence_set_transaction(ctx.session_mut(), plan);
                     ctx.retire(result);
@@ -265,11 +287,23 @@ impl Coordinator {
                         ctx.session()
                             .add_notice(AdapterNotice::ExistingTransactionInProgress);
                     }
-                    let result = ctx.session_mut().start_transaction(
-                        self.now_datetime(),
-                        plan.access,
-                        plan.isolation_level,
-                    );
+                    let as_of = plan
+                        .as_of
+                        .map(|as_of| {
+                            Coordinator::evaluate_when(self.catalog().state(), as_of, ctx.session())
+                        })
+                        .transpose();
+                    let result = as_of.and_then(|as_of| {
+                        ctx.session_mut().start_transaction(
+                            self.now_datetime(),
+                            plan.access,
+                            plan.isolation_level,
+                        )?;
+                        match as_of {
+                            Some(as_of) => ctx.session_mut().pin_transaction_as_of(as_of),
+                            None => Ok(()),
+                        }
+                    });
                     ctx.retire(result.map(|_| ExecuteResponse::StartedTransaction))
                 }
                 Plan::CommitTransaction(CommitTransactionPlan {
@@ -296,7 +330,8 @@ impl Coordinator {
                     self.sequence_end_transaction(ctx, action).await;
                 }
                 Plan::Select(plan) => {
-                    self.sequence_peek(ctx, plan, target_cluster).await;
+                    self.sequence_peek(ctx, plan, target_cluster, plan_cache_key)
+                        .await;
                 }
                 Plan::Subscribe(plan) => {
                     self.sequence_subscribe(ctx, plan, target_cluster).await;
@@ -308,7 +343,7 @@ impl Coordinator {
                     ctx.retire(Ok(Self::send_immediate_rows(plan.row)));
                 }
                 Plan::ShowColumns(show_columns_plan) => {
-                    self.sequence_peek(ctx, show_columns_plan.select_plan, target_cluster)
+                    self.sequence_peek(ctx, show_columns_plan.select_plan, target_cluster, None)
                         .await;
                 }
                 Plan::CopyFrom(plan) => {
@@ -347,10 +382,15 @@ impl Coordinator {
                 Plan::AlterNoop(plan) => {
                     ctx.retire(Ok(ExecuteResponse::AlteredObject(plan.object_type)));
                 }
-                Plan::AlterCluster(plan) => {
-                    let result = self.sequence_alter_cluster(ctx.session(), plan).await;
-                    ctx.retire(result);
-                }
+                Plan::AlterCluster(plan) => match plan.strategy {
+                    AlterClusterPlanStrategy::None => {
+                        let result = self.sequence_alter_cluster(ctx.session(), plan).await;
+                        ctx.retire(result);
+                    }
+                    AlterClusterPlanStrategy::Graceful => {
+                        self.sequence_alter_cluster_graceful(ctx, plan).await;
+                    }
+                },
                 Plan::AlterClusterRename(plan) => {
                     let result = self
                         .sequence_alter_cluster_rename(ctx.session_mut(), plan)
@@ -373,8 +413,7 @@ impl Coordinator {
                     self.sequence_alter_connection(ctx, plan).await;
                 }
                 Plan::AlterSetCluster(plan) => {
-                    let result = self.sequence_alter_set_cluster(ctx.session(), plan).await;
-                    ctx.retire(result);
+                    self.sequence_alter_set_cluster(ctx, plan).await;
                 }
                 Plan::AlterRetainHistory(plan) => {
                     let result = self
@@ -409,6 +448,10 @@ impl Coordinator {
                     let result = self.sequence_alter_role(ctx.session_mut(), plan).await;
                     ctx.retire(result);
                 }
+                Plan::AlterDatabase(plan) => {
+                    let result = self.sequence_alter_database(ctx.session_mut(), plan).await;
+                    ctx.retire(result);
+                }
                 Plan::AlterSecret(plan) => {
                     self.sequence_alter_secret(ctx, plan).await;
                 }
@@ -441,8 +484,8 @@ impl Coordinator {
                     let ret = if let TransactionStatus::Started(_) = ctx.session().transaction() {
                         self.clear_transaction(ctx.session_mut()).await;
                         self.drop_temp_items(ctx.session().conn_id()).await;
-                        ctx.session_mut().reset();
-                        Ok(ExecuteResponse::DiscardedAll)
+                        let params = ctx.session_mut().reset();
+                        Ok(ExecuteResponse::DiscardedAll { params })
                     } else {
                         Err(AdapterError::OperationProhibitsTransaction(
                             "DISCARD ALL".into(),
@@ -570,10 +613,32 @@ impl Coordinator {
                     let current_storage_configuration = self.controller.storage.config().clone();
                     mz_ore::task::spawn(|| "coord::validate_connection", async move {
                         let res = match connection
-                            .validate(plan.id, &current_storage_configuration)
+                            .validate_checks(plan.id, &current_storage_configuration)
                             .await
                         {
-                            Ok(()) => Ok(ExecuteResponse::ValidatedConnection),
+                            Ok(results) => {
+                                let rows: Vec<_> = results
+                                    .into_iter()
+                                    .map(|result| {
+                                        let (status, detail) = match &result.status {
+                                            ValidationStatus::Ok => ("ok", None),
+                                            ValidationStatus::Skipped(reason) => {
+                                                ("skipped", Some(reason.as_str()))
+                                            }
+                                        };
+                                        Row::pack_slice(&[
+                                            Datum::String(result.check.as_str()),
+                                            result
+                                                .target
+                                                .as_deref()
+                                                .map_or(Datum::Null, Datum::String),
+                                            Datum::String(status),
+                                            detail.map_or(Datum::Null, Datum::String),
+                                        ])
+                                    })
+                                    .collect();
+                                Ok(Self::send_immediate_rows(rows))
+                            }
                             Err(err) => Err(err.into()),
                         };
                         ctx.retire(res);
@@ -690,9 +755,15 @@ impl Coordinator {
         constants: MirRelationExpr,
     ) -> Result<ExecuteResponse, AdapterError> {
         // Insert can be queued, so we need to re-verify the id exists.
-        let desc = match catalog.try_get_entry(&id) {
-            Some(table) => {
-                table.desc(&catalog.resolve_full_name(table.name(), Some(session.conn_id())))?
+        let (desc, relation, checks) = match catalog.try_get_entry(&id) {
+            Some(entry) => {
+                let desc = entry
+                    .desc(&catalog.resolve_full_name(entry.name(), Some(session.conn_id())))?;
+                let checks = match entry.item() {
+                    CatalogItem::Table(table) => table.checks.as_slice(),
+                    _ => &[],
+                };
+                (desc, &entry.name().item, checks)
             }
             None => {
                 return Err(AdapterError::Catalog(mz_catalog::memory::error::Error {
@@ -710,6 +781,7 @@ impl Coordinator {
                     for (i, datum) in row.iter().enumerate() {
                         desc.constraints_met(i, &datum)?;
                     }
+                    check_constraints_met(relation, checks, row)?;
                 }
                 let diffs_plan = plan::SendDiffsPlan {
                     id,
@@ -784,7 +856,7 @@ impl Coordinator {
                 offset: 0,
                 project: (0..plan.returning[0].0.iter().count()).collect(),
             };
-            let max_returned_query_size = session.vars().max_query_result_size();
+            let max_returned_query_size = session.vars().effective_max_query_result_size();
 
             return match finishing.finish(
                 RowCollection::new(&plan.returning),
@@ -802,3 +874,27 @@ impl Coordinator {
         })
     }
 }
+
+/// Verifies that `row`, written to the table named `relation`, satisfies the
+/// table's `CHECK` constraints.
+pub(crate) fn check_constraints_met(
+    relation: &str,
+    checks: &[TableCheck],
+    row: &Row,
+) -> Result<(), AdapterError> {
+    if checks.is_empty() {
+        return Ok(());
+    }
+    let arena = RowArena::new();
+    let datums: Vec<_> = row.iter().collect();
+    for check in checks {
+        // Like in PostgreSQL, a condition that evaluates to `NULL` is satisfied.
+        if check.expr.eval(&datums, &arena)? == Datum::False {
+            return Err(AdapterError::CheckViolation {
+                relation: relation.to_string(),
+                constraint: check.name.clone(),
+            });
+        }
+    }
+    Ok(())
+}
@@ -7,29 +7,402 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use mz_sql::plan::AlterSetClusterPlan;
+//! Moving compute objects between clusters.
+//!
+//! Moving a materialized view happens in three steps: the dataflow is first
+//! re-optimized against the target cluster and installed there next to the
+//! existing dataflow, both writing to the same persist shard. Once the new
+//! dataflow has hydrated on at least one replica of the target cluster, the
+//! catalog entry is atomically updated to point at the target cluster and the
+//! old dataflow is dropped.
 
-use crate::coord::Coordinator;
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use maplit::btreeset;
+use mz_catalog::memory::objects::CatalogItem;
+use mz_compute_types::ComputeInstanceId;
+use mz_ore::instrument;
+use mz_repr::optimize::OverrideFrom;
+use mz_repr::GlobalId;
+use mz_sql::catalog::ObjectType;
+use mz_sql::plan::{self, AlterSetClusterPlan};
+use mz_sql::session::metadata::SessionMetadata;
+use timely::progress::Antichain;
+use tracing::{Instrument, Span};
+
+use crate::coord::sequencer::inner::return_if_err;
+use crate::coord::{
+    AlterSetClusterOptimize, AlterSetClusterShip, AlterSetClusterStage,
+    AlterSetClusterWaitForHydration, Coordinator, Message, PlanValidity, StageResult, Staged,
+};
+use crate::error::AdapterError;
+use crate::optimize::dataflows::dataflow_import_id_bundle;
+use crate::optimize::{self, Optimize};
 use crate::session::Session;
-use crate::{AdapterError, ExecuteResponse};
+use crate::util::ResultExt;
+use crate::{catalog, ExecuteContext, ExecuteResponse};
+
+/// How often to check whether the dataflow on the target cluster has hydrated.
+const HYDRATION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+impl Staged for AlterSetClusterStage {
+    fn validity(&mut self) -> &mut PlanValidity {
+        match self {
+            AlterSetClusterStage::Optimize(stage) => &mut stage.validity,
+            AlterSetClusterStage::Ship(stage) => &mut stage.validity,
+            AlterSetClusterStage::WaitForHydration(stage) => &mut stage.validity,
+        }
+    }
+
+    async fn stage(
+        self,
+        coord: &mut Coordinator,
+        ctx: &mut ExecuteContext,
+    ) -> Result<StageResult<Box<Self>>, AdapterError> {
+        match self {
+            AlterSetClusterStage::Optimize(stage) => coord.alter_set_cluster_optimize(stage),
+            AlterSetClusterStage::Ship(stage) => coord.alter_set_cluster_ship(stage).await,
+            AlterSetClusterStage::WaitForHydration(stage) => {
+                coord
+                    .alter_set_cluster_wait_for_hydration(ctx.session(), stage)
+                    .await
+            }
+        }
+    }
+
+    fn message(self, ctx: ExecuteContext, span: Span) -> Message {
+        Message::AlterSetClusterStageReady {
+            ctx,
+            span,
+            stage: self,
+        }
+    }
+
+    fn cancel_enabled(&self) -> bool {
+        // Once the new dataflow has been shipped, abandoning the statement would leak it on the
+        // target cluster, so only the optimization stage can be canceled.
+        matches!(self, AlterSetClusterStage::Optimize(_))
+    }
+}
 
 impl Coordinator {
-    /// Convert a [`AlterSetClusterPlan`] to a sequence of catalog operators and adjust state.
+    /// Moves the object identified in the [`AlterSetClusterPlan`] to the target cluster.
+    #[instrument]
     pub(super) async fn sequence_alter_set_cluster(
         &mut self,
-        _session: &Session,
-        AlterSetClusterPlan { id, set_cluster: _ }: AlterSetClusterPlan,
-    ) -> Result<ExecuteResponse, AdapterError> {
-        // TODO: This function needs to be implemented.
-
-        // Satisfy Clippy that this is an async func.
-        async {}.await;
-        let entry = self.catalog().get_entry(&id);
-        match entry.item().typ() {
-            _ => {
-                // Unexpected; planner permitted unsupported plan.
-                Err(AdapterError::Unsupported("ALTER SET CLUSTER"))
+        ctx: ExecuteContext,
+        plan: AlterSetClusterPlan,
+    ) {
+        let stage = return_if_err!(self.alter_set_cluster_validate(ctx.session(), plan), ctx);
+        self.sequence_staged(ctx, Span::current(), stage).await;
+    }
+
+    fn alter_set_cluster_validate(
+        &self,
+        session: &Session,
+        plan: AlterSetClusterPlan,
+    ) -> Result<AlterSetClusterStage, AdapterError> {
+        let entry = self.catalog().get_entry(&plan.id);
+        let CatalogItem::MaterializedView(mview) = entry.item() else {
+            // Unexpected; planner permitted unsupported plan.
+            return Err(AdapterError::Unsupported("ALTER SET CLUSTER"));
+        };
+
+        let mut dependency_ids = mview.resolved_ids.0.clone();
+        dependency_ids.insert(plan.id);
+        let validity = PlanValidity {
+            transient_revision: self.catalog().transient_revision(),
+            dependency_ids,
+            cluster_id: Some(plan.set_cluster),
+            replica_id: None,
+            role_metadata: session.role_metadata().clone(),
+        };
+
+        Ok(AlterSetClusterStage::Optimize(AlterSetClusterOptimize {
+            validity,
+            old_cluster: mview.cluster_id,
+            plan,
+        }))
+    }
+
+    #[instrument]
+    fn alter_set_cluster_optimize(
+        &mut self,
+        AlterSetClusterOptimize {
+            validity,
+            plan,
+            old_cluster,
+        }: AlterSetClusterOptimize,
+    ) -> Result<StageResult<Box<AlterSetClusterStage>>, AdapterError> {
+        let entry = self.catalog().get_entry(&plan.id);
+        let CatalogItem::MaterializedView(mview) = entry.item() else {
+            return Err(AdapterError::ChangedPlan(format!(
+                "materialized view {} was replaced",
+                plan.id
+            )));
+        };
+
+        // Collect optimizer parameters.
+        let compute_instance = self
+            .instance_snapshot(plan.set_cluster)
+            .expect("compute instance does not exist");
+        let view_id = self.allocate_transient_id();
+        let debug_name = self
+            .catalog()
+            .resolve_full_name(entry.name(), None)
+            .to_string();
+        let optimizer_config = optimize::OptimizerConfig::from(self.catalog().system_config())
+            .override_from(&self.catalog.get_cluster(plan.set_cluster).config.features());
+
+        // Build an optimizer for the materialized view, targeting the new cluster but reusing the
+        // existing sink id so the new dataflow writes to the same storage collection.
+        let mut optimizer = optimize::materialized_view::Optimizer::new(
+            self.owned_catalog(),
+            compute_instance,
+            plan.id,
+            view_id,
+            mview.desc.iter_names().cloned().collect(),
+            mview.non_null_assertions.clone(),
+            mview.append_only,
+            mview.refresh_schedule.clone(),
+            debug_name,
+            optimizer_config,
+            self.optimizer_metrics(),
+        );
+        let raw_expr = mview.raw_expr.clone();
+
+        let span = Span::current();
+        Ok(StageResult::Handle(mz_ore::task::spawn_blocking(
+            || "optimize alter set cluster",
+            move || {
+                span.in_scope(|| {
+                    // HIR ⇒ MIR lowering and MIR ⇒ MIR optimization (local and global)
+                    let local_mir_plan = optimizer.catch_unwind_optimize(raw_expr)?;
+                    let global_mir_plan = optimizer.catch_unwind_optimize(local_mir_plan)?;
+                    // MIR ⇒ LIR lowering and LIR ⇒ LIR optimization (global)
+                    let global_lir_plan =
+                        optimizer.catch_unwind_optimize(global_mir_plan.clone())?;
+
+                    let stage = AlterSetClusterStage::Ship(AlterSetClusterShip {
+                        validity,
+                        plan,
+                        old_cluster,
+                        global_mir_plan,
+                        global_lir_plan,
+                    });
+                    Ok(Box::new(stage))
+                })
+            },
+        )))
+    }
+
+    #[instrument]
+    async fn alter_set_cluster_ship(
+        &mut self,
+        AlterSetClusterShip {
+            mut validity,
+            plan,
+            old_cluster,
+            global_mir_plan,
+            global_lir_plan,
+        }: AlterSetClusterShip,
+    ) -> Result<StageResult<Box<AlterSetClusterStage>>, AdapterError> {
+        let id = plan.id;
+        let cluster_id = plan.set_cluster;
+
+        let CatalogItem::MaterializedView(mview) = self.catalog().get_entry(&id).item() else {
+            return Err(AdapterError::ChangedPlan(format!(
+                "materialized view {id} was replaced"
+            )));
+        };
+        if mview.cluster_id != old_cluster
+            || self.controller.compute.collection(cluster_id, id).is_ok()
+        {
+            return Err(AdapterError::ChangedPlan(format!(
+                "materialized view {id} was concurrently moved to another cluster"
+            )));
+        }
+        let initial_as_of = mview.initial_as_of.clone();
+        // If the refresh schedule has a last refresh, no data can be produced beyond it.
+        let until = Antichain::from_iter(
+            mview
+                .refresh_schedule
+                .as_ref()
+                .and_then(|s| s.last_refresh())
+                .and_then(|r| r.try_step_forward()),
+        );
+
+        // Timestamp selection. The persist sink of the new dataflow only writes out the updates
+        // the shard is missing, so it can start at the earliest readable time of its inputs.
+        let id_bundle = dataflow_import_id_bundle(global_lir_plan.df_desc(), cluster_id);
+        let read_holds = self.acquire_read_holds(&id_bundle);
+        let as_of = read_holds.least_valid_read();
+
+        let optimized_plan = global_mir_plan.df_desc().clone();
+        let physical_plan = global_lir_plan.df_desc().clone();
+        let (mut df_desc, df_meta) = global_lir_plan.unapply();
+        df_desc.set_as_of(as_of);
+        if let Some(initial_as_of) = initial_as_of {
+            df_desc.set_initial_as_of(initial_as_of);
+        }
+        df_desc.until = until;
+
+        self.ship_dataflow(df_desc, cluster_id).await;
+        drop(read_holds);
+
+        // From here on, the checks of `PlanValidity` are performed in
+        // `alter_set_cluster_wait_for_hydration`, so that the new dataflow is
+        // cleaned up if the plan becomes invalid.
+        validity.dependency_ids = BTreeSet::new();
+        validity.cluster_id = None;
+
+        Ok(StageResult::Immediate(Box::new(
+            AlterSetClusterStage::WaitForHydration(AlterSetClusterWaitForHydration {
+                validity,
+                plan,
+                old_cluster,
+                optimized_plan,
+                physical_plan,
+                df_meta,
+            }),
+        )))
+    }
+
+    #[instrument]
+    async fn alter_set_cluster_wait_for_hydration(
+        &mut self,
+        session: &Session,
+        stage: AlterSetClusterWaitForHydration,
+    ) -> Result<StageResult<Box<AlterSetClusterStage>>, AdapterError> {
+        let id = stage.plan.id;
+        let cluster_id = stage.plan.set_cluster;
+
+        if !self.controller.compute.instance_exists(cluster_id) {
+            // Dropping the cluster also dropped the new dataflow.
+            return Err(AdapterError::ChangedPlan(format!(
+                "cluster {cluster_id} was removed"
+            )));
+        }
+        let current_cluster = self
+            .catalog()
+            .try_get_entry(&id)
+            .and_then(|entry| entry.item().cluster_id());
+        if current_cluster != Some(stage.old_cluster) {
+            if current_cluster != Some(cluster_id) {
+                self.drop_alter_set_cluster_dataflow(cluster_id, id);
             }
+            return Err(AdapterError::ChangedPlan(format!(
+                "materialized view {id} was dropped or moved to another cluster"
+            )));
+        }
+
+        // A cluster without replicas cannot hydrate anything, so there is nothing to wait for.
+        let has_replicas = self
+            .catalog()
+            .get_cluster(cluster_id)
+            .replicas()
+            .next()
+            .is_some();
+        let hydrated = self
+            .controller
+            .compute
+            .collection_hydrated(cluster_id, id)
+            .expect("dataflow was shipped");
+        if has_replicas && !hydrated {
+            let span = Span::current();
+            return Ok(StageResult::Handle(mz_ore::task::spawn(
+                || "alter set cluster wait for hydration",
+                async move {
+                    tokio::time::sleep(HYDRATION_POLL_INTERVAL).await;
+                    Ok(Box::new(AlterSetClusterStage::WaitForHydration(stage)))
+                }
+                .instrument(span),
+            )));
+        }
+
+        self.alter_set_cluster_finish(session, stage).await
+    }
+
+    #[instrument]
+    async fn alter_set_cluster_finish(
+        &mut self,
+        session: &Session,
+        AlterSetClusterWaitForHydration {
+            plan: plan::AlterSetClusterPlan { id, set_cluster },
+            old_cluster,
+            optimized_plan,
+            physical_plan,
+            df_meta,
+            ..
+        }: AlterSetClusterWaitForHydration,
+    ) -> Result<StageResult<Box<AlterSetClusterStage>>, AdapterError> {
+        let ops = vec![catalog::Op::AlterSetCluster {
+            id,
+            cluster: set_cluster,
+        }];
+
+        // Pre-allocate a vector of transient GlobalIds for each notice.
+        let notice_ids = std::iter::repeat_with(|| self.allocate_transient_id())
+            .take(df_meta.optimizer_notices.len())
+            .collect::<Vec<_>>();
+
+        let transact_result = self
+            .catalog_transact_with_side_effects(Some(session), ops, |coord| async {
+                // Replace the plan structures with those of the new dataflow.
+                let dropped_notices = coord.catalog_mut().drop_plans_and_metainfos(&btreeset![id]);
+                coord.catalog_mut().set_optimized_plan(id, optimized_plan);
+                coord.catalog_mut().set_physical_plan(id, physical_plan);
+
+                coord.emit_optimizer_notices(session, &df_meta.optimizer_notices);
+                let df_meta = coord
+                    .catalog()
+                    .render_notices(df_meta, notice_ids, Some(id));
+                coord
+                    .catalog_mut()
+                    .set_dataflow_metainfo(id, df_meta.clone());
+
+                if coord.catalog().state().system_config().enable_mz_notices() {
+                    let mut builtin_table_updates = Vec::new();
+                    coord.catalog().state().pack_optimizer_notices(
+                        &mut builtin_table_updates,
+                        dropped_notices.iter(),
+                        -1,
+                    );
+                    coord.catalog().state().pack_optimizer_notices(
+                        &mut builtin_table_updates,
+                        df_meta.optimizer_notices.iter(),
+                        1,
+                    );
+                    coord
+                        .builtin_table_update()
+                        .execute(builtin_table_updates)
+                        .await
+                        .await;
+                }
+
+                coord.drop_alter_set_cluster_dataflow(old_cluster, id);
+            })
+            .await;
+
+        if let Err(err) = transact_result {
+            self.drop_alter_set_cluster_dataflow(set_cluster, id);
+            return Err(err);
+        }
+
+        Ok(StageResult::Response(ExecuteResponse::AlteredObject(
+            ObjectType::MaterializedView,
+        )))
+    }
+
+    /// Drops the materialized view dataflow `id` installed on `cluster_id`, if the cluster still
+    /// exists.
+    fn drop_alter_set_cluster_dataflow(&mut self, cluster_id: ComputeInstanceId, id: GlobalId) {
+        let compute = &mut self.controller.compute;
+        if compute.instance_exists(cluster_id) {
+            compute
+                .drop_collections(cluster_id, vec![id])
+                .unwrap_or_terminate("cannot fail to drop collections");
         }
     }
 }
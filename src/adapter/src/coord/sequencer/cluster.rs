@@ -10,6 +10,7 @@
 //! Coordinator functionality to sequence cluster-related plans
 
 use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
 
 use mz_adapter_types::compaction::CompactionWindow;
 use mz_catalog::memory::objects::{ClusterConfig, ClusterVariant, ClusterVariantManaged};
@@ -30,11 +31,23 @@ use mz_sql::plan::{
 };
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql::session::vars::{SystemVars, Var, MAX_REPLICAS_PER_CLUSTER};
+use mz_sql_parser::ast::{Ident, QualifiedReplica};
+use tracing::{Instrument, Span};
 
 use crate::catalog::{Op, ReplicaCreateDropReason};
-use crate::coord::Coordinator;
+use crate::coord::sequencer::inner::return_if_err;
+use crate::coord::{
+    AlterClusterStage, AlterClusterWaitForHydrated, Coordinator, Message, PlanValidity,
+    StageResult, Staged,
+};
 use crate::session::Session;
-use crate::{catalog, AdapterError, ExecuteResponse};
+use crate::{catalog, AdapterError, ExecuteContext, ExecuteResponse};
+
+/// How often to check whether the new replicas of a graceful `ALTER CLUSTER` have caught up.
+const HYDRATION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The suffix of the names of replicas created by a graceful `ALTER CLUSTER`.
+const PENDING_REPLICA_SUFFIX: &str = "-pending";
 
 impl Coordinator {
     #[mz_ore::instrument(level = "debug")]
@@ -69,6 +82,7 @@ impl Coordinator {
                     disk: plan.disk,
                     optimizer_feature_overrides: plan.optimizer_feature_overrides.clone(),
                     schedule: plan.schedule.clone(),
+                    autoscale: plan.autoscale.clone(),
                 })
             }
             CreateClusterVariant::Unmanaged(_) => ClusterVariant::Unmanaged,
@@ -108,6 +122,7 @@ impl Coordinator {
             disk,
             optimizer_feature_overrides: _,
             schedule: _,
+            autoscale,
         }: CreateClusterManagedPlan,
         cluster_id: ClusterId,
         mut ops: Vec<catalog::Op>,
@@ -123,6 +138,7 @@ impl Coordinator {
                 .get_role_allowed_cluster_sizes(&Some(role_id)),
             &size,
         )?;
+        self.ensure_valid_cluster_autoscale(&size, autoscale.as_ref(), Some(role_id))?;
 
         // Eagerly validate the `max_replicas_per_cluster` limit.
         // `catalog_transact` will do this validation too, but allocating
@@ -163,7 +179,7 @@ impl Coordinator {
         Ok(ExecuteResponse::CreatedCluster)
     }
 
-    fn create_managed_cluster_replica_op(
+    pub(crate) fn create_managed_cluster_replica_op(
         &mut self,
         cluster_id: ClusterId,
         id: ReplicaId,
@@ -543,12 +559,61 @@ impl Coordinator {
             id: cluster_id,
             name: _,
             options,
+            strategy: _,
         }: AlterClusterPlan,
     ) -> Result<ExecuteResponse, AdapterError> {
         use mz_catalog::memory::objects::ClusterVariant::*;
 
         let config = self.catalog.get_cluster(cluster_id).config.clone();
-        let mut new_config = config.clone();
+        let new_config = self.alter_cluster_config(cluster_id, &options)?;
+
+        if new_config == config {
+            return Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster));
+        }
+
+        match (&config.variant, new_config.variant) {
+            (Managed(config), Managed(new_config)) => {
+                self.sequence_alter_cluster_managed_to_managed(
+                    Some(session),
+                    cluster_id,
+                    config,
+                    new_config,
+                    ReplicaCreateDropReason::Manual,
+                )
+                .await?;
+            }
+            (Unmanaged, Managed(new_config)) => {
+                self.sequence_alter_cluster_unmanaged_to_managed(
+                    session, cluster_id, new_config, options,
+                )
+                .await?;
+            }
+            (Managed(_), Unmanaged) => {
+                self.sequence_alter_cluster_managed_to_unmanaged(session, cluster_id)
+                    .await?;
+            }
+            (Unmanaged, Unmanaged) => {
+                self.sequence_alter_cluster_unmanaged_to_unmanaged(
+                    session,
+                    cluster_id,
+                    options.replicas,
+                )?;
+            }
+        }
+
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster))
+    }
+
+    /// Returns the configuration of the identified cluster after applying `options` to it.
+    fn alter_cluster_config(
+        &self,
+        cluster_id: ClusterId,
+        options: &PlanClusterOption,
+    ) -> Result<ClusterConfig, AdapterError> {
+        use mz_catalog::memory::objects::ClusterVariant::*;
+
+        let config = self.catalog.get_cluster(cluster_id).config.clone();
+        let mut new_config = config;
 
         match (&new_config.variant, &options.managed) {
             (Managed(_), AlterOptionParameter::Reset)
@@ -576,6 +641,7 @@ impl Coordinator {
                     disk,
                     optimizer_feature_overrides: Default::default(),
                     schedule: Default::default(),
+                    autoscale: None,
                 });
             }
         }
@@ -589,6 +655,7 @@ impl Coordinator {
                 disk,
                 optimizer_feature_overrides: _,
                 schedule,
+                autoscale,
             }) => {
                 use AlterOptionParameter::*;
                 match &options.size {
@@ -628,6 +695,11 @@ impl Coordinator {
                     Reset => *schedule = Default::default(),
                     Unchanged => {}
                 }
+                match &options.autoscale {
+                    Set(new_autoscale) => *autoscale = Some(new_autoscale.clone()),
+                    Reset => *autoscale = None,
+                    Unchanged => {}
+                }
                 if !matches!(options.replicas, Unchanged) {
                     coord_bail!("Cannot change REPLICAS of managed clusters");
                 }
@@ -649,44 +721,13 @@ impl Coordinator {
                 if !matches!(options.replication_factor, Unchanged) {
                     coord_bail!("Cannot change REPLICATION FACTOR of unmanaged clusters");
                 }
+                if !matches!(options.autoscale, Unchanged) {
+                    coord_bail!("Cannot change AUTOSCALE of unmanaged clusters");
+                }
             }
         }
 
-        if new_config == config {
-            return Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster));
-        }
-
-        match (&config.variant, new_config.variant) {
-            (Managed(config), Managed(new_config)) => {
-                self.sequence_alter_cluster_managed_to_managed(
-                    Some(session),
-                    cluster_id,
-                    config,
-                    new_config,
-                    ReplicaCreateDropReason::Manual,
-                )
-                .await?;
-            }
-            (Unmanaged, Managed(new_config)) => {
-                self.sequence_alter_cluster_unmanaged_to_managed(
-                    session, cluster_id, new_config, options,
-                )
-                .await?;
-            }
-            (Managed(_), Unmanaged) => {
-                self.sequence_alter_cluster_managed_to_unmanaged(session, cluster_id)
-                    .await?;
-            }
-            (Unmanaged, Unmanaged) => {
-                self.sequence_alter_cluster_unmanaged_to_unmanaged(
-                    session,
-                    cluster_id,
-                    options.replicas,
-                )?;
-            }
-        }
-
-        Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster))
+        Ok(new_config)
     }
 
     /// When this is called by the automated cluster scheduling, `scheduling_decision_reason` should
@@ -714,6 +755,7 @@ impl Coordinator {
                 disk,
                 optimizer_feature_overrides: _,
                 schedule: _,
+                autoscale: _,
             },
             ClusterVariantManaged {
                 size: new_size,
@@ -723,6 +765,7 @@ impl Coordinator {
                 disk: new_disk,
                 optimizer_feature_overrides: _,
                 schedule: _,
+                autoscale: new_autoscale,
             },
         ) = (&config, &new_config);
 
@@ -731,6 +774,7 @@ impl Coordinator {
             &self.catalog().get_role_allowed_cluster_sizes(&role_id),
             new_size,
         )?;
+        self.ensure_valid_cluster_autoscale(new_size, new_autoscale.as_ref(), role_id)?;
 
         let mut create_cluster_replicas = vec![];
 
@@ -862,6 +906,7 @@ impl Coordinator {
             disk: new_disk,
             optimizer_feature_overrides: _,
             schedule: _,
+            autoscale: new_autoscale,
         } = &mut new_config;
 
         // Validate replication factor parameter
@@ -944,6 +989,12 @@ impl Coordinator {
             );
         }
 
+        self.ensure_valid_cluster_autoscale(
+            new_size,
+            new_autoscale.as_ref(),
+            Some(session.role_metadata().current_role),
+        )?;
+
         for i in 0..*new_replication_factor {
             let name = managed_cluster_replica_name(i);
             names.remove(&name);
@@ -1096,8 +1147,435 @@ impl Coordinator {
             Err(err) => Err(err),
         }
     }
+
+    /// Sequences an `ALTER CLUSTER .. WITH (GRACEFUL)`.
+    ///
+    /// Alterations that don't require replacing the replicas of the cluster are applied like
+    /// any other `ALTER CLUSTER`.
+    #[mz_ore::instrument(level = "debug")]
+    pub(super) async fn sequence_alter_cluster_graceful(
+        &mut self,
+        ctx: ExecuteContext,
+        plan: AlterClusterPlan,
+    ) {
+        let stage = return_if_err!(
+            self.alter_cluster_graceful_create(ctx.session(), &plan)
+                .await,
+            ctx
+        );
+        match stage {
+            Some(stage) => self.sequence_staged(ctx, Span::current(), stage).await,
+            None => {
+                let result = self.sequence_alter_cluster(ctx.session(), plan).await;
+                ctx.retire(result);
+            }
+        }
+    }
+
+    /// Creates the replicas that will replace the existing replicas of the cluster, next to the
+    /// existing replicas. Returns `None` if the alteration doesn't replace any replicas.
+    async fn alter_cluster_graceful_create(
+        &mut self,
+        session: &Session,
+        plan: &AlterClusterPlan,
+    ) -> Result<Option<AlterClusterStage>, AdapterError> {
+        let cluster_id = plan.id;
+        let config = self.catalog.get_cluster(cluster_id).config.clone();
+        let new_config = self.alter_cluster_config(cluster_id, &plan.options)?;
+
+        let (ClusterVariant::Managed(config), ClusterVariant::Managed(new_config)) =
+            (config.variant, new_config.variant)
+        else {
+            coord_bail!("GRACEFUL is only supported for managed clusters");
+        };
+        if new_config.size == config.size
+            && new_config.availability_zones == config.availability_zones
+            && new_config.logging == config.logging
+            && new_config.disk == config.disk
+        {
+            return Ok(None);
+        }
+
+        let role_id = session.role_metadata().current_role;
+        self.catalog.ensure_valid_replica_size(
+            &self
+                .catalog()
+                .get_role_allowed_cluster_sizes(&Some(role_id)),
+            &new_config.size,
+        )?;
+        self.ensure_valid_cluster_autoscale(
+            &new_config.size,
+            new_config.autoscale.as_ref(),
+            Some(role_id),
+        )?;
+        self.ensure_valid_azs(new_config.availability_zones.iter())?;
+
+        let pending_replicas = self
+            .create_pending_replicas(
+                Some(session),
+                cluster_id,
+                &new_config,
+                ReplicaCreateDropReason::Manual,
+            )
+            .await?;
+
+        // From here on, the validity of the alteration is checked in
+        // `alter_cluster_wait_for_hydrated`, so that the new replicas are cleaned up if the
+        // alteration can't complete.
+        let validity = PlanValidity {
+            transient_revision: self.catalog().transient_revision(),
+            dependency_ids: BTreeSet::new(),
+            cluster_id: None,
+            replica_id: None,
+            role_metadata: session.role_metadata().clone(),
+        };
+        let timeout = self
+            .catalog()
+            .system_config()
+            .cluster_graceful_reconfiguration_timeout();
+
+        Ok(Some(AlterClusterStage::WaitForHydrated(
+            AlterClusterWaitForHydrated {
+                validity,
+                cluster_id,
+                config,
+                new_config,
+                pending_replicas,
+                deadline: Instant::now() + timeout,
+            },
+        )))
+    }
+
+    /// Creates replicas with `new_config` next to the existing replicas of a managed cluster,
+    /// under pending names, and returns their IDs. Once they have caught up, the pending replicas
+    /// take over from the existing replicas in [`Coordinator::finish_pending_replicas`].
+    pub(crate) async fn create_pending_replicas(
+        &mut self,
+        session: Option<&Session>,
+        cluster_id: ClusterId,
+        new_config: &ClusterVariantManaged,
+        reason: ReplicaCreateDropReason,
+    ) -> Result<Vec<ReplicaId>, AdapterError> {
+        let cluster = self.catalog.get_cluster(cluster_id);
+        let owner_id = cluster.owner_id();
+        // The existing replicas stay around until the new ones have caught up, so both count
+        // against the limit in the meantime.
+        self.validate_resource_limit(
+            cluster.replicas().count(),
+            i64::from(new_config.replication_factor),
+            SystemVars::max_replicas_per_cluster,
+            "cluster replica",
+            MAX_REPLICAS_PER_CLUSTER.name(),
+        )?;
+
+        let mut ops = vec![];
+
+        // Replicas left behind by an earlier graceful alteration that didn't complete, e.g.,
+        // because `environmentd` restarted, are of no further use.
+        let stale_replicas: Vec<_> = cluster
+            .replicas()
+            .filter(|replica| replica.name.ends_with(PENDING_REPLICA_SUFFIX))
+            .map(|replica| {
+                catalog::DropObjectInfo::ClusterReplica((
+                    cluster_id,
+                    replica.replica_id,
+                    reason.clone(),
+                ))
+            })
+            .collect();
+        if !stale_replicas.is_empty() {
+            ops.push(catalog::Op::DropObjects(stale_replicas));
+        }
+
+        let compute = mz_sql::plan::ComputeReplicaConfig {
+            introspection: new_config.logging.interval.map(|interval| {
+                ComputeReplicaIntrospectionConfig {
+                    debugging: new_config.logging.log_logging,
+                    interval,
+                }
+            }),
+        };
+        let mut pending_replicas = vec![];
+        for index in 0..new_config.replication_factor {
+            let id = self.catalog_mut().allocate_replica_id(&cluster_id).await?;
+            self.create_managed_cluster_replica_op(
+                cluster_id,
+                id,
+                pending_replica_name(index),
+                &compute,
+                &new_config.size,
+                &mut ops,
+                Some(new_config.availability_zones.as_ref()),
+                new_config.disk,
+                owner_id,
+                reason.clone(),
+            )?;
+            pending_replicas.push(id);
+        }
+
+        self.catalog_transact(session, ops).await?;
+        let create_cluster_replicas: Vec<_> = pending_replicas
+            .iter()
+            .map(|replica_id| (cluster_id, *replica_id))
+            .collect();
+        self.create_cluster_replicas(&create_cluster_replicas).await;
+        Ok(pending_replicas)
+    }
+
+    #[mz_ore::instrument(level = "debug")]
+    async fn alter_cluster_wait_for_hydrated(
+        &mut self,
+        session: &Session,
+        stage: AlterClusterWaitForHydrated,
+    ) -> Result<StageResult<Box<AlterClusterStage>>, AdapterError> {
+        let cluster_id = stage.cluster_id;
+        let Some(cluster) = self.catalog().try_get_cluster(cluster_id) else {
+            // Dropping the cluster also dropped the new replicas.
+            return Err(AdapterError::ChangedPlan(format!(
+                "cluster {cluster_id} was removed"
+            )));
+        };
+        let name = cluster.name().to_string();
+
+        if let Some(changed) =
+            self.pending_replicas_conflict(cluster_id, &stage.config, &stage.pending_replicas)
+        {
+            self.drop_pending_replicas(
+                Some(session),
+                cluster_id,
+                &stage.pending_replicas,
+                ReplicaCreateDropReason::Manual,
+            )
+            .await?;
+            return Err(AdapterError::ChangedPlan(changed));
+        }
+
+        if !self.replicas_caught_up(cluster_id, &stage.pending_replicas) {
+            if Instant::now() >= stage.deadline {
+                self.drop_pending_replicas(
+                    Some(session),
+                    cluster_id,
+                    &stage.pending_replicas,
+                    ReplicaCreateDropReason::Manual,
+                )
+                .await?;
+                coord_bail!("timed out waiting for the new replicas of cluster {name} to hydrate");
+            }
+            let span = Span::current();
+            return Ok(StageResult::Handle(mz_ore::task::spawn(
+                || "alter cluster wait for hydrated",
+                async move {
+                    tokio::time::sleep(HYDRATION_POLL_INTERVAL).await;
+                    Ok(Box::new(AlterClusterStage::WaitForHydrated(stage)))
+                }
+                .instrument(span),
+            )));
+        }
+
+        self.finish_pending_replicas(
+            Some(session),
+            cluster_id,
+            &stage.config,
+            stage.new_config,
+            stage.pending_replicas,
+            ReplicaCreateDropReason::Manual,
+        )
+        .await?;
+        Ok(StageResult::Response(ExecuteResponse::AlteredObject(
+            ObjectType::Cluster,
+        )))
+    }
+
+    /// Reports why the pending replicas of a cluster that was configured with `config` when they
+    /// were created can no longer take over from its existing replicas, if they can't.
+    ///
+    /// The cluster must exist.
+    pub(crate) fn pending_replicas_conflict(
+        &self,
+        cluster_id: ClusterId,
+        config: &ClusterVariantManaged,
+        pending_replicas: &[ReplicaId],
+    ) -> Option<String> {
+        let cluster = self.catalog().get_cluster(cluster_id);
+        let name = cluster.name();
+        if cluster.config.variant != ClusterVariant::Managed(config.clone()) {
+            Some(format!("cluster {name} was concurrently altered"))
+        } else if pending_replicas
+            .iter()
+            .any(|replica_id| cluster.replica(*replica_id).is_none())
+        {
+            Some(format!("a new replica of cluster {name} was removed"))
+        } else {
+            None
+        }
+    }
+
+    /// Retires the existing replicas of the cluster, hands their names to the pending replicas,
+    /// and installs the new configuration, all in one catalog transaction.
+    ///
+    /// When this is called by the cluster autoscaler, the resize is additionally recorded as an
+    /// audit log event of its own, along with its trigger.
+    pub(crate) async fn finish_pending_replicas(
+        &mut self,
+        session: Option<&Session>,
+        cluster_id: ClusterId,
+        config: &ClusterVariantManaged,
+        new_config: ClusterVariantManaged,
+        pending_replicas: Vec<ReplicaId>,
+        reason: ReplicaCreateDropReason,
+    ) -> Result<(), AdapterError> {
+        let cluster = self.catalog.get_cluster(cluster_id);
+        let name = cluster.name().to_string();
+
+        let mut ops = vec![];
+        let old_replicas = (0..config.replication_factor)
+            .map(managed_cluster_replica_name)
+            .filter_map(|name| cluster.replica_id(&name))
+            .map(|replica_id| {
+                catalog::DropObjectInfo::ClusterReplica((cluster_id, replica_id, reason.clone()))
+            })
+            .collect();
+        ops.push(catalog::Op::DropObjects(old_replicas));
+        for (index, replica_id) in (0..).zip(pending_replicas) {
+            ops.push(catalog::Op::RenameClusterReplica {
+                cluster_id,
+                replica_id,
+                name: QualifiedReplica {
+                    cluster: Ident::new_unchecked(name.clone()),
+                    replica: Ident::new_unchecked(pending_replica_name(index)),
+                },
+                to_name: managed_cluster_replica_name(index),
+            });
+        }
+        ops.push(catalog::Op::UpdateClusterConfig {
+            id: cluster_id,
+            name: name.clone(),
+            config: ClusterConfig {
+                variant: ClusterVariant::Managed(new_config),
+            },
+        });
+        if let ReplicaCreateDropReason::Autoscaling(decision) = reason {
+            ops.push(catalog::Op::RecordClusterAutoscaling {
+                id: cluster_id,
+                name,
+                decision,
+            });
+        }
+
+        self.catalog_transact(session, ops).await
+    }
+
+    /// Reports whether the given replicas have hydrated all compute collections installed on the
+    /// cluster, and whether their write frontiers trail those of the cluster by no more than
+    /// `cluster_graceful_reconfiguration_max_lag`.
+    pub(crate) fn replicas_caught_up(
+        &self,
+        cluster_id: ClusterId,
+        replica_ids: &[ReplicaId],
+    ) -> bool {
+        let max_lag = self
+            .catalog()
+            .system_config()
+            .cluster_graceful_reconfiguration_max_lag();
+        let max_lag = u64::try_from(max_lag.as_millis()).unwrap_or(u64::MAX);
+
+        let compute = &self.controller.compute;
+        let cluster = self.catalog().get_cluster(cluster_id);
+        cluster.bound_objects().iter().all(|id| {
+            let Ok(collection) = compute.collection(cluster_id, *id) else {
+                // Storage objects don't need to hydrate on compute replicas.
+                return true;
+            };
+            replica_ids.iter().all(|replica_id| {
+                let hydrated = compute
+                    .collection_hydrated_on_replica(cluster_id, *id, *replica_id)
+                    .unwrap_or(false);
+                let Some(replica_frontier) = collection.replica_write_frontier(*replica_id) else {
+                    return false;
+                };
+                let caught_up = match (
+                    collection.write_frontier().as_option(),
+                    replica_frontier.as_option(),
+                ) {
+                    (_, None) => true,
+                    (None, Some(_)) => false,
+                    (Some(frontier), Some(replica_frontier)) => {
+                        u64::from(*replica_frontier).saturating_add(max_lag) >= u64::from(*frontier)
+                    }
+                };
+                hydrated && caught_up
+            })
+        })
+    }
+
+    /// Drops the pending replicas of a reconfiguration that can't complete.
+    pub(crate) async fn drop_pending_replicas(
+        &mut self,
+        session: Option<&Session>,
+        cluster_id: ClusterId,
+        replica_ids: &[ReplicaId],
+        reason: ReplicaCreateDropReason,
+    ) -> Result<(), AdapterError> {
+        let cluster = self.catalog.get_cluster(cluster_id);
+        let replicas: Vec<_> = replica_ids
+            .iter()
+            .filter(|replica_id| cluster.replica(**replica_id).is_some())
+            .map(|replica_id| {
+                catalog::DropObjectInfo::ClusterReplica((cluster_id, *replica_id, reason.clone()))
+            })
+            .collect();
+        if !replicas.is_empty() {
+            self.catalog_transact(session, vec![catalog::Op::DropObjects(replicas)])
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+impl Staged for AlterClusterStage {
+    fn validity(&mut self) -> &mut PlanValidity {
+        match self {
+            AlterClusterStage::WaitForHydrated(stage) => &mut stage.validity,
+        }
+    }
+
+    async fn stage(
+        self,
+        coord: &mut Coordinator,
+        ctx: &mut ExecuteContext,
+    ) -> Result<StageResult<Box<Self>>, AdapterError> {
+        match self {
+            AlterClusterStage::WaitForHydrated(stage) => {
+                coord
+                    .alter_cluster_wait_for_hydrated(ctx.session(), stage)
+                    .await
+            }
+        }
+    }
+
+    fn message(self, ctx: ExecuteContext, span: Span) -> Message {
+        Message::AlterClusterStageReady {
+            ctx,
+            span,
+            stage: self,
+        }
+    }
+
+    fn cancel_enabled(&self) -> bool {
+        // Abandoning the statement would leak the new replicas.
+        false
+    }
 }
 
 fn managed_cluster_replica_name(index: u32) -> String {
     format!("r{}", index + 1)
 }
+
+/// The name of a replica created by a graceful `ALTER CLUSTER`, until it takes over the name of
+/// the managed replica at the same index.
+fn pending_replica_name(index: u32) -> String {
+    format!(
+        "{}{PENDING_REPLICA_SUFFIX}",
+        managed_cluster_replica_name(index)
+    )
+}
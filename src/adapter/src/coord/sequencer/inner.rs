@@ -54,6 +54,7 @@ use mz_storage_types::sources::mysql::{MySqlSourceDetails, ProtoMySqlSourceDetai
 use mz_storage_types::sources::postgres::{
     PostgresSourcePublicationDetails, ProtoPostgresSourcePublicationDetails,
 };
+use mz_storage_types::sources::GenericSourceConnection;
 use prost::Message as _;
 use timely::progress::Timestamp as TimelyTimestamp;
 // Import `plan` module, but only import select elements to avoid merge conflicts on use statements.
@@ -73,7 +74,7 @@ use mz_sql::plan::{
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql::session::user::UserKind;
 use mz_sql::session::vars::{
-    self, IsolationLevel, OwnedVarInput, SessionVars, Var, VarInput, SCHEMA_ALIAS,
+    self, IsolationLevel, OwnedVarInput, SessionVars, SystemVars, Var, VarInput, SCHEMA_ALIAS,
     TRANSACTION_ISOLATION_VAR_NAME,
 };
 use mz_sql::{plan, rbac};
@@ -92,14 +93,16 @@ use mz_storage_types::controller::StorageError;
 use mz_storage_types::stats::RelationPartStats;
 use mz_storage_types::AlterCompatible;
 use mz_transform::notice::{OptimizerNoticeApi, OptimizerNoticeKind, RawOptimizerNotice};
-use mz_transform::EmptyStatisticsOracle;
+use mz_transform::{ColumnStatistics, EmptyStatisticsOracle};
 use timely::progress::Antichain;
 use tokio::sync::{oneshot, watch, OwnedMutexGuard};
 use tracing::{warn, Instrument, Span};
 
 use crate::catalog::{self, Catalog, ConnCatalog, DropObjectInfo, UpdatePrivilegeVariant};
 use crate::command::{ExecuteResponse, Response};
-use crate::coord::appends::{Deferred, DeferredPlan, PendingWriteTxn};
+use crate::coord::appends::{Deferred, DeferredPlan, PendingWriteTxn, TxnReads};
+use crate::coord::sequencer::check_constraints_met;
+use crate::coord::statistics::CollectionStatistics;
 use crate::coord::{
     AlterConnectionValidationReady, AlterSinkReadyContext, Coordinator,
     CreateConnectionValidationReady, ExecuteContext, ExplainContext, Message, PendingRead,
@@ -868,6 +871,7 @@ impl Coordinator {
             create_sql: Some(table.create_sql),
             desc: table.desc,
             defaults: table.defaults,
+            checks: table.checks,
             conn_id: conn_id.cloned(),
             resolved_ids,
             custom_logical_compaction_window: table.compaction_window,
@@ -1849,6 +1853,22 @@ impl Coordinator {
         Ok(ExecuteResponse::SetVariable { name, reset: true })
     }
 
+    pub(super) fn sequence_reset_all_variables(
+        &self,
+        session: &mut Session,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let params = session.vars_mut().reset_all();
+        // The reset is only staged, so erroring here discards it when the
+        // transaction rolls back.
+        if params.contains_key(TRANSACTION_ISOLATION_VAR_NAME) {
+            self.validate_set_isolation_level(session)?;
+        }
+        if params.contains_key(vars::CLUSTER.name()) {
+            self.validate_set_cluster(session)?;
+        }
+        Ok(ExecuteResponse::ResetAllVariables { params })
+    }
+
     pub(super) fn sequence_set_transaction(
         &self,
         session: &mut Session,
@@ -1928,6 +1948,36 @@ impl Coordinator {
                     span: Span::current(),
                     writes,
                     write_lock_guard,
+                    reads: None,
+                    pending_txn: PendingTxn {
+                        ctx,
+                        response,
+                        action,
+                    },
+                });
+                return;
+            }
+            // A read-write transaction whose writes were all empty has nothing
+            // to validate its reads against.
+            Ok((Some(TransactionOps::ReadWrite { writes, .. }), _)) if writes.is_empty() => {
+                (response, action)
+            }
+            Ok((
+                Some(TransactionOps::ReadWrite {
+                    read_ts,
+                    table_ids,
+                    writes,
+                }),
+                write_lock_guard,
+            )) => {
+                self.submit_write(PendingWriteTxn::User {
+                    span: Span::current(),
+                    writes,
+                    write_lock_guard,
+                    reads: Some(TxnReads {
+                        timestamp: read_ts,
+                        table_ids,
+                    }),
                     pending_txn: PendingTxn {
                         ctx,
                         response,
@@ -2023,7 +2073,7 @@ impl Coordinator {
         if let EndTransactionAction::Commit = action {
             if let (Some(mut ops), write_lock_guard) = txn.into_ops_and_lock_guard() {
                 match &mut ops {
-                    TransactionOps::Writes(writes) => {
+                    TransactionOps::Writes(writes) | TransactionOps::ReadWrite { writes, .. } => {
                         for WriteOp { id, .. } in &mut writes.iter() {
                             // Re-verify this id exists.
                             let _ = self.catalog().try_get_entry(id).ok_or_else(|| {
@@ -2079,7 +2129,8 @@ impl Coordinator {
                     self.active_conns.get_key_value(&connection_id)
                 {
                     // check_plan already verified role membership.
-                    self.handle_privileged_cancel(id_handle.clone()).await;
+                    self.handle_privileged_cancel(id_handle.clone(), Instant::now())
+                        .await;
                     Datum::True
                 } else {
                     Datum::False
@@ -2213,6 +2264,7 @@ impl Coordinator {
                         target_cluster,
                         None,
                         ExplainContext::Pushdown,
+                        None,
                     ),
                     ctx
                 );
@@ -2255,7 +2307,7 @@ impl Coordinator {
         mz_now: ResultSpec<'static>,
         imports: impl IntoIterator<Item = (GlobalId, MapFilterProject)>,
     ) -> impl Future<Output = Result<ExecuteResponse, AdapterError>> {
-        let explain_timeout = *session.vars().statement_timeout();
+        let explain_timeout = session.vars().effective_statement_timeout();
         let mut futures = FuturesOrdered::new();
         for (gid, mfp) in imports {
             let catalog_entry = self.catalog.get_entry(&gid);
@@ -2457,15 +2509,22 @@ impl Coordinator {
         } = plan;
 
         // Read then writes can be queued, so re-verify the id exists.
-        let desc = match self.catalog().try_get_entry(&id) {
-            Some(table) => table
-                .desc(
-                    &self
-                        .catalog()
-                        .resolve_full_name(table.name(), Some(ctx.session().conn_id())),
-                )
-                .expect("desc called on table")
-                .into_owned(),
+        let (desc, relation, checks) = match self.catalog().try_get_entry(&id) {
+            Some(table) => {
+                let desc = table
+                    .desc(
+                        &self
+                            .catalog()
+                            .resolve_full_name(table.name(), Some(ctx.session().conn_id())),
+                    )
+                    .expect("desc called on table")
+                    .into_owned();
+                let checks = match table.item() {
+                    CatalogItem::Table(table) => table.checks.clone(),
+                    _ => Vec::new(),
+                };
+                (desc, table.name().item.clone(), checks)
+            }
             None => {
                 ctx.retire(Err(AdapterError::Catalog(
                     mz_catalog::memory::error::Error {
@@ -2577,6 +2636,7 @@ impl Coordinator {
                 copy_to: None,
             },
             TargetCluster::Active,
+            None,
         )
         .await;
 
@@ -2608,7 +2668,7 @@ impl Coordinator {
                 Err(e) => return warn!("internal_cmd_rx dropped before we could send: {:?}", e),
             };
             let mut ctx = ExecuteContext::from_parts(tx, internal_cmd_tx.clone(), session, extra);
-            let mut timeout_dur = *ctx.session().vars().statement_timeout();
+            let mut timeout_dur = ctx.session().vars().effective_statement_timeout();
 
             // Timeout of 0 is equivalent to "off", meaning we will wait "forever."
             if timeout_dur == Duration::ZERO {
@@ -2657,6 +2717,7 @@ impl Coordinator {
                             for (idx, datum) in row.iter().enumerate() {
                                 desc.constraints_met(idx, &datum)?;
                             }
+                            check_constraints_met(&relation, &checks, row)?;
                         }
                     }
                     Ok(diffs)
@@ -2957,61 +3018,27 @@ impl Coordinator {
                 if let Some(inherit) = attrs.inherit {
                     attributes.inherit = inherit;
                 }
+                if let Some(scram_verifier) = attrs.scram_verifier {
+                    attributes.scram_verifier = scram_verifier;
+                }
 
                 if let Some(notice) = self.should_emit_rbac_notice(session) {
                     notices.push(notice);
                 }
             }
             PlannedAlterRoleOption::Variable(variable) => {
-                // Get the variable to make sure it's valid and visible.
-                let session_var = session.vars().inspect(variable.name())?;
-                // Return early if it's not visible.
-                session_var.visible(session.user(), Some(catalog.system_vars()))?;
-
-                // Emit a warning when deprecated variables are used.
-                // TODO(#27285) remove this after sufficient time has passed
-                if variable.name() == vars::OLD_AUTO_ROUTE_CATALOG_QUERIES {
-                    notices.push(AdapterNotice::AutoRouteIntrospectionQueriesUsage);
-                } else if let PlannedRoleVariable::Set {
-                    name,
-                    value: VariableValue::Values(vals),
-                } = &variable
-                {
-                    if name == vars::CLUSTER.name() && vals[0] == vars::OLD_CATALOG_SERVER_CLUSTER {
-                        notices.push(AdapterNotice::IntrospectionClusterUsage);
-                    }
-                }
-
-                let var_name = match variable {
-                    PlannedRoleVariable::Set { name, value } => {
-                        // Update our persisted set.
-                        match &value {
-                            VariableValue::Default => {
-                                vars.remove(&name);
-                            }
-                            VariableValue::Values(vals) => {
-                                let var = match &vals[..] {
-                                    [val] => OwnedVarInput::Flat(val.clone()),
-                                    vals => OwnedVarInput::SqlSet(vals.to_vec()),
-                                };
-                                // Make sure the input is valid.
-                                session_var.check(var.borrow())?;
-
-                                vars.insert(name.clone(), var);
-                            }
-                        };
-                        name
-                    }
-                    PlannedRoleVariable::Reset { name } => {
-                        // Remove it from our persisted values.
-                        vars.remove(&name);
-                        name
-                    }
-                };
+                let var_name = Self::apply_default_variable(
+                    session,
+                    catalog.system_vars(),
+                    variable,
+                    &mut vars,
+                    &mut notices,
+                )?;
 
                 // Emit a notice that they need to reconnect to see the change take effect.
                 notices.push(AdapterNotice::VarDefaultUpdated {
                     role: Some(name.clone()),
+                    database: None,
                     var_name: Some(var_name),
                 });
             }
@@ -3034,6 +3061,103 @@ impl Coordinator {
         Ok(response)
     }
 
+    pub(super) async fn sequence_alter_database(
+        &mut self,
+        session: &Session,
+        plan::AlterDatabasePlan { id, name, variable }: plan::AlterDatabasePlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let catalog = self.catalog().for_session(session);
+        let mut vars = catalog.get_database(&id).vars().clone();
+
+        // We'll send these notices to the user, if the operation is successful.
+        let mut notices = vec![];
+        let var_name = Self::apply_default_variable(
+            session,
+            catalog.system_vars(),
+            variable,
+            &mut vars,
+            &mut notices,
+        )?;
+        // Emit a notice that they need to reconnect to see the change take effect.
+        notices.push(AdapterNotice::VarDefaultUpdated {
+            role: None,
+            database: Some(name.clone()),
+            var_name: Some(var_name),
+        });
+
+        let op = catalog::Op::AlterDatabase {
+            id,
+            name,
+            vars: RoleVars { map: vars },
+        };
+        let response = self
+            .catalog_transact(Some(session), vec![op])
+            .await
+            .map(|_| ExecuteResponse::AlteredObject(ObjectType::Database))?;
+
+        // Send all of our queued notices.
+        session.add_notices(notices);
+
+        Ok(response)
+    }
+
+    /// Applies `variable` to the persisted default values `vars` of a role or database,
+    /// returning the name of the variable.
+    fn apply_default_variable(
+        session: &Session,
+        system_vars: &SystemVars,
+        variable: PlannedRoleVariable,
+        vars: &mut BTreeMap<String, OwnedVarInput>,
+        notices: &mut Vec<AdapterNotice>,
+    ) -> Result<String, AdapterError> {
+        // Get the variable to make sure it's valid and visible.
+        let session_var = session.vars().inspect(variable.name())?;
+        // Return early if it's not visible.
+        session_var.visible(session.user(), Some(system_vars))?;
+
+        // Emit a warning when deprecated variables are used.
+        // TODO(#27285) remove this after sufficient time has passed
+        if variable.name() == vars::OLD_AUTO_ROUTE_CATALOG_QUERIES {
+            notices.push(AdapterNotice::AutoRouteIntrospectionQueriesUsage);
+        } else if let PlannedRoleVariable::Set {
+            name,
+            value: VariableValue::Values(vals),
+        } = &variable
+        {
+            if name == vars::CLUSTER.name() && vals[0] == vars::OLD_CATALOG_SERVER_CLUSTER {
+                notices.push(AdapterNotice::IntrospectionClusterUsage);
+            }
+        }
+
+        let var_name = match variable {
+            PlannedRoleVariable::Set { name, value } => {
+                // Update our persisted set.
+                match &value {
+                    VariableValue::Default => {
+                        vars.remove(&name);
+                    }
+                    VariableValue::Values(vals) => {
+                        let var = match &vals[..] {
+                            [val] => OwnedVarInput::Flat(val.clone()),
+                            vals => OwnedVarInput::SqlSet(vals.to_vec()),
+                        };
+                        // Make sure the input is valid.
+                        session_var.check(var.borrow())?;
+
+                        vars.insert(name.clone(), var);
+                    }
+                };
+                name
+            }
+            PlannedRoleVariable::Reset { name } => {
+                // Remove it from our persisted values.
+                vars.remove(&name);
+                name
+            }
+        };
+        Ok(var_name)
+    }
+
     #[instrument]
     pub(super) async fn sequence_alter_sink_prepare(
         &mut self,
@@ -3953,6 +4077,98 @@ impl Coordinator {
                 )
                 .await;
             }
+            plan::AlterSourceAction::SwapConnection { connection_id } => {
+                const ALTER_SOURCE: &str = "ALTER SOURCE...SWAP CONNECTION";
+
+                let (mut create_source_stmt, mut resolved_ids) =
+                    create_sql_to_stmt_deps(self, ALTER_SOURCE, cur_entry.create_sql())?;
+
+                // Point the statement at the new connection.
+                let CreateSourceConnection::Kafka { connection, .. } =
+                    &mut create_source_stmt.connection
+                else {
+                    return Err(AdapterError::internal(
+                        ALTER_SOURCE,
+                        "source is not a Kafka source",
+                    ));
+                };
+                let prev_connection_id = *connection.item_id();
+                let connection_name = self.catalog().get_entry(&connection_id).name().clone();
+                *connection = ResolvedItemName::Item {
+                    id: connection_id,
+                    qualifiers: connection_name.qualifiers.clone(),
+                    full_name: self.catalog().resolve_full_name(&connection_name, None),
+                    print_id: true,
+                };
+                resolved_ids.0.remove(&prev_connection_id);
+                resolved_ids.0.insert(connection_id);
+
+                let mut catalog = self.catalog().for_system_session();
+                catalog.mark_id_unresolvable_for_replanning(cur_entry.id());
+
+                // Re-define our source in terms of the amended statement
+                let plan = match mz_sql::plan::plan(
+                    None,
+                    &catalog,
+                    Statement::CreateSource(create_source_stmt),
+                    &Params::empty(),
+                    &resolved_ids,
+                )
+                .map_err(|e| AdapterError::internal(ALTER_SOURCE, e))?
+                {
+                    Plan::CreateSource(plan) => plan,
+                    _ => unreachable!("create source plan is only valid response"),
+                };
+
+                let source = Source::new(
+                    plan,
+                    resolved_ids,
+                    cur_source.custom_logical_compaction_window,
+                    cur_source.is_retained_metrics_object,
+                );
+
+                let inline_desc = |source: &Source| match &source.data_source {
+                    DataSourceDesc::Ingestion { ingestion_desc, .. } => ingestion_desc
+                        .desc
+                        .clone()
+                        .into_inline_connection(self.catalog().state()),
+                    _ => unreachable!("already verified of type ingestion"),
+                };
+                let cur_desc = inline_desc(cur_source);
+                let desc = inline_desc(&source);
+
+                // Apart from its connection, the source must be unchanged, or its existing
+                // progress would no longer describe it.
+                let mut swapped_desc = desc.clone();
+                match (&mut swapped_desc.connection, &cur_desc.connection) {
+                    (
+                        GenericSourceConnection::Kafka(swapped),
+                        GenericSourceConnection::Kafka(cur),
+                    ) => {
+                        swapped.connection = cur.connection.clone();
+                        swapped.connection_id = cur.connection_id;
+                    }
+                    _ => unreachable!("verified to be Kafka sources"),
+                }
+                cur_desc
+                    .alter_compatible(id, &swapped_desc)
+                    .map_err(StorageError::InvalidAlter)?;
+
+                let ops = vec![catalog::Op::UpdateItem {
+                    id,
+                    name: self.catalog.get_entry(&id).name().clone(),
+                    to_item: CatalogItem::Source(source),
+                }];
+                self.catalog_transact(Some(session), ops).await?;
+
+                // Restarts the ingestion against the new connection, resuming from the
+                // source's existing progress.
+                self.controller
+                    .storage
+                    .alter_ingestion_connections(BTreeMap::from([(id, desc.connection)]))
+                    .await
+                    .unwrap_or_terminate("cannot fail to alter ingestion connection");
+            }
         }
 
         Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
@@ -3978,6 +4194,7 @@ impl Coordinator {
 
         session.add_notice(AdapterNotice::VarDefaultUpdated {
             role: None,
+            database: None,
             var_name: Some(name),
         });
         Ok(ExecuteResponse::AlteredSystemConfiguration)
@@ -3994,6 +4211,7 @@ impl Coordinator {
         self.catalog_transact(Some(session), vec![op]).await?;
         session.add_notice(AdapterNotice::VarDefaultUpdated {
             role: None,
+            database: None,
             var_name: Some(name),
         });
         Ok(ExecuteResponse::AlteredSystemConfiguration)
@@ -4010,6 +4228,7 @@ impl Coordinator {
         self.catalog_transact(Some(session), vec![op]).await?;
         session.add_notice(AdapterNotice::VarDefaultUpdated {
             role: None,
+            database: None,
             var_name: None,
         });
         Ok(ExecuteResponse::AlteredSystemConfiguration)
@@ -4460,6 +4679,7 @@ impl Coordinator {
 #[derive(Debug)]
 struct CachedStatisticsOracle {
     cache: BTreeMap<GlobalId, usize>,
+    column_stats: BTreeMap<GlobalId, Arc<Vec<ColumnStatistics>>>,
 }
 
 impl CachedStatisticsOracle {
@@ -4484,7 +4704,20 @@ impl CachedStatisticsOracle {
             }
         }
 
-        Ok(Self { cache })
+        Ok(Self {
+            cache,
+            column_stats: BTreeMap::new(),
+        })
+    }
+
+    /// Adds the column statistics from `stored`, and its cardinality estimates
+    /// for identifiers we have no fresh estimates for.
+    fn with_stored_statistics(mut self, stored: BTreeMap<GlobalId, CollectionStatistics>) -> Self {
+        for (id, stats) in stored {
+            self.cache.entry(id).or_insert(stats.num_updates);
+            self.column_stats.insert(id, stats.columns);
+        }
+        self
     }
 }
 
@@ -4496,6 +4729,14 @@ impl mz_transform::StatisticsOracle for CachedStatisticsOracle {
     fn as_map(&self) -> BTreeMap<GlobalId, usize> {
         self.cache.clone()
     }
+
+    fn column_statistics(&self, id: GlobalId) -> Option<Arc<Vec<ColumnStatistics>>> {
+        self.column_stats.get(&id).cloned()
+    }
+
+    fn column_statistics_map(&self) -> BTreeMap<GlobalId, Arc<Vec<ColumnStatistics>>> {
+        self.column_stats.clone()
+    }
 }
 
 impl Coordinator {
@@ -4519,6 +4760,9 @@ impl Coordinator {
             self.catalog().system_config().optimizer_stats_timeout()
         };
 
+        // Statistics collected in the background, which are less fresh but more detailed.
+        let stored_stats = self.stored_collection_statistics(source_ids);
+
         let cached_stats = mz_ore::future::timeout(
             timeout,
             CachedStatisticsOracle::new(source_ids, query_as_of, self.controller.storage.as_ref()),
@@ -4526,7 +4770,7 @@ impl Coordinator {
         .await;
 
         match cached_stats {
-            Ok(stats) => Ok(Box::new(stats)),
+            Ok(stats) => Ok(Box::new(stats.with_stored_statistics(stored_stats))),
             Err(mz_ore::future::TimeoutError::DeadlineElapsed) => {
                 warn!(
                     is_oneshot = is_oneshot,
@@ -4534,7 +4778,15 @@ impl Coordinator {
                     timeout.as_millis()
                 );
 
-                Ok(Box::new(EmptyStatisticsOracle))
+                if stored_stats.is_empty() {
+                    Ok(Box::new(EmptyStatisticsOracle))
+                } else {
+                    let stats = CachedStatisticsOracle {
+                        cache: BTreeMap::new(),
+                        column_stats: BTreeMap::new(),
+                    };
+                    Ok(Box::new(stats.with_stored_statistics(stored_stats)))
+                }
             }
             Err(mz_ore::future::TimeoutError::Inner(e)) => Err(AdapterError::Storage(e)),
         }
@@ -4620,8 +4872,11 @@ impl Coordinator {
                 // `emit_optimizer_notices` is onlyy called by the `sequence_~`
                 // method for the DDL that produces that notice.
                 session.add_notice(AdapterNotice::OptimizerNotice {
+                    kind,
                     notice: notice.message(&humanizer, false).to_string(),
                     hint: notice.hint(&humanizer, false).to_string(),
+                    action: notice.action(&humanizer, false).to_string(),
+                    action_kind: notice.action_kind(&humanizer),
                 });
             }
             self.metrics
@@ -422,6 +422,7 @@ impl Coordinator {
                     column_names,
                     cluster_id,
                     non_null_assertions,
+                    append_only,
                     refresh_schedule,
                     ..
                 },
@@ -451,6 +452,7 @@ impl Coordinator {
             view_id,
             column_names.clone(),
             non_null_assertions.clone(),
+            *append_only,
             refresh_schedule.clone(),
             debug_name,
             optimizer_config,
@@ -555,6 +557,7 @@ impl Coordinator {
                             expr: raw_expr,
                             cluster_id,
                             non_null_assertions,
+                            append_only,
                             compaction_window,
                             refresh_schedule,
                             ..
@@ -632,6 +635,7 @@ impl Coordinator {
                     resolved_ids,
                     cluster_id,
                     non_null_assertions,
+                    append_only,
                     custom_logical_compaction_window: compaction_window,
                     refresh_schedule,
                     initial_as_of: Some(initial_as_of.clone()),
@@ -14,6 +14,7 @@ use std::sync::Arc;
 use http::Uri;
 use itertools::Either;
 use maplit::btreemap;
+use mz_adapter_types::dyncfgs::PEEK_PLAN_CACHE_MAX_ENTRIES;
 use mz_controller_types::ClusterId;
 use mz_expr::{CollectionPlan, ResultSpec};
 use mz_ore::cast::CastFrom;
@@ -36,7 +37,8 @@ use tracing::{Instrument, Span};
 use crate::active_compute_sink::{ActiveComputeSink, ActiveCopyTo};
 use crate::command::ExecuteResponse;
 use crate::coord::id_bundle::CollectionIdBundle;
-use crate::coord::peek::{self, PeekDataflowPlan, PeekPlan, PlannedPeek};
+use crate::coord::peek::{self, PagedPeek, PeekDataflowPlan, PeekPlan, PlannedPeek};
+use crate::coord::plan_cache::{self, CachedPeekPlan, PeekPlanCacheKey};
 use crate::coord::sequencer::inner::{check_log_reads, return_if_err};
 use crate::coord::timeline::TimelineContext;
 use crate::coord::timestamp_selection::{
@@ -84,7 +86,16 @@ impl Staged for PeekStage {
                 coord.peek_real_time_recency(ctx.session(), stage).await
             }
             PeekStage::TimestampReadHold(stage) => {
-                coord.peek_timestamp_read_hold(ctx.session_mut(), stage)
+                let result = coord.peek_timestamp_read_hold(ctx.session_mut(), stage);
+                if let (Ok(_), Some(id)) = (&result, ctx.extra().contents()) {
+                    let now = coord.now();
+                    coord.record_statement_lifecycle_event(
+                        &id,
+                        &StatementLifecycleEvent::TimestampSelected,
+                        now,
+                    );
+                }
+                result
             }
             PeekStage::Optimize(stage) => coord.peek_optimize(ctx.session(), stage).await,
             PeekStage::Finish(stage) => coord.peek_finish(ctx, stage).await,
@@ -122,6 +133,7 @@ impl Coordinator {
         ctx: ExecuteContext,
         plan: plan::SelectPlan,
         target_cluster: TargetCluster,
+        plan_cache_key: Option<PeekPlanCacheKey>,
     ) {
         let explain_ctx = if ctx.session().vars().emit_plan_insights_notice() {
             let optimizer_trace = OptimizerTrace::new(ExplainStage::PlanInsights.paths());
@@ -131,7 +143,14 @@ impl Coordinator {
         };
 
         let stage = return_if_err!(
-            self.peek_validate(ctx.session(), plan, target_cluster, None, explain_ctx),
+            self.peek_validate(
+                ctx.session(),
+                plan,
+                target_cluster,
+                None,
+                explain_ctx,
+                plan_cache_key,
+            ),
             ctx
         );
         self.sequence_staged(ctx, Span::current(), stage).await;
@@ -149,6 +168,7 @@ impl Coordinator {
             connection_id,
             format,
             max_file_size,
+            manifest,
         }: plan::CopyToPlan,
         target_cluster: TargetCluster,
     ) {
@@ -191,10 +211,12 @@ impl Coordinator {
                     connection_id,
                     format,
                     max_file_size,
+                    manifest,
                     // This will be set in `peek_stage_validate` stage below.
                     output_batch_count: None,
                 }),
                 ExplainContext::None,
+                None,
             ),
             ctx
         );
@@ -242,7 +264,8 @@ impl Coordinator {
                     replan: None,
                     desc: Some(desc),
                     optimizer_trace,
-                })
+                }),
+                None,
             ),
             ctx
         );
@@ -254,11 +277,20 @@ impl Coordinator {
     pub fn peek_validate(
         &self,
         session: &Session,
-        plan: mz_sql::plan::SelectPlan,
+        mut plan: mz_sql::plan::SelectPlan,
         target_cluster: TargetCluster,
         copy_to_ctx: Option<CopyToContext>,
         explain_ctx: ExplainContext,
+        plan_cache_key: Option<PeekPlanCacheKey>,
     ) -> Result<PeekStage, AdapterError> {
+        // Reads in a transaction started with `BEGIN ... AS OF` happen at the
+        // timestamp of the transaction, unless they specify their own.
+        if plan.when == QueryWhen::Immediately {
+            if let Some(when) = session.transaction().pinned_when() {
+                plan.when = when;
+            }
+        }
+
         // Collect optimizer parameters.
         let catalog = self.owned_catalog();
         let cluster = catalog.resolve_target_cluster(target_cluster, session)?;
@@ -373,6 +405,7 @@ impl Coordinator {
             timeline_context,
             optimizer,
             explain_ctx,
+            plan_cache_key,
         }))
     }
 
@@ -389,6 +422,7 @@ impl Coordinator {
             timeline_context,
             optimizer,
             explain_ctx,
+            plan_cache_key,
         }: PeekStageLinearizeTimestamp,
     ) -> Result<StageResult<Box<PeekStage>>, AdapterError> {
         let isolation_level = session.vars().transaction_isolation().clone();
@@ -405,6 +439,7 @@ impl Coordinator {
             oracle_read_ts,
             optimizer,
             explain_ctx,
+            plan_cache_key,
         };
 
         match timeline {
@@ -449,6 +484,7 @@ impl Coordinator {
             real_time_recency_ts,
             optimizer,
             explain_ctx,
+            plan_cache_key,
         }: PeekStageTimestampReadHold,
     ) -> Result<StageResult<Box<PeekStage>>, AdapterError> {
         let cluster_id = match optimizer.as_ref() {
@@ -484,6 +520,7 @@ impl Coordinator {
             determination,
             optimizer,
             explain_ctx,
+            plan_cache_key,
         });
         Ok(StageResult::Immediate(Box::new(stage)))
     }
@@ -501,6 +538,7 @@ impl Coordinator {
             determination,
             mut optimizer,
             explain_ctx,
+            plan_cache_key,
         }: PeekStageOptimize,
     ) -> Result<StageResult<Box<PeekStage>>, AdapterError> {
         // Generate data structures that can be moved to another task where we will perform possibly
@@ -523,6 +561,49 @@ impl Coordinator {
             }
         }
 
+        // Plain `SELECT`s can reuse the optimization of an earlier execution of
+        // the same statement with the same parameters. `EXPLAIN`s must trace
+        // the full pipeline, so they always optimize from scratch.
+        let plan_cache_max_entries =
+            PEEK_PLAN_CACHE_MAX_ENTRIES.get(self.catalog().system_config().dyncfgs());
+        let catalog_revision = self.catalog().transient_revision();
+        let (cached_plan, caching) = match (plan_cache_key, optimizer.as_ref().left()) {
+            (Some(key), Some(optimizer))
+                if plan_cache_max_entries > 0 && matches!(explain_ctx, ExplainContext::None) =>
+            {
+                let cached_plan = self.peek_plan_cache.lock().expect("poisoned").get(
+                    &key,
+                    &optimizer.config().features,
+                    optimizer.cluster_id(),
+                    catalog_revision,
+                );
+                let result = match &cached_plan {
+                    None => "miss",
+                    Some(CachedPeekPlan::Local(_)) => "local_hit",
+                    Some(CachedPeekPlan::Global(_)) => "global_hit",
+                };
+                self.metrics
+                    .peek_plan_cache_lookups
+                    .with_label_values(&[result])
+                    .inc();
+                // Views are inlined during global optimization, so the global
+                // plan depends on the timestamp or the session if any of the
+                // views it reads calls unmaterializable functions.
+                let views_materializable = source_ids
+                    .iter()
+                    .flat_map(|id| self.catalog().state().transitive_uses(*id))
+                    .all(|id| match self.catalog().get_entry(&id).item() {
+                        CatalogItem::View(view) => {
+                            !plan_cache::contains_unmaterializable(&view.optimized_expr)
+                        }
+                        _ => true,
+                    });
+                let caching = (Arc::clone(&self.peek_plan_cache), key, views_materializable);
+                (cached_plan, Some(caching))
+            }
+            _ => (None, None),
+        };
+
         let span = Span::current();
         Ok(StageResult::Handle(mz_ore::task::spawn_blocking(
             || "optimize peek",
@@ -537,11 +618,43 @@ impl Coordinator {
                             // Optimize SELECT statement.
                             Either::Left(optimizer) => {
                                 // HIR ⇒ MIR lowering and MIR optimization (local)
-                                let local_mir_plan = optimizer.catch_unwind_optimize(raw_expr)?;
+                                let local_mir_plan = match cached_plan {
+                                    Some(CachedPeekPlan::Global(global_lir_plan)) => {
+                                        return Ok(Either::Left(global_lir_plan));
+                                    }
+                                    Some(CachedPeekPlan::Local(local_mir_plan)) => local_mir_plan,
+                                    None => {
+                                        let local_mir_plan = optimizer.catch_unwind_optimize(raw_expr)?;
+                                        if let Some((cache, key, _)) = &caching {
+                                            cache.lock().expect("poisoned").insert(
+                                                key.clone(),
+                                                optimizer.config().features.clone(),
+                                                source_ids.clone(),
+                                                local_mir_plan.clone(),
+                                                plan_cache_max_entries,
+                                            );
+                                        }
+                                        local_mir_plan
+                                    }
+                                };
+                                // Fast path plans that depend neither on the
+                                // timestamp nor on the session can be reused
+                                // as long as the catalog doesn't change.
+                                let materializable = !plan_cache::contains_unmaterializable(local_mir_plan.expr());
                                 // Attach resolved context required to continue the pipeline.
                                 let local_mir_plan = local_mir_plan.resolve(timestamp_context.clone(), &session, stats);
                                 // MIR optimization (global), MIR ⇒ LIR lowering, and LIR optimization (global)
                                 let global_lir_plan = optimizer.catch_unwind_optimize(local_mir_plan)?;
+                                if let Some((cache, key, views_materializable)) = &caching {
+                                    if materializable && *views_materializable && global_lir_plan.is_fast_path() {
+                                        cache.lock().expect("poisoned").insert_global(
+                                            key,
+                                            global_lir_plan.clone(),
+                                            optimizer.cluster_id(),
+                                            catalog_revision,
+                                        );
+                                    }
+                                }
 
                                 Ok(Either::Left(global_lir_plan))
                             }
@@ -708,6 +821,7 @@ impl Coordinator {
             oracle_read_ts,
             optimizer,
             explain_ctx,
+            plan_cache_key,
         }: PeekStageRealTimeRecency,
     ) -> Result<StageResult<Box<PeekStage>>, AdapterError> {
         let fut = self
@@ -729,6 +843,7 @@ impl Coordinator {
                             source_ids,
                             optimizer,
                             explain_ctx,
+                            plan_cache_key,
                             oracle_read_ts,
                             real_time_recency_ts: Some(real_time_recency_ts),
                         });
@@ -746,6 +861,7 @@ impl Coordinator {
                     source_ids,
                     optimizer,
                     explain_ctx,
+                    plan_cache_key,
                     oracle_read_ts,
                     real_time_recency_ts: None,
                 }),
@@ -806,7 +922,7 @@ impl Coordinator {
             session.add_notice(AdapterNotice::PlanInsights(insights));
         }
 
-        let planned_peek = PlannedPeek {
+        let mut planned_peek = PlannedPeek {
             plan: peek_plan,
             determination: determination.clone(),
             conn_id: conn_id.clone(),
@@ -861,21 +977,26 @@ impl Coordinator {
             )
         }
 
-        let max_query_size = ctx.session().vars().max_query_result_size();
+        let max_query_size = ctx.session().vars().effective_max_query_result_size();
         let max_result_size = self.catalog().system_config().max_result_size();
 
-        // Implement the peek, and capture the response.
-        let resp = self
-            .implement_peek_plan(
-                ctx.extra_mut(),
-                planned_peek,
-                optimizer.finishing().clone(),
-                optimizer.cluster_id(),
-                target_replica,
-                max_result_size,
-                Some(max_query_size),
-            )
-            .await?;
+        // The rows of cursors over indexes can be read in pages, as they are fetched.
+        let system_config = self.catalog().system_config();
+        let paged_peek =
+            if ctx.is_cursor() && plan.copy_to.is_none() && system_config.enable_paged_cursors() {
+                PagedPeek::new(
+                    &planned_peek,
+                    optimizer.finishing(),
+                    &typ,
+                    optimizer.cluster_id(),
+                    target_replica,
+                    system_config.cursor_page_size(),
+                    max_result_size,
+                    Some(max_query_size),
+                )
+            } else {
+                None
+            };
 
         if ctx.session().vars().emit_timestamp_notice() {
             let explanation = self.explain_timestamp(
@@ -888,6 +1009,92 @@ impl Coordinator {
                 .add_notice(AdapterNotice::QueryTimestamp { explanation });
         }
 
+        // Consolidating and sorting a large constant result can take a while, so we finish it
+        // off the coordinator's main loop. Errors are retired with their SQLSTATE like those of
+        // any other stage.
+        if let (None, PeekPlan::FastPath(peek::FastPathPlan::Constant(Ok(rows), _))) =
+            (&paged_peek, &mut planned_peek.plan)
+        {
+            if rows.len() > peek::MAX_INLINE_CONSTANT_ROWS {
+                let rows = std::mem::take(rows);
+                let finishing = optimizer.finishing().clone();
+                let copy_to = plan.copy_to;
+                let handle = mz_ore::task::spawn_blocking(
+                    || "finish constant peek",
+                    move || {
+                        let rows = peek::finish_constant_rows(
+                            rows,
+                            &finishing,
+                            max_result_size,
+                            Some(max_query_size),
+                        )?;
+                        let resp = Coordinator::send_immediate_rows(rows);
+                        Ok(match copy_to {
+                            None => resp,
+                            Some(format) => ExecuteResponse::CopyTo {
+                                format,
+                                resp: Box::new(resp),
+                            },
+                        })
+                    },
+                );
+                return Ok(StageResult::HandleRetire(handle));
+            }
+        }
+
+        // Implement the peek, and capture the response.
+        let resp = match paged_peek {
+            Some(paged_peek) => self.implement_paged_peek(ctx.extra_mut(), paged_peek),
+            None => {
+                self.implement_peek_plan(
+                    ctx.extra_mut(),
+                    planned_peek,
+                    optimizer.finishing().clone(),
+                    optimizer.cluster_id(),
+                    target_replica,
+                    max_result_size,
+                    Some(max_query_size),
+                )
+                .await?
+            }
+        };
+
+        // Enforce the role's `statement_timeout_limit`, if any, on the rows of the peek.
+        let timeout_limit = *ctx.session().vars().statement_timeout_limit();
+        let resp = match resp {
+            ExecuteResponse::SendingRows {
+                future,
+                instance_id,
+                strategy,
+            } if !timeout_limit.is_zero() => {
+                let internal_cmd_tx = self.internal_cmd_tx.clone();
+                let conn_id = ctx.session().conn_id().clone();
+                let future = Box::pin(async move {
+                    match tokio::time::timeout(timeout_limit, future).await {
+                        Ok(res) => res,
+                        Err(_) => {
+                            // Best-effort cancellation of the pending peek. It is not an error
+                            // for this to happen after `internal_cmd_rx` has been dropped.
+                            let result =
+                                internal_cmd_tx.send(Message::CancelPendingPeeks { conn_id });
+                            if let Err(e) = result {
+                                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                            }
+                            peek::PeekResponseUnary::Error(
+                                AdapterError::StatementTimeout.to_string(),
+                            )
+                        }
+                    }
+                });
+                ExecuteResponse::SendingRows {
+                    future,
+                    instance_id,
+                    strategy,
+                }
+            }
+            resp => resp,
+        };
+
         let resp = match plan.copy_to {
             None => resp,
             Some(format) => ExecuteResponse::CopyTo {
@@ -1151,11 +1358,17 @@ impl Coordinator {
         // necessary to support PG's `BEGIN` semantics, whose behavior can
         // depend on whether or not reads have occurred in the txn.
         let mut transaction_determination = determination.clone();
+        let table_ids = source_ids
+            .iter()
+            .flat_map(|id| self.catalog().state().transitive_uses(*id))
+            .filter(|id| self.catalog().get_entry(id).is_table())
+            .collect();
         if when.is_transactional() {
             session.add_transaction_ops(TransactionOps::Peeks {
                 determination: transaction_determination,
                 cluster_id,
                 requires_linearization,
+                table_ids,
             })?;
         } else if matches!(session.transaction(), &TransactionStatus::InTransaction(_)) {
             // If the query uses AS OF, then ignore the timestamp.
@@ -1164,6 +1377,7 @@ impl Coordinator {
                 determination: transaction_determination,
                 cluster_id,
                 requires_linearization,
+                table_ids,
             })?;
         };
 
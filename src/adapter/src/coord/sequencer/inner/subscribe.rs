@@ -7,15 +7,20 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use mz_ore::instrument;
+use std::collections::BTreeMap;
+
+use mz_compute_client::protocol::response::SubscribeBatch;
+use mz_controller_types::{ClusterId, ReplicaId};
+use mz_ore::{instrument, soft_assert_or_log};
 use mz_repr::optimize::OverrideFrom;
-use mz_sql::plan::{self, QueryWhen};
+use mz_repr::{GlobalId, Timestamp};
+use mz_sql::plan::{self, QueryWhen, SubscribeFrom};
 use mz_sql::session::metadata::SessionMetadata;
 use timely::progress::Antichain;
 use tokio::sync::mpsc;
 use tracing::Span;
 
-use crate::active_compute_sink::{ActiveComputeSink, ActiveSubscribe};
+use crate::active_compute_sink::{ActiveComputeSink, ActiveSubscribe, SharedSubscribeKey};
 use crate::command::ExecuteResponse;
 use crate::coord::sequencer::inner::{check_log_reads, return_if_err};
 use crate::coord::{
@@ -226,7 +231,7 @@ impl Coordinator {
     #[instrument]
     async fn subscribe_timestamp_optimize_lir(
         &mut self,
-        ctx: &ExecuteContext,
+        ctx: &mut ExecuteContext,
         SubscribeTimestampOptimizeLir {
             validity,
             plan,
@@ -266,6 +271,21 @@ impl Coordinator {
 
         self.store_transaction_read_holds(ctx.session(), read_holds);
 
+        // Attach to an existing dataflow instead of installing a new one, if
+        // possible. Subscribes that need a snapshot still install a dataflow
+        // computing their snapshot, see `subscribe_finish`.
+        if let Some(key) = self
+            .shared_subscribe_key(&plan, optimizer.cluster_id(), validity.replica_id)
+            .filter(|_| !plan.with_snapshot)
+        {
+            if let Some(dataflow_id) = self.shared_subscribes.attachable(&key, as_of) {
+                let resp = self
+                    .subscribe_attach(ctx, validity, plan, key, dataflow_id, as_of)
+                    .await;
+                return Ok(StageResult::Response(resp));
+            }
+        }
+
         let global_mir_plan = global_mir_plan.resolve(Antichain::from_elem(as_of));
 
         // Optimize LIR
@@ -297,33 +317,67 @@ impl Coordinator {
         SubscribeFinish {
             validity,
             cluster_id,
-            plan:
-                plan::SubscribePlan {
-                    copy_to,
-                    emit_progress,
-                    output,
-                    ..
-                },
-            global_lir_plan,
+            plan,
+            mut global_lir_plan,
         }: SubscribeFinish,
     ) -> Result<StageResult<Box<SubscribeStage>>, AdapterError> {
         let sink_id = global_lir_plan.sink_id();
+        let as_of = global_lir_plan
+            .as_of()
+            .expect("set to Some in an earlier stage");
+
+        // A subscribe that needs a snapshot can still attach to an existing
+        // dataflow, by only computing its snapshot in a dataflow of its own.
+        // Otherwise, let later identical subscribes share the dataflow.
+        let shared_key = self.shared_subscribe_key(&plan, cluster_id, validity.replica_id);
+        let attach_to = shared_key
+            .as_ref()
+            .filter(|_| plan.with_snapshot)
+            .and_then(|key| self.shared_subscribes.attachable(key, as_of));
+        let shared = match (attach_to, shared_key) {
+            (Some(dataflow_id), _) => {
+                global_lir_plan.restrict_to_snapshot();
+                let batch = self
+                    .shared_subscribes
+                    .attach(dataflow_id, sink_id, as_of, true);
+                soft_assert_or_log!(batch.is_none(), "subscribe activated before its snapshot");
+                true
+            }
+            (None, Some(key)) => {
+                self.shared_subscribes.insert(key, sink_id, as_of);
+                true
+            }
+            (None, None) => false,
+        };
+
+        let plan::SubscribePlan {
+            copy_to,
+            emit_progress,
+            output,
+            ..
+        } = plan;
 
         let (tx, rx) = mpsc::unbounded_channel();
-        let active_subscribe = ActiveSubscribe {
+        let mut active_subscribe = ActiveSubscribe {
             conn_id: ctx.session().conn_id().clone(),
             channel: tx,
             emit_progress,
-            as_of: global_lir_plan
-                .as_of()
-                .expect("set to Some in an earlier stage"),
+            as_of,
             arity: global_lir_plan.sink_desc().from_desc.arity(),
             cluster_id,
             depends_on: validity.dependency_ids,
             start_time: self.now(),
             output,
+            fan_out: None,
         };
         active_subscribe.initialize();
+        if shared {
+            let max_buffered_rows = self
+                .catalog()
+                .system_config()
+                .subscribe_fan_out_max_buffered_rows();
+            active_subscribe.spawn_fan_out(max_buffered_rows);
+        }
 
         let (df_desc, df_meta) = global_lir_plan.unapply();
         // Emit notices.
@@ -371,3 +425,142 @@ impl Coordinator {
         Ok(StageResult::Response(resp))
     }
 }
+
+impl Coordinator {
+    /// Returns the key under which the subscribe can share a dataflow with
+    /// identical subscribes, if sharing is enabled and possible.
+    fn shared_subscribe_key(
+        &self,
+        plan: &plan::SubscribePlan,
+        cluster_id: ClusterId,
+        replica_id: Option<ReplicaId>,
+    ) -> Option<SharedSubscribeKey> {
+        if !self.catalog().system_config().enable_subscribe_fan_out() {
+            return None;
+        }
+        // Subscribes over queries may depend on the session, e.g. through
+        // `current_user()`, so only subscribes to relations are shared.
+        match (&plan.from, &plan.up_to) {
+            (SubscribeFrom::Id(from), None) => Some(SharedSubscribeKey {
+                cluster_id,
+                replica_id,
+                from: *from,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Attaches a subscribe at `as_of` to the shared dataflow `dataflow_id`.
+    async fn subscribe_attach(
+        &mut self,
+        ctx: &mut ExecuteContext,
+        validity: PlanValidity,
+        plan: plan::SubscribePlan,
+        key: SharedSubscribeKey,
+        dataflow_id: GlobalId,
+        as_of: Timestamp,
+    ) -> ExecuteResponse {
+        let plan::SubscribePlan {
+            copy_to,
+            emit_progress,
+            output,
+            ..
+        } = plan;
+        let sink_id = self.allocate_transient_id();
+        let arity = self
+            .catalog()
+            .get_entry(&key.from)
+            .desc_opt()
+            .expect("subscribed relations have a desc")
+            .arity();
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut active_subscribe = ActiveSubscribe {
+            conn_id: ctx.session().conn_id().clone(),
+            channel: tx,
+            emit_progress,
+            as_of,
+            arity,
+            cluster_id: key.cluster_id,
+            depends_on: validity.dependency_ids,
+            start_time: self.now(),
+            output,
+            fan_out: None,
+        };
+        active_subscribe.initialize();
+        let max_buffered_rows = self
+            .catalog()
+            .system_config()
+            .subscribe_fan_out_max_buffered_rows();
+        active_subscribe.spawn_fan_out(max_buffered_rows);
+
+        let batch = self
+            .shared_subscribes
+            .attach(dataflow_id, sink_id, as_of, false);
+        let retire_reason = batch.and_then(|batch| active_subscribe.fan_out_response(batch));
+
+        self.add_active_compute_sink(sink_id, ActiveComputeSink::Subscribe(active_subscribe))
+            .await
+            .await;
+        if let Some(reason) = retire_reason {
+            self.retire_compute_sinks(BTreeMap::from([(sink_id, reason)]))
+                .await;
+        }
+
+        // The shared dataflow holds back the inputs, so the pre-optimization
+        // read holds are no longer needed.
+        let txn_read_holds = self.txn_read_holds.remove(ctx.session().conn_id());
+        drop(txn_read_holds);
+
+        let resp = ExecuteResponse::Subscribing {
+            rx,
+            ctx_extra: std::mem::take(ctx.extra_mut()),
+            instance_id: key.cluster_id,
+        };
+        match copy_to {
+            None => resp,
+            Some(format) => ExecuteResponse::CopyTo {
+                format,
+                resp: Box::new(resp),
+            },
+        }
+    }
+
+    /// Fans out a batch of results from a shared subscribe dataflow, or from
+    /// the snapshot dataflow of a subscribe attached to one, to the subscribes
+    /// receiving its results.
+    pub(crate) async fn process_shared_subscribe_response(
+        &mut self,
+        id: GlobalId,
+        batch: SubscribeBatch,
+    ) {
+        let (batches, completed_snapshot) = self.shared_subscribes.process_response(id, batch);
+
+        // The subscribe receives all further results from the shared dataflow.
+        if let Some((cluster_id, snapshot_id)) = completed_snapshot {
+            // A cluster could have been dropped, so verify it exists.
+            if self.controller.compute.instance_exists(cluster_id) {
+                self.controller
+                    .compute
+                    .drop_collections(cluster_id, vec![snapshot_id])
+                    .unwrap_or_terminate("cannot fail to drop collections");
+            }
+        }
+
+        // The subscribes' tasks format and deliver the results, so fanning
+        // out only queues a shared batch for each of them.
+        let mut retire = BTreeMap::new();
+        for (sink_id, batch) in batches {
+            if let Some(ActiveComputeSink::Subscribe(active_subscribe)) =
+                self.active_compute_sinks.get(&sink_id)
+            {
+                if let Some(reason) = active_subscribe.fan_out_response(batch) {
+                    retire.insert(sink_id, reason);
+                }
+            }
+        }
+        if !retire.is_empty() {
+            self.retire_compute_sinks(retire).await;
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Periodic reporting of the blob usage and fragmentation of storage shards in
+//! `mz_internal.mz_storage_shard_usage`.
+//!
+//! Unlike `mz_storage_usage_by_shard`, which is retained for billing, the
+//! report only reflects the most recent collection.
+
+use std::collections::BTreeMap;
+
+use mz_ore::now::EpochMillis;
+use mz_ore::task;
+use mz_persist_client::usage::ShardUsageReport;
+use mz_persist_client::ShardId;
+use tracing::warn;
+
+use crate::coord::{Coordinator, Message};
+
+impl Coordinator {
+    /// Schedules the next report of the usage of storage shards.
+    pub(crate) fn schedule_storage_shard_usage_report(&self) {
+        let interval = self
+            .catalog()
+            .system_config()
+            .storage_shard_usage_report_interval();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "storage_shard_usage_report", async move {
+            tokio::time::sleep(interval).await;
+            // If sending fails, the main thread has shutdown.
+            let _ = internal_cmd_tx.send(Message::StorageShardUsageFetch);
+        });
+    }
+
+    /// Starts computing the usage of all live storage shards in the background.
+    #[mz_ore::instrument(level = "debug")]
+    pub(crate) fn storage_shard_usage_fetch(&mut self) {
+        if !self
+            .catalog()
+            .system_config()
+            .enable_storage_shard_usage_report()
+        {
+            self.storage_shard_usage_update(BTreeMap::new());
+            return;
+        }
+
+        let live_shards = self.live_storage_shards();
+        let client = self.storage_usage_client.clone();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "storage_shard_usage_fetch", async move {
+            // Computing the report requires a slow scan of blob storage.
+            let reports = client.shards_usage_report(live_shards).await;
+            if let Err(e) = internal_cmd_tx.send(Message::StorageShardUsageUpdate(reports)) {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
+    }
+
+    /// Replaces the reported usage of storage shards.
+    pub(crate) fn storage_shard_usage_update(
+        &mut self,
+        reports: BTreeMap<ShardId, ShardUsageReport>,
+    ) {
+        let collection_timestamp: EpochMillis = self.now();
+        let reports: BTreeMap<_, _> = reports
+            .into_iter()
+            .map(|(shard_id, report)| (shard_id, (report, collection_timestamp)))
+            .collect();
+        let old_reports = std::mem::replace(&mut self.storage_shard_usage, reports);
+
+        let state = self.catalog().state();
+        let mut updates = Vec::new();
+        for (shard_id, (report, collection_timestamp)) in &old_reports {
+            updates.extend(state.pack_storage_shard_usage_updates(
+                shard_id,
+                report,
+                *collection_timestamp,
+                -1,
+            ));
+        }
+        for (shard_id, (report, collection_timestamp)) in &self.storage_shard_usage {
+            updates.extend(state.pack_storage_shard_usage_updates(
+                shard_id,
+                report,
+                *collection_timestamp,
+                1,
+            ));
+        }
+        if !updates.is_empty() {
+            let updates = state.resolve_builtin_table_updates(updates);
+            self.builtin_table_update().background(updates);
+        }
+
+        self.schedule_storage_shard_usage_report();
+    }
+}
@@ -77,7 +77,7 @@ impl Coordinator {
         let result_formats = vec![mz_pgwire_common::Format::Text; desc.arity()];
         let logging = session.mint_logging(sql, Some(&stmt), now);
         session.set_portal(
-            name,
+            name.clone(),
             desc,
             Some(stmt),
             logging,
@@ -85,6 +85,10 @@ impl Coordinator {
             result_formats,
             catalog.transient_revision(),
         )?;
+        session
+            .get_portal_unverified_mut(&name)
+            .expect("known to exist")
+            .is_cursor = true;
         Ok(())
     }
 
@@ -87,9 +87,12 @@ impl PreparedStatementLoggingInfo {
         let kind = stmt.map(StatementKind::from);
         let sql = match kind {
             // We __always__ want to redact SQL statements that might contain secret values.
-            Some(StatementKind::CreateSecret | StatementKind::AlterSecret) => {
-                stmt.map(|s| s.to_ast_string_redacted()).unwrap_or_default()
-            }
+            Some(
+                StatementKind::CreateSecret
+                | StatementKind::AlterSecret
+                | StatementKind::CreateRole
+                | StatementKind::AlterRole,
+            ) => stmt.map(|s| s.to_ast_string_redacted()).unwrap_or_default(),
             _ => raw_sql,
         };
 
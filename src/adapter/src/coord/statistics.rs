@@ -0,0 +1,308 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Periodic collection of the statistics the optimizer uses to estimate the
+//! cardinality of persisted collections.
+//!
+//! Statistics are derived from the stats persist keeps for each part of a
+//! shard, so collecting them does not require reading any data. Every part
+//! contributes its update count, and per column its `NULL` count and the bounds
+//! of its non-null values. The bounds of all parts together form a histogram of
+//! the column's values.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use mz_catalog::memory::objects::CatalogItem;
+use mz_ore::task;
+use mz_persist_client::stats::SnapshotPartsStats;
+use mz_repr::{GlobalId, RelationDesc, Row, RowArena};
+use mz_storage_types::controller::StorageError;
+use mz_storage_types::stats::RelationPartStats;
+use mz_transform::{ColumnStatistics, HistogramBucket};
+use timely::PartialOrder;
+
+use crate::coord::id_bundle::CollectionIdBundle;
+use crate::coord::{Coordinator, Message};
+
+/// The maximum number of buckets in the histogram of a column.
+const MAX_HISTOGRAM_BUCKETS: usize = 64;
+
+/// The shortest interval at which statistics are recollected.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Statistics about the contents of a persisted collection.
+#[derive(Clone, Debug)]
+pub struct CollectionStatistics {
+    /// The number of updates in the collection.
+    pub num_updates: usize,
+    /// Statistics about each column of the collection.
+    pub columns: Arc<Vec<ColumnStatistics>>,
+}
+
+impl Coordinator {
+    /// Schedules the next collection of optimizer statistics.
+    pub(crate) fn schedule_collection_statistics_refresh(&self) {
+        let interval = self
+            .catalog()
+            .system_config()
+            .optimizer_statistics_refresh_interval()
+            .max(MIN_REFRESH_INTERVAL);
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "collection_statistics_refresh", async move {
+            tokio::time::sleep(interval).await;
+            // If sending fails, the main thread has shutdown.
+            let _ = internal_cmd_tx.send(Message::CollectionStatisticsFetch);
+        });
+    }
+
+    /// Starts collecting statistics for all user tables, sources, and
+    /// materialized views in the background.
+    #[mz_ore::instrument(level = "debug")]
+    pub(crate) async fn collection_statistics_fetch(&mut self) {
+        if !self
+            .catalog()
+            .system_config()
+            .enable_optimizer_statistics_collection()
+        {
+            self.collection_statistics.clear();
+            self.schedule_collection_statistics_refresh();
+            return;
+        }
+
+        let mut descs = BTreeMap::new();
+        for entry in self.catalog().entries() {
+            if !entry.id().is_user() {
+                continue;
+            }
+            if !matches!(
+                entry.item(),
+                CatalogItem::Table(_) | CatalogItem::Source(_) | CatalogItem::MaterializedView(_)
+            ) {
+                continue;
+            }
+            if let Some(desc) = entry.desc_opt() {
+                descs.insert(entry.id(), desc.into_owned());
+            }
+        }
+
+        let id_bundle = CollectionIdBundle {
+            storage_ids: descs.keys().copied().collect(),
+            compute_ids: BTreeMap::new(),
+        };
+        let read_holds = self.acquire_read_holds(&id_bundle);
+
+        let mut futures = Vec::new();
+        for (id, desc) in descs {
+            // Only collect statistics at times the collection is readable at,
+            // so that we never wait for a collection to make progress.
+            let as_of = read_holds.since(&id);
+            let readable = match self.controller.storage.collection_frontiers(id) {
+                Ok((_since, upper)) => PartialOrder::less_than(&as_of, &upper),
+                Err(_) => false,
+            };
+            if !readable {
+                continue;
+            }
+            let stats_future = self
+                .controller
+                .storage
+                .snapshot_parts_stats(id, as_of)
+                .await;
+            futures.push((id, desc, stats_future));
+        }
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "collection_statistics_fetch", async move {
+            // Hold back the since of the collections until we're done.
+            let _read_holds = read_holds;
+            let stats = collect_statistics(futures).await;
+            // If sending fails, the main thread has shutdown.
+            let _ = internal_cmd_tx.send(Message::CollectionStatisticsUpdate(stats));
+        });
+    }
+
+    /// Replaces the stored optimizer statistics with freshly collected ones.
+    pub(crate) fn collection_statistics_update(
+        &mut self,
+        stats: BTreeMap<GlobalId, CollectionStatistics>,
+    ) {
+        self.collection_statistics = stats;
+        self.schedule_collection_statistics_refresh();
+    }
+
+    /// Returns the stored optimizer statistics for the given collections.
+    pub(crate) fn stored_collection_statistics(
+        &self,
+        ids: &BTreeSet<GlobalId>,
+    ) -> BTreeMap<GlobalId, CollectionStatistics> {
+        ids.iter()
+            .filter_map(|id| {
+                self.collection_statistics
+                    .get(id)
+                    .map(|stats| (*id, stats.clone()))
+            })
+            .collect()
+    }
+}
+
+async fn collect_statistics(
+    futures: Vec<(
+        GlobalId,
+        RelationDesc,
+        BoxFuture<'static, Result<SnapshotPartsStats, StorageError<mz_repr::Timestamp>>>,
+    )>,
+) -> BTreeMap<GlobalId, CollectionStatistics> {
+    let mut result = BTreeMap::new();
+    for (id, desc, stats_future) in futures {
+        match stats_future.await {
+            Ok(snapshot_stats) => {
+                if let Some(stats) = collection_statistics(&id.to_string(), &desc, &snapshot_stats)
+                {
+                    result.insert(id, stats);
+                }
+            }
+            Err(StorageError::IdentifierMissing(_)) => {
+                // The collection was dropped in the meantime.
+            }
+            Err(e) => tracing::warn!("failed to collect statistics for {id}: {e}"),
+        }
+    }
+    result
+}
+
+/// Summarizes the stats of the parts of a collection.
+///
+/// Returns `None` if some of the parts have no stats.
+fn collection_statistics(
+    name: &str,
+    desc: &RelationDesc,
+    snapshot_stats: &SnapshotPartsStats,
+) -> Option<CollectionStatistics> {
+    let arity = desc.arity();
+    let mut num_updates = 0;
+    let mut null_counts = vec![Some(0); arity];
+    let mut buckets = vec![Vec::new(); arity];
+
+    for part in &snapshot_stats.parts {
+        let stats = part.stats.as_ref()?.decode();
+        let stats = RelationPartStats::new(
+            name,
+            &snapshot_stats.metrics.pushdown.part_stats,
+            desc,
+            &stats,
+        );
+        let len = stats.len()?;
+        let ok_count = stats.ok_count().unwrap_or(len);
+        num_updates += len;
+
+        let arena = RowArena::new();
+        for idx in 0..arity {
+            // Columns for which some part has incomplete stats are left empty,
+            // which the optimizer treats as having no statistics.
+            let Some(null_count) = &mut null_counts[idx] else {
+                continue;
+            };
+            match stats.col_bounds(idx, &arena) {
+                Some((nulls, bounds)) => {
+                    *null_count += nulls;
+                    match bounds {
+                        Some((lower, upper)) => buckets[idx].push(HistogramBucket {
+                            lower: Row::pack_slice(&[lower]),
+                            upper: Row::pack_slice(&[upper]),
+                            count: ok_count.saturating_sub(nulls),
+                        }),
+                        None if ok_count > nulls => null_counts[idx] = None,
+                        None => {}
+                    }
+                }
+                None => null_counts[idx] = None,
+            }
+        }
+    }
+
+    let columns = null_counts
+        .into_iter()
+        .zip(buckets)
+        .map(|(null_count, buckets)| match null_count {
+            Some(null_count) => ColumnStatistics {
+                null_count,
+                buckets: merge_buckets(buckets),
+            },
+            None => ColumnStatistics::default(),
+        })
+        .collect();
+
+    Some(CollectionStatistics {
+        num_updates,
+        columns: Arc::new(columns),
+    })
+}
+
+/// Merges the (possibly overlapping) per-part buckets into at most
+/// [`MAX_HISTOGRAM_BUCKETS`] buckets of roughly the same number of parts.
+fn merge_buckets(mut buckets: Vec<HistogramBucket>) -> Vec<HistogramBucket> {
+    buckets.sort_by(|a, b| a.lower.unpack_first().cmp(&b.lower.unpack_first()));
+    let chunk_size = buckets.len().div_ceil(MAX_HISTOGRAM_BUCKETS).max(1);
+    buckets
+        .chunks(chunk_size)
+        .map(|chunk| HistogramBucket {
+            lower: chunk[0].lower.clone(),
+            upper: chunk
+                .iter()
+                .map(|b| &b.upper)
+                .max_by(|a, b| a.unpack_first().cmp(&b.unpack_first()))
+                .expect("chunks are nonempty")
+                .clone(),
+            count: chunk.iter().map(|b| b.count).sum(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::Datum;
+
+    use super::*;
+
+    fn bucket(lower: i64, upper: i64, count: usize) -> HistogramBucket {
+        HistogramBucket {
+            lower: Row::pack_slice(&[Datum::Int64(lower)]),
+            upper: Row::pack_slice(&[Datum::Int64(upper)]),
+            count,
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_merge_buckets() {
+        // Few buckets are kept as they are, but sorted by their lower bound.
+        let buckets = vec![bucket(10, 20, 5), bucket(-5, 3, 2)];
+        assert_eq!(
+            merge_buckets(buckets),
+            vec![bucket(-5, 3, 2), bucket(10, 20, 5)]
+        );
+
+        // Many buckets are merged, keeping the largest upper bound of each group.
+        let buckets = (0..MAX_HISTOGRAM_BUCKETS * 2)
+            .map(|i| {
+                let i = i64::try_from(i).expect("fits");
+                bucket(i, if i % 2 == 0 { i + 100 } else { i }, 1)
+            })
+            .collect();
+        let merged = merge_buckets(buckets);
+        assert_eq!(merged.len(), MAX_HISTOGRAM_BUCKETS);
+        assert_eq!(merged[0], bucket(0, 100, 2));
+        assert_eq!(
+            merged.iter().map(|b| b.count).sum::<usize>(),
+            MAX_HISTOGRAM_BUCKETS * 2
+        );
+    }
+}
@@ -10,6 +10,7 @@
 use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
+use std::net::IpAddr;
 use std::num::TryFromIntError;
 
 use dec::TryFromDecimalError;
@@ -95,6 +96,11 @@ pub enum AdapterError {
     InvalidTableMutationSelection,
     /// Expression violated a column's constraint
     ConstraintViolation(NotNullViolation),
+    /// A row written to a table violated one of its `CHECK` constraints.
+    CheckViolation {
+        relation: String,
+        constraint: String,
+    },
     /// Transaction cluster was dropped in the middle of a transaction.
     ConcurrentClusterDrop,
     /// Target cluster has no replicas to service query.
@@ -150,6 +156,11 @@ pub enum AdapterError {
         depends_on: SmallVec<[String; 2]>,
         cluster: String,
     },
+    /// A role tried to run a query on a cluster not in its `allowed_clusters`.
+    UnallowedClusterForRole {
+        role: String,
+        cluster: String,
+    },
     /// A user tried to perform an action that they were unauthorized to do.
     Unauthorized(rbac::UnauthorizedError),
     /// The named cursor does not exist.
@@ -176,14 +187,15 @@ pub enum AdapterError {
     },
     /// The transaction is in write-only mode.
     WriteOnlyTransaction,
-    /// The transaction only supports single table writes
-    MultiTableWriteTransaction,
     /// The transaction can only execute a single statement.
     SingleStatementTransaction,
     /// The transaction can only execute simple DDL.
     DDLOnlyTransaction,
     /// Another session modified the Catalog while this transaction was open.
     DDLTransactionRace,
+    /// Another transaction wrote to a table this read-write transaction read
+    /// after it was read.
+    ReadWriteTransactionConflict,
     /// Used to prevent us from durably committing state while a DDL transaction is open, should
     /// never be returned to the user.
     TransactionDryRun {
@@ -226,6 +238,10 @@ pub enum AdapterError {
     UnreadableSinkCollection,
     /// User sessions have been blocked.
     UserSessionsDisallowed,
+    /// The client address of a session is not allowed by the network policy.
+    NetworkPolicyDenied(IpAddr),
+    /// No `tls_client_cert_role_mapping` rule matched a client certificate.
+    UnmappedClientCertificate,
 }
 
 impl AdapterError {
@@ -237,6 +253,7 @@ impl AdapterError {
             detail: self.detail(),
             hint: self.hint(),
             position: self.position(),
+            structured: None,
         }
     }
 
@@ -339,6 +356,12 @@ impl AdapterError {
             AdapterError::RtrTimeout(name) => Some(format!("{name} failed to ingest data up to the real-time recency point")),
             AdapterError::RtrDropFailure(name) => Some(format!("{name} dropped before ingesting data to the real-time recency point")),
             AdapterError::UserSessionsDisallowed => Some(format!("Your organization has been blocked. Please contact support.")),
+            AdapterError::NetworkPolicyDenied(addr) => Some(format!(
+                "Connections from {addr} are not allowed by network_policy_ingress_allowlist."
+            )),
+            AdapterError::UnmappedClientCertificate => {
+                Some("Map the certificate to a role with tls_client_cert_role_mapping.".into())
+            }
             _ => None,
         }
     }
@@ -370,6 +393,9 @@ impl AdapterError {
             AdapterError::NoClusterReplicasAvailable(_) => {
                 Some("You can create cluster replicas using CREATE CLUSTER REPLICA".into())
             }
+            AdapterError::ReadWriteTransactionConflict => {
+                Some("The transaction might succeed if retried.".into())
+            }
             AdapterError::UntargetedLogRead { .. } => Some(
                 "Use `SET cluster_replica = <replica-name>` to target a specific replica in the \
                  active cluster. Note that subsequent queries will only be answered by \
@@ -456,6 +482,7 @@ impl AdapterError {
             AdapterError::SourceOrSinkSizeRequired { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::InvalidTableMutationSelection => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::ConstraintViolation(NotNullViolation(_)) => SqlState::NOT_NULL_VIOLATION,
+            AdapterError::CheckViolation { .. } => SqlState::CHECK_VIOLATION,
             AdapterError::ConcurrentClusterDrop => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::NoClusterReplicasAvailable(_) => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::OperationProhibitsTransaction(_) => SqlState::ACTIVE_SQL_TRANSACTION,
@@ -498,6 +525,7 @@ impl AdapterError {
             AdapterError::UnallowedOnCluster { .. } => {
                 SqlState::S_R_E_PROHIBITED_SQL_STATEMENT_ATTEMPTED
             }
+            AdapterError::UnallowedClusterForRole { .. } => SqlState::INSUFFICIENT_PRIVILEGE,
             AdapterError::Unauthorized(_) => SqlState::INSUFFICIENT_PRIVILEGE,
             AdapterError::UnknownCursor(_) => SqlState::INVALID_CURSOR_NAME,
             AdapterError::UnknownPreparedStatement(_) => SqlState::UNDEFINED_PSTATEMENT,
@@ -508,13 +536,12 @@ impl AdapterError {
             AdapterError::Unstructured(_) => SqlState::INTERNAL_ERROR,
             AdapterError::UntargetedLogRead { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::DDLTransactionRace => SqlState::T_R_SERIALIZATION_FAILURE,
+            AdapterError::ReadWriteTransactionConflict => SqlState::T_R_SERIALIZATION_FAILURE,
             AdapterError::TransactionDryRun { .. } => SqlState::T_R_SERIALIZATION_FAILURE,
             // It's not immediately clear which error code to use here because a
-            // "write-only transaction", "single table write transaction", or "ddl only
-            // transaction" are not things in Postgres. This error code is the generic "bad txn
-            // thing" code, so it's probably the best choice.
+            // "write-only transaction" or "ddl only transaction" are not things in Postgres. This
+            // error code is the generic "bad txn thing" code, so it's probably the best choice.
             AdapterError::WriteOnlyTransaction => SqlState::INVALID_TRANSACTION_STATE,
-            AdapterError::MultiTableWriteTransaction => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::DDLOnlyTransaction => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::Storage(_) | AdapterError::Compute(_) | AdapterError::Orchestrator(_) => {
                 SqlState::INTERNAL_ERROR
@@ -532,6 +559,10 @@ impl AdapterError {
             AdapterError::RtrDropFailure(_) => SqlState::UNDEFINED_OBJECT,
             AdapterError::UnreadableSinkCollection => SqlState::from_code("MZ009"),
             AdapterError::UserSessionsDisallowed => SqlState::from_code("MZ010"),
+            AdapterError::NetworkPolicyDenied(_) => SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
+            AdapterError::UnmappedClientCertificate => {
+                SqlState::INVALID_AUTHORIZATION_SPECIFICATION
+            }
         }
     }
 
@@ -596,6 +627,15 @@ impl fmt::Display for AdapterError {
             AdapterError::ConstraintViolation(not_null_violation) => {
                 write!(f, "{}", not_null_violation)
             }
+            AdapterError::CheckViolation {
+                relation,
+                constraint,
+            } => write!(
+                f,
+                "new row for relation {} violates check constraint {}",
+                relation.quoted(),
+                constraint.quoted()
+            ),
             AdapterError::ConcurrentClusterDrop => {
                 write!(f, "the transaction's active cluster has been dropped")
             }
@@ -675,6 +715,14 @@ impl fmt::Display for AdapterError {
                     cluster.quoted()
                 )
             }
+            AdapterError::UnallowedClusterForRole { role, cluster } => {
+                write!(
+                    f,
+                    "role {} is not allowed to run queries on cluster {}",
+                    role.quoted(),
+                    cluster.quoted()
+                )
+            }
             AdapterError::Unauthorized(unauthorized) => {
                 write!(f, "{unauthorized}")
             }
@@ -705,15 +753,15 @@ impl fmt::Display for AdapterError {
             AdapterError::UntargetedLogRead { .. } => {
                 f.write_str("log source reads must target a replica")
             }
-            AdapterError::MultiTableWriteTransaction => {
-                f.write_str("write transactions only support writes to a single table")
-            }
             AdapterError::DDLOnlyTransaction => f.write_str(
                 "transactions which modify objects are restricted to just modifying objects",
             ),
             AdapterError::DDLTransactionRace => {
                 f.write_str("object state changed while transaction was in progress")
             }
+            AdapterError::ReadWriteTransactionConflict => f.write_str(
+                "could not serialize access due to concurrent writes to tables read by the transaction",
+            ),
             AdapterError::TransactionDryRun { .. } => f.write_str("transaction dry run"),
             AdapterError::Storage(e) => e.fmt(f),
             AdapterError::Compute(e) => e.fmt(f),
@@ -761,6 +809,12 @@ impl fmt::Display for AdapterError {
                 write!(f, "collection is not readable at any time")
             }
             AdapterError::UserSessionsDisallowed => write!(f, "login blocked"),
+            AdapterError::NetworkPolicyDenied(_) => {
+                write!(f, "session denied by network policy")
+            }
+            AdapterError::UnmappedClientCertificate => {
+                write!(f, "client certificate is not mapped to a role")
+            }
         }
     }
 }
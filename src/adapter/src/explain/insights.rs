@@ -9,16 +9,18 @@
 
 //! Derive insights for plans.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
 use std::sync::Arc;
 
+use itertools::Itertools;
 use mz_compute_types::dataflows::{BuildDesc, DataflowDescription};
+use mz_expr::explain::{HumanizedNotice, HumanizerMode};
 use mz_expr::{
-    AccessStrategy, AggregateExpr, AggregateFunc, Id, MirRelationExpr, OptimizedMirRelationExpr,
-    RowSetFinishing,
+    AccessStrategy, AggregateExpr, AggregateFunc, BinaryFunc, Id, JoinInputMapper, LocalId,
+    MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, RowSetFinishing,
 };
-use mz_repr::explain::ExprHumanizer;
+use mz_repr::explain::{ExprHumanizer, IndexUsageType};
 use mz_repr::{GlobalId, Timestamp};
 use mz_sql::plan::HirRelationExpr;
 use mz_sql::session::metadata::SessionMetadata;
@@ -67,6 +69,11 @@ pub struct PlanInsights {
     pub fast_path_clusters: BTreeMap<String, Option<FastPathCluster>>,
     /// Names of persist sources over which a count(*) is done.
     pub persist_count: Vec<Name>,
+    /// Joins with inputs that are not related by any equality, i.e. cross joins.
+    pub cross_joins: Vec<CrossJoin>,
+    /// Indexes that would allow the plan to look up literal values instead of scanning a
+    /// collection.
+    pub index_recommendations: Vec<IndexRecommendation>,
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
@@ -166,6 +173,37 @@ pub enum ImportType {
     Storage,
 }
 
+/// A join with inputs that are not related by any equality.
+#[derive(Clone, Debug, Serialize)]
+pub struct CrossJoin {
+    /// The collections read by the inputs of the join.
+    pub inputs: Vec<Name>,
+}
+
+/// An index that the plan could use to look up literal values.
+#[derive(Clone, Debug, Serialize)]
+pub struct IndexRecommendation {
+    /// The collection to create the index on.
+    pub on: Name,
+    /// The recommended key expressions.
+    pub key: Vec<String>,
+    /// Why the index is recommended.
+    pub reason: IndexRecommendationReason,
+    /// A statement that creates the recommended index.
+    pub action: String,
+}
+
+/// The reason for an [`IndexRecommendation`].
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndexRecommendationReason {
+    /// The plan filters a collection read from persist by literal values.
+    LiteralConstraint,
+    /// The plan filters a collection by literal values, but the existing indexes on the
+    /// collection have keys that don't match the filter, so they are fully scanned.
+    NonMatchingIndex,
+}
+
 /// The name of a collection.
 #[derive(Debug, Clone, Serialize)]
 pub struct Name {
@@ -187,8 +225,13 @@ pub fn plan_insights(
 ) -> Option<PlanInsights> {
     match (global_plan, fast_path_plan) {
         (None, None) => None,
-        (None | Some(_), Some(fast_path_plan)) => {
-            Some(fast_path_insights(humanizer, fast_path_plan))
+        (global_plan, Some(fast_path_plan)) => {
+            let mut insights = fast_path_insights(humanizer, fast_path_plan);
+            // The global plan still tells us how the fast path reads its input.
+            if let Some(global_plan) = global_plan {
+                add_plan_recommendations(&mut insights, humanizer, &global_plan);
+            }
+            Some(insights)
         }
         (Some(global_plan), None) => Some(global_insights(humanizer, global_plan)),
     }
@@ -213,6 +256,7 @@ fn global_insights(
     plan: DataflowDescription<OptimizedMirRelationExpr>,
 ) -> PlanInsights {
     let mut insights = PlanInsights::default();
+    add_plan_recommendations(&mut insights, humanizer, &plan);
     for (id, _) in plan.source_imports {
         add_import_insights(&mut insights, humanizer, id, ImportType::Storage)
     }
@@ -269,6 +313,189 @@ fn global_insights(
     insights
 }
 
+/// Adds the cross joins and index recommendations of `plan` to `insights`.
+fn add_plan_recommendations(
+    insights: &mut PlanInsights,
+    humanizer: &dyn ExprHumanizer,
+    plan: &DataflowDescription<OptimizedMirRelationExpr>,
+) {
+    for BuildDesc { plan, .. } in &plan.objects_to_build {
+        // The global collections read by each local binding.
+        let mut bindings: BTreeMap<LocalId, BTreeSet<GlobalId>> = BTreeMap::new();
+        plan.visit_pre(|expr| match expr {
+            MirRelationExpr::Let { id, value, .. } => {
+                let reads = global_reads(value, &bindings);
+                bindings.insert(*id, reads);
+            }
+            MirRelationExpr::LetRec { ids, values, .. } => {
+                for (id, value) in ids.iter().zip_eq(values) {
+                    let reads = global_reads(value, &bindings);
+                    bindings.insert(*id, reads);
+                }
+            }
+            MirRelationExpr::Join {
+                inputs,
+                equivalences,
+                ..
+            } => {
+                let input_reads = inputs
+                    .iter()
+                    .map(|input| global_reads(input, &bindings))
+                    .collect::<Vec<_>>();
+                if is_cross_join(inputs, equivalences, &input_reads) {
+                    let reads = input_reads.into_iter().flatten().collect::<BTreeSet<_>>();
+                    insights.cross_joins.push(CrossJoin {
+                        inputs: reads
+                            .into_iter()
+                            .map(|id| structured_name(humanizer, id))
+                            .collect(),
+                    });
+                }
+            }
+            MirRelationExpr::Filter { input, predicates } => {
+                if let Some(recommendation) = recommend_index(humanizer, input, predicates) {
+                    insights.index_recommendations.push(recommendation);
+                }
+            }
+            _ => (),
+        });
+    }
+}
+
+/// Returns the global collections read by `expr`, looking through the local `bindings`.
+fn global_reads(
+    expr: &MirRelationExpr,
+    bindings: &BTreeMap<LocalId, BTreeSet<GlobalId>>,
+) -> BTreeSet<GlobalId> {
+    let mut reads = BTreeSet::new();
+    expr.visit_pre(|expr| match expr {
+        MirRelationExpr::Get {
+            id: Id::Global(id), ..
+        } => {
+            reads.insert(*id);
+        }
+        MirRelationExpr::Get {
+            id: Id::Local(id), ..
+        } => {
+            if let Some(ids) = bindings.get(id) {
+                reads.extend(ids);
+            }
+        }
+        _ => (),
+    });
+    reads
+}
+
+/// Returns whether the join `inputs` that read some global collection, according to
+/// `input_reads`, fall into more than one group of inputs that are related by the
+/// `equivalences`.
+///
+/// Inputs that don't read any global collection, e.g. constants, are ignored.
+fn is_cross_join(
+    inputs: &[MirRelationExpr],
+    equivalences: &[Vec<MirScalarExpr>],
+    input_reads: &[BTreeSet<GlobalId>],
+) -> bool {
+    if inputs.len() < 2 {
+        return false;
+    }
+    let input_mapper = JoinInputMapper::new(inputs);
+    // A simple union-find over the inputs.
+    let mut parents = (0..inputs.len()).collect::<Vec<_>>();
+    fn root(parents: &mut [usize], mut input: usize) -> usize {
+        while parents[input] != input {
+            parents[input] = parents[parents[input]];
+            input = parents[input];
+        }
+        input
+    }
+    for class in equivalences {
+        let related = class
+            .iter()
+            .flat_map(|expr| input_mapper.lookup_inputs(expr))
+            .collect::<Vec<_>>();
+        for pair in related.windows(2) {
+            let (a, b) = (root(&mut parents, pair[0]), root(&mut parents, pair[1]));
+            parents[a] = b;
+        }
+    }
+    (0..inputs.len())
+        .filter(|input| !input_reads[*input].is_empty())
+        .map(|input| root(&mut parents, input))
+        .collect::<BTreeSet<_>>()
+        .len()
+        > 1
+}
+
+/// Recommends an index for a `Filter` with `predicates` of the form `<expr> = <literal>` that
+/// reads `input` in full, either from persist or by fully scanning its indexes.
+fn recommend_index(
+    humanizer: &dyn ExprHumanizer,
+    input: &MirRelationExpr,
+    predicates: &[MirScalarExpr],
+) -> Option<IndexRecommendation> {
+    let MirRelationExpr::Get {
+        id: Id::Global(id),
+        access_strategy,
+        ..
+    } = input
+    else {
+        return None;
+    };
+    if !id.is_user() {
+        return None;
+    }
+    let reason = match access_strategy {
+        AccessStrategy::Persist => IndexRecommendationReason::LiteralConstraint,
+        AccessStrategy::Index(indexes)
+            if indexes
+                .iter()
+                .all(|(_, usage)| matches!(usage, IndexUsageType::FullScan)) =>
+        {
+            IndexRecommendationReason::NonMatchingIndex
+        }
+        _ => return None,
+    };
+
+    let mut key: Vec<MirScalarExpr> = Vec::new();
+    for predicate in predicates {
+        let MirScalarExpr::CallBinary {
+            func: BinaryFunc::Eq,
+            expr1,
+            expr2,
+        } = predicate
+        else {
+            continue;
+        };
+        let expr = match (expr1.is_literal(), expr2.is_literal()) {
+            (false, true) => expr1,
+            (true, false) => expr2,
+            _ => continue,
+        };
+        if !expr.contains_temporal() && !key.contains(&**expr) {
+            key.push((**expr).clone());
+        }
+    }
+    if key.is_empty() {
+        return None;
+    }
+
+    let col_names = humanizer.column_names_for_id(*id);
+    let mode = HumanizedNotice::new(false);
+    let key = mode
+        .seq(&key, col_names.as_ref())
+        .map(|expr| expr.to_string())
+        .collect::<Vec<_>>();
+    let on_name = humanizer.humanize_id_unqualified(*id)?;
+    let action = format!("CREATE INDEX ON {on_name}({});", key.join(", "));
+    Some(IndexRecommendation {
+        on: structured_name(humanizer, *id),
+        key,
+        reason,
+        action,
+    })
+}
+
 fn add_import_insights(
     insights: &mut PlanInsights,
     humanizer: &dyn ExprHumanizer,
@@ -52,6 +52,7 @@ pub fn storage_config(config: &SystemVars) -> StorageParameters {
         keep_n_sink_status_history_entries: config.keep_n_sink_status_history_entries(),
         keep_n_privatelink_status_history_entries: config
             .keep_n_privatelink_status_history_entries(),
+        keep_n_cluster_replica_event_entries: config.keep_n_cluster_replica_event_entries(),
         upsert_rocksdb_tuning_config: {
             match mz_rocksdb_types::RocksDBTuningParameters::from_parameters(
                 config.upsert_rocksdb_compaction_style(),
@@ -127,6 +128,8 @@ pub fn storage_config(config: &SystemVars) -> StorageParameters {
             check_interval: config.ssh_check_interval(),
             connect_timeout: config.ssh_connect_timeout(),
             keepalives_idle: config.ssh_keepalives_idle(),
+            reconnect_backoff: config.ssh_reconnect_backoff(),
+            reconnect_grace_period: config.ssh_reconnect_grace_period(),
         },
         kafka_timeout_config: mz_kafka_util::client::TimeoutConfig::build(
             config.kafka_socket_keepalive(),
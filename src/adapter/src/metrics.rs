@@ -13,7 +13,7 @@ use mz_ore::stats::{histogram_milliseconds_buckets, histogram_seconds_buckets};
 use mz_sql::ast::{AstInfo, Statement, StatementKind, SubscribeOutput};
 use mz_sql::session::user::User;
 use mz_sql_parser::ast::statement_kind_label_value;
-use prometheus::{HistogramVec, IntCounter, IntCounterVec, IntGaugeVec};
+use prometheus::{Histogram, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec};
 
 #[derive(Debug, Clone)]
 pub struct Metrics {
@@ -28,6 +28,9 @@ pub struct Metrics {
     pub storage_usage_collection_time_seconds: HistogramVec,
     pub subscribe_outputs: IntCounterVec,
     pub canceled_peeks: IntCounterVec,
+    pub canceled_dataflows: IntCounterVec,
+    pub cancellation_seconds: HistogramVec,
+    pub peek_result_cache_hits: IntCounterVec,
     pub linearize_message_seconds: HistogramVec,
     pub time_to_first_row_seconds: HistogramVec,
     pub statement_logging_unsampled_bytes: IntCounterVec,
@@ -39,6 +42,11 @@ pub struct Metrics {
     pub webhook_get_appender: IntCounter,
     pub check_scheduling_policies_seconds: HistogramVec,
     pub handle_scheduling_decisions_seconds: HistogramVec,
+    pub peek_plan_cache_lookups: IntCounterVec,
+    pub replica_health_transitions: IntCounterVec,
+    pub crash_loop_replica_replacements: IntCounter,
+    pub cluster_autoscaling_resizes: IntCounterVec,
+    pub cluster_autoscaling_seconds: Histogram,
 }
 
 impl Metrics {
@@ -99,6 +107,20 @@ impl Metrics {
                 name: "mz_canceled_peeks_total",
                 help: "The total number of canceled peeks since process start.",
             )),
+            canceled_dataflows: registry.register(metric!(
+                name: "mz_canceled_dataflows_total",
+                help: "The total number of one-shot dataflows torn down by cancellation since process start.",
+                var_labels: ["kind"],
+            )),
+            cancellation_seconds: registry.register(metric!(
+                name: "mz_cancellation_seconds",
+                help: "The time from a cancellation request to the coordinator tearing down the work it canceled.",
+                buckets: histogram_seconds_buckets(0.000_128, 32.0),
+            )),
+            peek_result_cache_hits: registry.register(metric!(
+                name: "mz_peek_result_cache_hits_total",
+                help: "The total number of peeks answered from the peek result cache since process start.",
+            )),
             linearize_message_seconds: registry.register(metric!(
                 name: "mz_linearize_message_seconds",
                 help: "The number of seconds it takes to linearize strict serializable messages",
@@ -156,6 +178,30 @@ impl Metrics {
                 var_labels: ["altered_a_cluster"],
                 buckets: histogram_seconds_buckets(0.000_128, 8.0),
             )),
+            peek_plan_cache_lookups: registry.register(metric!(
+                name: "mz_peek_plan_cache_lookups",
+                help: "The total number of peek plan cache lookups, by whether they missed or hit a local or global plan.",
+                var_labels: ["result"],
+            )),
+            replica_health_transitions: registry.register(metric!(
+                name: "mz_replica_health_transitions_total",
+                help: "The total number of cluster replica health transitions, by the new health.",
+                var_labels: ["health"],
+            )),
+            crash_loop_replica_replacements: registry.register(metric!(
+                name: "mz_crash_loop_replica_replacements_total",
+                help: "The total number of crash looping replicas that were automatically replaced.",
+            )),
+            cluster_autoscaling_resizes: registry.register(metric!(
+                name: "mz_cluster_autoscaling_resizes_total",
+                help: "The total number of cluster resizes by the autoscaler, by trigger.",
+                var_labels: ["trigger"],
+            )),
+            cluster_autoscaling_seconds: registry.register(metric!(
+                name: "mz_cluster_autoscaling_seconds",
+                help: "The time a run of the cluster autoscaler takes.",
+                buckets: histogram_seconds_buckets(0.000_128, 8.0),
+            )),
         }
     }
 }
@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeMap;
 use std::fmt;
 
 use chrono::{DateTime, Utc};
@@ -21,6 +22,8 @@ use mz_sql::ast::NoticeSeverity;
 use mz_sql::catalog::ErrorMessageObjectDescription;
 use mz_sql::plan::PlanNotice;
 use mz_sql::session::vars::IsolationLevel;
+use mz_transform::notice::{ActionKind, OptimizerNoticeKind};
+use serde_json::json;
 use tokio_postgres::error::SqlState;
 
 use crate::TimestampExplanation;
@@ -118,8 +121,11 @@ pub enum AdapterNotice {
     PlanNotice(PlanNotice),
     UnknownSessionDatabase(String),
     OptimizerNotice {
+        kind: OptimizerNoticeKind,
         notice: String,
         hint: String,
+        action: String,
+        action_kind: ActionKind,
     },
     WebhookSourceCreated {
         url: url::Url,
@@ -130,6 +136,7 @@ pub enum AdapterNotice {
     },
     VarDefaultUpdated {
         role: Option<String>,
+        database: Option<String>,
         var_name: Option<String>,
     },
     Welcome(String),
@@ -147,9 +154,197 @@ impl AdapterNotice {
             detail: self.detail(),
             hint: self.hint(),
             position: None,
+            structured: None,
         }
     }
 
+    /// Like [`AdapterNotice::into_response`], but additionally describes the
+    /// notice as a JSON object of its [`AdapterNotice::name`] and
+    /// [`AdapterNotice::fields`].
+    pub fn into_structured_response(self) -> ErrorResponse {
+        let structured = json!({
+            "name": self.name(),
+            "fields": self.fields(),
+        });
+        ErrorResponse {
+            structured: Some(structured.to_string()),
+            ..self.into_response()
+        }
+    }
+
+    /// Returns a stable, machine-readable name for the kind of notice.
+    ///
+    /// Unlike the SQLSTATE code, which many notices share, the name identifies
+    /// the notice uniquely. Names must not change once introduced.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AdapterNotice::DatabaseAlreadyExists { .. } => "database_already_exists",
+            AdapterNotice::SchemaAlreadyExists { .. } => "schema_already_exists",
+            AdapterNotice::TableAlreadyExists { .. } => "table_already_exists",
+            AdapterNotice::ObjectAlreadyExists { .. } => "object_already_exists",
+            AdapterNotice::DatabaseDoesNotExist { .. } => "database_does_not_exist",
+            AdapterNotice::ClusterDoesNotExist { .. } => "cluster_does_not_exist",
+            AdapterNotice::DefaultClusterDoesNotExist { .. } => "default_cluster_does_not_exist",
+            AdapterNotice::NoResolvableSearchPathSchema { .. } => {
+                "no_resolvable_search_path_schema"
+            }
+            AdapterNotice::ExistingTransactionInProgress => "existing_transaction_in_progress",
+            AdapterNotice::ExplicitTransactionControlInImplicitTransaction => {
+                "explicit_transaction_control_in_implicit_transaction"
+            }
+            AdapterNotice::UserRequested { .. } => "user_requested",
+            AdapterNotice::ClusterReplicaStatusChanged { .. } => "cluster_replica_status_changed",
+            AdapterNotice::CascadeDroppedObject { .. } => "cascade_dropped_object",
+            AdapterNotice::DroppedActiveDatabase { .. } => "dropped_active_database",
+            AdapterNotice::DroppedActiveCluster { .. } => "dropped_active_cluster",
+            AdapterNotice::QueryTimestamp { .. } => "query_timestamp",
+            AdapterNotice::EqualSubscribeBounds { .. } => "equal_subscribe_bounds",
+            AdapterNotice::QueryTrace { .. } => "query_trace",
+            AdapterNotice::UnimplementedIsolationLevel { .. } => "unimplemented_isolation_level",
+            AdapterNotice::StrongSessionSerializable => "strong_session_serializable",
+            AdapterNotice::BadStartupSetting { .. } => "bad_startup_setting",
+            AdapterNotice::RbacUserDisabled => "rbac_user_disabled",
+            AdapterNotice::RoleMembershipAlreadyExists { .. } => "role_membership_already_exists",
+            AdapterNotice::RoleMembershipDoesNotExists { .. } => "role_membership_does_not_exist",
+            AdapterNotice::AutoRunOnCatalogServerCluster => "auto_run_on_catalog_server_cluster",
+            AdapterNotice::AlterIndexOwner { .. } => "alter_index_owner",
+            AdapterNotice::CannotRevoke { .. } => "cannot_revoke",
+            AdapterNotice::NonApplicablePrivilegeTypes { .. } => "non_applicable_privilege_types",
+            AdapterNotice::PlanNotice(notice) => match notice {
+                PlanNotice::ObjectDoesNotExist { .. } => "object_does_not_exist",
+                PlanNotice::UpsertSinkKeyNotEnforced { .. } => "upsert_sink_key_not_enforced",
+            },
+            AdapterNotice::UnknownSessionDatabase(_) => "unknown_session_database",
+            AdapterNotice::OptimizerNotice { kind, .. } => kind.name(),
+            AdapterNotice::WebhookSourceCreated { .. } => "webhook_source_created",
+            AdapterNotice::DroppedInUseIndex(_) => "dropped_in_use_index",
+            AdapterNotice::PerReplicaLogRead { .. } => "per_replica_log_read",
+            AdapterNotice::VarDefaultUpdated { .. } => "var_default_updated",
+            AdapterNotice::Welcome(_) => "welcome",
+            AdapterNotice::PlanInsights(_) => "plan_insights",
+            AdapterNotice::IntrospectionClusterUsage => "introspection_cluster_usage",
+            AdapterNotice::AutoRouteIntrospectionQueriesUsage => {
+                "auto_route_introspection_queries_usage"
+            }
+        }
+    }
+
+    /// Returns the values the notice is about, in a machine-readable form.
+    pub fn fields(&self) -> BTreeMap<&'static str, serde_json::Value> {
+        let fields = match self {
+            AdapterNotice::DatabaseAlreadyExists { name }
+            | AdapterNotice::SchemaAlreadyExists { name }
+            | AdapterNotice::TableAlreadyExists { name }
+            | AdapterNotice::DatabaseDoesNotExist { name }
+            | AdapterNotice::ClusterDoesNotExist { name }
+            | AdapterNotice::DroppedActiveDatabase { name }
+            | AdapterNotice::DroppedActiveCluster { name }
+            | AdapterNotice::AlterIndexOwner { name }
+            | AdapterNotice::UnknownSessionDatabase(name) => vec![("name", json!(name))],
+            AdapterNotice::ObjectAlreadyExists { name, ty } => {
+                vec![("name", json!(name)), ("type", json!(ty))]
+            }
+            AdapterNotice::DefaultClusterDoesNotExist { name, kind, .. } => {
+                vec![("name", json!(name)), ("kind", json!(kind))]
+            }
+            AdapterNotice::NoResolvableSearchPathSchema { search_path } => {
+                vec![("search_path", json!(search_path))]
+            }
+            AdapterNotice::ClusterReplicaStatusChanged {
+                cluster,
+                replica,
+                status,
+                time,
+            } => vec![
+                ("cluster", json!(cluster)),
+                ("replica", json!(replica)),
+                ("status", json!(status.as_kebab_case_str())),
+                ("time", json!(time.to_rfc3339())),
+            ],
+            AdapterNotice::CascadeDroppedObject { objects } => vec![("objects", json!(objects))],
+            AdapterNotice::EqualSubscribeBounds { bound } => {
+                vec![("bound", json!(bound.to_string()))]
+            }
+            AdapterNotice::QueryTrace { trace_id } => {
+                vec![("trace_id", json!(trace_id.to_string()))]
+            }
+            AdapterNotice::UnimplementedIsolationLevel { isolation_level } => {
+                vec![("isolation_level", json!(isolation_level))]
+            }
+            AdapterNotice::BadStartupSetting { name, reason } => {
+                vec![("name", json!(name)), ("reason", json!(reason))]
+            }
+            AdapterNotice::RoleMembershipAlreadyExists {
+                role_name,
+                member_name,
+            }
+            | AdapterNotice::RoleMembershipDoesNotExists {
+                role_name,
+                member_name,
+            } => vec![("role", json!(role_name)), ("member", json!(member_name))],
+            AdapterNotice::CannotRevoke { object_description }
+            | AdapterNotice::NonApplicablePrivilegeTypes {
+                object_description, ..
+            } => vec![("object", json!(object_description.to_string()))],
+            AdapterNotice::PlanNotice(notice) => match notice {
+                PlanNotice::ObjectDoesNotExist { name, object_type } => vec![
+                    ("name", json!(name)),
+                    ("type", json!(object_type.to_string())),
+                ],
+                PlanNotice::UpsertSinkKeyNotEnforced { key, name } => vec![
+                    ("name", json!(name)),
+                    (
+                        "key",
+                        json!(key.iter().map(|k| k.as_str()).collect::<Vec<_>>()),
+                    ),
+                ],
+            },
+            AdapterNotice::OptimizerNotice {
+                action,
+                action_kind,
+                ..
+            } => match action_kind {
+                ActionKind::None => vec![],
+                _ => vec![
+                    ("action", json!(action)),
+                    ("action_kind", json!(action_kind.as_str())),
+                ],
+            },
+            AdapterNotice::WebhookSourceCreated { url } => vec![("url", json!(url.as_str()))],
+            AdapterNotice::DroppedInUseIndex(DroppedInUseIndex {
+                index_name,
+                dependant_objects,
+            }) => vec![
+                ("index", json!(index_name)),
+                ("dependent_objects", json!(dependant_objects)),
+            ],
+            AdapterNotice::PerReplicaLogRead { log_names } => {
+                vec![("log_names", json!(log_names))]
+            }
+            AdapterNotice::VarDefaultUpdated {
+                role,
+                database,
+                var_name,
+            } => vec![
+                ("role", json!(role)),
+                ("database", json!(database)),
+                ("variable", json!(var_name)),
+            ],
+            AdapterNotice::ExistingTransactionInProgress
+            | AdapterNotice::ExplicitTransactionControlInImplicitTransaction
+            | AdapterNotice::UserRequested { .. }
+            | AdapterNotice::QueryTimestamp { .. }
+            | AdapterNotice::StrongSessionSerializable
+            | AdapterNotice::RbacUserDisabled
+            | AdapterNotice::AutoRunOnCatalogServerCluster
+            | AdapterNotice::Welcome(_)
+            | AdapterNotice::PlanInsights(_)
+            | AdapterNotice::IntrospectionClusterUsage
+            | AdapterNotice::AutoRouteIntrospectionQueriesUsage => vec![],
+        };
+        fields.into_iter().collect()
+    }
+
     /// Returns the severity for a notice.
     pub fn severity(&self) -> Severity {
         match self {
@@ -243,7 +438,7 @@ impl AdapterNotice {
                  List available databases with SHOW DATABASES."
                     .into(),
             ),
-            AdapterNotice::OptimizerNotice { notice: _, hint } => Some(hint.clone()),
+            AdapterNotice::OptimizerNotice { hint, .. } => Some(hint.clone()),
             AdapterNotice::DroppedInUseIndex(..) => Some("To free up the resources used by the index, recreate all the above-mentioned objects.".into()),
             AdapterNotice::IntrospectionClusterUsage => Some("Use the new name instead.".into()),
             AdapterNotice::AutoRouteIntrospectionQueriesUsage => Some("Use the new name instead.".into()),
@@ -442,7 +637,7 @@ impl fmt::Display for AdapterNotice {
             AdapterNotice::UnknownSessionDatabase(name) => {
                 write!(f, "session database {} does not exist", name.quoted())
             }
-            AdapterNotice::OptimizerNotice { notice, hint: _ } => notice.fmt(f),
+            AdapterNotice::OptimizerNotice { notice, .. } => notice.fmt(f),
             AdapterNotice::WebhookSourceCreated { url } => {
                 write!(f, "URL to POST data is '{url}'")
             }
@@ -455,14 +650,19 @@ impl fmt::Display for AdapterNotice {
             AdapterNotice::PerReplicaLogRead { log_names } => {
                 write!(f, "Queried introspection relations: {}. Unlike other objects in Materialize, results from querying these objects depend on the current values of the `cluster` and `cluster_replica` session variables.", log_names.join(", "))
             }
-            AdapterNotice::VarDefaultUpdated { role, var_name } => {
+            AdapterNotice::VarDefaultUpdated {
+                role,
+                database,
+                var_name,
+            } => {
                 let vars = match var_name {
                     Some(name) => format!("variable {} was", name.quoted()),
                     None => "variables were".to_string(),
                 };
-                let target = match role {
-                    Some(role_name) => role_name.quoted().to_string(),
-                    None => "the system".to_string(),
+                let target = match (role, database) {
+                    (_, Some(database_name)) => format!("database {}", database_name.quoted()),
+                    (Some(role_name), None) => role_name.quoted().to_string(),
+                    (None, None) => "the system".to_string(),
                 };
                 write!(
                     f,
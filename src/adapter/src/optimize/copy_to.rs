@@ -237,6 +237,7 @@ impl<'s> Optimize<LocalMirPlan<Resolved<'s>>> for Optimizer {
                     upload_info: S3UploadInfo {
                         uri: self.copy_to_context.uri.to_string(),
                         max_file_size: self.copy_to_context.max_file_size,
+                        manifest: self.copy_to_context.manifest,
                         desc: self.copy_to_context.desc.clone(),
                         format: self.copy_to_context.format.clone(),
                     },
@@ -264,6 +265,7 @@ impl<'s> Optimize<LocalMirPlan<Resolved<'s>>> for Optimizer {
             up_to: Default::default(),
             // No `FORCE NOT NULL` for copy_to.
             non_null_assertions: Vec::new(),
+            assert_append_only: false,
             // No `REFRESH` for copy_to.
             refresh_schedule: None,
         };
@@ -324,6 +324,11 @@ impl<'a> DataflowBuilder<'a> {
         self.checked_recur(|_| {
             match self.catalog.get_entry(&id).item() {
                 CatalogItem::Source(source) => Ok(self.monotonic_source(source)),
+                // Retractions in append-only materialized views are reported as errors and never
+                // make it into the output.
+                CatalogItem::MaterializedView(MaterializedView {
+                    append_only: true, ..
+                }) => Ok(true),
                 CatalogItem::View(View { optimized_expr, .. })
                 | CatalogItem::MaterializedView(MaterializedView { optimized_expr, .. }) => {
                     let mut view_expr = optimized_expr.clone().into_inner();
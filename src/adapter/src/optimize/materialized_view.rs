@@ -67,6 +67,9 @@ pub struct Optimizer {
     /// Output columns that are asserted to be not null in the `CREATE VIEW`
     /// statement.
     non_null_assertions: Vec<usize>,
+    /// Whether the materialized view is asserted to never retract rows, in
+    /// which case its dataflow reports retractions as errors.
+    append_only: bool,
     /// Refresh schedule, e.g., `REFRESH EVERY '1 day'`
     refresh_schedule: Option<RefreshSchedule>,
     /// A human-readable name exposed internally (useful for debugging).
@@ -87,6 +90,7 @@ impl Optimizer {
         view_id: GlobalId,
         column_names: Vec<ColumnName>,
         non_null_assertions: Vec<usize>,
+        append_only: bool,
         refresh_schedule: Option<RefreshSchedule>,
         debug_name: String,
         config: OptimizerConfig,
@@ -100,6 +104,7 @@ impl Optimizer {
             view_id,
             column_names,
             non_null_assertions,
+            append_only,
             refresh_schedule,
             debug_name,
             config,
@@ -238,6 +243,7 @@ impl Optimize<LocalMirPlan> for Optimizer {
             with_snapshot: true,
             up_to: Antichain::default(),
             non_null_assertions: self.non_null_assertions.clone(),
+            assert_append_only: self.append_only,
             refresh_schedule: self.refresh_schedule.clone(),
         };
         df_desc.export_sink(self.sink_id, sink_description);
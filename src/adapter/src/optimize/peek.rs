@@ -159,7 +159,7 @@ pub struct Resolved<'s> {
 ///
 ///  MIR ⇒ LIR lowering and optimizing the resulting
 /// `DataflowDescription` with `LIR` plans.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct GlobalLirPlan {
     peek_plan: PeekPlan,
     df_meta: DataflowMetainfo,
@@ -196,6 +196,11 @@ impl Optimize<HirRelationExpr> for Optimizer {
 }
 
 impl LocalMirPlan<Unresolved> {
+    /// The optimized expression.
+    pub fn expr(&self) -> &MirRelationExpr {
+        &self.expr
+    }
+
     /// Produces the [`LocalMirPlan`] with [`Resolved`] contextual information
     /// required for the next stage.
     pub fn resolve(
@@ -410,6 +415,11 @@ impl<'s> Optimize<LocalMirPlan<Resolved<'s>>> for Optimizer {
 }
 
 impl GlobalLirPlan {
+    /// Whether the plan is a fast path plan, which doesn't need a dataflow.
+    pub fn is_fast_path(&self) -> bool {
+        matches!(self.peek_plan, PeekPlan::FastPath(_))
+    }
+
     /// Unwraps the parts of the final result of the optimization pipeline.
     pub fn unapply(self) -> (PeekPlan, DataflowMetainfo, RelationType) {
         (self.peek_plan, self.df_meta, self.typ)
@@ -216,6 +216,7 @@ impl Optimize<SubscribeFrom> for Optimizer {
                     up_to: self.up_to.map(Antichain::from_elem).unwrap_or_default(),
                     // No `FORCE NOT NULL` for subscribes
                     non_null_assertions: vec![],
+                    assert_append_only: false,
                     // No `REFRESH` for subscribes
                     refresh_schedule: None,
                 };
@@ -245,6 +246,7 @@ impl Optimize<SubscribeFrom> for Optimizer {
                     up_to: self.up_to.map(Antichain::from_elem).unwrap_or_default(),
                     // No `FORCE NOT NULL` for subscribes
                     non_null_assertions: vec![],
+                    assert_append_only: false,
                     // No `REFRESH` for subscribes
                     refresh_schedule: None,
                 };
@@ -351,6 +353,19 @@ impl Optimize<GlobalMirPlan<Resolved>> for Optimizer {
 }
 
 impl GlobalLirPlan {
+    /// Restricts the dataflow to the snapshot at its `as_of`, by setting the
+    /// `up_to` of its sink to the time after the `as_of`.
+    ///
+    /// Used for subscribes that receive their updates from a shared dataflow.
+    pub fn restrict_to_snapshot(&mut self) {
+        let as_of = self.as_of().expect("as_of must be set");
+        let up_to = Antichain::from_elem(as_of.step_forward());
+        for sink in self.df_desc.sink_exports.values_mut() {
+            sink.up_to = up_to.clone();
+        }
+        self.df_desc.until = up_to;
+    }
+
     /// Unwraps the parts of the final result of the optimization pipeline.
     pub fn unapply(self) -> (LirDataflowDescription, DataflowMetainfo) {
         (self.df_desc, self.df_meta)
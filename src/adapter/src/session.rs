@@ -15,6 +15,7 @@ use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
 use std::mem;
+use std::net::IpAddr;
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
@@ -22,6 +23,7 @@ use derivative::Derivative;
 use mz_adapter_types::connection::ConnectionId;
 use mz_build_info::{BuildInfo, DUMMY_BUILD_INFO};
 use mz_controller_types::ClusterId;
+use mz_expr::MirScalarExpr;
 use mz_ore::now::{EpochMillis, NowFn};
 use mz_pgwire_common::Format;
 use mz_repr::role_id::RoleId;
@@ -93,6 +95,7 @@ where
     next_transaction_id: TransactionId,
     secret_key: u32,
     external_metadata_rx: Option<watch::Receiver<ExternalUserMetadata>>,
+    client_ip: Option<IpAddr>,
     // Token allowing us to access `Arc<QCell<StatementLogging>>`
     // metadata. We want these to be reference-counted, because the same
     // statement might be referenced from multiple portals simultaneously.
@@ -175,6 +178,9 @@ pub struct SessionConfig {
     /// An optional receiver that the session will periodically check for
     /// updates to a user's external metadata.
     pub external_metadata_rx: Option<watch::Receiver<ExternalUserMetadata>>,
+    /// The IP address of the client, if the session is for a network
+    /// connection.
+    pub client_ip: Option<IpAddr>,
 }
 
 impl<T: TimestampManipulation> Session<T> {
@@ -235,6 +241,12 @@ impl<T: TimestampManipulation> Session<T> {
         self.uuid
     }
 
+    /// Returns the IP address of the client, if the session is for a network
+    /// connection.
+    pub fn client_ip(&self) -> Option<IpAddr> {
+        self.client_ip
+    }
+
     /// Creates a new dummy session.
     ///
     /// Dummy sessions are intended for use when executing queries on behalf of
@@ -246,6 +258,7 @@ impl<T: TimestampManipulation> Session<T> {
                 conn_id: DUMMY_CONNECTION_ID,
                 user: SYSTEM_USER.name.clone(),
                 external_metadata_rx: None,
+                client_ip: None,
             },
         );
         dummy.initialize_role_metadata(RoleId::User(0));
@@ -258,6 +271,7 @@ impl<T: TimestampManipulation> Session<T> {
             conn_id,
             user,
             mut external_metadata_rx,
+            client_ip,
         }: SessionConfig,
     ) -> Session<T> {
         let (notices_tx, notices_rx) = mpsc::unbounded_channel();
@@ -286,6 +300,7 @@ impl<T: TimestampManipulation> Session<T> {
             next_transaction_id: 0,
             secret_key: rand::thread_rng().gen(),
             external_metadata_rx,
+            client_ip,
             qcell_owner: QCellOwner::new(),
             session_oracles: BTreeMap::new(),
         }
@@ -322,6 +337,7 @@ impl<T: TimestampManipulation> Session<T> {
                 }
                 TransactionOps::None
                 | TransactionOps::Writes(_)
+                | TransactionOps::ReadWrite { .. }
                 | TransactionOps::SingleStatement { .. }
                 | TransactionOps::DDL { .. } => false,
             };
@@ -340,6 +356,7 @@ impl<T: TimestampManipulation> Session<T> {
                     ops: TransactionOps::None,
                     write_lock_guard: None,
                     access,
+                    as_of: None,
                     id,
                 });
             }
@@ -363,6 +380,22 @@ impl<T: TimestampManipulation> Session<T> {
         Ok(())
     }
 
+    /// Pins the reads of the current explicit transaction that do not specify
+    /// their own `AS OF` to `as_of`.
+    ///
+    /// Errors if the transaction has already executed any statements.
+    pub fn pin_transaction_as_of(&mut self, as_of: mz_repr::Timestamp) -> Result<(), AdapterError> {
+        match &mut self.transaction {
+            TransactionStatus::InTransaction(txn) if matches!(txn.ops, TransactionOps::None) => {
+                txn.as_of = Some(as_of);
+                Ok(())
+            }
+            _ => Err(AdapterError::Unsupported(
+                "BEGIN ... AS OF after statements in the same transaction",
+            )),
+        }
+    }
+
     /// Starts either a single statement or implicit transaction based on the
     /// number of statements, but only if no transaction has been started already.
     pub fn start_transaction_implicit(&mut self, wall_time: DateTime<Utc>, stmts: usize) {
@@ -374,6 +407,7 @@ impl<T: TimestampManipulation> Session<T> {
                 ops: TransactionOps::None,
                 write_lock_guard: None,
                 access: None,
+                as_of: None,
                 id,
             };
             match stmts {
@@ -669,6 +703,7 @@ impl<T: TimestampManipulation> Session<T> {
                 result_formats: result_formats.into_iter().map(Into::into).collect(),
                 state: PortalState::NotStarted,
                 logging,
+                is_cursor: false,
             },
         );
         Ok(())
@@ -720,6 +755,7 @@ impl<T: TimestampManipulation> Session<T> {
                         result_formats,
                         state: PortalState::NotStarted,
                         logging,
+                        is_cursor: false,
                     });
                     return Ok(name);
                 }
@@ -729,12 +765,17 @@ impl<T: TimestampManipulation> Session<T> {
         coord_bail!("unable to create a new portal");
     }
 
-    /// Resets the session to its initial state. Returns sinks that need to be
-    /// dropped.
-    pub fn reset(&mut self) {
+    /// Resets the session to its initial state, clearing its transaction,
+    /// portals, and prepared statements. Returns the session parameters that
+    /// changed.
+    pub fn reset(&mut self) -> BTreeMap<&'static str, String> {
         let _ = self.clear_transaction();
         self.prepared_statements.clear();
-        self.vars.reset_all();
+        let changed = self.vars.reset_all();
+        // The transaction is gone, so apply the reset now rather than leaving
+        // it staged for whichever transaction ends next, which might roll back.
+        let _ = self.vars.end_transaction(EndTransactionAction::Commit);
+        changed
     }
 
     /// Returns the [application_name] that created this session.
@@ -883,6 +924,9 @@ pub struct Portal {
     /// The execution state of the portal.
     #[derivative(Debug = "ignore")]
     pub state: PortalState,
+    /// Whether the portal was created by `DECLARE`, and its rows are thus
+    /// consumed incrementally by `FETCH`.
+    pub is_cursor: bool,
 }
 
 /// Execution states of a portal.
@@ -892,6 +936,17 @@ pub enum PortalState {
     /// Portal is a rows-returning statement in progress with 0 or more rows
     /// remaining.
     InProgress(Option<InProgressRows>),
+    /// Portal is a `FETCH` that was executed with a row limit smaller than its
+    /// count, and has been suspended with rows left to send from its cursor.
+    SuspendedFetch {
+        /// The name of the cursor the rows are fetched from.
+        cursor: String,
+        /// The number of rows the `FETCH` has left to send, or `None` for a
+        /// `FETCH ALL`.
+        remaining: Option<usize>,
+        /// The number of rows the `FETCH` has sent so far.
+        sent: usize,
+    },
     /// Portal has completed and should not be re-executed. If the optional string
     /// is present, it is returned as a CommandComplete tag, otherwise an error
     /// is sent.
@@ -1005,9 +1060,22 @@ impl<T: TimestampManipulation> TransactionStatus<T> {
         }
     }
 
-    /// Whether the transaction is in a multi-statement, immediate transaction.
+    /// Whether the transaction is in a multi-statement, immediate transaction,
+    /// or in a multi-statement transaction whose reads are pinned to `when`.
     pub fn in_immediate_multi_stmt_txn(&self, when: &QueryWhen) -> bool {
-        self.is_in_multi_statement_transaction() && when == &QueryWhen::Immediately
+        self.is_in_multi_statement_transaction()
+            && (when == &QueryWhen::Immediately || self.pinned_when().as_ref() == Some(when))
+    }
+
+    /// Returns the `AS OF` that the reads of the transaction that do not
+    /// specify their own are pinned to, if the transaction was started with
+    /// `BEGIN ... AS OF`.
+    pub fn pinned_when(&self) -> Option<QueryWhen> {
+        let as_of = self.inner()?.as_of?;
+        Some(QueryWhen::AtTimestamp(MirScalarExpr::literal_ok(
+            Datum::MzTimestamp(as_of),
+            ScalarType::MzTimestamp,
+        )))
     }
 
     /// Grants the write lock to the inner transaction.
@@ -1094,13 +1162,16 @@ impl<T: TimestampManipulation> TransactionStatus<T> {
                         determination,
                         cluster_id,
                         requires_linearization,
+                        table_ids,
                     } => match add_ops {
                         TransactionOps::Peeks {
                             determination: add_timestamp_determination,
                             cluster_id: add_cluster_id,
                             requires_linearization: add_requires_linearization,
+                            table_ids: add_table_ids,
                         } => {
                             assert_eq!(*cluster_id, add_cluster_id);
+                            table_ids.extend(add_table_ids);
                             match (
                                 &determination.timestamp_context,
                                 &add_timestamp_determination.timestamp_context,
@@ -1135,13 +1206,33 @@ impl<T: TimestampManipulation> TransactionStatus<T> {
                             }
                         }
                         // Iff peeks thus far do not have a timestamp (i.e.
-                        // they are constant), we can switch to a write
-                        // transaction.
+                        // they are constant, or use AS OF), we can switch to a
+                        // write transaction, unless it is read only.
                         writes @ TransactionOps::Writes(..)
-                            if !determination.timestamp_context.contains_timestamp() =>
+                            if !determination.timestamp_context.contains_timestamp()
+                                && !matches!(access, Some(TransactionAccessMode::ReadOnly)) =>
                         {
                             *ops = writes;
                         }
+                        // Explicit `READ WRITE` transactions can write after
+                        // reading at a timestamp. Their reads are validated
+                        // against the writes of other transactions when they
+                        // commit.
+                        TransactionOps::Writes(writes)
+                            if matches!(access, Some(TransactionAccessMode::ReadWrite)) =>
+                        {
+                            let read_ts = determination
+                                .timestamp_context
+                                .timestamp()
+                                .expect("peeks without a timestamp handled above")
+                                .clone();
+                            let table_ids = mem::take(table_ids);
+                            *ops = TransactionOps::ReadWrite {
+                                read_ts,
+                                table_ids,
+                                writes,
+                            };
+                        }
                         _ => return Err(AdapterError::ReadOnlyTransaction),
                     },
                     TransactionOps::Subscribe => {
@@ -1152,17 +1243,11 @@ impl<T: TimestampManipulation> TransactionStatus<T> {
                             // We should have already checked the access above, but make sure we don't miss
                             // it anyway.
                             assert!(!matches!(access, Some(TransactionAccessMode::ReadOnly)));
-                            txn_writes.append(&mut add_writes);
 
-                            if txn_writes
-                                .iter()
-                                .map(|op| op.id)
-                                .collect::<BTreeSet<_>>()
-                                .len()
-                                > 1
-                            {
-                                return Err(AdapterError::MultiTableWriteTransaction);
-                            }
+                            // Writes may target any number of tables. Group
+                            // commit appends them all atomically at the
+                            // transaction's write timestamp.
+                            txn_writes.append(&mut add_writes);
                         }
                         // Iff peeks do not have a timestamp (i.e. they are
                         // constant), we can permit them.
@@ -1172,6 +1257,21 @@ impl<T: TimestampManipulation> TransactionStatus<T> {
                             return Err(AdapterError::WriteOnlyTransaction);
                         }
                     },
+                    TransactionOps::ReadWrite {
+                        writes: txn_writes, ..
+                    } => match add_ops {
+                        TransactionOps::Writes(mut add_writes) => {
+                            txn_writes.append(&mut add_writes);
+                        }
+                        // Reads after the first write would not observe the
+                        // transaction's own writes, so only constant reads are
+                        // permitted.
+                        TransactionOps::Peeks { determination, .. }
+                            if !determination.timestamp_context.contains_timestamp() => {}
+                        _ => {
+                            return Err(AdapterError::WriteOnlyTransaction);
+                        }
+                    },
                     TransactionOps::SingleStatement { .. } => {
                         return Err(AdapterError::SingleStatementTransaction)
                     }
@@ -1230,6 +1330,9 @@ pub struct Transaction<T> {
     write_lock_guard: Option<OwnedMutexGuard<()>>,
     /// Access mode (read only, read write).
     access: Option<TransactionAccessMode>,
+    /// The timestamp that reads without an `AS OF` are pinned to, set by
+    /// `BEGIN ... AS OF`.
+    as_of: Option<mz_repr::Timestamp>,
 }
 
 impl<T> Transaction<T> {
@@ -1253,6 +1356,7 @@ impl<T> Transaction<T> {
             | TransactionOps::None
             | TransactionOps::Subscribe
             | TransactionOps::Writes(_)
+            | TransactionOps::ReadWrite { .. }
             | TransactionOps::SingleStatement { .. }
             | TransactionOps::DDL { .. } => None,
         }
@@ -1265,6 +1369,7 @@ impl<T> Transaction<T> {
             TransactionOps::None
             | TransactionOps::Subscribe
             | TransactionOps::Writes(_)
+            | TransactionOps::ReadWrite { .. }
             | TransactionOps::SingleStatement { .. }
             | TransactionOps::DDL { .. } => None,
         }
@@ -1308,7 +1413,11 @@ impl<T> From<&TransactionStatus<T>> for TransactionCode {
     fn from(status: &TransactionStatus<T>) -> TransactionCode {
         match status {
             TransactionStatus::Default => TransactionCode::Idle,
-            TransactionStatus::Started(_) => TransactionCode::InTransaction,
+            // A single statement transaction is not a transaction block, so
+            // like PostgreSQL's `TBLOCK_STARTED` it is reported as idle.
+            // Connection poolers rely on this to know when a server
+            // connection can be handed to another client.
+            TransactionStatus::Started(_) => TransactionCode::Idle,
             TransactionStatus::InTransaction(_) => TransactionCode::InTransaction,
             TransactionStatus::InTransactionImplicit(_) => TransactionCode::InTransaction,
             TransactionStatus::Failed(_) => TransactionCode::Failed,
@@ -1327,9 +1436,9 @@ pub enum TransactionOps<T> {
     /// in it.
     None,
     /// This transaction has had a peek (`SELECT`, `SUBSCRIBE`). If the inner value
-    /// is has a timestamp, it must only do other peeks. However, if it doesn't
-    /// have a timestamp (i.e. the values are constants), the transaction can still
-    /// perform writes.
+    /// is has a timestamp, it must only do other peeks, unless it is an explicit
+    /// `READ WRITE` transaction. However, if it doesn't have a timestamp (i.e.
+    /// the values are constants), the transaction can still perform writes.
     Peeks {
         /// The timestamp and timestamp related metadata for the peek.
         determination: TimestampDetermination<T>,
@@ -1337,12 +1446,26 @@ pub enum TransactionOps<T> {
         cluster_id: ClusterId,
         /// Whether this peek needs to be linearized.
         requires_linearization: RequireLinearization,
+        /// The tables the peeks read, directly or through views and indexes.
+        table_ids: BTreeSet<GlobalId>,
     },
     /// This transaction has done a `SUBSCRIBE` and must do nothing else.
     Subscribe,
     /// This transaction has had a write (`INSERT`, `UPDATE`, `DELETE`) and must
     /// only do other writes, or reads whose timestamp is None (i.e. constants).
     Writes(Vec<WriteOp>),
+    /// This `READ WRITE` transaction has had peeks at a timestamp followed by
+    /// writes, and must only do other writes, or reads whose timestamp is None.
+    /// It fails to commit if another transaction has written to one of the
+    /// tables it read since `read_ts`.
+    ReadWrite {
+        /// The timestamp of the transaction's peeks.
+        read_ts: T,
+        /// The tables the transaction's peeks read.
+        table_ids: BTreeSet<GlobalId>,
+        /// The transaction's writes.
+        writes: Vec<WriteOp>,
+    },
     /// This transaction has a prospective statement that will execute during commit.
     SingleStatement {
         /// The prospective statement.
@@ -1368,6 +1491,7 @@ impl<T> TransactionOps<T> {
             TransactionOps::None
             | TransactionOps::Subscribe
             | TransactionOps::Writes(_)
+            | TransactionOps::ReadWrite { .. }
             | TransactionOps::SingleStatement { .. }
             | TransactionOps::DDL { .. } => None,
         }
@@ -20,6 +20,8 @@ use crate::{AdapterError, ExecuteResponse};
 #[derive(Clone, Debug)]
 pub enum StatementLifecycleEvent {
     ExecutionBegan,
+    PlanningFinished,
+    TimestampSelected,
     OptimizationFinished,
     StorageDependenciesFinished,
     ComputeDependenciesFinished,
@@ -30,6 +32,8 @@ impl StatementLifecycleEvent {
     pub fn as_str(&self) -> &str {
         match self {
             Self::ExecutionBegan => "execution-began",
+            Self::PlanningFinished => "planning-finished",
+            Self::TimestampSelected => "timestamp-selected",
             Self::OptimizationFinished => "optimization-finished",
             Self::StorageDependenciesFinished => "storage-dependencies-finished",
             Self::ComputeDependenciesFinished => "compute-dependencies-finished",
@@ -71,6 +75,9 @@ pub enum StatementExecutionStrategy {
     /// The statement was determined to be constant by
     /// environmentd, and not sent to a cluster.
     Constant,
+    /// The result of an identical fast-path peek at the same timestamp
+    /// was reused, and nothing was sent to a cluster.
+    Cached,
 }
 
 impl StatementExecutionStrategy {
@@ -80,6 +87,7 @@ impl StatementExecutionStrategy {
             Self::FastPath => "fast-path",
             Self::PersistFastPath => "persist-fast-path",
             Self::Constant => "constant",
+            Self::Cached => "cached",
         }
     }
 }
@@ -169,7 +177,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             ExecuteResponse::Fetch { .. } => {
                 panic!("FETCHes terminate after a follow-up message is sent.")
             }
-            ExecuteResponse::SendingRows { .. } => {
+            ExecuteResponse::SendingRows { .. } | ExecuteResponse::SendingRowsStreaming { .. } => {
                 panic!("SELECTs terminate on peek finalization, not here.")
             }
             ExecuteResponse::Subscribing { .. } => {
@@ -209,7 +217,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::DeclaredCursor
             | ExecuteResponse::Deleted(_)
             | ExecuteResponse::DiscardedTemp
-            | ExecuteResponse::DiscardedAll
+            | ExecuteResponse::DiscardedAll { .. }
             | ExecuteResponse::DroppedObject(_)
             | ExecuteResponse::DroppedOwned
             | ExecuteResponse::EmptyQuery
@@ -221,17 +229,15 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::ReassignOwned
             | ExecuteResponse::RevokedPrivilege
             | ExecuteResponse::RevokedRole
+            | ExecuteResponse::ResetAllVariables { .. }
             | ExecuteResponse::SetVariable { .. }
             | ExecuteResponse::StartedTransaction
             | ExecuteResponse::TransactionCommitted { .. }
             | ExecuteResponse::TransactionRolledBack { .. }
-            | ExecuteResponse::Updated(_)
-            | ExecuteResponse::ValidatedConnection { .. } => {
-                StatementEndedExecutionReason::Success {
-                    rows_returned: None,
-                    execution_strategy: None,
-                }
-            }
+            | ExecuteResponse::Updated(_) => StatementEndedExecutionReason::Success {
+                rows_returned: None,
+                execution_strategy: None,
+            },
         }
     }
 }
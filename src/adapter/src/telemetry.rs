@@ -148,6 +148,7 @@ pub fn analyze_audited_statement(
         StatementKind::AlterConnection => Some((StatementAction::Alter, ObjectType::Connection)),
         StatementKind::AlterIndex => Some((StatementAction::Alter, ObjectType::Index)),
         StatementKind::AlterRole => Some((StatementAction::Alter, ObjectType::Role)),
+        StatementKind::AlterDatabase => Some((StatementAction::Alter, ObjectType::Database)),
         StatementKind::AlterSecret => Some((StatementAction::Alter, ObjectType::Secret)),
         StatementKind::AlterSource => Some((StatementAction::Alter, ObjectType::Source)),
         StatementKind::CreateCluster => Some((StatementAction::Create, ObjectType::Cluster)),
@@ -8,7 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::BTreeMap;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use anyhow::Context;
@@ -36,6 +36,8 @@ pub enum AppendWebhookError {
     InvalidUtf8Body { msg: String },
     #[error("the provided request body is not valid JSON: {msg}")]
     InvalidJsonBody { msg: String },
+    #[error("the provided request body contains {rows} rows, which exceeds the limit of {limit}")]
+    TooManyRows { rows: usize, limit: usize },
     #[error("webhook source '{database}.{schema}.{name}' does not exist")]
     UnknownWebhook {
         database: String,
@@ -241,6 +243,8 @@ pub struct AppendWebhookResponse {
     /// Expression used to validate a webhook request.
     #[derivative(Debug = "ignore")]
     pub validator: Option<AppendWebhookValidator>,
+    /// Maximum number of rows a single request can append.
+    pub row_limit: WebhookRowLimit,
 }
 
 /// A wrapper around [`MonotonicAppender`] that can get closed by the `Coordinator` if the webhook
@@ -425,6 +429,29 @@ impl Default for WebhookConcurrencyLimiter {
     }
 }
 
+/// The maximum number of rows a single webhook request may append.
+///
+/// Shared between the `Coordinator` and every [`AppendWebhookResponse`] so that changes to the
+/// limit apply to cached [`WebhookAppender`]s too.
+#[derive(Debug, Clone)]
+pub struct WebhookRowLimit(Arc<AtomicUsize>);
+
+impl WebhookRowLimit {
+    pub fn new(limit: usize) -> Self {
+        WebhookRowLimit(Arc::new(AtomicUsize::new(limit)))
+    }
+
+    /// Returns the current limit.
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Updates the limit for all holders of this [`WebhookRowLimit`].
+    pub fn set(&self, limit: usize) {
+        self.0.store(limit, Ordering::Relaxed);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::WebhookConcurrencyLimiter;
@@ -96,6 +96,7 @@ async fn datadriven() {
                                             )),
                                             desc: RelationDesc::empty(),
                                             defaults: vec![Expr::null(); 0],
+                                            checks: Vec::new(),
                                             conn_id: None,
                                             resolved_ids: ResolvedIds(BTreeSet::new()),
                                             custom_logical_compaction_window: None,
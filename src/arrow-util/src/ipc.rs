@@ -0,0 +1,169 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Encoding of [`Row`]s as an Arrow IPC stream.
+//!
+//! See <https://arrow.apache.org/docs/format/Columnar.html#ipc-streaming-format>.
+
+use arrow::ipc::writer::StreamWriter;
+use mz_repr::{RelationDesc, Row};
+
+use crate::builder::ArrowBuilder;
+
+/// The media type of an Arrow IPC stream.
+pub const ARROW_STREAM_CONTENT_TYPE: &str = "application/vnd.apache.arrow.stream";
+
+/// Incrementally encodes [`Row`]s as an Arrow IPC stream.
+///
+/// Rows are buffered until [`IpcStreamEncoder::flush`] encodes them as a
+/// record batch. The encoded bytes accumulate in the encoder until they are
+/// taken with [`IpcStreamEncoder::take_bytes`], so that callers can forward
+/// the stream in chunks as it is produced.
+pub struct IpcStreamEncoder {
+    desc: RelationDesc,
+    builder: ArrowBuilder,
+    pending_rows: usize,
+    writer: StreamWriter<Vec<u8>>,
+}
+
+impl std::fmt::Debug for IpcStreamEncoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IpcStreamEncoder")
+            .field("desc", &self.desc)
+            .field("pending_rows", &self.pending_rows)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IpcStreamEncoder {
+    /// Initial capacity, in rows, of the column builders of each batch.
+    const ITEM_CAPACITY: usize = 1024;
+
+    /// Returns a new encoder for rows of the given relation.
+    ///
+    /// The schema message that starts the stream is encoded immediately.
+    /// Errors if the relation contains a type that can't be encoded in Arrow.
+    pub fn new(desc: &RelationDesc) -> Result<Self, anyhow::Error> {
+        let builder = ArrowBuilder::new(desc, Self::ITEM_CAPACITY, 0)?;
+        let writer = StreamWriter::try_new(Vec::new(), &builder.schema())?;
+        Ok(IpcStreamEncoder {
+            desc: desc.clone(),
+            builder,
+            pending_rows: 0,
+            writer,
+        })
+    }
+
+    /// Buffers a row for the next record batch.
+    pub fn add_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        self.builder.add_row(row)?;
+        self.pending_rows += 1;
+        Ok(())
+    }
+
+    /// Returns an estimate of the size of the buffered rows, in bytes.
+    pub fn pending_bytes(&self) -> usize {
+        self.builder.row_size_bytes()
+    }
+
+    /// Encodes the buffered rows, if any, as a record batch.
+    pub fn flush(&mut self) -> Result<(), anyhow::Error> {
+        if self.pending_rows == 0 {
+            return Ok(());
+        }
+        let builder = std::mem::replace(
+            &mut self.builder,
+            ArrowBuilder::new(&self.desc, Self::ITEM_CAPACITY, 0)?,
+        );
+        self.pending_rows = 0;
+        self.writer.write(&builder.to_record_batch()?)?;
+        Ok(())
+    }
+
+    /// Takes the bytes of the stream encoded so far.
+    pub fn take_bytes(&mut self) -> Vec<u8> {
+        std::mem::take(self.writer.get_mut())
+    }
+
+    /// Encodes any buffered rows and the end of the stream, and returns the
+    /// remaining bytes of the stream.
+    pub fn finish(mut self) -> Result<Vec<u8>, anyhow::Error> {
+        self.flush()?;
+        self.writer.finish()?;
+        Ok(self.take_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Array, Int64Array, StringArray};
+    use arrow::ipc::reader::StreamReader;
+    use mz_repr::{Datum, ScalarType};
+
+    use super::*;
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // slow
+    fn test_ipc_stream_roundtrip() {
+        let desc = RelationDesc::empty()
+            .with_column("a", ScalarType::Int64.nullable(false))
+            .with_column("b", ScalarType::String.nullable(true));
+        let expected: Vec<_> = (0..10)
+            .map(|i| {
+                let b = if i % 2 == 0 {
+                    Datum::Null
+                } else {
+                    Datum::String("odd")
+                };
+                Row::pack_slice(&[Datum::Int64(i), b])
+            })
+            .collect();
+
+        // Encode the rows in two batches, taking the bytes in chunks.
+        let mut encoder = IpcStreamEncoder::new(&desc).unwrap();
+        let mut buf = encoder.take_bytes();
+        assert!(!buf.is_empty(), "schema is encoded eagerly");
+        for (i, row) in expected.iter().enumerate() {
+            encoder.add_row(row).unwrap();
+            if i == 4 {
+                encoder.flush().unwrap();
+                buf.extend(encoder.take_bytes());
+            }
+        }
+        buf.extend(encoder.finish().unwrap());
+
+        let reader = StreamReader::try_new(buf.as_slice(), None).unwrap();
+        let mut actual = vec![];
+        let mut batches = 0;
+        for batch in reader {
+            let batch = batch.unwrap();
+            let a = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap();
+            let b = batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap();
+            for i in 0..batch.num_rows() {
+                let b = if b.is_null(i) {
+                    Datum::Null
+                } else {
+                    Datum::String(b.value(i))
+                };
+                actual.push(Row::pack_slice(&[Datum::Int64(a.value(i)), b]));
+            }
+            batches += 1;
+        }
+        assert_eq!(batches, 2);
+        assert_eq!(actual, expected);
+    }
+}
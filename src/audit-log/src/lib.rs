@@ -178,6 +178,8 @@ pub enum EventDetails {
     AlterRetainHistoryV1(AlterRetainHistoryV1),
     ToNewIdV1(ToNewIdV1),
     FromPreviousIdV1(FromPreviousIdV1),
+    AutoscaleClusterV1(AutoscaleClusterV1),
+    ReplicaHealthV1(ReplicaHealthV1),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
@@ -360,6 +362,36 @@ pub struct AlterSetClusterV1 {
     pub new_cluster: Option<String>,
 }
 
+/// A resize of a managed cluster by the cluster autoscaler.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
+pub struct AutoscaleClusterV1 {
+    pub cluster_id: String,
+    pub cluster_name: String,
+    pub old_size: String,
+    pub new_size: String,
+    /// What made the autoscaler resize the cluster, e.g., `memory-pressure`.
+    pub trigger: String,
+}
+
+/// A change of the health or the hydration status of a cluster replica.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
+pub struct ReplicaHealthV1 {
+    pub cluster_id: String,
+    pub cluster_name: String,
+    pub replica_id: String,
+    pub replica_name: String,
+    /// The health before the change, e.g., `healthy`.
+    pub old_health: String,
+    /// The health after the change, e.g., `crash-looping`.
+    pub new_health: String,
+    /// Why the replica is not healthy, if known, e.g., `oom-killed`.
+    pub reason: Option<String>,
+    /// Whether the replica has hydrated all of its compute collections.
+    pub hydrated: bool,
+    /// When the controller last heard from the replica, in milliseconds since the Unix epoch.
+    pub last_heartbeat: Option<u64>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
 pub struct GrantRoleV1 {
     pub role_id: String,
@@ -506,6 +538,8 @@ impl EventDetails {
             }
             EventDetails::ToNewIdV1(v) => serde_json::to_value(v).expect("must serialize"),
             EventDetails::FromPreviousIdV1(v) => serde_json::to_value(v).expect("must serialize"),
+            EventDetails::AutoscaleClusterV1(v) => serde_json::to_value(v).expect("must serialize"),
+            EventDetails::ReplicaHealthV1(v) => serde_json::to_value(v).expect("must serialize"),
         }
     }
 }
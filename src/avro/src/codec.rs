@@ -47,6 +47,10 @@ pub enum Codec {
     /// compression library. Each compressed block is followed by the 4-byte, big-endian
     /// CRC32 checksum of the uncompressed data in the block.
     Snappy,
+    #[cfg(feature = "zstandard")]
+    /// The `Zstandard` codec uses Facebook's [Zstandard](https://facebook.github.io/zstd/)
+    /// compression library.
+    Zstandard,
 }
 
 impl ToAvro for Codec {
@@ -57,6 +61,8 @@ impl ToAvro for Codec {
                 Codec::Deflate => "deflate",
                 #[cfg(feature = "snappy")]
                 Codec::Snappy => "snappy",
+                #[cfg(feature = "zstandard")]
+                Codec::Zstandard => "zstandard",
             }
             .to_owned()
             .into_bytes(),
@@ -73,6 +79,8 @@ impl FromStr for Codec {
             "deflate" => Ok(Codec::Deflate),
             #[cfg(feature = "snappy")]
             "snappy" => Ok(Codec::Snappy),
+            #[cfg(feature = "zstandard")]
+            "zstandard" => Ok(Codec::Zstandard),
             other => Err(DecodeError::UnrecognizedCodec(other.to_string()).into()),
         }
     }
@@ -106,6 +114,10 @@ impl Codec {
 
                 *stream = encoded;
             }
+            #[cfg(feature = "zstandard")]
+            Codec::Zstandard => {
+                *stream = zstd::stream::encode_all(&stream[..], 0)?;
+            }
         };
 
         Ok(())
@@ -113,13 +125,28 @@ impl Codec {
 
     /// Decompress a stream of bytes in-place.
     pub fn decompress(self, stream: &mut Vec<u8>) -> Result<(), AvroError> {
+        self.decompress_with_limit(stream, usize::MAX)
+    }
+
+    /// Decompress a stream of bytes in-place, failing if the decompressed
+    /// bytes would exceed `limit`.
+    pub fn decompress_with_limit(
+        self,
+        stream: &mut Vec<u8>,
+        limit: usize,
+    ) -> Result<(), AvroError> {
         match self {
             Codec::Null => (),
             Codec::Deflate => {
                 let mut decoded = Vec::new();
                 {
-                    let mut decoder = DeflateDecoder::new(&**stream);
-                    decoder.read_to_end(&mut decoded)?;
+                    let decoder = DeflateDecoder::new(&**stream);
+                    decoder
+                        .take((limit as u64).saturating_add(1))
+                        .read_to_end(&mut decoded)?;
+                }
+                if decoded.len() > limit {
+                    return Err(DecodeError::BlockTooLarge { limit }.into());
                 }
                 *stream = decoded;
             }
@@ -127,8 +154,14 @@ impl Codec {
             Codec::Snappy => {
                 use byteorder::ByteOrder;
 
+                if stream.len() < 4 {
+                    return Err(DecodeError::MissingSnappyChecksum.into());
+                }
                 let decompressed_size = snap::raw::decompress_len(&stream[..stream.len() - 4])
                     .map_err(std::io::Error::from)?;
+                if decompressed_size > limit {
+                    return Err(DecodeError::BlockTooLarge { limit }.into());
+                }
                 let mut decoded = vec![0; decompressed_size];
                 snap::raw::Decoder::new()
                     .decompress(&stream[..stream.len() - 4], &mut decoded[..])
@@ -150,6 +183,17 @@ impl Codec {
                 }
                 *stream = decoded;
             }
+            #[cfg(feature = "zstandard")]
+            Codec::Zstandard => {
+                let mut decoded = Vec::new();
+                zstd::stream::read::Decoder::new(&**stream)?
+                    .take((limit as u64).saturating_add(1))
+                    .read_to_end(&mut decoded)?;
+                if decoded.len() > limit {
+                    return Err(DecodeError::BlockTooLarge { limit }.into());
+                }
+                *stream = decoded;
+            }
         };
 
         Ok(())
@@ -195,4 +239,50 @@ mod tests {
         codec.decompress(&mut stream).unwrap();
         assert_eq!(INPUT, stream.as_slice());
     }
+
+    #[cfg(feature = "snappy")]
+    #[mz_ore::test]
+    fn snappy_bad_checksum() {
+        let codec = Codec::Snappy;
+        let mut stream = INPUT.to_vec();
+        codec.compress(&mut stream).unwrap();
+        let last = stream.len() - 1;
+        stream[last] ^= 0xff;
+        assert!(codec.decompress(&mut stream).is_err());
+
+        let mut short = vec![0; 3];
+        assert!(codec.decompress(&mut short).is_err());
+    }
+
+    #[cfg(feature = "zstandard")]
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `ZSTD_compressStream2` on OS `linux`
+    fn zstandard_compress_and_decompress() {
+        let codec = Codec::Zstandard;
+        let mut stream = INPUT.to_vec();
+        codec.compress(&mut stream).unwrap();
+        assert_ne!(INPUT, stream.as_slice());
+        assert!(INPUT.len() > stream.len());
+        codec.decompress(&mut stream).unwrap();
+        assert_eq!(INPUT, stream.as_slice());
+
+        codec.compress(&mut stream).unwrap();
+        assert!(codec.decompress_with_limit(&mut stream, 16).is_err());
+    }
+
+    #[mz_ore::test]
+    fn codec_names() {
+        let mut codecs = vec![Codec::Null, Codec::Deflate];
+        #[cfg(feature = "snappy")]
+        codecs.push(Codec::Snappy);
+        #[cfg(feature = "zstandard")]
+        codecs.push(Codec::Zstandard);
+        for codec in codecs {
+            let Value::Bytes(name) = codec.avro() else {
+                panic!("codec name is not bytes");
+            };
+            let name = String::from_utf8(name).unwrap();
+            assert_eq!(name.parse::<Codec>().unwrap(), codec);
+        }
+    }
 }
@@ -22,9 +22,10 @@
 // of which can be found in the LICENSE file at the root of this repository.
 
 use std::cmp;
+use std::collections::BTreeMap;
 use std::fmt::{self, Display};
 use std::fs::File;
-use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::io::{self, Cursor, Read, Seek, SeekFrom, Take};
 
 use chrono::{DateTime, NaiveDate};
 use flate2::read::MultiGzDecoder;
@@ -34,7 +35,7 @@ use crate::schema::{
     RecordField, ResolvedDefaultValueField, ResolvedRecordField, SchemaNode, SchemaPiece,
     SchemaPieceOrNamed,
 };
-use crate::types::{Scalar, Value};
+use crate::types::{Scalar, Value, ValueRef};
 use crate::util::{safe_len, zag_i32, zag_i64, TsUnit};
 use crate::{TrivialDecoder, ValueDecoder};
 
@@ -243,6 +244,8 @@ impl<T: AsRef<[u8]>> Skip for Cursor<T> {
 
 impl<R: Read> Skip for MultiGzDecoder<R> {}
 
+impl<R: Read> Skip for Take<R> {}
+
 pub enum ValueOrReader<'a, V, R: AvroRead> {
     Value(V),
     Reader { len: usize, r: &'a mut R },
@@ -1228,6 +1231,174 @@ pub mod public_decoders {
             Ok(Value::Map(entries))
         }
     }
+
+    /// A set of record fields to decode, for use with [`ProjectingDecoder`].
+    ///
+    /// Each field is either needed in full or, if it is itself a record (or an
+    /// array, map or union of records), needed only for some of its fields.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct Projection {
+        /// Maps each needed field name to the projection of its value, or
+        /// `None` if the whole value is needed.
+        fields: BTreeMap<String, Option<Projection>>,
+    }
+
+    impl Projection {
+        /// Builds a projection from dot-separated field paths, e.g. `a.b`
+        /// for the field `b` of the record in field `a`.
+        ///
+        /// A path that is a prefix of another subsumes it: `a` and `a.b`
+        /// together project all of `a`.
+        pub fn from_paths<'a, I>(paths: I) -> Self
+        where
+            I: IntoIterator<Item = &'a str>,
+        {
+            let mut projection = Projection::default();
+            for path in paths {
+                projection.insert(path.split('.'));
+            }
+            projection
+        }
+
+        fn insert<'a>(&mut self, mut path: impl Iterator<Item = &'a str>) {
+            let Some(name) = path.next() else {
+                return;
+            };
+            let mut rest = path.peekable();
+            if rest.peek().is_none() {
+                self.fields.insert(name.to_string(), None);
+                return;
+            }
+            match self
+                .fields
+                .entry(name.to_string())
+                .or_insert_with(|| Some(Projection::default()))
+            {
+                // The whole field is already needed.
+                None => {}
+                Some(inner) => inner.insert(rest),
+            }
+        }
+    }
+
+    /// Decodes a [`Value`] like [`ValueDecoder`], but only decodes the record
+    /// fields named by a [`Projection`]. Other fields are skipped over in the
+    /// input without being materialized, and are absent from the decoded
+    /// records.
+    #[derive(Clone, Copy, Debug)]
+    pub struct ProjectingDecoder<'a> {
+        projection: &'a Projection,
+    }
+
+    impl<'a> ProjectingDecoder<'a> {
+        pub fn new(projection: &'a Projection) -> Self {
+            ProjectingDecoder { projection }
+        }
+    }
+
+    impl<'p> AvroDecode for ProjectingDecoder<'p> {
+        type Out = Value;
+        fn record<R: AvroRead, A: AvroRecordAccess<R>>(
+            self,
+            a: &mut A,
+        ) -> Result<Value, AvroError> {
+            let mut fields = vec![];
+            while let Some((name, idx, f)) = a.next_field()? {
+                let val = match self.projection.fields.get(name) {
+                    None => {
+                        f.decode_field(TrivialDecoder)?;
+                        continue;
+                    }
+                    Some(None) => f.decode_field(ValueDecoder)?,
+                    Some(Some(projection)) => f.decode_field(ProjectingDecoder { projection })?,
+                };
+                fields.push((idx, (name.to_string(), val)));
+            }
+            fields.sort_by_key(|(idx, _)| *idx);
+
+            Ok(Value::Record(
+                fields
+                    .into_iter()
+                    .map(|(_idx, (name, val))| (name, val))
+                    .collect(),
+            ))
+        }
+        fn union_branch<'a, R: AvroRead, D: AvroDeserializer>(
+            self,
+            index: usize,
+            n_variants: usize,
+            null_variant: Option<usize>,
+            deserializer: D,
+            reader: &'a mut R,
+        ) -> Result<Value, AvroError> {
+            let inner = Box::new(deserializer.deserialize(reader, self)?);
+            Ok(Value::Union {
+                index,
+                inner,
+                n_variants,
+                null_variant,
+            })
+        }
+        fn array<A: AvroArrayAccess>(self, a: &mut A) -> Result<Value, AvroError> {
+            let mut items = vec![];
+            while let Some(value) = a.decode_next(self)? {
+                items.push(value);
+            }
+            Ok(Value::Array(items))
+        }
+        fn map<M: AvroMapAccess>(self, m: &mut M) -> Result<Value, AvroError> {
+            let mut entries = BTreeMap::new();
+            while let Some((name, a)) = m.next_entry()? {
+                let val = a.decode_field(self)?;
+                entries.insert(name, val);
+            }
+            Ok(Value::Map(entries))
+        }
+        fn enum_variant(self, symbol: &str, idx: usize) -> Result<Value, AvroError> {
+            ValueDecoder.enum_variant(symbol, idx)
+        }
+        fn scalar(self, scalar: Scalar) -> Result<Value, AvroError> {
+            ValueDecoder.scalar(scalar)
+        }
+        fn decimal<'a, R: AvroRead>(
+            self,
+            precision: usize,
+            scale: usize,
+            r: ValueOrReader<'a, &'a [u8], R>,
+        ) -> Result<Value, AvroError> {
+            ValueDecoder.decimal(precision, scale, r)
+        }
+        fn bytes<'a, R: AvroRead>(
+            self,
+            r: ValueOrReader<'a, &'a [u8], R>,
+        ) -> Result<Value, AvroError> {
+            ValueDecoder.bytes(r)
+        }
+        fn string<'a, R: AvroRead>(
+            self,
+            r: ValueOrReader<'a, &'a str, R>,
+        ) -> Result<Value, AvroError> {
+            ValueDecoder.string(r)
+        }
+        fn json<'a, R: AvroRead>(
+            self,
+            r: ValueOrReader<'a, &'a serde_json::Value, R>,
+        ) -> Result<Value, AvroError> {
+            ValueDecoder.json(r)
+        }
+        fn uuid<'a, R: AvroRead>(
+            self,
+            r: ValueOrReader<'a, &'a [u8], R>,
+        ) -> Result<Value, AvroError> {
+            ValueDecoder.uuid(r)
+        }
+        fn fixed<'a, R: AvroRead>(
+            self,
+            r: ValueOrReader<'a, &'a [u8], R>,
+        ) -> Result<Value, AvroError> {
+            ValueDecoder.fixed(r)
+        }
+    }
 }
 
 impl<'a> AvroDeserializer for &'a Value {
@@ -1584,3 +1755,288 @@ pub fn decode<'a, R: AvroRead>(
     let val = dsr.deserialize(reader, d)?;
     Ok(val)
 }
+
+/// Decode a [`ValueRef`] from avro format given its `Schema`, borrowing strings,
+/// bytes, and fixed values from `buf` rather than copying them.
+///
+/// On success, `buf` is advanced past the decoded datum.
+pub fn decode_borrowed<'a>(
+    schema: SchemaNode<'a>,
+    buf: &mut &'a [u8],
+) -> Result<ValueRef<'a>, AvroError> {
+    let value = match schema.inner {
+        SchemaPiece::Null => ValueRef::Null,
+        SchemaPiece::Boolean => match take(buf, 1)?[0] {
+            0u8 => ValueRef::Boolean(false),
+            1u8 => ValueRef::Boolean(true),
+            other => return Err(AvroError::Decode(DecodeError::BadBoolean(other))),
+        },
+        SchemaPiece::Int => ValueRef::Int(zag_i32(buf)?),
+        SchemaPiece::Long => ValueRef::Long(zag_i64(buf)?),
+        SchemaPiece::Float => ValueRef::Float(decode_float(buf)?),
+        SchemaPiece::Double => ValueRef::Double(decode_double(buf)?),
+        SchemaPiece::Date => ValueRef::Date(zag_i32(buf)?),
+        SchemaPiece::TimestampMilli => borrowed_ts_value(zag_i64(buf)?, TsUnit::Millis)?,
+        SchemaPiece::TimestampMicro => borrowed_ts_value(zag_i64(buf)?, TsUnit::Micros)?,
+        SchemaPiece::Decimal {
+            precision,
+            scale,
+            fixed_size,
+        } => {
+            let len = fixed_size.map(Ok).unwrap_or_else(|| decode_len(buf))?;
+            ValueRef::Decimal {
+                unscaled: take(buf, len)?,
+                precision: *precision,
+                scale: *scale,
+            }
+        }
+        SchemaPiece::Bytes => {
+            let len = decode_len(buf)?;
+            ValueRef::Bytes(take(buf, len)?)
+        }
+        SchemaPiece::String => {
+            let len = decode_len(buf)?;
+            let s = std::str::from_utf8(take(buf, len)?)
+                .map_err(|_e| AvroError::Decode(DecodeError::StringUtf8Error))?;
+            ValueRef::String(s)
+        }
+        SchemaPiece::Json => {
+            let len = decode_len(buf)?;
+            let bytes = take(buf, len)?;
+            let val = serde_json::from_slice(bytes).map_err(|e| {
+                AvroError::Decode(DecodeError::BadJson {
+                    category: e.classify(),
+                    bytes: bytes.to_owned(),
+                })
+            })?;
+            ValueRef::Json(val)
+        }
+        SchemaPiece::Uuid => {
+            let len = decode_len(buf)?;
+            let s = std::str::from_utf8(take(buf, len)?)
+                .map_err(|_| AvroError::Decode(DecodeError::UuidUtf8Error))?;
+            let val =
+                uuid::Uuid::parse_str(s).map_err(|e| AvroError::Decode(DecodeError::BadUuid(e)))?;
+            ValueRef::Uuid(val)
+        }
+        SchemaPiece::Array(inner) => {
+            let inner = schema.step(inner);
+            let mut items = vec![];
+            while let Some(len) = decode_block_len(buf)? {
+                for _ in 0..len {
+                    items.push(decode_borrowed(inner, buf)?);
+                }
+            }
+            ValueRef::Array(items)
+        }
+        SchemaPiece::Map(inner) => {
+            let inner = schema.step(inner);
+            let mut entries = BTreeMap::new();
+            while let Some(len) = decode_block_len(buf)? {
+                for _ in 0..len {
+                    let key_len = decode_len(buf)?;
+                    let key = std::str::from_utf8(take(buf, key_len)?)
+                        .map_err(|_e| AvroError::Decode(DecodeError::MapKeyUtf8Error))?;
+                    entries.insert(key, decode_borrowed(inner, buf)?);
+                }
+            }
+            ValueRef::Map(entries)
+        }
+        SchemaPiece::Union(inner) => {
+            let index = decode_long_nonneg(buf)? as usize;
+            let variants = inner.variants();
+            match variants.get(index) {
+                Some(variant) => ValueRef::Union {
+                    index,
+                    inner: Box::new(decode_borrowed(schema.step(variant), buf)?),
+                    n_variants: variants.len(),
+                    null_variant: variants
+                        .iter()
+                        .position(|v| v == &SchemaPieceOrNamed::Piece(SchemaPiece::Null)),
+                },
+                None => {
+                    return Err(AvroError::Decode(DecodeError::BadUnionIndex {
+                        index,
+                        len: variants.len(),
+                    }))
+                }
+            }
+        }
+        SchemaPiece::ResolveIntLong => ValueRef::Long(zag_i32(buf)? as i64),
+        SchemaPiece::ResolveIntFloat => ValueRef::Float(zag_i32(buf)? as f32),
+        SchemaPiece::ResolveIntDouble => ValueRef::Double(zag_i32(buf)? as f64),
+        SchemaPiece::ResolveLongFloat => ValueRef::Float(zag_i64(buf)? as f32),
+        SchemaPiece::ResolveLongDouble => ValueRef::Double(zag_i64(buf)? as f64),
+        SchemaPiece::ResolveFloatDouble => ValueRef::Double(decode_float(buf)? as f64),
+        SchemaPiece::ResolveConcreteUnion {
+            index,
+            inner,
+            n_reader_variants,
+            reader_null_variant,
+        } => ValueRef::Union {
+            index: *index,
+            inner: Box::new(decode_borrowed(schema.step(&**inner), buf)?),
+            n_variants: *n_reader_variants,
+            null_variant: *reader_null_variant,
+        },
+        SchemaPiece::ResolveUnionUnion {
+            permutation,
+            n_reader_variants,
+            reader_null_variant,
+        } => {
+            let index = decode_long_nonneg(buf)? as usize;
+            match permutation.get(index) {
+                None => {
+                    return Err(AvroError::Decode(DecodeError::BadUnionIndex {
+                        index,
+                        len: permutation.len(),
+                    }))
+                }
+                Some(Err(e)) => return Err(e.clone()),
+                Some(Ok((index, variant))) => ValueRef::Union {
+                    index: *index,
+                    inner: Box::new(decode_borrowed(schema.step(variant), buf)?),
+                    n_variants: *n_reader_variants,
+                    null_variant: *reader_null_variant,
+                },
+            }
+        }
+        SchemaPiece::ResolveUnionConcrete { index, inner } => {
+            let found_index = decode_long_nonneg(buf)? as usize;
+            if *index != found_index {
+                return Err(AvroError::Decode(DecodeError::WrongUnionIndex {
+                    expected: *index,
+                    actual: found_index,
+                }));
+            }
+            // The reader is not expecting a union here, so don't produce one.
+            decode_borrowed(schema.step(inner.as_ref()), buf)?
+        }
+        SchemaPiece::Record { fields, .. } => {
+            let mut values = Vec::with_capacity(fields.len());
+            for field in fields {
+                values.push((
+                    field.name.as_str(),
+                    decode_borrowed(schema.step(&field.schema), buf)?,
+                ));
+            }
+            ValueRef::Record(values)
+        }
+        SchemaPiece::Enum { symbols, .. } => {
+            let index = decode_int_nonneg(buf)? as usize;
+            match symbols.get(index) {
+                None => {
+                    return Err(AvroError::Decode(DecodeError::BadEnumIndex {
+                        index,
+                        len: symbols.len(),
+                    }))
+                }
+                Some(symbol) => ValueRef::Enum(index, symbol),
+            }
+        }
+        SchemaPiece::Fixed { size } => ValueRef::Fixed(take(buf, *size)?),
+        SchemaPiece::ResolveRecord {
+            defaults,
+            fields,
+            n_reader_fields,
+        } => {
+            let mut values = Vec::with_capacity(*n_reader_fields);
+            for default in defaults {
+                values.push((
+                    default.position,
+                    (default.name.as_str(), ValueRef::from(&default.default)),
+                ));
+            }
+            for field in fields {
+                match field {
+                    ResolvedRecordField::Absent(absent_schema) => {
+                        // The value is not needed, but must still be read in
+                        // order to skip ahead the proper amount in the input.
+                        let d = GeneralDeserializer {
+                            schema: absent_schema.top_node(),
+                        };
+                        d.deserialize(buf, TrivialDecoder)?;
+                    }
+                    ResolvedRecordField::Present(field) => values.push((
+                        field.position,
+                        (
+                            field.name.as_str(),
+                            decode_borrowed(schema.step(&field.schema), buf)?,
+                        ),
+                    )),
+                }
+            }
+            values.sort_by_key(|(position, _)| *position);
+            ValueRef::Record(values.into_iter().map(|(_, field)| field).collect())
+        }
+        SchemaPiece::ResolveEnum {
+            symbols, default, ..
+        } => {
+            let index = decode_int_nonneg(buf)? as usize;
+            match symbols.get(index) {
+                None => {
+                    return Err(AvroError::Decode(DecodeError::BadEnumIndex {
+                        index,
+                        len: symbols.len(),
+                    }))
+                }
+                Some(Err(missing)) => match default {
+                    Some((reader_index, symbol)) => ValueRef::Enum(*reader_index, symbol),
+                    None => {
+                        return Err(AvroError::Decode(DecodeError::MissingEnumIndex {
+                            index,
+                            symbol: missing.clone(),
+                        }))
+                    }
+                },
+                Some(Ok((index, name))) => ValueRef::Enum(*index, name),
+            }
+        }
+        SchemaPiece::ResolveIntTsMilli => borrowed_ts_value(zag_i32(buf)?.into(), TsUnit::Millis)?,
+        SchemaPiece::ResolveIntTsMicro => borrowed_ts_value(zag_i32(buf)?.into(), TsUnit::Micros)?,
+        SchemaPiece::ResolveDateTimestamp => {
+            let days = zag_i32(buf)?;
+            let date = NaiveDate::from_ymd_opt(1970, 1, 1)
+                .expect("naive date known valid")
+                .checked_add_signed(
+                    chrono::Duration::try_days(days.into())
+                        .ok_or(AvroError::Decode(DecodeError::BadDate(days)))?,
+                )
+                .ok_or(AvroError::Decode(DecodeError::BadDate(days)))?;
+            ValueRef::Timestamp(date.and_hms_opt(0, 0, 0).expect("HMS known valid"))
+        }
+    };
+    Ok(value)
+}
+
+/// Splits the first `len` bytes off of `buf`.
+fn take<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8], AvroError> {
+    if buf.len() < len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+    let (head, tail) = buf.split_at(len);
+    *buf = tail;
+    Ok(head)
+}
+
+/// Decodes the item count at the start of an array or map block, returning
+/// `None` for the empty block that terminates the array or map.
+fn decode_block_len(buf: &mut &[u8]) -> Result<Option<usize>, AvroError> {
+    match zag_i64(buf)? {
+        0 => Ok(None),
+        len if len > 0 => Ok(Some(safe_len(len as usize)?)),
+        // A negative count is followed by the size of the block in bytes,
+        // which is only useful for skipping the block.
+        neglen => {
+            decode_len(buf)?;
+            Ok(Some(safe_len(neglen.unsigned_abs() as usize)?))
+        }
+    }
+}
+
+fn borrowed_ts_value<'a>(value: i64, unit: TsUnit) -> Result<ValueRef<'a>, AvroError> {
+    match build_ts_value(value, unit)? {
+        Value::Timestamp(ts) => Ok(ValueRef::Timestamp(ts)),
+        _ => unreachable!(),
+    }
+}
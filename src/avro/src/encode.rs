@@ -93,9 +93,23 @@ pub fn encode_ref(value: &Value, schema: SchemaNode, buffer: &mut Vec<u8>) {
             encode_long(ts, buffer)
         }
         Value::Double(x) => buffer.extend_from_slice(&x.to_le_bytes()),
-        Value::Decimal(DecimalValue { unscaled, .. }) => match schema.name {
-            None => encode_bytes(unscaled, buffer),
-            Some(_) => buffer.extend(unscaled),
+        Value::Decimal(DecimalValue { unscaled, .. }) => match (schema.inner, schema.name) {
+            (
+                SchemaPiece::Decimal {
+                    fixed_size: Some(size),
+                    ..
+                },
+                _,
+            ) => {
+                // Sign-extend the unscaled value to the width of the fixed.
+                let negative = unscaled.first().is_some_and(|b| b & 0x80 != 0);
+                let pad = if negative { 0xff } else { 0 };
+                let padding = size.saturating_sub(unscaled.len());
+                buffer.extend(std::iter::repeat(pad).take(padding));
+                buffer.extend(unscaled);
+            }
+            (_, None) => encode_bytes(unscaled, buffer),
+            (_, Some(_)) => buffer.extend(unscaled),
         },
         Value::Bytes(bytes) => encode_bytes(bytes, buffer),
         Value::String(s) => match schema.inner {
@@ -197,4 +211,25 @@ mod tests {
         );
         assert_eq!(vec![0u8], buf);
     }
+
+    #[mz_ore::test]
+    fn test_encode_fixed_decimal() {
+        let schema = r#"{"type": "fixed", "name": "dec", "size": 4, "logicalType": "decimal", "precision": 9}"#
+            .parse()
+            .unwrap();
+        for (unscaled, expected) in [
+            (vec![0x01, 0x02], vec![0x00, 0x00, 0x01, 0x02]),
+            (vec![0xfe], vec![0xff, 0xff, 0xff, 0xfe]),
+            (vec![0x00, 0x01, 0x02, 0x03], vec![0x00, 0x01, 0x02, 0x03]),
+        ] {
+            let mut buf = Vec::new();
+            let value = Value::Decimal(DecimalValue {
+                unscaled,
+                precision: 9,
+                scale: 0,
+            });
+            encode(&value, &schema, &mut buf);
+            assert_eq!(buf, expected);
+        }
+    }
 }
@@ -41,6 +41,7 @@ pub enum DecodeError {
         expected: u32,
         actual: u32,
     },
+    MissingSnappyChecksum,
     ExpectedNonnegInteger(i64),
     BadTimestamp {
         unit: TsUnit,
@@ -102,6 +103,9 @@ pub enum DecodeError {
         expected: [u8; 16],
         actual: [u8; 16],
     },
+    BlockTooLarge {
+        limit: usize,
+    },
     DateOutOfRange(i32),
     TimestampOutOfRange(NaiveDateTime),
     Custom(String),
@@ -111,6 +115,9 @@ impl DecodeError {
     fn fmt_inner(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             DecodeError::UnrecognizedCodec(codec) => write!(f, "Unrecognized codec: {}", codec),
+            DecodeError::MissingSnappyChecksum => {
+                write!(f, "Snappy block too short to contain a CRC32")
+            }
             DecodeError::BadSnappyChecksum { expected, actual } => write!(
                 f,
                 "Bad Snappy CRC32; expected {:x} but got {:x}",
@@ -185,6 +192,9 @@ impl DecodeError {
                 "Block marker ({:x?}) does not match header marker ({:x?})",
                 actual, expected
             ),
+            DecodeError::BlockTooLarge { limit } => {
+                write!(f, "Block exceeds maximum size of {} bytes", limit)
+            }
             DecodeError::DateOutOfRange(inner) => write!(f, "Date out of range: {}", inner),
             DecodeError::TimestampOutOfRange(inner) => {
                 write!(f, "Timestamp out of range: {}", inner)
@@ -333,6 +333,7 @@
 
 mod codec;
 mod decode;
+mod rabin;
 mod reader;
 mod util;
 mod writer;
@@ -350,10 +351,18 @@ pub use crate::decode::{
     ValueOrReader,
 };
 pub use crate::encode::encode as encode_unchecked;
-pub use crate::reader::{from_avro_datum, Block, BlockIter, Reader};
+pub use crate::rabin::Rabin;
+pub use crate::reader::{
+    from_avro_datum, from_avro_datum_borrowed, from_avro_datum_projected, Block, BlockIter, Reader,
+    StreamingReader, DEFAULT_MAX_BLOCK_BYTES,
+};
 pub use crate::schema::{ParseSchemaError, Schema};
 pub use crate::types::SchemaResolutionError;
-pub use crate::writer::{to_avro_datum, write_avro_datum, ValidationError, Writer};
+pub use crate::writer::{
+    to_avro_datum, to_confluent_datum, to_single_object_datum, write_avro_datum,
+    write_confluent_header, write_single_object_header, ValidationError, Writer, CONFLUENT_MAGIC,
+    SINGLE_OBJECT_MAGIC,
+};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,113 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The 64-bit Rabin fingerprint (CRC-64-AVRO) described by the Avro
+//! specification.
+
+use digest::consts::U8;
+use digest::{FixedOutput, HashMarker, Output, OutputSizeUser, Reset, Update};
+use once_cell::sync::Lazy;
+
+const EMPTY: i64 = -4513414715797952619; // 0xc15d213aa4d7a795
+
+static FPTABLE: Lazy<[i64; 256]> = Lazy::new(|| {
+    let mut fptable = [0; 256];
+    for (i, entry) in fptable.iter_mut().enumerate() {
+        let mut fp = i as i64;
+        for _ in 0..8 {
+            let mask = -(fp & 1);
+            fp = ((fp as u64) >> 1) as i64 ^ (EMPTY & mask);
+        }
+        *entry = fp;
+    }
+    fptable
+});
+
+/// A [`Digest`](digest::Digest) implementing the [Rabin fingerprint]
+/// recommended by the Avro specification for schemas, so that it can be used
+/// with [`Schema::fingerprint`](crate::Schema::fingerprint).
+///
+/// The fingerprint is output as 8 little-endian bytes, as in Avro's
+/// single-object encoding.
+///
+/// [Rabin fingerprint]: https://avro.apache.org/docs/current/spec.html#schema_fingerprints
+#[derive(Clone, Debug)]
+pub struct Rabin {
+    result: i64,
+}
+
+impl Rabin {
+    /// Returns the fingerprint of the bytes seen so far as an integer.
+    pub fn value(&self) -> i64 {
+        self.result
+    }
+}
+
+impl Default for Rabin {
+    fn default() -> Self {
+        Rabin { result: EMPTY }
+    }
+}
+
+impl HashMarker for Rabin {}
+
+impl OutputSizeUser for Rabin {
+    type OutputSize = U8;
+}
+
+impl Update for Rabin {
+    fn update(&mut self, data: &[u8]) {
+        for b in data {
+            self.result = ((self.result as u64) >> 8) as i64
+                ^ FPTABLE[((self.result ^ i64::from(*b)) & 0xff) as usize];
+        }
+    }
+}
+
+impl FixedOutput for Rabin {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.result.to_le_bytes());
+    }
+}
+
+impl Reset for Rabin {
+    fn reset(&mut self) {
+        self.result = EMPTY;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use digest::Digest;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_rabin() {
+        // Test vectors from the Avro specification's test suite.
+        for (input, expected) in [
+            (r#""null""#, 7195948357588979594),
+            (r#""int""#, 8247732601305521295),
+        ] {
+            let mut rabin = Rabin::default();
+            Update::update(&mut rabin, input.as_bytes());
+            assert_eq!(rabin.value(), expected);
+            assert_eq!(
+                Rabin::digest(input).as_slice(),
+                expected.to_le_bytes().as_slice()
+            );
+        }
+    }
+}
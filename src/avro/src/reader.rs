@@ -24,20 +24,24 @@
 //! Logic handling reading from Avro format at user level.
 
 use std::collections::BTreeMap;
+use std::io::{Cursor, Read, Take};
 use std::str::{from_utf8, FromStr};
 
+use flate2::read::DeflateDecoder;
 use serde_json::from_slice;
 use sha2::Sha256;
 
-use crate::decode::{decode, AvroRead};
+use crate::decode::{
+    decode, decode_borrowed, AvroDeserializer, AvroRead, GeneralDeserializer, Skip,
+};
 use crate::error::{DecodeError, Error as AvroError};
 use crate::schema::{
     resolve_schemas, FullName, NamedSchemaPiece, ParseSchemaError, RecordField,
     ResolvedDefaultValueField, ResolvedRecordField, Schema, SchemaNodeOrNamed, SchemaPiece,
     SchemaPieceOrNamed, SchemaPieceRefOrNamed,
 };
-use crate::types::Value;
-use crate::{util, Codec, SchemaResolutionError};
+use crate::types::{Value, ValueRef};
+use crate::{util, Codec, ProjectingDecoder, Projection, SchemaResolutionError};
 
 #[derive(Debug, Clone)]
 pub(crate) struct Header {
@@ -112,14 +116,73 @@ impl Header {
     pub fn into_parts(self) -> (Schema, [u8; 16], Codec) {
         (self.writer_schema, self.marker, self.codec)
     }
+
+    /// Resolves the writer schema against `reader_schema`, returning `None` if
+    /// the two are identical.
+    fn resolve(&self, reader_schema: &Schema) -> Result<Option<Schema>, AvroError> {
+        let writer_schema = &self.writer_schema;
+        if reader_schema.fingerprint::<Sha256>().bytes
+            != writer_schema.fingerprint::<Sha256>().bytes
+        {
+            Ok(Some(resolve_schemas(writer_schema, reader_schema)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Reads the object count and size of the next block, returning `None` if the
+/// stream ended cleanly before the block began.
+fn read_block_header<R: Read>(
+    reader: &mut R,
+    max_block_bytes: usize,
+) -> Result<Option<(usize, usize)>, AvroError> {
+    let block_len = match util::read_long(reader) {
+        Ok(block_len) => block_len,
+        Err(AvroError::IO(std::io::ErrorKind::UnexpectedEof)) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let block_bytes = util::read_long(reader)?;
+
+    let block_len =
+        usize::try_from(block_len).map_err(|_| DecodeError::ExpectedNonnegInteger(block_len))?;
+    let block_bytes = usize::try_from(block_bytes)
+        .map_err(|_| DecodeError::ExpectedNonnegInteger(block_bytes))?;
+    if block_bytes > max_block_bytes {
+        return Err(DecodeError::BlockTooLarge {
+            limit: max_block_bytes,
+        }
+        .into());
+    }
+    Ok(Some((block_len, block_bytes)))
+}
+
+/// Reads the sync marker that terminates a block and checks that it matches
+/// the one from the file header.
+fn read_block_marker<R: Read>(reader: &mut R, expected: &[u8; 16]) -> Result<(), AvroError> {
+    let mut marker = [0u8; 16];
+    reader.read_exact(&mut marker)?;
+    if marker != *expected {
+        return Err(DecodeError::MismatchedBlockHeader {
+            expected: *expected,
+            actual: marker,
+        }
+        .into());
+    }
+    Ok(())
 }
 
+/// The default limit on the size of a single block, compressed or
+/// decompressed, that a [`Reader`] or [`StreamingReader`] will accept.
+pub const DEFAULT_MAX_BLOCK_BYTES: usize = 64 << 20;
+
 pub struct Reader<R> {
     header: Header,
     inner: R,
     errored: bool,
     resolved_schema: Option<Schema>,
     messages_remaining: usize,
+    max_block_bytes: usize,
     // Internal buffering to reduce allocation.
     buf: Vec<u8>,
     buf_idx: usize,
@@ -181,6 +244,7 @@ impl<R: AvroRead> Reader<R> {
             errored: false,
             resolved_schema: None,
             messages_remaining: 0,
+            max_block_bytes: DEFAULT_MAX_BLOCK_BYTES,
             buf: vec![],
             buf_idx: 0,
         };
@@ -193,15 +257,7 @@ impl<R: AvroRead> Reader<R> {
     /// **NOTE** The avro header is going to be read automatically upon creation of the `Reader`.
     pub fn with_schema(reader_schema: &Schema, mut inner: R) -> Result<Reader<R>, AvroError> {
         let header = Header::from_reader(&mut inner)?;
-
-        let writer_schema = &header.writer_schema;
-        let resolved_schema = if reader_schema.fingerprint::<Sha256>().bytes
-            != writer_schema.fingerprint::<Sha256>().bytes
-        {
-            Some(resolve_schemas(writer_schema, reader_schema)?)
-        } else {
-            None
-        };
+        let resolved_schema = header.resolve(reader_schema)?;
 
         Ok(Reader {
             header,
@@ -209,11 +265,22 @@ impl<R: AvroRead> Reader<R> {
             resolved_schema,
             inner,
             messages_remaining: 0,
+            max_block_bytes: DEFAULT_MAX_BLOCK_BYTES,
             buf: vec![],
             buf_idx: 0,
         })
     }
 
+    /// Sets the maximum size of a single block, compressed or decompressed.
+    ///
+    /// Blocks are buffered in memory in their entirety, so this bounds the
+    /// memory used by the reader. Encountering a larger block is an error.
+    /// Defaults to [`DEFAULT_MAX_BLOCK_BYTES`].
+    pub fn with_max_block_bytes(mut self, max_block_bytes: usize) -> Self {
+        self.max_block_bytes = max_block_bytes;
+        self
+    }
+
     /// Get a reference to the writer `Schema`.
     pub fn writer_schema(&self) -> &Schema {
         &self.header.writer_schema
@@ -265,41 +332,27 @@ impl<R: AvroRead> Reader<R> {
 
     fn read_block_next(&mut self) -> Result<(), AvroError> {
         assert!(self.is_empty(), "Expected self to be empty!");
-        match util::read_long(&mut self.inner) {
-            Ok(block_len) => {
-                self.messages_remaining = block_len as usize;
-                let block_bytes = util::read_long(&mut self.inner)?;
-                self.fill_buf(block_bytes as usize)?;
-                let mut marker = [0u8; 16];
-                self.inner.read_exact(&mut marker)?;
-
-                if marker != self.header.marker {
-                    return Err(DecodeError::MismatchedBlockHeader {
-                        expected: self.header.marker,
-                        actual: marker,
-                    }
-                    .into());
-                }
-
-                // NOTE (JAB): This doesn't fit this Reader pattern very well.
-                // `self.buf` is a growable buffer that is reused as the reader is iterated.
-                // For non `Codec::Null` variants, `decompress` will allocate a new `Vec`
-                // and replace `buf` with the new one, instead of reusing the same buffer.
-                // We can address this by using some "limited read" type to decode directly
-                // into the buffer. But this is fine, for now.
-                self.header.codec.decompress(&mut self.buf)?;
+        let Some((block_len, block_bytes)) =
+            read_block_header(&mut self.inner, self.max_block_bytes)?
+        else {
+            // We finished reading cleanly from the stream.
+            return Ok(());
+        };
+        self.fill_buf(block_bytes)?;
+        read_block_marker(&mut self.inner, &self.header.marker)?;
+
+        // NOTE (JAB): This doesn't fit this Reader pattern very well.
+        // `self.buf` is a growable buffer that is reused as the reader is iterated.
+        // For non `Codec::Null` variants, `decompress` will allocate a new `Vec`
+        // and replace `buf` with the new one, instead of reusing the same buffer.
+        // We can address this by using some "limited read" type to decode directly
+        // into the buffer. But this is fine, for now.
+        self.header
+            .codec
+            .decompress_with_limit(&mut self.buf, self.max_block_bytes)?;
+        self.messages_remaining = block_len;
 
-                Ok(())
-            }
-            Err(e) => {
-                if let AvroError::IO(std::io::ErrorKind::UnexpectedEof) = e {
-                    // to not return any error in case we only finished to read cleanly from the stream
-                    Ok(())
-                } else {
-                    Err(e)
-                }
-            }
-        }
+        Ok(())
     }
 }
 
@@ -321,6 +374,234 @@ impl<R: AvroRead> Iterator for Reader<R> {
     }
 }
 
+/// Reads Avro values from an object container file, decoding them directly
+/// from each block as it is read rather than first materializing the block.
+///
+/// Uncompressed, deflate-compressed and zstandard-compressed blocks are
+/// decoded straight from the underlying reader, so memory use does not grow
+/// with the size of a block. Blocks using other codecs are buffered in full
+/// before decompression. In all cases, blocks larger than the configured
+/// maximum, either compressed or decompressed, are rejected.
+///
+/// Once an error has been returned, subsequent reads return `Ok(None)`.
+pub struct StreamingReader<R> {
+    header: Header,
+    resolved_schema: Option<Schema>,
+    max_block_bytes: usize,
+    /// `None` once the reader has failed.
+    state: Option<StreamState<R>>,
+    messages_remaining: usize,
+}
+
+enum StreamState<R> {
+    /// Positioned at the start of the next block, or at the end of the file.
+    Idle(R),
+    /// Decoding an uncompressed block straight from the underlying reader.
+    Null(Take<R>),
+    /// Decoding a deflate-compressed block as it is decompressed.
+    Deflate(Take<DeflateDecoder<Take<R>>>),
+    /// Decoding a zstandard-compressed block as it is decompressed.
+    #[cfg(feature = "zstandard")]
+    Zstandard(Take<zstd::stream::read::Decoder<'static, std::io::BufReader<Take<R>>>>),
+    /// Decoding a block that was read and decompressed in full, whose sync
+    /// marker has already been checked.
+    #[cfg_attr(not(feature = "snappy"), allow(dead_code))]
+    Buffered { inner: R, block: Cursor<Vec<u8>> },
+}
+
+impl<R: AvroRead> StreamingReader<R> {
+    /// Creates a `StreamingReader` that decodes values with the writer schema.
+    ///
+    /// **NOTE** The avro header is going to be read automatically upon creation of the `StreamingReader`.
+    pub fn new(mut inner: R) -> Result<StreamingReader<R>, AvroError> {
+        let header = Header::from_reader(&mut inner)?;
+        Ok(StreamingReader {
+            header,
+            resolved_schema: None,
+            max_block_bytes: DEFAULT_MAX_BLOCK_BYTES,
+            state: Some(StreamState::Idle(inner)),
+            messages_remaining: 0,
+        })
+    }
+
+    /// Creates a `StreamingReader` that resolves values to the given reader `Schema`.
+    ///
+    /// **NOTE** The avro header is going to be read automatically upon creation of the `StreamingReader`.
+    pub fn with_schema(
+        reader_schema: &Schema,
+        mut inner: R,
+    ) -> Result<StreamingReader<R>, AvroError> {
+        let header = Header::from_reader(&mut inner)?;
+        let resolved_schema = header.resolve(reader_schema)?;
+        Ok(StreamingReader {
+            header,
+            resolved_schema,
+            max_block_bytes: DEFAULT_MAX_BLOCK_BYTES,
+            state: Some(StreamState::Idle(inner)),
+            messages_remaining: 0,
+        })
+    }
+
+    /// Sets the maximum size of a single block, compressed or decompressed.
+    /// Encountering a larger block is an error. Defaults to
+    /// [`DEFAULT_MAX_BLOCK_BYTES`].
+    pub fn with_max_block_bytes(mut self, max_block_bytes: usize) -> Self {
+        self.max_block_bytes = max_block_bytes;
+        self
+    }
+
+    /// Get a reference to the writer `Schema`.
+    pub fn writer_schema(&self) -> &Schema {
+        &self.header.writer_schema
+    }
+
+    /// Get a reference to the resolved schema
+    /// (or just the writer schema, if no reader schema was provided
+    ///  or the two schemas are identical)
+    pub fn schema(&self) -> &Schema {
+        match &self.resolved_schema {
+            Some(schema) => schema,
+            None => self.writer_schema(),
+        }
+    }
+
+    /// Read the next Avro value from the file, if one exists.
+    pub fn read_next(&mut self) -> Result<Option<Value>, AvroError> {
+        let result = self.read_next_inner();
+        if result.is_err() {
+            // The position within the stream is unknown, so nothing further
+            // can be decoded.
+            self.state = None;
+        }
+        result
+    }
+
+    fn read_next_inner(&mut self) -> Result<Option<Value>, AvroError> {
+        while self.messages_remaining == 0 {
+            if !self.next_block()? {
+                return Ok(None);
+            }
+        }
+
+        let schema = match &self.resolved_schema {
+            Some(schema) => schema,
+            None => &self.header.writer_schema,
+        };
+        let item = match &mut self.state {
+            None => return Ok(None),
+            Some(StreamState::Idle(_)) => unreachable!("values remaining outside of a block"),
+            Some(StreamState::Null(block)) => from_avro_datum(schema, block)?,
+            Some(StreamState::Deflate(block)) => {
+                from_limited_avro_datum(schema, block, self.max_block_bytes)?
+            }
+            #[cfg(feature = "zstandard")]
+            Some(StreamState::Zstandard(block)) => {
+                from_limited_avro_datum(schema, block, self.max_block_bytes)?
+            }
+            Some(StreamState::Buffered { block, .. }) => from_avro_datum(schema, block)?,
+        };
+        self.messages_remaining -= 1;
+        Ok(Some(item))
+    }
+
+    /// Finishes the current block, if any, and begins the next one. Returns
+    /// `false` if the end of the file was reached.
+    fn next_block(&mut self) -> Result<bool, AvroError> {
+        let Some(state) = self.state.take() else {
+            return Ok(false);
+        };
+        let mut inner = self.finish_block(state)?;
+        let Some((block_len, block_bytes)) = read_block_header(&mut inner, self.max_block_bytes)?
+        else {
+            self.state = Some(StreamState::Idle(inner));
+            return Ok(false);
+        };
+
+        let state = match self.header.codec {
+            Codec::Null => StreamState::Null(inner.take(block_bytes as u64)),
+            Codec::Deflate => StreamState::Deflate(
+                DeflateDecoder::new(inner.take(block_bytes as u64))
+                    .take(self.max_block_bytes as u64),
+            ),
+            #[cfg(feature = "zstandard")]
+            Codec::Zstandard => StreamState::Zstandard(
+                zstd::stream::read::Decoder::new(inner.take(block_bytes as u64))?
+                    .take(self.max_block_bytes as u64),
+            ),
+            #[cfg(feature = "snappy")]
+            codec @ Codec::Snappy => {
+                let mut buf = vec![0; block_bytes];
+                inner.read_exact(&mut buf)?;
+                read_block_marker(&mut inner, &self.header.marker)?;
+                codec.decompress_with_limit(&mut buf, self.max_block_bytes)?;
+                StreamState::Buffered {
+                    inner,
+                    block: Cursor::new(buf),
+                }
+            }
+        };
+        self.state = Some(state);
+        self.messages_remaining = block_len;
+        Ok(true)
+    }
+
+    /// Skips whatever remains of the current block and checks its sync marker,
+    /// returning the underlying reader positioned at the start of the next block.
+    fn finish_block(&self, state: StreamState<R>) -> Result<R, AvroError> {
+        let (mut inner, remaining) = match state {
+            StreamState::Idle(inner) => return Ok(inner),
+            StreamState::Buffered { inner, .. } => return Ok(inner),
+            StreamState::Null(block) => {
+                let remaining = block.limit();
+                (block.into_inner(), remaining)
+            }
+            StreamState::Deflate(block) => {
+                // Compressed bytes already buffered by the decoder were
+                // accounted for by the inner `Take`.
+                let compressed = block.into_inner().into_inner();
+                let remaining = compressed.limit();
+                (compressed.into_inner(), remaining)
+            }
+            #[cfg(feature = "zstandard")]
+            StreamState::Zstandard(block) => {
+                // As above, bytes buffered by the decoder's `BufReader` were
+                // accounted for by the inner `Take`.
+                let compressed = block.into_inner().finish().into_inner();
+                let remaining = compressed.limit();
+                (compressed.into_inner(), remaining)
+            }
+        };
+        inner.skip(remaining as usize)?;
+        read_block_marker(&mut inner, &self.header.marker)?;
+        Ok(inner)
+    }
+}
+
+/// Decodes a value from a block whose decompressed size is capped by `block`.
+fn from_limited_avro_datum<R: AvroRead>(
+    schema: &Schema,
+    block: &mut Take<R>,
+    limit: usize,
+) -> Result<Value, AvroError> {
+    from_avro_datum(schema, block).map_err(|e| {
+        // Running out of decompressed bytes mid-value means the block
+        // exceeded the limit, not that it was truncated.
+        if block.limit() == 0 {
+            DecodeError::BlockTooLarge { limit }.into()
+        } else {
+            e
+        }
+    })
+}
+
+impl<R: AvroRead> Iterator for StreamingReader<R> {
+    type Item = Result<Value, AvroError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_next().transpose()
+    }
+}
+
 pub struct SchemaResolver<'a> {
     pub named: Vec<Option<NamedSchemaPiece>>,
     pub indices: BTreeMap<FullName, usize>,
@@ -359,7 +640,7 @@ impl<'a> SchemaResolver<'a> {
                 let mut defaults = Vec::new();
                 let mut fields: Vec<Option<RecordField>> = Vec::new();
                 for (r_index, rf) in r_fields.iter().enumerate() {
-                    match w_lookup.get(&rf.name) {
+                    match rf.writer_position(w_lookup) {
                         None => {
                             let default_field = match &rf.default {
                                 Some(v) => ResolvedDefaultValueField {
@@ -385,7 +666,7 @@ impl<'a> SchemaResolver<'a> {
                             defaults.push(default_field);
                         }
                         Some(w_index) => {
-                            if fields.len() > *w_index && fields[*w_index].is_some() {
+                            if fields.len() > w_index && fields[w_index].is_some() {
                                 return Err(SchemaResolutionError::new(format!(
                                     "Duplicate field `{}.{}` in schema",
                                     self.get_current_human_readable_path(),
@@ -393,7 +674,7 @@ impl<'a> SchemaResolver<'a> {
                                 ))
                                 .into());
                             }
-                            let wf = &w_fields[*w_index];
+                            let wf = &w_fields[w_index];
                             let w_node = SchemaNodeOrNamed {
                                 root: writer,
                                 inner: wf.schema.as_ref(),
@@ -414,11 +695,12 @@ impl<'a> SchemaResolver<'a> {
                                 schema: new_inner,
                                 order: rf.order.clone(),
                                 position: r_index,
+                                aliases: rf.aliases.clone(),
                             };
-                            while fields.len() <= *w_index {
+                            while fields.len() <= w_index {
                                 fields.push(None);
                             }
-                            fields[*w_index] = Some(field)
+                            fields[w_index] = Some(field)
                         }
                     }
                 }
@@ -880,9 +1162,11 @@ impl<'a> SchemaResolver<'a> {
                                     return Err(e);
                                 }
                             };
-                        let name = &self.reader_schema.named[r_index].name;
+                        let reader_piece = &self.reader_schema.named[r_index];
+                        let name = &reader_piece.name;
                         let ns = NamedSchemaPiece {
                             name: name.clone(),
+                            aliases: reader_piece.aliases.clone(),
                             piece,
                         };
                         self.named[resolved_idx] = Some(ns);
@@ -928,12 +1212,37 @@ pub fn from_avro_datum<R: AvroRead>(schema: &Schema, reader: &mut R) -> Result<V
     Ok(value)
 }
 
+/// Like [`from_avro_datum`], but only decodes the record fields named by
+/// `projection`, skipping over the rest of the datum's fields without
+/// materializing them.
+pub fn from_avro_datum_projected<R: AvroRead>(
+    schema: &Schema,
+    reader: &mut R,
+    projection: &Projection,
+) -> Result<Value, AvroError> {
+    let dsr = GeneralDeserializer {
+        schema: schema.top_node(),
+    };
+    dsr.deserialize(reader, ProjectingDecoder::new(projection))
+}
+
+/// Like [`from_avro_datum`], but decodes into a [`ValueRef`] whose strings,
+/// bytes, and fixed values borrow from `buf` rather than being copied.
+///
+/// On success, `buf` is advanced past the decoded datum.
+pub fn from_avro_datum_borrowed<'a>(
+    schema: &'a Schema,
+    buf: &mut &'a [u8],
+) -> Result<ValueRef<'a>, AvroError> {
+    decode_borrowed(schema.top_node(), buf)
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
 
     use crate::types::{Record, ToAvro};
-    use crate::Reader;
+    use crate::{Reader, Writer};
 
     use super::*;
 
@@ -980,6 +1289,177 @@ mod tests {
         assert_eq!(from_avro_datum(&schema, &mut encoded).unwrap(), expected);
     }
 
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: inline assembly is not supported
+    fn test_from_avro_datum_projected() {
+        let schema: Schema = r#"
+            {
+                "type": "record",
+                "name": "outer",
+                "fields": [
+                    {"name": "a", "type": "long"},
+                    {"name": "b", "type": {"type": "array", "items": "string"}},
+                    {"name": "c", "type": ["null", {
+                        "type": "record",
+                        "name": "inner",
+                        "fields": [
+                            {"name": "d", "type": "string"},
+                            {"name": "e", "type": "int"}
+                        ]
+                    }]},
+                    {"name": "f", "type": {"type": "map", "values": "inner"}}
+                ]
+            }
+        "#
+        .parse()
+        .unwrap();
+        let inner = |d: &str, e: i32| {
+            Value::Record(vec![
+                ("d".into(), Value::String(d.into())),
+                ("e".into(), Value::Int(e)),
+            ])
+        };
+        let value = Value::Record(vec![
+            ("a".into(), Value::Long(1)),
+            (
+                "b".into(),
+                Value::Array(vec![Value::String("x".into()), Value::String("y".into())]),
+            ),
+            (
+                "c".into(),
+                Value::Union {
+                    index: 1,
+                    inner: Box::new(inner("foo", 2)),
+                    n_variants: 2,
+                    null_variant: Some(0),
+                },
+            ),
+            (
+                "f".into(),
+                Value::Map([("k".to_string(), inner("bar", 3))].into()),
+            ),
+        ]);
+        let encoded = crate::to_avro_datum(&schema, value.clone()).unwrap();
+
+        let decode = |paths: &[&str]| {
+            let projection = Projection::from_paths(paths.iter().copied());
+            from_avro_datum_projected(&schema, &mut &encoded[..], &projection).unwrap()
+        };
+
+        assert_eq!(decode(&["a", "b", "c", "f"]), value);
+        assert_eq!(decode(&["c", "c.d"]), decode(&["c"]));
+        assert_eq!(decode(&[]), Value::Record(vec![]));
+        assert_eq!(
+            decode(&["f.e", "c.d", "a"]),
+            Value::Record(vec![
+                ("a".into(), Value::Long(1)),
+                (
+                    "c".into(),
+                    Value::Union {
+                        index: 1,
+                        inner: Box::new(Value::Record(vec![(
+                            "d".into(),
+                            Value::String("foo".into())
+                        )])),
+                        n_variants: 2,
+                        null_variant: Some(0),
+                    },
+                ),
+                (
+                    "f".into(),
+                    Value::Map(
+                        [(
+                            "k".to_string(),
+                            Value::Record(vec![("e".into(), Value::Int(3))])
+                        )]
+                        .into()
+                    ),
+                ),
+            ])
+        );
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: inline assembly is not supported
+    fn test_from_avro_datum_borrowed() {
+        let writer_schema: Schema = r#"
+            {
+                "type": "record",
+                "name": "test",
+                "fields": [
+                    {"name": "a", "type": "string"},
+                    {"name": "b", "type": "bytes"},
+                    {"name": "c", "type": {"type": "map", "values": ["null", "string"]}},
+                    {"name": "d", "type": "int"},
+                    {"name": "e", "type": {"type": "enum", "name": "e", "symbols": ["X", "Y"]}}
+                ]
+            }
+        "#
+        .parse()
+        .unwrap();
+        let value = Value::Record(vec![
+            ("a".into(), Value::String("foo".into())),
+            ("b".into(), Value::Bytes(vec![1, 2, 3])),
+            (
+                "c".into(),
+                Value::Map(
+                    [(
+                        "k".to_string(),
+                        Value::Union {
+                            index: 1,
+                            inner: Box::new(Value::String("bar".into())),
+                            n_variants: 2,
+                            null_variant: Some(0),
+                        },
+                    )]
+                    .into(),
+                ),
+            ),
+            ("d".into(), Value::Int(7)),
+            ("e".into(), Value::Enum(1, "Y".into())),
+        ]);
+        let encoded = crate::to_avro_datum(&writer_schema, value.clone()).unwrap();
+
+        let mut buf = &encoded[..];
+        let borrowed = from_avro_datum_borrowed(&writer_schema, &mut buf).unwrap();
+        assert!(buf.is_empty());
+        assert_eq!(borrowed.to_value(), value);
+        match &borrowed {
+            ValueRef::Record(fields) => {
+                let ValueRef::String(a) = fields[0].1 else {
+                    panic!("unexpected value: {:?}", fields[0].1);
+                };
+                // The string points into the encoded datum.
+                assert!(encoded.as_ptr_range().contains(&a.as_ptr()));
+            }
+            other => panic!("unexpected value: {other:?}"),
+        }
+
+        // Resolution against a reader schema produces the same values as
+        // the owned decoder.
+        let reader_schema: Schema = r#"
+            {
+                "type": "record",
+                "name": "test",
+                "fields": [
+                    {"name": "d", "type": "long"},
+                    {"name": "a", "type": "string"},
+                    {"name": "f", "type": "string", "default": "baz"},
+                    {"name": "e", "type": {"type": "enum", "name": "e", "symbols": ["Y", "Z"]}}
+                ]
+            }
+        "#
+        .parse()
+        .unwrap();
+        let resolved = resolve_schemas(&writer_schema, &reader_schema).unwrap();
+        let borrowed = from_avro_datum_borrowed(&resolved, &mut &encoded[..]).unwrap();
+        let owned = from_avro_datum(&resolved, &mut &encoded[..]).unwrap();
+        assert_eq!(borrowed.to_value(), owned);
+
+        let truncated = &encoded[..encoded.len() - 1];
+        assert!(from_avro_datum_borrowed(&writer_schema, &mut &truncated[..]).is_err());
+    }
+
     #[mz_ore::test]
     fn test_null_union() {
         let schema: Schema = UNION_SCHEMA.parse().unwrap();
@@ -1058,6 +1538,143 @@ mod tests {
         }
     }
 
+    fn encode_records(codec: Codec, values: &[(i64, String)]) -> Vec<u8> {
+        let schema: Schema = SCHEMA.parse().unwrap();
+        let mut writer = Writer::with_codec(schema.clone(), Vec::new(), codec);
+        for (a, b) in values {
+            let mut record = Record::new(schema.top_node()).unwrap();
+            record.put("a", *a);
+            record.put("b", b.as_str());
+            writer.append(record).unwrap();
+        }
+        writer.flush().unwrap();
+        writer.into_inner()
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: inline assembly is not supported
+    fn test_streaming_reader() {
+        let schema: Schema = SCHEMA.parse().unwrap();
+        let expected = Reader::with_schema(&schema, ENCODED)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let actual = StreamingReader::with_schema(&schema, ENCODED)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual, expected);
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: inline assembly is not supported
+    fn test_streaming_reader_codecs() {
+        // Enough values to span several blocks.
+        let values = (0..5000)
+            .map(|i| (i, format!("value {i}")))
+            .collect::<Vec<_>>();
+        let mut codecs = vec![Codec::Deflate];
+        #[cfg(feature = "snappy")]
+        codecs.push(Codec::Snappy);
+        #[cfg(feature = "zstandard")]
+        codecs.push(Codec::Zstandard);
+
+        for codec in codecs {
+            let encoded = encode_records(codec, &values);
+
+            let expected = Reader::new(&encoded[..])
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let actual = StreamingReader::new(&encoded[..])
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(actual.len(), values.len(), "{codec:?}");
+            assert_eq!(actual, expected, "{codec:?}");
+        }
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: inline assembly is not supported
+    fn test_reader_block_too_large() {
+        let too_large = |e: AvroError| {
+            assert_eq!(
+                e,
+                AvroError::Decode(DecodeError::BlockTooLarge { limit: 4 }),
+            )
+        };
+
+        let mut reader = Reader::new(ENCODED).unwrap().with_max_block_bytes(4);
+        too_large(reader.next().unwrap().unwrap_err());
+        assert!(reader.next().is_none());
+
+        let mut reader = StreamingReader::new(ENCODED)
+            .unwrap()
+            .with_max_block_bytes(4);
+        too_large(reader.next().unwrap().unwrap_err());
+        assert!(reader.next().is_none());
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: inline assembly is not supported
+    fn test_reader_decompressed_block_too_large() {
+        // Highly compressible values, so that the block is well under the
+        // limit when compressed but well over it when decompressed.
+        let values = (0..100).map(|i| (i, "x".repeat(1000))).collect::<Vec<_>>();
+        let encoded = encode_records(Codec::Deflate, &values);
+        let limit = 10_000;
+
+        let err = Reader::new(&encoded[..])
+            .unwrap()
+            .with_max_block_bytes(limit)
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err, AvroError::Decode(DecodeError::BlockTooLarge { limit }));
+
+        let results = StreamingReader::new(&encoded[..])
+            .unwrap()
+            .with_max_block_bytes(limit)
+            .collect::<Vec<_>>();
+        // Values are yielded as they are decoded, up until the limit is hit.
+        let (last, decoded) = results.split_last().unwrap();
+        assert!(!decoded.is_empty());
+        assert!(decoded.iter().all(|value| value.is_ok()));
+        assert_eq!(
+            last.clone().unwrap_err(),
+            AvroError::Decode(DecodeError::BlockTooLarge { limit })
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_reader_negative_block_size() {
+        let mut invalid = ENCODED.iter().copied().take(163).collect::<Vec<u8>>();
+        // A block of one object whose size is -1.
+        invalid.extend([2, 1]);
+
+        let err = Reader::new(&invalid[..])
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AvroError::Decode(DecodeError::ExpectedNonnegInteger(-1))
+        );
+
+        let err = StreamingReader::new(&invalid[..])
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AvroError::Decode(DecodeError::ExpectedNonnegInteger(-1))
+        );
+    }
+
     #[mz_ore::test]
     fn test_resolution_nested_types_error() {
         let r = r#"
@@ -38,6 +38,7 @@ use regex::Regex;
 use serde::ser::{SerializeMap, SerializeSeq};
 use serde::{Serialize, Serializer};
 use serde_json::{self, Map, Value};
+use sha2::Sha256;
 use tracing::{debug, warn};
 
 use crate::decode::build_ts_value;
@@ -50,23 +51,51 @@ pub fn resolve_schemas(
     writer_schema: &Schema,
     reader_schema: &Schema,
 ) -> Result<Schema, AvroError> {
-    let r_indices = reader_schema.indices.clone();
-    let (reader_to_writer_names, writer_to_reader_names): (BTreeMap<_, _>, BTreeMap<_, _>) =
-        writer_schema
-            .indices
+    let mut resolver = new_resolver(writer_schema, reader_schema);
+    let writer_node = writer_schema.top_node_or_named();
+    let reader_node = reader_schema.top_node_or_named();
+    let inner = resolver.resolve(writer_node, reader_node)?;
+    let sch = Schema {
+        named: resolver.named.into_iter().map(Option::unwrap).collect(),
+        indices: resolver.indices,
+        top: inner,
+    };
+    Ok(sch)
+}
+
+fn new_resolver<'a>(writer_schema: &Schema, reader_schema: &'a Schema) -> SchemaResolver<'a> {
+    let mut writer_to_reader_names = BTreeMap::new();
+    let mut reader_to_writer_names = BTreeMap::new();
+    for (name, widx) in &writer_schema.indices {
+        if let Some(ridx) = reader_schema.indices.get(name) {
+            writer_to_reader_names.insert(*widx, *ridx);
+            reader_to_writer_names.insert(*ridx, *widx);
+        }
+    }
+    // A reader type that no writer type shares a name with matches the
+    // writer type named by the first of its aliases that the writer defines,
+    // unless that writer type already matched by name.
+    for (ridx, piece) in reader_schema.named.iter().enumerate() {
+        if reader_to_writer_names.contains_key(&ridx) {
+            continue;
+        }
+        let widx = piece
+            .aliases
             .iter()
-            .flat_map(|(name, widx)| {
-                r_indices
-                    .get(name)
-                    .map(|ridx| ((*ridx, *widx), (*widx, *ridx)))
-            })
-            .unzip();
+            .find_map(|alias| writer_schema.indices.get(alias));
+        if let Some(widx) = widx {
+            if let Entry::Vacant(e) = writer_to_reader_names.entry(*widx) {
+                e.insert(ridx);
+                reader_to_writer_names.insert(ridx, *widx);
+            }
+        }
+    }
     let reader_fullnames = reader_schema
         .indices
         .iter()
         .map(|(f, i)| (*i, f))
         .collect::<BTreeMap<_, _>>();
-    let mut resolver = SchemaResolver {
+    SchemaResolver {
         named: Default::default(),
         indices: Default::default(),
         human_readable_field_path: Vec::new(),
@@ -76,16 +105,234 @@ pub fn resolve_schemas(
         reader_to_resolved_names: Default::default(),
         reader_fullnames,
         reader_schema,
-    };
-    let writer_node = writer_schema.top_node_or_named();
-    let reader_node = reader_schema.top_node_or_named();
-    let inner = resolver.resolve(writer_node, reader_node)?;
-    let sch = Schema {
-        named: resolver.named.into_iter().map(Option::unwrap).collect(),
-        indices: resolver.indices,
-        top: inner,
-    };
-    Ok(sch)
+    }
+}
+
+/// A way in which a writer schema is incompatible with a reader schema, as
+/// reported by [`Schema::compatible_with`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaIncompatibility {
+    /// The dot-separated path of the reader field at which the
+    /// incompatibility was found, or empty if it is at the top level.
+    pub path: String,
+    pub reason: String,
+}
+
+impl fmt::Display for SchemaIncompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.reason)
+        } else {
+            write!(f, "field `{}`: {}", self.path, self.reason)
+        }
+    }
+}
+
+/// Walks a writer and a reader schema together, collecting every
+/// incompatibility between them.
+struct CompatibilityChecker<'a> {
+    writer: &'a Schema,
+    reader: &'a Schema,
+    /// Pairs of writer and reader records that have already been checked.
+    visited: BTreeSet<(usize, usize)>,
+    incompatibilities: Vec<SchemaIncompatibility>,
+}
+
+impl<'a> CompatibilityChecker<'a> {
+    fn push(&mut self, path: &[String], reason: String) {
+        self.incompatibilities.push(SchemaIncompatibility {
+            path: path.join("."),
+            reason,
+        });
+    }
+
+    fn push_error(&mut self, path: &[String], e: &AvroError) {
+        let reason = match e {
+            AvroError::ResolveSchema(e) => e.to_string(),
+            e => e.to_string(),
+        };
+        self.push(path, reason);
+    }
+
+    fn check(
+        &mut self,
+        writer: SchemaNodeOrNamed<'a>,
+        reader: SchemaNodeOrNamed<'a>,
+        path: &mut Vec<String>,
+    ) {
+        let (writer_schema, reader_schema) = (self.writer, self.reader);
+
+        // Descend into records of the same name field by field, so that every
+        // incompatible field is reported rather than only the first.
+        if let (SchemaPieceRefOrNamed::Named(w_idx), SchemaPieceRefOrNamed::Named(r_idx)) =
+            (writer.inner, reader.inner)
+        {
+            let ws = writer_schema.lookup(w_idx);
+            let rs = reader_schema.lookup(r_idx);
+            if let (
+                SchemaPiece::Record {
+                    fields: w_fields,
+                    lookup: w_lookup,
+                    ..
+                },
+                SchemaPiece::Record {
+                    fields: r_fields, ..
+                },
+            ) = (&ws.piece, &rs.piece)
+            {
+                if ws.name == rs.name || rs.aliases.contains(&ws.name) {
+                    if !self.visited.insert((w_idx, r_idx)) {
+                        return;
+                    }
+                    for rf in r_fields {
+                        path.push(rf.name.clone());
+                        match rf.writer_position(w_lookup) {
+                            Some(w_index) => {
+                                let w_node = SchemaNodeOrNamed {
+                                    root: writer_schema,
+                                    inner: w_fields[w_index].schema.as_ref(),
+                                };
+                                let r_node = SchemaNodeOrNamed {
+                                    root: reader_schema,
+                                    inner: rf.schema.as_ref(),
+                                };
+                                self.check(w_node, r_node, path);
+                            }
+                            None if rf.default.is_none() => self
+                                .push(path, "not found in writer, and has no default".to_string()),
+                            None => (),
+                        }
+                        path.pop();
+                    }
+                    return;
+                }
+            }
+        }
+
+        let mut resolver = new_resolver(writer_schema, reader_schema);
+        match resolver.resolve(writer, reader) {
+            Ok(resolved) => {
+                self.check_resolved(&resolved, &resolver.named, &mut BTreeSet::new(), path)
+            }
+            Err(e) => self.push_error(path, &e),
+        }
+    }
+
+    /// Collects the failures that schema resolution defers until a value is
+    /// decoded: writer union variants with no counterpart in the reader, and
+    /// writer enum symbols that the reader can't map to a symbol or default.
+    fn check_resolved(
+        &mut self,
+        node: &SchemaPieceOrNamed,
+        named: &[Option<NamedSchemaPiece>],
+        seen: &mut BTreeSet<usize>,
+        path: &mut Vec<String>,
+    ) {
+        match node {
+            SchemaPieceOrNamed::Piece(SchemaPiece::ResolveUnionUnion { permutation, .. }) => {
+                for variant in permutation {
+                    match variant {
+                        Ok((_, inner)) => self.check_resolved(inner, named, seen, path),
+                        Err(e) => self.push_error(path, e),
+                    }
+                }
+            }
+            SchemaPieceOrNamed::Piece(
+                SchemaPiece::ResolveConcreteUnion { inner, .. }
+                | SchemaPiece::ResolveUnionConcrete { inner, .. }
+                | SchemaPiece::Array(inner)
+                | SchemaPiece::Map(inner),
+            ) => self.check_resolved(inner, named, seen, path),
+            SchemaPieceOrNamed::Piece(_) => (),
+            SchemaPieceOrNamed::Named(idx) => {
+                if !seen.insert(*idx) {
+                    return;
+                }
+                let Some(Some(named_piece)) = named.get(*idx) else {
+                    return;
+                };
+                match &named_piece.piece {
+                    SchemaPiece::ResolveEnum {
+                        symbols,
+                        default: None,
+                        ..
+                    } => {
+                        for symbol in symbols {
+                            if let Err(symbol) = symbol {
+                                self.push(
+                                    path,
+                                    format!(
+                                        "writer enum symbol `{}` not found in reader, \
+                                        and reader has no default",
+                                        symbol
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                    SchemaPiece::ResolveRecord { fields, .. } => {
+                        for field in fields {
+                            if let ResolvedRecordField::Present(rf) = field {
+                                path.push(rf.name.clone());
+                                self.check_resolved(&rf.schema, named, seen, path);
+                                path.pop();
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+/// Caches the results of [`resolve_schemas`], so that decoding data written
+/// with a rotating set of writer schemas (e.g., on a topic with several
+/// producers) resolves each pair of schemas only once.
+///
+/// Entries are keyed by fingerprints of the full writer and reader schemas.
+/// Fingerprints of the [Parsing Canonical Form] are not suitable, since it
+/// strips attributes that affect resolution, like defaults and logical types.
+///
+/// [Parsing Canonical Form]:
+/// https://avro.apache.org/docs/1.8.2/spec.html#Parsing+Canonical+Form+for+Schemas
+#[derive(Default)]
+pub struct ResolutionCache {
+    cache: BTreeMap<(SchemaFingerprint, SchemaFingerprint), Result<Schema, AvroError>>,
+}
+
+impl ResolutionCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the result of resolving `writer_schema` against
+    /// `reader_schema`, performing the resolution only if this pair of
+    /// schemas has not been seen before. Resolution errors are cached as well.
+    pub fn resolve(
+        &mut self,
+        writer_schema: &Schema,
+        reader_schema: &Schema,
+    ) -> Result<&Schema, AvroError> {
+        let key = (
+            writer_schema.full_fingerprint(),
+            reader_schema.full_fingerprint(),
+        );
+        self.cache
+            .entry(key)
+            .or_insert_with(|| resolve_schemas(writer_schema, reader_schema))
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Returns the number of cached resolutions.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
 }
 
 /// Describes errors happened while parsing Avro schemas.
@@ -112,7 +359,7 @@ impl std::error::Error for ParseSchemaError {}
 /// Represents an Avro schema fingerprint
 /// More information about Avro schema fingerprints can be found in the
 /// [Avro Schema Fingerprint documentation](https://avro.apache.org/docs/current/spec.html#schema_fingerprints)
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SchemaFingerprint {
     pub bytes: Vec<u8>,
 }
@@ -380,6 +627,36 @@ impl std::fmt::Debug for Schema {
     }
 }
 
+/// An alias declared in a schema, under which the schema reads data that
+/// a writer wrote with a different name. See [`Schema::aliases`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaAlias {
+    /// The full name of the type that declares the alias, or of the record
+    /// containing the field that declares it.
+    pub type_name: String,
+    /// The field that declares the alias, if it is not declared by the type.
+    pub field: Option<String>,
+    /// The name under which a writer may have written the type or field.
+    pub alias: String,
+}
+
+impl fmt::Display for SchemaAlias {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.field {
+            Some(field) => write!(
+                f,
+                "field `{}.{}` reads writer field `{}`",
+                self.type_name, field, self.alias
+            ),
+            None => write!(
+                f,
+                "type `{}` reads writer type `{}`",
+                self.type_name, self.alias
+            ),
+        }
+    }
+}
+
 impl Schema {
     pub fn top_node(&self) -> SchemaNode {
         let (inner, name) = self.top.get_piece_and_name(self);
@@ -401,6 +678,34 @@ impl Schema {
     pub fn try_lookup_name(&self, name: &FullName) -> Option<&NamedSchemaPiece> {
         self.indices.get(name).map(|&idx| &self.named[idx])
     }
+    /// Returns the aliases declared by the named types and record fields of
+    /// this schema, which schema resolution uses to match writer types and
+    /// fields that have since been renamed.
+    pub fn aliases(&self) -> Vec<SchemaAlias> {
+        let mut aliases = vec![];
+        for named in &self.named {
+            let type_name = named.name.human_name();
+            for alias in &named.aliases {
+                aliases.push(SchemaAlias {
+                    type_name: type_name.clone(),
+                    field: None,
+                    alias: alias.human_name(),
+                });
+            }
+            if let SchemaPiece::Record { fields, .. } = &named.piece {
+                for field in fields {
+                    for alias in &field.aliases {
+                        aliases.push(SchemaAlias {
+                            type_name: type_name.clone(),
+                            field: Some(field.name.clone()),
+                            alias: alias.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        aliases
+    }
 }
 
 /// This type is used to simplify enum variant comparison between `Schema` and `types::Value`.
@@ -747,6 +1052,8 @@ pub struct RecordField {
     pub order: RecordFieldOrder,
     /// Position of the field in the list of `field` of its parent `Schema`
     pub position: usize,
+    /// Alternate names under which a writer may have written the field.
+    pub aliases: Vec<String>,
 }
 
 /// Represents any valid order for a `field` in a `record` Avro schema.
@@ -757,7 +1064,22 @@ pub enum RecordFieldOrder {
     Ignore,
 }
 
-impl RecordField {}
+impl RecordField {
+    /// Returns the position of the writer field that this reader field
+    /// reads, given the writer record's `lookup` table: the field with the
+    /// same name if there is one, or else the field named by the first of
+    /// this field's aliases that the writer defines.
+    pub fn writer_position(&self, writer_lookup: &BTreeMap<String, usize>) -> Option<usize> {
+        writer_lookup
+            .get(&self.name)
+            .or_else(|| {
+                self.aliases
+                    .iter()
+                    .find_map(|alias| writer_lookup.get(alias))
+            })
+            .copied()
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct UnionSchema {
@@ -934,6 +1256,14 @@ impl SchemaParser {
         };
         let fullname = name.fullname(default_namespace);
         let default_namespace = fullname.namespace.clone();
+        // Aliases that aren't fully qualified are relative to the namespace
+        // of the type that declares them.
+        let aliases = name
+            .aliases
+            .unwrap_or_default()
+            .iter()
+            .map(|alias| FullName::from_parts(alias, None, &default_namespace))
+            .collect();
         let idx = self.alloc_name(fullname.clone())?;
         let piece = match type_name {
             "record" => self.parse_record(&default_namespace, complex),
@@ -946,6 +1276,7 @@ impl SchemaParser {
             idx,
             NamedSchemaPiece {
                 name: fullname,
+                aliases,
                 piece,
             },
         );
@@ -1025,6 +1356,18 @@ impl SchemaParser {
         for field in &fields {
             lookup.insert(field.name.clone(), field.position);
         }
+        // A writer field must resolve to at most one reader field, so no
+        // alias may repeat a field name or another alias.
+        let mut names: BTreeSet<_> = fields.iter().map(|field| &field.name).collect();
+        for alias in fields.iter().flat_map(|field| &field.aliases) {
+            if !names.insert(alias) {
+                return Err(ParseSchemaError::new(format!(
+                    "Field alias `{}` repeats a field name or another alias in the record",
+                    alias
+                ))
+                .into());
+            }
+        }
 
         Ok(SchemaPiece::Record {
             doc: complex.doc(),
@@ -1053,6 +1396,27 @@ impl SchemaParser {
 
         let default = field.get("default").cloned();
 
+        let aliases = match field.get("aliases") {
+            None => vec![],
+            Some(aliases) => aliases
+                .as_array()
+                .and_then(|aliases| {
+                    aliases
+                        .iter()
+                        .map(|alias| alias.as_str().map(|a| a.to_string()))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| {
+                    ParseSchemaError::new(format!(
+                        "`aliases` of field `{}` must be an array of strings",
+                        name
+                    ))
+                })?,
+        };
+        for alias in &aliases {
+            Name::validate(alias)?;
+        }
+
         let order = field
             .get("order")
             .and_then(|order| order.as_str())
@@ -1071,6 +1435,7 @@ impl SchemaParser {
             schema,
             order,
             position,
+            aliases,
         })
     }
 
@@ -1186,7 +1551,7 @@ impl SchemaParser {
             return Err(ParseSchemaError::new("Decimal scale must be greater than zero").into());
         }
 
-        if precision < 0 {
+        if precision < 1 {
             return Err(
                 ParseSchemaError::new("Decimal precision must be greater than zero").into(),
             );
@@ -1338,7 +1703,11 @@ impl SchemaParser {
         if let Some("decimal") = logical_type {
             match Self::parse_decimal(complex) {
                 Ok((precision, scale)) => {
-                    let max = ((2_usize.pow((8 * size - 1) as u32) - 1) as f64).log10() as usize;
+                    // The largest number of base-10 digits that fits in a
+                    // signed integer of `size` bytes, i.e.
+                    // floor(log10(2^(8 * size - 1) - 1)). Computed in floating
+                    // point, since the power overflows for sizes beyond 8.
+                    let max = ((8 * size - 1) as f64 * 2_f64.log10()).floor() as usize;
                     if precision > max {
                         warn!("Decimal precision {} requires more than {} bytes of space, parsing as fixed", precision, size);
                     } else {
@@ -1396,6 +1765,43 @@ impl Schema {
         }
     }
 
+    /// Checks whether data written with `writer` can be read with this schema,
+    /// following the rules of [`resolve_schemas`]: matching names, type
+    /// promotions, and defaults for reader fields missing from the writer.
+    ///
+    /// Unlike [`resolve_schemas`], which stops at the first error, this
+    /// reports every incompatibility found. That includes writer union
+    /// variants and enum symbols that resolution only rejects when a value
+    /// using them is decoded.
+    pub fn compatible_with(&self, writer: &Schema) -> Result<(), Vec<SchemaIncompatibility>> {
+        let mut checker = CompatibilityChecker {
+            writer,
+            reader: self,
+            visited: BTreeSet::new(),
+            incompatibilities: vec![],
+        };
+        checker.check(
+            writer.top_node_or_named(),
+            self.top_node_or_named(),
+            &mut vec![],
+        );
+        if checker.incompatibilities.is_empty() {
+            Ok(())
+        } else {
+            Err(checker.incompatibilities)
+        }
+    }
+
+    /// Generates a fingerprint of the full schema, including the attributes
+    /// that are stripped from its Parsing Canonical Form.
+    fn full_fingerprint(&self) -> SchemaFingerprint {
+        let mut d = Sha256::new();
+        d.update(serde_json::to_string(self).unwrap());
+        SchemaFingerprint {
+            bytes: d.finalize().to_vec(),
+        }
+    }
+
     /// Parse a `serde_json::Value` representing a primitive Avro type into a
     /// `Schema`.
     fn parse_primitive(primitive: &str) -> Result<SchemaPiece, AvroError> {
@@ -1427,6 +1833,8 @@ impl FromStr for Schema {
 #[derive(Clone, Debug, PartialEq)]
 pub struct NamedSchemaPiece {
     pub name: FullName,
+    /// Alternate names under which a writer may have written the type.
+    pub aliases: Vec<FullName>,
     pub piece: SchemaPiece,
 }
 
@@ -1620,6 +2028,7 @@ impl<'a> SchemaSubtreeDeepCloner<'a> {
                         schema: self.clone_piece_or_named(rf.schema.as_ref()),
                         order: rf.order,
                         position: rf.position,
+                        aliases: rf.aliases.clone(),
                     })
                     .collect(),
                 lookup: lookup.clone(),
@@ -1651,6 +2060,7 @@ impl<'a> SchemaSubtreeDeepCloner<'a> {
                                 schema: self.clone_piece_or_named(rf.schema.as_ref()),
                                 order: rf.order,
                                 position: rf.position,
+                                aliases: rf.aliases.clone(),
                             })
                         }
                         ResolvedRecordField::Absent(writer_schema) => {
@@ -1683,6 +2093,7 @@ impl<'a> SchemaSubtreeDeepCloner<'a> {
                         let old_named_piece = self.old_root.lookup(index);
                         let new_named_piece = NamedSchemaPiece {
                             name: old_named_piece.name.clone(),
+                            aliases: old_named_piece.aliases.clone(),
                             piece: self.clone_piece(&old_named_piece.piece),
                         };
                         self.named[new_index] = Some(new_named_piece);
@@ -2001,6 +2412,11 @@ impl<'a> Serialize for SchemaSerContext<'a> {
                 let name = &named_piece.name;
                 map.insert(index, name.clone());
                 std::mem::drop(map);
+                let aliases: Vec<_> = named_piece
+                    .aliases
+                    .iter()
+                    .map(|alias| alias.short_name(&name.namespace))
+                    .collect();
                 match &named_piece.piece {
                     SchemaPiece::Record { doc, fields, .. } => {
                         let mut map = serializer.serialize_map(None)?;
@@ -2012,7 +2428,9 @@ impl<'a> Serialize for SchemaSerContext<'a> {
                         if let Some(ref docstr) = doc {
                             map.serialize_entry("doc", docstr)?;
                         }
-                        // TODO (brennan) - serialize aliases
+                        if !aliases.is_empty() {
+                            map.serialize_entry("aliases", &aliases)?;
+                        }
                         map.serialize_entry(
                             "fields",
                             &fields
@@ -2036,6 +2454,9 @@ impl<'a> Serialize for SchemaSerContext<'a> {
                         if self.enclosing_ns != &name.namespace {
                             map.serialize_entry("namespace", &name.namespace)?;
                         }
+                        if !aliases.is_empty() {
+                            map.serialize_entry("aliases", &aliases)?;
+                        }
                         map.serialize_entry("symbols", symbols)?;
                         if let Some(default_idx) = *default_idx {
                             assert!(default_idx < symbols.len());
@@ -2050,6 +2471,9 @@ impl<'a> Serialize for SchemaSerContext<'a> {
                         if self.enclosing_ns != &name.namespace {
                             map.serialize_entry("namespace", &name.namespace)?;
                         }
+                        if !aliases.is_empty() {
+                            map.serialize_entry("aliases", &aliases)?;
+                        }
                         map.serialize_entry("size", size)?;
                         map.end()
                     }
@@ -2144,6 +2568,9 @@ impl<'a> Serialize for RecordFieldSerContext<'a> {
         if let Some(doc) = &self.inner.doc {
             map.serialize_entry("doc", doc)?;
         }
+        if !self.inner.aliases.is_empty() {
+            map.serialize_entry("aliases", &self.inner.aliases)?;
+        }
         map.end()
     }
 }
@@ -2283,6 +2710,7 @@ fn field_ordering_position(field: &str) -> Option<usize> {
 #[cfg(test)]
 mod tests {
     use crate::types::{Record, ToAvro};
+    use crate::Rabin;
 
     use super::*;
 
@@ -2397,6 +2825,7 @@ mod tests {
                     schema: SchemaPiece::Long.into(),
                     order: RecordFieldOrder::Ascending,
                     position: 0,
+                    aliases: vec![],
                 },
                 RecordField {
                     name: "b".to_string(),
@@ -2405,6 +2834,7 @@ mod tests {
                     schema: SchemaPiece::String.into(),
                     order: RecordFieldOrder::Ascending,
                     position: 1,
+                    aliases: vec![],
                 },
             ],
             lookup,
@@ -2652,6 +3082,39 @@ mod tests {
         };
         check_schema(schema, expected);
 
+        // Sizes whose maximum value exceeds 64 bits.
+        let schema = r#"{
+                "type": "fixed",
+                "name": "dec",
+                "size": 16,
+                "logicalType": "decimal",
+                "precision": 38,
+                "scale": 10
+            }"#;
+        let expected = SchemaPiece::Decimal {
+            precision: 38,
+            scale: 10,
+            fixed_size: Some(16),
+        };
+        check_schema(schema, expected);
+
+        // Precisions that don't fit in the fixed size, and invalid precisions,
+        // fall back to the underlying type.
+        let schema = r#"{
+                "type": "fixed",
+                "name": "dec",
+                "size": 16,
+                "logicalType": "decimal",
+                "precision": 39
+            }"#;
+        check_schema(schema, SchemaPiece::Fixed { size: 16 });
+        let schema = r#"{
+                "type": "bytes",
+                "logicalType": "decimal",
+                "precision": 0
+            }"#;
+        check_schema(schema, SchemaPiece::Bytes);
+
         let res = Schema::from_str(
             r#"["bytes", {
                 "type": "bytes",
@@ -2961,8 +3424,6 @@ mod tests {
     #[mz_ore::test]
     #[cfg_attr(miri, ignore)] // unsupported operation: inline assembly is not supported
     fn test_schema_fingerprint() {
-        use sha2::Sha256;
-
         let raw_schema = r#"
         {
             "type": "record",
@@ -2981,6 +3442,10 @@ mod tests {
             format!("{}", schema.fingerprint::<Sha256>()),
             expected_fingerprint
         );
+        assert_eq!(
+            schema.fingerprint::<Rabin>().bytes,
+            Rabin::digest(expected_canonical).to_vec()
+        );
 
         let raw_schema = r#"
 {
@@ -3009,6 +3474,109 @@ mod tests {
         );
     }
 
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: inline assembly is not supported
+    fn test_resolution_cache() {
+        let reader = Schema::from_str(
+            r#"{"type": "record", "name": "r", "fields": [{"name": "a", "type": "long"}]}"#,
+        )
+        .unwrap();
+        let writer = Schema::from_str(
+            r#"{"type": "record", "name": "r", "fields": [{"name": "a", "type": "int"}]}"#,
+        )
+        .unwrap();
+        // Differs from `reader` only in a default, which its canonical form strips.
+        let reader_with_default = Schema::from_str(
+            r#"{"type": "record", "name": "r", "fields": [{"name": "a", "type": "long"}, {"name": "b", "type": "long", "default": 1}]}"#,
+        )
+        .unwrap();
+        let incompatible = Schema::from_str(
+            r#"{"type": "record", "name": "r", "fields": [{"name": "a", "type": "string"}]}"#,
+        )
+        .unwrap();
+
+        let mut cache = ResolutionCache::new();
+        assert!(cache.is_empty());
+        let resolved = cache.resolve(&writer, &reader).unwrap().clone();
+        assert_eq!(resolved, resolve_schemas(&writer, &reader).unwrap());
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.resolve(&writer, &reader).unwrap(), &resolved);
+        assert_eq!(cache.len(), 1);
+
+        cache.resolve(&writer, &reader_with_default).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        assert!(cache.resolve(&incompatible, &reader).is_err());
+        assert!(cache.resolve(&incompatible, &reader).is_err());
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: inline assembly is not supported
+    fn test_compatible_with() {
+        let writer = Schema::from_str(
+            r#"{
+                "type": "record",
+                "name": "r",
+                "fields": [
+                    {"name": "a", "type": "int"},
+                    {"name": "c", "type": {"type": "enum", "name": "e", "symbols": ["X", "Y"]}},
+                    {"name": "u", "type": ["null", "string"]},
+                    {"name": "n", "type": {"type": "record", "name": "inner", "fields": [{"name": "x", "type": "int"}]}}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        // Promotions, defaults, and dropped writer fields are all compatible.
+        let reader = Schema::from_str(
+            r#"{
+                "type": "record",
+                "name": "r",
+                "fields": [
+                    {"name": "a", "type": "long"},
+                    {"name": "b", "type": "string", "default": ""},
+                    {"name": "u", "type": ["null", "string"]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(reader.compatible_with(&writer), Ok(()));
+
+        let reader = Schema::from_str(
+            r#"{
+                "type": "record",
+                "name": "r",
+                "fields": [
+                    {"name": "a", "type": "string"},
+                    {"name": "b", "type": "string"},
+                    {"name": "c", "type": {"type": "enum", "name": "e", "symbols": ["X"]}},
+                    {"name": "u", "type": ["null", "long"]},
+                    {"name": "n", "type": {"type": "record", "name": "inner", "fields": [{"name": "x", "type": "string"}]}}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let incompatibilities = reader.compatible_with(&writer).unwrap_err();
+        assert_eq!(
+            incompatibilities
+                .iter()
+                .map(|i| i.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c", "u", "n.x"],
+        );
+        assert_eq!(
+            incompatibilities[1].to_string(),
+            "field `b`: not found in writer, and has no default"
+        );
+        assert_eq!(
+            incompatibilities[2].to_string(),
+            "field `c`: writer enum symbol `Y` not found in reader, and reader has no default"
+        );
+        // Resolution itself only reports the first of these.
+        assert!(resolve_schemas(&writer, &reader).is_err());
+    }
+
     #[mz_ore::test]
     fn test_make_valid() {
         for (input, expected) in [
@@ -61,6 +61,42 @@ pub struct DecimalValue {
     pub scale: usize,
 }
 
+impl DecimalValue {
+    /// Returns the number of base-10 digits in the unscaled value, ignoring
+    /// its sign.
+    pub fn digits(&self) -> usize {
+        let negative = self.unscaled.first().is_some_and(|b| b & 0x80 != 0);
+        // The magnitude, as a big-endian unsigned integer.
+        let mut magnitude = self.unscaled.clone();
+        if negative {
+            // Two's complement negation: invert, then add one.
+            for b in magnitude.iter_mut() {
+                *b = !*b;
+            }
+            for b in magnitude.iter_mut().rev() {
+                let (sum, overflow) = b.overflowing_add(1);
+                *b = sum;
+                if !overflow {
+                    break;
+                }
+            }
+        }
+
+        let mut digits = 0;
+        while magnitude.iter().any(|b| *b != 0) {
+            // Divide the magnitude by 10 in place, long-division style.
+            let mut remainder = 0u16;
+            for b in magnitude.iter_mut() {
+                let cur = (remainder << 8) | u16::from(*b);
+                *b = (cur / 10) as u8;
+                remainder = cur % 10;
+            }
+            digits += 1;
+        }
+        digits
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, EnumKind)] // Can't be Eq because there are floats
 #[enum_kind(ScalarKind)]
 pub enum Scalar {
@@ -165,6 +201,181 @@ pub enum Value {
     Uuid(uuid::Uuid),
 }
 
+/// A borrowed Avro value, whose strings, bytes, and names borrow from the
+/// buffer the value was decoded from and from its schema.
+///
+/// This is the zero-copy counterpart of [`Value`], produced by
+/// [`from_avro_datum_borrowed`](crate::from_avro_datum_borrowed). Decoding into
+/// a `ValueRef` only allocates for records, arrays, maps, and unions, and for
+/// values that must be parsed, like JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValueRef<'a> {
+    /// A `null` Avro value.
+    Null,
+    /// A `boolean` Avro value.
+    Boolean(bool),
+    /// A `int` Avro value.
+    Int(i32),
+    /// A `long` Avro value.
+    Long(i64),
+    /// A `float` Avro value.
+    Float(f32),
+    /// A `double` Avro value.
+    Double(f64),
+    /// A `Date` coming from an avro Logical `Date`, which is an i32 number of
+    /// days since the Unix epoch.
+    Date(i32),
+    /// A `DateTime` coming from an avro Logical `Timestamp`
+    Timestamp(NaiveDateTime),
+    /// A `decimal` Avro value. See [`Value::Decimal`].
+    Decimal {
+        /// An unscaled two's-complement integer value in big-endian byte order.
+        unscaled: &'a [u8],
+        precision: usize,
+        scale: usize,
+    },
+    /// A `bytes` Avro value.
+    Bytes(&'a [u8]),
+    /// A `string` Avro value.
+    String(&'a str),
+    /// A `fixed` Avro value.
+    Fixed(&'a [u8]),
+    /// An `enum` Avro value, represented by its position in the symbols list
+    /// of the schema and its symbol.
+    Enum(usize, &'a str),
+    /// An `union` Avro value. See [`Value::Union`].
+    Union {
+        /// The index of this variant in the reader schema
+        index: usize,
+        /// The value of the variant
+        inner: Box<ValueRef<'a>>,
+        /// The number of variants in the reader schema
+        n_variants: usize,
+        /// Which variant is null in the reader schema.
+        null_variant: Option<usize>,
+    },
+    /// An `array` Avro value.
+    Array(Vec<ValueRef<'a>>),
+    /// A `map` Avro value.
+    Map(BTreeMap<&'a str, ValueRef<'a>>),
+    /// A `record` Avro value, represented by a vector of (`<field name>`,
+    /// `value`) in the order of the reader schema's fields.
+    Record(Vec<(&'a str, ValueRef<'a>)>),
+    /// A `string` Avro value that has been interpreted as JSON.
+    Json(serde_json::Value),
+    /// A `Uuid` coming from an avro Logical `uuid`.
+    Uuid(uuid::Uuid),
+}
+
+impl<'a> ValueRef<'a> {
+    /// Converts the borrowed value into an owned [`Value`].
+    pub fn to_value(&self) -> Value {
+        match self {
+            ValueRef::Null => Value::Null,
+            ValueRef::Boolean(b) => Value::Boolean(*b),
+            ValueRef::Int(i) => Value::Int(*i),
+            ValueRef::Long(i) => Value::Long(*i),
+            ValueRef::Float(f) => Value::Float(*f),
+            ValueRef::Double(f) => Value::Double(*f),
+            ValueRef::Date(d) => Value::Date(*d),
+            ValueRef::Timestamp(ts) => Value::Timestamp(*ts),
+            ValueRef::Decimal {
+                unscaled,
+                precision,
+                scale,
+            } => Value::Decimal(DecimalValue {
+                unscaled: unscaled.to_vec(),
+                precision: *precision,
+                scale: *scale,
+            }),
+            ValueRef::Bytes(b) => Value::Bytes(b.to_vec()),
+            ValueRef::String(s) => Value::String(s.to_string()),
+            ValueRef::Fixed(b) => Value::Fixed(b.len(), b.to_vec()),
+            ValueRef::Enum(idx, symbol) => Value::Enum(*idx, symbol.to_string()),
+            ValueRef::Union {
+                index,
+                inner,
+                n_variants,
+                null_variant,
+            } => Value::Union {
+                index: *index,
+                inner: Box::new(inner.to_value()),
+                n_variants: *n_variants,
+                null_variant: *null_variant,
+            },
+            ValueRef::Array(items) => Value::Array(items.iter().map(|v| v.to_value()).collect()),
+            ValueRef::Map(entries) => Value::Map(
+                entries
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_value()))
+                    .collect(),
+            ),
+            ValueRef::Record(fields) => Value::Record(
+                fields
+                    .iter()
+                    .map(|(name, v)| (name.to_string(), v.to_value()))
+                    .collect(),
+            ),
+            ValueRef::Json(j) => Value::Json(j.clone()),
+            ValueRef::Uuid(u) => Value::Uuid(*u),
+        }
+    }
+}
+
+impl<'a> From<&'a Value> for ValueRef<'a> {
+    fn from(value: &'a Value) -> ValueRef<'a> {
+        match value {
+            Value::Null => ValueRef::Null,
+            Value::Boolean(b) => ValueRef::Boolean(*b),
+            Value::Int(i) => ValueRef::Int(*i),
+            Value::Long(i) => ValueRef::Long(*i),
+            Value::Float(f) => ValueRef::Float(*f),
+            Value::Double(f) => ValueRef::Double(*f),
+            Value::Date(d) => ValueRef::Date(*d),
+            Value::Timestamp(ts) => ValueRef::Timestamp(*ts),
+            Value::Decimal(DecimalValue {
+                unscaled,
+                precision,
+                scale,
+            }) => ValueRef::Decimal {
+                unscaled,
+                precision: *precision,
+                scale: *scale,
+            },
+            Value::Bytes(b) => ValueRef::Bytes(b),
+            Value::String(s) => ValueRef::String(s),
+            Value::Fixed(_, b) => ValueRef::Fixed(b),
+            Value::Enum(idx, symbol) => ValueRef::Enum(*idx, symbol),
+            Value::Union {
+                index,
+                inner,
+                n_variants,
+                null_variant,
+            } => ValueRef::Union {
+                index: *index,
+                inner: Box::new(ValueRef::from(&**inner)),
+                n_variants: *n_variants,
+                null_variant: *null_variant,
+            },
+            Value::Array(items) => ValueRef::Array(items.iter().map(ValueRef::from).collect()),
+            Value::Map(entries) => ValueRef::Map(
+                entries
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), ValueRef::from(v)))
+                    .collect(),
+            ),
+            Value::Record(fields) => ValueRef::Record(
+                fields
+                    .iter()
+                    .map(|(name, v)| (name.as_str(), ValueRef::from(v)))
+                    .collect(),
+            ),
+            Value::Json(j) => ValueRef::Json(j.clone()),
+            Value::Uuid(u) => ValueRef::Uuid(*u),
+        }
+    }
+}
+
 /// Any structure implementing the [ToAvro](trait.ToAvro.html) trait will be usable
 /// from a [Writer](../writer/struct.Writer.html).
 pub trait ToAvro {
@@ -366,17 +577,24 @@ impl Value {
             (&Value::Timestamp(_), SchemaPiece::TimestampMicro) => true,
             (&Value::Timestamp(_), SchemaPiece::TimestampMilli) => true,
             (
-                &Value::Decimal(DecimalValue {
-                    precision: vp,
-                    scale: vs,
-                    ..
-                }),
+                Value::Decimal(
+                    value @ DecimalValue {
+                        unscaled,
+                        precision: vp,
+                        scale: vs,
+                    },
+                ),
                 SchemaPiece::Decimal {
                     precision: sp,
                     scale: ss,
-                    fixed_size: _,
+                    fixed_size,
                 },
-            ) => vp == *sp && vs == *ss,
+            ) => {
+                vp == sp
+                    && vs == ss
+                    && fixed_size.map_or(true, |size| unscaled.len() <= size)
+                    && value.digits() <= *sp
+            }
             (&Value::Bytes(_), SchemaPiece::Bytes) => true,
             (&Value::String(_), SchemaPiece::String) => true,
             (&Value::Fixed(n, _), SchemaPiece::Fixed { size }) => n == *size,
@@ -649,4 +867,61 @@ mod tests {
             .top_node()
         ));
     }
+
+    #[mz_ore::test]
+    fn validate_decimal_unscaled() {
+        let bytes = Schema::from_str(
+            r#"{"type": "bytes", "logicalType": "decimal", "precision": 3, "scale": 1}"#,
+        )
+        .unwrap();
+        let fixed = Schema::from_str(
+            r#"{"type": "fixed", "name": "dec", "size": 1, "logicalType": "decimal", "precision": 2}"#,
+        )
+        .unwrap();
+        let decimal = |unscaled: Vec<u8>, precision| {
+            Value::Decimal(DecimalValue {
+                unscaled,
+                precision,
+                scale: 1,
+            })
+        };
+
+        // 999 and -999 fit in a precision of 3, but 1000 does not.
+        assert!(decimal(vec![0x03, 0xe7], 3).validate(bytes.top_node()));
+        assert!(decimal(vec![0xfc, 0x19], 3).validate(bytes.top_node()));
+        assert!(!decimal(vec![0x03, 0xe8], 3).validate(bytes.top_node()));
+
+        // Unscaled values must fit in the fixed size.
+        let fixed_decimal = |unscaled: Vec<u8>| {
+            Value::Decimal(DecimalValue {
+                unscaled,
+                precision: 2,
+                scale: 0,
+            })
+        };
+        assert!(fixed_decimal(vec![99]).validate(fixed.top_node()));
+        assert!(fixed_decimal(vec![0x9d]).validate(fixed.top_node()));
+        assert!(!fixed_decimal(vec![0, 99]).validate(fixed.top_node()));
+    }
+
+    #[mz_ore::test]
+    fn decimal_digits() {
+        for (unscaled, digits) in [
+            (vec![], 0),
+            (vec![0], 0),
+            (vec![0xff], 1),
+            (vec![9], 1),
+            (vec![10], 2),
+            (vec![0x80], 3),
+            (vec![0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], 19),
+            (vec![0x80, 0, 0, 0, 0, 0, 0, 0], 19),
+        ] {
+            let value = DecimalValue {
+                unscaled,
+                precision: 0,
+                scale: 0,
+            };
+            assert_eq!(value.digits(), digits, "{:?}", value.unscaled);
+        }
+    }
 }
@@ -35,13 +35,24 @@ use crate::encode::{encode, encode_ref, encode_to_vec};
 use crate::reader::Header;
 use crate::schema::{Schema, SchemaPiece};
 use crate::types::{ToAvro, Value};
-use crate::Codec;
+use crate::{Codec, Rabin};
 
 const SYNC_SIZE: usize = 16;
 const SYNC_INTERVAL: usize = 1000 * SYNC_SIZE; // TODO: parametrize in Writer
 
 const AVRO_OBJECT_HEADER: &[u8] = &[b'O', b'b', b'j', 1u8];
 
+/// The marker bytes that begin a datum in the Avro [single-object encoding].
+///
+/// [single-object encoding]: https://avro.apache.org/docs/current/spec.html#single_object_encoding
+pub const SINGLE_OBJECT_MAGIC: [u8; 2] = [0xc3, 0x01];
+
+/// The magic byte that begins a datum in the Confluent Schema Registry
+/// [wire format].
+///
+/// [wire format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+pub const CONFLUENT_MAGIC: u8 = 0;
+
 /// Describes errors happened while validating Avro data.
 #[derive(Debug)]
 pub struct ValidationError(String);
@@ -364,6 +375,53 @@ pub fn to_avro_datum<T: ToAvro>(schema: &Schema, value: T) -> Result<Vec<u8>, Er
     Ok(buffer)
 }
 
+/// Writes the header of the Avro single-object encoding: the marker bytes,
+/// followed by the little-endian Rabin fingerprint of the writer schema.
+///
+/// The fingerprint is that of the schema's Parsing Canonical Form, as computed
+/// by `schema.fingerprint::<Rabin>()`. Callers encoding many values should
+/// compute it once and reuse it.
+pub fn write_single_object_header(fingerprint: &[u8; 8], buffer: &mut Vec<u8>) {
+    buffer.extend_from_slice(&SINGLE_OBJECT_MAGIC);
+    buffer.extend_from_slice(fingerprint);
+}
+
+/// Encode a compatible value into the Avro single-object encoding, also
+/// performing schema validation.
+pub fn to_single_object_datum<T: ToAvro>(schema: &Schema, value: T) -> Result<Vec<u8>, Error> {
+    let fingerprint: [u8; 8] = schema
+        .fingerprint::<Rabin>()
+        .bytes
+        .try_into()
+        .expect("Rabin fingerprints are 8 bytes");
+    let mut buffer = Vec::new();
+    write_single_object_header(&fingerprint, &mut buffer);
+    write_avro_datum(schema, value, &mut buffer)?;
+    Ok(buffer)
+}
+
+/// Writes the header of the Confluent Schema Registry wire format: the magic
+/// byte, followed by the big-endian 32-bit ID of the writer schema in the
+/// registry.
+pub fn write_confluent_header(schema_id: i32, buffer: &mut Vec<u8>) {
+    buffer.push(CONFLUENT_MAGIC);
+    buffer.extend_from_slice(&schema_id.to_be_bytes());
+}
+
+/// Encode a compatible value into the Confluent Schema Registry wire format,
+/// also performing schema validation. `schema_id` must be the ID under which
+/// `schema` is registered.
+pub fn to_confluent_datum<T: ToAvro>(
+    schema_id: i32,
+    schema: &Schema,
+    value: T,
+) -> Result<Vec<u8>, Error> {
+    let mut buffer = Vec::new();
+    write_confluent_header(schema_id, &mut buffer);
+    write_avro_datum(schema, value, &mut buffer)?;
+    Ok(buffer)
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
@@ -635,4 +693,27 @@ mod tests {
             actual
         );
     }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: inline assembly is not supported
+    fn test_to_single_object_datum() {
+        let schema = Schema::from_str(r#""int""#).unwrap();
+        // The fingerprint of `"int"` is 8247732601305521295.
+        let expected = [
+            0xc3, 0x01, 0x8f, 0x5c, 0x39, 0x3f, 0x1a, 0xd5, 0x75, 0x72, 0x02,
+        ];
+        assert_eq!(to_single_object_datum(&schema, 1i32).unwrap(), expected);
+        assert!(to_single_object_datum(&schema, "foo").is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_to_confluent_datum() {
+        let schema = Schema::from_str(r#""int""#).unwrap();
+        let expected = [0x00, 0x01, 0x02, 0x03, 0x04, 0x02];
+        assert_eq!(
+            to_confluent_datum(0x01020304, &schema, 1i32).unwrap(),
+            expected
+        );
+        assert!(to_confluent_datum(1, &schema, "foo").is_err());
+    }
 }
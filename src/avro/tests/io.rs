@@ -285,6 +285,84 @@ fn test_default_value() {
     }
 }
 
+#[mz_ore::test]
+fn test_alias_resolution() {
+    let writer_schema = Schema::from_str(
+        r#"{
+            "type": "record",
+            "name": "OldRecord",
+            "namespace": "ns",
+            "fields": [
+                {"name": "old_a", "type": "long"},
+                {"name": "b", "type": {"type": "enum", "name": "OldEnum", "symbols": ["X", "Y"]}}
+            ]
+        }"#,
+    )
+    .unwrap();
+    let reader_schema = Schema::from_str(
+        r#"{
+            "type": "record",
+            "name": "NewRecord",
+            "namespace": "ns",
+            "aliases": ["OldRecord"],
+            "fields": [
+                {"name": "b", "type": {"type": "enum", "name": "NewEnum", "aliases": ["ns.OldEnum"], "symbols": ["Y", "X"]}},
+                {"name": "a", "aliases": ["old_a"], "type": "long"}
+            ]
+        }"#,
+    )
+    .unwrap();
+    let encoded = to_avro_datum(
+        &writer_schema,
+        Value::Record(vec![
+            ("old_a".to_string(), Value::Long(7)),
+            ("b".to_string(), Value::Enum(1, "Y".to_string())),
+        ]),
+    )
+    .unwrap();
+    let resolved_schema = resolve_schemas(&writer_schema, &reader_schema).unwrap();
+    let decoded = from_avro_datum(&resolved_schema, &mut Cursor::new(encoded)).unwrap();
+    assert_eq!(
+        decoded,
+        Value::Record(vec![
+            ("b".to_string(), Value::Enum(0, "Y".to_string())),
+            ("a".to_string(), Value::Long(7)),
+        ])
+    );
+    assert!(reader_schema.compatible_with(&writer_schema).is_ok());
+
+    let aliases: Vec<_> = reader_schema
+        .aliases()
+        .iter()
+        .map(|alias| alias.to_string())
+        .collect();
+    assert_eq!(
+        aliases,
+        vec![
+            "type `ns.NewRecord` reads writer type `ns.OldRecord`",
+            "field `ns.NewRecord.a` reads writer field `old_a`",
+            "type `ns.NewEnum` reads writer type `ns.OldEnum`",
+        ]
+    );
+
+    // Aliases survive a round trip through the schema's JSON representation.
+    let reparsed = Schema::from_str(&reader_schema.to_string()).unwrap();
+    assert_eq!(reparsed.aliases(), reader_schema.aliases());
+
+    // A writer field may only resolve to one reader field.
+    let ambiguous = Schema::from_str(
+        r#"{
+            "type": "record",
+            "name": "NewRecord",
+            "fields": [
+                {"name": "a", "aliases": ["b"], "type": "long"},
+                {"name": "b", "type": "long"}
+            ]
+        }"#,
+    );
+    assert!(ambiguous.is_err());
+}
+
 #[mz_ore::test]
 fn test_no_default_value() -> Result<(), String> {
     let reader_schema = Schema::from_str(
@@ -7,6 +7,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::future::Future;
+
 use aws_sdk_s3::config::Builder;
 use aws_sdk_s3::Client;
 use aws_types::sdk_config::SdkConfig;
@@ -24,26 +26,143 @@ pub fn new_client(sdk_config: &SdkConfig) -> Client {
     Client::from_conf(conf)
 }
 
+/// Lists the keys of all objects in `bucket` whose key starts with `prefix`.
+///
+/// `ListObjectsV2` returns at most 1000 keys per response, so this follows
+/// continuation tokens until the listing is exhausted. Returns `None` if no
+/// object matches the prefix.
 pub async fn list_bucket_path(
     client: &Client,
     bucket: &str,
     prefix: &str,
 ) -> Result<Option<Vec<String>>, anyhow::Error> {
-    let res = client
-        .list_objects_v2()
-        .bucket(bucket)
-        .prefix(prefix)
-        .send()
-        .await?;
-    Ok(res
-        .contents
-        .map(|objs| {
-            objs.into_iter()
-                .map(|obj| {
-                    obj.key
-                        .ok_or(anyhow::anyhow!("key not provided from list_objects_v2"))
-                })
-                .collect::<Result<Vec<String>, _>>()
+    collect_pages(|continuation_token| async move {
+        let res = client
+            .list_objects_v2()
+            .bucket(bucket)
+            .prefix(prefix)
+            .set_continuation_token(continuation_token)
+            .send()
+            .await?;
+        let keys = match res.contents {
+            Some(objs) => Some(
+                objs.into_iter()
+                    .map(|obj| {
+                        obj.key
+                            .ok_or(anyhow::anyhow!("key not provided from list_objects_v2"))
+                    })
+                    .collect::<Result<Vec<String>, _>>()?,
+            ),
+            None => None,
+        };
+        let next_continuation_token = match res.is_truncated {
+            Some(true) => res.next_continuation_token,
+            _ => None,
+        };
+        Ok::<_, anyhow::Error>(ListPage {
+            keys,
+            next_continuation_token,
+        })
+    })
+    .await
+}
+
+/// A page of a listing of object keys.
+struct ListPage {
+    /// The keys in this page, if the page has any contents.
+    keys: Option<Vec<String>>,
+    /// The token with which to request the next page, if the listing is truncated.
+    next_continuation_token: Option<String>,
+}
+
+/// Concatenates the keys of the pages returned by `fetch_page`, which is called with the
+/// continuation token of the previous page until a page has none.
+///
+/// Returns `None` if no page had any contents.
+async fn collect_pages<F, Fut>(mut fetch_page: F) -> Result<Option<Vec<String>>, anyhow::Error>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<ListPage, anyhow::Error>>,
+{
+    let mut keys: Option<Vec<String>> = None;
+    let mut continuation_token = None;
+    loop {
+        let page = fetch_page(continuation_token.take()).await?;
+        if let Some(page_keys) = page.keys {
+            keys.get_or_insert_with(Vec::new).extend(page_keys);
+        }
+        match page.next_continuation_token {
+            Some(token) => continuation_token = Some(token),
+            None => break,
+        }
+    }
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    /// Serves `pages`, each of which is keyed by the continuation token that requests it.
+    fn serve(
+        pages: BTreeMap<Option<&'static str>, (Option<Vec<&'static str>>, Option<&'static str>)>,
+        requests: &mut Vec<Option<String>>,
+    ) -> impl FnMut(Option<String>) -> std::future::Ready<Result<ListPage, anyhow::Error>> + '_
+    {
+        move |token| {
+            requests.push(token.clone());
+            let (keys, next) = pages[&token.as_deref()].clone();
+            std::future::ready(Ok(ListPage {
+                keys: keys.map(|keys| keys.into_iter().map(String::from).collect()),
+                next_continuation_token: next.map(String::from),
+            }))
+        }
+    }
+
+    #[mz_ore::test(tokio::test)]
+    async fn test_collect_pages_follows_continuation_tokens() {
+        let pages = BTreeMap::from([
+            (None, (Some(vec!["a", "b"]), Some("t1"))),
+            (Some("t1"), (Some(vec!["c"]), Some("t2"))),
+            (Some("t2"), (Some(vec!["d", "e"]), None)),
+        ]);
+        let mut requests = Vec::new();
+        let keys = collect_pages(serve(pages, &mut requests)).await.unwrap();
+        assert_eq!(
+            keys,
+            Some(
+                vec!["a", "b", "c", "d", "e"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            )
+        );
+        assert_eq!(
+            requests,
+            vec![None, Some("t1".to_string()), Some("t2".to_string())]
+        );
+    }
+
+    #[mz_ore::test(tokio::test)]
+    async fn test_collect_pages_empty() {
+        let pages = BTreeMap::from([(None, (None, None))]);
+        let mut requests = Vec::new();
+        let keys = collect_pages(serve(pages, &mut requests)).await.unwrap();
+        assert_eq!(keys, None);
+        assert_eq!(requests, vec![None]);
+    }
+
+    #[mz_ore::test(tokio::test)]
+    async fn test_collect_pages_error() {
+        let mut calls = 0;
+        let res = collect_pages(|_| {
+            calls += 1;
+            std::future::ready(Err(anyhow::anyhow!("boom")))
         })
-        .transpose()?)
+        .await;
+        assert!(res.is_err());
+        assert_eq!(calls, 1);
+    }
 }
@@ -16,7 +16,7 @@ use mz_ore::future::OreSinkExt;
 use mz_ore::netio::AsyncReady;
 use mz_pgwire_common::{
     parse_frame_len, Conn, Cursor, DecodeState, ErrorResponse, FrontendMessage, Pgbuf,
-    MAX_REQUEST_SIZE,
+    MAX_REQUEST_SIZE, STRUCTURED_FIELD_TYPE,
 };
 use tokio::io::{self, AsyncRead, AsyncWrite, Interest, Ready};
 use tokio_util::codec::{Decoder, Encoder, Framed};
@@ -173,6 +173,7 @@ impl Encoder<BackendMessage> for Codec {
                 detail,
                 hint,
                 position,
+                structured,
             }) => {
                 dst.put_u8(b'S');
                 dst.put_string(severity.as_str());
@@ -192,6 +193,10 @@ impl Encoder<BackendMessage> for Codec {
                     dst.put_u8(b'P');
                     dst.put_string(&position.to_string());
                 }
+                if let Some(structured) = &structured {
+                    dst.put_u8(STRUCTURED_FIELD_TYPE);
+                    dst.put_string(structured);
+                }
                 dst.put_u8(b'\0');
             }
         }
@@ -139,6 +139,12 @@ pub async fn run(args: Args) -> Result<(), anyhow::Error> {
             "exactly one of --static-resolver-addr or --frontegg-resolver-template must be present"
         ),
     };
+    let tls = args.tls.into_config()?;
+    if tls.as_ref().map_or(false, |tls| tls.client_ca.is_some()) {
+        // Client certificates identify clients to environmentd, which never
+        // sees them when balancerd terminates TLS.
+        anyhow::bail!("--tls-client-ca is not supported by balancerd");
+    }
     let config = BalancerConfig::new(
         &BUILD_INFO,
         args.sigterm_wait_seconds.map(Duration::from_secs),
@@ -148,7 +154,7 @@ pub async fn run(args: Args) -> Result<(), anyhow::Error> {
         args.cancellation_resolver_dir,
         resolver,
         args.https_resolver_template,
-        args.tls.into_config()?,
+        tls,
         metrics_registry,
         mz_server_core::default_cert_reload_ticker(),
     );
@@ -48,9 +48,9 @@ use mz_sql::session::user::{
 };
 use mz_storage_client::controller::IntrospectionType;
 use mz_storage_client::healthcheck::{
-    MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC, MZ_PREPARED_STATEMENT_HISTORY_DESC,
-    MZ_SESSION_HISTORY_DESC, MZ_SINK_STATUS_HISTORY_DESC, MZ_SOURCE_STATUS_HISTORY_DESC,
-    MZ_SQL_TEXT_DESC, MZ_STATEMENT_EXECUTION_HISTORY_DESC,
+    MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC, MZ_CLUSTER_REPLICA_EVENTS_DESC,
+    MZ_PREPARED_STATEMENT_HISTORY_DESC, MZ_SESSION_HISTORY_DESC, MZ_SINK_STATUS_HISTORY_DESC,
+    MZ_SOURCE_STATUS_HISTORY_DESC, MZ_SQL_TEXT_DESC, MZ_STATEMENT_EXECUTION_HISTORY_DESC,
 };
 use mz_storage_client::statistics::{MZ_SINK_STATISTICS_RAW_DESC, MZ_SOURCE_STATISTICS_RAW_DESC};
 use once_cell::sync::Lazy;
@@ -1818,6 +1818,14 @@ pub static MZ_COMPUTE_ERROR_COUNTS_RAW: Lazy<BuiltinLog> = Lazy::new(|| BuiltinL
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_COMPUTE_LIR_MAPPING_PER_WORKER: Lazy<BuiltinLog> = Lazy::new(|| BuiltinLog {
+    name: "mz_compute_lir_mapping_per_worker",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::LOG_MZ_COMPUTE_LIR_MAPPING_PER_WORKER_OID,
+    variant: LogVariant::Compute(ComputeLog::LirMapping),
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_ACTIVE_PEEKS_PER_WORKER: Lazy<BuiltinLog> = Lazy::new(|| BuiltinLog {
     name: "mz_active_peeks_per_worker",
     schema: MZ_INTERNAL_SCHEMA,
@@ -1914,6 +1922,7 @@ pub static MZ_KAFKA_SINKS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     desc: RelationDesc::empty()
         .with_column("id", ScalarType::String.nullable(false))
         .with_column("topic", ScalarType::String.nullable(false))
+        .with_column("delivery_guarantee", ScalarType::String.nullable(false))
         .with_key(vec![0]),
     is_retained_metrics_object: false,
     access: vec![PUBLIC_SELECT],
@@ -2010,6 +2019,22 @@ pub static MZ_COMPUTE_HYDRATION_STATUSES: Lazy<BuiltinSource> = Lazy::new(|| Bui
     is_retained_metrics_object: false,
     access: vec![PUBLIC_SELECT],
 });
+pub static MZ_COMPUTE_REAPED_DATAFLOWS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_compute_reaped_dataflows",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_COMPUTE_REAPED_DATAFLOWS_OID,
+    data_source: IntrospectionType::ComputeReapedDataflows,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("kind", ScalarType::String.nullable(false))
+        .with_column(
+            "reaped_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
 pub static MZ_COMPUTE_OPERATOR_HYDRATION_STATUSES_PER_WORKER: Lazy<BuiltinSource> =
     Lazy::new(|| BuiltinSource {
         name: "mz_compute_operator_hydration_statuses_per_worker",
@@ -2414,6 +2439,17 @@ pub static MZ_ROLE_PARAMETERS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     is_retained_metrics_object: false,
     access: vec![PUBLIC_SELECT],
 });
+pub static MZ_DATABASE_PARAMETERS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_database_parameters",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_DATABASE_PARAMETERS_OID,
+    desc: RelationDesc::empty()
+        .with_column("database_id", ScalarType::String.nullable(false))
+        .with_column("parameter_name", ScalarType::String.nullable(false))
+        .with_column("parameter_value", ScalarType::String.nullable(false)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
 pub static MZ_PSEUDO_TYPES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_pseudo_types",
     schema: MZ_CATALOG_SCHEMA,
@@ -2496,6 +2532,8 @@ pub static MZ_CLUSTERS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
             }
             .nullable(true),
         )
+        .with_column("autoscale_min_size", ScalarType::String.nullable(true))
+        .with_column("autoscale_max_size", ScalarType::String.nullable(true))
         .with_key(vec![0]),
     is_retained_metrics_object: false,
     access: vec![PUBLIC_SELECT],
@@ -2510,6 +2548,14 @@ pub static MZ_CLUSTER_SCHEDULES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable
         .with_column(
             "refresh_rehydration_time_estimate",
             ScalarType::Interval.nullable(true),
+        )
+        .with_column(
+            "off_during",
+            ScalarType::List {
+                element_type: Box::new(ScalarType::String),
+                custom_id: None,
+            }
+            .nullable(true),
         ),
     is_retained_metrics_object: false,
     access: vec![PUBLIC_SELECT],
@@ -2636,6 +2682,32 @@ pub static MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY: Lazy<BuiltinSource> =
         access: vec![PUBLIC_SELECT],
     });
 
+pub static MZ_STORAGE_SHARD_FINALIZATIONS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_storage_shard_finalizations",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_STORAGE_SHARD_FINALIZATIONS_OID,
+    data_source: IntrospectionType::StorageShardFinalizations,
+    desc: RelationDesc::empty()
+        .with_column("shard_id", ScalarType::String.nullable(false))
+        .with_column("reclaimed_bytes", ScalarType::UInt64.nullable(false))
+        .with_column(
+            "finalized_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_CLUSTER_REPLICA_EVENTS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_cluster_replica_events",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_CLUSTER_REPLICA_EVENTS_OID,
+    data_source: IntrospectionType::ReplicaEvents,
+    desc: MZ_CLUSTER_REPLICA_EVENTS_DESC.clone(),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_AWS_PRIVATELINK_CONNECTION_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_aws_privatelink_connection_statuses",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2851,6 +2923,40 @@ pub static MZ_STATEMENT_LIFECYCLE_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| Bu
     access: vec![SUPPORT_SELECT, MONITOR_REDACTED_SELECT, MONITOR_SELECT],
 });
 
+/// How long each phase of a logged statement's execution took, derived from
+/// the events in `mz_statement_lifecycle_history`. A phase is NULL if the
+/// statement never reached it, e.g. the timestamp selection and optimization
+/// phases of statements that aren't queries.
+pub static MZ_STATEMENT_LIFECYCLE_DURATIONS: Lazy<BuiltinView> = Lazy::new(|| {
+    BuiltinView {
+    name: "mz_statement_lifecycle_durations",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_STATEMENT_LIFECYCLE_DURATIONS_OID,
+    column_defs: None,
+    sql: "
+WITH events AS (
+    SELECT
+        statement_id,
+        min(CASE WHEN event_type = 'execution-began' THEN occurred_at END) AS began_at,
+        min(CASE WHEN event_type = 'planning-finished' THEN occurred_at END) AS planned_at,
+        min(CASE WHEN event_type = 'timestamp-selected' THEN occurred_at END) AS timestamp_selected_at,
+        min(CASE WHEN event_type = 'optimization-finished' THEN occurred_at END) AS optimized_at,
+        min(CASE WHEN event_type = 'execution-finished' THEN occurred_at END) AS finished_at
+    FROM mz_internal.mz_statement_lifecycle_history
+    GROUP BY statement_id
+)
+SELECT
+    statement_id,
+    planned_at - began_at AS planning_duration,
+    timestamp_selected_at - planned_at AS timestamp_selection_duration,
+    optimized_at - coalesce(timestamp_selected_at, planned_at) AS optimization_duration,
+    finished_at - coalesce(optimized_at, timestamp_selected_at, planned_at) AS execution_duration,
+    finished_at - began_at AS total_duration
+FROM events",
+    access: vec![SUPPORT_SELECT, MONITOR_REDACTED_SELECT, MONITOR_SELECT],
+}
+});
+
 pub static MZ_SOURCE_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_source_statuses",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2964,6 +3070,38 @@ WHERE
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_SSH_TUNNEL_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_ssh_tunnel_statuses",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_SSH_TUNNEL_STATUSES_OID,
+    column_defs: None,
+    sql: "
+WITH object_statuses AS (
+    SELECT id, 'source' AS type, status, last_status_change_at, details
+    FROM mz_internal.mz_source_statuses
+    UNION ALL
+    SELECT id, 'sink' AS type, status, last_status_change_at, details
+    FROM mz_internal.mz_sink_statuses
+)
+SELECT
+    ssh.id AS connection_id,
+    o.id AS object_id,
+    o.type AS object_type,
+    CASE
+        WHEN o.details->'namespaced'->>'ssh' IS NOT NULL THEN 'stalled'
+        WHEN o.status IN ('running', 'stalled') THEN 'running'
+        ELSE 'starting'
+    END AS status,
+    o.details->'namespaced'->>'ssh' AS error,
+    o.last_status_change_at
+FROM
+    mz_catalog.mz_ssh_tunnel_connections AS ssh
+        JOIN mz_internal.mz_object_transitive_dependencies AS deps
+            ON deps.referenced_object_id = ssh.id
+        JOIN object_statuses AS o ON o.id = deps.object_id",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_STORAGE_USAGE_BY_SHARD: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_storage_usage_by_shard",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2980,6 +3118,66 @@ pub static MZ_STORAGE_USAGE_BY_SHARD: Lazy<BuiltinTable> = Lazy::new(|| BuiltinT
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_STORAGE_SHARD_USAGE: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_storage_shard_usage",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_STORAGE_SHARD_USAGE_OID,
+    desc: RelationDesc::empty()
+        .with_column("shard_id", ScalarType::String.nullable(false))
+        .with_column("live_bytes", ScalarType::UInt64.nullable(false))
+        .with_column("rollup_bytes", ScalarType::UInt64.nullable(false))
+        .with_column("pending_gc_bytes", ScalarType::UInt64.nullable(false))
+        .with_column("garbage_bytes", ScalarType::UInt64.nullable(false))
+        .with_column("batch_count", ScalarType::UInt64.nullable(false))
+        .with_column(
+            "compaction_debt_batches",
+            ScalarType::UInt64.nullable(false),
+        )
+        .with_column("compaction_debt_bytes", ScalarType::UInt64.nullable(false))
+        .with_column(
+            "collection_timestamp",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_key(vec![0]),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_STORAGE_SHARD_BATCH_SIZES_HISTOGRAM: Lazy<BuiltinTable> =
+    Lazy::new(|| BuiltinTable {
+        name: "mz_storage_shard_batch_sizes_histogram",
+        schema: MZ_INTERNAL_SCHEMA,
+        oid: oid::TABLE_MZ_STORAGE_SHARD_BATCH_SIZES_HISTOGRAM_OID,
+        desc: RelationDesc::empty()
+            .with_column("shard_id", ScalarType::String.nullable(false))
+            .with_column("size_bytes", ScalarType::UInt64.nullable(false))
+            .with_column("count", ScalarType::UInt64.nullable(false))
+            .with_key(vec![0, 1]),
+        is_retained_metrics_object: false,
+        access: vec![PUBLIC_SELECT],
+    });
+
+pub static MZ_STORAGE_SHARD_READERS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_storage_shard_readers",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_STORAGE_SHARD_READERS_OID,
+    desc: RelationDesc::empty()
+        .with_column("shard_id", ScalarType::String.nullable(false))
+        .with_column("reader_id", ScalarType::String.nullable(false))
+        .with_column("kind", ScalarType::String.nullable(false))
+        .with_column("since", ScalarType::MzTimestamp.nullable(true))
+        .with_column("hostname", ScalarType::String.nullable(false))
+        .with_column("purpose", ScalarType::String.nullable(false))
+        .with_column(
+            "last_heartbeat_at",
+            ScalarType::TimestampTz { precision: None }.nullable(true),
+        )
+        .with_column("lease_duration_ms", ScalarType::UInt64.nullable(true))
+        .with_key(vec![0, 1]),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_EGRESS_IPS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_egress_ips",
     schema: MZ_CATALOG_SCHEMA,
@@ -4673,6 +4871,26 @@ HAVING pg_catalog.sum(count) != 0",
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_LIR_MAPPING: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_lir_mapping",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_LIR_MAPPING_OID,
+    column_defs: None,
+    sql: "
+SELECT
+    global_id,
+    lir_id,
+    dataflow_id,
+    operator,
+    parent_lir_id,
+    nesting,
+    operator_id_start,
+    operator_id_end
+FROM mz_internal.mz_compute_lir_mapping_per_worker
+WHERE worker_id = 0",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_COMPUTE_OPERATOR_HYDRATION_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_compute_operator_hydration_statuses",
     schema: MZ_INTERNAL_SCHEMA,
@@ -6398,6 +6616,65 @@ pub static MZ_CLUSTER_REPLICA_HISTORY: Lazy<BuiltinView> = Lazy::new(|| BuiltinV
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_METERED_USAGE: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_metered_usage",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_METERED_USAGE_OID,
+    column_defs: Some(
+        "usage_type, object_id, cluster_name, size, quantity, unit, interval_start, interval_end",
+    ),
+    sql: r#"
+WITH
+    intervals AS
+    (
+        SELECT
+            lag(collection_timestamp) OVER (ORDER BY collection_timestamp) AS interval_start,
+            collection_timestamp AS interval_end
+        FROM
+        (
+            SELECT DISTINCT collection_timestamp
+            FROM mz_internal.mz_storage_usage_by_shard
+        )
+    )
+SELECT
+    'storage',
+    storage.object_id,
+    NULL::pg_catalog.text,
+    NULL::pg_catalog.text,
+    storage.size_bytes::pg_catalog.numeric,
+    'bytes',
+    intervals.interval_start,
+    intervals.interval_end
+FROM
+    mz_catalog.mz_storage_usage AS storage
+        JOIN intervals ON storage.collection_timestamp = intervals.interval_end
+UNION ALL
+SELECT
+    'compute',
+    replicas.replica_id,
+    replicas.cluster_name,
+    replicas.size,
+    EXTRACT(
+        EPOCH FROM
+            least(coalesce(replicas.dropped_at, intervals.interval_end), intervals.interval_end)
+                - greatest(replicas.created_at, intervals.interval_start)
+    ),
+    'seconds',
+    intervals.interval_start,
+    intervals.interval_end
+FROM
+    mz_internal.mz_cluster_replica_history AS replicas
+        JOIN
+            intervals
+            ON
+                intervals.interval_start IS NOT NULL
+                    AND
+                replicas.created_at < intervals.interval_end
+                    AND
+                (replicas.dropped_at IS NULL OR replicas.dropped_at > intervals.interval_start)"#,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_HYDRATION_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_hydration_statuses",
     schema: MZ_INTERNAL_SCHEMA,
@@ -6865,6 +7142,43 @@ ON mz_internal.mz_source_statistics (id)",
     is_retained_metrics_object: false,
 };
 
+/// A single place to check on the health and progress of every source and
+/// subsource, combining its status, its statistics and its write frontier.
+pub static MZ_SOURCE_HEALTH: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_source_health",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_SOURCE_HEALTH_OID,
+    column_defs: None,
+    sql: "
+SELECT
+    s.id,
+    s.name,
+    s.type,
+    st.status,
+    st.error,
+    stats.offset_known,
+    stats.offset_committed,
+    stats.snapshot_records_known,
+    stats.snapshot_records_staged,
+    stats.snapshot_committed,
+    f.write_frontier,
+    -- Staleness is NULL for sources that have reached the empty frontier, as they will never
+    -- produce new data, and never negative, even if the frontier is ahead of the clock.
+    CASE
+        WHEN f.write_frontier IS NULL THEN NULL
+        ELSE greatest(
+            now() - to_timestamp(f.write_frontier::text::double / 1000),
+            INTERVAL '0'
+        )
+    END AS staleness
+FROM mz_catalog.mz_sources s
+LEFT JOIN mz_internal.mz_source_statuses st ON s.id = st.id
+LEFT JOIN mz_internal.mz_source_statistics stats ON s.id = stats.id
+LEFT JOIN mz_internal.mz_frontiers f ON s.id = f.object_id
+WHERE s.id NOT LIKE 's%'",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_SINK_STATISTICS: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_sink_statistics",
     schema: MZ_INTERNAL_SCHEMA,
@@ -7259,6 +7573,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Log(&MZ_COMPUTE_FRONTIERS_PER_WORKER),
         Builtin::Log(&MZ_COMPUTE_IMPORT_FRONTIERS_PER_WORKER),
         Builtin::Log(&MZ_COMPUTE_ERROR_COUNTS_RAW),
+        Builtin::Log(&MZ_COMPUTE_LIR_MAPPING_PER_WORKER),
         Builtin::Table(&MZ_KAFKA_SINKS),
         Builtin::Table(&MZ_KAFKA_CONNECTIONS),
         Builtin::Table(&MZ_KAFKA_SOURCES),
@@ -7286,6 +7601,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_ROLES),
         Builtin::Table(&MZ_ROLE_MEMBERS),
         Builtin::Table(&MZ_ROLE_PARAMETERS),
+        Builtin::Table(&MZ_DATABASE_PARAMETERS),
         Builtin::Table(&MZ_PSEUDO_TYPES),
         Builtin::Table(&MZ_FUNCTIONS),
         Builtin::Table(&MZ_OPERATORS),
@@ -7302,6 +7618,9 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_INTERNAL_CLUSTER_REPLICAS),
         Builtin::Table(&MZ_AUDIT_EVENTS),
         Builtin::Table(&MZ_STORAGE_USAGE_BY_SHARD),
+        Builtin::Table(&MZ_STORAGE_SHARD_USAGE),
+        Builtin::Table(&MZ_STORAGE_SHARD_BATCH_SIZES_HISTOGRAM),
+        Builtin::Table(&MZ_STORAGE_SHARD_READERS),
         Builtin::Table(&MZ_EGRESS_IPS),
         Builtin::Table(&MZ_AWS_PRIVATELINK_CONNECTIONS),
         Builtin::Table(&MZ_AWS_CONNECTIONS),
@@ -7448,8 +7767,11 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_SHOW_MY_DEFAULT_PRIVILEGES),
         Builtin::Source(&MZ_SINK_STATUS_HISTORY),
         Builtin::View(&MZ_SINK_STATUSES),
+        Builtin::View(&MZ_SSH_TUNNEL_STATUSES),
         Builtin::Source(&MZ_SOURCE_STATUS_HISTORY),
         Builtin::Source(&MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY),
+        Builtin::Source(&MZ_STORAGE_SHARD_FINALIZATIONS),
+        Builtin::Source(&MZ_CLUSTER_REPLICA_EVENTS),
         Builtin::View(&MZ_AWS_PRIVATELINK_CONNECTION_STATUSES),
         Builtin::Source(&MZ_STATEMENT_EXECUTION_HISTORY),
         Builtin::View(&MZ_STATEMENT_EXECUTION_HISTORY_REDACTED),
@@ -7467,6 +7789,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Index(&MZ_RECENT_ACTIVITY_LOG_THINNED_IND),
         Builtin::View(&MZ_SOURCE_STATUSES),
         Builtin::Source(&MZ_STATEMENT_LIFECYCLE_HISTORY),
+        Builtin::View(&MZ_STATEMENT_LIFECYCLE_DURATIONS),
         Builtin::Source(&MZ_STORAGE_SHARDS),
         Builtin::Source(&MZ_SOURCE_STATISTICS_RAW),
         Builtin::Source(&MZ_SINK_STATISTICS_RAW),
@@ -7477,16 +7800,20 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_SINK_STATISTICS),
         Builtin::Index(&MZ_SINK_STATISTICS_IND),
         Builtin::View(&MZ_STORAGE_USAGE),
+        Builtin::View(&MZ_METERED_USAGE),
         Builtin::Source(&MZ_FRONTIERS),
         Builtin::View(&MZ_GLOBAL_FRONTIERS),
+        Builtin::View(&MZ_SOURCE_HEALTH),
         Builtin::Source(&MZ_MATERIALIZED_VIEW_REFRESHES),
         Builtin::Source(&MZ_COMPUTE_DEPENDENCIES),
         Builtin::Source(&MZ_COMPUTE_HYDRATION_STATUSES),
         Builtin::Source(&MZ_COMPUTE_OPERATOR_HYDRATION_STATUSES_PER_WORKER),
+        Builtin::Source(&MZ_COMPUTE_REAPED_DATAFLOWS),
         Builtin::View(&MZ_HYDRATION_STATUSES),
         Builtin::View(&MZ_MATERIALIZATION_LAG),
         Builtin::View(&MZ_COMPUTE_ERROR_COUNTS_PER_WORKER),
         Builtin::View(&MZ_COMPUTE_ERROR_COUNTS),
+        Builtin::View(&MZ_LIR_MAPPING),
         Builtin::View(&MZ_COMPUTE_OPERATOR_HYDRATION_STATUSES),
         Builtin::Source(&MZ_CLUSTER_REPLICA_FRONTIERS),
         Builtin::Index(&MZ_SHOW_DATABASES_IND),
@@ -635,6 +635,7 @@ fn default_cluster_config(args: &BootstrapArgs) -> ClusterConfig {
             disk: is_cluster_size_v2(&args.default_cluster_replica_size),
             optimizer_feature_overrides: Default::default(),
             schedule: Default::default(),
+            autoscale: None,
         }),
     }
 }
@@ -41,7 +41,7 @@ use mz_sql::catalog::{
     RoleMembership, RoleVars,
 };
 use mz_sql::names::{CommentObjectId, DatabaseId, SchemaId};
-use mz_sql::plan::ClusterSchedule;
+use mz_sql::plan::{ClusterAutoscale, ClusterSchedule};
 use proptest_derive::Arbitrary;
 
 use crate::durable::objects::serialization::proto;
@@ -85,6 +85,7 @@ pub struct Database {
     pub name: String,
     pub owner_id: RoleId,
     pub privileges: Vec<MzAclItem>,
+    pub vars: RoleVars,
 }
 
 impl DurableType for Database {
@@ -99,6 +100,7 @@ impl DurableType for Database {
                 name: self.name,
                 owner_id: self.owner_id,
                 privileges: self.privileges,
+                vars: self.vars,
             },
         )
     }
@@ -110,6 +112,7 @@ impl DurableType for Database {
             name: value.name,
             owner_id: value.owner_id,
             privileges: value.privileges,
+            vars: value.vars,
         }
     }
 
@@ -264,6 +267,7 @@ pub struct ClusterVariantManaged {
     pub disk: bool,
     pub optimizer_feature_overrides: BTreeMap<String, String>,
     pub schedule: ClusterSchedule,
+    pub autoscale: Option<ClusterAutoscale>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -1037,6 +1041,7 @@ pub struct DatabaseValue {
     pub(crate) owner_id: RoleId,
     pub(crate) privileges: Vec<MzAclItem>,
     pub(crate) oid: u32,
+    pub(crate) vars: RoleVars,
 }
 
 #[derive(Clone, Copy, Debug, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
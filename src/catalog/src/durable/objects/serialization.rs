@@ -11,14 +11,14 @@
 
 use mz_audit_log::{
     AlterDefaultPrivilegeV1, AlterRetainHistoryV1, AlterSetClusterV1, AlterSourceSinkV1,
-    CreateClusterReplicaV1, CreateClusterReplicaV2, CreateOrDropClusterReplicaReasonV1,
-    CreateSourceSinkV1, CreateSourceSinkV2, CreateSourceSinkV3, DropClusterReplicaV1,
-    DropClusterReplicaV2, EventDetails, EventType, EventV1, FromPreviousIdV1, FullNameV1,
-    GrantRoleV1, GrantRoleV2, IdFullNameV1, IdNameV1, RefreshDecisionWithReasonV1,
-    RenameClusterReplicaV1, RenameClusterV1, RenameItemV1, RenameSchemaV1, RevokeRoleV1,
-    RevokeRoleV2, SchedulingDecisionV1, SchedulingDecisionsWithReasonsV1, SchemaV1, SchemaV2,
-    StorageUsageV1, ToNewIdV1, UpdateItemV1, UpdateOwnerV1, UpdatePrivilegeV1, VersionedEvent,
-    VersionedStorageUsage,
+    AutoscaleClusterV1, CreateClusterReplicaV1, CreateClusterReplicaV2,
+    CreateOrDropClusterReplicaReasonV1, CreateSourceSinkV1, CreateSourceSinkV2, CreateSourceSinkV3,
+    DropClusterReplicaV1, DropClusterReplicaV2, EventDetails, EventType, EventV1, FromPreviousIdV1,
+    FullNameV1, GrantRoleV1, GrantRoleV2, IdFullNameV1, IdNameV1, RefreshDecisionWithReasonV1,
+    RenameClusterReplicaV1, RenameClusterV1, RenameItemV1, RenameSchemaV1, ReplicaHealthV1,
+    RevokeRoleV1, RevokeRoleV2, SchedulingDecisionV1, SchedulingDecisionsWithReasonsV1, SchemaV1,
+    SchemaV2, StorageUsageV1, ToNewIdV1, UpdateItemV1, UpdateOwnerV1, UpdatePrivilegeV1,
+    VersionedEvent, VersionedStorageUsage,
 };
 use mz_compute_client::controller::ComputeReplicaLogging;
 use mz_controller_types::ReplicaId;
@@ -31,13 +31,13 @@ use mz_sql::catalog::{CatalogItemType, ObjectType, RoleAttributes, RoleMembershi
 use mz_sql::names::{
     CommentObjectId, DatabaseId, ResolvedDatabaseSpecifier, SchemaId, SchemaSpecifier,
 };
-use mz_sql::plan::ClusterSchedule;
+use mz_sql::plan::{ClusterAutoscale, ClusterSchedule, CronSchedule};
 use mz_sql::session::vars::OwnedVarInput;
 use mz_storage_types::instances::StorageInstanceId;
 use std::time::Duration;
 
 use crate::durable::objects::serialization::proto::{
-    cluster_schedule, ClusterScheduleRefreshOptions, Empty,
+    cluster_schedule, ClusterScheduleOffDuringOptions, ClusterScheduleRefreshOptions, Empty,
 };
 use crate::durable::objects::state_update::StateUpdateKindRaw;
 use crate::durable::objects::{
@@ -100,6 +100,13 @@ impl RustType<proto::ClusterSchedule> for ClusterSchedule {
                     },
                 )),
             },
+            ClusterSchedule::OffDuring { windows } => proto::ClusterSchedule {
+                value: Some(cluster_schedule::Value::OffDuring(
+                    ClusterScheduleOffDuringOptions {
+                        windows: windows.iter().map(|window| window.to_string()).collect(),
+                    },
+                )),
+            },
         }
     }
 
@@ -112,10 +119,35 @@ impl RustType<proto::ClusterSchedule> for ClusterSchedule {
                     .rehydration_time_estimate
                     .into_rust_if_some("rehydration_time_estimate")?,
             }),
+            Some(cluster_schedule::Value::OffDuring(csodo)) => Ok(ClusterSchedule::OffDuring {
+                windows: csodo
+                    .windows
+                    .iter()
+                    .map(|window| {
+                        CronSchedule::parse(window).map_err(TryFromProtoError::InvalidFieldError)
+                    })
+                    .collect::<Result<_, _>>()?,
+            }),
         }
     }
 }
 
+impl RustType<proto::ClusterAutoscale> for ClusterAutoscale {
+    fn into_proto(&self) -> proto::ClusterAutoscale {
+        proto::ClusterAutoscale {
+            min_size: self.min_size.clone(),
+            max_size: self.max_size.clone(),
+        }
+    }
+
+    fn from_proto(proto: proto::ClusterAutoscale) -> Result<Self, TryFromProtoError> {
+        Ok(ClusterAutoscale {
+            min_size: proto.min_size,
+            max_size: proto.max_size,
+        })
+    }
+}
+
 impl RustType<proto::ClusterConfig> for ClusterConfig {
     fn into_proto(&self) -> proto::ClusterConfig {
         proto::ClusterConfig {
@@ -141,6 +173,7 @@ impl RustType<proto::cluster_config::Variant> for ClusterVariant {
                 disk,
                 optimizer_feature_overrides,
                 schedule,
+                autoscale,
             }) => proto::cluster_config::Variant::Managed(proto::cluster_config::ManagedCluster {
                 size: size.to_string(),
                 availability_zones: availability_zones.clone(),
@@ -149,6 +182,7 @@ impl RustType<proto::cluster_config::Variant> for ClusterVariant {
                 disk: *disk,
                 optimizer_feature_overrides: optimizer_feature_overrides.into_proto(),
                 schedule: Some(schedule.into_proto()),
+                autoscale: autoscale.into_proto(),
             }),
             ClusterVariant::Unmanaged => proto::cluster_config::Variant::Unmanaged(proto::Empty {}),
         }
@@ -168,6 +202,7 @@ impl RustType<proto::cluster_config::Variant> for ClusterVariant {
                     disk: managed.disk,
                     optimizer_feature_overrides: managed.optimizer_feature_overrides.into_rust()?,
                     schedule: managed.schedule.unwrap_or_default().into_rust()?,
+                    autoscale: managed.autoscale.into_rust()?,
                 }))
             }
         }
@@ -469,6 +504,7 @@ impl RustType<proto::DatabaseValue> for DatabaseValue {
             owner_id: Some(self.owner_id.into_proto()),
             privileges: self.privileges.into_proto(),
             oid: self.oid,
+            vars: Some(self.vars.into_proto()),
         }
     }
 
@@ -480,6 +516,7 @@ impl RustType<proto::DatabaseValue> for DatabaseValue {
                 .into_rust_if_some("DatabaseValue::owner_id")?),
             privileges: proto.privileges.into_rust()?,
             oid: proto.oid,
+            vars: proto.vars.into_rust_if_some("DatabaseValue::vars")?,
         })
     }
 }
@@ -980,6 +1017,7 @@ impl RustType<proto::RoleAttributes> for RoleAttributes {
     fn into_proto(&self) -> proto::RoleAttributes {
         proto::RoleAttributes {
             inherit: self.inherit,
+            scram_verifier: self.scram_verifier.clone(),
         }
     }
 
@@ -987,6 +1025,7 @@ impl RustType<proto::RoleAttributes> for RoleAttributes {
         let mut attributes = RoleAttributes::new();
 
         attributes.inherit = proto.inherit;
+        attributes.scram_verifier = proto.scram_verifier;
 
         Ok(attributes)
     }
@@ -2262,6 +2301,62 @@ impl RustType<proto::audit_log_event_v1::FromPreviousIdV1> for FromPreviousIdV1
     }
 }
 
+impl RustType<proto::audit_log_event_v1::AutoscaleClusterV1> for AutoscaleClusterV1 {
+    fn into_proto(&self) -> proto::audit_log_event_v1::AutoscaleClusterV1 {
+        proto::audit_log_event_v1::AutoscaleClusterV1 {
+            cluster_id: self.cluster_id.to_string(),
+            cluster_name: self.cluster_name.to_string(),
+            old_size: self.old_size.to_string(),
+            new_size: self.new_size.to_string(),
+            trigger: self.trigger.to_string(),
+        }
+    }
+
+    fn from_proto(
+        proto: proto::audit_log_event_v1::AutoscaleClusterV1,
+    ) -> Result<Self, TryFromProtoError> {
+        Ok(AutoscaleClusterV1 {
+            cluster_id: proto.cluster_id,
+            cluster_name: proto.cluster_name,
+            old_size: proto.old_size,
+            new_size: proto.new_size,
+            trigger: proto.trigger,
+        })
+    }
+}
+
+impl RustType<proto::audit_log_event_v1::ReplicaHealthV1> for ReplicaHealthV1 {
+    fn into_proto(&self) -> proto::audit_log_event_v1::ReplicaHealthV1 {
+        proto::audit_log_event_v1::ReplicaHealthV1 {
+            cluster_id: self.cluster_id.to_string(),
+            cluster_name: self.cluster_name.to_string(),
+            replica_id: self.replica_id.to_string(),
+            replica_name: self.replica_name.to_string(),
+            old_health: self.old_health.to_string(),
+            new_health: self.new_health.to_string(),
+            reason: self.reason.clone(),
+            hydrated: self.hydrated,
+            last_heartbeat: self.last_heartbeat,
+        }
+    }
+
+    fn from_proto(
+        proto: proto::audit_log_event_v1::ReplicaHealthV1,
+    ) -> Result<Self, TryFromProtoError> {
+        Ok(ReplicaHealthV1 {
+            cluster_id: proto.cluster_id,
+            cluster_name: proto.cluster_name,
+            replica_id: proto.replica_id,
+            replica_name: proto.replica_name,
+            old_health: proto.old_health,
+            new_health: proto.new_health,
+            reason: proto.reason,
+            hydrated: proto.hydrated,
+            last_heartbeat: proto.last_heartbeat,
+        })
+    }
+}
+
 impl RustType<proto::audit_log_event_v1::Details> for EventDetails {
     fn into_proto(&self) -> proto::audit_log_event_v1::Details {
         use proto::audit_log_event_v1::Details::*;
@@ -2309,6 +2404,8 @@ impl RustType<proto::audit_log_event_v1::Details> for EventDetails {
             }
             EventDetails::ToNewIdV1(details) => ToNewIdV1(details.into_proto()),
             EventDetails::FromPreviousIdV1(details) => FromPreviousIdV1(details.into_proto()),
+            EventDetails::AutoscaleClusterV1(details) => AutoscaleClusterV1(details.into_proto()),
+            EventDetails::ReplicaHealthV1(details) => ReplicaHealthV1(details.into_proto()),
         }
     }
 
@@ -2364,6 +2461,10 @@ impl RustType<proto::audit_log_event_v1::Details> for EventDetails {
             }
             ToNewIdV1(details) => Ok(EventDetails::ToNewIdV1(details.into_rust()?)),
             FromPreviousIdV1(details) => Ok(EventDetails::FromPreviousIdV1(details.into_rust()?)),
+            AutoscaleClusterV1(details) => {
+                Ok(EventDetails::AutoscaleClusterV1(details.into_rust()?))
+            }
+            ReplicaHealthV1(details) => Ok(EventDetails::ReplicaHealthV1(details.into_rust()?)),
         }
     }
 }
@@ -238,6 +238,7 @@ impl<'a> Transaction<'a> {
                 owner_id,
                 privileges,
                 oid,
+                vars: RoleVars::default(),
             },
             self.op_id,
         ) {
@@ -170,14 +170,14 @@ macro_rules! objects {
     }
 }
 
-objects!(v54, v55, v56, v57, v58);
+objects!(v54, v55, v56, v57, v58, v59);
 
 /// The current version of the `Catalog`.
 ///
 /// We will initialize new `Catalog`es with this version, and migrate existing `Catalog`es to this
 /// version. Whenever the `Catalog` changes, e.g. the protobufs we serialize in the `Catalog`
 /// change, we need to bump this version.
-pub const CATALOG_VERSION: u64 = 58;
+pub const CATALOG_VERSION: u64 = 59;
 
 /// The minimum `Catalog` version number that we support migrating from.
 ///
@@ -193,6 +193,7 @@ mod v54_to_v55;
 mod v55_to_v56;
 mod v56_to_v57;
 mod v57_to_v58;
+mod v58_to_v59;
 
 /// Describes a single action to take during a migration from `V1` to `V2`.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -285,6 +286,9 @@ async fn run_upgrade(
         57 => {
             run_versioned_upgrade(unopened_catalog_state, mode, version, v57_to_v58::upgrade).await
         }
+        58 => {
+            run_versioned_upgrade(unopened_catalog_state, mode, version, v58_to_v59::upgrade).await
+        }
 
         // Up-to-date, no migration needed!
         CATALOG_VERSION => Ok(CATALOG_VERSION),
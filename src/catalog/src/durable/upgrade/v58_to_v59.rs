@@ -0,0 +1,120 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use mz_proto::wire_compatible;
+use mz_proto::wire_compatible::WireCompatible;
+
+use crate::durable::upgrade::MigrationAction;
+use crate::durable::upgrade::{objects_v58 as v58, objects_v59 as v59};
+
+wire_compatible!(v58::DatabaseKey with v59::DatabaseKey);
+wire_compatible!(v58::RoleId with v59::RoleId);
+wire_compatible!(v58::MzAclItem with v59::MzAclItem);
+
+/// Adds empty session variable defaults to all databases.
+pub fn upgrade(
+    snapshot: Vec<v58::StateUpdateKind>,
+) -> Vec<MigrationAction<v58::StateUpdateKind, v59::StateUpdateKind>> {
+    snapshot
+        .into_iter()
+        .filter_map(|update| {
+            let v58::state_update_kind::Kind::Database(v58::state_update_kind::Database {
+                key,
+                value,
+            }) = update.kind.as_ref().expect("missing field")
+            else {
+                return None;
+            };
+
+            let new = v59::StateUpdateKind {
+                kind: Some(v59::state_update_kind::Kind::Database(
+                    v59::state_update_kind::Database {
+                        key: key.as_ref().map(WireCompatible::convert),
+                        value: value.as_ref().map(|old_val| v59::DatabaseValue {
+                            name: old_val.name.clone(),
+                            owner_id: old_val.owner_id.as_ref().map(WireCompatible::convert),
+                            privileges: old_val
+                                .privileges
+                                .iter()
+                                .map(WireCompatible::convert)
+                                .collect(),
+                            oid: old_val.oid,
+                            vars: Some(v59::RoleVars {
+                                entries: Vec::new(),
+                            }),
+                        }),
+                    },
+                )),
+            };
+
+            Some(MigrationAction::Update(update, new))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn smoke_test_migration() {
+        let v58 = v58::StateUpdateKind {
+            kind: Some(v58::state_update_kind::Kind::Database(
+                v58::state_update_kind::Database {
+                    key: Some(v58::DatabaseKey {
+                        id: Some(v58::DatabaseId {
+                            value: Some(v58::database_id::Value::User(7)),
+                        }),
+                    }),
+                    value: Some(v58::DatabaseValue {
+                        name: "db".to_string(),
+                        owner_id: Some(v58::RoleId {
+                            value: Some(v58::role_id::Value::User(3)),
+                        }),
+                        privileges: vec![],
+                        oid: 20_000,
+                    }),
+                },
+            )),
+        };
+
+        let v59 = v59::StateUpdateKind {
+            kind: Some(v59::state_update_kind::Kind::Database(
+                v59::state_update_kind::Database {
+                    key: Some(v59::DatabaseKey {
+                        id: Some(v59::DatabaseId {
+                            value: Some(v59::database_id::Value::User(7)),
+                        }),
+                    }),
+                    value: Some(v59::DatabaseValue {
+                        name: "db".to_string(),
+                        owner_id: Some(v59::RoleId {
+                            value: Some(v59::role_id::Value::User(3)),
+                        }),
+                        privileges: vec![],
+                        oid: 20_000,
+                        vars: Some(v59::RoleVars {
+                            entries: Vec::new(),
+                        }),
+                    }),
+                },
+            )),
+        };
+
+        let actions = upgrade(vec![v58.clone()]);
+
+        match &actions[..] {
+            [MigrationAction::Update(old, new)] => {
+                assert_eq!(old, &v58);
+                assert_eq!(new, &v59);
+            }
+            o => panic!("expected single MigrationAction::Update, got {:?}", o),
+        }
+    }
+}
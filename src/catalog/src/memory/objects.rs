@@ -41,8 +41,8 @@ use mz_sql::names::{
     ResolvedDatabaseSpecifier, ResolvedIds, SchemaId, SchemaSpecifier,
 };
 use mz_sql::plan::{
-    ClusterSchedule, CreateSourcePlan, HirRelationExpr, Ingestion as PlanIngestion,
-    WebhookBodyFormat, WebhookHeaders, WebhookValidation,
+    ClusterAutoscale, ClusterSchedule, CreateSourcePlan, HirRelationExpr,
+    Ingestion as PlanIngestion, TableCheck, WebhookBodyFormat, WebhookHeaders, WebhookValidation,
 };
 use mz_sql::rbac;
 use mz_sql::session::vars::OwnedVarInput;
@@ -77,6 +77,7 @@ pub struct Database {
     pub schemas_by_name: BTreeMap<String, SchemaId>,
     pub owner_id: RoleId,
     pub privileges: PrivilegeMap,
+    pub vars: RoleVars,
 }
 
 impl From<Database> for durable::Database {
@@ -87,6 +88,7 @@ impl From<Database> for durable::Database {
             name: database.name,
             owner_id: database.owner_id,
             privileges: database.privileges.into_all_values().collect(),
+            vars: database.vars,
         }
     }
 }
@@ -99,6 +101,7 @@ impl From<durable::Database> for Database {
             name,
             owner_id,
             privileges,
+            vars,
         }: durable::Database,
     ) -> Database {
         Database {
@@ -109,6 +112,7 @@ impl From<durable::Database> for Database {
             name,
             owner_id,
             privileges: PrivilegeMap::from_mz_acl_items(privileges),
+            vars,
         }
     }
 }
@@ -122,6 +126,7 @@ impl UpdateFrom<durable::Database> for Database {
             name,
             owner_id,
             privileges,
+            vars,
         }: durable::Database,
     ) {
         self.id = id;
@@ -129,6 +134,7 @@ impl UpdateFrom<durable::Database> for Database {
         self.name = name;
         self.owner_id = owner_id;
         self.privileges = PrivilegeMap::from_mz_acl_items(privileges);
+        self.vars = vars;
     }
 }
 
@@ -482,6 +488,10 @@ pub struct Table {
     pub desc: RelationDesc,
     #[serde(skip)]
     pub defaults: Vec<Expr<Aug>>,
+    /// The table's `CHECK` constraints, which must hold for every row written
+    /// to the table.
+    #[serde(skip)]
+    pub checks: Vec<TableCheck>,
     #[serde(skip)]
     pub conn_id: Option<ConnectionId>,
     pub resolved_ids: ResolvedIds,
@@ -813,6 +823,9 @@ pub struct MaterializedView {
     pub resolved_ids: ResolvedIds,
     pub cluster_id: ClusterId,
     pub non_null_assertions: Vec<usize>,
+    /// Whether the materialized view is asserted to never retract rows. Readers may treat it as
+    /// monotonic, and its dataflow reports retractions as errors.
+    pub append_only: bool,
     pub custom_logical_compaction_window: Option<CompactionWindow>,
     pub refresh_schedule: Option<RefreshSchedule>,
     /// The initial `as_of` of the storage collection associated with the materialized view.
@@ -2012,6 +2025,7 @@ pub struct ClusterVariantManaged {
     pub disk: bool,
     pub optimizer_feature_overrides: OptimizerFeatureOverrides,
     pub schedule: ClusterSchedule,
+    pub autoscale: Option<ClusterAutoscale>,
 }
 
 impl From<ClusterVariantManaged> for durable::ClusterVariantManaged {
@@ -2024,6 +2038,7 @@ impl From<ClusterVariantManaged> for durable::ClusterVariantManaged {
             disk: managed.disk,
             optimizer_feature_overrides: managed.optimizer_feature_overrides.into(),
             schedule: managed.schedule,
+            autoscale: managed.autoscale,
         }
     }
 }
@@ -2038,6 +2053,7 @@ impl From<durable::ClusterVariantManaged> for ClusterVariantManaged {
             disk: managed.disk,
             optimizer_feature_overrides: managed.optimizer_feature_overrides.into(),
             schedule: managed.schedule,
+            autoscale: managed.autoscale,
         }
     }
 }
@@ -2099,6 +2115,10 @@ impl mz_sql::catalog::CatalogDatabase for Database {
     fn privileges(&self) -> &PrivilegeMap {
         &self.privileges
     }
+
+    fn vars(&self) -> &BTreeMap<String, OwnedVarInput> {
+        &self.vars.map
+    }
 }
 
 impl mz_sql::catalog::CatalogSchema for Schema {
@@ -278,6 +278,7 @@ async fn test_open_savepoint(
                     name: format!("db{i}"),
                     owner_id: RoleId::User(i),
                     privileges: Vec::new(),
+                    vars: RoleVars::default(),
                 },
                 Schema {
                     id: schema_id,
@@ -10,12 +10,15 @@
 use std::collections::BTreeSet;
 use std::error::Error;
 use std::fmt;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::bail;
 use proptest_derive::Arbitrary;
-use reqwest::{Method, Response, Url};
+use rand::Rng;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Method, Response, StatusCode, Url};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +31,7 @@ pub struct Client {
     url: Arc<dyn Fn() -> Url + Send + Sync + 'static>,
     auth: Option<Auth>,
     timeout: Duration,
+    schema_cache_directory: Option<PathBuf>,
 }
 
 impl fmt::Debug for Client {
@@ -36,6 +40,7 @@ impl fmt::Debug for Client {
             .field("inner", &self.inner)
             .field("url", &"...")
             .field("auth", &self.auth)
+            .field("schema_cache_directory", &self.schema_cache_directory)
             .finish()
     }
 }
@@ -46,6 +51,7 @@ impl Client {
         url: Arc<dyn Fn() -> Url + Send + Sync + 'static>,
         auth: Option<Auth>,
         timeout: Duration,
+        schema_cache_directory: Option<PathBuf>,
     ) -> Result<Self, anyhow::Error> {
         if url().cannot_be_a_base() {
             bail!("cannot construct a CCSR client with a cannot-be-a-base URL");
@@ -55,6 +61,7 @@ impl Client {
             url,
             auth,
             timeout,
+            schema_cache_directory,
         })
     }
 
@@ -81,15 +88,65 @@ impl Client {
     }
 
     /// Gets the schema with the associated ID.
+    ///
+    /// If the client was configured with a schema cache directory, the schema
+    /// is read from the cache when present, and written to the cache after
+    /// being fetched from the registry. Schemas are immutable once assigned an
+    /// ID, so cached entries never need to be invalidated.
     pub async fn get_schema_by_id(&self, id: i32) -> Result<Schema, GetByIdError> {
+        if let Some(raw) = self.read_cached_schema(id).await {
+            return Ok(Schema { id, raw });
+        }
         let req = self.make_request(Method::GET, &["schemas", "ids", &id.to_string()]);
         let res: GetByIdResponse = send_request(req).await?;
+        self.write_cached_schema(id, &res).await;
         Ok(Schema {
             id,
             raw: res.schema,
         })
     }
 
+    fn cached_schema_path(&self, id: i32) -> Option<PathBuf> {
+        self.schema_cache_directory
+            .as_ref()
+            .map(|dir| dir.join(format!("{id}.json")))
+    }
+
+    /// Reads the schema with the specified ID from the schema cache.
+    ///
+    /// Any error reading the cache is treated as a cache miss.
+    async fn read_cached_schema(&self, id: i32) -> Option<String> {
+        let path = self.cached_schema_path(id)?;
+        let bytes = tokio::fs::read(path).await.ok()?;
+        let res: GetByIdResponse = serde_json::from_slice(&bytes).ok()?;
+        Some(res.schema)
+    }
+
+    /// Writes the schema with the specified ID to the schema cache.
+    ///
+    /// The cache is best effort, so errors writing to it are ignored. The
+    /// schema is written to a temporary file that is then renamed into place,
+    /// so that concurrent readers never observe a partially written entry.
+    async fn write_cached_schema(&self, id: i32, res: &GetByIdResponse) {
+        let Some(path) = self.cached_schema_path(id) else {
+            return;
+        };
+        let Ok(bytes) = serde_json::to_vec(res) else {
+            return;
+        };
+        let tmp_path = path.with_extension(format!("json.{}.tmp", rand::random::<u64>()));
+        let write = async {
+            if let Some(dir) = path.parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            tokio::fs::write(&tmp_path, bytes).await?;
+            tokio::fs::rename(&tmp_path, &path).await
+        };
+        if write.await.is_err() {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+        }
+    }
+
     /// Gets the latest schema for the specified subject.
     pub async fn get_schema_by_subject(&self, subject: &str) -> Result<Schema, GetBySubjectError> {
         self.get_subject_latest(subject).await.map(|s| s.schema)
@@ -191,6 +248,39 @@ impl Client {
         Ok(res.id)
     }
 
+    /// Checks whether `schema` is compatible with the latest version of the
+    /// specified subject, according to the subject's compatibility level.
+    ///
+    /// If the schema is not compatible, the reasons reported by the schema
+    /// registry are returned in [`CheckCompatibilityError::IncompatibleSchema`].
+    /// Registries that do not support verbose compatibility checks report no
+    /// reasons.
+    pub async fn check_compatibility(
+        &self,
+        subject: &str,
+        schema: &str,
+        schema_type: SchemaType,
+        references: &[SchemaReference],
+    ) -> Result<(), CheckCompatibilityError> {
+        let req = self.make_request(
+            Method::POST,
+            &["compatibility", "subjects", subject, "versions", "latest"],
+        );
+        let req = req.query(&[("verbose", "true")]).json(&PublishRequest {
+            schema,
+            schema_type,
+            references,
+        });
+        let res: CompatibilityResponse = send_request(req).await?;
+        if res.is_compatible {
+            Ok(())
+        } else {
+            Err(CheckCompatibilityError::IncompatibleSchema {
+                messages: res.messages,
+            })
+        }
+    }
+
     /// Sets the compatibility level for the specified subject.
     pub async fn set_subject_compatibility_level(
         &self,
@@ -269,8 +359,40 @@ where
     Ok(res.json().await?)
 }
 
+/// The maximum number of times a request that was rejected by the schema
+/// registry's rate limiter is retried.
+const MAX_RATE_LIMIT_RETRIES: u32 = 5;
+
+/// The backoff before the first retry of a rate limited request, in the
+/// absence of a `Retry-After` header. The backoff doubles with each retry.
+const RATE_LIMIT_INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+
+/// The maximum backoff between retries of a rate limited request, including
+/// backoffs requested via a `Retry-After` header.
+const RATE_LIMIT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
 async fn send_request_raw(req: reqwest::RequestBuilder) -> Result<Response, UnhandledError> {
-    let res = req.send().await?;
+    let mut backoff = RATE_LIMIT_INITIAL_BACKOFF;
+    let mut retries = 0;
+    let res = loop {
+        // Requests with streaming bodies cannot be cloned, and so cannot be
+        // retried.
+        let Some(retry_req) = req.try_clone() else {
+            break req.send().await?;
+        };
+        let res = retry_req.send().await?;
+        if res.status() != StatusCode::TOO_MANY_REQUESTS || retries == MAX_RATE_LIMIT_RETRIES {
+            break res;
+        }
+        let delay = match retry_after(&res) {
+            Some(delay) => delay.min(RATE_LIMIT_MAX_BACKOFF),
+            // Jitter the backoff, so that many clients do not retry in lockstep.
+            None => backoff.mul_f64(rand::thread_rng().gen_range(0.5..=1.0)),
+        };
+        tokio::time::sleep(delay).await;
+        backoff = (backoff * 2).min(RATE_LIMIT_MAX_BACKOFF);
+        retries += 1;
+    };
     let status = res.status();
     if status.is_success() {
         Ok(res)
@@ -288,6 +410,19 @@ async fn send_request_raw(req: reqwest::RequestBuilder) -> Result<Response, Unha
     }
 }
 
+/// Returns the delay requested by the `Retry-After` header of the response, if
+/// the header is present and specifies a number of seconds.
+fn retry_after(res: &Response) -> Option<Duration> {
+    let secs = res
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(secs))
+}
+
 /// The type of a schema stored by a schema registry.
 #[derive(Clone, Copy, Debug, Serialize)]
 #[serde(rename_all = "UPPERCASE")]
@@ -338,7 +473,7 @@ pub struct SchemaReference {
     pub version: i32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 struct GetByIdResponse {
     schema: String,
 }
@@ -642,6 +777,86 @@ impl fmt::Display for PublishError {
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+struct CompatibilityResponse {
+    is_compatible: bool,
+    #[serde(default)]
+    messages: Vec<String>,
+}
+
+/// Errors for compatibility checks.
+#[derive(Debug)]
+pub enum CheckCompatibilityError {
+    /// The provided schema was not compatible with the latest schema for the
+    /// subject, according to the subject's compatibility level.
+    IncompatibleSchema {
+        /// The reasons for the incompatibility, as reported by the schema
+        /// registry.
+        messages: Vec<String>,
+    },
+    /// The provided schema was invalid.
+    InvalidSchema { message: String },
+    /// The requested subject does not exist.
+    SubjectNotFound,
+    /// The requested subject has no versions.
+    VersionNotFound(String),
+    /// The underlying HTTP transport failed.
+    Transport(reqwest::Error),
+    /// An internal server error occurred.
+    Server { code: i32, message: String },
+}
+
+impl From<UnhandledError> for CheckCompatibilityError {
+    fn from(err: UnhandledError) -> CheckCompatibilityError {
+        match err {
+            UnhandledError::Transport(err) => CheckCompatibilityError::Transport(err),
+            UnhandledError::Api { code, message } => match code {
+                40401 => CheckCompatibilityError::SubjectNotFound,
+                40402 => CheckCompatibilityError::VersionNotFound(message),
+                42201 => CheckCompatibilityError::InvalidSchema { message },
+                _ => CheckCompatibilityError::Server { code, message },
+            },
+        }
+    }
+}
+
+impl Error for CheckCompatibilityError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CheckCompatibilityError::IncompatibleSchema { .. }
+            | CheckCompatibilityError::InvalidSchema { .. }
+            | CheckCompatibilityError::SubjectNotFound
+            | CheckCompatibilityError::VersionNotFound(_)
+            | CheckCompatibilityError::Server { .. } => None,
+            CheckCompatibilityError::Transport(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for CheckCompatibilityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckCompatibilityError::IncompatibleSchema { messages } => {
+                write!(f, "schema is incompatible with the latest schema")?;
+                if !messages.is_empty() {
+                    write!(f, ": {}", messages.join("; "))?;
+                }
+                Ok(())
+            }
+            CheckCompatibilityError::InvalidSchema { message } => write!(f, "{}", message),
+            CheckCompatibilityError::SubjectNotFound => write!(f, "subject not found"),
+            CheckCompatibilityError::VersionNotFound(message) => {
+                write!(f, "version not found: {}", message)
+            }
+            CheckCompatibilityError::Transport(err) => write!(f, "transport: {}", err),
+            CheckCompatibilityError::Server { code, message } => {
+                write!(f, "server error {}: {}", code, message)
+            }
+        }
+    }
+}
+
 /// Errors for list operations.
 #[derive(Debug)]
 pub enum ListError {
@@ -10,6 +10,7 @@
 use std::collections::BTreeMap;
 use std::fmt;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -33,6 +34,7 @@ pub struct ClientConfig {
     identity: Option<Identity>,
     auth: Option<Auth>,
     dns_overrides: BTreeMap<String, Vec<SocketAddr>>,
+    schema_cache_directory: Option<PathBuf>,
 }
 
 impl fmt::Debug for ClientConfig {
@@ -43,6 +45,7 @@ impl fmt::Debug for ClientConfig {
             .field("identity", &self.identity)
             .field("auth", &self.auth)
             .field("dns_overrides", &self.dns_overrides)
+            .field("schema_cache_directory", &self.schema_cache_directory)
             .finish()
     }
 }
@@ -57,6 +60,7 @@ impl ClientConfig {
             identity: None,
             auth: None,
             dns_overrides: BTreeMap::new(),
+            schema_cache_directory: None,
         }
     }
 
@@ -90,6 +94,16 @@ impl ClientConfig {
         self
     }
 
+    /// Caches schemas fetched by ID in the specified directory, so that they
+    /// survive restarts of the process.
+    ///
+    /// Schema IDs are only unique within a single schema registry, so the
+    /// directory must not be shared by clients for different registries.
+    pub fn schema_cache_directory(mut self, dir: PathBuf) -> ClientConfig {
+        self.schema_cache_directory = Some(dir);
+        self
+    }
+
     /// Sets a callback that will be used to dynamically override the url
     /// the client uses.
     // Note this this doesn't use native `reqwest` `Proxy`s because not all schema
@@ -127,6 +141,12 @@ impl ClientConfig {
             .build()
             .unwrap();
 
-        Client::new(inner, self.url, self.auth, timeout)
+        Client::new(
+            inner,
+            self.url,
+            self.auth,
+            timeout,
+            self.schema_cache_directory,
+        )
     }
 }
@@ -8,13 +8,15 @@
 // by the Apache License, Version 2.0.
 
 use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 use hyper::server::conn::AddrIncoming;
 use hyper::{service, Body, Response, Server, StatusCode};
 use mz_ccsr::tls::Identity;
 use mz_ccsr::{
-    Client, CompatibilityLevel, DeleteError, GetByIdError, GetBySubjectError,
-    GetSubjectConfigError, PublishError, SchemaReference, SchemaType,
+    CheckCompatibilityError, Client, CompatibilityLevel, DeleteError, GetByIdError,
+    GetBySubjectError, GetSubjectConfigError, PublishError, SchemaReference, SchemaType,
 };
 use once_cell::sync::Lazy;
 
@@ -67,6 +69,24 @@ async fn test_client() -> Result<(), anyhow::Error> {
         res => panic!("expected IncompatibleSchema error, got {:?}", res),
     }
 
+    match client
+        .check_compatibility(test_subject, schema_v2_incompat, SchemaType::Avro, &[])
+        .await
+    {
+        Err(CheckCompatibilityError::IncompatibleSchema { .. }) => (),
+        res => panic!("expected IncompatibleSchema error, got {:?}", res),
+    }
+    client
+        .check_compatibility(test_subject, schema_v2, SchemaType::Avro, &[])
+        .await?;
+    match client
+        .check_compatibility("ccsr-test-noexist", schema_v1, SchemaType::Avro, &[])
+        .await
+    {
+        Err(CheckCompatibilityError::SubjectNotFound) => (),
+        res => panic!("expected SubjectNotFound error, got {:?}", res),
+    }
+
     {
         let res = client.get_schema_by_subject(test_subject).await?;
         assert_eq!(schema_v1_id, res.id);
@@ -388,18 +408,100 @@ async fn test_server_errors() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `TLS_method` on OS `linux`
+async fn test_rate_limit_retries() -> Result<(), anyhow::Error> {
+    // The first two requests are rate limited, and the third succeeds.
+    let requests = Arc::new(AtomicUsize::new(0));
+    let url = start_server_fn({
+        let requests = Arc::clone(&requests);
+        move || match requests.fetch_add(1, Ordering::SeqCst) {
+            0 | 1 => (
+                StatusCode::TOO_MANY_REQUESTS,
+                r#"{ "error_code": 429, "message": "rate limited" }"#,
+            ),
+            _ => (StatusCode::OK, r#"{ "schema": "\"int\"" }"#),
+        }
+    });
+    let client = mz_ccsr::ClientConfig::new(url).build()?;
+    let schema = client.get_schema_by_id(1).await?;
+    assert_eq!(schema.raw, r#""int""#);
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+    // Requests that are rate limited indefinitely eventually fail.
+    let client = start_server(
+        StatusCode::TOO_MANY_REQUESTS,
+        r#"{ "error_code": 429, "message": "rate limited" }"#,
+    )?;
+    match client.get_schema_by_id(1).await {
+        Err(GetByIdError::Server { code: 429, .. }) => (),
+        res => panic!("expected GetByIdError::Server, got {:?}", res),
+    }
+
+    Ok(())
+}
+
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `TLS_method` on OS `linux`
+async fn test_schema_cache() -> Result<(), anyhow::Error> {
+    let cache_dir = tempfile::tempdir()?;
+
+    let url = start_server_fn(|| (StatusCode::OK, r#"{ "schema": "\"long\"" }"#));
+    let client = mz_ccsr::ClientConfig::new(url)
+        .schema_cache_directory(cache_dir.path().to_path_buf())
+        .build()?;
+    assert_eq!(client.get_schema_by_id(7).await?.raw, r#""long""#);
+
+    // A new client using the same cache directory does not need the registry
+    // to look up schemas it has already seen.
+    let url = start_server_fn(|| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            r#"{ "error_code": 50001, "message": "unavailable" }"#,
+        )
+    });
+    let client = mz_ccsr::ClientConfig::new(url)
+        .schema_cache_directory(cache_dir.path().to_path_buf())
+        .build()?;
+    let schema = client.get_schema_by_id(7).await?;
+    assert_eq!(schema.id, 7);
+    assert_eq!(schema.raw, r#""long""#);
+    match client.get_schema_by_id(8).await {
+        Err(GetByIdError::Server { code: 50001, .. }) => (),
+        res => panic!("expected GetByIdError::Server, got {:?}", res),
+    }
+
+    Ok(())
+}
+
 fn start_server(status_code: StatusCode, body: &'static str) -> Result<Client, anyhow::Error> {
+    let url = start_server_fn(move || (status_code, body));
+    mz_ccsr::ClientConfig::new(url).build()
+}
+
+/// Starts a server that responds to every request with the status code and
+/// body returned by `respond`.
+fn start_server_fn<F>(respond: F) -> reqwest::Url
+where
+    F: Fn() -> (StatusCode, &'static str) + Send + Sync + 'static,
+{
+    let respond = Arc::new(respond);
     let addr = {
         let incoming = AddrIncoming::bind(&([127, 0, 0, 1], 0).into()).unwrap();
         let addr = incoming.local_addr();
-        let server =
-            Server::builder(incoming).serve(service::make_service_fn(move |_conn| async move {
-                Ok::<_, hyper::Error>(service::service_fn(move |_req| async move {
-                    Response::builder()
-                        .status(status_code)
-                        .body(Body::from(body))
+        let server = Server::builder(incoming).serve(service::make_service_fn(move |_conn| {
+            let respond = Arc::clone(&respond);
+            async move {
+                Ok::<_, hyper::Error>(service::service_fn(move |_req| {
+                    let (status_code, body) = respond();
+                    async move {
+                        Response::builder()
+                            .status(status_code)
+                            .body(Body::from(body))
+                    }
                 }))
-            }));
+            }
+        }));
         mz_ore::task::spawn(|| "start_server", async {
             match server.await {
                 Ok(()) => (),
@@ -409,8 +511,7 @@ fn start_server(status_code: StatusCode, body: &'static str) -> Result<Client, a
         addr
     };
 
-    let url: reqwest::Url = format!("http://{}", addr).parse().unwrap();
-    mz_ccsr::ClientConfig::new(url).build()
+    format!("http://{}", addr).parse().unwrap()
 }
 
 fn assert_raw_schemas_eq(schema1: &str, schema2: &str) {
@@ -252,6 +252,10 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
         PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone(), mz_dyncfgs::all_dyncfgs());
     persist_cfg.is_cc_active = args.is_cluster_size_v2;
     persist_cfg.announce_memory_limit = args.announce_memory_limit;
+    persist_cfg.blob_cache_disk_path = args
+        .scratch_directory
+        .as_ref()
+        .map(|dir| dir.join("persist-blob-cache"));
     let persist_clients = Arc::new(PersistClientCache::new(
         persist_cfg,
         &metrics_registry,
@@ -273,6 +277,9 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
         args.aws_connection_role_arn,
         secrets_reader,
         None,
+        args.scratch_directory
+            .as_ref()
+            .map(|dir| dir.join("ccsr-schema-cache")),
     );
 
     let grpc_server_metrics = GrpcServerMetrics::register_with(&metrics_registry);
@@ -32,7 +32,7 @@ use std::collections::BTreeMap;
 use std::num::NonZeroI64;
 use std::pin::Pin;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use differential_dataflow::consolidation::consolidate;
 use futures::{future, Future, FutureExt};
@@ -44,6 +44,7 @@ use mz_compute_types::ComputeInstanceId;
 use mz_dyncfg::ConfigSet;
 use mz_expr::RowSetFinishing;
 use mz_ore::metrics::MetricsRegistry;
+use mz_ore::now::NowFn;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_ore::{soft_assert_or_log, soft_panic_or_log};
 use mz_repr::global_id::TransientIdGen;
@@ -173,6 +174,8 @@ pub struct ComputeController<T> {
     /// Updated through `ComputeController::update_configuration` calls and shared with all
     /// subcomponents of the compute controller.
     dyncfg: Arc<ConfigSet>,
+    /// A function that returns the current time, used to timestamp introspection updates.
+    now: NowFn,
 
     /// Receiver for responses produced by `Instance`s, to be delivered on subsequent calls to
     /// [`ComputeController::process`].
@@ -199,6 +202,7 @@ impl<T: ComputeControllerTimestamp> ComputeController<T> {
         read_only: bool,
         transient_id_gen: Arc<TransientIdGen>,
         metrics_registry: MetricsRegistry,
+        now: NowFn,
     ) -> Self {
         let (response_tx, response_rx) = crossbeam_channel::unbounded();
         let (introspection_tx, introspection_rx) = crossbeam_channel::unbounded();
@@ -219,6 +223,7 @@ impl<T: ComputeControllerTimestamp> ComputeController<T> {
             transient_id_gen,
             metrics: ComputeControllerMetrics::new(metrics_registry),
             dyncfg: Arc::new(mz_dyncfgs::all_dyncfgs()),
+            now,
             response_rx,
             response_tx,
             introspection_rx,
@@ -264,6 +269,58 @@ impl<T: ComputeControllerTimestamp> ComputeController<T> {
         Ok(collection)
     }
 
+    /// Returns whether the indicated collection is hydrated on at least one replica of the
+    /// indicated instance.
+    pub fn collection_hydrated(
+        &self,
+        instance_id: ComputeInstanceId,
+        collection_id: GlobalId,
+    ) -> Result<bool, CollectionLookupError> {
+        let hydrated = self
+            .instance(instance_id)?
+            .collection_hydrated(collection_id)?;
+        Ok(hydrated)
+    }
+
+    /// Returns whether the indicated collection is hydrated on the indicated replica of the
+    /// indicated instance.
+    pub fn collection_hydrated_on_replica(
+        &self,
+        instance_id: ComputeInstanceId,
+        collection_id: GlobalId,
+        replica_id: ReplicaId,
+    ) -> Result<bool, CollectionLookupError> {
+        let hydrated = self
+            .instance(instance_id)?
+            .collection_hydrated_on_replica(collection_id, replica_id)?;
+        Ok(hydrated)
+    }
+
+    /// Returns when the indicated replica of the indicated instance last sent a response.
+    ///
+    /// Returns `None` if the replica does not exist.
+    pub fn replica_last_heartbeat(
+        &self,
+        instance_id: ComputeInstanceId,
+        replica_id: ReplicaId,
+    ) -> Result<Option<Instant>, InstanceMissing> {
+        Ok(self
+            .instance(instance_id)?
+            .replica_last_heartbeat(replica_id))
+    }
+
+    /// Returns whether the indicated replica of the indicated instance has hydrated all
+    /// collections it maintains.
+    ///
+    /// Returns `None` if the replica does not exist.
+    pub fn replica_hydrated(
+        &self,
+        instance_id: ComputeInstanceId,
+        replica_id: ReplicaId,
+    ) -> Result<Option<bool>, InstanceMissing> {
+        Ok(self.instance(instance_id)?.replica_hydrated(replica_id))
+    }
+
     /// Return a read-only handle to the indicated collection.
     pub fn find_collection(
         &self,
@@ -397,6 +454,7 @@ where
         self.instances.insert(
             id,
             Instance::new(
+                id,
                 self.build_info,
                 Arc::clone(&self.storage_collections),
                 arranged_logs,
@@ -404,6 +462,7 @@ where
                 Arc::clone(&self.transient_id_gen),
                 self.metrics.for_instance(id),
                 Arc::clone(&self.dyncfg),
+                self.now.clone(),
                 self.response_tx.clone(),
                 self.introspection_tx.clone(),
             ),
@@ -646,6 +705,21 @@ where
         Ok(())
     }
 
+    /// Renew the leases of the given transient collections and peeks.
+    ///
+    /// Transient collections and peeks whose leases are not renewed within
+    /// `compute_transient_dataflow_lease` are torn down by the controller.
+    pub fn renew_leases(
+        &mut self,
+        instance_id: ComputeInstanceId,
+        collection_ids: impl IntoIterator<Item = GlobalId>,
+        peek_uuids: impl IntoIterator<Item = Uuid>,
+    ) -> Result<(), InstanceMissing> {
+        self.instance_mut(instance_id)?
+            .renew_leases(collection_ids, peek_uuids);
+        Ok(())
+    }
+
     /// Assign a read policy to specific identifiers.
     ///
     /// The policies are assigned in the order presented, and repeated identifiers should
@@ -846,6 +920,14 @@ impl<T> CollectionState<T> {
         self.write_frontier.borrow()
     }
 
+    /// Reports the write frontier reported by the given replica, if the replica maintains this
+    /// collection.
+    pub fn replica_write_frontier(&self, replica_id: ReplicaId) -> Option<AntichainRef<T>> {
+        self.replica_write_frontiers
+            .get(&replica_id)
+            .map(|frontier| frontier.borrow())
+    }
+
     /// Reports the IDs of the dependencies of this collection.
     fn dependency_ids(&self) -> impl Iterator<Item = GlobalId> + '_ {
         let compute = self.compute_dependencies.iter().copied();
@@ -9,7 +9,7 @@
 
 //! A controller for a compute instance.
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fmt::Debug;
 use std::num::NonZeroI64;
 use std::sync::Arc;
@@ -21,14 +21,19 @@ use futures::{future, StreamExt};
 use mz_build_info::BuildInfo;
 use mz_cluster_client::client::{ClusterStartupEpoch, TimelyConfig};
 use mz_compute_types::dataflows::{BuildDesc, DataflowDescription};
+use mz_compute_types::dyncfgs::{
+    COMPUTE_REAPED_DATAFLOWS_HISTORY_SIZE, COMPUTE_TRANSIENT_DATAFLOW_LEASE,
+};
 use mz_compute_types::plan::flat_plan::FlatPlan;
 use mz_compute_types::plan::LirId;
 use mz_compute_types::sinks::{ComputeSinkConnection, ComputeSinkDesc, PersistSinkConnection};
 use mz_compute_types::sources::SourceInstanceDesc;
+use mz_compute_types::ComputeInstanceId;
 use mz_dyncfg::ConfigSet;
 use mz_expr::RowSetFinishing;
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
+use mz_ore::now::NowFn;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_repr::global_id::TransientIdGen;
 use mz_repr::refresh_schedule::RefreshSchedule;
@@ -151,6 +156,8 @@ pub(super) enum SubscribeTargetError {
 /// The state we keep for a compute instance.
 #[derive(Debug)]
 pub(super) struct Instance<T> {
+    /// The ID of this compute instance.
+    instance_id: ComputeInstanceId,
     /// Build info for spawning replicas
     build_info: &'static BuildInfo,
     /// A handle providing access to storage collections.
@@ -206,6 +213,26 @@ pub(super) struct Instance<T> {
     /// The entry for a copy to is removed once at least one replica has finished
     /// or the exporting collection is dropped.
     copy_tos: BTreeSet<GlobalId>,
+    /// Leases on transient dataflows and peeks.
+    ///
+    /// Leases are installed for all transient sink exports (subscribes and COPY TOs) created
+    /// through [`Instance::create_dataflow`] and all peeks initiated through [`Instance::peek`],
+    /// if `compute_transient_dataflow_lease` is non-zero. Transient index exports don't receive a
+    /// lease: they only exist to serve slow-path peeks, which are covered by the peek's lease, and
+    /// are dropped by the client once the peek has been served. Clients are expected to renew the leases of
+    /// work they are still interested in through [`Instance::renew_leases`]. Work whose lease
+    /// expires is torn down by [`Instance::reap_expired_leases`].
+    leases: BTreeMap<LeaseTarget, Instant>,
+    /// IDs of transient collections that were dropped because their lease expired.
+    ///
+    /// Clients that are unaware of the reaping might still attempt to drop these collections, so
+    /// we remember them to be able to ignore those drop requests.
+    reaped_collections: BTreeSet<GlobalId>,
+    /// Rows reported to `mz_compute_reaped_dataflows`, oldest first.
+    ///
+    /// Bounded by `compute_reaped_dataflows_history_size`. Rows that fall out of the history are
+    /// retracted from the introspection collection.
+    reaped_history: VecDeque<Row>,
     /// The command history, used when introducing new replicas or restarting existing replicas.
     history: ComputeCommandHistory<UIntGauge, T>,
     /// Sender for responses to be delivered.
@@ -224,6 +251,8 @@ pub(super) struct Instance<T> {
     metrics: InstanceMetrics,
     /// Dynamic system configuration.
     dyncfg: Arc<ConfigSet>,
+    /// A function that returns the current time.
+    now: NowFn,
 }
 
 impl<T: ComputeControllerTimestamp> Instance<T> {
@@ -311,6 +340,8 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
         // Update introspection.
         self.report_dependency_updates(id, -1);
 
+        self.leases.remove(&LeaseTarget::Collection(id));
+
         // Remove per-replica collection state.
         for replica in self.replicas.values_mut() {
             replica.remove_collection(id);
@@ -381,6 +412,58 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
         self.replicas.keys().copied()
     }
 
+    /// Returns whether the identified collection is hydrated on at least one replica.
+    pub fn collection_hydrated(&self, id: GlobalId) -> Result<bool, CollectionMissing> {
+        self.collection(id)?;
+
+        let hydrated = self.replicas.values().any(|replica| {
+            replica
+                .collections
+                .get(&id)
+                .map_or(false, |collection| collection.hydrated())
+        });
+        Ok(hydrated)
+    }
+
+    /// Returns whether the identified collection is hydrated on the identified replica.
+    ///
+    /// Returns `false` if the replica does not exist or does not maintain the collection.
+    pub fn collection_hydrated_on_replica(
+        &self,
+        id: GlobalId,
+        replica_id: ReplicaId,
+    ) -> Result<bool, CollectionMissing> {
+        self.collection(id)?;
+
+        let hydrated = self
+            .replicas
+            .get(&replica_id)
+            .and_then(|replica| replica.collections.get(&id))
+            .map_or(false, |collection| collection.hydrated());
+        Ok(hydrated)
+    }
+
+    /// Returns when the identified replica last sent a response.
+    ///
+    /// Returns `None` if the replica does not exist.
+    pub fn replica_last_heartbeat(&self, replica_id: ReplicaId) -> Option<Instant> {
+        self.replicas
+            .get(&replica_id)
+            .map(|replica| replica.last_heartbeat)
+    }
+
+    /// Returns whether the identified replica has hydrated all collections it maintains.
+    ///
+    /// Returns `None` if the replica does not exist.
+    pub fn replica_hydrated(&self, replica_id: ReplicaId) -> Option<bool> {
+        self.replicas.get(&replica_id).map(|replica| {
+            replica
+                .collections
+                .values()
+                .all(|collection| collection.hydrated())
+        })
+    }
+
     /// Return the IDs of pending peeks targeting the specified replica.
     fn peeks_targeting(
         &self,
@@ -581,6 +664,7 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
 
         // Destructure `self` here so we don't forget to consider dumping newly added fields.
         let Self {
+            instance_id: _,
             build_info: _,
             storage_collections: _,
             initialized,
@@ -591,6 +675,9 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
             peeks,
             subscribes,
             copy_tos,
+            leases,
+            reaped_collections,
+            reaped_history: _,
             history: _,
             response_tx: _,
             introspection_tx: _,
@@ -599,6 +686,7 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
             transient_id_gen: _,
             metrics: _,
             dyncfg: _,
+            now: _,
         } = self;
 
         fn field(
@@ -626,6 +714,12 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
             .map(|(id, subscribe)| (id.to_string(), format!("{subscribe:?}")))
             .collect();
         let copy_tos: Vec<_> = copy_tos.iter().map(|id| id.to_string()).collect();
+        let leases: BTreeMap<_, _> = leases
+            .iter()
+            .map(|(target, deadline)| (target.to_string(), format!("{deadline:?}")))
+            .collect();
+        let reaped_collections: Vec<_> =
+            reaped_collections.iter().map(|id| id.to_string()).collect();
         let replica_epochs: BTreeMap<_, _> = replica_epochs
             .iter()
             .map(|(id, epoch)| (id.to_string(), epoch))
@@ -639,6 +733,8 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
             field("peeks", peeks)?,
             field("subscribes", subscribes)?,
             field("copy_tos", copy_tos)?,
+            field("leases", leases)?,
+            field("reaped_collections", reaped_collections)?,
             field("envd_epoch", envd_epoch)?,
             field("replica_epochs", replica_epochs)?,
         ]);
@@ -652,6 +748,7 @@ where
     ComputeGrpcClient: ComputeClient<T>,
 {
     pub fn new(
+        instance_id: ComputeInstanceId,
         build_info: &'static BuildInfo,
         storage: Arc<dyn StorageCollections<Timestamp = T>>,
         arranged_logs: BTreeMap<LogVariant, GlobalId>,
@@ -659,6 +756,7 @@ where
         transient_id_gen: Arc<TransientIdGen>,
         metrics: InstanceMetrics,
         dyncfg: Arc<ConfigSet>,
+        now: NowFn,
         response_tx: crossbeam_channel::Sender<ComputeControllerResponse<T>>,
         introspection_tx: crossbeam_channel::Sender<IntrospectionUpdates>,
     ) -> Self {
@@ -673,6 +771,7 @@ where
         let history = ComputeCommandHistory::new(metrics.for_history());
 
         let mut instance = Self {
+            instance_id,
             build_info,
             storage_collections: storage,
             initialized: false,
@@ -683,6 +782,9 @@ where
             peeks: Default::default(),
             subscribes: Default::default(),
             copy_tos: Default::default(),
+            leases: Default::default(),
+            reaped_collections: Default::default(),
+            reaped_history: Default::default(),
             history,
             response_tx,
             introspection_tx,
@@ -691,6 +793,7 @@ where
             transient_id_gen,
             metrics,
             dyncfg,
+            now,
         };
 
         instance.send(ComputeCommand::CreateTimely {
@@ -743,6 +846,15 @@ where
         // Collections might have been dropped but not cleaned up yet.
         self.cleanup_collections();
 
+        // Retract this instance's entries from `mz_compute_reaped_dataflows`.
+        let retractions: Vec<_> = self.reaped_history.drain(..).map(|row| (row, -1)).collect();
+        if !retractions.is_empty() {
+            self.deliver_introspection_updates(
+                IntrospectionType::ComputeReapedDataflows,
+                retractions,
+            );
+        }
+
         assert!(
             self.replicas.is_empty(),
             "cannot drop instances with provisioned replicas"
@@ -1144,6 +1256,7 @@ where
                 with_snapshot: se.with_snapshot,
                 up_to: se.up_to,
                 non_null_assertions: se.non_null_assertions,
+                assert_append_only: se.assert_append_only,
                 refresh_schedule: se.refresh_schedule,
             };
             sink_exports.insert(id, desc);
@@ -1202,8 +1315,18 @@ where
             );
         } else {
             let collections: Vec<_> = augmented_dataflow.export_ids().collect();
+            let leased: Vec<_> = augmented_dataflow
+                .sink_exports
+                .keys()
+                .filter(|id| id.is_transient())
+                .copied()
+                .collect();
             self.send(ComputeCommand::CreateDataflow(augmented_dataflow));
 
+            for id in leased {
+                self.install_lease(LeaseTarget::Collection(id));
+            }
+
             for id in collections {
                 self.maybe_schedule_collection(id);
             }
@@ -1284,6 +1407,11 @@ where
         let mut read_capability_updates = BTreeMap::new();
 
         for id in &ids {
+            // Collections reaped because of an expired lease have already been dropped.
+            if self.reaped_collections.remove(id) {
+                continue;
+            }
+
             let collection = self.collection_mut(*id)?;
 
             // Mark the collection as dropped to allow it to be removed from the controller state.
@@ -1377,6 +1505,7 @@ where
                 requested_at: Instant::now(),
             },
         );
+        self.install_lease(LeaseTarget::Peek(uuid));
 
         self.send(ComputeCommand::Peek(Peek {
             literal_constraints,
@@ -1736,6 +1865,8 @@ where
             return;
         };
 
+        self.leases.remove(&LeaseTarget::Peek(uuid));
+
         // NOTE: We need to send the `CancelPeek` command _before_ we release the peek's read hold,
         // to avoid the edge case that caused #16615.
         self.send(ComputeCommand::CancelPeek { uuid });
@@ -1752,6 +1883,10 @@ where
     }
 
     pub fn handle_response(&mut self, response: ComputeResponse<T>, replica_id: ReplicaId) {
+        if let Some(replica) = self.replicas.get_mut(&replica_id) {
+            replica.last_heartbeat = Instant::now();
+        }
+
         match response {
             ComputeResponse::Frontiers(id, frontiers) => {
                 self.handle_frontiers_response(id, frontiers, replica_id);
@@ -2066,6 +2201,117 @@ where
         }
     }
 
+    /// Install a lease for the given target, if leases are enabled.
+    fn install_lease(&mut self, target: LeaseTarget) {
+        let lease = COMPUTE_TRANSIENT_DATAFLOW_LEASE.get(&self.dyncfg);
+        if !lease.is_zero() {
+            self.leases.insert(target, Instant::now() + lease);
+        }
+    }
+
+    /// Renew the leases of the identified transient collections and peeks.
+    ///
+    /// Targets that don't hold a lease are ignored.
+    pub fn renew_leases(
+        &mut self,
+        collection_ids: impl IntoIterator<Item = GlobalId>,
+        peek_uuids: impl IntoIterator<Item = Uuid>,
+    ) {
+        let deadline = Instant::now() + COMPUTE_TRANSIENT_DATAFLOW_LEASE.get(&self.dyncfg);
+        let targets = collection_ids
+            .into_iter()
+            .map(LeaseTarget::Collection)
+            .chain(peek_uuids.into_iter().map(LeaseTarget::Peek));
+        for target in targets {
+            if let Some(lease) = self.leases.get_mut(&target) {
+                *lease = deadline;
+            }
+        }
+    }
+
+    /// Tear down transient collections and peeks whose lease has expired.
+    ///
+    /// Clients of reaped subscribes and COPY TOs receive an error response, clients of reaped
+    /// peeks receive a `Canceled` response. Each reaped target is recorded in the
+    /// `mz_compute_reaped_dataflows` introspection relation.
+    fn reap_expired_leases(&mut self) {
+        let now = Instant::now();
+        let expired: Vec<_> = self
+            .leases
+            .iter()
+            .filter(|(_target, deadline)| **deadline <= now)
+            .map(|(target, _deadline)| *target)
+            .collect();
+
+        let mut updates = Vec::new();
+        for target in expired {
+            self.leases.remove(&target);
+
+            let kind = match target {
+                LeaseTarget::Peek(uuid) => {
+                    self.cancel_peek(uuid);
+                    "peek"
+                }
+                LeaseTarget::Collection(id) => {
+                    let kind = if let Some(subscribe) = self.subscribes.get(&id) {
+                        let batch = SubscribeBatch {
+                            lower: subscribe.frontier.clone(),
+                            upper: Antichain::new(),
+                            updates: Err("subscribe dataflow reaped after lease expiry".into()),
+                        };
+                        self.deliver_response(ComputeControllerResponse::SubscribeResponse(
+                            id, batch,
+                        ));
+                        "subscribe"
+                    } else {
+                        // Only sink exports hold leases, so this must be a COPY TO. Its client
+                        // has already been notified if it completed on some replica.
+                        if self.copy_tos.contains(&id) {
+                            let error =
+                                anyhow::anyhow!("COPY TO dataflow reaped after lease expiry");
+                            self.deliver_response(ComputeControllerResponse::CopyToResponse(
+                                id,
+                                Err(error),
+                            ));
+                        }
+                        "copy-to"
+                    };
+
+                    let needs_drop = self.collection(id).map_or(false, |c| !c.dropped);
+                    if needs_drop {
+                        self.drop_collections(vec![id]).expect("collection exists");
+                        self.reaped_collections.insert(id);
+                    }
+                    kind
+                }
+            };
+
+            tracing::info!(%target, kind, "reaped transient compute work after lease expiry");
+            let row = Row::pack_slice(&[
+                Datum::String(&target.to_string()),
+                Datum::String(&self.instance_id.to_string()),
+                Datum::String(kind),
+                Datum::TimestampTz(
+                    mz_ore::now::to_datetime((self.now)())
+                        .try_into()
+                        .expect("must fit"),
+                ),
+            ]);
+            self.reaped_history.push_back(row.clone());
+            updates.push((row, 1));
+        }
+
+        let history_size = COMPUTE_REAPED_DATAFLOWS_HISTORY_SIZE.get(&self.dyncfg);
+        while self.reaped_history.len() > history_size {
+            let row = self.reaped_history.pop_front().expect("history not empty");
+            updates.push((row, -1));
+        }
+
+        if !updates.is_empty() {
+            self.deliver_introspection_updates(IntrospectionType::ComputeReapedDataflows, updates);
+        }
+    }
+
     /// Process pending maintenance work.
     ///
     /// This method is invoked periodically by the global controller.
@@ -2074,6 +2320,7 @@ where
     pub fn maintain(&mut self) {
         self.rehydrate_failed_replicas();
         self.downgrade_warmup_capabilities();
+        self.reap_expired_leases();
         self.schedule_collections();
         self.cleanup_collections();
         self.refresh_state_metrics();
@@ -2098,6 +2345,24 @@ struct PendingPeek<T> {
     requested_at: Instant,
 }
 
+/// The target of a lease on transient compute work.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum LeaseTarget {
+    /// A transient collection, e.g. a subscribe.
+    Collection(GlobalId),
+    /// A peek.
+    Peek(Uuid),
+}
+
+impl std::fmt::Display for LeaseTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LeaseTarget::Collection(id) => write!(f, "{id}"),
+            LeaseTarget::Peek(uuid) => write!(f, "{uuid}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct ActiveSubscribe<T> {
     /// Current upper frontier of this subscribe.
@@ -2134,6 +2399,8 @@ pub struct ReplicaState<T> {
     collections: BTreeMap<GlobalId, ReplicaCollectionState<T>>,
     /// Whether the replica has failed and requires rehydration.
     failed: bool,
+    /// When the replica last sent a response, or when it was created if it hasn't sent any yet.
+    last_heartbeat: Instant,
 }
 
 impl<T: Debug> ReplicaState<T> {
@@ -2152,6 +2419,7 @@ impl<T: Debug> ReplicaState<T> {
             introspection_tx,
             collections: Default::default(),
             failed: false,
+            last_heartbeat: Instant::now(),
         }
     }
 
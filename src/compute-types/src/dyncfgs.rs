@@ -52,6 +52,15 @@ pub const ENABLE_CHUNKED_STACK: Config<bool> = Config::new(
     "Enable the chunked stack implementation in compute.",
 );
 
+/// The chunk size of the chunked stack implementation, in number of elements.
+pub const CHUNKED_STACK_CHUNK_SIZE: Config<usize> = Config::new(
+    "compute_chunked_stack_chunk_size",
+    64 << 10,
+    "The number of elements per chunk of the chunked stack implementation in compute. \
+     Rounded up to the next power of two and capped at the compile-time maximum. Applies \
+     to the arrangements of dataflows created after the change.",
+);
+
 /// Maximum number of in-flight bytes emitted by persist_sources feeding dataflows.
 pub const DATAFLOW_MAX_INFLIGHT_BYTES: Config<Option<usize>> = Config::new(
     "compute_dataflow_max_inflight_bytes",
@@ -125,6 +134,24 @@ pub const PERSIST_SINK_OBEY_READ_ONLY: Config<bool> = Config::new(
     "Whether the compute persist_sink obeys read-only mode.",
 );
 
+/// The duration of leases on transient dataflows and peeks.
+///
+/// Transient work whose lease isn't renewed by the client before it expires is torn down by the
+/// compute controller. A zero duration disables leases.
+pub const COMPUTE_TRANSIENT_DATAFLOW_LEASE: Config<Duration> = Config::new(
+    "compute_transient_dataflow_lease",
+    Duration::from_secs(60),
+    "The duration after which transient compute dataflows and peeks are torn down if their \
+     client stops renewing their lease. Zero disables leases.",
+);
+
+/// The number of entries retained in `mz_compute_reaped_dataflows`, per cluster.
+pub const COMPUTE_REAPED_DATAFLOWS_HISTORY_SIZE: Config<usize> = Config::new(
+    "compute_reaped_dataflows_history_size",
+    100,
+    "The number of most recent entries per cluster retained in mz_compute_reaped_dataflows.",
+);
+
 /// Adds the full set of all compute `Config`s.
 pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
     configs
@@ -133,6 +160,7 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&ENABLE_COLUMNATION_LGALLOC)
         .add(&ENABLE_LGALLOC_EAGER_RECLAMATION)
         .add(&ENABLE_CHUNKED_STACK)
+        .add(&CHUNKED_STACK_CHUNK_SIZE)
         .add(&DATAFLOW_MAX_INFLIGHT_BYTES)
         .add(&DATAFLOW_MAX_INFLIGHT_BYTES_CC)
         .add(&LGALLOC_BACKGROUND_INTERVAL)
@@ -142,4 +170,6 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&COPY_TO_S3_ARROW_BUILDER_BUFFER_RATIO)
         .add(&COPY_TO_S3_MULTIPART_PART_SIZE_BYTES)
         .add(&PERSIST_SINK_OBEY_READ_ONLY)
+        .add(&COMPUTE_TRANSIENT_DATAFLOW_LEASE)
+        .add(&COMPUTE_REAPED_DATAFLOWS_HISTORY_SIZE)
 }
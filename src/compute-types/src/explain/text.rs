@@ -766,6 +766,10 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for BasicPlan {
                     writeln!(f, "{}aggrs[{}]=({}, {})", ctx.indent, i, i_datum, agg)?;
                 }
             }
+            BasicPlan::IncrementalWindow(idx, agg) => {
+                let agg = mode.expr(agg, None);
+                writeln!(f, "{}incremental_aggr=({}, {})", ctx.indent, idx, agg)?;
+            }
         }
         Ok(())
     }
@@ -656,6 +656,30 @@ impl<T> FlatPlan<T> {
         (self.nodes, self.root, self.topological_order)
     }
 
+    /// Returns the parent and the nesting depth of each node reachable from the root.
+    ///
+    /// The root has no parent and a nesting depth of zero. Nodes that are referenced by multiple
+    /// other nodes are attributed to the first of them found in a depth-first traversal from the
+    /// root.
+    pub fn parents(&self) -> BTreeMap<LirId, (Option<LirId>, u8)> {
+        let mut parents = BTreeMap::new();
+        let mut todo = vec![(self.root, None, 0_u8)];
+        while let Some((lir_id, parent, nesting)) = todo.pop() {
+            if parents.contains_key(&lir_id) {
+                continue;
+            }
+            parents.insert(lir_id, (parent, nesting));
+
+            let node = self.nodes.get(&lir_id).expect("FlatPlan invariant (2)");
+            let child_nesting = nesting.saturating_add(1);
+            todo.extend(
+                node.input_lir_ids()
+                    .map(|input| (input, Some(lir_id), child_nesting)),
+            );
+        }
+        parents
+    }
+
     /// Replace references to global IDs by the result of `func`.
     pub fn replace_ids<F>(&mut self, mut func: F)
     where
@@ -744,6 +768,47 @@ impl<T: Clone> FlatPlanNode<T> {
 }
 
 impl<T> FlatPlanNode<T> {
+    /// Returns a short, human-readable description of this node's operator.
+    ///
+    /// The descriptions match the operator names used by `EXPLAIN PHYSICAL PLAN`.
+    pub fn operator_name(&self) -> String {
+        use FlatPlanNode::*;
+
+        match self {
+            Constant { .. } => "Constant".into(),
+            Get { id, plan, .. } => match plan {
+                GetPlan::PassArrangements => format!("Get::PassArrangements {id}"),
+                GetPlan::Arrangement(..) => format!("Get::Arrangement {id}"),
+                GetPlan::Collection(..) => format!("Get::Collection {id}"),
+            },
+            LetRec { .. } => "With Mutually Recursive".into(),
+            Mfp { .. } => "Mfp".into(),
+            FlatMap { func, .. } => format!("FlatMap {func}"),
+            Join { plan, .. } => match plan {
+                JoinPlan::Linear(..) => "Join::Linear".into(),
+                JoinPlan::Delta(..) => "Join::Delta".into(),
+            },
+            Reduce { plan, .. } => match plan {
+                ReducePlan::Distinct => "Reduce::Distinct".into(),
+                ReducePlan::Accumulable(..) => "Reduce::Accumulable".into(),
+                ReducePlan::Hierarchical(..) => "Reduce::Hierarchical".into(),
+                ReducePlan::Basic(..) => "Reduce::Basic".into(),
+                ReducePlan::Collation(..) => "Reduce::Collation".into(),
+            },
+            TopK { top_k_plan, .. } => match top_k_plan {
+                TopKPlan::MonotonicTop1(..) => "TopK::MonotonicTop1".into(),
+                TopKPlan::MonotonicTopK(..) => "TopK::MonotonicTopK".into(),
+                TopKPlan::Basic(..) => "TopK::Basic".into(),
+            },
+            Negate { .. } => "Negate".into(),
+            Threshold { threshold_plan, .. } => match threshold_plan {
+                ThresholdPlan::Basic(..) => "Threshold::Basic".into(),
+            },
+            Union { .. } => "Union".into(),
+            ArrangeBy { .. } => "ArrangeBy".into(),
+        }
+    }
+
     /// Returns the IDs of input nodes to this node.
     fn input_lir_ids(&self) -> impl Iterator<Item = LirId> {
         use FlatPlanNode::*;
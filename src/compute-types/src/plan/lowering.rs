@@ -14,7 +14,7 @@ use std::collections::BTreeMap;
 use mz_expr::JoinImplementation::{DeltaQuery, Differential, IndexedFilter, Unimplemented};
 use mz_expr::{
     permutation_for_arrangement, Id, JoinInputMapper, MapFilterProject, MirRelationExpr,
-    MirScalarExpr, OptimizedMirRelationExpr,
+    MirScalarExpr, OptimizedMirRelationExpr, TableFunc,
 };
 use mz_ore::{soft_assert_eq_or_log, soft_panic_or_log};
 use mz_repr::optimize::OptimizerFeatures;
@@ -23,7 +23,7 @@ use timely::progress::Timestamp;
 
 use crate::dataflows::{BuildDesc, DataflowDescription, IndexImport};
 use crate::plan::join::{DeltaJoinPlan, JoinPlan, LinearJoinPlan};
-use crate::plan::reduce::{KeyValPlan, ReducePlan};
+use crate::plan::reduce::{supports_incremental_window, BasicPlan, KeyValPlan, ReducePlan};
 use crate::plan::threshold::ThresholdPlan;
 use crate::plan::top_k::TopKPlan;
 use crate::plan::{AvailableCollections, GetPlan, LirId, Plan};
@@ -37,6 +37,11 @@ pub(super) struct Context {
     debug_info: LirDebugInfo,
     /// Whether to enable fusion of MFPs in reductions.
     enable_reduce_mfp_fusion: bool,
+    /// Whether to render supported window functions over monotonic inputs incrementally.
+    enable_incremental_window_functions: bool,
+    /// Set by a `FlatMap` that immediately unnests the output of the `Reduce` it is
+    /// about to lower, if that `Reduce` can be rendered as an incremental window function.
+    unnested_incremental_window: bool,
 }
 
 impl Context {
@@ -49,6 +54,8 @@ impl Context {
                 id: GlobalId::Transient(0),
             },
             enable_reduce_mfp_fusion: features.enable_reduce_mfp_fusion,
+            enable_incremental_window_functions: features.enable_incremental_window_functions,
+            unnested_incremental_window: false,
         }
     }
 
@@ -93,6 +100,16 @@ impl Context {
                 .or_insert_with(AvailableCollections::new_raw);
         }
 
+        // Incremental window functions process updates in timestamp order, which is not
+        // available in the iterative scopes that recursive dataflows are rendered in.
+        if desc
+            .objects_to_build
+            .iter()
+            .any(|build| build.plan.is_recursive())
+        {
+            self.enable_incremental_window_functions = false;
+        }
+
         // Build each object in order, registering the arrangements it forms.
         let mut objects_to_build = Vec::with_capacity(desc.objects_to_build.len());
         for build in desc.objects_to_build {
@@ -381,6 +398,8 @@ impl Context {
                 )
             }
             MirRelationExpr::FlatMap { input, func, exprs } => {
+                self.unnested_incremental_window = self.enable_incremental_window_functions
+                    && is_unnested_incremental_window(input, func, exprs);
                 let (input, keys) = self.lower_mir_expr(input)?;
                 // This stage can absorb arbitrary MFP instances.
                 let mfp = mfp.take();
@@ -553,6 +572,9 @@ This is not expected to cause incorrect results, but could indicate a performanc
                 monotonic,
                 expected_group_size,
             } => {
+                // Consume the flag before lowering the input, which may contain other reductions.
+                let unnested_incremental_window =
+                    std::mem::take(&mut self.unnested_incremental_window);
                 let input_arity = input.arity();
                 let (input, keys) = self.lower_mir_expr(input)?;
                 let (input_key, permutation_and_new_arity) = if let Some((
@@ -574,8 +596,11 @@ This is not expected to cause incorrect results, but could indicate a performanc
                     aggregates,
                     permutation_and_new_arity,
                 );
-                let reduce_plan =
-                    ReducePlan::create_from(aggregates.clone(), *monotonic, *expected_group_size);
+                let reduce_plan = if unnested_incremental_window {
+                    ReducePlan::Basic(BasicPlan::IncrementalWindow(0, aggregates[0].clone()))
+                } else {
+                    ReducePlan::create_from(aggregates.clone(), *monotonic, *expected_group_size)
+                };
                 // Return the plan, and the keys it produces.
                 let mfp_after;
                 let output_arity;
@@ -923,6 +948,35 @@ This is not expected to cause incorrect results, but could indicate a performanc
     }
 }
 
+/// Determines whether the `input` of a `FlatMap` applying `func` to `exprs` is a `Reduce`
+/// that can be rendered as an incremental window function.
+///
+/// This requires that the `FlatMap` unnests the window function output, possibly through a
+/// projection, as the incremental rendering produces one list per input record rather than one
+/// list per group.
+fn is_unnested_incremental_window(
+    input: &MirRelationExpr,
+    func: &TableFunc,
+    exprs: &[MirScalarExpr],
+) -> bool {
+    let (TableFunc::UnnestList { .. }, [MirScalarExpr::Column(column)]) = (func, exprs) else {
+        return false;
+    };
+    let (input, column) = match input {
+        MirRelationExpr::Project { input, outputs } => (&**input, outputs[*column]),
+        input => (input, *column),
+    };
+    match input {
+        MirRelationExpr::Reduce {
+            group_key,
+            aggregates,
+            monotonic,
+            ..
+        } => column == group_key.len() && supports_incremental_window(aggregates, *monotonic),
+        _ => false,
+    }
+}
+
 /// Various bits of state to print along with error messages during LIR planning,
 /// to aid debugging.
 #[derive(Clone, Debug)]
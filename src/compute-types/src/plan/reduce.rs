@@ -473,6 +473,16 @@ pub enum BasicPlan {
     /// `(index of the set of the input we are aggregating over,
     ///   the aggregation function)`
     Multiple(Vec<(usize, AggregateExpr)>),
+    /// Plan for incrementally maintaining a single window function over
+    /// a monotonic input. Instead of re-evaluating the whole group on each
+    /// change, the rendering keeps the group sorted and only updates the
+    /// positions affected by new records.
+    ///
+    /// The output differs from `Single` in that it contains one list per
+    /// input record rather than one list per group, which means that the
+    /// plan must only be used when the output is immediately unnested. See
+    /// [`supports_incremental_window`] for the supported aggregations.
+    IncrementalWindow(usize, AggregateExpr),
 }
 
 impl RustType<proto_basic_plan::ProtoSingleBasicPlan> for (usize, AggregateExpr) {
@@ -508,6 +518,12 @@ impl RustType<ProtoBasicPlan> for BasicPlan {
                 BasicPlan::Multiple(aggrs) => Kind::Multiple(ProtoMultipleBasicPlan {
                     aggrs: aggrs.into_proto(),
                 }),
+                BasicPlan::IncrementalWindow(index, expr) => Kind::IncrementalWindow({
+                    ProtoSingleBasicPlan {
+                        index: index.into_proto(),
+                        expr: Some(expr.into_proto()),
+                    }
+                }),
             }),
         }
     }
@@ -524,6 +540,10 @@ impl RustType<ProtoBasicPlan> for BasicPlan {
                 x.expr.into_rust_if_some("ProtoSingleBasicPlan.expr")?,
             ),
             Kind::Multiple(x) => BasicPlan::Multiple(x.aggrs.into_rust()?),
+            Kind::IncrementalWindow(x) => BasicPlan::IncrementalWindow(
+                x.index.into_rust()?,
+                x.expr.into_rust_if_some("ProtoSingleBasicPlan.expr")?,
+            ),
         })
     }
 }
@@ -1001,6 +1021,30 @@ pub fn reduction_type(func: &AggregateFunc) -> ReductionType {
     }
 }
 
+/// Determines whether the aggregations of a `Reduce` can be rendered with
+/// [`BasicPlan::IncrementalWindow`].
+///
+/// This is the case for a single `ROW_NUMBER`, `LAG`, or `LEAD` over a monotonic
+/// input. `IGNORE NULLS` is excluded, because the positions affected by a new
+/// record then depend on the data rather than only on the offsets.
+pub fn supports_incremental_window(aggregates: &[AggregateExpr], monotonic: bool) -> bool {
+    match aggregates {
+        [AggregateExpr {
+            func,
+            distinct: false,
+            ..
+        }] if monotonic => matches!(
+            func,
+            AggregateFunc::RowNumber { .. }
+                | AggregateFunc::LagLead {
+                    ignore_nulls: false,
+                    ..
+                }
+        ),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mz_proto::protobuf_roundtrip;
@@ -37,6 +37,8 @@ pub struct ComputeSinkDesc<S: 'static = (), T = Timestamp> {
     pub up_to: Antichain<T>,
     /// TODO(#25239): Add documentation.
     pub non_null_assertions: Vec<usize>,
+    /// Whether to report retractions in the sinked collection as errors.
+    pub assert_append_only: bool,
     /// TODO(#25239): Add documentation.
     pub refresh_schedule: Option<RefreshSchedule>,
 }
@@ -53,6 +55,7 @@ impl Arbitrary for ComputeSinkDesc<CollectionMetadata, Timestamp> {
             any::<bool>(),
             proptest::collection::vec(any::<Timestamp>(), 1..4),
             proptest::collection::vec(any::<usize>(), 0..4),
+            any::<bool>(),
             proptest::option::of(any::<RefreshSchedule>()),
         )
             .prop_map(
@@ -63,6 +66,7 @@ impl Arbitrary for ComputeSinkDesc<CollectionMetadata, Timestamp> {
                     with_snapshot,
                     up_to_frontier,
                     non_null_assertions,
+                    assert_append_only,
                     refresh_schedule,
                 )| {
                     ComputeSinkDesc {
@@ -72,6 +76,7 @@ impl Arbitrary for ComputeSinkDesc<CollectionMetadata, Timestamp> {
                         with_snapshot,
                         up_to: Antichain::from(up_to_frontier),
                         non_null_assertions,
+                        assert_append_only,
                         refresh_schedule,
                     }
                 },
@@ -89,6 +94,7 @@ impl RustType<ProtoComputeSinkDesc> for ComputeSinkDesc<CollectionMetadata, Time
             with_snapshot: self.with_snapshot,
             up_to: Some(self.up_to.into_proto()),
             non_null_assertions: self.non_null_assertions.into_proto(),
+            assert_append_only: self.assert_append_only,
             refresh_schedule: self.refresh_schedule.into_proto(),
         }
     }
@@ -107,6 +113,7 @@ impl RustType<ProtoComputeSinkDesc> for ComputeSinkDesc<CollectionMetadata, Time
                 .up_to
                 .into_rust_if_some("ProtoComputeSinkDesc::up_to")?,
             non_null_assertions: proto.non_null_assertions.into_rust()?,
+            assert_append_only: proto.assert_append_only,
             refresh_schedule: proto.refresh_schedule.into_rust()?,
         })
     }
@@ -28,6 +28,7 @@ use mz_compute_client::protocol::response::{
     StatusResponse, SubscribeResponse,
 };
 use mz_compute_types::dataflows::DataflowDescription;
+use mz_compute_types::dyncfgs::CHUNKED_STACK_CHUNK_SIZE;
 use mz_compute_types::plan::flat_plan::FlatPlan;
 use mz_compute_types::plan::LirId;
 use mz_dyncfg::ConfigSet;
@@ -447,12 +448,18 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
                 .insert(id, Rc::clone(&suspension_token));
         }
 
-        crate::render::build_compute_dataflow(
-            self.timely_worker,
-            self.compute_state,
-            dataflow,
-            start_signal,
-        );
+        // The chunk size is selected per dataflow: its arrangements keep the chunk size that
+        // was configured when the dataflow was created.
+        let chunk_size = CHUNKED_STACK_CHUNK_SIZE.get(&self.compute_state.worker_config);
+        let (timely_worker, compute_state) = (&mut *self.timely_worker, &mut *self.compute_state);
+        mz_timely_util::containers::stack::with_chunk_size(chunk_size, || {
+            crate::render::build_compute_dataflow(
+                timely_worker,
+                compute_state,
+                dataflow,
+                start_signal,
+            )
+        });
     }
 
     fn handle_schedule(&mut self, id: GlobalId) {
@@ -1003,6 +1010,7 @@ impl PersistPeek {
             txns_read.as_mut(),
             metrics,
             &metadata.relation_desc,
+            &mfp_plan,
             Antichain::from_elem(as_of),
         )
         .await
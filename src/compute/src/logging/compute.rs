@@ -18,6 +18,7 @@ use std::time::Duration;
 use differential_dataflow::collection::AsCollection;
 use differential_dataflow::trace::{BatchReader, Cursor};
 use differential_dataflow::Collection;
+use mz_compute_types::plan::LirId;
 use mz_ore::cast::CastFrom;
 use mz_repr::{Datum, Diff, GlobalId, Timestamp};
 use mz_timely_util::replay::MzReplay;
@@ -128,6 +129,30 @@ pub enum ComputeEvent {
         /// The change in error count.
         diff: i64,
     },
+    /// The LIR nodes of an object built by a dataflow have been rendered.
+    LirMapping {
+        /// Identifier of the object.
+        global_id: GlobalId,
+        /// Timely worker index of the dataflow building the object.
+        dataflow_index: usize,
+        /// Metadata about each rendered LIR node.
+        mapping: Vec<(LirId, LirMetadata)>,
+    },
+}
+
+/// Metadata about a rendered LIR node.
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub struct LirMetadata {
+    /// A human-readable description of the node's operator.
+    pub operator: String,
+    /// The ID of the node consuming this node's output, if any.
+    pub parent_lir_id: Option<LirId>,
+    /// The depth of the node below the root of its plan.
+    pub nesting: u8,
+    /// The range of Timely operator IDs created to render the node.
+    ///
+    /// The start is inclusive and the end is exclusive.
+    pub operator_span: (usize, usize),
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -209,6 +234,7 @@ pub(super) fn construct<A: Allocate + 'static>(
         let (mut arrangement_heap_allocations_out, arrangement_heap_allocations) =
             demux.new_output();
         let (mut error_count_out, error_count) = demux.new_output();
+        let (mut lir_mapping_out, lir_mapping) = demux.new_output();
 
         let mut demux_state = DemuxState::new(worker2);
         let mut demux_buffer = Vec::new();
@@ -224,6 +250,7 @@ pub(super) fn construct<A: Allocate + 'static>(
                 let mut arrangement_heap_capacity = arrangement_heap_capacity_out.activate();
                 let mut arrangement_heap_allocations = arrangement_heap_allocations_out.activate();
                 let mut error_count = error_count_out.activate();
+                let mut lir_mapping = lir_mapping_out.activate();
 
                 input.for_each(|cap, data| {
                     data.swap(&mut demux_buffer);
@@ -239,6 +266,7 @@ pub(super) fn construct<A: Allocate + 'static>(
                         arrangement_heap_capacity: arrangement_heap_capacity.session(&cap),
                         arrangement_heap_allocations: arrangement_heap_allocations.session(&cap),
                         error_count: error_count.session(&cap),
+                        lir_mapping: lir_mapping.session(&cap),
                     };
 
                     for (time, logger_id, event) in demux_buffer.drain(..) {
@@ -363,6 +391,28 @@ pub(super) fn construct<A: Allocate + 'static>(
             }
         });
 
+        let mut packer = PermutedRowPacker::new(ComputeLog::LirMapping);
+        let lir_mapping = lir_mapping.as_collection().map({
+            let mut scratch = String::new();
+            move |datum: LirMappingDatum| {
+                let (start, end) = datum.metadata.operator_span;
+                packer.pack_slice(&[
+                    make_string_datum(datum.global_id, &mut scratch),
+                    Datum::UInt64(datum.lir_id),
+                    Datum::UInt64(u64::cast_from(worker_id)),
+                    Datum::UInt64(u64::cast_from(datum.dataflow_id)),
+                    Datum::String(&datum.metadata.operator),
+                    datum
+                        .metadata
+                        .parent_lir_id
+                        .map_or(Datum::Null, Datum::UInt64),
+                    Datum::UInt16(u16::from(datum.metadata.nesting)),
+                    Datum::UInt64(u64::cast_from(start)),
+                    Datum::UInt64(u64::cast_from(end)),
+                ])
+            }
+        });
+
         use ComputeLog::*;
         let logs = [
             (DataflowCurrent, dataflow_current),
@@ -375,6 +425,7 @@ pub(super) fn construct<A: Allocate + 'static>(
             (ArrangementHeapCapacity, arrangement_heap_capacity),
             (ArrangementHeapAllocations, arrangement_heap_allocations),
             (ErrorCount, error_count),
+            (LirMapping, lir_mapping),
         ];
 
         // Build the output arrangements.
@@ -428,6 +479,8 @@ struct DemuxState<A: Allocate> {
     peek_stash: BTreeMap<Uuid, Duration>,
     /// Arrangement size stash
     arrangement_size: BTreeMap<usize, ArrangementSizeState>,
+    /// Maps live dataflows to the LIR mappings of the objects they build.
+    lir_mapping: BTreeMap<usize, Vec<LirMappingDatum>>,
 }
 
 impl<A: Allocate> DemuxState<A> {
@@ -440,6 +493,7 @@ impl<A: Allocate> DemuxState<A> {
             shutdown_dataflows: Default::default(),
             peek_stash: Default::default(),
             arrangement_size: Default::default(),
+            lir_mapping: Default::default(),
         }
     }
 }
@@ -489,6 +543,7 @@ struct DemuxOutput<'a> {
     arrangement_heap_capacity: OutputSession<'a, ArrangementHeapDatum>,
     arrangement_heap_allocations: OutputSession<'a, ArrangementHeapDatum>,
     error_count: OutputSession<'a, ErrorCountDatum>,
+    lir_mapping: OutputSession<'a, LirMappingDatum>,
 }
 
 #[derive(Clone)]
@@ -536,6 +591,14 @@ struct ErrorCountDatum {
     count: i64,
 }
 
+#[derive(Clone)]
+struct LirMappingDatum {
+    global_id: GlobalId,
+    lir_id: LirId,
+    dataflow_id: usize,
+    metadata: LirMetadata,
+}
+
 /// Event handler of the demux operator.
 struct DemuxHandler<'a, 'b, A: Allocate + 'static> {
     /// State kept by the demux operator.
@@ -604,6 +667,11 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
             }
             DataflowShutdown { dataflow_index } => self.handle_dataflow_shutdown(dataflow_index),
             ErrorCount { export_id, diff } => self.handle_error_count(export_id, diff),
+            LirMapping {
+                global_id,
+                dataflow_index,
+                mapping,
+            } => self.handle_lir_mapping(global_id, dataflow_index, mapping),
         }
     }
 
@@ -660,6 +728,14 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
     fn handle_dataflow_dropped(&mut self, id: usize) {
         self.state.dataflow_export_counts.remove(&id);
 
+        // Remove LIR mapping logging for objects built by this dataflow.
+        if let Some(mapping) = self.state.lir_mapping.remove(&id) {
+            let ts = self.ts();
+            for datum in mapping {
+                self.output.lir_mapping.give((datum, ts, -1));
+            }
+        }
+
         if self.state.shutdown_dataflows.remove(&id) {
             // Dataflow has already shut down before it was dropped.
             self.output.shutdown_duration.give((0, self.ts(), 1));
@@ -719,6 +795,26 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
         export.error_count = new_count;
     }
 
+    fn handle_lir_mapping(
+        &mut self,
+        global_id: GlobalId,
+        dataflow_id: usize,
+        mapping: Vec<(LirId, LirMetadata)>,
+    ) {
+        let ts = self.ts();
+        let data = self.state.lir_mapping.entry(dataflow_id).or_default();
+        for (lir_id, metadata) in mapping {
+            let datum = LirMappingDatum {
+                global_id,
+                lir_id,
+                dataflow_id,
+                metadata,
+            };
+            self.output.lir_mapping.give((datum.clone(), ts, 1));
+            data.push(datum);
+        }
+    }
+
     fn handle_peek_install(&mut self, peek: Peek, peek_type: PeekType) {
         let uuid = peek.uuid;
         let ts = self.ts();
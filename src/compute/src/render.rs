@@ -139,14 +139,14 @@ use timely::order::Product;
 use timely::progress::timestamp::Refines;
 use timely::progress::{Antichain, Timestamp};
 use timely::scheduling::ActivateOnDrop;
-use timely::worker::Worker as TimelyWorker;
+use timely::worker::{AsWorker, Worker as TimelyWorker};
 use timely::PartialOrder;
 
 use crate::arrangement::manager::TraceBundle;
 use crate::compute_state::ComputeState;
 use crate::extensions::arrange::{KeyCollection, MzArrange};
 use crate::extensions::reduce::MzReduce;
-use crate::logging::compute::LogDataflowErrors;
+use crate::logging::compute::{ComputeEvent, LirMetadata, LogDataflowErrors};
 use crate::render::context::{
     ArrangementFlavor, Context, MzArrangement, MzArrangementImport, ShutdownToken,
 };
@@ -300,7 +300,7 @@ pub fn build_compute_dataflow<A: Allocate>(
                     context.shutdown_token = ShutdownToken::new(Rc::downgrade(&object_token));
                     tokens.insert(object.id, object_token);
 
-                    let bundle = context.render_recursive_plan(0, object.plan);
+                    let bundle = context.render_recursive_plan(object.id, 0, object.plan);
                     context.insert_id(Id::Global(object.id), bundle);
                 }
 
@@ -458,7 +458,7 @@ where
 {
     pub(crate) fn build_object(&mut self, object: BuildDesc<FlatPlan>) {
         // First, transform the relation expression into a render plan.
-        let bundle = self.render_plan(object.plan);
+        let bundle = self.render_plan(object.id, object.plan);
         self.insert_id(Id::Global(object.id), bundle);
     }
 }
@@ -633,7 +633,12 @@ where
     ///
     /// The method requires that all variables conclude with a physical representation that
     /// contains a collection (i.e. a non-arrangement), and it will panic otherwise.
-    pub fn render_recursive_plan(&mut self, level: usize, plan: FlatPlan) -> CollectionBundle<G> {
+    pub fn render_recursive_plan(
+        &mut self,
+        object_id: GlobalId,
+        level: usize,
+        plan: FlatPlan,
+    ) -> CollectionBundle<G> {
         if plan.is_recursive() {
             let (values, body) = plan.split_recursive();
             let ids: Vec<_> = values.iter().map(|(id, _, _)| *id).collect();
@@ -659,7 +664,7 @@ where
             }
             // Now render each of the bindings.
             for (id, value, limit) in values {
-                let bundle = self.render_recursive_plan(level + 1, value);
+                let bundle = self.render_recursive_plan(object_id, level + 1, value);
                 // We need to ensure that the raw collection exists, but do not have enough information
                 // here to cause that to happen.
                 let (oks, mut err) = bundle.collection.clone().unwrap();
@@ -725,9 +730,9 @@ where
                 );
             }
 
-            self.render_recursive_plan(level, body)
+            self.render_recursive_plan(object_id, level, body)
         } else {
-            self.render_plan(plan)
+            self.render_plan(object_id, plan)
         }
     }
 }
@@ -741,21 +746,50 @@ where
     ///
     /// The return type reflects the uncertainty about the data representation, perhaps
     /// as a stream of data, perhaps as an arrangement, perhaps as a stream of batches.
-    pub fn render_plan(&mut self, plan: FlatPlan) -> CollectionBundle<G> {
+    pub fn render_plan(&mut self, object_id: GlobalId, plan: FlatPlan) -> CollectionBundle<G> {
+        let parents = plan.parents();
         let (mut nodes, root_id, topological_order) = plan.destruct();
 
         // Rendered collections by their `LirId`.
         let mut collections = BTreeMap::new();
+        // Metadata about the rendered nodes, for the LIR mapping log.
+        let mut lir_mapping = Vec::with_capacity(topological_order.len());
 
         for id in topological_order {
             let node = nodes.remove(&id).unwrap();
-            let mut bundle = self.render_plan_node(node, &collections);
+            let operator = node.operator_name();
 
+            // Timely assigns operator IDs from a per-worker counter, so the operators created
+            // while rendering the node are exactly those with IDs between two identifiers
+            // allocated before and after. Dataflow construction is deterministic, so the IDs
+            // agree across workers.
+            let operator_id_start = self.scope.new_identifier() + 1;
+            let mut bundle = self.render_plan_node(node, &collections);
             self.log_operator_hydration(&mut bundle, id);
+            let operator_id_end = self.scope.new_identifier();
+
+            let (parent_lir_id, nesting) = parents.get(&id).copied().unwrap_or((None, 0));
+            lir_mapping.push((
+                id,
+                LirMetadata {
+                    operator,
+                    parent_lir_id,
+                    nesting,
+                    operator_span: (operator_id_start, operator_id_end),
+                },
+            ));
 
             collections.insert(id, bundle);
         }
 
+        if let Some(logger) = &self.compute_logger {
+            logger.log(ComputeEvent::LirMapping {
+                global_id: object_id,
+                dataflow_index: self.dataflow_id,
+                mapping: lir_mapping,
+            });
+        }
+
         collections
             .remove(&root_id)
             .expect("FlatPlan invariant (1)")
@@ -42,6 +42,7 @@ use tracing::error;
 use crate::arrangement::manager::SpecializedTraceHandle;
 use crate::compute_state::{ComputeState, HydrationEvent};
 use crate::extensions::arrange::{KeyCollection, MzArrange};
+use crate::logging::compute::Logger as ComputeLogger;
 use crate::render::errors::ErrorLogger;
 use crate::render::{LinearJoinSpec, RenderTimestamp};
 use crate::typedefs::{ErrAgent, ErrEnter, ErrSpine, RowRowAgent, RowRowEnter, RowRowSpine};
@@ -85,6 +86,10 @@ where
     ///
     /// `None` if no hydration events should be logged in this context.
     pub(super) hydration_logger: Option<HydrationLogger>,
+    /// A logger for compute events, such as the mapping of LIR nodes to dataflow operators.
+    ///
+    /// `None` if compute logging is not initialized.
+    pub(super) compute_logger: Option<ComputeLogger>,
     /// Specification for rendering linear joins.
     pub(super) linear_join_spec: LinearJoinSpec,
 }
@@ -127,6 +132,7 @@ where
             bindings: BTreeMap::new(),
             shutdown_token: Default::default(),
             hydration_logger,
+            compute_logger: compute_state.compute_logger.clone(),
             linear_join_spec: compute_state.linear_join_spec,
         }
     }
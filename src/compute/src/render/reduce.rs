@@ -39,6 +39,8 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use timely::container::columnation::{Columnation, CopyRegion, TimelyStack};
 use timely::container::{CapacityContainerBuilder, PushInto};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::Operator;
 use timely::dataflow::Scope;
 use timely::progress::timestamp::Refines;
 use timely::progress::Timestamp;
@@ -242,6 +244,12 @@ where
                 errors.push(errs);
                 MzArrangement::RowRow(output)
             }
+            ReducePlan::Basic(BasicPlan::IncrementalWindow(index, aggr)) => {
+                let (output, errs) =
+                    self.build_incremental_window(collection, index, aggr, mfp_after);
+                errors.push(errs);
+                MzArrangement::RowRow(output)
+            }
             // Otherwise, we need to render something different for each type of
             // reduction, and then stitch them together.
             ReducePlan::Collation(expr) => {
@@ -866,6 +874,126 @@ where
         (oks, err_output)
     }
 
+    /// Build the dataflow to incrementally maintain a single window function over a
+    /// monotonic input.
+    ///
+    /// Each group is kept sorted by the window ordering. Once a time is complete, its records
+    /// are inserted into their groups, and only the outputs of the positions they affect are
+    /// updated. The resulting arrangement contains one list per input record, which matches
+    /// the output of [`Self::build_basic_aggregate`] once unnested.
+    ///
+    /// The operator retains every input record, along with the output it last produced, for
+    /// as long as the dataflow runs: it needs them to place and number records that arrive
+    /// later. Its memory use is therefore linear in the size of the input, like the input
+    /// arrangement of the reduction it replaces, but it is not reported in the arrangement
+    /// sizes. Updates wait in `pending` only until their time is complete.
+    fn build_incremental_window<S>(
+        &self,
+        input: Collection<S, (Row, Row), Diff>,
+        index: usize,
+        aggr: AggregateExpr,
+        mfp_after: Option<SafeMfpPlan>,
+    ) -> (RowRowArrangement<S>, Collection<S, DataflowError, Diff>)
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        // Extract the value we were asked to aggregate over.
+        let partial = input.map(move |(key, row)| {
+            let binding = SharedRow::get();
+            let mut row_builder = binding.borrow_mut();
+            let value = row.iter().nth(index).unwrap();
+            row_builder.packer().push(value);
+            (key, row_builder.clone())
+        });
+
+        let error_logger = self.error_logger();
+        let (partial, validation_errs) = partial.ensure_monotonic(move |data, diff| {
+            error_logger.log(
+                "Non-monotonic input to ReduceIncrementalWindow",
+                &format!("data={data:?}, diff={diff}"),
+            );
+            let m = "tried to build an incremental window function on non-monotonic input";
+            (EvalError::Internal(m.to_string()).into(), 1)
+        });
+
+        let mut window = incremental_window::IncrementalWindow::new(aggr.func);
+        let mut groups = BTreeMap::<Row, incremental_window::WindowGroup>::new();
+        let mut pending = BTreeMap::<G::Timestamp, Vec<((Row, Row), Diff)>>::new();
+        let mut buffer = Vec::new();
+        let mut datums = DatumVec::new();
+        let exchange =
+            Exchange::new(|((key, _), _, _): &((Row, Row), G::Timestamp, Diff)| key.hashed());
+        let (oks, errs) = partial
+            .inner
+            .unary_notify(
+                exchange,
+                "ReduceIncrementalWindow",
+                [],
+                move |input, output, notificator| {
+                    while let Some((time, data)) = input.next() {
+                        data.swap(&mut buffer);
+                        for (update, record_time, diff) in buffer.drain(..) {
+                            let updates = pending.entry(record_time.clone()).or_insert_with(|| {
+                                notificator.notify_at(time.delayed(&record_time));
+                                Vec::new()
+                            });
+                            updates.push((update, diff));
+                        }
+                    }
+
+                    notificator.for_each(|time, _, _| {
+                        let Some(mut updates) = pending.remove(time.time()) else {
+                            return;
+                        };
+                        updates.sort_by(|(left, _), (right, _)| left.0.cmp(&right.0));
+
+                        let mut session = output.session(&time);
+                        let mut updates = updates.into_iter().peekable();
+                        while let Some(((key, value), diff)) = updates.next() {
+                            let mut values = vec![(value, diff)];
+                            while let Some(((_, value), diff)) =
+                                updates.next_if(|((next, _), _)| *next == key)
+                            {
+                                values.push((value, diff));
+                            }
+                            let group = groups.entry(key.clone()).or_default();
+                            for (agg, diff) in window.update(group, values) {
+                                let temp_storage = RowArena::new();
+                                let mut datums_local = datums.borrow();
+                                datums_local.extend(key.iter());
+                                let key_len = datums_local.len();
+                                datums_local.extend(agg.iter());
+                                let result = match &mfp_after {
+                                    Some(mfp) => {
+                                        match mfp.evaluate_iter(&mut datums_local, &temp_storage) {
+                                            Ok(Some(iter)) => {
+                                                Some(Ok(Row::pack(iter.skip(key_len))))
+                                            }
+                                            Ok(None) => None,
+                                            Err(e) => Some(Err(DataflowError::from(e))),
+                                        }
+                                    }
+                                    None => Some(Ok(agg.clone())),
+                                };
+                                if let Some(result) = result {
+                                    let result = result.map(|val| (key.clone(), val));
+                                    session.give((result, time.time().clone(), diff));
+                                }
+                            }
+                        }
+                    });
+                },
+            )
+            .as_collection()
+            .map_fallible::<CapacityContainerBuilder<_>, CapacityContainerBuilder<_>, _, _, _>(
+                "Demux Errors",
+                |result| result,
+            );
+
+        let arranged = oks.mz_arrange::<RowRowSpine<_, _>>("Arranged ReduceIncrementalWindow");
+        (arranged, validation_errs.concat(&errs))
+    }
+
     fn build_reduce_inaccumulable_distinct<S, V, Tr>(
         &self,
         input: Collection<S, Row, Diff>,
@@ -2524,3 +2652,312 @@ mod window_agg_helpers {
         }
     }
 }
+
+/// State for incrementally maintaining window functions over monotonic inputs.
+///
+/// The outputs mirror those of `row_number` and `lag_lead` in `mz_expr`, but are produced
+/// per record, so that only the records whose output changes need to be updated.
+mod incremental_window {
+    use std::cmp::Ordering;
+
+    use mz_expr::{compare_columns, AggregateFunc, ColumnOrder, LagLeadType};
+    use mz_repr::{Datum, DatumVec, Diff, Row};
+
+    /// A record of a window partition.
+    struct Entry {
+        /// The aggregation input, a list of the function input and the `ORDER BY` values.
+        value: Row,
+        /// The output last produced for this record, if any.
+        output: Option<Row>,
+        /// For `ROW_NUMBER`, the row number following the ones assigned to this record.
+        next_number: i64,
+    }
+
+    /// The records of a window partition, sorted by the window ordering.
+    ///
+    /// Holds one entry per input record of the partition, and never shrinks because the
+    /// input is monotonic.
+    #[derive(Default)]
+    pub struct WindowGroup {
+        entries: Vec<Entry>,
+        /// The largest absolute `LAG`/`LEAD` offset seen so far. Inserting a record can
+        /// only change the outputs of records at most this many positions away.
+        max_offset: usize,
+    }
+
+    /// Maintains window partitions for a single window function.
+    pub struct IncrementalWindow {
+        func: AggregateFunc,
+        left: DatumVec,
+        right: DatumVec,
+    }
+
+    impl IncrementalWindow {
+        pub fn new(func: AggregateFunc) -> Self {
+            assert!(
+                matches!(
+                    func,
+                    AggregateFunc::RowNumber { .. }
+                        | AggregateFunc::LagLead {
+                            ignore_nulls: false,
+                            ..
+                        }
+                ),
+                "unsupported incremental window function: {func:?}"
+            );
+            Self {
+                func,
+                left: DatumVec::new(),
+                right: DatumVec::new(),
+            }
+        }
+
+        /// Inserts `values` into `group`, and returns the resulting changes to the outputs
+        /// of the group's records.
+        pub fn update(
+            &mut self,
+            group: &mut WindowGroup,
+            values: Vec<(Row, Diff)>,
+        ) -> Vec<(Row, Diff)> {
+            let Self { func, left, right } = self;
+            let func = &*func;
+            let order_by = match func {
+                AggregateFunc::RowNumber { order_by } | AggregateFunc::LagLead { order_by, .. } => {
+                    order_by
+                }
+                _ => unreachable!(),
+            };
+
+            // Insert the new records after any equal ones, tracking the range of positions
+            // that received new records.
+            let mut lowest = usize::MAX;
+            let mut highest = 0;
+            for (value, diff) in values {
+                if let AggregateFunc::LagLead { .. } = func {
+                    let (_, offset, _, _) = lag_lead_args(&value);
+                    if !offset.is_null() {
+                        let offset = usize::try_from(offset.unwrap_int32().unsigned_abs())
+                            .expect("u32 fits in usize");
+                        group.max_offset = std::cmp::max(group.max_offset, offset);
+                    }
+                }
+                for _ in 0..diff {
+                    let position = group.entries.partition_point(|entry| {
+                        compare(order_by, left, right, &entry.value, &value) != Ordering::Greater
+                    });
+                    group.entries.insert(
+                        position,
+                        Entry {
+                            value: value.clone(),
+                            output: None,
+                            next_number: 0,
+                        },
+                    );
+                    if position <= highest && lowest != usize::MAX {
+                        highest += 1;
+                    } else {
+                        highest = position;
+                    }
+                    lowest = std::cmp::min(lowest, position);
+                }
+            }
+            if lowest == usize::MAX {
+                return Vec::new();
+            }
+
+            // Determine the positions whose output may have changed.
+            let len = group.entries.len();
+            let (start, end) = match func {
+                AggregateFunc::RowNumber { .. } => (lowest, len),
+                _ => (
+                    lowest.saturating_sub(group.max_offset),
+                    std::cmp::min(highest.saturating_add(group.max_offset) + 1, len),
+                ),
+            };
+
+            let mut changes = Vec::new();
+            for position in start..end {
+                let output = match func {
+                    AggregateFunc::RowNumber { .. } => {
+                        let first = match position.checked_sub(1) {
+                            Some(previous) => group.entries[previous].next_number,
+                            None => 1,
+                        };
+                        let (output, next) =
+                            row_number_output(&group.entries[position].value, first);
+                        group.entries[position].next_number = next;
+                        output
+                    }
+                    AggregateFunc::LagLead { lag_lead, .. } => {
+                        lag_lead_output(&group.entries, position, lag_lead)
+                    }
+                    _ => unreachable!(),
+                };
+                let entry = &mut group.entries[position];
+                if entry.output.as_ref() != Some(&output) {
+                    if let Some(previous) = entry.output.take() {
+                        changes.push((previous, -1));
+                    }
+                    changes.push((output.clone(), 1));
+                    entry.output = Some(output);
+                }
+            }
+            changes
+        }
+    }
+
+    /// Compares two aggregation inputs according to `order_by`, breaking ties by the
+    /// remaining contents so that the order is deterministic.
+    fn compare(
+        order_by: &[ColumnOrder],
+        left_vec: &mut DatumVec,
+        right_vec: &mut DatumVec,
+        left: &Row,
+        right: &Row,
+    ) -> Ordering {
+        let mut left_datums = left_vec.borrow();
+        left_datums.extend(left.unpack_first().unwrap_list().iter().skip(1));
+        let mut right_datums = right_vec.borrow();
+        right_datums.extend(right.unpack_first().unwrap_list().iter().skip(1));
+        compare_columns(order_by, &left_datums, &right_datums, || {
+            left_datums.cmp(&right_datums)
+        })
+        .then_with(|| left.cmp(right))
+    }
+
+    /// Produces the `ROW_NUMBER` output of a record whose first row number is `first`, along
+    /// with the row number following the ones it was assigned.
+    fn row_number_output(value: &Row, first: i64) -> (Row, i64) {
+        let records = value.unpack_first().unwrap_list().iter().next().unwrap();
+        let mut next = first;
+        let mut output = Row::default();
+        output.packer().push_list_with(|packer| {
+            for record in records.unwrap_list().iter() {
+                packer.push_list([Datum::Int64(next), record]);
+                next += 1;
+            }
+        });
+        (output, next)
+    }
+
+    /// Decodes the `((OriginalRow, EncodedArgs), OrderBy...)` input of `LAG`/`LEAD` into the
+    /// input value, the offset, the default value, and the original row.
+    fn lag_lead_args(value: &Row) -> (Datum, Datum, Datum, Datum) {
+        let input = value.unpack_first().unwrap_list().iter().next().unwrap();
+        let mut input = input.unwrap_list().iter();
+        let original_row = input.next().unwrap();
+        let mut encoded_args = input.next().unwrap().unwrap_list().iter();
+        let input_value = encoded_args.next().unwrap();
+        let offset = encoded_args.next().unwrap();
+        let default_value = encoded_args.next().unwrap();
+        (input_value, offset, default_value, original_row)
+    }
+
+    /// Produces the `LAG`/`LEAD` output of the record at `position`.
+    fn lag_lead_output(entries: &[Entry], position: usize, lag_lead: &LagLeadType) -> Row {
+        let (_, offset, default_value, original_row) = lag_lead_args(&entries[position].value);
+        // Null offsets are acceptable, and always return null.
+        let result = if offset.is_null() {
+            Datum::Null
+        } else {
+            let offset = i64::from(offset.unwrap_int32());
+            let offset = match lag_lead {
+                LagLeadType::Lag => -offset,
+                LagLeadType::Lead => offset,
+            };
+            let target = i64::try_from(position)
+                .ok()
+                .and_then(|position| position.checked_add(offset))
+                .and_then(|target| usize::try_from(target).ok());
+            match target.and_then(|target| entries.get(target)) {
+                Some(entry) => lag_lead_args(&entry.value).0,
+                None => default_value,
+            }
+        };
+        let mut output = Row::default();
+        output.packer().push_list_with(|packer| {
+            packer.push_list([result, original_row]);
+        });
+        output
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use mz_expr::{AggregateFunc, ColumnOrder, LagLeadType};
+        use mz_repr::{Datum, Row};
+
+        use super::{IncrementalWindow, WindowGroup};
+
+        fn order_by() -> Vec<ColumnOrder> {
+            vec![ColumnOrder {
+                column: 0,
+                desc: false,
+                nulls_last: false,
+            }]
+        }
+
+        /// Builds the aggregation input of a `ROW_NUMBER` record ordered by `key`.
+        fn row_number_value(key: i32) -> Row {
+            let mut row = Row::default();
+            row.packer().push_list_with(|packer| {
+                packer.push_list_with(|packer| packer.push_list([Datum::Int32(key)]));
+                packer.push(Datum::Int32(key));
+            });
+            row
+        }
+
+        /// Builds the aggregation input of a `LAG`/`LEAD` record ordered by `key`.
+        fn lag_lead_value(key: i32, offset: i32) -> Row {
+            let mut row = Row::default();
+            row.packer().push_list_with(|packer| {
+                packer.push_list_with(|packer| {
+                    packer.push_list([Datum::Int32(key)]);
+                    packer.push_list([Datum::Int32(key), Datum::Int32(offset), Datum::Null]);
+                });
+                packer.push(Datum::Int32(key));
+            });
+            row
+        }
+
+        #[mz_ore::test]
+        fn test_row_number_state() {
+            let mut window = IncrementalWindow::new(AggregateFunc::RowNumber {
+                order_by: order_by(),
+            });
+            let mut group = WindowGroup::default();
+
+            // Appending a record only assigns its own number.
+            for key in 0..100 {
+                let changes = window.update(&mut group, vec![(row_number_value(key), 1)]);
+                assert_eq!(changes.len(), 1);
+            }
+            assert_eq!(group.entries.len(), 100);
+
+            // Prepending a record renumbers every following record.
+            let changes = window.update(&mut group, vec![(row_number_value(-1), 2)]);
+            assert_eq!(changes.len(), 2 + 2 * 100);
+            assert_eq!(group.entries.len(), 102);
+        }
+
+        #[mz_ore::test]
+        fn test_lag_lead_state() {
+            let mut window = IncrementalWindow::new(AggregateFunc::LagLead {
+                order_by: order_by(),
+                lag_lead: LagLeadType::Lead,
+                ignore_nulls: false,
+            });
+            let mut group = WindowGroup::default();
+
+            // Appending a record sets its own output, and changes the outputs of the
+            // records at most the offset before it.
+            let offset = 3;
+            for key in 0..100 {
+                let changes = window.update(&mut group, vec![(lag_lead_value(key, offset), 1)]);
+                let expected = if key < offset { 1 } else { 1 + 2 };
+                assert_eq!(changes.len(), expected);
+            }
+            assert_eq!(group.entries.len(), 100);
+            assert_eq!(group.max_offset, 3);
+        }
+    }
+}
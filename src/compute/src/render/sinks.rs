@@ -13,7 +13,7 @@ use std::any::Any;
 use std::collections::{BTreeMap, BTreeSet};
 use std::rc::Rc;
 
-use differential_dataflow::Collection;
+use differential_dataflow::{AsCollection, Collection};
 use mz_compute_types::sinks::{ComputeSinkConnection, ComputeSinkDesc};
 use mz_expr::{permutation_for_arrangement, EvalError, MapFilterProject};
 use mz_ore::soft_assert_or_log;
@@ -24,6 +24,7 @@ use mz_storage_types::controller::CollectionMetadata;
 use mz_storage_types::errors::DataflowError;
 use mz_timely_util::operator::CollectionExt;
 use timely::container::CapacityContainerBuilder;
+use timely::dataflow::operators::{Filter, Map};
 use timely::dataflow::scopes::Child;
 use timely::dataflow::Scope;
 use timely::progress::Antichain;
@@ -32,6 +33,7 @@ use crate::compute_state::SinkToken;
 use crate::logging::compute::LogDataflowErrors;
 use crate::render::context::Context;
 use crate::render::{RenderTimestamp, StartSignal};
+use crate::typedefs::KeyBatcher;
 
 impl<'g, G, T> Context<Child<'g, G, T>>
 where
@@ -119,6 +121,27 @@ where
             err_collection = err_collection.concat(&null_errs);
         }
 
+        if sink.assert_append_only {
+            // A retraction that survives consolidation removes a row that was inserted at an
+            // earlier time. We report it as an error and keep it out of the sinked collection,
+            // so readers can rely on the collection being monotonic.
+            let name = format!("AppendOnlyConsolidation({sink_id:?})");
+            let consolidated = ok_collection.consolidate_named::<KeyBatcher<_, _, _>>(&name);
+            let retraction_errs = consolidated
+                .inner
+                .filter(|(_row, _time, diff)| *diff < 0)
+                .map(|(_row, time, diff)| {
+                    let err = DataflowError::EvalError(Box::new(EvalError::AppendOnlyViolated));
+                    (err, time, -diff)
+                })
+                .as_collection();
+            ok_collection = consolidated
+                .inner
+                .filter(|(_row, _time, diff)| *diff > 0)
+                .as_collection();
+            err_collection = err_collection.concat(&retraction_errs);
+        }
+
         let region_name = match sink.connection {
             ComputeSinkConnection::Subscribe(_) => format!("SubscribeSink({:?})", sink_id),
             ComputeSinkConnection::Persist(_) => format!("PersistSink({:?})", sink_id),
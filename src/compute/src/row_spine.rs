@@ -24,7 +24,7 @@ mod spines {
     use differential_dataflow::trace::implementations::Update;
     use differential_dataflow::trace::rc_blanket_impls::RcBuilder;
     use mz_repr::Row;
-    use mz_timely_util::containers::stack::StackWrapper;
+    use mz_timely_util::containers::stack::{ChunkSizeBatcher, StackWrapper};
     use timely::container::columnation::{Columnation, TimelyStack};
 
     use crate::row_spine::{DatumContainer, OffsetOptimized};
@@ -32,17 +32,17 @@ mod spines {
 
     pub type RowRowSpine<T, R> = Spine<
         Rc<OrdValBatch<RowRowLayout<((Row, Row), T, R)>>>,
-        KeyValBatcher<Row, Row, T, R>,
+        ChunkSizeBatcher<KeyValBatcher<Row, Row, T, R>>,
         RcBuilder<OrdValBuilder<RowRowLayout<((Row, Row), T, R)>, TimelyStack<((Row, Row), T, R)>>>,
     >;
     pub type RowValSpine<V, T, R> = Spine<
         Rc<OrdValBatch<RowValLayout<((Row, V), T, R)>>>,
-        KeyValBatcher<Row, V, T, R>,
+        ChunkSizeBatcher<KeyValBatcher<Row, V, T, R>>,
         RcBuilder<OrdValBuilder<RowValLayout<((Row, V), T, R)>, TimelyStack<((Row, V), T, R)>>>,
     >;
     pub type RowSpine<T, R> = Spine<
         Rc<OrdKeyBatch<RowLayout<((Row, ()), T, R)>>>,
-        KeyBatcher<Row, T, R>,
+        ChunkSizeBatcher<KeyBatcher<Row, T, R>>,
         RcBuilder<OrdKeyBuilder<RowLayout<((Row, ()), T, R)>, TimelyStack<((Row, ()), T, R)>>>,
     >;
 
@@ -39,7 +39,7 @@ pub(crate) mod spines {
     use differential_dataflow::trace::implementations::spine_fueled::Spine;
     use differential_dataflow::trace::implementations::{Layout, Update};
     use differential_dataflow::trace::rc_blanket_impls::RcBuilder;
-    use mz_timely_util::containers::stack::StackWrapper;
+    use mz_timely_util::containers::stack::{ChunkSizeBatcher, StackWrapper};
     use timely::container::columnation::{Columnation, TimelyStack};
     use timely::container::flatcontainer::{Containerized, FlatStack, Push, Region};
 
@@ -49,14 +49,14 @@ pub(crate) mod spines {
     /// A spine for generic keys and values.
     pub type ColValSpine<K, V, T, R> = Spine<
         Rc<OrdValBatch<MzStack<((K, V), T, R)>>>,
-        KeyValBatcher<K, V, T, R>,
+        ChunkSizeBatcher<KeyValBatcher<K, V, T, R>>,
         RcBuilder<OrdValBuilder<MzStack<((K, V), T, R)>, TimelyStack<((K, V), T, R)>>>,
     >;
 
     /// A spine for generic keys
     pub type ColKeySpine<K, T, R> = Spine<
         Rc<OrdKeyBatch<MzStack<((K, ()), T, R)>>>,
-        KeyBatcher<K, T, R>,
+        ChunkSizeBatcher<KeyBatcher<K, T, R>>,
         RcBuilder<OrdKeyBuilder<MzStack<((K, ()), T, R)>, TimelyStack<((K, ()), T, R)>>>,
     >;
 
@@ -29,7 +29,7 @@ use mz_controller_types::{is_cluster_size_v2, ClusterId, ReplicaId};
 use mz_orchestrator::NamespacedOrchestrator;
 use mz_orchestrator::{
     CpuLimit, DiskLimit, LabelSelectionLogic, LabelSelector, MemoryLimit, Service, ServiceConfig,
-    ServiceEvent, ServicePort,
+    ServiceEvent, ServicePort, ServiceProcessEvent, ServiceProcessEventKind,
 };
 use mz_ore::halt;
 use mz_ore::instrument;
@@ -312,6 +312,55 @@ pub struct ClusterEvent {
     pub time: DateTime<Utc>,
 }
 
+/// An event reported by the orchestrator for a cluster replica process, like
+/// the process being scheduled or killed because it ran out of memory.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusterReplicaEvent {
+    pub cluster_id: ClusterId,
+    pub replica_id: ReplicaId,
+    pub process_id: ProcessId,
+    pub kind: ServiceProcessEventKind,
+    pub message: Option<String>,
+    pub time: DateTime<Utc>,
+}
+
+/// Returns a stream of the process events the orchestrator reports for
+/// cluster replicas.
+pub(crate) fn replica_events_stream(
+    orchestrator: &dyn NamespacedOrchestrator,
+) -> BoxStream<'static, ClusterReplicaEvent> {
+    fn translate_event(event: ServiceProcessEvent) -> Result<ClusterReplicaEvent, anyhow::Error> {
+        let ReplicaServiceName {
+            cluster_id,
+            replica_id,
+            ..
+        } = event.service_id.parse()?;
+        Ok(ClusterReplicaEvent {
+            cluster_id,
+            replica_id,
+            process_id: event.process_id,
+            kind: event.kind,
+            message: event.message,
+            time: event.time,
+        })
+    }
+
+    let stream = orchestrator
+        .watch_service_process_events()
+        .map(|event| event.and_then(translate_event))
+        .filter_map(|event| async {
+            match event {
+                Ok(event) => Some(event),
+                Err(error) => {
+                    error!("service process event watch error: {error}");
+                    None
+                }
+            }
+        });
+
+    Box::pin(stream)
+}
+
 /// A struct describing a replica that needs to be created,
 /// using `Controller::create_replicas`.
 pub struct CreateReplicaConfig {
@@ -28,7 +28,8 @@ use std::num::NonZeroI64;
 use std::sync::Arc;
 
 use futures::future::BoxFuture;
-use futures::stream::{Peekable, StreamExt};
+use futures::stream::{BoxStream, Peekable, StreamExt};
+use futures::FutureExt;
 use mz_build_info::BuildInfo;
 use mz_cluster_client::ReplicaId;
 use mz_compute_client::controller::{
@@ -49,12 +50,14 @@ use mz_persist_client::PersistLocation;
 use mz_persist_types::Codec64;
 use mz_proto::RustType;
 use mz_repr::global_id::TransientIdGen;
-use mz_repr::{GlobalId, TimestampManipulation};
+use mz_repr::{Datum, GlobalId, Row, TimestampManipulation};
 use mz_service::secrets::SecretsReaderCliArgs;
 use mz_storage_client::client::{
     ProtoStorageCommand, ProtoStorageResponse, StorageCommand, StorageResponse,
 };
-use mz_storage_client::controller::{StorageController, StorageMetadata, StorageTxn};
+use mz_storage_client::controller::{
+    IntrospectionType, StorageController, StorageMetadata, StorageTxn,
+};
 use mz_storage_client::storage_collections::{self, StorageCollections};
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::connections::ConnectionContext;
@@ -67,6 +70,8 @@ use tokio::time::{self, Duration, Interval, MissedTickBehavior};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use uuid::Uuid;
 
+use crate::clusters::ClusterReplicaEvent;
+
 pub mod clusters;
 
 // Export this on behalf of the storage controller to provide a unified
@@ -143,6 +148,8 @@ enum Readiness<T> {
     Metrics,
     /// Frontiers are ready for recording.
     Frontiers,
+    /// Replica process events are ready for recording.
+    ReplicaEvents,
     /// An internally-generated message is ready to be returned.
     Internal(ControllerResponse<T>),
 }
@@ -176,6 +183,8 @@ pub struct Controller<T = mz_repr::Timestamp> {
     metrics_rx: Peekable<UnboundedReceiverStream<(ReplicaId, Vec<ServiceProcessMetrics>)>>,
     /// Periodic notification to record frontiers.
     frontiers_ticker: Interval,
+    /// Process events reported by the orchestrator for cluster replicas.
+    replica_events: Peekable<BoxStream<'static, ClusterReplicaEvent>>,
 
     /// The URL for Persist PubSub.
     persist_pubsub_url: String,
@@ -250,6 +259,7 @@ impl<T: ComputeControllerTimestamp> Controller<T> {
             metrics_tx: _,
             metrics_rx: _,
             frontiers_ticker: _,
+            replica_events: _,
             persist_pubsub_url: _,
             txn_wal_tables: _,
             secrets_args: _,
@@ -370,6 +380,9 @@ where
                     _ = Pin::new(&mut self.metrics_rx).peek() => {
                         self.readiness = Readiness::Metrics;
                     }
+                    _ = Pin::new(&mut self.replica_events).peek() => {
+                        self.readiness = Readiness::ReplicaEvents;
+                    }
                     _ = self.frontiers_ticker.tick() => {
                         self.readiness = Readiness::Frontiers;
                     }
@@ -540,6 +553,10 @@ where
                 self.record_frontiers().await;
                 Ok(None)
             }
+            Readiness::ReplicaEvents => {
+                self.record_replica_events().await;
+                Ok(None)
+            }
             Readiness::Internal(message) => Ok(Some(message)),
         }
     }
@@ -595,6 +612,38 @@ where
             .await;
     }
 
+    /// Records all currently available replica process events in
+    /// `mz_cluster_replica_events`.
+    async fn record_replica_events(&mut self) {
+        let mut events = Vec::new();
+        while let Some(Some(event)) = self.replica_events.next().now_or_never() {
+            events.push(event);
+        }
+
+        // In read-only mode the leader environment is responsible for
+        // recording events, and recording them here would duplicate them.
+        if self.read_only {
+            return;
+        }
+
+        let updates = events
+            .into_iter()
+            .map(|event| {
+                let row = Row::pack_slice(&[
+                    Datum::String(&event.replica_id.to_string()),
+                    Datum::UInt64(event.process_id),
+                    Datum::String(event.kind.as_kebab_case_str()),
+                    event.message.as_deref().map_or(Datum::Null, Datum::String),
+                    Datum::TimestampTz(event.time.try_into().expect("must fit")),
+                ]);
+                (row, 1)
+            })
+            .collect();
+        self.storage
+            .append_introspection_updates(IntrospectionType::ReplicaEvents, updates)
+            .await;
+    }
+
     /// Determine the "real-time recency" timestamp for all `ids`.
     ///
     /// Real-time recency is defined as the minimum value of `T` that all
@@ -671,7 +720,7 @@ where
             config.build_info,
             config.persist_location,
             config.persist_clients,
-            config.now,
+            config.now.clone(),
             Arc::clone(&txns_metrics),
             envd_epoch,
             config.metrics_registry.clone(),
@@ -690,12 +739,22 @@ where
             read_only,
             transient_id_gen,
             config.metrics_registry.clone(),
+            config.now,
         );
         let (metrics_tx, metrics_rx) = mpsc::unbounded_channel();
 
         let mut frontiers_ticker = time::interval(Duration::from_secs(1));
         frontiers_ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
+        let orchestrator = config.orchestrator.namespace("cluster");
+        // The orchestrator's event stream may end, e.g. when the orchestrator
+        // does not report any events. Chain a pending stream so that `ready`
+        // doesn't spin on a terminated stream.
+        let replica_events = clusters::replica_events_stream(&*orchestrator)
+            .chain(futures::stream::pending())
+            .boxed()
+            .peekable();
+
         let mut this = Self {
             storage: Box::new(storage_controller),
             storage_collections: collections_ctl,
@@ -707,12 +766,13 @@ where
             // based on our input. This way we avoid having the same logic in
             // two places.
             read_only: true,
-            orchestrator: config.orchestrator.namespace("cluster"),
+            orchestrator,
             readiness: Readiness::NotReady,
             metrics_tasks: BTreeMap::new(),
             metrics_tx,
             metrics_rx: UnboundedReceiverStream::new(metrics_rx).peekable(),
             frontiers_ticker,
+            replica_events,
             persist_pubsub_url: config.persist_pubsub_url,
             txn_wal_tables,
             secrets_args: config.secrets_args,
@@ -869,6 +869,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
         args.aws_connection_role_arn,
         secrets_reader,
         cloud_resource_reader,
+        None,
     );
     let orchestrator = Arc::new(TracingOrchestrator::new(orchestrator, args.tracing.clone()));
     let controller = ControllerConfig {
@@ -18,6 +18,7 @@
 
 use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::net::IpAddr;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -43,7 +44,7 @@ use mz_ore::cast::u64_to_usize;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::str::StrExt;
 use mz_repr::user::ExternalUserMetadata;
-use mz_server_core::{ConnectionHandler, ReloadingSslContext, Server};
+use mz_server_core::{ClientCertIdentity, ConnectionHandler, ReloadingSslContext, Server};
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql::session::user::{HTTP_DEFAULT_USER, SUPPORT_USER_NAME, SYSTEM_USER_NAME};
 use mz_sql::session::vars::{
@@ -140,6 +141,7 @@ impl HttpServer {
         let tls_mode = tls.as_ref().map(|tls| tls.mode).unwrap_or(TlsMode::Disable);
         let frontegg = Arc::new(frontegg);
         let base_frontegg = Arc::clone(&frontegg);
+        let base_adapter_client = adapter_client.clone();
         let (adapter_client_tx, adapter_client_rx) = oneshot::channel();
         adapter_client_tx
             .send(adapter_client.clone())
@@ -150,7 +152,17 @@ impl HttpServer {
         let base_router = base_router(BaseRouterConfig { profiling: false })
             .layer(middleware::from_fn(move |req, next| {
                 let base_frontegg = Arc::clone(&base_frontegg);
-                async move { http_auth(req, next, tls_mode, base_frontegg.as_ref().as_ref()).await }
+                let base_adapter_client = base_adapter_client.clone();
+                async move {
+                    http_auth(
+                        req,
+                        next,
+                        tls_mode,
+                        base_frontegg.as_ref().as_ref(),
+                        &base_adapter_client,
+                    )
+                    .await
+                }
             }))
             .layer(Extension(adapter_client_rx.clone()))
             .layer(Extension(Arc::clone(&active_connection_count)))
@@ -215,7 +227,8 @@ impl Server for HttpServer {
     fn handle_connection(&self, conn: TcpStream) -> ConnectionHandler {
         let router = self.router.clone();
         let tls_config = self.tls.clone();
-        Box::pin(async {
+        let client_ip = ClientIp(conn.peer_addr().ok().map(|addr| addr.ip()));
+        Box::pin(async move {
             let (conn, conn_protocol) = match tls_config {
                 Some(tls_config) => {
                     let mut ssl_stream =
@@ -224,11 +237,17 @@ impl Server for HttpServer {
                         let _ = ssl_stream.get_mut().shutdown().await;
                         return Err(e.into());
                     }
-                    (MaybeHttpsStream::Https(ssl_stream), ConnProtocol::Https)
+                    let client_cert = ClientCertIdentity::from_ssl(ssl_stream.ssl());
+                    (
+                        MaybeHttpsStream::Https(ssl_stream),
+                        ConnProtocol::Https { client_cert },
+                    )
                 }
                 _ => (MaybeHttpsStream::Http(conn), ConnProtocol::Http),
             };
-            let svc = router.layer(Extension(conn_protocol));
+            let svc = router
+                .layer(Extension(conn_protocol))
+                .layer(Extension(client_ip));
             let http = hyper::server::conn::Http::new();
             http.serve_connection(conn, svc)
                 .with_upgrades()
@@ -538,12 +557,21 @@ type Delayed<T> = Shared<oneshot::Receiver<T>>;
 
 type SharedConnectionCounter = Arc<Mutex<ConnectionCounter>>;
 
+/// The protocol of an HTTP connection.
 #[derive(Clone)]
-enum ConnProtocol {
+pub enum ConnProtocol {
     Http,
-    Https,
+    Https {
+        /// The identity in the client's verified TLS certificate, if the
+        /// client presented one.
+        client_cert: Option<ClientCertIdentity>,
+    },
 }
 
+/// The IP address of the client of an HTTP connection, if known.
+#[derive(Clone, Copy, Debug)]
+pub struct ClientIp(pub Option<IpAddr>);
+
 #[derive(Clone, Debug)]
 pub struct AuthedUser {
     name: String,
@@ -559,6 +587,7 @@ impl AuthedClient {
     async fn new<F>(
         adapter_client: &Client,
         user: AuthedUser,
+        client_ip: Option<IpAddr>,
         active_connection_count: SharedConnectionCounter,
         session_config: F,
         options: BTreeMap<String, String>,
@@ -571,6 +600,7 @@ impl AuthedClient {
             conn_id,
             user: user.name,
             external_metadata_rx: user.external_metadata_rx,
+            client_ip,
         });
         let drop_connection =
             DropConnection::new_connection(session.user(), active_connection_count)?;
@@ -625,6 +655,7 @@ where
             (StatusCode::INTERNAL_SERVER_ERROR, "adapter client missing").into_response()
         })?;
         let active_connection_count = req.extensions.get::<SharedConnectionCounter>().unwrap();
+        let client_ip = req.extensions.get::<ClientIp>().and_then(|ip| ip.0);
 
         let options = if params.options.is_empty() {
             // It's possible 'options' simply wasn't provided, we don't want that to
@@ -645,6 +676,7 @@ where
         let client = AuthedClient::new(
             &adapter_client,
             user.clone(),
+            client_ip,
             Arc::clone(active_connection_count),
             |session| {
                 session
@@ -657,7 +689,9 @@ where
         .await
         .map_err(|e| {
             let status = match e {
-                AdapterError::UserSessionsDisallowed => StatusCode::FORBIDDEN,
+                AdapterError::UserSessionsDisallowed | AdapterError::NetworkPolicyDenied(_) => {
+                    StatusCode::FORBIDDEN
+                }
                 _ => StatusCode::INTERNAL_SERVER_ERROR,
             };
             (status, Json(SqlError::from(e))).into_response()
@@ -681,6 +715,8 @@ enum AuthError {
     MismatchedUser(String),
     #[error("unexpected credentials")]
     UnexpectedCredentials,
+    #[error("{0}")]
+    ClientCertificate(AdapterError),
 }
 
 impl IntoResponse for AuthError {
@@ -706,15 +742,23 @@ async fn http_auth<B>(
     next: Next<B>,
     tls_mode: TlsMode,
     frontegg: Option<&FronteggAuthentication>,
+    adapter_client: &mz_adapter::Client,
 ) -> impl IntoResponse {
-    // First, extract the username from the certificate, validating that the
+    // First, extract the identity from the certificate, validating that the
     // connection matches the TLS configuration along the way.
     let conn_protocol = req.extensions().get::<ConnProtocol>().unwrap();
-    match (tls_mode, &conn_protocol) {
-        (TlsMode::Disable, ConnProtocol::Http) => {}
+    let client_cert = match (tls_mode, &conn_protocol) {
+        (TlsMode::Disable, ConnProtocol::Http) => None,
         (TlsMode::Disable, ConnProtocol::Https { .. }) => unreachable!(),
         (TlsMode::Require, ConnProtocol::Http) => return Err(AuthError::HttpsRequired),
-        (TlsMode::Require, ConnProtocol::Https { .. }) => {}
+        (TlsMode::Require, ConnProtocol::Https { client_cert }) => client_cert.clone(),
+    };
+    // A verified client certificate authenticates the client in place of any
+    // other credentials.
+    if let Some(identity) = client_cert {
+        let user = cert_auth(adapter_client, identity).await?;
+        req.extensions_mut().insert(user);
+        return Ok(next.run(req).await);
     }
     let creds = match frontegg {
         // If no Frontegg authentication, use the default HTTP user.
@@ -752,8 +796,16 @@ async fn init_ws(
         active_connection_count,
     }: &WsState,
     existing_user: Option<AuthedUser>,
+    client_cert: Option<ClientCertIdentity>,
+    client_ip: Option<IpAddr>,
     ws: &mut WebSocket,
 ) -> Result<AuthedClient, anyhow::Error> {
+    // A verified client certificate authenticates the client in place of any
+    // other credentials.
+    let existing_user = match client_cert {
+        Some(identity) => Some(cert_auth(&adapter_client_rx.clone().await?, identity).await?),
+        None => existing_user,
+    };
     // TODO: Add a timeout here to prevent resource leaks by clients that
     // connect then never send a message.
     let init_msg = ws.recv().await.ok_or_else(|| anyhow::anyhow!("closed"))??;
@@ -774,6 +826,10 @@ async fn init_ws(
         }
     };
     let (user, options) = match (frontegg.as_ref(), existing_user, ws_auth) {
+        // An already authenticated user only specifies options.
+        (_, Some(existing_user), WebSocketAuth::OptionsOnly { options }) => {
+            (existing_user, options)
+        }
         (Some(frontegg), None, ws_auth) => {
             let (creds, options) = match ws_auth {
                 WebSocketAuth::Basic {
@@ -806,17 +862,11 @@ async fn init_ws(
                 options,
             },
         ) => (auth(None, Credentials::User(user)).await?, options),
-        // No frontegg, specified existing user, we only accept options only.
-        (None, Some(existing_user), WebSocketAuth::OptionsOnly { options }) => {
-            (existing_user, options)
-        }
-        // No frontegg, specified existing user, we do not expect basic or bearer auth.
-        (None, Some(_), WebSocketAuth::Basic { .. } | WebSocketAuth::Bearer { .. }) => {
-            warn!("Unexpected bearer or basic auth provided when using user header");
+        // Specified existing user, we do not expect basic or bearer auth.
+        (_, Some(_), WebSocketAuth::Basic { .. } | WebSocketAuth::Bearer { .. }) => {
+            warn!("Unexpected bearer or basic auth provided with an authenticated user");
             anyhow::bail!("unexpected")
         }
-        // Specifying both frontegg and an existing user should not be possible.
-        (Some(_), Some(_), _) => anyhow::bail!("unexpected"),
         // No frontegg, no existing user, and no passed username.
         (None, None, WebSocketAuth::Bearer { .. } | WebSocketAuth::OptionsOnly { .. }) => {
             warn!("Unexpected auth type when not using frontegg or user header");
@@ -827,6 +877,7 @@ async fn init_ws(
     let client = AuthedClient::new(
         &adapter_client_rx.clone().await?,
         user,
+        client_ip,
         Arc::clone(active_connection_count),
         |_session| (),
         options,
@@ -836,6 +887,24 @@ async fn init_ws(
     Ok(client)
 }
 
+/// Authenticates a client by the identity in its verified TLS certificate.
+async fn cert_auth(
+    adapter_client: &mz_adapter::Client,
+    identity: ClientCertIdentity,
+) -> Result<AuthedUser, AuthError> {
+    let name = adapter_client
+        .map_client_certificate(identity.common_name, identity.subject_alt_names)
+        .await
+        .map_err(AuthError::ClientCertificate)?;
+    if mz_adapter::catalog::is_reserved_role_name(name.as_str()) {
+        return Err(AuthError::InvalidLogin(name));
+    }
+    Ok(AuthedUser {
+        name,
+        external_metadata_rx: None,
+    })
+}
+
 enum Credentials {
     User(String),
     DefaultUser,
@@ -916,6 +985,10 @@ fn base_router(BaseRouterConfig { profiling }: BaseRouterConfig) -> Router {
             routing::get(move || async move { root::handle_home(profiling).await }),
         )
         .route("/api/sql", routing::post(sql::handle_sql))
+        .route(
+            "/api/experimental/sql/arrow",
+            routing::post(sql::handle_sql_arrow),
+        )
         .route("/memory", routing::get(memory::handle_memory))
         .route(
             "/hierarchical-memory",
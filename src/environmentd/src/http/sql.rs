@@ -9,18 +9,20 @@
 
 use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::net::IpAddr;
 use std::pin::pin;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use axum::body::StreamBody;
 use axum::extract::ws::{CloseFrame, Message, WebSocket};
 use axum::extract::{State, WebSocketUpgrade};
-use axum::response::IntoResponse;
+use axum::response::{IntoResponse, Response};
 use axum::{Extension, Json};
 use futures::future::BoxFuture;
-use futures::Future;
+use futures::{Future, StreamExt};
 use http::StatusCode;
 use itertools::izip;
 use mz_adapter::client::RecordFirstRowStream;
@@ -30,24 +32,32 @@ use mz_adapter::{
     verify_datum_desc, AdapterError, AdapterNotice, ExecuteContextExtra, ExecuteResponse,
     ExecuteResponseKind, PeekResponseUnary, SessionClient,
 };
+use mz_arrow_util::ipc::{IpcStreamEncoder, ARROW_STREAM_CONTENT_TYPE};
 use mz_interchange::encode::TypedDatum;
 use mz_interchange::json::{JsonNumberPolicy, ToJson};
 use mz_ore::cast::CastFrom;
 use mz_ore::result::ResultExt;
-use mz_repr::{Datum, RelationDesc, RowArena, RowIterator};
+use mz_repr::{Datum, RelationDesc, Row, RowArena, RowIterator};
+use mz_server_core::ClientCertIdentity;
 use mz_sql::ast::display::AstDisplay;
-use mz_sql::ast::{CopyDirection, CopyStatement, CopyTarget, Raw, Statement, StatementKind};
+use mz_sql::ast::{
+    AsOf, CopyDirection, CopyStatement, CopyTarget, Expr, Raw, Statement, StatementKind,
+    SubscribeOption, SubscribeOptionName, Value, WithOptionValue,
+};
 use mz_sql::parse::StatementParseResult;
 use mz_sql::plan::Plan;
 use mz_sql::session::metadata::SessionMetadata;
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
 use tokio::{select, time};
 use tokio_postgres::error::SqlState;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::debug;
 use tungstenite::protocol::frame::coding::CloseCode;
 
-use crate::http::{init_ws, AuthedClient, AuthedUser, WsState, MAX_REQUEST_SIZE};
+use crate::http::{
+    init_ws, AuthedClient, AuthedUser, ClientIp, ConnProtocol, WsState, MAX_REQUEST_SIZE,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -98,30 +108,115 @@ impl Error {
     }
 }
 
-pub async fn handle_sql(
-    mut client: AuthedClient,
-    Json(request): Json<SqlRequest>,
-) -> impl IntoResponse {
-    let mut res = SqlResponse {
-        results: Vec::new(),
-    };
+pub async fn handle_sql(mut client: AuthedClient, Json(request): Json<SqlRequest>) -> Response {
     // Don't need to worry about timeouts or resetting cancel here because there is always exactly 1
     // request.
-    match execute_request(&mut client, request, &mut res).await {
-        Ok(()) => Ok(Json(res)),
-        Err(e) => Err((StatusCode::BAD_REQUEST, e.to_string())),
+    let res = match request {
+        SqlRequest::Transaction { .. } => {
+            let mut res = TransactionSender {
+                results: Vec::new(),
+                last_result_at: Instant::now(),
+            };
+            execute_request(&mut client, request, &mut res)
+                .await
+                .map(|()| Json(res.finish()).into_response())
+        }
+        request => {
+            let mut res = SqlResponse {
+                results: Vec::new(),
+            };
+            execute_request(&mut client, request, &mut res)
+                .await
+                .map(|()| Json(res).into_response())
+        }
+    };
+    match res {
+        Ok(res) => res,
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
     }
 }
 
 pub async fn handle_sql_ws(
     State(state): State<WsState>,
     existing_user: Option<Extension<AuthedUser>>,
+    conn_protocol: Option<Extension<ConnProtocol>>,
+    client_ip: Option<Extension<ClientIp>>,
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
     // An upstream middleware may have already provided the user for us
     let user = existing_user.and_then(|Extension(user)| Some(user));
+    let client_cert = match conn_protocol {
+        Some(Extension(ConnProtocol::Https { client_cert })) => client_cert,
+        Some(Extension(ConnProtocol::Http)) | None => None,
+    };
+    let client_ip = client_ip.and_then(|Extension(ClientIp(ip))| ip);
     ws.max_message_size(MAX_REQUEST_SIZE)
-        .on_upgrade(|ws| async move { run_ws(&state, user, ws).await })
+        .on_upgrade(move |ws| async move { run_ws(&state, user, client_cert, client_ip, ws).await })
+}
+
+/// A request to execute a query and stream its results in the Arrow IPC
+/// format.
+#[derive(Debug, Deserialize)]
+pub struct ArrowRequest {
+    /// A query string containing one `SELECT` or `SUBSCRIBE` statement.
+    query: String,
+    /// Optional parameters for the query.
+    #[serde(default)]
+    params: Vec<Option<String>>,
+}
+
+pub async fn handle_sql_arrow(
+    mut client: AuthedClient,
+    Json(request): Json<ArrowRequest>,
+) -> Response {
+    // Only queries have results to encode. Parse errors are left for
+    // execution to report.
+    if let Ok(stmts) = mz_sql::parse::parse(&request.query) {
+        if let [stmt] = stmts.as_slice() {
+            if !matches!(stmt.ast, Statement::Select(_) | Statement::Subscribe(_)) {
+                let err = Error::Unsupported(stmt.ast.to_ast_string());
+                return (StatusCode::BAD_REQUEST, err.to_string()).into_response();
+            }
+        }
+    }
+
+    let request = SqlRequest::Extended {
+        queries: vec![ExtendedRequest {
+            query: request.query,
+            params: request.params,
+            resume_token: None,
+        }],
+    };
+    let (tx, mut rx) = mpsc::channel(ArrowResponse::CHANNEL_CAPACITY);
+    mz_ore::task::spawn(|| "http_sql_arrow", async move {
+        let mut sender = ArrowResponse { tx };
+        if let Err(err) = execute_request(&mut client, request, &mut sender).await {
+            let _ = sender.tx.send(Err(err)).await;
+        }
+    });
+
+    // Wait for the start of the stream, so that errors that occur before any
+    // results are produced, which are the common case, get an error status.
+    match rx.recv().await {
+        Some(Ok(first)) => {
+            // An error after the stream has started aborts the response, as
+            // the status has already been sent.
+            let chunks = futures::stream::once(async { Ok(first) })
+                .chain(tokio_stream::wrappers::ReceiverStream::new(rx))
+                .map(|chunk| chunk.map_err(|e| e.to_string()));
+            (
+                [(http::header::CONTENT_TYPE, ARROW_STREAM_CONTENT_TYPE)],
+                StreamBody::new(chunks),
+            )
+                .into_response()
+        }
+        Some(Err(err)) => (StatusCode::BAD_REQUEST, err.to_string()).into_response(),
+        None => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "query ended without producing a result",
+        )
+            .into_response(),
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -144,8 +239,14 @@ pub enum WebSocketAuth {
     },
 }
 
-async fn run_ws(state: &WsState, user: Option<AuthedUser>, mut ws: WebSocket) {
-    let mut client = match init_ws(state, user, &mut ws).await {
+async fn run_ws(
+    state: &WsState,
+    user: Option<AuthedUser>,
+    client_cert: Option<ClientCertIdentity>,
+    client_ip: Option<IpAddr>,
+    mut ws: WebSocket,
+) {
+    let mut client = match init_ws(state, user, client_cert, client_ip, &mut ws).await {
         Ok(client) => client,
         Err(e) => {
             // We omit most detail from the error message we send to the client, to
@@ -301,15 +402,9 @@ async fn forward_notices(
     ws: &mut WebSocket,
     notices: impl IntoIterator<Item = AdapterNotice>,
 ) -> Result<(), Error> {
-    let ws_notices = notices.into_iter().map(|notice| {
-        WebSocketResponse::Notice(Notice {
-            message: notice.to_string(),
-            code: notice.code().code().to_string(),
-            severity: notice.severity().as_str().to_lowercase(),
-            detail: notice.detail(),
-            hint: notice.hint(),
-        })
-    });
+    let ws_notices = notices
+        .into_iter()
+        .map(|notice| WebSocketResponse::Notice(Notice::from(notice)));
 
     for notice in ws_notices {
         send_ws_response(ws, notice).await?;
@@ -327,12 +422,22 @@ pub enum SqlRequest {
         /// A query string containing zero or more queries delimited by
         /// semicolons.
         query: String,
+        /// An optional resume token with which to continue the `SUBSCRIBE`s
+        /// in `query`. See [`WebSocketResponse::ResumeToken`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        resume_token: Option<String>,
     },
     /// An extended query request.
     Extended {
         /// Queries to execute using the extended protocol.
         queries: Vec<ExtendedRequest>,
     },
+    /// A transactional batch request.
+    Transaction {
+        /// Queries to execute using the extended protocol, atomically in a
+        /// single transaction.
+        transaction: Vec<ExtendedRequest>,
+    },
 }
 
 /// An request to execute a SQL query using the extended protocol.
@@ -343,6 +448,10 @@ pub struct ExtendedRequest {
     /// Optional parameters for the query.
     #[serde(default)]
     params: Vec<Option<String>>,
+    /// An optional resume token with which to continue the `SUBSCRIBE` in
+    /// `query`. See [`WebSocketResponse::ResumeToken`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resume_token: Option<String>,
 }
 
 /// The response to a `SqlRequest`.
@@ -352,8 +461,54 @@ pub struct SqlResponse {
     results: Vec<SqlResult>,
 }
 
+/// The response to a transactional batch [`SqlRequest`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionResponse {
+    /// The results for each query executed. If a query fails, its error is
+    /// the last result.
+    results: Vec<TransactionResult>,
+    /// Whether the transaction committed.
+    committed: bool,
+}
+
+/// The result of a single query in a [`TransactionResponse`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionResult {
+    #[serde(flatten)]
+    result: SqlResult,
+    /// The time taken to execute the query, in milliseconds.
+    duration_ms: f64,
+}
+
+/// Accumulates the results of a transactional batch, timing each query.
+struct TransactionSender {
+    results: Vec<TransactionResult>,
+    /// When the previous query completed. Queries in a batch execute back to
+    /// back, so this is when the next query started.
+    last_result_at: Instant,
+}
+
+impl TransactionSender {
+    fn finish(self) -> TransactionResponse {
+        let committed = self
+            .results
+            .iter()
+            .all(|res| !matches!(res.result, SqlResult::Err { .. }));
+        TransactionResponse {
+            results: self.results,
+            committed,
+        }
+    }
+}
+
 enum StatementResult {
     SqlResult(SqlResult),
+    /// The unencoded rows of a query, produced instead of [`SqlResult::Rows`]
+    /// for senders that set [`ResultSender::WANTS_RAW_ROWS`].
+    Rows {
+        desc: RelationDesc,
+        rows: Box<dyn RowIterator + Send + Sync>,
+    },
     Subscribe {
         desc: RelationDesc,
         tag: String,
@@ -503,6 +658,10 @@ pub enum WebSocketResponse {
     Error(SqlError),
     ParameterStatus(ParameterStatus),
     BackendKeyData(BackendKeyData),
+    /// Sent after a `SUBSCRIBE WITH (PROGRESS)` reports progress. All updates
+    /// at times before the token have been sent, and passing it back in a
+    /// request continues the `SUBSCRIBE` from there.
+    ResumeToken(String),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -514,6 +673,11 @@ pub struct Notice {
     pub detail: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hint: Option<String>,
+    /// A stable, machine-readable name for the kind of notice.
+    pub name: String,
+    /// Machine-readable details about the notice, keyed by field name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, serde_json::Value>,
 }
 
 impl Notice {
@@ -522,6 +686,24 @@ impl Notice {
     }
 }
 
+impl From<AdapterNotice> for Notice {
+    fn from(notice: AdapterNotice) -> Notice {
+        Notice {
+            message: notice.to_string(),
+            code: notice.code().code().to_string(),
+            severity: notice.severity().as_str().to_lowercase(),
+            detail: notice.detail(),
+            hint: notice.hint(),
+            name: notice.name().to_string(),
+            fields: notice
+                .fields()
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Description {
     pub columns: Vec<Column>,
@@ -577,6 +759,9 @@ pub struct CommandStarting {
 #[async_trait]
 trait ResultSender: Send {
     const SUPPORTS_STREAMING_NOTICES: bool = false;
+    /// Whether the rows of queries should be passed to the sender as
+    /// [`StatementResult::Rows`], rather than encoded as JSON.
+    const WANTS_RAW_ROWS: bool = false;
 
     /// Adds a result to the client. The first component of the return value is
     /// Err if sending to the client
@@ -631,6 +816,7 @@ impl ResultSender for SqlResponse {
                 let res = if is_err { Err(()) } else { Ok(()) };
                 (res, None)
             }
+            StatementResult::Rows { .. } => unreachable!("raw rows not requested"),
             StatementResult::Subscribe { ctx_extra, .. } => {
                 let message = "SUBSCRIBE only supported over websocket";
                 self.results.push(SqlResult::Err {
@@ -681,6 +867,7 @@ impl ResultSender for WebSocket {
             StatementResult::SqlResult(SqlResult::Err { .. }) => (false, false),
             StatementResult::SqlResult(SqlResult::Ok { .. }) => (false, false),
             StatementResult::SqlResult(SqlResult::Rows { .. }) => (true, false),
+            StatementResult::Rows { .. } => unreachable!("raw rows not requested"),
             StatementResult::Subscribe { .. } => (true, true),
         };
         if let Err(e) = send_ws_response(
@@ -727,6 +914,7 @@ impl ResultSender for WebSocket {
                 msgs.extend(notices.into_iter().map(WebSocketResponse::Notice));
                 (true, msgs, None)
             }
+            StatementResult::Rows { .. } => unreachable!("raw rows not requested"),
             StatementResult::Subscribe {
                 ref desc,
                 tag,
@@ -744,6 +932,11 @@ impl ResultSender for WebSocket {
 
                 let mut datum_vec = mz_repr::DatumVec::new();
                 let mut rows_returned = 0;
+                // Progress rows carry the new frontier in their timestamp
+                // column, which we forward as a resume token.
+                let progressed_col = desc
+                    .iter_names()
+                    .position(|name| name.as_str() == "mz_progressed");
                 loop {
                     let res = match await_rows(self, client, rx.recv()).await {
                         Ok(res) => res,
@@ -771,8 +964,15 @@ impl ResultSender for WebSocket {
                             }
 
                             rows_returned += rows.count();
+                            let mut resume_token = None;
                             while let Some(row) = rows.next() {
                                 let datums = datum_vec.borrow_with(row);
+                                if let Some(col) = progressed_col {
+                                    if datums[col] == Datum::True {
+                                        resume_token =
+                                            Some(datums[0].unwrap_numeric().0.to_string());
+                                    }
+                                }
                                 let types = &desc.typ().column_types;
                                 if let Err(e) = send_ws_response(
                                     self,
@@ -797,6 +997,19 @@ impl ResultSender for WebSocket {
                                     );
                                 }
                             }
+                            if let Some(resume_token) = resume_token {
+                                if let Err(e) = send_ws_response(
+                                    self,
+                                    WebSocketResponse::ResumeToken(resume_token),
+                                )
+                                .await
+                                {
+                                    return (
+                                        Err(e),
+                                        Some((StatementEndedExecutionReason::Canceled, ctx_extra)),
+                                    );
+                                }
+                            }
                         }
                         Some(PeekResponseUnary::Error(error)) => {
                             break (
@@ -870,6 +1083,265 @@ impl ResultSender for WebSocket {
     }
 }
 
+/// Streams the rows of a single query to an HTTP response as an Arrow IPC
+/// stream, in chunks of encoded bytes.
+struct ArrowResponse {
+    tx: mpsc::Sender<Result<Vec<u8>, Error>>,
+}
+
+impl ArrowResponse {
+    /// The number of chunks buffered for the response body.
+    const CHANNEL_CAPACITY: usize = 4;
+    /// The size of the rows, in bytes, at which a record batch is encoded and
+    /// sent to the client.
+    const BATCH_SIZE_BYTES: usize = 1 << 20;
+
+    /// Sends a chunk of the response body, erroring if the client has gone
+    /// away.
+    async fn send(&mut self, chunk: Result<Vec<u8>, Error>) -> Result<(), Error> {
+        self.tx
+            .send(chunk)
+            .await
+            .map_err(|_| Error::Unstructured(anyhow!("client disconnected")))
+    }
+
+    /// Encodes `rows`, sending a chunk each time a batch fills up. Returns the
+    /// number of rows encoded.
+    async fn send_rows(
+        &mut self,
+        encoder: &mut IpcStreamEncoder,
+        desc: &RelationDesc,
+        rows: &mut (dyn RowIterator + Send + Sync),
+    ) -> Result<Result<usize, String>, Error> {
+        if let Err(err) = verify_datum_desc(desc, rows) {
+            return Ok(Err(err.to_string()));
+        }
+        let mut row = Row::default();
+        let mut count = 0;
+        while let Some(next) = rows.next() {
+            // The encoder takes owned rows, so copy each into a reused row.
+            row.packer().extend(next.iter());
+            if let Err(err) = encoder.add_row(&row) {
+                return Ok(Err(err.to_string()));
+            }
+            count += 1;
+            if encoder.pending_bytes() >= Self::BATCH_SIZE_BYTES {
+                if let Err(err) = encoder.flush() {
+                    return Ok(Err(err.to_string()));
+                }
+                self.send(Ok(encoder.take_bytes())).await?;
+            }
+        }
+        Ok(Ok(count))
+    }
+}
+
+#[async_trait]
+impl ResultSender for ArrowResponse {
+    const WANTS_RAW_ROWS: bool = true;
+
+    async fn add_result(
+        &mut self,
+        client: &mut SessionClient,
+        res: StatementResult,
+    ) -> (
+        Result<Result<(), ()>, Error>,
+        Option<(StatementEndedExecutionReason, ExecuteContextExtra)>,
+    ) {
+        match res {
+            StatementResult::SqlResult(SqlResult::Err { error, .. }) => {
+                let err = Error::Unstructured(anyhow!(error.message));
+                (self.send(Err(err)).await.map(|()| Err(())), None)
+            }
+            StatementResult::SqlResult(SqlResult::Ok { ok, .. }) => {
+                let err = Error::Unsupported(format!("{ok} statements"));
+                (self.send(Err(err)).await.map(|()| Err(())), None)
+            }
+            StatementResult::SqlResult(SqlResult::Rows { .. }) => {
+                unreachable!("raw rows requested")
+            }
+            StatementResult::Rows { desc, mut rows } => {
+                let mut encoder = match IpcStreamEncoder::new(&desc) {
+                    Ok(encoder) => encoder,
+                    Err(err) => {
+                        let err = Error::Unstructured(err);
+                        return (self.send(Err(err)).await.map(|()| Err(())), None);
+                    }
+                };
+                let res = match self.send_rows(&mut encoder, &desc, &mut *rows).await {
+                    Ok(Ok(_)) => encoder.finish().map_err(Error::Unstructured),
+                    Ok(Err(error)) => Err(Error::Unstructured(anyhow!(error))),
+                    Err(e) => return (Err(e), None),
+                };
+                let is_err = res.is_err();
+                let sent = self.send(res).await;
+                (sent.map(|()| if is_err { Err(()) } else { Ok(()) }), None)
+            }
+            StatementResult::Subscribe {
+                desc,
+                mut rx,
+                ctx_extra,
+                ..
+            } => {
+                let mut encoder = match IpcStreamEncoder::new(&desc) {
+                    Ok(encoder) => encoder,
+                    Err(err) => {
+                        let error = err.to_string();
+                        return (
+                            self.send(Err(Error::Unstructured(err)))
+                                .await
+                                .map(|()| Err(())),
+                            Some((StatementEndedExecutionReason::Errored { error }, ctx_extra)),
+                        );
+                    }
+                };
+                // Send the schema right away, as the first batch of a
+                // SUBSCRIBE may not arrive for a while.
+                if let Err(e) = self.send(Ok(encoder.take_bytes())).await {
+                    return (
+                        Err(e),
+                        Some((StatementEndedExecutionReason::Canceled, ctx_extra)),
+                    );
+                }
+                let mut rows_returned = 0;
+                let (res, reason) = loop {
+                    let res = match await_rows(self, client, rx.recv()).await {
+                        Ok(res) => res,
+                        Err(e) => {
+                            // We consider the remote breaking the connection to be a cancellation,
+                            // matching the behavior for pgwire
+                            return (
+                                Err(e),
+                                Some((StatementEndedExecutionReason::Canceled, ctx_extra)),
+                            );
+                        }
+                    };
+                    let error = match res {
+                        Some(PeekResponseUnary::Rows(mut rows)) => {
+                            match self.send_rows(&mut encoder, &desc, &mut *rows).await {
+                                Ok(Ok(count)) => rows_returned += count,
+                                Ok(Err(error)) => {
+                                    break (
+                                        Err(error.clone()),
+                                        StatementEndedExecutionReason::Errored { error },
+                                    )
+                                }
+                                Err(e) => {
+                                    return (
+                                        Err(e),
+                                        Some((StatementEndedExecutionReason::Canceled, ctx_extra)),
+                                    );
+                                }
+                            }
+                            // Each update from the SUBSCRIBE is forwarded as
+                            // soon as it arrives.
+                            encoder.flush().err()
+                        }
+                        Some(PeekResponseUnary::Error(error)) => {
+                            break (
+                                Err(error.clone()),
+                                StatementEndedExecutionReason::Errored { error },
+                            );
+                        }
+                        Some(PeekResponseUnary::Canceled) => {
+                            break (
+                                Err(AdapterError::Canceled.to_string()),
+                                StatementEndedExecutionReason::Canceled,
+                            );
+                        }
+                        None => {
+                            break (
+                                Ok(()),
+                                StatementEndedExecutionReason::Success {
+                                    rows_returned: Some(u64::cast_from(rows_returned)),
+                                    execution_strategy: Some(StatementExecutionStrategy::Standard),
+                                },
+                            );
+                        }
+                    };
+                    if let Some(err) = error {
+                        let error = err.to_string();
+                        break (
+                            Err(error.clone()),
+                            StatementEndedExecutionReason::Errored { error },
+                        );
+                    }
+                    if let Err(e) = self.send(Ok(encoder.take_bytes())).await {
+                        return (
+                            Err(e),
+                            Some((StatementEndedExecutionReason::Canceled, ctx_extra)),
+                        );
+                    }
+                };
+                let (chunk, is_err) = match res {
+                    Ok(()) => (encoder.finish().map_err(Error::Unstructured), false),
+                    Err(error) => (Err(Error::Unstructured(anyhow!(error))), true),
+                };
+                let sent = self.send(chunk).await;
+                (
+                    sent.map(|()| if is_err { Err(()) } else { Ok(()) }),
+                    Some((reason, ctx_extra)),
+                )
+            }
+        }
+    }
+
+    fn connection_error(&mut self) -> BoxFuture<Error> {
+        Box::pin(async {
+            self.tx.closed().await;
+            Error::Unstructured(anyhow!("client disconnected"))
+        })
+    }
+
+    fn allow_subscribe(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl ResultSender for TransactionSender {
+    async fn add_result(
+        &mut self,
+        _client: &mut SessionClient,
+        res: StatementResult,
+    ) -> (
+        Result<Result<(), ()>, Error>,
+        Option<(StatementEndedExecutionReason, ExecuteContextExtra)>,
+    ) {
+        let (result, stmt_logging) = match res {
+            StatementResult::SqlResult(res) => (res, None),
+            StatementResult::Rows { .. } => unreachable!("raw rows not requested"),
+            StatementResult::Subscribe { ctx_extra, .. } => {
+                let error = Error::SubscribeOnlyOverWs;
+                let reason = StatementEndedExecutionReason::Errored {
+                    error: error.to_string(),
+                };
+                let result = SqlResult::Err {
+                    error: error.into(),
+                    notices: Vec::new(),
+                };
+                (result, Some((reason, ctx_extra)))
+            }
+        };
+        let is_err = matches!(result, SqlResult::Err { .. });
+        let now = Instant::now();
+        self.results.push(TransactionResult {
+            result,
+            duration_ms: (now - self.last_result_at).as_secs_f64() * 1000.0,
+        });
+        self.last_result_at = now;
+        (Ok(if is_err { Err(()) } else { Ok(()) }), stmt_logging)
+    }
+
+    fn connection_error(&mut self) -> BoxFuture<Error> {
+        Box::pin(futures::future::pending())
+    }
+
+    fn allow_subscribe(&self) -> bool {
+        false
+    }
+}
+
 async fn await_rows<S, F, R>(sender: &mut S, client: &mut SessionClient, f: F) -> Result<R, Error>
 where
     S: ResultSender,
@@ -907,10 +1379,6 @@ async fn execute_stmt_group<S: ResultSender>(
 ) -> Result<Result<(), ()>, Error> {
     let num_stmts = stmt_group.len();
     for (stmt, sql, params) in stmt_group {
-        assert!(num_stmts <= 1 || params.is_empty(),
-            "statement groups contain more than 1 statement iff Simple request, which does not support parameters"
-        );
-
         let is_aborted_txn = matches!(client.session().transaction(), TransactionStatus::Failed(_));
         if is_aborted_txn && !is_txn_exit_stmt(&stmt) {
             let err = SqlResult::err(client, Error::AbortedTransaction);
@@ -1024,20 +1492,72 @@ async fn execute_request<S: ResultSender>(
         result.map_err(|e| AdapterError::from(e).into())
     }
 
+    /// Rewrites the `SUBSCRIBE` statements in `stmts` to continue after the
+    /// frontier in `resume_token`, i.e. to only emit updates at or after it.
+    fn apply_resume_token(
+        stmts: &mut [(Statement<Raw>, String, Vec<Option<String>>)],
+        resume_token: &str,
+    ) -> Result<(), Error> {
+        let frontier: mz_repr::Timestamp = resume_token
+            .parse()
+            .map_err(|_| Error::Unstructured(anyhow!("invalid resume token: {resume_token}")))?;
+        let mut found = false;
+        for (stmt, sql, _params) in stmts {
+            let Statement::Subscribe(subscribe) = stmt else {
+                continue;
+            };
+            if subscribe.as_of.is_some() {
+                return Err(Error::Unstructured(anyhow!(
+                    "a resume token cannot be combined with AS OF"
+                )));
+            }
+            // SUBSCRIBE ... AS OF t WITH (SNAPSHOT = false) emits the updates
+            // after t.
+            let as_of = frontier.step_back().unwrap_or(frontier);
+            subscribe.as_of = Some(AsOf::At(Expr::Value(Value::Number(as_of.to_string()))));
+            subscribe
+                .options
+                .retain(|o| o.name != SubscribeOptionName::Snapshot);
+            subscribe.options.push(SubscribeOption {
+                name: SubscribeOptionName::Snapshot,
+                value: Some(WithOptionValue::Value(Value::Boolean(false))),
+            });
+            *sql = stmt.to_ast_string();
+            found = true;
+        }
+        if !found {
+            return Err(Error::Unstructured(anyhow!(
+                "a resume token can only be used with SUBSCRIBE"
+            )));
+        }
+        Ok(())
+    }
+
     let mut stmt_groups = vec![];
 
     match request {
-        SqlRequest::Simple { query } => {
+        SqlRequest::Simple {
+            query,
+            resume_token,
+        } => {
             let stmts = parse(client, &query)?;
             let mut stmt_group = Vec::with_capacity(stmts.len());
             for StatementParseResult { ast: stmt, sql } in stmts {
                 check_prohibited_stmts(sender, &stmt)?;
                 stmt_group.push((stmt, sql.to_string(), vec![]));
             }
+            if let Some(resume_token) = resume_token {
+                apply_resume_token(&mut stmt_group, &resume_token)?;
+            }
             stmt_groups.push(stmt_group);
         }
         SqlRequest::Extended { queries } => {
-            for ExtendedRequest { query, params } in queries {
+            for ExtendedRequest {
+                query,
+                params,
+                resume_token,
+            } in queries
+            {
                 let mut stmts = parse(client, &query)?;
                 if stmts.len() != 1 {
                     return Err(Error::Unstructured(anyhow!(
@@ -1050,8 +1570,52 @@ async fn execute_request<S: ResultSender>(
                 let StatementParseResult { ast: stmt, sql } = stmts.pop().unwrap();
                 check_prohibited_stmts(sender, &stmt)?;
 
-                stmt_groups.push(vec![(stmt, sql.to_string(), params)]);
+                let mut stmt_group = vec![(stmt, sql.to_string(), params)];
+                if let Some(resume_token) = resume_token {
+                    apply_resume_token(&mut stmt_group, &resume_token)?;
+                }
+                stmt_groups.push(stmt_group);
+            }
+        }
+        SqlRequest::Transaction { transaction } => {
+            // All queries go in one group, so that they execute in a single
+            // implicit transaction that is rolled back if any query fails.
+            let mut stmt_group = Vec::with_capacity(transaction.len());
+            for ExtendedRequest {
+                query,
+                params,
+                resume_token,
+            } in transaction
+            {
+                let mut stmts = parse(client, &query)?;
+                if stmts.len() != 1 {
+                    return Err(Error::Unstructured(anyhow!(
+                        "each query must contain exactly 1 statement, but \"{}\" contains {}",
+                        query,
+                        stmts.len()
+                    )));
+                }
+
+                let StatementParseResult { ast: stmt, sql } = stmts.pop().unwrap();
+                check_prohibited_stmts(sender, &stmt)?;
+                // Transaction control would break the atomicity of the batch.
+                if matches!(
+                    stmt,
+                    Statement::StartTransaction(_) | Statement::Commit(_) | Statement::Rollback(_)
+                ) {
+                    return Err(Error::Unsupported(format!(
+                        "{} in a transactional batch",
+                        stmt.to_ast_string()
+                    )));
+                }
+                if resume_token.is_some() {
+                    return Err(Error::Unstructured(anyhow!(
+                        "a resume token cannot be used in a transactional batch"
+                    )));
+                }
+                stmt_group.push((stmt, sql.to_string(), params));
             }
+            stmt_groups.push(stmt_group);
         }
     }
 
@@ -1202,7 +1766,6 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::Comment
         | ExecuteResponse::Deleted(_)
         | ExecuteResponse::DiscardedTemp
-        | ExecuteResponse::DiscardedAll
         | ExecuteResponse::DroppedObject(_)
         | ExecuteResponse::DroppedOwned
         | ExecuteResponse::EmptyQuery
@@ -1221,7 +1784,6 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::AlteredRole
         | ExecuteResponse::AlteredSystemConfiguration
         | ExecuteResponse::Deallocate { .. }
-        | ExecuteResponse::ValidatedConnection
         | ExecuteResponse::Prepare => SqlResult::ok(
             client,
             tag.expect("ok only called on tag-generating results"),
@@ -1229,7 +1791,9 @@ async fn execute_stmt<S: ResultSender>(
         )
         .into(),
         ExecuteResponse::TransactionCommitted { params }
-        | ExecuteResponse::TransactionRolledBack { params } => {
+        | ExecuteResponse::TransactionRolledBack { params }
+        | ExecuteResponse::DiscardedAll { params }
+        | ExecuteResponse::ResetAllVariables { params } => {
             let notify_set: mz_ore::collections::HashSet<String> = client
                 .session()
                 .vars()
@@ -1286,10 +1850,10 @@ async fn execute_stmt<S: ResultSender>(
                     return Ok(SqlResult::err(client, AdapterError::Canceled).into());
                 }
             };
-            SqlResult::rows(client, rows, &desc.relation_desc.expect("RelationDesc must exist")).into()
+            rows_result::<S>(client, rows, desc.relation_desc.expect("RelationDesc must exist"))
         }
         ExecuteResponse::SendingRowsImmediate { rows } => {
-            SqlResult::rows(client, rows, &desc.relation_desc.expect("RelationDesc must exist")).into()
+            rows_result::<S>(client, rows, desc.relation_desc.expect("RelationDesc must exist"))
         }
         ExecuteResponse::Subscribing { rx, ctx_extra, instance_id } => StatementResult::Subscribe {
             tag: "SUBSCRIBE".into(),
@@ -1304,6 +1868,7 @@ async fn execute_stmt<S: ResultSender>(
             ctx_extra,
         },
         res @ (ExecuteResponse::Fetch { .. }
+        | ExecuteResponse::SendingRowsStreaming { .. }
         | ExecuteResponse::CopyTo { .. }
         | ExecuteResponse::CopyFrom { .. }
         | ExecuteResponse::DeclaredCursor
@@ -1317,18 +1882,25 @@ async fn execute_stmt<S: ResultSender>(
     })
 }
 
+/// Returns the result of a query that returned `rows`, in the form `S` wants.
+fn rows_result<S: ResultSender>(
+    client: &mut SessionClient,
+    rows: Box<dyn RowIterator + Send + Sync>,
+    desc: RelationDesc,
+) -> StatementResult {
+    if S::WANTS_RAW_ROWS {
+        StatementResult::Rows { desc, rows }
+    } else {
+        SqlResult::rows(client, rows, &desc).into()
+    }
+}
+
 fn make_notices(client: &mut SessionClient) -> Vec<Notice> {
     client
         .session()
         .drain_notices()
         .into_iter()
-        .map(|notice| Notice {
-            message: notice.to_string(),
-            code: notice.code().code().to_string(),
-            severity: notice.severity().as_str().to_lowercase(),
-            detail: notice.detail(),
-            hint: notice.hint(),
-        })
+        .map(Notice::from)
         .collect()
 }
 
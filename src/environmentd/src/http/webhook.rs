@@ -103,6 +103,7 @@ async fn append_webhook(
         body_format,
         header_tys,
         validator,
+        row_limit,
     } = async {
         let mut guard = webhook_cache.entries.lock().await;
 
@@ -150,6 +151,15 @@ async fn append_webhook(
     // Pack our body and headers into a Row.
     let rows = pack_rows(body, &body_format, headers, &header_tys)?;
 
+    // All of the rows get appended atomically, so reject the whole request if it's too large.
+    let limit = row_limit.get();
+    if rows.len() > limit {
+        return Err(AppendWebhookError::TooManyRows {
+            rows: rows.len(),
+            limit,
+        });
+    }
+
     // Send the row to get appended.
     tx.append(rows).await?;
 
@@ -320,6 +330,8 @@ pub enum WebhookError {
     InvalidHeaders(String),
     #[error("failed to deserialize body as {ty:?}: {msg}")]
     InvalidBody { ty: ScalarType, msg: String },
+    #[error("request contains {rows} rows, which exceeds the limit of {limit}")]
+    TooManyRows { rows: usize, limit: usize },
     #[error("failed to validate the request")]
     ValidationFailed,
     #[error("error occurred while running validation")]
@@ -345,6 +357,9 @@ impl From<AppendWebhookError> for WebhookError {
                 ty: ScalarType::Jsonb,
                 msg,
             },
+            AppendWebhookError::TooManyRows { rows, limit } => {
+                WebhookError::TooManyRows { rows, limit }
+            }
             AppendWebhookError::UnknownWebhook {
                 database,
                 schema,
@@ -380,6 +395,9 @@ impl IntoResponse for WebhookError {
             | e @ WebhookError::ValidationError => {
                 (StatusCode::BAD_REQUEST, e.to_string()).into_response()
             }
+            e @ WebhookError::TooManyRows { .. } => {
+                (StatusCode::PAYLOAD_TOO_LARGE, e.to_string()).into_response()
+            }
             e @ WebhookError::InvalidHeaders(_) => {
                 (StatusCode::UNAUTHORIZED, e.to_string()).into_response()
             }
@@ -470,6 +488,14 @@ mod tests {
         ))
         .into_response();
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        // Exceeding the row limit of a single request is the request's fault.
+        let resp = WebhookError::from(AppendWebhookError::TooManyRows {
+            rows: 11,
+            limit: 10,
+        })
+        .into_response();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
     }
 
     #[mz_ore::test]
@@ -583,6 +609,31 @@ mod tests {
         assert_eq!(rows.len(), 2);
     }
 
+    #[mz_ore::test]
+    fn test_json_deserializer_ndjson() {
+        let ndjson_raw = "{ \"event_type\": \"smol\" }\n{ \"event_type\": \"dog\" }\n[1, 2]\n";
+
+        // Newline delimited objects always get split into separate rows.
+        let rows = pack_rows(
+            ndjson_raw.as_bytes(),
+            &WebhookBodyFormat::Json { array: false },
+            &BTreeMap::default(),
+            &WebhookHeaders::default(),
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 3);
+
+        // Arrays within the batch only get expanded if requested.
+        let rows = pack_rows(
+            ndjson_raw.as_bytes(),
+            &WebhookBodyFormat::Json { array: true },
+            &BTreeMap::default(),
+            &WebhookHeaders::default(),
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 4);
+    }
+
     proptest! {
         #[mz_ore::test]
         fn proptest_pack_row_never_panics(
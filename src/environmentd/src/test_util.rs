@@ -199,10 +199,20 @@ impl TestHarness {
         self.tls = Some(TlsCertConfig {
             cert: cert_path.into(),
             key: key_path.into(),
+            client_ca: None,
         });
         self
     }
 
+    /// Requires clients to present certificates signed by the certificate
+    /// authorities at `client_ca_path`. Must be called after
+    /// [`TestHarness::with_tls`].
+    pub fn with_tls_client_ca(mut self, client_ca_path: impl Into<PathBuf>) -> Self {
+        let tls = self.tls.as_mut().expect("with_tls must be called first");
+        tls.client_ca = Some(client_ca_path.into());
+        self
+    }
+
     pub fn unsafe_mode(mut self) -> Self {
         self.unsafe_mode = true;
         self
@@ -15,6 +15,7 @@ use std::future::IntoFuture;
 use std::io::{Read, Write};
 use std::net::{IpAddr, Ipv4Addr, TcpStream};
 use std::num::NonZeroUsize;
+use std::path::Path;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -40,8 +41,10 @@ use mz_ore::retry::Retry;
 use mz_sql::names::PUBLIC_ROLE_NAME;
 use mz_sql::session::user::{HTTP_DEFAULT_USER, SYSTEM_USER};
 use openssl::error::ErrorStack;
-use openssl::ssl::{SslConnector, SslConnectorBuilder, SslMethod, SslOptions, SslVerifyMode};
-use postgres::config::SslMode;
+use openssl::ssl::{
+    SslConnector, SslConnectorBuilder, SslFiletype, SslMethod, SslOptions, SslVerifyMode,
+};
+use postgres::config::{ChannelBinding, SslMode};
 use postgres::error::SqlState;
 use serde::Deserialize;
 use serde_json::json;
@@ -1417,6 +1420,265 @@ async fn test_auth_base_require_tls() {
     .await;
 }
 
+#[allow(clippy::unit_arg)]
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl` on OS `linux`
+async fn test_auth_client_cert() {
+    let ca = Ca::new_root("test ca").unwrap();
+    let (server_cert, server_key) = ca
+        .request_cert("server", vec![IpAddr::V4(Ipv4Addr::LOCALHOST)])
+        .unwrap();
+    let (alice_cert, alice_key) = ca.request_client_cert("alice").unwrap();
+    let (bot_cert, bot_key) = ca.request_client_cert("ops-bot").unwrap();
+    let (eve_cert, eve_key) = ca.request_client_cert("eve").unwrap();
+
+    fn client_cert<'a>(
+        cert: &'a Path,
+        key: &'a Path,
+    ) -> Box<dyn Fn(&mut SslConnectorBuilder) -> Result<(), ErrorStack> + 'a> {
+        Box::new(move |b| {
+            b.set_verify(SslVerifyMode::NONE);
+            b.set_certificate_file(cert, SslFiletype::PEM)?;
+            b.set_private_key_file(key, SslFiletype::PEM)
+        })
+    }
+
+    let no_headers = HeaderMap::new();
+
+    let server = test_util::TestHarness::default()
+        .with_tls(server_cert, server_key)
+        .with_tls_client_ca(ca.ca_cert_path())
+        .with_system_parameter_default(
+            "tls_client_cert_role_mapping".to_string(),
+            "cn:ops-bot=ops, cn:alice=*".to_string(),
+        )
+        .start()
+        .await;
+
+    run_tests(
+        "TlsMode::Require, client certificates",
+        &server,
+        &[
+            // A certificate logs in as the role it maps to.
+            TestCase::Pgwire {
+                user_to_auth_as: "alice",
+                user_reported_by_system: "alice",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: client_cert(&alice_cert, &alice_key),
+                assert: Assert::Success,
+            },
+            TestCase::Pgwire {
+                user_to_auth_as: "ops",
+                user_reported_by_system: "ops",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: client_cert(&bot_cert, &bot_key),
+                assert: Assert::Success,
+            },
+            TestCase::Http {
+                user_to_auth_as: "alice",
+                user_reported_by_system: "alice",
+                scheme: Scheme::HTTPS,
+                headers: &no_headers,
+                configure: client_cert(&alice_cert, &alice_key),
+                assert: Assert::Success,
+            },
+            // A certificate cannot log in as another role.
+            TestCase::Pgwire {
+                user_to_auth_as: "alice",
+                user_reported_by_system: "alice",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: client_cert(&bot_cert, &bot_key),
+                assert: Assert::DbErr(Box::new(|err| {
+                    assert_eq!(*err.code(), SqlState::INVALID_AUTHORIZATION_SPECIFICATION);
+                    assert_eq!(
+                        err.message(),
+                        "certificate authentication failed for user \"alice\""
+                    );
+                })),
+            },
+            // A certificate that no rule maps is rejected.
+            TestCase::Pgwire {
+                user_to_auth_as: "eve",
+                user_reported_by_system: "eve",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: client_cert(&eve_cert, &eve_key),
+                assert: Assert::DbErr(Box::new(|err| {
+                    assert_eq!(*err.code(), SqlState::INVALID_AUTHORIZATION_SPECIFICATION);
+                    assert_eq!(err.message(), "client certificate is not mapped to a role");
+                })),
+            },
+            TestCase::Http {
+                user_to_auth_as: "eve",
+                user_reported_by_system: "eve",
+                scheme: Scheme::HTTPS,
+                headers: &no_headers,
+                configure: client_cert(&eve_cert, &eve_key),
+                assert: Assert::Err(Box::new(|code, message| {
+                    assert_eq!(code, Some(StatusCode::UNAUTHORIZED));
+                    assert_eq!(&message, "unauthorized");
+                })),
+            },
+            // Connecting without a certificate fails the TLS handshake.
+            TestCase::Pgwire {
+                user_to_auth_as: "alice",
+                user_reported_by_system: "alice",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Err(Box::new(|err| {
+                    assert_contains!(err.to_string(), "error performing TLS handshake");
+                })),
+            },
+            TestCase::Http {
+                user_to_auth_as: "alice",
+                user_reported_by_system: "alice",
+                scheme: Scheme::HTTPS,
+                headers: &no_headers,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Err(Box::new(|code, _message| {
+                    assert_eq!(code, None);
+                })),
+            },
+        ],
+    )
+    .await;
+}
+
+#[allow(clippy::unit_arg)]
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl` on OS `linux`
+async fn test_auth_scram() {
+    let ca = Ca::new_root("test ca").unwrap();
+    let (server_cert, server_key) = ca
+        .request_cert("server", vec![IpAddr::V4(Ipv4Addr::LOCALHOST)])
+        .unwrap();
+    let server = test_util::TestHarness::default()
+        .with_tls(server_cert, server_key)
+        .start()
+        .await;
+
+    let mz_system = server.connect().internal().await.unwrap();
+    mz_system
+        .batch_execute("CREATE ROLE alice PASSWORD 'hunter2'")
+        .await
+        .unwrap();
+    mz_system.batch_execute("CREATE ROLE bob").await.unwrap();
+
+    run_tests(
+        "TlsMode::Require, SCRAM-SHA-256",
+        &server,
+        &[
+            // Roles with a password require it. Over TLS, the client binds the
+            // exchange to the connection with SCRAM-SHA-256-PLUS.
+            TestCase::Pgwire {
+                user_to_auth_as: "alice",
+                user_reported_by_system: "alice",
+                password: Some("hunter2"),
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Success,
+            },
+            TestCase::Pgwire {
+                user_to_auth_as: "alice",
+                user_reported_by_system: "alice",
+                password: Some("hunter3"),
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::DbErr(Box::new(|err| {
+                    assert_eq!(*err.code(), SqlState::INVALID_PASSWORD);
+                    assert_eq!(
+                        err.message(),
+                        "password authentication failed for user \"alice\""
+                    );
+                })),
+            },
+            // Roles without a password are still trusted.
+            TestCase::Pgwire {
+                user_to_auth_as: "bob",
+                user_reported_by_system: "bob",
+                password: None,
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Success,
+            },
+        ],
+    )
+    .await;
+
+    // Clients that don't bind the exchange to the connection use
+    // SCRAM-SHA-256.
+    let mut config = server
+        .connect()
+        .ssl_mode(SslMode::Require)
+        .user("alice")
+        .password("hunter2")
+        .as_pg_config()
+        .clone();
+    config.channel_binding(ChannelBinding::Disable);
+    let client = server
+        .connect()
+        .with_config(config)
+        .with_tls(make_pg_tls(|b| Ok(b.set_verify(SslVerifyMode::NONE))))
+        .await
+        .unwrap();
+    let row = client.query_one("SELECT current_user", &[]).await.unwrap();
+    assert_eq!(row.get::<_, String>(0), "alice");
+
+    // Passwords can be changed and removed.
+    mz_system
+        .batch_execute("ALTER ROLE alice PASSWORD 'hunter3'")
+        .await
+        .unwrap();
+    mz_system
+        .batch_execute("ALTER ROLE bob PASSWORD 'swordfish'")
+        .await
+        .unwrap();
+    run_tests(
+        "TlsMode::Require, changed passwords",
+        &server,
+        &[
+            TestCase::Pgwire {
+                user_to_auth_as: "alice",
+                user_reported_by_system: "alice",
+                password: Some("hunter3"),
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Success,
+            },
+            TestCase::Pgwire {
+                user_to_auth_as: "bob",
+                user_reported_by_system: "bob",
+                password: Some("swordfish"),
+                ssl_mode: SslMode::Require,
+                configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+                assert: Assert::Success,
+            },
+        ],
+    )
+    .await;
+    mz_system
+        .batch_execute("ALTER ROLE alice PASSWORD NULL")
+        .await
+        .unwrap();
+    run_tests(
+        "TlsMode::Require, removed password",
+        &server,
+        &[TestCase::Pgwire {
+            user_to_auth_as: "alice",
+            user_reported_by_system: "alice",
+            password: None,
+            ssl_mode: SslMode::Require,
+            configure: Box::new(|b| Ok(b.set_verify(SslVerifyMode::NONE))),
+            assert: Assert::Success,
+        }],
+    )
+    .await;
+}
+
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
 #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `OPENSSL_init_ssl` on OS `linux`
 async fn test_auth_intermediate_ca_no_intermediary() {
@@ -21,7 +21,7 @@ use mz_environmentd::test_util::{self, PostgresErrorExt};
 use mz_ore::collections::CollectionExt;
 use mz_ore::retry::Retry;
 use mz_pgrepr::{Numeric, Record};
-use postgres::binary_copy::BinaryCopyOutIter;
+use postgres::binary_copy::{BinaryCopyInWriter, BinaryCopyOutIter};
 use postgres::error::SqlState;
 use postgres::types::Type;
 use postgres::SimpleQueryMessage;
@@ -417,6 +417,44 @@ fn test_copy() {
             .unwrap();
         assert_eq!(buf, ",21,2\n\t,\"my,str\",4\n");
     }
+
+    // Test binary COPY FROM.
+    {
+        client
+            .batch_execute("CREATE TABLE copy_binary (a text, b int4, c int4[])")
+            .unwrap();
+        let mut writer = BinaryCopyInWriter::new(
+            client
+                .copy_in("COPY copy_binary FROM STDIN (FORMAT BINARY)")
+                .unwrap(),
+            &[Type::TEXT, Type::INT4, Type::INT4_ARRAY],
+        );
+        writer
+            .write(&[&None::<String>, &2i32, &vec![Some(1i32), None]])
+            .unwrap();
+        writer.write(&[&"\t", &4i32, &None::<Vec<i32>>]).unwrap();
+        assert_eq!(writer.finish().unwrap(), 2);
+
+        let rows: Vec<(Option<String>, i32, Option<Vec<Option<i32>>>)> = client
+            .query("SELECT a, b, c FROM copy_binary ORDER BY b", &[])
+            .unwrap()
+            .into_iter()
+            .map(|row| (row.get(0), row.get(1), row.get(2)))
+            .collect();
+        assert_eq!(
+            rows,
+            &[
+                (None, 2, Some(vec![Some(1), None])),
+                (Some("\t".into()), 4, None)
+            ]
+        );
+
+        assert!(client
+            .copy_in::<_, bytes::Bytes>(
+                "COPY copy_binary FROM STDIN (FORMAT BINARY, DELIMITER ',')"
+            )
+            .is_err());
+    }
 }
 
 #[mz_ore::test]
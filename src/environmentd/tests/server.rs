@@ -924,8 +924,10 @@ fn test_cancel_long_running_query() {
 }
 
 fn test_cancellation_cancels_dataflows(query: &str) {
+    let metrics_registry = MetricsRegistry::new();
     let server = test_util::TestHarness::default()
         .unsafe_mode()
+        .with_metrics_registry(metrics_registry.clone())
         .start_blocking();
 
     let mut client1 = server.connect(postgres::NoTls).unwrap();
@@ -990,6 +992,26 @@ fn test_cancellation_cancels_dataflows(query: &str) {
             }
         })
         .unwrap();
+
+    // Expect the cancellation to be reflected in the metrics.
+    let metrics = metrics_registry.gather();
+    let canceled_dataflows: f64 = metrics
+        .iter()
+        .find(|m| m.get_name() == "mz_canceled_dataflows_total")
+        .unwrap()
+        .get_metric()
+        .iter()
+        .map(|m| m.get_counter().get_value())
+        .sum();
+    assert!(canceled_dataflows >= 1.0);
+    let cancellations = metrics
+        .iter()
+        .find(|m| m.get_name() == "mz_cancellation_seconds")
+        .unwrap()
+        .get_metric()[0]
+        .get_histogram()
+        .get_sample_count();
+    assert!(cancellations >= 1);
 }
 
 // Test that dataflow uninstalls cancelled peeks.
@@ -1995,6 +2017,70 @@ fn test_max_connections_on_all_interfaces() {
     assert!(text.contains("creating connection would violate max_connections limit (desired: 2, limit: 1, current: 1)"));
 }
 
+// Test that network_policy_ingress_allowlist limits the addresses that user
+// sessions may connect from.
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_network_policy_ingress_allowlist() {
+    let server = test_util::TestHarness::default().start_blocking();
+
+    let mut mz_client = server
+        .pg_config_internal()
+        .user(&SYSTEM_USER.name)
+        .connect(postgres::NoTls)
+        .unwrap();
+    let err = mz_client
+        .batch_execute("ALTER SYSTEM SET network_policy_ingress_allowlist = '10.0.0.1/8'")
+        .unwrap_db_error();
+    assert!(
+        err.message().contains("invalid IP network"),
+        "{}",
+        err.message()
+    );
+    mz_client
+        .batch_execute("ALTER SYSTEM SET network_policy_ingress_allowlist = '10.0.0.0/8'")
+        .unwrap();
+
+    let http_url = Url::parse(&format!(
+        "http://{}/api/sql",
+        server.inner().http_local_addr()
+    ))
+    .unwrap();
+    let json = serde_json::json!({"query": "SELECT 1"});
+
+    // Local connections are not in the allowlist.
+    let err = server.connect(postgres::NoTls).unwrap_db_error();
+    assert_eq!(err.code(), &SqlState::INVALID_AUTHORIZATION_SPECIFICATION);
+    assert_eq!(err.message(), "session denied by network policy");
+    let res = Client::new()
+        .post(http_url.clone())
+        .json(&json)
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    // The internal port is not subject to the network policy.
+    server
+        .pg_config_internal()
+        .user(&SYSTEM_USER.name)
+        .connect(postgres::NoTls)
+        .unwrap();
+
+    mz_client
+        .batch_execute(
+            "ALTER SYSTEM SET network_policy_ingress_allowlist = '10.0.0.0/8', '127.0.0.1', '::1'",
+        )
+        .unwrap();
+    server.connect(postgres::NoTls).unwrap();
+    let res = Client::new().post(http_url).json(&json).send().unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    mz_client
+        .batch_execute("ALTER SYSTEM RESET network_policy_ingress_allowlist")
+        .unwrap();
+    server.connect(postgres::NoTls).unwrap();
+}
+
 // Test max_connections and superuser_reserved_connections.
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
 async fn test_max_connections_limits() {
@@ -3031,7 +3117,7 @@ fn test_github_20262() {
         r#"{"type":"Rows","payload":{"columns":[{"name":"mz_timestamp","type_oid":1700,"type_len":-1,"type_mod":2555908},{"name":"mz_diff","type_oid":20,"type_len":8,"type_mod":-1},{"name":"i","type_oid":23,"type_len":4,"type_mod":-1}]}}"#,
         r#"{"type":"Error","payload":{"message":"canceling statement due to user request","code":"57014"}}"#,
         r#"{"type":"ReadyForQuery","payload":"I"}"#,
-        r#"{"type":"Notice","payload":{"message":"there is no transaction in progress","code":"25P01","severity":"warning"}}"#,
+        r#"{"type":"Notice","payload":{"message":"there is no transaction in progress","code":"25P01","severity":"warning","name":"explicit_transaction_control_in_implicit_transaction"}}"#,
         r#"{"type":"CommandStarting","payload":{"has_rows":false,"is_streaming":false}}"#,
         r#"{"type":"CommandComplete","payload":"COMMIT"}"#,
         r#"{"type":"ReadyForQuery","payload":"I"}"#,
@@ -3049,6 +3135,62 @@ fn test_github_20262() {
     }
 }
 
+// Test that SUBSCRIBE over websockets reports resume tokens, and that passing one back continues
+// the SUBSCRIBE without a snapshot.
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+fn test_ws_subscribe_resume_token() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client
+        .batch_execute("CREATE TABLE t (i INT); INSERT INTO t VALUES (1);")
+        .unwrap();
+
+    // Reads until a resume token follows some data rows, and returns the values of the data rows
+    // and the token.
+    let read_rows = |ws: &mut tungstenite::WebSocket<
+        tungstenite::stream::MaybeTlsStream<std::net::TcpStream>,
+    >| {
+        let mut rows = Vec::new();
+        loop {
+            let Message::Text(text) = ws.read().unwrap() else {
+                continue;
+            };
+            match serde_json::from_str(&text).unwrap() {
+                // The columns are mz_timestamp, mz_progressed, mz_diff, and i.
+                WebSocketResponse::Row(row) if row[1] == serde_json::Value::Bool(false) => {
+                    rows.push(row[3].clone());
+                }
+                WebSocketResponse::ResumeToken(token) if !rows.is_empty() => return (rows, token),
+                WebSocketResponse::Error(err) => panic!("unexpected error: {err:?}"),
+                _ => {}
+            }
+        }
+    };
+
+    let (mut ws, _resp) = tungstenite::connect(server.ws_addr()).unwrap();
+    test_util::auth_with_ws(&mut ws, BTreeMap::default()).unwrap();
+    ws.send(Message::Text(
+        r#"{"query": "SUBSCRIBE t WITH (PROGRESS)"}"#.into(),
+    ))
+    .unwrap();
+    let (rows, resume_token) = read_rows(&mut ws);
+    assert_eq!(rows, vec![serde_json::json!("1")]);
+    drop(ws);
+
+    client.batch_execute("INSERT INTO t VALUES (2)").unwrap();
+
+    let (mut ws, _resp) = tungstenite::connect(server.ws_addr()).unwrap();
+    test_util::auth_with_ws(&mut ws, BTreeMap::default()).unwrap();
+    let resume = serde_json::json!({
+        "query": "SUBSCRIBE t WITH (PROGRESS)",
+        "resume_token": resume_token,
+    });
+    ws.send(Message::Text(resume.to_string())).unwrap();
+    let (rows, _resume_token) = read_rows(&mut ws);
+    assert_eq!(rows, vec![serde_json::json!("2")]);
+}
+
 // Test that the server properly handles cancellation requests of read-then-write queries.
 // See #20404.
 #[mz_ore::test]
@@ -4032,7 +4174,11 @@ async fn test_startup_cluster_notice_with_http_options() {
         "message": "cluster \"i_do_not_exist\" does not exist",
         "code": "MZ007",
         "severity": "notice",
-        "hint": "Create the cluster with CREATE CLUSTER or pick an extant cluster with SET CLUSTER = name. List available clusters with SHOW CLUSTERS."
+        "hint": "Create the cluster with CREATE CLUSTER or pick an extant cluster with SET CLUSTER = name. List available clusters with SHOW CLUSTERS.",
+        "name": "cluster_does_not_exist",
+        "fields": {
+          "name": "i_do_not_exist"
+        }
       }
     ]
     "###);
@@ -4509,3 +4655,272 @@ async fn test_cert_reloading() {
     assert_eq!(resp_x509, next_x509);
     check_pgwire(&conn_str, &ca.ca_cert_path(), next_x509.clone()).await;
 }
+
+// Test that transactional batches over HTTP commit atomically and report per-statement results.
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+fn test_http_sql_transaction() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut super_user = server
+        .pg_config_internal()
+        .user(&SYSTEM_USER.name)
+        .connect(postgres::NoTls)
+        .unwrap();
+    super_user
+        .batch_execute(&format!(
+            "CREATE ROLE {user};
+            CREATE TABLE t (a int);
+            GRANT USAGE ON DATABASE materialize TO {user};
+            GRANT USAGE ON SCHEMA materialize.public TO {user};
+            GRANT INSERT ON TABLE t TO {user};",
+            user = &HTTP_DEFAULT_USER.name
+        ))
+        .unwrap();
+    let url = Url::parse(&format!(
+        "http://{}/api/sql",
+        server.inner().http_local_addr()
+    ))
+    .unwrap();
+    let post = |json: serde_json::Value| {
+        let res = Client::new().post(url.clone()).json(&json).send().unwrap();
+        let status = res.status();
+        (status, res.text().unwrap())
+    };
+
+    let (status, body) = post(serde_json::json!({
+        "transaction": [
+            { "query": "INSERT INTO t VALUES ($1::int)", "params": ["1"] },
+            { "query": "INSERT INTO t VALUES ($1::int)", "params": ["2"] },
+        ]
+    }));
+    assert_eq!(status, StatusCode::OK);
+    let res: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(res["committed"], true);
+    let results = res["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    for result in results {
+        assert_eq!(result["ok"], "INSERT 0 1");
+        assert!(result["duration_ms"].as_f64().unwrap() >= 0.0);
+    }
+
+    // A failing statement rolls back the whole batch, and is the last result.
+    let (status, body) = post(serde_json::json!({
+        "transaction": [
+            { "query": "INSERT INTO t VALUES ($1::int)", "params": ["3"] },
+            { "query": "INSERT INTO t VALUES ($1::int)", "params": ["x"] },
+            { "query": "INSERT INTO t VALUES ($1::int)", "params": ["4"] },
+        ]
+    }));
+    assert_eq!(status, StatusCode::OK);
+    let res: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(res["committed"], false);
+    let results = res["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ok"], "INSERT 0 1");
+    assert_contains!(
+        results[1]["error"]["message"].as_str().unwrap(),
+        "unable to decode parameter"
+    );
+
+    let rows: Vec<i32> = super_user
+        .query("SELECT a FROM t ORDER BY a", &[])
+        .unwrap()
+        .into_iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(rows, vec![1, 2]);
+
+    // Transaction control is rejected, as it would break atomicity.
+    let (status, body) = post(serde_json::json!({
+        "transaction": [{ "query": "COMMIT" }]
+    }));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_contains!(body, "in a transactional batch");
+}
+
+// Test that the Arrow endpoint streams query results as an Arrow IPC stream.
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+fn test_http_sql_arrow() {
+    use arrow::array::{Array, Int32Array, LargeStringArray};
+    use arrow::ipc::reader::StreamReader;
+
+    let server = test_util::TestHarness::default().start_blocking();
+    let url = Url::parse(&format!(
+        "http://{}/api/experimental/sql/arrow",
+        server.inner().http_local_addr()
+    ))
+    .unwrap();
+
+    let res = Client::new()
+        .post(url.clone())
+        .json(&serde_json::json!({
+            "query": "SELECT * FROM (VALUES (1, 'a'), (2, NULL), ($1::int, 'c')) AS t (x, y) ORDER BY x",
+            "params": ["3"],
+        }))
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.headers()[CONTENT_TYPE],
+        mz_arrow_util::ipc::ARROW_STREAM_CONTENT_TYPE
+    );
+    let body = res.bytes().unwrap();
+    let reader = StreamReader::try_new(&body[..], None).unwrap();
+    let batches: Vec<_> = reader.map(|batch| batch.unwrap()).collect();
+    assert_eq!(batches.len(), 1);
+    let batch = &batches[0];
+    let x = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(x.values().to_vec(), vec![1, 2, 3]);
+    let y = batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<LargeStringArray>()
+        .unwrap();
+    assert_eq!(
+        y.iter().collect::<Vec<_>>(),
+        vec![Some("a"), None, Some("c")]
+    );
+
+    // Statements that don't return rows are rejected.
+    let res = Client::new()
+        .post(url.clone())
+        .json(&serde_json::json!({ "query": "CREATE TABLE t (a int)" }))
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    // As are errors that occur before any results are produced.
+    let res = Client::new()
+        .post(url)
+        .json(&serde_json::json!({ "query": "SELECT 1/0" }))
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_contains!(res.text().unwrap(), "division by zero");
+}
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_peek_plan_cache_metrics() {
+    let server = test_util::TestHarness::default().start().await;
+    let client = server.connect().await.unwrap();
+    client
+        .batch_execute(
+            "CREATE TABLE t (a int);
+             INSERT INTO t VALUES (1), (2);
+             CREATE DEFAULT INDEX ON t;",
+        )
+        .await
+        .unwrap();
+
+    let lookups = |result: &str| -> f64 {
+        server
+            .metrics_registry
+            .gather()
+            .into_iter()
+            .filter(|metric| metric.get_name() == "mz_peek_plan_cache_lookups")
+            .flat_map(|metric| metric.get_metric().to_vec())
+            .filter(|metric| metric.get_label()[0].get_value() == result)
+            .map(|metric| metric.get_counter().get_value())
+            .sum()
+    };
+    let mut expected = BTreeMap::new();
+    let mut assert_lookup = |result: &'static str| {
+        *expected.entry(result).or_insert(0.0) += 1.0;
+        for result in ["miss", "local_hit", "global_hit"] {
+            let count = expected.get(result).copied().unwrap_or(0.0);
+            assert_eq!(lookups(result), count, "{result}");
+        }
+    };
+
+    // Fast path plans are cached for each combination of parameters.
+    let select = "SELECT a FROM t WHERE a = $1";
+    let rows = client.query(select, &[&1i32]).await.unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_lookup("miss");
+    let rows = client.query(select, &[&1i32]).await.unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_lookup("global_hit");
+    let rows = client.query(select, &[&2i32]).await.unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_lookup("miss");
+
+    // Catalog changes could have changed the indexes, so global plans are
+    // reoptimized.
+    client
+        .batch_execute("CREATE VIEW v AS SELECT 1")
+        .await
+        .unwrap();
+    client.query(select, &[&1i32]).await.unwrap();
+    assert_lookup("local_hit");
+    client.query(select, &[&1i32]).await.unwrap();
+    assert_lookup("global_hit");
+
+    // Plans that depend on the session are only cached locally.
+    let select = "SELECT a FROM t WHERE current_user() = 'materialize'";
+    let rows = client.query(select, &[]).await.unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_lookup("miss");
+    let rows = client.query(select, &[]).await.unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_lookup("local_hit");
+
+    // Statements that resolve to new objects are optimized from scratch.
+    client
+        .batch_execute("DROP TABLE t; CREATE TABLE t (a int)")
+        .await
+        .unwrap();
+    let rows = client.query(select, &[]).await.unwrap();
+    assert_eq!(rows.len(), 0);
+    assert_lookup("miss");
+}
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_large_constant_peek() {
+    let server = test_util::TestHarness::default().start().await;
+    let client = server.connect().await.unwrap();
+
+    // Constants with more than 1024 rows are finished off the coordinator's main loop, and
+    // must return the same results as smaller ones.
+    for (n, expected) in [(1000, vec![1000, 999, 998]), (2000, vec![2000, 1999, 1998])] {
+        let rows = client
+            .query(
+                &format!("SELECT * FROM generate_series(1, {n}) ORDER BY 1 DESC LIMIT 3"),
+                &[],
+            )
+            .await
+            .unwrap();
+        let values: Vec<i32> = rows.iter().map(|row| row.get(0)).collect();
+        assert_eq!(values, expected);
+
+        let count = client
+            .query(&format!("SELECT * FROM generate_series(1, {n})"), &[])
+            .await
+            .unwrap()
+            .len();
+        assert_eq!(count, n);
+    }
+
+    // Errors keep their SQLSTATE on both sides of the threshold.
+    client
+        .batch_execute("SET max_query_result_size = 100")
+        .await
+        .unwrap();
+    for n in [1000, 2000] {
+        let err = client
+            .query(&format!("SELECT * FROM generate_series(1, {n})"), &[])
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Some(&SqlState::OUT_OF_MEMORY));
+        assert_contains!(
+            err.as_db_error().unwrap().message(),
+            "result exceeds max size of 100 B"
+        );
+    }
+}
@@ -2562,6 +2562,33 @@ fn wrap<'a>(datums: &'a [Datum<'a>], width: usize) -> impl Iterator<Item = (Row,
     datums.chunks(width).map(|chunk| (Row::pack(chunk), 1))
 }
 
+/// Evaluates `inner` and appends a 1-based ordinality column to each of its output rows,
+/// numbering the rows in the order in which `inner` produces them.
+fn with_ordinality<'a>(
+    inner: &'a TableFunc,
+    datums: &'a [Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<impl Iterator<Item = (Row, Diff)> + 'a, EvalError> {
+    let rows = inner.eval(datums, temp_storage)?.collect::<Vec<_>>();
+    if rows.iter().any(|(_row, diff)| *diff < 0) {
+        return Err(EvalError::InvalidParameterValue(format!(
+            "{} WITH ORDINALITY cannot produce a negative number of rows",
+            inner
+        )));
+    }
+    let mut row_buf = Row::default();
+    Ok(rows
+        .into_iter()
+        .flat_map(|(row, diff)| iter::repeat(row).take(usize::cast_from(diff.unsigned_abs())))
+        .zip(1i64..)
+        .map(move |(row, ordinality)| {
+            let mut packer = row_buf.packer();
+            packer.extend(row.iter());
+            packer.push(Datum::Int64(ordinality));
+            (row_buf.clone(), 1)
+        }))
+}
+
 fn acl_explode<'a>(
     acl_items: Datum<'a>,
     temp_storage: &'a RowArena,
@@ -2656,11 +2683,21 @@ pub enum TableFunc {
         name: String,
         relation: RelationType,
     },
+    /// Appends an ordinality column to the output of `inner`, as requested by
+    /// `WITH ORDINALITY`.
+    ///
+    /// Unlike numbering the rows with a `row_number` window function, this
+    /// keeps the table function call stateless, so it can be applied directly
+    /// to each row of a correlated input.
+    WithOrdinality {
+        #[proptest(strategy = "Just(Box::new(TableFunc::GenerateSeriesInt64))")]
+        inner: Box<TableFunc>,
+    },
 }
 
 impl RustType<ProtoTableFunc> for TableFunc {
     fn into_proto(&self) -> ProtoTableFunc {
-        use proto_table_func::{Kind, ProtoWrap};
+        use proto_table_func::{Kind, ProtoWithOrdinality, ProtoWrap};
 
         ProtoTableFunc {
             kind: Some(match self {
@@ -2690,6 +2727,11 @@ impl RustType<ProtoTableFunc> for TableFunc {
                         relation: Some(relation.into_proto()),
                     })
                 }
+                TableFunc::WithOrdinality { inner } => {
+                    Kind::WithOrdinality(Box::new(ProtoWithOrdinality {
+                        inner: Some(inner.into_proto()),
+                    }))
+                }
             }),
         }
     }
@@ -2734,6 +2776,9 @@ impl RustType<ProtoTableFunc> for TableFunc {
                     .relation
                     .into_rust_if_some("ProtoTabletizedScalar::relation")?,
             },
+            Kind::WithOrdinality(v) => TableFunc::WithOrdinality {
+                inner: v.inner.into_rust_if_some("ProtoWithOrdinality::inner")?,
+            },
         })
     }
 }
@@ -2813,6 +2858,9 @@ impl TableFunc {
                 let r = Row::pack_slice(datums);
                 Ok(Box::new(std::iter::once((r, 1))))
             }
+            TableFunc::WithOrdinality { inner } => {
+                Ok(Box::new(with_ordinality(inner, datums, temp_storage)?))
+            }
         }
     }
 
@@ -2941,6 +2989,16 @@ impl TableFunc {
             TableFunc::TabletizedScalar { relation, .. } => {
                 return relation.clone();
             }
+            TableFunc::WithOrdinality { inner } => {
+                let inner_type = inner.output_type();
+                let ordinality = inner_type.column_types.len();
+                let mut column_types = inner_type.column_types;
+                column_types.push(ScalarType::Int64.nullable(false));
+                // The ordinality column is unique among the rows produced for a single input row.
+                let mut keys = inner_type.keys;
+                keys.push(vec![ordinality]);
+                (column_types, keys)
+            }
         };
 
         if !keys.is_empty() {
@@ -2970,6 +3028,7 @@ impl TableFunc {
             TableFunc::UnnestMap { .. } => 2,
             TableFunc::Wrap { width, .. } => *width,
             TableFunc::TabletizedScalar { relation, .. } => relation.column_types.len(),
+            TableFunc::WithOrdinality { inner } => inner.output_arity() + 1,
         }
     }
 
@@ -2993,6 +3052,7 @@ impl TableFunc {
             | TableFunc::UnnestMap { .. } => true,
             TableFunc::Wrap { .. } => false,
             TableFunc::TabletizedScalar { .. } => false,
+            TableFunc::WithOrdinality { inner } => inner.empty_on_null_input(),
         }
     }
 
@@ -3019,6 +3079,7 @@ impl TableFunc {
             TableFunc::UnnestMap { .. } => true,
             TableFunc::Wrap { .. } => true,
             TableFunc::TabletizedScalar { .. } => true,
+            TableFunc::WithOrdinality { inner } => inner.preserves_monotonicity(),
         }
     }
 }
@@ -3044,6 +3105,7 @@ impl fmt::Display for TableFunc {
             TableFunc::UnnestMap { .. } => f.write_str("unnest_map"),
             TableFunc::Wrap { width, .. } => write!(f, "wrap{}", width),
             TableFunc::TabletizedScalar { name, .. } => f.write_str(name),
+            TableFunc::WithOrdinality { inner } => write!(f, "{}[with_ordinality]", inner),
         }
     }
 }
@@ -2437,6 +2437,7 @@ pub enum EvalError {
     InvalidParameterValue(String),
     InvalidDatePart(String),
     KeyCannotBeNull,
+    AppendOnlyViolated,
     NegSqrt,
     NegLimit,
     NullCharacterNotPermitted,
@@ -2576,6 +2577,9 @@ impl fmt::Display for EvalError {
             ),
             EvalError::InvalidDatePart(part) => write!(f, "invalid datepart {}", part.quoted()),
             EvalError::KeyCannotBeNull => f.write_str("key cannot be null"),
+            EvalError::AppendOnlyViolated => {
+                f.write_str("retraction in a materialized view asserted to be append-only")
+            }
             EvalError::NegSqrt => f.write_str("cannot take square root of a negative number"),
             EvalError::NegLimit => f.write_str("LIMIT must not be negative"),
             EvalError::NullCharacterNotPermitted => f.write_str("null character not permitted"),
@@ -2875,6 +2879,7 @@ impl RustType<ProtoEvalError> for EvalError {
             EvalError::InvalidParameterValue(v) => InvalidParameterValue(v.clone()),
             EvalError::InvalidDatePart(part) => InvalidDatePart(part.to_string()),
             EvalError::KeyCannotBeNull => KeyCannotBeNull(()),
+            EvalError::AppendOnlyViolated => AppendOnlyViolated(()),
             EvalError::NegSqrt => NegSqrt(()),
             EvalError::NegLimit => NegLimit(()),
             EvalError::NullCharacterNotPermitted => NullCharacterNotPermitted(()),
@@ -3008,6 +3013,7 @@ impl RustType<ProtoEvalError> for EvalError {
                 InvalidParameterValue(v) => Ok(EvalError::InvalidParameterValue(v)),
                 InvalidDatePart(part) => Ok(EvalError::InvalidDatePart(part)),
                 KeyCannotBeNull(()) => Ok(EvalError::KeyCannotBeNull),
+                AppendOnlyViolated(()) => Ok(EvalError::AppendOnlyViolated),
                 NegSqrt(()) => Ok(EvalError::NegSqrt),
                 NegLimit(()) => Ok(EvalError::NegLimit),
                 NullCharacterNotPermitted(()) => Ok(EvalError::NullCharacterNotPermitted),
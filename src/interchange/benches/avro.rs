@@ -9,7 +9,7 @@
 use byteorder::{NetworkEndian, WriteBytesExt};
 use criterion::{black_box, Criterion, Throughput};
 use mz_avro::types::Value as AvroValue;
-use mz_interchange::avro::{parse_schema, Decoder};
+use mz_interchange::avro::{parse_schema, DecodeOptions, Decoder};
 use mz_ore::cast::CastFrom;
 use mz_repr::adt::date::Date;
 use tokio::runtime::Runtime;
@@ -392,7 +392,14 @@ pub fn bench_avro(c: &mut Criterion) {
     buf.extend(mz_avro::to_avro_datum(&schema, record).unwrap());
     let len = u64::cast_from(buf.len());
 
-    let mut decoder = Decoder::new(schema_str, None, "avro_bench".to_string(), false).unwrap();
+    let mut decoder = Decoder::new(
+        schema_str,
+        None,
+        "avro_bench".to_string(),
+        false,
+        DecodeOptions::default(),
+    )
+    .unwrap();
 
     let mut bg = c.benchmark_group("avro");
     bg.throughput(Throughput::Bytes(len));
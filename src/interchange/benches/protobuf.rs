@@ -8,7 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use criterion::{black_box, Criterion, Throughput};
-use mz_interchange::protobuf::{DecodedDescriptors, Decoder};
+use mz_interchange::protobuf::{DecodeOptions, DecodedDescriptors, Decoder};
 use mz_ore::cast::CastFrom;
 use prost::Message;
 
@@ -68,6 +68,7 @@ pub fn bench_protobuf(c: &mut Criterion) {
         DecodedDescriptors::from_bytes(
             &include_bytes!(concat!(env!("OUT_DIR"), "/file_descriptor_set.pb"))[..],
             ".benchmark.Record".to_string(),
+            DecodeOptions::default(),
         )
         .unwrap(),
         false,
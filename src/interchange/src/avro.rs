@@ -13,7 +13,7 @@ mod decode;
 mod encode;
 mod schema;
 
-pub use crate::avro::decode::{Decoder, DiffPair};
+pub use crate::avro::decode::{DecodeOptions, Decoder, DiffPair};
 pub use crate::avro::encode::{
     encode_datums_as_avro, encode_debezium_transaction_unchecked, get_debezium_transaction_schema,
     AvroEncoder, AvroSchemaGenerator, AvroSchemaOptions, DocTarget,
@@ -31,7 +31,7 @@ mod tests {
     use mz_repr::adt::date::Date;
     use mz_repr::adt::numeric::{self, NumericMaxScale};
     use mz_repr::adt::timestamp::CheckedTimestamp;
-    use mz_repr::{Datum, RelationDesc, ScalarType};
+    use mz_repr::{ColumnType, Datum, RelationDesc, ScalarType};
     use ordered_float::OrderedFloat;
 
     use super::*;
@@ -44,7 +44,7 @@ mod tests {
             "fields": []
         }"#;
 
-        let desc = schema_to_relationdesc(parse_schema(schema)?)?;
+        let desc = schema_to_relationdesc(parse_schema(schema)?, DecodeOptions::default())?;
         assert_eq!(desc.arity(), 0, "empty record produced rows");
 
         Ok(())
@@ -61,7 +61,7 @@ mod tests {
             ]
         }"#;
 
-        let desc = schema_to_relationdesc(parse_schema(schema)?)?;
+        let desc = schema_to_relationdesc(parse_schema(schema)?, DecodeOptions::default())?;
         let expected_desc = RelationDesc::empty()
             .with_column("f1", ScalarType::Int32.nullable(false))
             .with_column("f2", ScalarType::String.nullable(false));
@@ -70,6 +70,106 @@ mod tests {
         Ok(())
     }
 
+    #[mz_ore::test]
+    fn decimal_record() -> anyhow::Result<()> {
+        let schema = r#"{
+            "type": "record",
+            "name": "test",
+            "fields": [
+                { "name": "f1", "type": { "type": "bytes", "logicalType": "decimal", "precision": 10, "scale": 2 } },
+                { "name": "f2", "type": { "type": "fixed", "name": "dec", "size": 16, "logicalType": "decimal", "precision": 38, "scale": 10 } }
+            ]
+        }"#;
+
+        let desc = schema_to_relationdesc(parse_schema(schema)?, DecodeOptions::default())?;
+        let expected_desc = RelationDesc::empty()
+            .with_column(
+                "f1",
+                ScalarType::Numeric {
+                    max_scale: Some(NumericMaxScale::try_from(2_i64)?),
+                }
+                .nullable(false),
+            )
+            .with_column(
+                "f2",
+                ScalarType::Numeric {
+                    max_scale: Some(NumericMaxScale::try_from(10_i64)?),
+                }
+                .nullable(false),
+            );
+
+        assert_eq!(desc, expected_desc);
+        Ok(())
+    }
+
+    #[mz_ore::test]
+    fn union_columns() -> anyhow::Result<()> {
+        let schema = r#"{
+            "type": "record",
+            "name": "test",
+            "fields": [
+                { "name": "u", "type": ["null", "int", { "type": "record", "name": "r", "fields": [] }] },
+                { "name": "a", "type": { "type": "array", "items": ["int", "string"] } }
+            ]
+        }"#;
+        let int = ScalarType::Int32.nullable(true);
+        let record = ScalarType::Record {
+            fields: vec![],
+            custom_id: None,
+        };
+        let element = |fields: Vec<(&str, ColumnType)>| ScalarType::List {
+            element_type: Box::new(ScalarType::Record {
+                fields: fields.into_iter().map(|(n, t)| (n.into(), t)).collect(),
+                custom_id: None,
+            }),
+            custom_id: None,
+        };
+
+        let desc = schema_to_relationdesc(parse_schema(schema)?, DecodeOptions::default())?;
+        let expected_desc = RelationDesc::empty()
+            .with_column("u1", int.clone())
+            .with_column("u2", record.clone().nullable(true))
+            .with_column(
+                "a",
+                element(vec![
+                    ("int", int.clone()),
+                    ("string", ScalarType::String.nullable(true)),
+                ])
+                .nullable(false),
+            );
+        assert_eq!(desc, expected_desc);
+
+        let options = DecodeOptions {
+            union_discriminant: true,
+            union_type_names: true,
+        };
+        let desc = schema_to_relationdesc(parse_schema(schema)?, options)?;
+        let expected_desc = RelationDesc::empty()
+            .with_column("u_branch", int.clone())
+            .with_column("u_int", int.clone())
+            .with_column("u_r", record.nullable(true))
+            .with_column(
+                "a",
+                element(vec![
+                    ("branch", int.clone()),
+                    ("int", int),
+                    ("string", ScalarType::String.nullable(true)),
+                ])
+                .nullable(false),
+            );
+        assert_eq!(desc, expected_desc);
+
+        let schema =
+            r#"["int", {"type": "array", "items": "int"}, {"type": "array", "items": "long"}]"#;
+        let options = DecodeOptions {
+            union_discriminant: false,
+            union_type_names: true,
+        };
+        assert!(schema_to_relationdesc(parse_schema(schema)?, options).is_err());
+
+        Ok(())
+    }
+
     #[mz_ore::test]
     #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `decNumberFromInt32` on OS `linux`
     /// Test that primitive Avro Schema types are allow Datums to be correctly
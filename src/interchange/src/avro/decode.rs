@@ -31,6 +31,21 @@ use uuid::Uuid;
 
 use crate::avro::ConfluentAvroResolver;
 
+/// Options that control how Avro values are decoded.
+///
+/// These affect the columns of the decoded relation; see the documentation of
+/// the [schema module](crate::avro::schema) for details.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Whether unions with more than one non-null variant are decoded with an
+    /// additional column holding the number of the present variant.
+    pub union_discriminant: bool,
+    /// Whether the columns for the variants of unions with more than one
+    /// non-null variant are named after the variant types rather than
+    /// numbered.
+    pub union_type_names: bool,
+}
+
 /// Manages decoding of Avro-encoded bytes.
 #[derive(Debug)]
 pub struct Decoder {
@@ -38,13 +53,15 @@ pub struct Decoder {
     debug_name: String,
     buf1: Vec<u8>,
     row_buf: Row,
+    options: DecodeOptions,
 }
 
 #[cfg(test)]
 mod tests {
+    use mz_avro::types::Value;
     use mz_repr::{Datum, Row};
 
-    use crate::avro::Decoder;
+    use crate::avro::{parse_schema, DecodeOptions, Decoder};
 
     #[mz_ore::test(tokio::test)]
     async fn test_error_followed_by_success() {
@@ -53,7 +70,14 @@ mod tests {
 "name": "test",
 "fields": [{"name": "f1", "type": "int"}, {"name": "f2", "type": "int"}]
 }"#;
-        let mut decoder = Decoder::new(schema, None, "Test".to_string(), false).unwrap();
+        let mut decoder = Decoder::new(
+            schema,
+            None,
+            "Test".to_string(),
+            false,
+            DecodeOptions::default(),
+        )
+        .unwrap();
         // This is not a valid Avro blob for the given schema
         let mut bad_bytes: &[u8] = &[0];
         assert!(decoder.decode(&mut bad_bytes).await.unwrap().is_err());
@@ -65,6 +89,51 @@ mod tests {
             Row::pack([Datum::Int32(0), Datum::Int32(0)])
         );
     }
+
+    #[mz_ore::test(tokio::test)]
+    async fn test_union_discriminant() {
+        let schema = r#"{
+"type": "record",
+"name": "test",
+"fields": [
+    {"name": "u", "type": ["null", "int", "string"]},
+    {"name": "a", "type": {"type": "array", "items": ["int", "string"]}}
+]
+}"#;
+        let union = |index, inner, n_variants, null_variant| Value::Union {
+            index,
+            inner: Box::new(inner),
+            n_variants,
+            null_variant,
+        };
+        let record = Value::Record(vec![
+            ("u".into(), union(2, Value::String("x".into()), 3, Some(0))),
+            (
+                "a".into(),
+                Value::Array(vec![
+                    union(0, Value::Int(1), 2, None),
+                    union(1, Value::String("y".into()), 2, None),
+                ]),
+            ),
+        ]);
+        let bytes = mz_avro::to_avro_datum(&parse_schema(schema).unwrap(), record).unwrap();
+
+        let options = DecodeOptions {
+            union_discriminant: true,
+            union_type_names: true,
+        };
+        let mut decoder = Decoder::new(schema, None, "Test".to_string(), false, options).unwrap();
+        let row = decoder.decode(&mut &bytes[..]).await.unwrap().unwrap();
+
+        let mut expected = Row::default();
+        let mut packer = expected.packer();
+        packer.extend([Datum::Int32(2), Datum::Null, Datum::String("x")]);
+        packer.push_list_with(|packer| {
+            packer.push_list([Datum::Int32(1), Datum::Int32(1), Datum::Null]);
+            packer.push_list([Datum::Int32(2), Datum::Null, Datum::String("y")]);
+        });
+        assert_eq!(row, expected);
+    }
 }
 
 impl Decoder {
@@ -78,6 +147,7 @@ impl Decoder {
         ccsr_client: Option<mz_ccsr::Client>,
         debug_name: String,
         confluent_wire_format: bool,
+        options: DecodeOptions,
     ) -> anyhow::Result<Decoder> {
         let csr_avro =
             ConfluentAvroResolver::new(reader_schema, ccsr_client, confluent_wire_format)?;
@@ -87,6 +157,7 @@ impl Decoder {
             debug_name,
             buf1: vec![],
             row_buf: Row::default(),
+            options,
         })
     }
 
@@ -107,6 +178,8 @@ impl Decoder {
             packer: &mut packer,
             buf: &mut self.buf1,
             is_top: true,
+            is_element: false,
+            options: self.options,
         };
         let dsr = GeneralDeserializer {
             schema: resolved_schema.top_node(),
@@ -186,6 +259,8 @@ impl<'a, 'row> AvroDecode for OptionalRecordDecoder<'a, 'row> {
                 packer: self.packer,
                 buf: self.buf,
                 is_top: false,
+                is_element: false,
+                options: DecodeOptions::default(),
             };
             deserializer.deserialize(reader, d)?;
             Ok(true)
@@ -213,6 +288,8 @@ impl AvroDecode for RowDecoder {
             packer: &mut packer,
             buf: &mut buf_borrow,
             is_top: true,
+            is_element: false,
+            options: DecodeOptions::default(),
         };
         inner.record(a)?;
         Ok(RowWrapper(row_borrow.clone()))
@@ -242,6 +319,10 @@ pub struct AvroFlatDecoder<'a, 'row> {
     pub packer: &'a mut RowPacker<'row>,
     pub buf: &'a mut Vec<u8>,
     pub is_top: bool,
+    /// Whether the value is an array element or map value, in which case
+    /// unions with more than one non-null variant are decoded as records.
+    pub is_element: bool,
+    pub options: DecodeOptions,
 }
 
 impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
@@ -252,6 +333,7 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
         a: &mut A,
     ) -> Result<Self::Out, AvroError> {
         let mut str_buf = std::mem::take(self.buf);
+        let options = self.options;
         let mut pack_record = |rp: &mut RowPacker| -> Result<(), AvroError> {
             let mut expected = 0;
             let mut stash = vec![];
@@ -271,6 +353,8 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
                         packer: rp,
                         buf: &mut str_buf,
                         is_top: false,
+                        is_element: false,
+                        options,
                     })?;
                 } else {
                     let val = f.decode_field(ValueDecoder)?;
@@ -285,6 +369,8 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
                     packer: rp,
                     buf: &mut str_buf,
                     is_top: false,
+                    is_element: false,
+                    options,
                 };
                 give_value(dec, &val)?;
             }
@@ -307,26 +393,56 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
         deserializer: D,
         reader: &'b mut R,
     ) -> Result<Self::Out, AvroError> {
-        if null_variant == Some(idx) {
-            for _ in 0..n_variants - 1 {
-                self.packer.push(Datum::Null)
+        let n_non_null = n_variants - usize::from(null_variant.is_some());
+        let is_essential = n_non_null > 1;
+        if self.is_element && is_essential && null_variant == Some(idx) {
+            self.packer.push(Datum::Null);
+            return Ok(());
+        }
+        let options = self.options;
+        let buf = self.buf;
+        let pack_variants = |rp: &mut RowPacker| -> Result<(), AvroError> {
+            if is_essential && options.union_discriminant {
+                if null_variant == Some(idx) {
+                    rp.push(Datum::Null);
+                } else {
+                    // The 1-based number of the variant among the non-null variants.
+                    let branch = idx + 1 - usize::from(null_variant.map_or(false, |n| n < idx));
+                    let branch = i32::try_from(branch).map_err(|_| {
+                        DecodeError::Custom(format!("union variant {} out of range", branch))
+                    })?;
+                    rp.push(Datum::Int32(branch));
+                }
             }
-        } else {
-            let mut deserializer = Some(deserializer);
-            for i in 0..n_variants {
-                let dec = AvroFlatDecoder {
-                    packer: self.packer,
-                    buf: self.buf,
-                    is_top: false,
-                };
-                if null_variant != Some(i) {
-                    if i == idx {
-                        deserializer.take().unwrap().deserialize(reader, dec)?;
-                    } else {
-                        self.packer.push(Datum::Null)
+            if null_variant == Some(idx) {
+                for _ in 0..n_non_null {
+                    rp.push(Datum::Null)
+                }
+            } else {
+                let mut deserializer = Some(deserializer);
+                for i in 0..n_variants {
+                    let dec = AvroFlatDecoder {
+                        packer: rp,
+                        buf,
+                        is_top: false,
+                        is_element: false,
+                        options,
+                    };
+                    if null_variant != Some(i) {
+                        if i == idx {
+                            deserializer.take().unwrap().deserialize(reader, dec)?;
+                        } else {
+                            rp.push(Datum::Null)
+                        }
                     }
                 }
             }
+            Ok(())
+        };
+        if self.is_element && is_essential {
+            self.packer.push_list_with(pack_variants)?;
+        } else {
+            pack_variants(self.packer)?;
         }
         Ok(())
     }
@@ -509,12 +625,15 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
     fn array<A: AvroArrayAccess>(mut self, a: &mut A) -> Result<Self::Out, AvroError> {
         self.is_top = false;
         let mut str_buf = std::mem::take(self.buf);
+        let options = self.options;
         self.packer.push_list_with(|rp| -> Result<(), AvroError> {
             loop {
                 let next = AvroFlatDecoder {
                     packer: rp,
                     buf: &mut str_buf,
                     is_top: false,
+                    is_element: true,
+                    options,
                 };
                 if a.decode_next(next)?.is_none() {
                     break;
@@ -532,6 +651,7 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
         while let Some((name, f)) = a.next_entry()? {
             map.insert(name, f.decode_field(ValueDecoder)?);
         }
+        let options = self.options;
         self.packer
             .push_dict_with(|packer| -> Result<(), AvroError> {
                 for (key, val) in map {
@@ -541,6 +661,8 @@ impl<'a, 'row> AvroDecode for AvroFlatDecoder<'a, 'row> {
                             packer,
                             buf: &mut vec![],
                             is_top: false,
+                            is_element: true,
+                            options,
                         },
                         &val,
                     )?;
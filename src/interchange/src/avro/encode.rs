@@ -11,7 +11,6 @@ use std::collections::BTreeMap;
 use std::fmt;
 
 use anyhow::Ok;
-use byteorder::{NetworkEndian, WriteBytesExt};
 use chrono::Timelike;
 use itertools::Itertools;
 use mz_avro::types::{DecimalValue, ToAvro, Value};
@@ -82,17 +81,6 @@ static DEBEZIUM_TRANSACTION_SCHEMA: Lazy<Schema> = Lazy::new(|| {
     .expect("valid schema constructed")
 });
 
-fn encode_avro_header(buf: &mut Vec<u8>, schema_id: i32) {
-    // The first byte is a magic byte (0) that indicates the Confluent
-    // serialization format version, and the next four bytes are a
-    // 32-bit schema ID.
-    //
-    // https://docs.confluent.io/current/schema-registry/docs/serializer-formatter.html#wire-format
-    buf.write_u8(0).expect("writing to vec cannot fail");
-    buf.write_i32::<NetworkEndian>(schema_id)
-        .expect("writing to vec cannot fail");
-}
-
 #[derive(Debug)]
 struct KeyInfo {
     columns: Vec<(ColumnName, ColumnType)>,
@@ -106,7 +94,7 @@ fn encode_message_unchecked(
     columns: &[(ColumnName, ColumnType)],
 ) -> Vec<u8> {
     let mut buf = vec![];
-    encode_avro_header(&mut buf, schema_id);
+    mz_avro::write_confluent_header(schema_id, &mut buf);
     let value = encode_datums_as_avro(row.iter(), columns);
     mz_avro::encode_unchecked(&value, schema, &mut buf);
     buf
@@ -122,6 +110,8 @@ pub struct AvroSchemaOptions {
     pub set_null_defaults: bool,
     /// Boolean flag to indicate debezium envelope
     pub is_debezium: bool,
+    /// Boolean flag to include the `transaction` field in the debezium envelope
+    pub debezium_transaction: bool,
     /// The global ID of the item in the sink. This is used
     /// to lookup corresponding documentation for objects and fields
     /// in the `value_doc_options` and `key_doc_options`.
@@ -163,6 +153,7 @@ impl AvroSchemaGenerator {
         value_desc: RelationDesc,
         AvroSchemaOptions {
             is_debezium,
+            debezium_transaction,
             avro_value_fullname,
             avro_key_fullname,
             set_null_defaults,
@@ -173,7 +164,7 @@ impl AvroSchemaGenerator {
     ) -> Result<Self, anyhow::Error> {
         let mut value_columns = column_names_and_types(value_desc);
         if is_debezium {
-            value_columns = envelopes::dbz_envelope(value_columns);
+            value_columns = envelopes::dbz_envelope(value_columns, debezium_transaction);
             // With DEBEZIUM envelope the message is wrapped into "before" and "after"
             // with `DBZ_ROW_TYPE_ID` instead of `sink_from`.
             // Replacing comments for the columns and type in `sink_from` to `DBZ_ROW_TYPE_ID`.
@@ -516,7 +507,7 @@ pub fn encode_debezium_transaction_unchecked(
     message_count: Option<i64>,
 ) -> Vec<u8> {
     let mut buf = Vec::new();
-    encode_avro_header(&mut buf, schema_id);
+    mz_avro::write_confluent_header(schema_id, &mut buf);
 
     let transaction_id = Value::String(id.to_owned());
     let status = Value::String(status.to_owned());
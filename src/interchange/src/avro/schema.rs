@@ -29,11 +29,15 @@
 //! it to _n_ columns in SQL, where _n_ is the number of non-null variants in the union. These
 //! columns will be given names created by pasting their index at the end of the overall name
 //! of the field. For example, if an Essential Union in a field named `"Foo"` has schema `[int, bool]`, it will expand to the columns `"Foo1": bool, "Foo2": int`. There is an implicit constraint upheld be the source pipeline that only one such column will be non-`null` at a time
+//! If [`DecodeOptions::union_type_names`] is set, the columns are instead named after the
+//! type of each variant, e.g. `"Foo_int"` and `"Foo_boolean"`. If
+//! [`DecodeOptions::union_discriminant`] is set, the columns are preceded by an additional
+//! `"Foo_branch"` column holding the (1-based) number of the non-null variant that is present.
 //!
-//! When an Essential Union appears _elsewhere_ than as one of the fields of a record,
-//! there is nothing we can do, because we expect to be able to turn it into exactly one
-//! SQL type, not a series of them. Thus, in these cases, we just bail. For example, it's
-//! not possible to ingest an array or map whose element type is an Essential Union.
+//! When an Essential Union appears _elsewhere_ than as one of the fields of a record, e.g. as
+//! the element type of an array or the value type of a map, we expect to be able to turn it
+//! into exactly one SQL type, not a series of them. In these cases the columns described above
+//! become the fields of a record type, named after the type of each variant.
 
 use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, BTreeSet};
@@ -48,11 +52,13 @@ use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_ore::future::OreFutureExt;
 use mz_ore::retry::Retry;
+use mz_ore::str::StrExt;
 use mz_repr::adt::numeric::{NumericMaxScale, NUMERIC_DATUM_MAX_PRECISION};
 use mz_repr::adt::timestamp::TimestampPrecision;
 use mz_repr::{ColumnName, ColumnType, RelationDesc, ScalarType};
 use tracing::warn;
 
+use crate::avro::decode::DecodeOptions;
 use crate::avro::is_null;
 
 pub fn parse_schema(schema: &str) -> anyhow::Result<Schema> {
@@ -62,17 +68,24 @@ pub fn parse_schema(schema: &str) -> anyhow::Result<Schema> {
 
 /// Converts an Apache Avro schema into a list of column names and types.
 // TODO(petrosagg): find a way to make this a TryFrom impl somewhere
-pub fn schema_to_relationdesc(schema: Schema) -> Result<RelationDesc, anyhow::Error> {
+pub fn schema_to_relationdesc(
+    schema: Schema,
+    options: DecodeOptions,
+) -> Result<RelationDesc, anyhow::Error> {
     // TODO(petrosagg): call directly into validate_schema_2 and do the Record flattening once
     // we're in RelationDesc land
     Ok(RelationDesc::from_names_and_types(validate_schema_1(
         schema.top_node(),
+        options,
     )?))
 }
 
 /// Convert an Avro schema to a series of columns and names, flattening the top-level record,
 /// if the top node is indeed a record.
-fn validate_schema_1(schema: SchemaNode) -> anyhow::Result<Vec<(ColumnName, ColumnType)>> {
+fn validate_schema_1(
+    schema: SchemaNode,
+    options: DecodeOptions,
+) -> anyhow::Result<Vec<(ColumnName, ColumnType)>> {
     let mut columns = vec![];
     let mut seen_avro_nodes = Default::default();
     match schema.inner {
@@ -82,11 +95,17 @@ fn validate_schema_1(schema: SchemaNode) -> anyhow::Result<Vec<(ColumnName, Colu
                     &mut seen_avro_nodes,
                     schema.step(&f.schema),
                     Some(&f.name),
+                    options,
                 )?);
             }
         }
         _ => {
-            columns.extend(get_named_columns(&mut seen_avro_nodes, schema, None)?);
+            columns.extend(get_named_columns(
+                &mut seen_avro_nodes,
+                schema,
+                None,
+                options,
+            )?);
         }
     }
     Ok(columns)
@@ -94,10 +113,15 @@ fn validate_schema_1(schema: SchemaNode) -> anyhow::Result<Vec<(ColumnName, Colu
 
 /// Get the series of (one or more) SQL columns corresponding to an Avro union.
 /// See module comments for details.
+///
+/// If `type_names` is set, the columns for the variants of an Essential Union
+/// are named after the variant types rather than numbered.
 fn get_union_columns<'a>(
     seen_avro_nodes: &mut BTreeSet<usize>,
     schema: SchemaNode<'a>,
     base_name: Option<&str>,
+    type_names: bool,
+    options: DecodeOptions,
 ) -> anyhow::Result<Vec<(ColumnName, ColumnType)>> {
     let us = match schema.inner {
         SchemaPiece::Union(us) => us,
@@ -108,6 +132,14 @@ fn get_union_columns<'a>(
     if vs.is_empty() || (vs.len() == 1 && is_null(&vs[0])) {
         bail!(anyhow!("Empty or null-only unions are not supported"));
     } else {
+        let is_essential = vs.iter().filter(|v| !is_null(v)).count() > 1;
+        if is_essential && options.union_discriminant {
+            let name = match base_name {
+                Some(base_name) => format!("{}_branch", base_name),
+                None => "branch".into(),
+            };
+            columns.push((name.into(), ScalarType::Int32.nullable(true)));
+        }
         for (i, v) in vs.iter().filter(|v| !is_null(v)).enumerate() {
             let named_idx = match v {
                 SchemaPieceOrNamed::Named(idx) => Some(*idx),
@@ -126,7 +158,7 @@ fn get_union_columns<'a>(
                 unreachable!("Internal error: directly nested avro union!");
             }
 
-            let name = if vs.len() == 1 || (vs.len() == 2 && vs.iter().any(is_null)) {
+            let name = if !is_essential {
                 // There is only one non-null variant in the
                 // union, so we can use the field name directly.
                 base_name
@@ -137,6 +169,15 @@ fn get_union_columns<'a>(
                             .map(|full_name| full_name.base_name().to_owned())
                     })
                     .unwrap_or_else(|| "?column?".into())
+            } else if type_names {
+                // There are multiple non-null variants in the
+                // union, so we name the columns after the type of
+                // each variant.
+                let type_name = variant_type_name(schema.root, v);
+                match base_name {
+                    Some(base_name) => format!("{}_{}", base_name, type_name),
+                    None => type_name.to_owned(),
+                }
             } else {
                 // There are multiple non-null variants in the
                 // union, so we need to invent field names for
@@ -154,25 +195,68 @@ fn get_union_columns<'a>(
             // If there is more than one variant in the union,
             // the column's output type is nullable, as this
             // column will be null whenever it is uninhabited.
-            let ty = validate_schema_2(seen_avro_nodes, node)?;
+            let ty = validate_schema_2(seen_avro_nodes, node, options)?;
             columns.push((name.into(), ty.nullable(vs.len() > 1)));
             if let Some(named_idx) = named_idx {
                 seen_avro_nodes.remove(&named_idx);
             }
         }
     }
+    if type_names {
+        let mut names = BTreeSet::new();
+        for (name, _) in &columns {
+            if !names.insert(name) {
+                bail!(
+                    "Union variants cannot be named after their types: duplicate column name {}",
+                    name.as_str().quoted()
+                );
+            }
+        }
+    }
     Ok(columns)
 }
 
+/// A name for the type of a union variant.
+fn variant_type_name<'a>(root: &'a Schema, variant: &'a SchemaPieceOrNamed) -> &'a str {
+    match variant.get_piece_and_name(root) {
+        (_, Some(full_name)) => full_name.base_name(),
+        (piece, None) => match piece {
+            SchemaPiece::Null => "null",
+            SchemaPiece::Boolean => "boolean",
+            SchemaPiece::Int => "int",
+            SchemaPiece::Long => "long",
+            SchemaPiece::Float => "float",
+            SchemaPiece::Double => "double",
+            SchemaPiece::Date => "date",
+            SchemaPiece::TimestampMilli | SchemaPiece::TimestampMicro => "timestamp",
+            SchemaPiece::Decimal { .. } => "decimal",
+            SchemaPiece::Bytes => "bytes",
+            SchemaPiece::String => "string",
+            SchemaPiece::Json => "json",
+            SchemaPiece::Uuid => "uuid",
+            SchemaPiece::Array(_) => "array",
+            SchemaPiece::Map(_) => "map",
+            _ => "?column?",
+        },
+    }
+}
+
 fn get_named_columns<'a>(
     seen_avro_nodes: &mut BTreeSet<usize>,
     schema: SchemaNode<'a>,
     base_name: Option<&str>,
+    options: DecodeOptions,
 ) -> anyhow::Result<Vec<(ColumnName, ColumnType)>> {
     if let SchemaPiece::Union(_) = schema.inner {
-        get_union_columns(seen_avro_nodes, schema, base_name)
+        get_union_columns(
+            seen_avro_nodes,
+            schema,
+            base_name,
+            options.union_type_names,
+            options,
+        )
     } else {
-        let scalar_type = validate_schema_2(seen_avro_nodes, schema)?;
+        let scalar_type = validate_schema_2(seen_avro_nodes, schema, options)?;
         Ok(vec![(
             // TODO(benesch): we should do better than this when there's no base
             // name, e.g., invent a name based on the type.
@@ -183,17 +267,23 @@ fn get_named_columns<'a>(
 }
 
 /// Get the single column corresponding to a schema node.
-/// It is an error if this node should correspond to more than one column
-/// (because it is an Essential Union in the sense described in the module docs).
+/// If this node is an Essential Union in the sense described in the module docs,
+/// the column is a record with a field for each variant.
 fn validate_schema_2(
     seen_avro_nodes: &mut BTreeSet<usize>,
     schema: SchemaNode,
+    options: DecodeOptions,
 ) -> anyhow::Result<ScalarType> {
     Ok(match schema.inner {
         SchemaPiece::Union(_) => {
-            let columns = get_union_columns(seen_avro_nodes, schema, None)?;
+            // There is no field name to number, so always name the fields
+            // after the variant types.
+            let columns = get_union_columns(seen_avro_nodes, schema, None, true, options)?;
             if columns.len() != 1 {
-                bail!("Union of more than one non-null type not valid here");
+                return Ok(ScalarType::Record {
+                    fields: columns,
+                    custom_id: None,
+                });
             }
             let (_column_name, column_type) = columns.into_element();
             // It's okay to lose the nullability information here, as it's not relevant to
@@ -252,7 +342,8 @@ fn validate_schema_2(
                 }
                 let next_node = schema.step(&f.schema);
                 columns.extend(
-                    get_named_columns(seen_avro_nodes, next_node, Some(&f.name))?.into_iter(),
+                    get_named_columns(seen_avro_nodes, next_node, Some(&f.name), options)?
+                        .into_iter(),
                 );
                 if let Some(named_idx) = named_idx {
                     seen_avro_nodes.remove(&named_idx);
@@ -278,7 +369,7 @@ fn validate_schema_2(
             }
             let next_node = schema.step(inner);
             let ret = ScalarType::List {
-                element_type: Box::new(validate_schema_2(seen_avro_nodes, next_node)?),
+                element_type: Box::new(validate_schema_2(seen_avro_nodes, next_node, options)?),
                 custom_id: None,
             };
             if let Some(named_idx) = named_idx {
@@ -287,7 +378,11 @@ fn validate_schema_2(
             ret
         }
         SchemaPiece::Map(inner) => ScalarType::Map {
-            value_type: Box::new(validate_schema_2(seen_avro_nodes, schema.step(inner))?),
+            value_type: Box::new(validate_schema_2(
+                seen_avro_nodes,
+                schema.step(inner),
+                options,
+            )?),
             custom_id: None,
         },
 
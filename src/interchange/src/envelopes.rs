@@ -148,6 +148,7 @@ pub static ENVELOPE_CUSTOM_NAMES: Lazy<BTreeMap<GlobalId, String>> = Lazy::new(|
 
 pub(crate) fn dbz_envelope(
     names_and_types: Vec<(ColumnName, ColumnType)>,
+    include_transaction: bool,
 ) -> Vec<(ColumnName, ColumnType)> {
     let row = ColumnType {
         nullable: true,
@@ -156,7 +157,24 @@ pub(crate) fn dbz_envelope(
             custom_id: Some(DBZ_ROW_TYPE_ID),
         },
     };
-    vec![("before".into(), row.clone()), ("after".into(), row)]
+    let mut columns = vec![("before".into(), row.clone()), ("after".into(), row)];
+    if include_transaction {
+        let transaction = ColumnType {
+            nullable: false,
+            scalar_type: ScalarType::Record {
+                fields: vec![(
+                    "id".into(),
+                    ColumnType {
+                        nullable: false,
+                        scalar_type: ScalarType::String,
+                    },
+                )],
+                custom_id: Some(TRANSACTION_TYPE_ID),
+            },
+        };
+        columns.push(("transaction".into(), transaction));
+    }
+    columns
 }
 
 pub fn dbz_format(rp: &mut RowPacker, dp: DiffPair<Row>) {
@@ -171,3 +189,9 @@ pub fn dbz_format(rp: &mut RowPacker, dp: DiffPair<Row>) {
         rp.push(Datum::Null);
     }
 }
+
+/// Appends the `transaction` field of a Debezium envelope produced by
+/// [`dbz_envelope`] with `include_transaction` set.
+pub fn dbz_format_transaction(rp: &mut RowPacker, transaction_id: &str) {
+    rp.push_list_with(|rp| rp.push(Datum::String(transaction_id)));
+}
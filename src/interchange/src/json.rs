@@ -31,10 +31,15 @@ pub struct JsonEncoder {
 }
 
 impl JsonEncoder {
-    pub fn new(key_desc: Option<RelationDesc>, value_desc: RelationDesc, debezium: bool) -> Self {
+    pub fn new(
+        key_desc: Option<RelationDesc>,
+        value_desc: RelationDesc,
+        debezium: bool,
+        debezium_transaction: bool,
+    ) -> Self {
         let mut value_columns = column_names_and_types(value_desc);
         if debezium {
-            value_columns = envelopes::dbz_envelope(value_columns);
+            value_columns = envelopes::dbz_envelope(value_columns, debezium_transaction);
         }
         JsonEncoder {
             key_columns: if let Some(desc) = key_desc {
@@ -10,25 +10,48 @@
 use std::collections::BTreeSet;
 
 use anyhow::{anyhow, bail, Context};
+use chrono::DateTime;
 use mz_ore::str::StrExt;
+use mz_repr::adt::interval::Interval;
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::{ColumnName, ColumnType, Datum, Row, RowPacker, ScalarType};
 use prost_reflect::{
     Cardinality, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor,
-    ReflectMessage, Value,
+    ReflectMessage, SerializeOptions, Value,
 };
 
+/// Options that control how Protobuf messages are decoded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Whether to decode the well-known types `google.protobuf.Timestamp`,
+    /// `Duration`, `Struct`, `Value`, `ListValue` and the wrapper types into
+    /// native types, rather than into records of their fields.
+    pub well_known_types: bool,
+    /// Whether to decode `google.protobuf.Any` into JSON describing the
+    /// message it contains, rather than into a record of its type URL and
+    /// encoded bytes. The contained message must be described by the
+    /// descriptor set.
+    pub unpack_any: bool,
+}
+
 /// A decoded description of the schema of a Protobuf message.
 #[derive(Debug, PartialEq)]
 pub struct DecodedDescriptors {
     message_descriptor: MessageDescriptor,
     columns: Vec<(ColumnName, ColumnType)>,
     message_name: String,
+    options: DecodeOptions,
 }
 
 impl DecodedDescriptors {
     /// Builds a `DecodedDescriptors` from an encoded `FileDescriptorSet` and
     /// the fully qualified name of a message inside that file descriptor set.
-    pub fn from_bytes(bytes: &[u8], message_name: String) -> Result<Self, anyhow::Error> {
+    pub fn from_bytes(
+        bytes: &[u8],
+        message_name: String,
+        options: DecodeOptions,
+    ) -> Result<Self, anyhow::Error> {
         let fds = DescriptorPool::decode(bytes).context("decoding file descriptor set")?;
         let message_descriptor = fds.get_message_by_name(&message_name).ok_or_else(|| {
             anyhow!(
@@ -41,13 +64,14 @@ impl DecodedDescriptors {
         let mut columns = vec![];
         for field in message_descriptor.fields() {
             let name = ColumnName::from(field.name());
-            let ty = derive_column_type(&mut seen_messages, &field)?;
+            let ty = derive_column_type(&mut seen_messages, &options, &field)?;
             columns.push((name, ty))
         }
         Ok(DecodedDescriptors {
             message_descriptor,
             columns,
             message_name,
+            options,
         })
     }
 
@@ -106,20 +130,59 @@ impl Decoder {
         }
         let message = DynamicMessage::decode(self.descriptors.message_descriptor.clone(), bytes)?;
         let mut packer = self.row.packer();
-        pack_message(&mut packer, &message)?;
+        pack_message(&mut packer, &self.descriptors.options, &message)?;
         Ok(Some(self.row.clone()))
     }
 }
 
+/// A message type that is decoded into a native type rather than a record.
+enum WellKnownType {
+    Timestamp,
+    Duration,
+    /// Decoded into the JSON representation of the message.
+    Json,
+    /// A wrapper type, decoded into its nullable `value` field.
+    Wrapper(FieldDescriptor),
+}
+
+fn well_known_type(options: &DecodeOptions, message: &MessageDescriptor) -> Option<WellKnownType> {
+    if options.unpack_any && message.full_name() == "google.protobuf.Any" {
+        return Some(WellKnownType::Json);
+    }
+    if !options.well_known_types {
+        return None;
+    }
+    match message.full_name() {
+        "google.protobuf.Timestamp" => Some(WellKnownType::Timestamp),
+        "google.protobuf.Duration" => Some(WellKnownType::Duration),
+        "google.protobuf.Struct" | "google.protobuf.Value" | "google.protobuf.ListValue" => {
+            Some(WellKnownType::Json)
+        }
+        "google.protobuf.DoubleValue"
+        | "google.protobuf.FloatValue"
+        | "google.protobuf.Int64Value"
+        | "google.protobuf.UInt64Value"
+        | "google.protobuf.Int32Value"
+        | "google.protobuf.UInt32Value"
+        | "google.protobuf.BoolValue"
+        | "google.protobuf.StringValue"
+        | "google.protobuf.BytesValue" => message
+            .get_field_by_name("value")
+            .map(WellKnownType::Wrapper),
+        _ => None,
+    }
+}
+
 fn derive_column_type(
     seen_messages: &mut BTreeSet<String>,
+    options: &DecodeOptions,
     field: &FieldDescriptor,
 ) -> Result<ColumnType, anyhow::Error> {
     if field.is_map() {
         bail!("Protobuf map fields are not supported");
     }
 
-    let ty = derive_inner_type(seen_messages, field.kind())?;
+    let ty = derive_inner_type(seen_messages, options, field.kind())?;
     if field.is_list() {
         Ok(ColumnType {
             nullable: false,
@@ -135,6 +198,7 @@ fn derive_column_type(
 
 fn derive_inner_type(
     seen_messages: &mut BTreeSet<String>,
+    options: &DecodeOptions,
     ty: Kind,
 ) -> Result<ColumnType, anyhow::Error> {
     match ty {
@@ -149,6 +213,18 @@ fn derive_inner_type(
         Kind::Bytes => Ok(ScalarType::Bytes.nullable(false)),
         Kind::Enum(_) => Ok(ScalarType::String.nullable(false)),
         Kind::Message(m) => {
+            match well_known_type(options, &m) {
+                Some(WellKnownType::Timestamp) => {
+                    return Ok(ScalarType::TimestampTz { precision: None }.nullable(true))
+                }
+                Some(WellKnownType::Duration) => return Ok(ScalarType::Interval.nullable(true)),
+                Some(WellKnownType::Json) => return Ok(ScalarType::Jsonb.nullable(true)),
+                Some(WellKnownType::Wrapper(field)) => {
+                    let ty = derive_inner_type(seen_messages, options, field.kind())?;
+                    return Ok(ty.scalar_type.nullable(true));
+                }
+                None => (),
+            }
             if seen_messages.contains(m.name()) {
                 bail!("Recursive types are not supported: {}", m.name());
             }
@@ -156,7 +232,7 @@ fn derive_inner_type(
             let mut fields = Vec::with_capacity(m.fields().len());
             for field in m.fields() {
                 let column_name = ColumnName::from(field.name());
-                let column_type = derive_column_type(seen_messages, &field)?;
+                let column_type = derive_column_type(seen_messages, options, &field)?;
                 fields.push((column_name, column_type))
             }
             seen_messages.remove(m.name());
@@ -169,7 +245,11 @@ fn derive_inner_type(
     }
 }
 
-fn pack_message(packer: &mut RowPacker, message: &DynamicMessage) -> Result<(), anyhow::Error> {
+fn pack_message(
+    packer: &mut RowPacker,
+    options: &DecodeOptions,
+    message: &DynamicMessage,
+) -> Result<(), anyhow::Error> {
     for field_desc in message.descriptor().fields() {
         if !message.has_field(&field_desc) {
             if field_desc.cardinality() == Cardinality::Required {
@@ -184,13 +264,77 @@ fn pack_message(packer: &mut RowPacker, message: &DynamicMessage) -> Result<(),
             }
         }
         let value = message.get_field(&field_desc);
-        pack_value(packer, &field_desc, &*value)?;
+        pack_value(packer, options, &field_desc, &*value)?;
+    }
+    Ok(())
+}
+
+fn pack_well_known(
+    packer: &mut RowPacker,
+    options: &DecodeOptions,
+    ty: WellKnownType,
+    message: &DynamicMessage,
+) -> Result<(), anyhow::Error> {
+    let get_field = |name: &str| message.get_field_by_name(name);
+    match ty {
+        WellKnownType::Timestamp => {
+            let seconds = get_field("seconds").and_then(|v| v.as_i64()).unwrap_or(0);
+            let nanos = get_field("nanos").and_then(|v| v.as_i32()).unwrap_or(0);
+            let ts = u32::try_from(nanos)
+                .ok()
+                .and_then(|nanos| DateTime::from_timestamp(seconds, nanos))
+                .and_then(|ts| CheckedTimestamp::from_timestamplike(ts).ok())
+                .ok_or_else(|| {
+                    anyhow!(
+                        "error decoding protobuf: timestamp out of range: {} seconds, {} nanos",
+                        seconds,
+                        nanos
+                    )
+                })?;
+            packer.push(Datum::TimestampTz(ts));
+        }
+        WellKnownType::Duration => {
+            let seconds = get_field("seconds").and_then(|v| v.as_i64()).unwrap_or(0);
+            let nanos = get_field("nanos").and_then(|v| v.as_i32()).unwrap_or(0);
+            // Sub-microsecond precision is truncated, as for other intervals.
+            let micros = seconds
+                .checked_mul(1_000_000)
+                .and_then(|micros| micros.checked_add(i64::from(nanos / 1_000)))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "error decoding protobuf: duration out of range: {} seconds, {} nanos",
+                        seconds,
+                        nanos
+                    )
+                })?;
+            packer.push(Datum::Interval(Interval::new(0, 0, micros)));
+        }
+        WellKnownType::Json => {
+            let serialize_options = SerializeOptions::new()
+                .stringify_64_bit_integers(false)
+                .use_proto_field_name(true)
+                .skip_default_fields(false);
+            let json = message
+                .serialize_with_options(serde_json::value::Serializer, &serialize_options)
+                .with_context(|| {
+                    format!(
+                        "error decoding protobuf: converting {} to JSON",
+                        message.descriptor().full_name()
+                    )
+                })?;
+            JsonbPacker::new(packer).pack_serde_json(json)?;
+        }
+        WellKnownType::Wrapper(field_desc) => {
+            let value = message.get_field(&field_desc);
+            pack_value(packer, options, &field_desc, &*value)?;
+        }
     }
     Ok(())
 }
 
 fn pack_value(
     packer: &mut RowPacker,
+    options: &DecodeOptions,
     field_desc: &FieldDescriptor,
     value: &Value,
 ) -> Result<(), anyhow::Error> {
@@ -222,11 +366,14 @@ fn pack_value(
             })?;
             packer.push(Datum::String(value.name()));
         }
-        Value::Message(m) => packer.push_list_with(|packer| pack_message(packer, m))?,
+        Value::Message(m) => match well_known_type(options, &m.descriptor()) {
+            Some(ty) => pack_well_known(packer, options, ty, m)?,
+            None => packer.push_list_with(|packer| pack_message(packer, options, m))?,
+        },
         Value::List(values) => {
             packer.push_list_with(|packer| {
                 for value in values {
-                    pack_value(packer, field_desc, value)?;
+                    pack_value(packer, options, field_desc, value)?;
                 }
                 Ok::<_, anyhow::Error>(())
             })?;
@@ -56,6 +56,9 @@ pub struct MzClientContext {
     /// A tokio watch that retains the last statistics received by rdkafka and provides async
     /// notifications to anyone interested in subscribing.
     statistics_tx: watch::Sender<Statistics>,
+    /// The first message delivery failure reported to a producer since it was
+    /// last taken, if any.
+    delivery_error: Mutex<Option<KafkaError>>,
 }
 
 impl Default for MzClientContext {
@@ -75,6 +78,7 @@ impl MzClientContext {
         let ctx = Self {
             error_tx,
             statistics_tx,
+            delivery_error: Mutex::new(None),
         };
         (ctx, error_rx)
     }
@@ -85,6 +89,15 @@ impl MzClientContext {
         self.statistics_tx.subscribe()
     }
 
+    /// Takes the first message delivery failure reported since the last call,
+    /// if any.
+    ///
+    /// Delivery failures are only reported to producers, and only once the
+    /// failed message has been polled, e.g. by flushing the producer.
+    pub fn take_delivery_error(&self) -> Option<KafkaError> {
+        self.delivery_error.lock().expect("lock poisoned").take()
+    }
+
     fn record_error(&self, msg: &str) {
         let err = match MzKafkaError::from_str(msg) {
             Ok(err) => err,
@@ -268,6 +281,12 @@ impl ProducerContext for MzClientContext {
         delivery_result: &DeliveryResult<'_>,
         delivery_opaque: Self::DeliveryOpaque,
     ) {
+        if let Err((err, _message)) = delivery_result {
+            self.delivery_error
+                .lock()
+                .expect("lock poisoned")
+                .get_or_insert_with(|| err.clone());
+        }
         DefaultProducerContext.delivery(delivery_result, delivery_opaque);
     }
 }
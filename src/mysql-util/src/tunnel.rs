@@ -45,6 +45,9 @@ pub enum TunnelConfig {
     AwsPrivatelink {
         /// The ID of the AWS PrivateLink service.
         connection_id: GlobalId,
+        /// The availability zone of the AWS PrivateLink service endpoint to
+        /// connect to, if restricted to a single zone.
+        availability_zone: Option<String>,
     },
 }
 
@@ -296,8 +299,14 @@ impl Config {
                     _ssh_tunnel_handle: Some(tunnel),
                 })
             }
-            TunnelConfig::AwsPrivatelink { connection_id } => {
-                let privatelink_host = mz_cloud_resources::vpc_endpoint_name(*connection_id);
+            TunnelConfig::AwsPrivatelink {
+                connection_id,
+                availability_zone,
+            } => {
+                let privatelink_host = mz_cloud_resources::vpc_endpoint_host(
+                    *connection_id,
+                    availability_zone.as_deref(),
+                );
 
                 // Override the connection host for the actual TCP connection to point to
                 // the privatelink hostname instead.
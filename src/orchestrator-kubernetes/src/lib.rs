@@ -13,15 +13,15 @@ use std::sync::{Arc, Mutex};
 
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use clap::ArgEnum;
 use cloud_resource_controller::KubernetesResourceReader;
 use futures::stream::{BoxStream, StreamExt};
 use k8s_openapi::api::apps::v1::{StatefulSet, StatefulSetSpec};
 use k8s_openapi::api::core::v1::{
     Affinity, Capabilities, Container, ContainerPort, EnvVar, EnvVarSource, EphemeralVolumeSource,
-    NodeAffinity, NodeSelector, NodeSelectorRequirement, NodeSelectorTerm, ObjectFieldSelector,
-    ObjectReference, PersistentVolumeClaim, PersistentVolumeClaimSpec,
+    Event as K8sEvent, NodeAffinity, NodeSelector, NodeSelectorRequirement, NodeSelectorTerm,
+    ObjectFieldSelector, ObjectReference, PersistentVolumeClaim, PersistentVolumeClaimSpec,
     PersistentVolumeClaimTemplate, Pod, PodAffinity, PodAffinityTerm, PodAntiAffinity,
     PodSecurityContext, PodSpec, PodTemplateSpec, PreferredSchedulingTerm, ResourceRequirements,
     SeccompProfile, Secret, SecurityContext, Service as K8sService, ServicePort, ServiceSpec,
@@ -40,7 +40,7 @@ use mz_cloud_resources::AwsExternalIdPrefix;
 use mz_orchestrator::{
     scheduling_config::*, DiskLimit, LabelSelectionLogic, LabelSelector as MzLabelSelector,
     NamespacedOrchestrator, NotReadyReason, Orchestrator, Service, ServiceConfig, ServiceEvent,
-    ServiceProcessMetrics, ServiceStatus,
+    ServiceProcessEvent, ServiceProcessEventKind, ServiceProcessMetrics, ServiceStatus,
 };
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
@@ -152,6 +152,7 @@ impl Orchestrator for KubernetesOrchestrator {
                 service_api: Api::default_namespaced(self.client.clone()),
                 stateful_set_api: Api::default_namespaced(self.client.clone()),
                 pod_api: Api::default_namespaced(self.client.clone()),
+                event_api: Api::default_namespaced(self.client.clone()),
                 kubernetes_namespace: self.kubernetes_namespace.clone(),
                 namespace: namespace.into(),
                 config: self.config.clone(),
@@ -176,6 +177,7 @@ struct NamespacedKubernetesOrchestrator {
     service_api: Api<K8sService>,
     stateful_set_api: Api<StatefulSet>,
     pod_api: Api<Pod>,
+    event_api: Api<K8sEvent>,
     kubernetes_namespace: String,
     namespace: String,
     config: KubernetesOrchestratorConfig,
@@ -1457,11 +1459,146 @@ impl NamespacedOrchestrator for NamespacedKubernetesOrchestrator {
         Box::pin(stream)
     }
 
+    fn watch_service_process_events(
+        &self,
+    ) -> BoxStream<'static, Result<ServiceProcessEvent, anyhow::Error>> {
+        // Kubernetes retains events for a while, and the watchers below replay the current state
+        // whenever they (re)start. Only report what happens after the watch started, and only
+        // once, so restarts of `environmentd` or the watchers don't duplicate events.
+        let start = Utc::now();
+
+        // Scheduling, image pulls, and evictions are recorded as Kubernetes events on the pod.
+        let prefix = format!("{}-", self.namespace);
+        let mut seen_events = BTreeMap::new();
+        let event_params = watcher::Config::default().fields("involvedObject.kind=Pod");
+        let events = watcher(self.event_api.clone(), event_params)
+            .touched_objects()
+            .filter_map(move |object| {
+                let event = match object {
+                    Ok(event) => {
+                        into_service_process_event(event, &prefix, start, &mut seen_events)
+                    }
+                    Err(error) => {
+                        tracing::warn!("service process event watch error: {error}");
+                        None
+                    }
+                };
+                futures::future::ready(event.map(Ok))
+            });
+
+        // Running out of memory is only recorded in the status of the pod's containers.
+        let prefix = format!("{}-", self.namespace);
+        let mut seen_ooms = BTreeMap::new();
+        let ooms = watcher(self.pod_api.clone(), self.watch_pod_params())
+            .touched_objects()
+            .filter_map(move |object| {
+                let event = match object {
+                    Ok(pod) => into_oom_event(pod, &prefix, start, &mut seen_ooms),
+                    Err(error) => {
+                        tracing::warn!("service process event watch error: {error}");
+                        None
+                    }
+                };
+                futures::future::ready(event.map(Ok))
+            });
+
+        Box::pin(futures::stream::select(events, ooms))
+    }
+
     fn update_scheduling_config(&self, config: ServiceSchedulingConfig) {
         *self.scheduling_config.write().expect("poisoned") = config;
     }
 }
 
+/// Returns the service ID and process ID of the pod with the given name, if it belongs to the
+/// namespace with the given pod name prefix.
+fn parse_pod_name(pod_name: &str, prefix: &str) -> Option<(String, u64)> {
+    let (service_id, process_id) = pod_name.strip_prefix(prefix)?.rsplit_once('-')?;
+    Some((service_id.to_string(), process_id.parse().ok()?))
+}
+
+/// Translates a Kubernetes event about a pod into a [`ServiceProcessEvent`].
+///
+/// Returns `None` for events that aren't interesting, that concern pods of other namespaces, that
+/// happened before `start`, or that were already reported, according to `seen`.
+fn into_service_process_event(
+    event: K8sEvent,
+    prefix: &str,
+    start: DateTime<Utc>,
+    seen: &mut BTreeMap<String, i32>,
+) -> Option<ServiceProcessEvent> {
+    let kind = match event.reason.as_deref()? {
+        "Scheduled" => ServiceProcessEventKind::Scheduled,
+        "Pulled" => ServiceProcessEventKind::ImagePulled,
+        "Preempted" => ServiceProcessEventKind::Preempted,
+        // `TaintManagerEviction` is recorded when the node a pod runs on becomes unavailable.
+        "Evicted" | "TaintManagerEviction" => ServiceProcessEventKind::Evicted,
+        _ => return None,
+    };
+    let (service_id, process_id) = parse_pod_name(event.involved_object.name.as_deref()?, prefix)?;
+    let time = event
+        .last_timestamp
+        .map(|t| t.0)
+        .or(event.event_time.map(|t| t.0))
+        .unwrap_or_else(Utc::now);
+    if time < start {
+        return None;
+    }
+    // Kubernetes merges recurring events into one, bumping its count.
+    let count = event.count.unwrap_or(1);
+    if seen.insert(event.metadata.uid?, count) == Some(count) {
+        return None;
+    }
+
+    Some(ServiceProcessEvent {
+        service_id,
+        process_id,
+        kind,
+        message: event.message,
+        time,
+    })
+}
+
+/// Reports the most recent out-of-memory kill of a container of the given pod as a
+/// [`ServiceProcessEvent`].
+///
+/// Returns `None` if the pod belongs to another namespace, or if its last out-of-memory kill
+/// happened before `start` or was already reported, according to `seen`.
+fn into_oom_event(
+    pod: Pod,
+    prefix: &str,
+    start: DateTime<Utc>,
+    seen: &mut BTreeMap<String, DateTime<Utc>>,
+) -> Option<ServiceProcessEvent> {
+    let pod_name = pod.name_any();
+    let (service_id, process_id) = parse_pod_name(&pod_name, prefix)?;
+    let (time, message) = pod
+        .status?
+        .container_statuses?
+        .into_iter()
+        .flat_map(|cs| {
+            // The container might have already been restarted, in which case its previous state
+            // records why it terminated.
+            let current_state = cs.state.and_then(|s| s.terminated);
+            let last_state = cs.last_state.and_then(|s| s.terminated);
+            current_state.into_iter().chain(last_state)
+        })
+        .filter(|terminated| terminated.reason.as_deref() == Some("OOMKilled"))
+        .filter_map(|terminated| Some((terminated.finished_at?.0, terminated.message)))
+        .max_by_key(|(time, _)| *time)?;
+    if time < start || seen.insert(pod_name, time) == Some(time) {
+        return None;
+    }
+
+    Some(ServiceProcessEvent {
+        service_id,
+        process_id,
+        kind: ServiceProcessEventKind::OomKilled,
+        message,
+        time,
+    })
+}
+
 #[derive(Debug, Clone)]
 struct KubernetesService {
     hosts: Vec<String>,
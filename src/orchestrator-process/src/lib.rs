@@ -34,7 +34,7 @@ use libc::{SIGABRT, SIGBUS, SIGILL, SIGSEGV, SIGTRAP};
 use maplit::btreemap;
 use mz_orchestrator::{
     CpuLimit, MemoryLimit, NamespacedOrchestrator, Orchestrator, Service, ServiceConfig,
-    ServiceEvent, ServiceProcessMetrics, ServiceStatus,
+    ServiceEvent, ServiceProcessEvent, ServiceProcessMetrics, ServiceStatus,
 };
 use mz_ore::cast::{CastFrom, TryCastFrom};
 use mz_ore::error::ErrorExt;
@@ -546,6 +546,15 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
         })
     }
 
+    fn watch_service_process_events(
+        &self,
+    ) -> BoxStream<'static, Result<ServiceProcessEvent, anyhow::Error>> {
+        // Processes run directly on the local machine, so there is no
+        // scheduling or eviction to report, and we can't tell whether a killed
+        // process ran out of memory.
+        Box::pin(futures::stream::empty())
+    }
+
     fn update_scheduling_config(
         &self,
         _config: mz_orchestrator::scheduling_config::ServiceSchedulingConfig,
@@ -26,7 +26,7 @@ use mz_build_info::BuildInfo;
 use mz_orchestrator::ServicePort;
 use mz_orchestrator::{
     NamespacedOrchestrator, Orchestrator, Service, ServiceConfig, ServiceEvent,
-    ServiceProcessMetrics,
+    ServiceProcessEvent, ServiceProcessMetrics,
 };
 use mz_ore::cli::KeyValueArg;
 use mz_ore::metrics::MetricsRegistry;
@@ -510,6 +510,12 @@ impl NamespacedOrchestrator for NamespacedTracingOrchestrator {
         self.inner.watch_services()
     }
 
+    fn watch_service_process_events(
+        &self,
+    ) -> BoxStream<'static, Result<ServiceProcessEvent, anyhow::Error>> {
+        self.inner.watch_service_process_events()
+    }
+
     fn update_scheduling_config(
         &self,
         config: mz_orchestrator::scheduling_config::ServiceSchedulingConfig,
@@ -72,6 +72,13 @@ pub trait NamespacedOrchestrator: fmt::Debug + Send + Sync {
     /// Watch for status changes of all known services.
     fn watch_services(&self) -> BoxStream<'static, Result<ServiceEvent, anyhow::Error>>;
 
+    /// Watch for events that happen to the processes of all known services,
+    /// like being scheduled onto a node or being killed for running out of
+    /// memory.
+    fn watch_service_process_events(
+        &self,
+    ) -> BoxStream<'static, Result<ServiceProcessEvent, anyhow::Error>>;
+
     /// Gets resource usage metrics for all processes associated with a service.
     ///
     /// Returns `Err` if the entire process failed. Returns `Ok(v)` otherwise,
@@ -130,6 +137,48 @@ impl ServiceStatus {
     }
 }
 
+/// An event that happened to a process of an orchestrated service, as
+/// observed by the orchestrator.
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceProcessEvent {
+    pub service_id: String,
+    pub process_id: u64,
+    pub kind: ServiceProcessEventKind,
+    /// A description of the event provided by the orchestrator, if any.
+    pub message: Option<String>,
+    pub time: DateTime<Utc>,
+}
+
+/// The kinds of [`ServiceProcessEvent`]s.
+#[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq)]
+pub enum ServiceProcessEventKind {
+    /// The process was assigned to a node.
+    Scheduled,
+    /// The image of the process was pulled onto its node.
+    ImagePulled,
+    /// The process was killed for exceeding its memory limit.
+    OomKilled,
+    /// The process was evicted to make room for a process with a higher
+    /// priority.
+    Preempted,
+    /// The process was evicted from its node, e.g., because the node became
+    /// unavailable or was drained.
+    Evicted,
+}
+
+impl ServiceProcessEventKind {
+    /// Returns the event kind as a kebab-case string.
+    pub fn as_kebab_case_str(&self) -> &'static str {
+        match self {
+            ServiceProcessEventKind::Scheduled => "scheduled",
+            ServiceProcessEventKind::ImagePulled => "image-pulled",
+            ServiceProcessEventKind::OomKilled => "oom-killed",
+            ServiceProcessEventKind::Preempted => "preempted",
+            ServiceProcessEventKind::Evicted => "evicted",
+        }
+    }
+}
+
 /// Describes a running service managed by an `Orchestrator`.
 pub trait Service: fmt::Debug + Send + Sync {
     /// Given the name of a port, returns the addresses for each of the
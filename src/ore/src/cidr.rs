@@ -0,0 +1,173 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! IP networks in CIDR notation.
+
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+/// An IPv4 or IPv6 network, like `10.0.0.0/8` or `2001:db8::/32`.
+///
+/// The host bits of the address are always zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IpNetwork {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    /// Constructs the network of the given prefix length that contains `addr`.
+    ///
+    /// Returns `None` if the prefix length is longer than the address.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Option<IpNetwork> {
+        let addr = match addr {
+            IpAddr::V4(addr) => IpAddr::V4(u32::from(addr).checked_mask(prefix_len, 32)?.into()),
+            IpAddr::V6(addr) => IpAddr::V6(u128::from(addr).checked_mask(prefix_len, 128)?.into()),
+        };
+        Some(IpNetwork { addr, prefix_len })
+    }
+
+    /// Returns the first address of the network.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the number of leading bits that addresses in the network share.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Reports whether the network contains `addr`.
+    ///
+    /// IPv4-mapped IPv6 addresses, like `::ffff:10.0.0.1`, are treated as
+    /// their IPv4 address.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        let addr = match addr {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(addr),
+            IpAddr::V4(_) => addr,
+        };
+        IpNetwork::new(addr, self.prefix_len).map(|n| n.addr) == Some(self.addr)
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+/// An error while parsing an [`IpNetwork`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpNetworkParseError(String);
+
+impl fmt::Display for IpNetworkParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid IP network {:?}", self.0)
+    }
+}
+
+impl std::error::Error for IpNetworkParseError {}
+
+impl FromStr for IpNetwork {
+    type Err = IpNetworkParseError;
+
+    /// Parses a network in CIDR notation. A bare address is parsed as the
+    /// network that contains only that address. Host bits must be zero.
+    fn from_str(s: &str) -> Result<IpNetwork, IpNetworkParseError> {
+        let err = || IpNetworkParseError(s.into());
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, prefix_len)) => {
+                let addr = IpAddr::from_str(addr).map_err(|_| err())?;
+                let prefix_len = u8::from_str(prefix_len).map_err(|_| err())?;
+                (addr, prefix_len)
+            }
+            None => {
+                let addr = IpAddr::from_str(s).map_err(|_| err())?;
+                let prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+                (addr, prefix_len)
+            }
+        };
+        match IpNetwork::new(addr, prefix_len) {
+            Some(network) if network.addr == addr => Ok(network),
+            _ => Err(err()),
+        }
+    }
+}
+
+trait CheckedMask: Sized {
+    fn checked_mask(self, prefix_len: u8, bits: u8) -> Option<Self>;
+}
+
+impl CheckedMask for u32 {
+    fn checked_mask(self, prefix_len: u8, bits: u8) -> Option<u32> {
+        match prefix_len {
+            0 => Some(0),
+            _ if prefix_len <= bits => Some(self & (u32::MAX << (bits - prefix_len))),
+            _ => None,
+        }
+    }
+}
+
+impl CheckedMask for u128 {
+    fn checked_mask(self, prefix_len: u8, bits: u8) -> Option<u128> {
+        match prefix_len {
+            0 => Some(0),
+            _ if prefix_len <= bits => Some(self & (u128::MAX << (bits - prefix_len))),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[crate::test]
+    fn test_parse() {
+        for (input, expected) in [
+            ("10.0.0.0/8", Some("10.0.0.0/8")),
+            ("10.1.2.3", Some("10.1.2.3/32")),
+            ("0.0.0.0/0", Some("0.0.0.0/0")),
+            ("2001:db8::/32", Some("2001:db8::/32")),
+            ("::1", Some("::1/128")),
+            ("10.0.0.1/8", None),
+            ("10.0.0.0/33", None),
+            ("::/129", None),
+            ("10.0.0.0/", None),
+            ("example.com", None),
+        ] {
+            let actual = input.parse::<IpNetwork>().ok().map(|n| n.to_string());
+            assert_eq!(actual.as_deref(), expected, "parsing {input:?}");
+        }
+    }
+
+    #[crate::test]
+    fn test_contains() {
+        let network: IpNetwork = "10.0.0.0/8".parse().unwrap();
+        assert!(network.contains("10.255.0.1".parse().unwrap()));
+        assert!(network.contains("::ffff:10.0.0.1".parse().unwrap()));
+        assert!(!network.contains("11.0.0.1".parse().unwrap()));
+        assert!(!network.contains("2001:db8::1".parse().unwrap()));
+
+        let network: IpNetwork = "2001:db8::/32".parse().unwrap();
+        assert!(network.contains("2001:db8:ffff::1".parse().unwrap()));
+        assert!(!network.contains("2001:db9::1".parse().unwrap()));
+        assert!(!network.contains("10.0.0.1".parse().unwrap()));
+
+        let network: IpNetwork = "0.0.0.0/0".parse().unwrap();
+        assert!(network.contains("192.168.0.1".parse().unwrap()));
+    }
+}
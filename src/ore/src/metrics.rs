@@ -58,6 +58,8 @@ use prometheus::proto::MetricFamily;
 use prometheus::{HistogramOpts, Registry};
 
 mod delete_on_drop;
+#[cfg(feature = "tracing_")]
+pub mod otlp;
 
 pub use delete_on_drop::*;
 pub use prometheus::Opts as PrometheusOpts;
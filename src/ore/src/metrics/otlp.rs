@@ -0,0 +1,374 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Export of prometheus metrics via the OpenTelemetry Protocol (OTLP).
+//!
+//! This is for deployments that collect metrics with an OpenTelemetry
+//! collector instead of scraping the prometheus endpoints of each process.
+//! Metrics are pushed with cumulative temporality, so they carry the same
+//! information as a scrape of the corresponding prometheus endpoint.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_client::MetricsServiceClient;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue};
+use opentelemetry_proto::tonic::metrics::v1::{
+    metric, number_data_point, AggregationTemporality, Gauge, Histogram, HistogramDataPoint,
+    Metric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use prometheus::proto::{LabelPair, MetricFamily, MetricType};
+use tonic::transport::{Channel, Endpoint};
+
+/// A client that pushes prometheus metrics to an OTLP/gRPC endpoint.
+#[derive(Debug)]
+pub struct OtlpMetricsExporter {
+    client: MetricsServiceClient<Channel>,
+    resource: Vec<(String, String)>,
+    start_time_unix_nano: u64,
+}
+
+impl OtlpMetricsExporter {
+    /// Returns a new exporter for the given OTLP/gRPC endpoint.
+    ///
+    /// The `resource` attributes, e.g. the `service.name`, are attached to
+    /// every export.
+    pub fn new(
+        endpoint: &str,
+        timeout: Duration,
+        resource: Vec<(String, String)>,
+    ) -> Result<Self, anyhow::Error> {
+        let channel = Endpoint::from_shared(endpoint.to_owned())?
+            .timeout(timeout)
+            .connect_lazy();
+        Ok(OtlpMetricsExporter {
+            client: MetricsServiceClient::new(channel),
+            resource,
+            start_time_unix_nano: unix_nanos(SystemTime::now()),
+        })
+    }
+
+    /// Pushes the given metric families to the endpoint.
+    pub async fn export(&mut self, families: &[MetricFamily]) -> Result<(), anyhow::Error> {
+        let req = encode(
+            families,
+            &self.resource,
+            self.start_time_unix_nano,
+            unix_nanos(SystemTime::now()),
+        );
+        let _ = self.client.export(req).await?;
+        Ok(())
+    }
+}
+
+/// Encodes prometheus metric families as an OTLP export request.
+///
+/// Counters become monotonic sums and histograms become explicit bucket
+/// histograms, both with cumulative temporality. Gauges and untyped metrics
+/// become gauges. Summaries aren't supported and are skipped.
+pub fn encode(
+    families: &[MetricFamily],
+    resource: &[(String, String)],
+    start_time_unix_nano: u64,
+    time_unix_nano: u64,
+) -> ExportMetricsServiceRequest {
+    let number_point = |m: &prometheus::proto::Metric, value: f64| NumberDataPoint {
+        attributes: attributes(m.get_label()),
+        start_time_unix_nano,
+        time_unix_nano,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        ..Default::default()
+    };
+
+    let mut metrics = Vec::with_capacity(families.len());
+    for family in families {
+        let data = match family.get_field_type() {
+            MetricType::COUNTER => metric::Data::Sum(Sum {
+                data_points: family
+                    .get_metric()
+                    .iter()
+                    .map(|m| number_point(m, m.get_counter().get_value()))
+                    .collect(),
+                aggregation_temporality: AggregationTemporality::Cumulative.into(),
+                is_monotonic: true,
+            }),
+            MetricType::GAUGE => metric::Data::Gauge(Gauge {
+                data_points: family
+                    .get_metric()
+                    .iter()
+                    .map(|m| number_point(m, m.get_gauge().get_value()))
+                    .collect(),
+            }),
+            MetricType::UNTYPED => metric::Data::Gauge(Gauge {
+                data_points: family
+                    .get_metric()
+                    .iter()
+                    .map(|m| number_point(m, m.get_untyped().get_value()))
+                    .collect(),
+            }),
+            MetricType::HISTOGRAM => metric::Data::Histogram(Histogram {
+                data_points: family
+                    .get_metric()
+                    .iter()
+                    .map(|m| {
+                        let h = m.get_histogram();
+                        // Prometheus buckets are cumulative and exclude the
+                        // implicit +Inf bucket, whereas OTLP bucket counts
+                        // are per bucket and include it.
+                        let mut bucket_counts = Vec::with_capacity(h.get_bucket().len() + 1);
+                        let mut explicit_bounds = Vec::with_capacity(h.get_bucket().len());
+                        let mut prev = 0;
+                        for bucket in h.get_bucket() {
+                            if bucket.get_upper_bound().is_infinite() {
+                                continue;
+                            }
+                            let count = bucket.get_cumulative_count();
+                            bucket_counts.push(count.saturating_sub(prev));
+                            explicit_bounds.push(bucket.get_upper_bound());
+                            prev = count;
+                        }
+                        bucket_counts.push(h.get_sample_count().saturating_sub(prev));
+                        HistogramDataPoint {
+                            attributes: attributes(m.get_label()),
+                            start_time_unix_nano,
+                            time_unix_nano,
+                            count: h.get_sample_count(),
+                            sum: Some(h.get_sample_sum()),
+                            bucket_counts,
+                            explicit_bounds,
+                            ..Default::default()
+                        }
+                    })
+                    .collect(),
+                aggregation_temporality: AggregationTemporality::Cumulative.into(),
+            }),
+            MetricType::SUMMARY => continue,
+        };
+        metrics.push(Metric {
+            name: family.get_name().to_owned(),
+            description: family.get_help().to_owned(),
+            data: Some(data),
+            ..Default::default()
+        });
+    }
+
+    ExportMetricsServiceRequest {
+        resource_metrics: vec![ResourceMetrics {
+            resource: Some(Resource {
+                attributes: resource
+                    .iter()
+                    .map(|(key, value)| key_value(key, value))
+                    .collect(),
+                ..Default::default()
+            }),
+            scope_metrics: vec![ScopeMetrics {
+                scope: Some(InstrumentationScope {
+                    name: "mz_ore::metrics".to_owned(),
+                    ..Default::default()
+                }),
+                metrics,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    }
+}
+
+fn attributes(labels: &[LabelPair]) -> Vec<KeyValue> {
+    labels
+        .iter()
+        .map(|l| key_value(l.get_name(), l.get_value()))
+        .collect()
+}
+
+fn key_value(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        key: key.to_owned(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.to_owned())),
+        }),
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    let nanos = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_nanos();
+    u64::try_from(nanos).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_server::{
+        MetricsService, MetricsServiceServer,
+    };
+    use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceResponse;
+    use prometheus::{Histogram, IntCounter, IntCounterVec};
+    use tokio::net::TcpListener;
+    use tonic::transport::Server;
+
+    use crate::metric;
+    use crate::metrics::MetricsRegistry;
+
+    use super::*;
+
+    /// An OTLP collector that records the requests it receives.
+    #[derive(Clone, Default)]
+    struct TestCollector(Arc<Mutex<Vec<ExportMetricsServiceRequest>>>);
+
+    #[tonic::async_trait]
+    impl MetricsService for TestCollector {
+        async fn export(
+            &self,
+            request: tonic::Request<ExportMetricsServiceRequest>,
+        ) -> Result<tonic::Response<ExportMetricsServiceResponse>, tonic::Status> {
+            self.0
+                .lock()
+                .expect("lock poisoned")
+                .push(request.into_inner());
+            Ok(tonic::Response::new(ExportMetricsServiceResponse::default()))
+        }
+    }
+
+    #[crate::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `socket`
+    async fn export_metrics() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("bound address");
+        let incoming = futures::stream::unfold(listener, |listener| async move {
+            let conn = listener.accept().await.map(|(conn, _addr)| conn);
+            Some((conn, listener))
+        });
+        let collector = TestCollector::default();
+        let requests = Arc::clone(&collector.0);
+        let server = Server::builder()
+            .add_service(MetricsServiceServer::new(collector))
+            .serve_with_incoming(incoming);
+
+        let registry = MetricsRegistry::new();
+        let counter: IntCounter = registry.register(metric!(
+            name: "mz_test_exports",
+            help: "test exports",
+        ));
+        let export = async {
+            let resource = vec![("service.name".to_owned(), "test".to_owned())];
+            let mut exporter = OtlpMetricsExporter::new(
+                &format!("http://{addr}"),
+                Duration::from_secs(10),
+                resource,
+            )
+            .expect("valid endpoint");
+            counter.inc();
+            exporter.export(&registry.gather()).await.expect("export");
+            counter.inc();
+            exporter.export(&registry.gather()).await.expect("export");
+        };
+        tokio::select! {
+            res = server => panic!("collector exited: {res:?}"),
+            () = export => {}
+        }
+
+        let requests = requests.lock().expect("lock poisoned");
+        assert_eq!(requests.len(), 2);
+        let points: Vec<_> = requests
+            .iter()
+            .map(|req| {
+                let resource_metrics = &req.resource_metrics[0];
+                assert_eq!(
+                    resource_metrics.resource.as_ref().unwrap().attributes,
+                    vec![key_value("service.name", "test")]
+                );
+                let metric = &resource_metrics.scope_metrics[0].metrics[0];
+                assert_eq!(metric.name, "mz_test_exports");
+                let Some(metric::Data::Sum(sum)) = &metric.data else {
+                    panic!("expected sum: {:?}", metric.data);
+                };
+                sum.data_points[0].clone()
+            })
+            .collect();
+        // Exports are cumulative since the exporter was created.
+        assert_eq!(
+            points[0].start_time_unix_nano,
+            points[1].start_time_unix_nano
+        );
+        assert!(points[0].time_unix_nano <= points[1].time_unix_nano);
+        assert_eq!(
+            points[0].value,
+            Some(number_data_point::Value::AsDouble(1.0))
+        );
+        assert_eq!(
+            points[1].value,
+            Some(number_data_point::Value::AsDouble(2.0))
+        );
+    }
+
+    #[crate::test]
+    fn encode_metrics() {
+        let registry = MetricsRegistry::new();
+        let counter: IntCounterVec = registry.register(metric!(
+            name: "mz_test_ops",
+            help: "test ops",
+            var_labels: ["op"],
+        ));
+        let histogram: Histogram = registry.register(metric!(
+            name: "mz_test_seconds",
+            help: "test seconds",
+            buckets: vec![1.0, 2.0],
+        ));
+        counter.with_label_values(&["get"]).inc_by(3);
+        histogram.observe(0.5);
+        histogram.observe(1.5);
+        histogram.observe(5.0);
+
+        let resource = vec![("service.name".to_owned(), "test".to_owned())];
+        let req = encode(&registry.gather(), &resource, 1, 2);
+        let resource_metrics = &req.resource_metrics[0];
+        assert_eq!(
+            resource_metrics.resource.as_ref().unwrap().attributes,
+            vec![key_value("service.name", "test")]
+        );
+        let metrics = &resource_metrics.scope_metrics[0].metrics;
+        assert_eq!(metrics.len(), 2);
+
+        let ops = metrics.iter().find(|m| m.name == "mz_test_ops").unwrap();
+        let Some(metric::Data::Sum(sum)) = &ops.data else {
+            panic!("expected sum: {:?}", ops.data);
+        };
+        assert!(sum.is_monotonic);
+        assert_eq!(sum.data_points[0].attributes, vec![key_value("op", "get")]);
+        assert_eq!(
+            sum.data_points[0].value,
+            Some(number_data_point::Value::AsDouble(3.0))
+        );
+
+        let seconds = metrics
+            .iter()
+            .find(|m| m.name == "mz_test_seconds")
+            .unwrap();
+        let Some(metric::Data::Histogram(histogram)) = &seconds.data else {
+            panic!("expected histogram: {:?}", seconds.data);
+        };
+        let point = &histogram.data_points[0];
+        assert_eq!(point.count, 3);
+        assert_eq!(point.sum, Some(7.0));
+        assert_eq!(point.explicit_bounds, vec![1.0, 2.0]);
+        assert_eq!(point.bucket_counts, vec![1, 1, 1]);
+        assert_eq!((point.start_time_unix_nano, point.time_unix_nano), (1, 2));
+    }
+}
@@ -380,6 +380,16 @@ pub(crate) const INLINE_WRITES_TOTAL_MAX_BYTES: Config<usize> = Config::new(
     persist will backpressure them by flushing out to s3.",
 );
 
+pub(crate) const INLINE_WRITES_COMPACTION_MAX_BYTES: Config<usize> = Config::new(
+    "persist_inline_writes_compaction_max_bytes",
+    0,
+    "\
+    The (exclusive) maximum size of a compaction output that persist will \
+    inline in metadata, as long as the shard has plenty of room left for \
+    inline writes. This migrates existing low-throughput shards from tiny \
+    blobs to inline batches as they compact.",
+);
+
 impl BatchBuilderConfig {
     /// Initialize a batch builder config based on a snapshot of the Persist config.
     pub fn new(value: &PersistConfig, _writer_id: &WriterId) -> Self {
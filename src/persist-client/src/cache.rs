@@ -19,7 +19,9 @@ use std::time::{Duration, Instant};
 
 use differential_dataflow::difference::Semigroup;
 use differential_dataflow::lattice::Lattice;
+use mz_dyncfg::Config;
 use mz_ore::instrument;
+use mz_ore::metrics::otlp::OtlpMetricsExporter;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::task::{AbortOnDropHandle, JoinHandle};
 use mz_persist::cfg::{BlobConfig, ConsensusConfig};
@@ -30,11 +32,11 @@ use mz_persist::location::{
 use mz_persist_types::{Codec, Codec64};
 use timely::progress::Timestamp;
 use tokio::sync::{Mutex, OnceCell};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::async_runtime::IsolatedRuntime;
 use crate::error::{CodecConcreteType, CodecMismatch};
-use crate::internal::cache::BlobMemCache;
+use crate::internal::cache::{BlobDiskCache, BlobMemCache};
 use crate::internal::machine::retry_external;
 use crate::internal::metrics::{LockMetrics, Metrics, MetricsBlob, MetricsConsensus, ShardMetrics};
 use crate::internal::state::TypedState;
@@ -61,6 +63,7 @@ pub struct PersistClientCache {
     pub(crate) state_cache: Arc<StateCache>,
     pubsub_sender: Arc<dyn PubSubSender>,
     _pubsub_receiver_task: JoinHandle<()>,
+    _otlp_metrics_task: AbortOnDropHandle<()>,
 }
 
 #[derive(Debug)]
@@ -85,6 +88,7 @@ impl PersistClientCache {
             pubsub_client.receiver,
         );
         let isolated_runtime = IsolatedRuntime::new(cfg.isolated_runtime_worker_threads);
+        let _otlp_metrics_task = otlp_metrics_task(cfg.clone(), registry.clone()).abort_on_drop();
 
         PersistClientCache {
             cfg,
@@ -95,6 +99,7 @@ impl PersistClientCache {
             state_cache,
             pubsub_sender: pubsub_client.sender,
             _pubsub_receiver_task,
+            _otlp_metrics_task,
         }
     }
 
@@ -222,6 +227,13 @@ impl PersistClientCache {
                 .await;
                 // This is intentionally "outside" (wrapping) MetricsBlob so
                 // that we don't include cached responses in blob metrics.
+                let blob = match &self.cfg.blob_cache_disk_path {
+                    Some(dir) => {
+                        BlobDiskCache::new(&self.cfg, Arc::clone(&self.metrics), blob, dir.clone())
+                            .await
+                    }
+                    None => blob,
+                };
                 let blob = BlobMemCache::new(&self.cfg, Arc::clone(&self.metrics), blob);
                 Arc::clone(&x.insert((RttLatencyTask(task.abort_on_drop()), blob)).1)
             }
@@ -315,6 +327,74 @@ async fn consensus_rtt_latency_task(
     })
 }
 
+/// The OTLP/gRPC endpoint to periodically push persist metrics to, or empty
+/// to disable the export.
+///
+/// This is an alternative to scraping the prometheus endpoint of each process
+/// for deployments that collect metrics with an OpenTelemetry collector.
+pub(crate) const OTLP_METRICS_ENDPOINT: Config<&'static str> = Config::new(
+    "persist_otlp_metrics_endpoint",
+    "",
+    "\
+    The OTLP/gRPC endpoint to periodically push persist metrics to, or empty \
+    to disable the export (Materialize).",
+);
+
+/// The interval at which persist metrics are pushed to
+/// `persist_otlp_metrics_endpoint`.
+pub(crate) const OTLP_METRICS_INTERVAL: Config<Duration> = Config::new(
+    "persist_otlp_metrics_interval",
+    Duration::from_secs(60),
+    "The interval at which persist metrics are pushed via OTLP (Materialize).",
+);
+
+/// Starts a task to periodically push the persist metrics in `registry` to the
+/// OTLP endpoint in [OTLP_METRICS_ENDPOINT], if any.
+///
+/// The endpoint and interval are re-read on every tick, so the export can be
+/// enabled, disabled, or redirected at runtime.
+fn otlp_metrics_task(cfg: PersistConfig, registry: MetricsRegistry) -> JoinHandle<()> {
+    mz_ore::task::spawn(|| "persist::otlp_metrics", async move {
+        let mut exporter: Option<(String, OtlpMetricsExporter)> = None;
+        loop {
+            tokio::time::sleep(OTLP_METRICS_INTERVAL.get(&cfg)).await;
+            let endpoint = OTLP_METRICS_ENDPOINT.get(&cfg);
+            if endpoint.is_empty() {
+                exporter = None;
+                continue;
+            }
+            if exporter.as_ref().map(|(x, _)| x) != Some(&endpoint) {
+                let resource = vec![
+                    ("service.name".to_owned(), "persist".to_owned()),
+                    ("service.version".to_owned(), cfg.build_version.to_string()),
+                    ("host.name".to_owned(), cfg.hostname.clone()),
+                ];
+                match OtlpMetricsExporter::new(&endpoint, Duration::from_secs(30), resource) {
+                    Ok(x) => exporter = Some((endpoint, x)),
+                    Err(err) => {
+                        warn!(
+                            "invalid persist OTLP metrics endpoint {}: {}",
+                            endpoint, err
+                        );
+                        exporter = None;
+                        continue;
+                    }
+                }
+            }
+            let Some((endpoint, exporter)) = exporter.as_mut() else {
+                continue;
+            };
+            let mut families = registry.gather();
+            families.retain(|x| x.get_name().starts_with("mz_persist_"));
+            if let Err(err) = exporter.export(&families).await {
+                // Like the rtt latency tasks, don't retry. The next tick will
+                // push the (cumulative) metrics again.
+                warn!("failed to push persist metrics to {}: {}", endpoint, err);
+            }
+        }
+    })
+}
+
 pub(crate) trait DynState: Debug + Send + Sync {
     fn codecs(&self) -> (String, String, String, String, Option<CodecConcreteType>);
     fn as_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync>;
@@ -11,6 +11,7 @@
 
 //! The tunable knobs for persist.
 
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -100,6 +101,8 @@ pub struct PersistConfig {
     pub is_cc_active: bool,
     /// Memory limit of the process, if known.
     pub announce_memory_limit: Option<usize>,
+    /// Directory in which fetched blobs may be cached on local disk, if any.
+    pub blob_cache_disk_path: Option<PathBuf>,
     /// A clock to use for all leasing and other non-debugging use.
     pub now: NowFn,
     /// Persist [Config]s that can change value dynamically within the lifetime
@@ -182,6 +185,7 @@ impl PersistConfig {
             build_version: build_info.semver_version(),
             is_cc_active: false,
             announce_memory_limit: None,
+            blob_cache_disk_path: None,
             now,
             configs,
             configs_synced_once: Arc::new(configs_synced_once),
@@ -327,6 +331,7 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&crate::batch::BLOB_TARGET_SIZE)
         .add(&crate::batch::INLINE_WRITES_TOTAL_MAX_BYTES)
         .add(&crate::batch::INLINE_WRITES_SINGLE_MAX_BYTES)
+        .add(&crate::batch::INLINE_WRITES_COMPACTION_MAX_BYTES)
         .add(&crate::cfg::CONSENSUS_CONNECTION_POOL_TTL_STAGGER)
         .add(&crate::cfg::CONSENSUS_CONNECTION_POOL_TTL)
         .add(&crate::cfg::CRDB_CONNECT_TIMEOUT)
@@ -338,8 +343,19 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&crate::cfg::USE_GLOBAL_TXN_CACHE_SOURCE)
         .add(&crate::fetch::FETCH_SEMAPHORE_COST_ADJUSTMENT)
         .add(&crate::fetch::FETCH_SEMAPHORE_PERMIT_ADJUSTMENT)
+        .add(&crate::internal::cache::BLOB_CACHE_DISK_ENABLED)
+        .add(&crate::internal::cache::BLOB_CACHE_DISK_LIMIT_BYTES)
         .add(&crate::internal::cache::BLOB_CACHE_MEM_LIMIT_BYTES)
         .add(&crate::internal::compact::COMPACTION_MINIMUM_TIMEOUT)
+        .add(&crate::internal::compact::COMPACTION_CATALOG_BLOB_TARGET_SIZE)
+        .add(&crate::internal::compact::COMPACTION_CATALOG_HEURISTIC_MIN_INPUTS)
+        .add(&crate::internal::compact::COMPACTION_CATALOG_MEMORY_BOUND_BYTES)
+        .add(&crate::internal::compact::COMPACTION_INTROSPECTION_BLOB_TARGET_SIZE)
+        .add(&crate::internal::compact::COMPACTION_INTROSPECTION_HEURISTIC_MIN_INPUTS)
+        .add(&crate::internal::compact::COMPACTION_INTROSPECTION_MEMORY_BOUND_BYTES)
+        .add(&crate::internal::compact::COMPACTION_DATA_BLOB_TARGET_SIZE)
+        .add(&crate::internal::compact::COMPACTION_DATA_HEURISTIC_MIN_INPUTS)
+        .add(&crate::internal::compact::COMPACTION_DATA_MEMORY_BOUND_BYTES)
         .add(&crate::internal::machine::CLAIM_UNCLAIMED_COMPACTIONS)
         .add(&crate::internal::machine::NEXT_LISTEN_BATCH_RETRYER_CLAMP)
         .add(&crate::internal::machine::NEXT_LISTEN_BATCH_RETRYER_FIXED_SLEEP)
@@ -366,6 +382,8 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&crate::stats::STATS_UNTRIMMABLE_COLUMNS_PREFIX)
         .add(&crate::stats::STATS_UNTRIMMABLE_COLUMNS_SUFFIX)
         .add(&crate::fetch::PART_DECODE_FORMAT)
+        .add(&crate::cache::OTLP_METRICS_ENDPOINT)
+        .add(&crate::cache::OTLP_METRICS_INTERVAL)
 }
 
 impl PersistConfig {
@@ -40,6 +40,7 @@ use crate::internal::encoding::Schemas;
 use crate::internal::gc::{GarbageCollector, GcReq};
 use crate::internal::machine::Machine;
 use crate::internal::trace::FueledMergeRes;
+use crate::read::LeasedReaderId;
 use crate::rpc::{NoopPubSubSender, PubSubSender};
 use crate::write::{WriteHandle, WriterId};
 use crate::{
@@ -76,6 +77,8 @@ pub(crate) enum Command {
     /// Attempt to ensure that all the files referenced by consensus are available
     /// in Blob.
     RestoreBlob(RestoreBlobArgs),
+    /// Manually expire a leased reader of a shard.
+    ExpireReader(ExpireReaderArgs),
 }
 
 /// Manually completes all fueled compactions in a shard.
@@ -122,6 +125,18 @@ pub(crate) struct RestoreBlobArgs {
     concurrency: usize,
 }
 
+/// Manually expire a leased reader that is holding back the since of a shard.
+#[derive(Debug, clap::Parser)]
+pub(crate) struct ExpireReaderArgs {
+    #[clap(flatten)]
+    state: StateArgs,
+
+    /// The ID of the leased reader to expire, e.g. as reported by
+    /// `mz_internal.mz_storage_shard_readers`.
+    #[clap(long)]
+    reader_id: String,
+}
+
 /// Runs the given read-write admin command.
 pub async fn run(command: AdminArgs) -> Result<(), anyhow::Error> {
     match command.command {
@@ -312,6 +327,55 @@ pub async fn run(command: AdminArgs) -> Result<(), anyhow::Error> {
             }
             info_log_non_zero_metrics(&metrics_registry.gather());
         }
+        Command::ExpireReader(args) => {
+            let ExpireReaderArgs {
+                state:
+                    StateArgs {
+                        shard_id,
+                        consensus_uri,
+                        blob_uri,
+                    },
+                reader_id,
+            } = args;
+            let shard_id = ShardId::from_str(&shard_id).expect("invalid shard id");
+            let reader_id = LeasedReaderId::from_str(&reader_id).map_err(|e| anyhow!(e))?;
+            let commit = command.commit;
+            let expected_version = command
+                .expected_version
+                .as_ref()
+                .map(|v| Version::parse(v))
+                .transpose()?;
+
+            let configs = all_dyncfgs(ConfigSet::default());
+            // TODO: Fetch the latest values of these configs from Launch Darkly.
+            let cfg = PersistConfig::new(&BUILD_INFO, SYSTEM_TIME.clone(), configs);
+            let metrics_registry = MetricsRegistry::new();
+            let metrics = Arc::new(Metrics::new(&cfg, &metrics_registry));
+            let consensus =
+                make_consensus(&cfg, &consensus_uri, commit, Arc::clone(&metrics)).await?;
+            let blob = make_blob(&cfg, &blob_uri, commit, Arc::clone(&metrics)).await?;
+            let mut machine = make_machine(
+                &cfg,
+                consensus,
+                blob,
+                Arc::clone(&metrics),
+                shard_id,
+                commit,
+                expected_version,
+            )
+            .await?;
+
+            if !commit {
+                info!("skipping expiry of reader {reader_id} because --commit is not set");
+                return Ok(());
+            }
+            let (seqno, maintenance) = machine.expire_leased_reader(&reader_id).await;
+            if !maintenance.is_empty() {
+                info!("ignoring non-empty requested maintenance: {maintenance:?}")
+            }
+            info!("expired reader {reader_id} at {seqno}");
+            info_log_non_zero_metrics(&metrics_registry.gather());
+        }
         Command::RestoreBlob(args) => {
             let RestoreBlobArgs {
                 state:
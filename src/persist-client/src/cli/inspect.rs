@@ -24,6 +24,7 @@ use mz_ore::cast::CastFrom;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::SYSTEM_TIME;
 use mz_persist::indexed::encoding::BlobTraceBatchPart;
+use mz_persist::replicated::ReplicatedBlob;
 use mz_persist_types::codec_impls::TodoSchema;
 use mz_persist_types::{Codec, Codec64, Opaque};
 use mz_proto::RustType;
@@ -69,6 +70,9 @@ pub(crate) enum Command {
     /// Prints blob batch part contents
     BlobBatchPart(BlobBatchPartArgs),
 
+    /// Prints the blobs that differ between a blob and its replica
+    BlobReplicaConsistency(BlobReplicaArgs),
+
     /// Prints consolidated and unconsolidated size, in bytes and update count
     ConsolidatedSize(StateArgs),
 
@@ -153,6 +157,10 @@ pub async fn run(command: InspectArgs) -> Result<(), anyhow::Error> {
             let updates = blob_batch_part(&args.blob_uri, shard_id, args.key, args.limit).await?;
             println!("{}", json!(updates));
         }
+        Command::BlobReplicaConsistency(args) => {
+            let consistency = blob_replica_consistency(&args).await?;
+            println!("{}", json!(consistency));
+        }
         Command::ConsolidatedSize(args) => {
             let () = consolidated_size(&args).await?;
         }
@@ -433,6 +441,37 @@ pub struct BlobArgs {
     blob_uri: String,
 }
 
+/// Arguments for comparing a blob store against its replica.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct BlobReplicaArgs {
+    /// Blob to use as the primary
+    #[clap(long)]
+    blob_uri: String,
+
+    /// Blob to use as the replica of the primary
+    #[clap(long)]
+    replica_blob_uri: String,
+}
+
+/// Compares the contents of a blob store and its replica.
+pub async fn blob_replica_consistency(
+    args: &BlobReplicaArgs,
+) -> Result<impl serde::Serialize, anyhow::Error> {
+    let cfg = PersistConfig::new_default_configs(&READ_ALL_BUILD_INFO, SYSTEM_TIME.clone());
+    let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
+    let primary = make_blob(&cfg, &args.blob_uri, NO_COMMIT, Arc::clone(&metrics)).await?;
+    let replica = make_blob(&cfg, &args.replica_blob_uri, NO_COMMIT, metrics).await?;
+    let consistency = ReplicatedBlob::new(primary, replica)
+        .check_consistency(&BlobKeyPrefix::All.to_string())
+        .await?;
+    Ok(json!({
+        "consistent": consistency.is_consistent(),
+        "missing_in_replica": consistency.missing_in_replica,
+        "missing_in_primary": consistency.missing_in_primary,
+        "size_mismatch": consistency.size_mismatch,
+    }))
+}
+
 #[derive(Debug, Default, serde::Serialize)]
 struct BlobCounts {
     batch_part_count: usize,
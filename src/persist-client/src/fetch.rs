@@ -886,6 +886,10 @@ where
                     },
                     None => K::decode(k),
                 });
+                let k = k.map(|mut k| {
+                    self.schemas.key.backfill(&mut k);
+                    k
+                });
                 let v = self.metrics.codecs.val.decode(|| match val.take() {
                     Some(mut val) => match V::decode_from(&mut val, v, &mut self.val_storage) {
                         Ok(()) => Ok(val),
@@ -893,6 +897,10 @@ where
                     },
                     None => V::decode(v),
                 });
+                let v = v.map(|mut v| {
+                    self.schemas.val.backfill(&mut v);
+                    v
+                });
 
                 // Note: We only provide structured columns, if they were originally written, and a
                 // dyncfg was specified to run validation.
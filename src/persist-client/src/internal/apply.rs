@@ -25,6 +25,7 @@ use tracing::debug;
 
 use crate::cache::{LockingTypedState, StateCache};
 use crate::error::{CodecMismatch, InvalidUsage};
+use crate::internal::compact::ShardPurpose;
 use crate::internal::gc::GcReq;
 use crate::internal::maintenance::RoutineMaintenance;
 use crate::internal::metrics::{CmdMetrics, Metrics, ShardMetrics};
@@ -53,6 +54,8 @@ pub struct Applier<K, V, T, D> {
     shared_states: Arc<StateCache>,
     pubsub_sender: Arc<dyn PubSubSender>,
     pub(crate) shard_id: ShardId,
+    /// What the shard is used for, if known.
+    pub(crate) shard_purpose: Option<ShardPurpose>,
 
     // Access to the shard's state, shared across all handles created by the same
     // PersistClientCache. The state is wrapped in LockingTypedState, disallowing
@@ -76,6 +79,7 @@ impl<K, V, T: Clone, D> Clone for Applier<K, V, T, D> {
             shared_states: Arc::clone(&self.shared_states),
             pubsub_sender: Arc::clone(&self.pubsub_sender),
             shard_id: self.shard_id,
+            shard_purpose: self.shard_purpose,
             state: Arc::clone(&self.state),
         }
     }
@@ -105,6 +109,7 @@ where
         diagnostics: Diagnostics,
     ) -> Result<Self, Box<CodecMismatch>> {
         let shard_metrics = metrics.shards.shard(&shard_id, &diagnostics.shard_name);
+        let shard_purpose = ShardPurpose::from_shard_name(&diagnostics.shard_name);
         let state = shared_states
             .get::<K, V, T, D, _, _>(
                 shard_id,
@@ -124,6 +129,7 @@ where
             shared_states,
             pubsub_sender,
             shard_id,
+            shard_purpose,
             state,
         };
         Ok(ret)
@@ -264,6 +270,19 @@ where
             })
     }
 
+    /// The total size of the inline writes in the current state.
+    pub fn inline_bytes(&self) -> usize {
+        self.state
+            .read_lock(&self.metrics.locks.applier_read_noncacheable, |state| {
+                let mut inline_bytes = 0;
+                state
+                    .collections
+                    .trace
+                    .map_batches(|x| inline_bytes += x.inline_bytes());
+                inline_bytes
+            })
+    }
+
     pub fn all_batches(&self) -> Vec<HollowBatch<T>> {
         self.state
             .read_lock(&self.metrics.locks.applier_read_noncacheable, |state| {
@@ -9,6 +9,7 @@
 
 //! In-process caches of [Blob].
 
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
@@ -17,6 +18,7 @@ use mz_dyncfg::{Config, ConfigSet};
 use mz_ore::bytes::SegmentedBytes;
 use mz_ore::cast::CastFrom;
 use mz_persist::location::{Blob, BlobMetadata, ExternalError};
+use tracing::{debug, warn};
 
 use crate::cfg::PersistConfig;
 use crate::internal::metrics::Metrics;
@@ -87,6 +89,7 @@ impl Blob for BlobMemCache {
                 .inc_by(u64::cast_from(cached_value.len()));
             return Ok(Some(cached_value.clone()));
         }
+        self.metrics.blob_cache_mem.misses_blobs.inc();
 
         let res = self.blob.get(key).await?;
         if let Some(blob) = res.as_ref() {
@@ -140,6 +143,233 @@ impl Blob for BlobMemCache {
     }
 }
 
+// On-disk cache for [Blob].
+//
+// Only the index of the cached blobs is kept in memory. Blobs are write-once
+// modify-never, so a cached file is never stale. The cache starts out empty in
+// every process, because the index of a previous process is lost.
+#[derive(Debug)]
+pub struct BlobDiskCache {
+    cfg: ConfigSet,
+    metrics: Arc<Metrics>,
+    dir: PathBuf,
+    cache: Arc<Mutex<lru::Lru<String, PathBuf>>>,
+    blob: Arc<dyn Blob>,
+}
+
+pub(crate) const BLOB_CACHE_DISK_ENABLED: Config<bool> = Config::new(
+    "persist_blob_cache_disk_enabled",
+    false,
+    "\
+    Whether to cache fetched blobs on local disk, in processes with a scratch \
+    directory (Materialize).",
+);
+
+pub(crate) const BLOB_CACHE_DISK_LIMIT_BYTES: Config<usize> = Config::new(
+    "persist_blob_cache_disk_limit_bytes",
+    1024 * 1024 * 1024,
+    "Capacity of on-disk blob cache in bytes (Materialize).",
+);
+
+impl BlobDiskCache {
+    /// Returns `blob` wrapped in a cache that keeps fetched blobs in `dir`.
+    ///
+    /// Any existing contents of `dir` are removed. If `dir` can't be
+    /// prepared, `blob` is returned as is.
+    pub async fn new(
+        cfg: &PersistConfig,
+        metrics: Arc<Metrics>,
+        blob: Arc<dyn Blob>,
+        dir: PathBuf,
+    ) -> Arc<dyn Blob> {
+        let prepare_dir = dir.clone();
+        let res = mz_ore::task::spawn_blocking(
+            || "persist::blob_cache_disk::prepare",
+            move || {
+                match std::fs::remove_dir_all(&prepare_dir) {
+                    Ok(()) => {}
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(err),
+                }
+                std::fs::create_dir_all(&prepare_dir)
+            },
+        )
+        .await;
+        match res {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                warn!("disabling blob disk cache in {}: {}", dir.display(), err);
+                return blob;
+            }
+            Err(err) => {
+                warn!("disabling blob disk cache in {}: {}", dir.display(), err);
+                return blob;
+            }
+        }
+
+        let eviction_metrics = Arc::clone(&metrics);
+        let cache = lru::Lru::new(
+            BLOB_CACHE_DISK_LIMIT_BYTES.get(cfg),
+            move |_, path: PathBuf, _| {
+                eviction_metrics.blob_cache_disk.evictions.inc();
+                remove_cached_file(path);
+            },
+        );
+        let blob = BlobDiskCache {
+            cfg: cfg.configs.clone(),
+            metrics,
+            dir,
+            cache: Arc::new(Mutex::new(cache)),
+            blob,
+        };
+        Arc::new(blob)
+    }
+
+    /// Returns the path of the file caching the blob with the given key.
+    fn path(dir: &Path, key: &str) -> PathBuf {
+        // Blob keys contain slashes, so escape them to keep the cache flat.
+        dir.join(key.replace('%', "%25").replace('/', "%2F"))
+    }
+
+    fn resize_and_update_size_metrics(
+        cfg: &ConfigSet,
+        metrics: &Metrics,
+        cache: &mut lru::Lru<String, PathBuf>,
+    ) {
+        cache.update_capacity(BLOB_CACHE_DISK_LIMIT_BYTES.get(cfg));
+        metrics
+            .blob_cache_disk
+            .size_blobs
+            .set(u64::cast_from(cache.entry_count()));
+        metrics
+            .blob_cache_disk
+            .size_bytes
+            .set(u64::cast_from(cache.entry_weight()));
+    }
+
+    /// Writes a fetched blob to disk in the background, and adds it to the
+    /// cache once it's written.
+    fn insert_in_background(&self, key: &str, value: &SegmentedBytes) {
+        let weight = value.len();
+        if weight > self.cache.lock().expect("lock poisoned").capacity() {
+            // The blob would push out everything in the cache and then
+            // immediately get evicted itself.
+            return;
+        }
+
+        let key = key.to_owned();
+        let value = value.clone();
+        let path = Self::path(&self.dir, &key);
+        let cfg = self.cfg.clone();
+        let metrics = Arc::clone(&self.metrics);
+        let cache = Arc::clone(&self.cache);
+        mz_ore::task::spawn_blocking(
+            || "persist::blob_cache_disk::insert",
+            move || {
+                // Write to a temporary file first, so that readers never see
+                // a partially written blob.
+                let mut tmp_path = path.clone().into_os_string();
+                tmp_path.push(format!(".tmp-{}", uuid::Uuid::new_v4()));
+                let tmp_path = PathBuf::from(tmp_path);
+                let res = std::fs::write(&tmp_path, value.into_contiguous())
+                    .and_then(|()| std::fs::rename(&tmp_path, &path));
+                if let Err(err) = res {
+                    debug!("failed to cache blob {} on disk: {}", key, err);
+                    let _ = std::fs::remove_file(&tmp_path);
+                    return;
+                }
+                let mut cache = cache.lock().expect("lock poisoned");
+                cache.insert(key, path, weight);
+                Self::resize_and_update_size_metrics(&cfg, &metrics, &mut cache);
+            },
+        );
+    }
+}
+
+/// Removes a file evicted from the disk cache in the background.
+fn remove_cached_file(path: PathBuf) {
+    mz_ore::task::spawn_blocking(
+        || "persist::blob_cache_disk::remove",
+        move || {
+            if let Err(err) = std::fs::remove_file(&path) {
+                debug!("failed to remove cached blob {}: {}", path.display(), err);
+            }
+        },
+    );
+}
+
+#[async_trait]
+impl Blob for BlobDiskCache {
+    async fn get(&self, key: &str) -> Result<Option<SegmentedBytes>, ExternalError> {
+        if !BLOB_CACHE_DISK_ENABLED.get(&self.cfg) {
+            return self.blob.get(key).await;
+        }
+
+        let cached_path = self
+            .cache
+            .lock()
+            .expect("lock poisoned")
+            .get(key)
+            .map(|(_, path)| path.clone());
+        if let Some(path) = cached_path {
+            let res = mz_ore::task::spawn_blocking(
+                || "persist::blob_cache_disk::get",
+                move || std::fs::read(path),
+            )
+            .await;
+            match res {
+                Ok(Ok(value)) => {
+                    self.metrics.blob_cache_disk.hits_blobs.inc();
+                    self.metrics
+                        .blob_cache_disk
+                        .hits_bytes
+                        .inc_by(u64::cast_from(value.len()));
+                    return Ok(Some(SegmentedBytes::from(value)));
+                }
+                // The file was evicted concurrently or is otherwise unreadable,
+                // so fall back to fetching the blob.
+                Ok(Err(err)) => debug!("failed to read cached blob {}: {}", key, err),
+                Err(err) => debug!("failed to read cached blob {}: {}", key, err),
+            }
+        }
+        self.metrics.blob_cache_disk.misses_blobs.inc();
+
+        let res = self.blob.get(key).await?;
+        if let Some(value) = res.as_ref() {
+            self.insert_in_background(key, value);
+        }
+        Ok(res)
+    }
+
+    async fn list_keys_and_metadata(
+        &self,
+        key_prefix: &str,
+        f: &mut (dyn FnMut(BlobMetadata) + Send + Sync),
+    ) -> Result<(), ExternalError> {
+        self.blob.list_keys_and_metadata(key_prefix, f).await
+    }
+
+    async fn set(&self, key: &str, value: Bytes) -> Result<(), ExternalError> {
+        // Blobs are cached when they are read, because the processes with a
+        // disk cache rarely read back what they write.
+        self.blob.set(key, value).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<Option<usize>, ExternalError> {
+        let res = self.blob.delete(key).await;
+        let mut cache = self.cache.lock().expect("lock poisoned");
+        if let Some((_, path, _)) = cache.remove(key) {
+            remove_cached_file(path);
+        }
+        Self::resize_and_update_size_metrics(&self.cfg, &self.metrics, &mut cache);
+        res
+    }
+
+    async fn restore(&self, key: &str) -> Result<(), ExternalError> {
+        self.blob.restore(key).await
+    }
+}
+
 mod lru {
     use std::borrow::Borrow;
     use std::collections::BTreeMap;
@@ -379,11 +609,21 @@ mod lru {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use mz_ore::metrics::MetricsRegistry;
+    use mz_persist::location::Blob;
+    use mz_persist::mem::{MemBlob, MemBlobConfig};
     use proptest::arbitrary::any;
     use proptest::proptest;
     use proptest_derive::Arbitrary;
 
+    use crate::cfg::PersistConfig;
+    use crate::internal::metrics::Metrics;
+
     use super::lru::*;
+    use super::{BlobDiskCache, BLOB_CACHE_DISK_ENABLED};
 
     #[derive(Debug, Arbitrary)]
     enum LruOp {
@@ -523,4 +763,45 @@ mod tests {
         assert_eq!(cache.entry_weight(), 2);
         assert_eq!(cache.keys(), &["j", "i"]);
     }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn blob_disk_cache() {
+        let cfg = PersistConfig::new_for_tests();
+        cfg.set_config(&BLOB_CACHE_DISK_ENABLED, true);
+        let metrics = Arc::new(Metrics::new(&cfg, &MetricsRegistry::new()));
+        let dir = tempfile::tempdir().expect("tempdir");
+        let inner: Arc<dyn Blob> = Arc::new(MemBlob::open(MemBlobConfig::default()));
+        let blob = BlobDiskCache::new(
+            &cfg,
+            Arc::clone(&metrics),
+            Arc::clone(&inner),
+            dir.path().join("cache"),
+        )
+        .await;
+
+        blob.set("a/b", Bytes::from_static(b"foo")).await.unwrap();
+        let get = |blob: &Arc<dyn Blob>| {
+            let blob = Arc::clone(blob);
+            async move { blob.get("a/b").await.unwrap().map(|x| x.into_contiguous()) }
+        };
+
+        // The first read misses and populates the cache in the background.
+        assert_eq!(get(&blob).await, Some(b"foo".to_vec()));
+        assert_eq!(metrics.blob_cache_disk.misses_blobs.get(), 1);
+        while metrics.blob_cache_disk.size_blobs.get() == 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+
+        // Subsequent reads are served from disk, even if the blob is gone
+        // from the inner blob.
+        let _ = inner.delete("a/b").await.unwrap();
+        assert_eq!(get(&blob).await, Some(b"foo".to_vec()));
+        assert_eq!(metrics.blob_cache_disk.hits_blobs.get(), 1);
+
+        // Deletes through the cache also remove the cached copy.
+        let _ = blob.delete("a/b").await.unwrap();
+        assert_eq!(get(&blob).await, None);
+        assert_eq!(metrics.blob_cache_disk.misses_blobs.get(), 2);
+    }
 }
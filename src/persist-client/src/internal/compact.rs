@@ -31,7 +31,10 @@ use tokio::sync::{mpsc, oneshot, TryAcquireError};
 use tracing::{debug, debug_span, error, trace, warn, Instrument, Span};
 
 use crate::async_runtime::IsolatedRuntime;
-use crate::batch::{BatchBuilderConfig, BatchBuilderInternal, PartDeletes};
+use crate::batch::{
+    BatchBuilderConfig, BatchBuilderInternal, PartDeletes, INLINE_WRITES_COMPACTION_MAX_BYTES,
+    INLINE_WRITES_TOTAL_MAX_BYTES,
+};
 use crate::cfg::MiB;
 use crate::fetch::FetchBatchFilter;
 use crate::internal::encoding::Schemas;
@@ -78,6 +81,29 @@ pub struct CompactConfig {
 }
 
 impl CompactConfig {
+    /// Initialize the compaction config from Persist configuration, with the
+    /// overrides for shards of the given purpose applied.
+    pub(crate) fn for_purpose(
+        value: &PersistConfig,
+        writer_id: &WriterId,
+        purpose: Option<ShardPurpose>,
+    ) -> Self {
+        let mut ret = Self::new(value, writer_id);
+        let overrides = CompactionOverrides::new(value, purpose);
+        if let Some(blob_target_size) = overrides.blob_target_size {
+            ret.batch.blob_target_size = blob_target_size;
+        }
+        if let Some(memory_bound_bytes) = overrides.memory_bound_bytes {
+            ret.compaction_memory_bound_bytes = memory_bound_bytes;
+        }
+        // Compaction requires room for at least a few parts.
+        ret.compaction_memory_bound_bytes = std::cmp::max(
+            ret.compaction_memory_bound_bytes,
+            4 * ret.batch.blob_target_size,
+        );
+        ret
+    }
+
     /// Initialize the compaction config from Persist configuration.
     pub fn new(value: &PersistConfig, writer_id: &WriterId) -> Self {
         let mut ret = CompactConfig {
@@ -88,12 +114,22 @@ impl CompactConfig {
             batch: BatchBuilderConfig::new(value, writer_id),
         };
         // Use compaction as a method of getting inline writes out of state, to
-        // make room for more inline writes. We could instead do this at the end
-        // of compaction by flushing out the batch, but doing it here based on
-        // the config allows BatchBuilder to do its normal pipelining of writes.
-        ret.batch.inline_writes_single_max_bytes = 0;
+        // make room for more inline writes, except for outputs small enough to
+        // stay inline themselves. We could instead do this at the end of
+        // compaction by flushing out the batch, but doing it here based on the
+        // config allows BatchBuilder to do its normal pipelining of writes.
+        ret.batch.inline_writes_single_max_bytes = INLINE_WRITES_COMPACTION_MAX_BYTES.get(value);
         ret
     }
+
+    /// Only keeps compaction outputs inline while the shard, whose inline
+    /// writes take up `inline_bytes`, has plenty of room for inline writes.
+    /// Otherwise, compaction is what makes room.
+    fn limit_inline_outputs(&mut self, value: &PersistConfig, inline_bytes: usize) {
+        if inline_bytes >= INLINE_WRITES_TOTAL_MAX_BYTES.get(value) / 2 {
+            self.batch.inline_writes_single_max_bytes = 0;
+        }
+    }
 }
 
 /// A service for performing physical and logical compaction.
@@ -136,6 +172,144 @@ pub(crate) const COMPACTION_MINIMUM_TIMEOUT: Config<Duration> = Config::new(
     before timing it out (Materialize).",
 );
 
+/// What a shard is used for, which determines the compaction tuning that applies
+/// to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ShardPurpose {
+    /// The catalog shards and the txn-wal shard.
+    Catalog,
+    /// The shards of system collections, e.g. introspection sources.
+    Introspection,
+    /// The shards of user collections.
+    Data,
+}
+
+impl ShardPurpose {
+    /// Infers the purpose of a shard from the name it was opened with, if
+    /// possible.
+    pub(crate) fn from_shard_name(name: &str) -> Option<Self> {
+        let is_id = |prefix| {
+            name.strip_prefix(prefix)
+                .map_or(false, |id| id.parse::<u64>().is_ok())
+        };
+        match name {
+            "catalog" | "catalog_upgrade" | "txns" => Some(ShardPurpose::Catalog),
+            _ if is_id('s') => Some(ShardPurpose::Introspection),
+            _ if is_id('u') => Some(ShardPurpose::Data),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) const COMPACTION_CATALOG_BLOB_TARGET_SIZE: Config<Option<usize>> = Config::new(
+    "persist_compaction_catalog_blob_target_size",
+    None,
+    "\
+    If set, overrides persist_blob_target_size for the parts written by \
+    compaction of catalog shards (the catalog and the txn-wal shard) (Materialize).",
+);
+
+pub(crate) const COMPACTION_CATALOG_HEURISTIC_MIN_INPUTS: Config<Option<usize>> = Config::new(
+    "persist_compaction_catalog_heuristic_min_inputs",
+    None,
+    "\
+    If set, overrides the minimum number of batches merged by a compaction of \
+    catalog shards (the catalog and the txn-wal shard) (Materialize).",
+);
+
+pub(crate) const COMPACTION_CATALOG_MEMORY_BOUND_BYTES: Config<Option<usize>> = Config::new(
+    "persist_compaction_catalog_memory_bound_bytes",
+    None,
+    "\
+    If set, overrides persist_compaction_memory_bound_bytes, beyond which \
+    compaction of catalog shards (the catalog and the txn-wal shard) backpressures fetching its inputs (Materialize).",
+);
+
+pub(crate) const COMPACTION_INTROSPECTION_BLOB_TARGET_SIZE: Config<Option<usize>> = Config::new(
+    "persist_compaction_introspection_blob_target_size",
+    None,
+    "\
+    If set, overrides persist_blob_target_size for the parts written by \
+    compaction of shards of system collections (introspection sources and builtin tables) (Materialize).",
+);
+
+pub(crate) const COMPACTION_INTROSPECTION_HEURISTIC_MIN_INPUTS: Config<Option<usize>> = Config::new(
+    "persist_compaction_introspection_heuristic_min_inputs",
+    None,
+    "\
+    If set, overrides the minimum number of batches merged by a compaction of \
+    shards of system collections (introspection sources and builtin tables) (Materialize).",
+);
+
+pub(crate) const COMPACTION_INTROSPECTION_MEMORY_BOUND_BYTES: Config<Option<usize>> = Config::new(
+    "persist_compaction_introspection_memory_bound_bytes",
+    None,
+    "\
+    If set, overrides persist_compaction_memory_bound_bytes, beyond which \
+    compaction of shards of system collections (introspection sources and builtin tables) backpressures fetching its inputs (Materialize).",
+);
+
+pub(crate) const COMPACTION_DATA_BLOB_TARGET_SIZE: Config<Option<usize>> = Config::new(
+    "persist_compaction_data_blob_target_size",
+    None,
+    "\
+    If set, overrides persist_blob_target_size for the parts written by \
+    compaction of shards of user collections (Materialize).",
+);
+
+pub(crate) const COMPACTION_DATA_HEURISTIC_MIN_INPUTS: Config<Option<usize>> = Config::new(
+    "persist_compaction_data_heuristic_min_inputs",
+    None,
+    "\
+    If set, overrides the minimum number of batches merged by a compaction of \
+    shards of user collections (Materialize).",
+);
+
+pub(crate) const COMPACTION_DATA_MEMORY_BOUND_BYTES: Config<Option<usize>> = Config::new(
+    "persist_compaction_data_memory_bound_bytes",
+    None,
+    "\
+    If set, overrides persist_compaction_memory_bound_bytes, beyond which \
+    compaction of shards of user collections backpressures fetching its inputs (Materialize).",
+);
+
+/// Overrides of the global compaction tuning for a shard.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct CompactionOverrides {
+    pub(crate) blob_target_size: Option<usize>,
+    pub(crate) heuristic_min_inputs: Option<usize>,
+    pub(crate) memory_bound_bytes: Option<usize>,
+}
+
+impl CompactionOverrides {
+    /// Returns the overrides configured for shards with the given purpose.
+    pub(crate) fn new(cfg: &PersistConfig, purpose: Option<ShardPurpose>) -> Self {
+        let (blob_target_size, heuristic_min_inputs, memory_bound_bytes) = match purpose {
+            Some(ShardPurpose::Catalog) => (
+                &COMPACTION_CATALOG_BLOB_TARGET_SIZE,
+                &COMPACTION_CATALOG_HEURISTIC_MIN_INPUTS,
+                &COMPACTION_CATALOG_MEMORY_BOUND_BYTES,
+            ),
+            Some(ShardPurpose::Introspection) => (
+                &COMPACTION_INTROSPECTION_BLOB_TARGET_SIZE,
+                &COMPACTION_INTROSPECTION_HEURISTIC_MIN_INPUTS,
+                &COMPACTION_INTROSPECTION_MEMORY_BOUND_BYTES,
+            ),
+            Some(ShardPurpose::Data) => (
+                &COMPACTION_DATA_BLOB_TARGET_SIZE,
+                &COMPACTION_DATA_HEURISTIC_MIN_INPUTS,
+                &COMPACTION_DATA_MEMORY_BOUND_BYTES,
+            ),
+            None => return CompactionOverrides::default(),
+        };
+        CompactionOverrides {
+            blob_target_size: blob_target_size.get(cfg),
+            heuristic_min_inputs: heuristic_min_inputs.get(cfg),
+            memory_bound_bytes: memory_bound_bytes.get(cfg),
+        }
+    }
+}
+
 impl<K, V, T, D> Compactor<K, V, T, D>
 where
     K: Debug + Codec,
@@ -253,7 +427,11 @@ where
         // were just written, but it does result in non-trivial blob traffic
         // (especially in aggregate). This heuristic is something we'll need to
         // tune over time.
-        let should_compact = req.inputs.len() >= self.cfg.dynamic.compaction_heuristic_min_inputs()
+        let overrides = CompactionOverrides::new(&self.cfg, machine.applier.shard_purpose);
+        let min_inputs = overrides
+            .heuristic_min_inputs
+            .unwrap_or_else(|| self.cfg.dynamic.compaction_heuristic_min_inputs());
+        let should_compact = req.inputs.len() >= min_inputs
             || req.inputs.iter().map(|x| x.part_count()).sum::<usize>()
                 >= self.cfg.dynamic.compaction_heuristic_min_parts()
             || req.inputs.iter().map(|x| x.len).sum::<usize>()
@@ -321,6 +499,10 @@ where
             timeout.as_secs_f64()
         );
 
+        let mut compact_cfg =
+            CompactConfig::for_purpose(&cfg, &writer_id, machine.applier.shard_purpose);
+        compact_cfg.limit_inline_outputs(&cfg, machine.applier.inline_bytes());
+
         let compact_span = debug_span!("compact::consolidate");
         let res = tokio::time::timeout(
             timeout,
@@ -329,7 +511,7 @@ where
                 .spawn_named(
                     || "persist::compact::consolidate",
                     Self::compact(
-                        CompactConfig::new(&cfg, &writer_id),
+                        compact_cfg,
                         Arc::clone(&blob),
                         Arc::clone(&metrics),
                         Arc::clone(&machine.applier.shard_metrics),
@@ -757,9 +939,17 @@ where
         // `CompactConfig::new` by overriding the inline writes threshold
         // config. This is a bit action-at-a-distance, so defensively detect if
         // this breaks here and log and correct it if so.
-        let has_inline_parts = batch.batch.parts.iter().any(|x| x.is_inline());
-        if has_inline_parts {
-            error!(%shard_id, ?cfg, "compaction result unexpectedly had inline writes");
+        //
+        // Small outputs may stay inline if so configured, but only if the
+        // entire output fits under the threshold.
+        let inline_bytes = batch.batch.inline_bytes();
+        let all_inline = batch.batch.parts.iter().all(|x| x.is_inline());
+        if inline_bytes > 0
+            && (!all_inline || inline_bytes >= cfg.batch.inline_writes_single_max_bytes)
+        {
+            if cfg.batch.inline_writes_single_max_bytes == 0 {
+                error!(%shard_id, ?cfg, "compaction result unexpectedly had inline writes");
+            }
             let () = batch
                 .flush_to_blob(
                     &cfg.batch,
@@ -916,6 +1106,86 @@ mod tests {
         assert_eq!(updates, all_ok(&data, 10));
     }
 
+    // Verifies that small compaction outputs are kept inline, when configured.
+    #[mz_persist_proc::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn compaction_inline_output(dyncfgs: ConfigUpdates) {
+        let data = vec![
+            (("0".to_owned(), "zero".to_owned()), 0, 1),
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+        ];
+
+        let cache = new_test_client_cache(&dyncfgs);
+        let (mut write, _) = cache
+            .open(PersistLocation::new_in_mem())
+            .await
+            .expect("client construction failed")
+            .expect_open::<String, String, u64, i64>(ShardId::new())
+            .await;
+        let b0 = write
+            .expect_batch(&data[..1], 0, 1)
+            .await
+            .into_hollow_batch();
+        let b1 = write
+            .expect_batch(&data[1..], 1, 2)
+            .await
+            .into_hollow_batch();
+        let req = CompactReq {
+            shard_id: write.machine.shard_id(),
+            desc: Description::new(
+                b0.desc.lower().clone(),
+                b1.desc.upper().clone(),
+                Antichain::from_elem(0u64),
+            ),
+            inputs: vec![b0, b1],
+        };
+
+        write
+            .cfg
+            .set_config(&INLINE_WRITES_TOTAL_MAX_BYTES, 16 * 1024);
+        let compact = |max_bytes: usize, shard_inline_bytes: usize| {
+            write
+                .cfg
+                .set_config(&INLINE_WRITES_COMPACTION_MAX_BYTES, max_bytes);
+            let mut compact_cfg = CompactConfig::new(&write.cfg, &write.writer_id);
+            compact_cfg.limit_inline_outputs(&write.cfg, shard_inline_bytes);
+            let schemas = Schemas {
+                key: Arc::new(StringSchema),
+                val: Arc::new(StringSchema),
+            };
+            Compactor::<String, String, u64, i64>::compact(
+                compact_cfg,
+                Arc::clone(&write.blob),
+                Arc::clone(&write.metrics),
+                write.metrics.shards.shard(&write.machine.shard_id(), ""),
+                Arc::new(IsolatedRuntime::default()),
+                req.clone(),
+                schemas,
+            )
+        };
+
+        let res = compact(4 * 1024, 0).await.expect("compaction failed");
+        assert_eq!(res.output.len, 2);
+        assert!(res.output.parts.iter().all(|x| x.is_inline()));
+        let res = compact(4 * 1024, 8 * 1024 - 1)
+            .await
+            .expect("compaction failed");
+        assert!(res.output.parts.iter().all(|x| x.is_inline()));
+
+        // Outputs at or above the threshold are written out to blob.
+        let res = compact(1, 0).await.expect("compaction failed");
+        assert_eq!(res.output.len, 2);
+        assert!(res.output.parts.iter().all(|x| !x.is_inline()));
+
+        // So are all outputs once the shard's inline writes take up half of
+        // the room for them.
+        let res = compact(4 * 1024, 8 * 1024)
+            .await
+            .expect("compaction failed");
+        assert_eq!(res.output.len, 2);
+        assert!(res.output.parts.iter().all(|x| !x.is_inline()));
+    }
+
     #[mz_persist_proc::test(tokio::test)]
     #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
     async fn compaction_partial_order(dyncfgs: ConfigUpdates) {
@@ -993,4 +1263,42 @@ mod tests {
         assert_eq!(part.desc, res.output.desc);
         assert_eq!(updates, all_ok(&data, Product::new(10, 0)));
     }
+
+    #[mz_ore::test]
+    fn shard_purpose() {
+        let cases = [
+            ("catalog", Some(ShardPurpose::Catalog)),
+            ("txns", Some(ShardPurpose::Catalog)),
+            ("s123", Some(ShardPurpose::Introspection)),
+            ("u7", Some(ShardPurpose::Data)),
+            ("unknown", None),
+            ("s", None),
+            ("user", None),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(ShardPurpose::from_shard_name(name), expected, "{name}");
+        }
+    }
+
+    #[mz_ore::test]
+    fn compact_config_for_purpose() {
+        let cfg = PersistConfig::new_for_tests();
+        cfg.set_config(&BLOB_TARGET_SIZE, 1024);
+        cfg.set_config(&COMPACTION_INTROSPECTION_BLOB_TARGET_SIZE, Some(256));
+        cfg.set_config(&COMPACTION_DATA_BLOB_TARGET_SIZE, Some(1024 * MiB));
+        let writer_id = WriterId::new();
+
+        let compact_cfg = CompactConfig::for_purpose(&cfg, &writer_id, None);
+        assert_eq!(compact_cfg.batch.blob_target_size, 1024);
+        let compact_cfg = CompactConfig::for_purpose(&cfg, &writer_id, Some(ShardPurpose::Catalog));
+        assert_eq!(compact_cfg.batch.blob_target_size, 1024);
+        let compact_cfg =
+            CompactConfig::for_purpose(&cfg, &writer_id, Some(ShardPurpose::Introspection));
+        assert_eq!(compact_cfg.batch.blob_target_size, 256);
+
+        // The memory bound leaves room for the larger parts.
+        let compact_cfg = CompactConfig::for_purpose(&cfg, &writer_id, Some(ShardPurpose::Data));
+        assert_eq!(compact_cfg.batch.blob_target_size, 1024 * MiB);
+        assert_eq!(compact_cfg.compaction_memory_bound_bytes, 4 * 1024 * MiB);
+    }
 }
@@ -92,8 +92,10 @@ pub struct Metrics {
     pub pushdown: PushdownMetrics,
     /// Metrics for consolidation.
     pub consolidation: ConsolidationMetrics,
-    /// Metrics for blob caching.
+    /// Metrics for in-memory blob caching.
     pub blob_cache_mem: BlobMemCache,
+    /// Metrics for on-disk blob caching.
+    pub blob_cache_disk: BlobMemCache,
     /// Metrics for tokio tasks.
     pub tasks: TasksMetrics,
     /// Metrics for columnar data encoding and decoding.
@@ -160,7 +162,8 @@ impl Metrics {
             pubsub_client: PubSubClientMetrics::new(registry),
             pushdown: PushdownMetrics::new(registry),
             consolidation: ConsolidationMetrics::new(registry),
-            blob_cache_mem: BlobMemCache::new(registry),
+            blob_cache_mem: BlobMemCache::new(registry, "mem"),
+            blob_cache_disk: BlobMemCache::new(registry, "disk"),
             tasks: TasksMetrics::new(registry),
             columnar,
             inline: InlineMetrics::new(registry),
@@ -2323,36 +2326,42 @@ pub struct BlobMemCache {
     pub(crate) size_bytes: UIntGauge,
     pub(crate) hits_blobs: IntCounter,
     pub(crate) hits_bytes: IntCounter,
+    pub(crate) misses_blobs: IntCounter,
     pub(crate) evictions: IntCounter,
 }
 
 impl BlobMemCache {
-    fn new(registry: &MetricsRegistry) -> Self {
+    fn new(registry: &MetricsRegistry, cache: &str) -> Self {
         BlobMemCache {
             size_blobs: registry.register(metric!(
                 name: "mz_persist_blob_cache_size_blobs",
                 help: "count of blobs in the cache",
-                const_labels: {"cache" => "mem"},
+                const_labels: {"cache" => cache},
             )),
             size_bytes: registry.register(metric!(
                 name: "mz_persist_blob_cache_size_bytes",
                 help: "total size of blobs in the cache",
-                const_labels: {"cache" => "mem"},
+                const_labels: {"cache" => cache},
             )),
             hits_blobs: registry.register(metric!(
                 name: "mz_persist_blob_cache_hits_blobs",
                 help: "count of blobs served via cache instead of s3",
-                const_labels: {"cache" => "mem"},
+                const_labels: {"cache" => cache},
             )),
             hits_bytes: registry.register(metric!(
                 name: "mz_persist_blob_cache_hits_bytes",
                 help: "total size of blobs served via cache instead of s3",
-                const_labels: {"cache" => "mem"},
+                const_labels: {"cache" => cache},
+            )),
+            misses_blobs: registry.register(metric!(
+                name: "mz_persist_blob_cache_misses_blobs",
+                help: "count of blobs not found in the cache",
+                const_labels: {"cache" => cache},
             )),
             evictions: registry.register(metric!(
                 name: "mz_persist_blob_cache_evictions",
                 help: "count of capacity-based cache evictions",
-                const_labels: {"cache" => "mem"},
+                const_labels: {"cache" => cache},
             )),
         }
     }
@@ -440,6 +440,8 @@ pub(crate) struct SpineMetrics {
     pub compact_batches: u64,
     pub compacting_batches: u64,
     pub noncompact_batches: u64,
+    /// The encoded size of the batches that are not yet compact.
+    pub noncompact_bytes: u64,
 }
 
 impl<T> Trace<T> {
@@ -597,11 +599,17 @@ impl<T: Timestamp + Lattice> Trace<T> {
         for batch in self.spine.spine_batches() {
             if batch.is_compact() {
                 metrics.compact_batches += 1;
+                continue;
             } else if batch.is_merging() {
                 metrics.compacting_batches += 1;
             } else {
                 metrics.noncompact_batches += 1;
             }
+            metrics.noncompact_bytes += batch
+                .parts
+                .iter()
+                .map(|part| u64::cast_from(part.batch.encoded_size_bytes()))
+                .sum::<u64>();
         }
         metrics
     }
@@ -25,6 +25,7 @@ use mz_ore::now::EpochMillis;
 use mz_ore::task::{AbortOnDropHandle, JoinHandle, RuntimeExt};
 use mz_ore::{instrument, soft_assert_or_log};
 use mz_persist::location::{Blob, SeqNo};
+use mz_persist_types::columnar::Schema;
 use mz_persist_types::{Codec, Codec64};
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
@@ -212,6 +213,108 @@ where
     }
 }
 
+/// A consolidated snapshot of all data at `as_of`, followed by a consolidated
+/// stream of all updates after it, in timestamp order.
+///
+/// Unlike [`Subscribe`], which hands out parts in whatever order and shape
+/// they happen to be stored in, this does the stitching of snapshot and listen
+/// that most consumers of a shard outside of dataflows need:
+/// - The first batch of updates is the snapshot, consolidated, with all times
+///   advanced to `as_of`.
+/// - Each subsequent batch of updates contains exactly the updates between the
+///   previous and the next [ListenEvent::Progress] frontier, consolidated and
+///   sorted by time.
+///
+/// The handle's read capability is downgraded as the subscription progresses.
+///
+/// See [`ReadHandle::subscription`].
+#[derive(Debug)]
+pub struct SubscriptionHandle<K, V, T, D>
+where
+    T: Timestamp + Lattice + Codec64,
+    K: Debug + Codec,
+    V: Debug + Codec,
+    D: Semigroup + Codec64 + Send + Sync,
+{
+    snapshot: Option<Vec<((Result<K, String>, Result<V, String>), T, D)>>,
+    listen: Listen<K, V, T, D>,
+}
+
+impl<K, V, T, D> SubscriptionHandle<K, V, T, D>
+where
+    K: Debug + Codec + Ord + Default,
+    V: Debug + Codec + Ord + Default,
+    T: Timestamp + Lattice + Codec64,
+    D: Semigroup + Ord + Codec64 + Send + Sync,
+{
+    /// The frontier at which the snapshot was taken.
+    pub fn as_of(&self) -> &Antichain<T> {
+        &self.listen.as_of
+    }
+
+    /// An exclusive upper bound on the progress of this subscription.
+    ///
+    /// This doesn't account for the snapshot, which is always returned by the
+    /// first call to [Self::fetch_next].
+    pub fn frontier(&self) -> &Antichain<T> {
+        self.listen.frontier()
+    }
+
+    /// Attempt to pull out the next values of this subscription.
+    ///
+    /// The first call returns the consolidated snapshot. Every following call
+    /// waits for the shard to make progress and returns the consolidated
+    /// updates up to the new frontier, followed by a [ListenEvent::Progress]
+    /// with that frontier. Empty batches of updates are omitted.
+    #[instrument(level = "debug", name = "subscription::next", fields(shard = %self.listen.handle.machine.shard_id()))]
+    pub async fn fetch_next(
+        &mut self,
+    ) -> Vec<ListenEvent<T, ((Result<K, String>, Result<V, String>), T, D)>> {
+        if let Some(snapshot) = self.snapshot.take() {
+            if !snapshot.is_empty() {
+                return vec![ListenEvent::Updates(snapshot)];
+            }
+        }
+
+        let (parts, progress) = self.listen.next(None).await;
+        let mut updates = Vec::new();
+        for part in parts {
+            updates.extend(self.listen.fetch_batch_part(part).await);
+        }
+        // Consolidation sorts by data first, so re-sort by time afterward to
+        // present the updates in the order they happened.
+        consolidate_updates(&mut updates);
+        updates.sort_by(|(_, t1, _), (_, t2, _)| t1.cmp(t2));
+
+        let mut ret = Vec::with_capacity(2);
+        if !updates.is_empty() {
+            ret.push(ListenEvent::Updates(updates));
+        }
+        ret.push(ListenEvent::Progress(progress));
+        ret
+    }
+
+    /// Convert this subscription into a [futures::Stream].
+    pub fn into_stream(
+        mut self,
+    ) -> impl Stream<Item = ListenEvent<T, ((Result<K, String>, Result<V, String>), T, D)>> {
+        async_stream::stream!({
+            loop {
+                for msg in self.fetch_next().await {
+                    yield msg;
+                }
+            }
+        })
+    }
+
+    /// Politely expires this subscription, releasing its lease.
+    ///
+    /// See [`Subscribe::expire`] for details.
+    pub async fn expire(self) {
+        self.listen.expire().await
+    }
+}
+
 /// Data and progress events of a shard subscription.
 ///
 /// TODO: Unify this with [timely::dataflow::operators::capture::event::Event].
@@ -907,7 +1010,7 @@ pub(crate) struct UnexpiredReadHandleState {
 pub struct Cursor<K: Codec, V: Codec, T: Timestamp + Codec64, D> {
     consolidator: Consolidator<T, D>,
     _lease: Lease,
-    _schemas: Schemas<K, V>,
+    schemas: Schemas<K, V>,
 }
 
 impl<K, V, T, D> Cursor<K, V, T, D>
@@ -926,7 +1029,18 @@ where
             .next()
             .await
             .expect("fetching a leased part")?;
-        let iter = iter.map(|(k, v, t, d)| ((K::decode(k), V::decode(v)), t, d));
+        let schemas = &self.schemas;
+        let iter = iter.map(move |(k, v, t, d)| {
+            let k = K::decode(k).map(|mut k| {
+                schemas.key.backfill(&mut k);
+                k
+            });
+            let v = V::decode(v).map(|mut v| {
+                schemas.val.backfill(&mut v);
+                v
+            });
+            ((k, v), t, d)
+        });
         Some(iter)
     }
 }
@@ -1019,7 +1133,7 @@ where
         Ok(Cursor {
             consolidator,
             _lease: lease,
-            _schemas: self.schemas.clone(),
+            schemas: self.schemas.clone(),
         })
     }
 
@@ -1054,6 +1168,36 @@ where
     }
 }
 
+impl<K, V, T, D> ReadHandle<K, V, T, D>
+where
+    K: Debug + Codec + Ord + Default,
+    V: Debug + Codec + Ord + Default,
+    T: Timestamp + Lattice + Codec64,
+    D: Semigroup + Ord + Codec64 + Send + Sync,
+{
+    /// Returns a consolidated snapshot of all of a shard's data at `as_of`,
+    /// followed by consolidated, time-ordered updates after it.
+    ///
+    /// For more details on this operation's semantics, see
+    /// [SubscriptionHandle].
+    ///
+    /// The `Since` error indicates that the requested `as_of` cannot be served
+    /// (the caller has out of date information) and includes the smallest
+    /// `as_of` that would have been accepted.
+    #[instrument(level = "debug", fields(shard = %self.machine.shard_id()))]
+    pub async fn subscription(
+        mut self,
+        as_of: Antichain<T>,
+    ) -> Result<SubscriptionHandle<K, V, T, D>, Since<T>> {
+        let snapshot = self.snapshot_and_fetch(as_of.clone()).await?;
+        let listen = self.listen(as_of).await?;
+        Ok(SubscriptionHandle {
+            snapshot: Some(snapshot),
+            listen,
+        })
+    }
+}
+
 impl<K, V, T, D> ReadHandle<K, V, T, D>
 where
     K: Debug + Codec + Ord + Default,
@@ -1277,6 +1421,117 @@ mod tests {
         )
     }
 
+    #[mz_persist_proc::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn subscription(dyncfgs: ConfigUpdates) {
+        let data = &[
+            (("k1".to_owned(), "v1".to_owned()), 0, 1),
+            (("k1".to_owned(), "v1".to_owned()), 1, 1),
+            (("k2".to_owned(), "v2".to_owned()), 1, 1),
+            (("k2".to_owned(), "v2".to_owned()), 2, -1),
+            // Written out of order, and partially cancelling, to verify the
+            // listen updates come back consolidated and sorted by time.
+            (("k4".to_owned(), "v4".to_owned()), 4, 1),
+            (("k3".to_owned(), "v3".to_owned()), 3, 1),
+            (("k4".to_owned(), "v4".to_owned()), 4, 1),
+            (("k5".to_owned(), "v5".to_owned()), 4, 1),
+            (("k5".to_owned(), "v5".to_owned()), 4, -1),
+        ];
+
+        let (mut write, read) = {
+            let client = new_test_client(&dyncfgs).await;
+            client.cfg.set_config(&BLOB_TARGET_SIZE, 0); // split batches across multiple parts
+            client
+                .expect_open::<String, String, u64, i64>(crate::ShardId::new())
+                .await
+        };
+
+        write.expect_compare_and_append(&data[0..2], 0, 2).await;
+        write.expect_compare_and_append(&data[2..4], 2, 3).await;
+
+        let mut subscription = read.subscription(Antichain::from_elem(2)).await.unwrap();
+        assert_eq!(
+            subscription.fetch_next().await,
+            vec![ListenEvent::Updates(vec![(
+                (Ok("k1".to_owned()), Ok("v1".to_owned())),
+                2,
+                2
+            )])]
+        );
+
+        write.expect_compare_and_append(&data[4..], 3, 5).await;
+        let mut updates = vec![];
+        while subscription.frontier().less_than(&5) {
+            for event in subscription.fetch_next().await {
+                if let ListenEvent::Updates(x) = event {
+                    updates.extend(x);
+                }
+            }
+        }
+        assert_eq!(
+            updates,
+            vec![
+                ((Ok("k3".to_owned()), Ok("v3".to_owned())), 3, 1),
+                ((Ok("k4".to_owned()), Ok("v4".to_owned())), 4, 2),
+            ]
+        );
+        subscription.expire().await;
+    }
+
+    #[mz_persist_proc::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn subscription_omits_empty_updates(dyncfgs: ConfigUpdates) {
+        let data = &[
+            // Cancels out in the snapshot.
+            (("k1".to_owned(), "v1".to_owned()), 0, 1),
+            (("k1".to_owned(), "v1".to_owned()), 1, -1),
+            // Cancels out in the listen.
+            (("k2".to_owned(), "v2".to_owned()), 2, 1),
+            (("k2".to_owned(), "v2".to_owned()), 2, -1),
+            // Written in reverse time order.
+            (("k3".to_owned(), "v3".to_owned()), 4, 1),
+            (("k3".to_owned(), "v3".to_owned()), 3, 1),
+        ];
+
+        let (mut write, read) = {
+            let client = new_test_client(&dyncfgs).await;
+            client.cfg.set_config(&BLOB_TARGET_SIZE, 0); // split batches across multiple parts
+            client
+                .expect_open::<String, String, u64, i64>(crate::ShardId::new())
+                .await
+        };
+
+        write.expect_compare_and_append(&data[0..2], 0, 2).await;
+        let mut subscription = read.subscription(Antichain::from_elem(1)).await.unwrap();
+
+        write.expect_compare_and_append(&data[2..4], 2, 3).await;
+        write.expect_compare_and_append(&data[4..], 3, 5).await;
+        let mut updates = vec![];
+        let mut progress = vec![];
+        while subscription.frontier().less_than(&5) {
+            for event in subscription.fetch_next().await {
+                match event {
+                    ListenEvent::Updates(x) => {
+                        assert!(!x.is_empty(), "empty batch of updates");
+                        updates.extend(x);
+                    }
+                    ListenEvent::Progress(frontier) => progress.push(frontier),
+                }
+            }
+        }
+        assert_eq!(
+            updates,
+            vec![
+                ((Ok("k3".to_owned()), Ok("v3".to_owned())), 3, 1),
+                ((Ok("k3".to_owned()), Ok("v3".to_owned())), 4, 1),
+            ]
+        );
+        // Every call after the snapshot reports progress, even if all of its
+        // updates cancelled out.
+        assert_eq!(progress.last(), Some(&Antichain::from_elem(5)));
+        subscription.expire().await;
+    }
+
     #[mz_persist_proc::test(tokio::test)]
     #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
     async fn snapshot_and_stream(dyncfgs: ConfigUpdates) {
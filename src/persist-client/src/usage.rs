@@ -127,6 +127,65 @@ pub struct ShardsUsageAudit {
     pub unattributable_bytes: u64,
 }
 
+/// A report of a shard's blob (S3) usage and of how fragmented its data is,
+/// for tracing storage cost anomalies back to a shard.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShardUsageReport {
+    /// Data in batches referenced by the most recent version of state.
+    pub live_bytes: u64,
+    /// Data in rollups referenced by the most recent version of state.
+    pub rollup_bytes: u64,
+    /// Data referenced by a live version of state that is not the most
+    /// recent, which is reclaimed once GC advances past it.
+    pub pending_gc_bytes: u64,
+    /// Data not referenced by any live version of state, including leaked
+    /// blobs.
+    pub garbage_bytes: u64,
+    /// The number of batches in the most recent version of state, by the
+    /// smallest power of two that is at least their size in bytes.
+    pub batch_sizes: BTreeMap<u64, u64>,
+    /// The number of batches in the trace that are not yet compact.
+    pub compaction_debt_batches: u64,
+    /// Data in batches in the trace that are not yet compact.
+    pub compaction_debt_bytes: u64,
+    /// The readers registered in the most recent version of state.
+    pub readers: Vec<ShardReaderReport>,
+}
+
+/// A reader registered with a shard, for diagnosing readers that hold back
+/// the shard's since.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShardReaderReport {
+    /// The ID of the reader.
+    pub reader_id: String,
+    /// Whether this is a critical reader, which holds no lease and is never
+    /// expired automatically.
+    pub critical: bool,
+    /// The since capability of the reader, or None if it is the empty
+    /// antichain.
+    pub since: Option<u64>,
+    /// Hostname of the persist user that registered the reader.
+    pub hostname: String,
+    /// Plaintext description of the reader's intent.
+    pub purpose: String,
+    /// UNIX_EPOCH timestamp (in millis) of the reader's most recent heartbeat.
+    /// None for critical readers.
+    pub last_heartbeat_timestamp_ms: Option<u64>,
+    /// Duration (in millis) after the most recent heartbeat after which the
+    /// reader may be expired. None for critical readers.
+    pub lease_duration_ms: Option<u64>,
+}
+
+/// The parts of a [ShardUsageReport] derived from the most recent version of
+/// state.
+#[derive(Clone, Debug, Default)]
+struct ShardFragmentation {
+    batch_sizes: BTreeMap<u64, u64>,
+    compaction_debt_batches: u64,
+    compaction_debt_bytes: u64,
+    readers: Vec<ShardReaderReport>,
+}
+
 #[derive(Clone, Debug, Default)]
 struct BlobUsage {
     by_shard: BTreeMap<ShardId, ShardBlobUsage>,
@@ -285,8 +344,52 @@ impl StorageUsageClient {
     pub async fn shard_usage_audit(&self, shard_id: ShardId) -> ShardUsageAudit {
         let mut blob_usage = self.blob_raw_usage(BlobKeyPrefix::Shard(&shard_id)).await;
         let blob_usage = blob_usage.by_shard.remove(&shard_id).unwrap_or_default();
-        self.shard_usage_given_blob_usage(shard_id, &blob_usage)
-            .await
+        let (audit, _) = self
+            .shard_usage_given_blob_usage(shard_id, &blob_usage)
+            .await;
+        audit
+    }
+
+    /// Computes [ShardUsageReport] for a given set of shards.
+    ///
+    /// Like [Self::shards_usage_audit], this performs a full scan of [Blob].
+    /// Shards without any data in [Blob] are omitted.
+    pub async fn shards_usage_report<I>(&self, shard_ids: I) -> BTreeMap<ShardId, ShardUsageReport>
+    where
+        I: IntoIterator<Item = ShardId>,
+    {
+        let blob_usage = self.blob_raw_usage(BlobKeyPrefix::All).await;
+
+        let semaphore = Semaphore::new(self.cfg.dynamic.usage_state_fetch_concurrency_limit());
+        let by_shard_futures = FuturesUnordered::new();
+        for shard_id in shard_ids {
+            let Some(shard_blob_usage) = blob_usage.by_shard.get(&shard_id) else {
+                continue;
+            };
+            let semaphore = &semaphore;
+            let shard_usage_fut = async move {
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .expect("acquiring permit from open semaphore");
+                let (audit, fragmentation) = self
+                    .shard_usage_given_blob_usage(shard_id, shard_blob_usage)
+                    .await;
+                let report = ShardUsageReport {
+                    live_bytes: audit.current_state_batches_bytes,
+                    rollup_bytes: audit.current_state_rollups_bytes,
+                    pending_gc_bytes: audit.referenced_not_current_state_bytes,
+                    garbage_bytes: audit.not_leaked_not_referenced_bytes + audit.leaked_bytes,
+                    batch_sizes: fragmentation.batch_sizes,
+                    compaction_debt_batches: fragmentation.compaction_debt_batches,
+                    compaction_debt_bytes: fragmentation.compaction_debt_bytes,
+                    readers: fragmentation.readers,
+                };
+                (shard_id, report)
+            };
+            by_shard_futures.push(shard_usage_fut);
+        }
+        by_shard_futures.collect().await
     }
 
     /// Computes [ShardUsageAudit] for every shard in an env.
@@ -321,7 +424,7 @@ impl StorageUsageClient {
                     .acquire()
                     .await
                     .expect("acquiring permit from open semaphore");
-                let shard_usage = self
+                let (shard_usage, _) = self
                     .shard_usage_given_blob_usage(*shard_id, total_bytes)
                     .await;
                 (*shard_id, shard_usage)
@@ -398,7 +501,7 @@ impl StorageUsageClient {
         &self,
         shard_id: ShardId,
         blob_usage: &ShardBlobUsage,
-    ) -> ShardUsageAudit {
+    ) -> (ShardUsageAudit, ShardFragmentation) {
         let mut start = Instant::now();
         let states_iter = self
             .state_versions
@@ -420,13 +523,14 @@ impl StorageUsageClient {
                     "interrupted"),
                     shard_id
                 );
-                return ShardUsageAudit {
+                let audit = ShardUsageAudit {
                     current_state_batches_bytes: 0,
                     current_state_rollups_bytes: 0,
                     referenced_not_current_state_bytes: 0,
                     not_leaked_not_referenced_bytes: 0,
                     leaked_bytes: blob_usage.total_bytes(),
                 };
+                return (audit, ShardFragmentation::default());
             }
         };
         let mut states_iter = states_iter
@@ -468,11 +572,16 @@ impl StorageUsageClient {
 
         let mut current_state_batches_bytes = 0;
         let mut current_state_rollups_bytes = 0;
+        let mut batch_sizes = BTreeMap::new();
         states_iter.state().blobs().for_each(|x| match x {
             HollowBlobRef::Batch(x) => {
                 for part in x.parts.iter() {
                     current_state_batches_bytes += u64::cast_from(part.hollow_bytes());
                 }
+                let batch_bytes = u64::cast_from(x.encoded_size_bytes());
+                *batch_sizes
+                    .entry(batch_bytes.next_power_of_two())
+                    .or_default() += 1;
             }
             HollowBlobRef::Rollup(x) => {
                 current_state_rollups_bytes +=
@@ -481,6 +590,45 @@ impl StorageUsageClient {
         });
         let current_state_bytes = current_state_batches_bytes + current_state_rollups_bytes;
 
+        let spine_metrics = states_iter.state().collections.trace.spine_metrics();
+        let leased_readers =
+            states_iter
+                .state()
+                .collections
+                .leased_readers
+                .iter()
+                .map(|(id, reader)| ShardReaderReport {
+                    reader_id: id.to_string(),
+                    critical: false,
+                    since: reader.since.as_option().copied(),
+                    hostname: reader.debug.hostname.clone(),
+                    purpose: reader.debug.purpose.clone(),
+                    last_heartbeat_timestamp_ms: Some(reader.last_heartbeat_timestamp_ms),
+                    lease_duration_ms: Some(reader.lease_duration_ms),
+                });
+        let critical_readers =
+            states_iter
+                .state()
+                .collections
+                .critical_readers
+                .iter()
+                .map(|(id, reader)| ShardReaderReport {
+                    reader_id: id.to_string(),
+                    critical: true,
+                    since: reader.since.as_option().copied(),
+                    hostname: reader.debug.hostname.clone(),
+                    purpose: reader.debug.purpose.clone(),
+                    last_heartbeat_timestamp_ms: None,
+                    lease_duration_ms: None,
+                });
+        let fragmentation = ShardFragmentation {
+            batch_sizes,
+            compaction_debt_batches: spine_metrics.compacting_batches
+                + spine_metrics.noncompact_batches,
+            compaction_debt_bytes: spine_metrics.noncompact_bytes,
+            readers: leased_readers.chain(critical_readers).collect(),
+        };
+
         let live_writers = &states_iter.state().collections.writers;
         let ret = ShardUsageAudit::from(ShardUsageCumulativeMaybeRacy {
             current_state_batches_bytes,
@@ -515,7 +663,7 @@ impl StorageUsageClient {
             .audit
             .step_math
             .inc_by(start.elapsed().as_secs_f64());
-        ret
+        (ret, fragmentation)
     }
 
     /// Returns the size (in bytes) of a subset of blobs specified by
@@ -986,6 +1134,61 @@ mod tests {
         assert_eq!(shard_usage_referenced.batches_bytes, batches_size);
     }
 
+    #[mz_persist_proc::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn usage_report(dyncfgs: ConfigUpdates) {
+        mz_ore::test::init_logging();
+
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+            (("3".to_owned(), "three".to_owned()), 3, 1),
+            (("4".to_owned(), "four".to_owned()), 4, 1),
+        ];
+
+        let shard_id = ShardId::new();
+        let unknown_shard_id = ShardId::new();
+        let mut client = new_test_client(&dyncfgs).await;
+        client.cfg.compaction_enabled = false;
+
+        let (mut write, read) = client
+            .expect_open::<String, String, u64, i64>(shard_id)
+            .await;
+        write.expect_append(&data[..2], vec![0], vec![3]).await;
+        write.expect_append(&data[2..], vec![3], vec![5]).await;
+
+        let usage = StorageUsageClient::open(client);
+        let mut reports = usage
+            .shards_usage_report([shard_id, unknown_shard_id])
+            .await;
+        let report = reports.remove(&shard_id).expect("shard has data in blob");
+        assert!(!reports.contains_key(&unknown_shard_id));
+
+        // Every batch in state is accounted for in the size distribution.
+        assert_eq!(report.batch_sizes.values().sum::<u64>(), 2);
+        assert!(report.rollup_bytes > 0);
+
+        // The report partitions the blob usage of the shard.
+        let audit = usage.shard_usage_audit(shard_id).await;
+        assert_eq!(
+            report.live_bytes
+                + report.rollup_bytes
+                + report.pending_gc_bytes
+                + report.garbage_bytes,
+            audit.total_bytes()
+        );
+
+        // The open read handle is reported along with its lease.
+        let reader = report
+            .readers
+            .iter()
+            .find(|x| x.reader_id == read.reader_id.to_string())
+            .expect("read handle is registered");
+        assert!(!reader.critical);
+        assert_eq!(reader.since, Some(0));
+        assert!(reader.lease_duration_ms.is_some());
+    }
+
     fn writer_id(x: char) -> WriterId {
         let x = [x, x, x, x].iter().collect::<String>();
         let s = format!("w{x}{x}-{x}-{x}-{x}-{x}{x}{x}");
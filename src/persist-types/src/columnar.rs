@@ -259,6 +259,15 @@ pub trait Schema<T>: Debug + Send + Sync {
 
     /// Returns a [Self::Encoder] for the given columns.
     fn encoder(&self, cols: ColumnsMut) -> Result<Self::Encoder, String>;
+
+    /// Adapts a value decoded from data written with a previous version of
+    /// this schema to this version.
+    ///
+    /// This is what allows the schema of a shard to evolve in backward
+    /// compatible ways (e.g. by adding nullable columns) without rewriting the
+    /// data that's already been written. The default implementation does
+    /// nothing, which is correct for schemas that never change.
+    fn backfill(&self, _val: &mut T) {}
 }
 
 /// A __stable__ encoding for a type that gets durably persisted in an
@@ -26,6 +26,7 @@ use crate::location::{Blob, Consensus, Determinate, ExternalError};
 use crate::mem::{MemBlob, MemBlobConfig, MemConsensus};
 use crate::metrics::S3BlobMetrics;
 use crate::postgres::{PostgresConsensus, PostgresConsensusConfig};
+use crate::replicated::ReplicatedBlob;
 use crate::s3::{S3Blob, S3BlobConfig};
 
 /// Adds the full set of all mz_persist `Config`s.
@@ -47,6 +48,14 @@ pub enum BlobConfig {
     /// Config for [MemBlob], only available in testing to prevent
     /// footguns.
     Mem(bool),
+    /// Config for [ReplicatedBlob], which mirrors writes to the primary
+    /// into the replica.
+    Replicated {
+        /// The blob that serves reads.
+        primary: Box<BlobConfig>,
+        /// The blob that writes are mirrored to.
+        replica: Box<BlobConfig>,
+    },
 }
 
 /// Configuration knobs for [Blob].
@@ -66,16 +75,33 @@ pub trait BlobKnobs: std::fmt::Debug + Send + Sync {
 impl BlobConfig {
     /// Opens the associated implementation of [Blob].
     pub async fn open(self) -> Result<Arc<dyn Blob>, ExternalError> {
+        match self {
+            BlobConfig::Replicated { primary, replica } => {
+                let primary = primary.open_unreplicated().await?;
+                let replica = replica.open_unreplicated().await?;
+                Ok(Arc::new(ReplicatedBlob::new(primary, replica)))
+            }
+            config => config.open_unreplicated().await,
+        }
+    }
+
+    async fn open_unreplicated(self) -> Result<Arc<dyn Blob>, ExternalError> {
         match self {
             BlobConfig::File(config) => Ok(Arc::new(FileBlob::open(config).await?)),
             BlobConfig::S3(config) => Ok(Arc::new(S3Blob::open(config).await?)),
             BlobConfig::Mem(tombstone) => {
                 Ok(Arc::new(MemBlob::open(MemBlobConfig::new(tombstone))))
             }
+            BlobConfig::Replicated { .. } => Err(ExternalError::from(anyhow!(
+                "blob replica must not itself be replicated"
+            ))),
         }
     }
 
     /// Parses a [Blob] config from a uri string.
+    ///
+    /// A `replica` param, containing the url-encoded uri of a second blob,
+    /// mirrors all writes into that blob. See [ReplicatedBlob].
     pub async fn try_from(
         value: &str,
         knobs: Box<dyn BlobKnobs>,
@@ -84,6 +110,64 @@ impl BlobConfig {
     ) -> Result<Self, ExternalError> {
         let url = Url::parse(value)
             .map_err(|err| anyhow!("failed to parse blob location {} as a url: {}", &value, err))?;
+        let replica = url
+            .query_pairs()
+            .find(|(key, _)| key == "replica")
+            .map(|(_, value)| value.into_owned());
+        let Some(replica) = replica else {
+            return Self::try_from_url(url, knobs, metrics, cfg).await;
+        };
+
+        let mut primary_url = url.clone();
+        let query_params = url
+            .query_pairs()
+            .filter(|(key, _)| key != "replica")
+            .collect::<Vec<_>>();
+        if query_params.is_empty() {
+            primary_url.set_query(None);
+        } else {
+            primary_url
+                .query_pairs_mut()
+                .clear()
+                .extend_pairs(query_params);
+        }
+        let replica_url = Url::parse(&replica).map_err(|err| {
+            anyhow!(
+                "failed to parse blob replica location {} as a url: {}",
+                &replica,
+                err
+            )
+        })?;
+        if replica_url.query_pairs().any(|(key, _)| key == "replica") {
+            return Err(ExternalError::from(anyhow!(
+                "blob replica must not itself be replicated: {}",
+                url.as_str()
+            )));
+        }
+
+        // The replica is configured with the same knobs as the primary.
+        let knobs: Arc<dyn BlobKnobs> = Arc::from(knobs);
+        let primary = Self::try_from_url(
+            primary_url,
+            Box::new(SharedBlobKnobs(Arc::clone(&knobs))),
+            metrics.clone(),
+            cfg.clone(),
+        )
+        .await?;
+        let replica =
+            Self::try_from_url(replica_url, Box::new(SharedBlobKnobs(knobs)), metrics, cfg).await?;
+        Ok(BlobConfig::Replicated {
+            primary: Box::new(primary),
+            replica: Box::new(replica),
+        })
+    }
+
+    async fn try_from_url(
+        url: Url,
+        knobs: Box<dyn BlobKnobs>,
+        metrics: S3BlobMetrics,
+        cfg: ConfigSet,
+    ) -> Result<Self, ExternalError> {
         let mut query_params = url.query_pairs().collect::<BTreeMap<_, _>>();
 
         let config = match url.scheme() {
@@ -165,6 +249,33 @@ impl BlobConfig {
     }
 }
 
+/// [BlobKnobs] shared between the primary and the replica of a
+/// [BlobConfig::Replicated].
+#[derive(Debug)]
+struct SharedBlobKnobs(Arc<dyn BlobKnobs>);
+
+impl BlobKnobs for SharedBlobKnobs {
+    fn operation_timeout(&self) -> Duration {
+        self.0.operation_timeout()
+    }
+
+    fn operation_attempt_timeout(&self) -> Duration {
+        self.0.operation_attempt_timeout()
+    }
+
+    fn connect_timeout(&self) -> Duration {
+        self.0.connect_timeout()
+    }
+
+    fn read_timeout(&self) -> Duration {
+        self.0.read_timeout()
+    }
+
+    fn is_cc_active(&self) -> bool {
+        self.0.is_cc_active()
+    }
+}
+
 /// Config for an implementation of [Consensus].
 #[derive(Debug, Clone)]
 pub enum ConsensusConfig {
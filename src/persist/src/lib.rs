@@ -27,6 +27,7 @@ pub mod location;
 pub mod mem;
 pub mod metrics;
 pub mod postgres;
+pub mod replicated;
 pub mod retry;
 pub mod s3;
 pub mod unreliable;
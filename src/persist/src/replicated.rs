@@ -0,0 +1,211 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A [Blob] that mirrors writes to a replica, e.g. in another region.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use mz_ore::bytes::SegmentedBytes;
+
+use crate::location::{Blob, BlobMetadata, ExternalError};
+
+/// A [Blob] that writes through to a replica.
+///
+/// Reads and listings are served by the primary. A [Blob::set] only succeeds
+/// once the value is durable in both the primary and the replica, so anything
+/// referenced by consensus is also readable from the replica. This allows
+/// recovering from the loss of the primary by pointing persist at the replica.
+///
+/// Deletes are applied to both, so the replica doesn't accumulate data that
+/// has been garbage collected from the primary.
+#[derive(Debug)]
+pub struct ReplicatedBlob {
+    primary: Arc<dyn Blob>,
+    replica: Arc<dyn Blob>,
+}
+
+impl ReplicatedBlob {
+    /// Returns a new [ReplicatedBlob] mirroring writes to `primary` into
+    /// `replica`.
+    pub fn new(primary: Arc<dyn Blob>, replica: Arc<dyn Blob>) -> Self {
+        ReplicatedBlob { primary, replica }
+    }
+
+    /// Compares the keys under the given prefix in the primary and the
+    /// replica.
+    ///
+    /// Listings of the two aren't atomic with respect to concurrent writes and
+    /// deletes, so a key in flight may be spuriously reported. Callers should
+    /// recheck any keys reported here before acting on them.
+    pub async fn check_consistency(
+        &self,
+        key_prefix: &str,
+    ) -> Result<ReplicaConsistency, ExternalError> {
+        let mut primary = BTreeMap::new();
+        self.primary
+            .list_keys_and_metadata(key_prefix, &mut |x| {
+                primary.insert(x.key.to_owned(), x.size_in_bytes);
+            })
+            .await?;
+        let mut replica = BTreeMap::new();
+        self.replica
+            .list_keys_and_metadata(key_prefix, &mut |x| {
+                replica.insert(x.key.to_owned(), x.size_in_bytes);
+            })
+            .await?;
+
+        let mut ret = ReplicaConsistency::default();
+        for (key, size_in_bytes) in primary.iter() {
+            match replica.get(key) {
+                None => ret.missing_in_replica.push(key.clone()),
+                Some(replica_size) if replica_size != size_in_bytes => {
+                    ret.size_mismatch.push(key.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        ret.missing_in_primary = replica
+            .into_keys()
+            .filter(|key| !primary.contains_key(key))
+            .collect();
+        Ok(ret)
+    }
+}
+
+/// The differences between the contents of the primary and the replica of a
+/// [ReplicatedBlob].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReplicaConsistency {
+    /// Keys present in the primary but not in the replica. This would make the
+    /// replica unusable for recovery if state references any of them.
+    pub missing_in_replica: Vec<String>,
+    /// Keys present in the replica but not in the primary, e.g. left behind
+    /// by a delete that failed partway.
+    pub missing_in_primary: Vec<String>,
+    /// Keys present in both, but with differing sizes.
+    pub size_mismatch: Vec<String>,
+}
+
+impl ReplicaConsistency {
+    /// Whether the primary and the replica have identical contents.
+    pub fn is_consistent(&self) -> bool {
+        self.missing_in_replica.is_empty()
+            && self.missing_in_primary.is_empty()
+            && self.size_mismatch.is_empty()
+    }
+}
+
+#[async_trait]
+impl Blob for ReplicatedBlob {
+    async fn get(&self, key: &str) -> Result<Option<SegmentedBytes>, ExternalError> {
+        self.primary.get(key).await
+    }
+
+    async fn list_keys_and_metadata(
+        &self,
+        key_prefix: &str,
+        f: &mut (dyn FnMut(BlobMetadata) + Send + Sync),
+    ) -> Result<(), ExternalError> {
+        self.primary.list_keys_and_metadata(key_prefix, f).await
+    }
+
+    async fn set(&self, key: &str, value: Bytes) -> Result<(), ExternalError> {
+        // Bytes is cheaply clonable, so write to both concurrently. Keys are
+        // never reused for different values, so retrying after a partial
+        // failure is safe.
+        let (primary, replica) = futures_util::join!(
+            self.primary.set(key, value.clone()),
+            self.replica.set(key, value)
+        );
+        primary?;
+        replica
+    }
+
+    async fn delete(&self, key: &str) -> Result<Option<usize>, ExternalError> {
+        // Delete from the primary first, so that a failure partway never
+        // leaves a key in the primary that the replica is missing. A failure
+        // partway instead leaves the key in the replica only, which is
+        // harmless and cleaned up when the delete is retried: deleting a
+        // missing key succeeds.
+        let deleted = self.primary.delete(key).await?;
+        let _ = self.replica.delete(key).await?;
+        Ok(deleted)
+    }
+
+    async fn restore(&self, key: &str) -> Result<(), ExternalError> {
+        self.primary.restore(key).await?;
+        self.replica.restore(key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::location::tests::blob_impl_test;
+    use crate::mem::{MemBlob, MemBlobConfig, MemMultiRegistry};
+
+    use super::*;
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn replicated_blob() -> Result<(), ExternalError> {
+        let registry = Arc::new(tokio::sync::Mutex::new(MemMultiRegistry::new(false)));
+        blob_impl_test(move |path| {
+            let path = path.to_owned();
+            let registry = Arc::clone(&registry);
+            async move {
+                let mut registry = registry.lock().await;
+                let primary = Arc::new(registry.blob(&format!("primary/{path}")));
+                let replica = Arc::new(registry.blob(&format!("replica/{path}")));
+                Ok(ReplicatedBlob::new(primary, replica))
+            }
+        })
+        .await
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn replicated_blob_consistency() -> Result<(), ExternalError> {
+        let primary = Arc::new(MemBlob::open(MemBlobConfig::new(false)));
+        let replica = Arc::new(MemBlob::open(MemBlobConfig::new(false)));
+        let blob = ReplicatedBlob::new(
+            Arc::clone(&primary) as Arc<dyn Blob>,
+            Arc::clone(&replica) as Arc<dyn Blob>,
+        );
+
+        // Writes through the replicated blob land in both.
+        blob.set("k0", Bytes::from("v0")).await?;
+        assert_eq!(
+            replica.get("k0").await?.map(|x| x.into_contiguous()),
+            Some(b"v0".to_vec())
+        );
+        assert!(blob.check_consistency("").await?.is_consistent());
+
+        // Divergence from writes that bypassed it is reported.
+        primary.set("k1", Bytes::from("v1")).await?;
+        replica.set("k2", Bytes::from("v2")).await?;
+        replica.set("k0", Bytes::from("v0-longer")).await?;
+        assert_eq!(
+            blob.check_consistency("").await?,
+            ReplicaConsistency {
+                missing_in_replica: vec!["k1".to_owned()],
+                missing_in_primary: vec!["k2".to_owned()],
+                size_mismatch: vec!["k0".to_owned()],
+            }
+        );
+
+        // Deletes are applied to both.
+        assert_eq!(blob.delete("k0").await?, Some(2));
+        assert_eq!(replica.get("k0").await?, None);
+
+        Ok(())
+    }
+}
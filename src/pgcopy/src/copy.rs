@@ -21,6 +21,9 @@ use serde::Serialize;
 
 static END_OF_COPY_MARKER: &[u8] = b"\\.";
 
+/// The signature that starts the header of the binary format.
+pub static BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xFF\r\n\0";
+
 include!(concat!(env!("OUT_DIR"), "/mz_pgcopy.copy.rs"));
 
 fn encode_copy_row_binary(
@@ -495,11 +498,56 @@ pub fn decode_copy_format<'a>(
     match params {
         CopyFormatParams::Text(params) => decode_copy_format_text(data, column_types, params),
         CopyFormatParams::Csv(params) => decode_copy_format_csv(data, column_types, params),
-        CopyFormatParams::Binary => Err(io::Error::new(
-            io::ErrorKind::Unsupported,
-            "cannot decode as binary format",
-        )),
+        CopyFormatParams::Binary => decode_copy_format_binary(data, column_types),
+    }
+}
+
+/// Returns the length of the longest prefix of `data` that consists only of
+/// complete records in the given format, or `None` if `data` does not contain a
+/// complete record.
+///
+/// Decoding `data` in pieces split at these boundaries produces the same rows
+/// as decoding it all at once, which lets callers decode large payloads
+/// incrementally. The binary format is not supported and always returns
+/// `None`.
+pub fn copy_record_boundary(data: &[u8], params: &CopyFormatParams) -> Option<usize> {
+    let mut boundary = None;
+    match params {
+        CopyFormatParams::Text(_) => {
+            // Newlines within values are escaped, except that a backslash
+            // followed by a literal newline is part of the value.
+            let mut escaped = false;
+            for (i, b) in data.iter().enumerate() {
+                match b {
+                    b'\\' => escaped = !escaped,
+                    b'\n' if !escaped => boundary = Some(i + 1),
+                    _ => escaped = false,
+                }
+            }
+        }
+        CopyFormatParams::Csv(CopyCsvFormatParams { quote, escape, .. }) => {
+            // Newlines are part of the value when they appear within quotes.
+            let mut in_quotes = false;
+            let mut i = 0;
+            while i < data.len() {
+                let b = data[i];
+                if in_quotes {
+                    if b == *escape && data.get(i + 1) == Some(quote) {
+                        i += 1;
+                    } else if b == *quote {
+                        in_quotes = false;
+                    }
+                } else if b == *quote {
+                    in_quotes = true;
+                } else if b == b'\n' {
+                    boundary = Some(i + 1);
+                }
+                i += 1;
+            }
+        }
+        CopyFormatParams::Binary => {}
     }
+    boundary
 }
 
 /// Encodes the given `Row` into bytes based on the given `CopyFormatParams`.
@@ -598,6 +646,86 @@ pub fn decode_copy_format_text(
     Ok(rows)
 }
 
+pub fn decode_copy_format_binary(
+    mut data: &[u8],
+    column_types: &[mz_pgrepr::Type],
+) -> Result<Vec<Row>, io::Error> {
+    fn invalid(msg: impl Into<String>) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, msg.into())
+    }
+    fn take<'a>(data: &mut &'a [u8], n: usize) -> Result<&'a [u8], io::Error> {
+        if data.len() < n {
+            return Err(invalid("unexpected EOF in COPY data"));
+        }
+        let (taken, rest) = data.split_at(n);
+        *data = rest;
+        Ok(taken)
+    }
+    fn take_i32(data: &mut &[u8]) -> Result<i32, io::Error> {
+        let bytes = take(data, 4)?;
+        Ok(i32::from_be_bytes(
+            bytes.try_into().expect("slice has length 4"),
+        ))
+    }
+
+    // The header is the signature, a 32-bit flags field, and a header
+    // extension area prefixed by its 32-bit length.
+    if take(&mut data, BINARY_SIGNATURE.len()).ok() != Some(BINARY_SIGNATURE) {
+        return Err(invalid("COPY file signature not recognized"));
+    }
+    let flags =
+        take_i32(&mut data).map_err(|_| invalid("invalid COPY file header (missing flags)"))?;
+    // The upper 16 bits are critical flags, e.g. whether OIDs are included,
+    // none of which we support.
+    if flags & !0xFFFF != 0 {
+        return Err(invalid("unrecognized critical flags in COPY file header"));
+    }
+    let extension_len = take_i32(&mut data)
+        .ok()
+        .and_then(|len| usize::try_from(len).ok())
+        .ok_or_else(|| invalid("invalid COPY file header (missing length)"))?;
+    take(&mut data, extension_len)
+        .map_err(|_| invalid("invalid COPY file header (wrong length)"))?;
+
+    let mut rows = Vec::new();
+    while !data.is_empty() {
+        let field_count =
+            i16::from_be_bytes(take(&mut data, 2)?.try_into().expect("slice has length 2"));
+        // A field count of -1 is the trailer. Like the end of copy marker of
+        // the text format, we drop any data after it on the floor.
+        if field_count == -1 {
+            break;
+        }
+        if usize::try_from(field_count).ok() != Some(column_types.len()) {
+            return Err(invalid(format!(
+                "row field count is {}, expected {}",
+                field_count,
+                column_types.len()
+            )));
+        }
+        let mut row = Vec::with_capacity(column_types.len());
+        let buf = RowArena::new();
+        for typ in column_types {
+            let len = take_i32(&mut data)?;
+            if len == -1 {
+                row.push(Datum::Null);
+                continue;
+            }
+            let len = usize::try_from(len).map_err(|_| invalid("invalid field size"))?;
+            let raw_value = take(&mut data, len)?;
+            match mz_pgrepr::Value::decode_binary(typ, raw_value) {
+                Ok(value) => row.push(value.into_datum(&buf, typ)),
+                Err(err) => {
+                    let msg = format!("unable to decode column: {}", err);
+                    return Err(invalid(msg));
+                }
+            }
+        }
+        rows.push(Row::pack(row));
+    }
+    Ok(rows)
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 pub struct CopyCsvFormatParams<'a> {
     pub delimiter: u8,
@@ -787,6 +915,69 @@ mod tests {
 
     use super::*;
 
+    #[mz_ore::test]
+    fn test_copy_record_boundary() {
+        let text = CopyFormatParams::Text(CopyTextFormatParams::default());
+        assert_eq!(copy_record_boundary(b"a\tb", &text), None);
+        assert_eq!(copy_record_boundary(b"a\tb\nc\td", &text), Some(4));
+        assert_eq!(copy_record_boundary(b"a\\\nb\nc", &text), Some(5));
+        assert_eq!(copy_record_boundary(b"a\\\\\nb", &text), Some(4));
+
+        let csv = CopyFormatParams::Csv(CopyCsvFormatParams {
+            delimiter: b',',
+            quote: b'"',
+            escape: b'"',
+            header: false,
+            null: "".into(),
+        });
+        assert_eq!(copy_record_boundary(b"\"a\nb\",c", &csv), None);
+        assert_eq!(copy_record_boundary(b"\"a\nb\",c\nd", &csv), Some(8));
+        assert_eq!(copy_record_boundary(b"\"a\"\"\n\",c\nd", &csv), Some(9));
+
+        assert_eq!(
+            copy_record_boundary(b"a\n", &CopyFormatParams::Binary),
+            None
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_copy_format_binary_roundtrip() {
+        let typ = RelationType::new(vec![
+            ColumnType {
+                scalar_type: ScalarType::String,
+                nullable: true,
+            },
+            ColumnType {
+                scalar_type: ScalarType::Int32,
+                nullable: true,
+            },
+        ]);
+        let rows = vec![
+            Row::pack_slice(&[Datum::Null, Datum::Int32(2)]),
+            Row::pack_slice(&[Datum::String("\t"), Datum::Int32(4)]),
+        ];
+
+        let mut data = Vec::new();
+        data.extend(BINARY_SIGNATURE);
+        data.extend([0; 8]);
+        for row in &rows {
+            encode_copy_format(&CopyFormatParams::Binary, row, &typ, &mut data).unwrap();
+        }
+        data.extend((-1i16).to_be_bytes());
+
+        let column_types: Vec<_> = typ
+            .column_types
+            .iter()
+            .map(|x| mz_pgrepr::Type::from(&x.scalar_type))
+            .collect();
+        let decoded = decode_copy_format(&data, &column_types, CopyFormatParams::Binary).unwrap();
+        assert_eq!(decoded, rows);
+
+        // A missing header or mismatched field count is rejected.
+        assert!(decode_copy_format(&data[8..], &column_types, CopyFormatParams::Binary).is_err());
+        assert!(decode_copy_format(&data, &column_types[..1], CopyFormatParams::Binary).is_err());
+    }
+
     #[mz_ore::test]
     fn test_copy_format_text_parser() {
         let text = "\t\\nt e\t\\N\t\n\\x60\\xA\\x7D\\x4a\n\\44\\044\\123".as_bytes();
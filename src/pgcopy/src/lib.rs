@@ -16,7 +16,7 @@
 mod copy;
 
 pub use copy::{
-    decode_copy_format, encode_copy_format, CopyCsvFormatParams, CopyFormatParams,
-    CopyTextFormatParams, CopyTextFormatParser, ProtoCopyCsvFormatParams, ProtoCopyFormatParams,
-    ProtoCopyTextFormatParams,
+    copy_record_boundary, decode_copy_format, encode_copy_format, CopyCsvFormatParams,
+    CopyFormatParams, CopyTextFormatParams, CopyTextFormatParser, ProtoCopyCsvFormatParams,
+    ProtoCopyFormatParams, ProtoCopyTextFormatParams, BINARY_SIGNATURE,
 };
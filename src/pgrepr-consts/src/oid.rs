@@ -691,3 +691,16 @@ pub const VIEW_PG_ATTRDEF_ALL_DATABASES_OID: u32 = 16968;
 pub const INDEX_PG_ATTRDEF_ALL_DATABASES_IND_OID: u32 = 16969;
 pub const VIEW_PG_ATTRIBUTE_ALL_DATABASES_OID: u32 = 16970;
 pub const INDEX_PG_ATTRIBUTE_ALL_DATABASES_IND_OID: u32 = 16971;
+pub const SOURCE_MZ_COMPUTE_REAPED_DATAFLOWS_OID: u32 = 16972;
+pub const VIEW_MZ_SOURCE_HEALTH_OID: u32 = 16973;
+pub const VIEW_MZ_STATEMENT_LIFECYCLE_DURATIONS_OID: u32 = 16974;
+pub const LOG_MZ_COMPUTE_LIR_MAPPING_PER_WORKER_OID: u32 = 16975;
+pub const VIEW_MZ_LIR_MAPPING_OID: u32 = 16976;
+pub const TABLE_MZ_DATABASE_PARAMETERS_OID: u32 = 16977;
+pub const TABLE_MZ_STORAGE_SHARD_USAGE_OID: u32 = 16978;
+pub const TABLE_MZ_STORAGE_SHARD_BATCH_SIZES_HISTOGRAM_OID: u32 = 16979;
+pub const TABLE_MZ_STORAGE_SHARD_READERS_OID: u32 = 16980;
+pub const VIEW_MZ_METERED_USAGE_OID: u32 = 16981;
+pub const SOURCE_MZ_STORAGE_SHARD_FINALIZATIONS_OID: u32 = 16982;
+pub const SOURCE_MZ_CLUSTER_REPLICA_EVENTS_OID: u32 = 16983;
+pub const VIEW_MZ_SSH_TUNNEL_STATUSES_OID: u32 = 16984;
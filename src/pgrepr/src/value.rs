@@ -634,7 +634,7 @@ impl Value {
     /// format](Format::Binary).
     pub fn decode_binary(ty: &Type, raw: &[u8]) -> Result<Value, Box<dyn Error + Sync + Send>> {
         match ty {
-            Type::Array(_) => Err("input of array types is not implemented".into()),
+            Type::Array(elem_type) => decode_binary_array(elem_type, raw),
             Type::Int2Vector => Err("input of int2vector types is not implemented".into()),
             Type::Bool => bool::from_sql(ty.inner(), raw).map(Value::Bool),
             Type::Bytea => Vec::<u8>::from_sql(ty.inner(), raw).map(Value::Bytea),
@@ -701,6 +701,70 @@ impl Value {
     }
 }
 
+/// Deserializes an array of `elem_type` elements in the format written by
+/// [`Value::encode_binary`].
+fn decode_binary_array(
+    elem_type: &Type,
+    mut raw: &[u8],
+) -> Result<Value, Box<dyn Error + Sync + Send>> {
+    let ndims = usize::try_from(decode_i32(&mut raw)?)?;
+    // Whether the array contains NULLs is implied by the element lengths.
+    let _has_null = decode_i32(&mut raw)?;
+    let elem_oid = u32::reinterpret_cast(decode_i32(&mut raw)?);
+    if elem_oid != elem_type.oid() {
+        return Err(format!(
+            "wrong element type: expected {}, got {}",
+            elem_type.oid(),
+            elem_oid
+        )
+        .into());
+    }
+    let mut dims = Vec::with_capacity(ndims);
+    for _ in 0..ndims {
+        let length = usize::try_from(decode_i32(&mut raw)?)?;
+        let lower_bound = isize::try_from(decode_i32(&mut raw)?)?;
+        dims.push(ArrayDimension {
+            lower_bound,
+            length,
+        });
+    }
+    let nelements = if dims.is_empty() {
+        0
+    } else {
+        dims.iter().map(|dim| dim.length).product()
+    };
+    let mut elements = Vec::new();
+    for _ in 0..nelements {
+        let len = decode_i32(&mut raw)?;
+        if len < 0 {
+            elements.push(None);
+            continue;
+        }
+        let len = usize::try_from(len)?;
+        if raw.len() < len {
+            return Err("insufficient data left in message".into());
+        }
+        let (elem, rest) = raw.split_at(len);
+        elements.push(Some(Value::decode_binary(elem_type, elem)?));
+        raw = rest;
+    }
+    if !raw.is_empty() {
+        return Err("incorrect binary data format in array".into());
+    }
+    Ok(Value::Array { dims, elements })
+}
+
+fn decode_i32(raw: &mut &[u8]) -> Result<i32, Box<dyn Error + Sync + Send>> {
+    if raw.len() < 4 {
+        return Err("insufficient data left in message".into());
+    }
+    let (bytes, rest) = raw.split_at(4);
+    *raw = rest;
+    Ok(i32::from_be_bytes(
+        bytes.try_into().expect("slice has length 4"),
+    ))
+}
+
 fn encode_element(buf: &mut BytesMut, elem: Option<&Value>, ty: &Type) -> Result<(), io::Error> {
     match elem {
         None => buf.put_i32(-1),
@@ -762,4 +826,41 @@ mod tests {
             "invalid input syntax for type array: Specifying array lower bounds is not supported: \"[0:0]={t}\"".to_string()
         );
     }
+    #[mz_ore::test]
+    fn decode_binary_array_roundtrip() {
+        let int_array = Value::Array {
+            dims: vec![
+                ArrayDimension {
+                    lower_bound: 1,
+                    length: 2,
+                },
+                ArrayDimension {
+                    lower_bound: 1,
+                    length: 2,
+                },
+            ],
+            elements: vec![
+                Some(Value::Int4(1)),
+                None,
+                Some(Value::Int4(3)),
+                Some(Value::Int4(4)),
+            ],
+        };
+        let int_array_tpe = Type::Array(Box::new(Type::Int4));
+
+        let mut buf = BytesMut::new();
+        int_array.encode_binary(&int_array_tpe, &mut buf).unwrap();
+        let decoded = Value::decode_binary(&int_array_tpe, &buf).unwrap();
+        let mut roundtrip = BytesMut::new();
+        decoded
+            .encode_binary(&int_array_tpe, &mut roundtrip)
+            .unwrap();
+        assert_eq!(buf, roundtrip);
+
+        // The element type must match.
+        let text_array_tpe = Type::Array(Box::new(Type::Text));
+        assert!(Value::decode_binary(&text_array_tpe, &buf).is_err());
+        // Truncated input is rejected.
+        assert!(Value::decode_binary(&int_array_tpe, &buf[..buf.len() - 1]).is_err());
+    }
 }
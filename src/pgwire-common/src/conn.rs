@@ -12,7 +12,9 @@ use std::task::{Context, Poll};
 
 use async_trait::async_trait;
 use mz_ore::netio::AsyncReady;
-use mz_server_core::TlsMode;
+use mz_server_core::{ClientCertIdentity, TlsMode};
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
 use tokio::io::{self, AsyncRead, AsyncWrite, Interest, ReadBuf, Ready};
 use tokio_openssl::SslStream;
 use tokio_postgres::error::SqlState;
@@ -51,6 +53,40 @@ impl<A> Conn<A> {
 
         Ok(())
     }
+
+    /// Returns the identity in the client's verified TLS certificate, if the
+    /// client presented one.
+    pub fn client_cert_identity(&self) -> Option<ClientCertIdentity> {
+        match self {
+            Conn::Unencrypted(_) => None,
+            Conn::Ssl(stream) => ClientCertIdentity::from_ssl(stream.ssl()),
+        }
+    }
+
+    /// Returns the data that binds a SCRAM exchange to this connection, if it
+    /// is encrypted.
+    ///
+    /// This is the `tls-server-end-point` channel binding of RFC 5929: the
+    /// hash of the server's certificate, computed with the hash function of
+    /// the certificate's signature algorithm, or SHA-256 if that is MD5 or
+    /// SHA-1.
+    pub fn tls_server_end_point(&self) -> Option<Vec<u8>> {
+        let Conn::Ssl(stream) = self else {
+            return None;
+        };
+        let cert = stream.ssl().certificate()?;
+        let algorithms = cert
+            .signature_algorithm()
+            .object()
+            .nid()
+            .signature_algorithms()?;
+        let digest = match algorithms.digest {
+            Nid::MD5 | Nid::SHA1 => MessageDigest::sha256(),
+            nid => MessageDigest::from_nid(nid)?,
+        };
+        let hash = cert.digest(digest).ok()?;
+        Some(hash.to_vec())
+    }
 }
 
 impl<A> AsyncRead for Conn<A>
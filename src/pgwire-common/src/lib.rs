@@ -16,6 +16,7 @@ mod codec;
 mod conn;
 mod format;
 mod message;
+pub mod scram;
 mod severity;
 
 pub use codec::{
@@ -25,7 +26,7 @@ pub use codec::{
 pub use conn::Conn;
 pub use format::Format;
 pub use message::{
-    ErrorResponse, FrontendMessage, FrontendStartupMessage, VERSIONS, VERSION_3, VERSION_CANCEL,
-    VERSION_GSSENC, VERSION_SSL,
+    ErrorResponse, FrontendMessage, FrontendStartupMessage, STRUCTURED_FIELD_TYPE, VERSIONS,
+    VERSION_3, VERSION_CANCEL, VERSION_GSSENC, VERSION_SSL,
 };
 pub use severity::Severity;
@@ -182,6 +182,12 @@ pub enum FrontendMessage {
     Password {
         password: String,
     },
+
+    /// A response to an authentication request.
+    ///
+    /// Its contents depend on the authentication method the server requested,
+    /// so they are left for the server to decode.
+    AuthenticationResponse(Vec<u8>),
 }
 
 impl FrontendMessage {
@@ -202,6 +208,7 @@ impl FrontendMessage {
             FrontendMessage::CopyDone => "copy_done",
             FrontendMessage::CopyFail(_) => "copy_fail",
             FrontendMessage::Password { .. } => "password",
+            FrontendMessage::AuthenticationResponse(_) => "authentication_response",
         }
     }
 }
@@ -214,8 +221,18 @@ pub struct ErrorResponse {
     pub detail: Option<String>,
     pub hint: Option<String>,
     pub position: Option<usize>,
+    /// A JSON description of the notice, sent in the
+    /// [`STRUCTURED_FIELD_TYPE`] field when present.
+    pub structured: Option<String>,
 }
 
+/// The field type of the Materialize-specific `ErrorResponse`/`NoticeResponse`
+/// field that carries a JSON description of the message.
+///
+/// PostgreSQL requires frontends to silently ignore fields of unrecognized
+/// type, so clients that don't know about this field are unaffected.
+pub const STRUCTURED_FIELD_TYPE: u8 = b'J';
+
 impl ErrorResponse {
     pub fn fatal<S>(code: SqlState, message: S) -> ErrorResponse
     where
@@ -249,6 +266,7 @@ impl ErrorResponse {
             detail: None,
             hint: None,
             position: None,
+            structured: None,
         }
     }
 
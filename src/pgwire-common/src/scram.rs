@@ -0,0 +1,481 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The SCRAM-SHA-256 authentication mechanism.
+//!
+//! See [RFC 5802] for the mechanism, [RFC 7677] for its SHA-256 variant and
+//! [RFC 5929] for the `tls-server-end-point` channel binding type. Verifiers
+//! use the same textual format as PostgreSQL, so they can be moved between the
+//! two systems.
+//!
+//! [RFC 5802]: https://datatracker.ietf.org/doc/html/rfc5802
+//! [RFC 7677]: https://datatracker.ietf.org/doc/html/rfc7677
+//! [RFC 5929]: https://datatracker.ietf.org/doc/html/rfc5929
+
+use std::fmt;
+
+use mz_ore::cast::CastFrom;
+use openssl::base64;
+use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
+use openssl::memcmp;
+use openssl::pkcs5;
+use openssl::pkey::PKey;
+use openssl::rand;
+use openssl::sha;
+use openssl::sign::Signer;
+
+/// The name of the SCRAM-SHA-256 SASL mechanism.
+pub const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+
+/// The name of the SCRAM-SHA-256 SASL mechanism with channel binding.
+pub const SCRAM_SHA_256_PLUS: &str = "SCRAM-SHA-256-PLUS";
+
+/// The only supported channel binding type.
+const CHANNEL_BINDING_TYPE: &str = "tls-server-end-point";
+
+/// The number of PBKDF2 iterations used for new verifiers. This matches the
+/// default of PostgreSQL's `scram_iterations`.
+const ITERATIONS: u32 = 4096;
+
+/// The length of the salt of new verifiers, in bytes.
+const SALT_LEN: usize = 16;
+
+/// The length of the server's part of the nonce, in bytes.
+const NONCE_LEN: usize = 18;
+
+/// The length of a SHA-256 digest, in bytes.
+const KEY_LEN: usize = 32;
+
+/// What a server stores to verify a password with SCRAM-SHA-256, without
+/// storing the password itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScramVerifier {
+    iterations: u32,
+    salt: Vec<u8>,
+    stored_key: [u8; KEY_LEN],
+    server_key: [u8; KEY_LEN],
+}
+
+impl ScramVerifier {
+    /// Computes a verifier for `password` with a random salt.
+    pub fn new(password: &str) -> Result<ScramVerifier, ErrorStack> {
+        let mut salt = vec![0; SALT_LEN];
+        rand::rand_bytes(&mut salt)?;
+        ScramVerifier::with_salt(password, salt, ITERATIONS)
+    }
+
+    fn with_salt(
+        password: &str,
+        salt: Vec<u8>,
+        iterations: u32,
+    ) -> Result<ScramVerifier, ErrorStack> {
+        // Like PostgreSQL, fall back to the password as given if it can't be
+        // normalized. Clients do the same.
+        let password = match stringprep::saslprep(password) {
+            Ok(password) => password,
+            Err(_) => password.into(),
+        };
+        let mut salted_password = [0; KEY_LEN];
+        pkcs5::pbkdf2_hmac(
+            password.as_bytes(),
+            &salt,
+            usize::cast_from(iterations),
+            MessageDigest::sha256(),
+            &mut salted_password,
+        )?;
+        let client_key = hmac(&salted_password, b"Client Key")?;
+        Ok(ScramVerifier {
+            iterations,
+            salt,
+            stored_key: sha::sha256(&client_key),
+            server_key: hmac(&salted_password, b"Server Key")?,
+        })
+    }
+
+    /// Parses a verifier in the format produced by its `Display`
+    /// implementation, `SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>`.
+    pub fn parse(s: &str) -> Option<ScramVerifier> {
+        let s = s.strip_prefix(SCRAM_SHA_256)?.strip_prefix('$')?;
+        let (params, keys) = s.split_once('$')?;
+        let (iterations, salt) = params.split_once(':')?;
+        let (stored_key, server_key) = keys.split_once(':')?;
+        Some(ScramVerifier {
+            iterations: iterations.parse().ok()?,
+            salt: base64::decode_block(salt).ok()?,
+            stored_key: base64::decode_block(stored_key).ok()?.try_into().ok()?,
+            server_key: base64::decode_block(server_key).ok()?.try_into().ok()?,
+        })
+    }
+}
+
+impl fmt::Display for ScramVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{SCRAM_SHA_256}${}:{}${}:{}",
+            self.iterations,
+            base64::encode_block(&self.salt),
+            base64::encode_block(&self.stored_key),
+            base64::encode_block(&self.server_key),
+        )
+    }
+}
+
+/// An error in a SCRAM exchange.
+#[derive(Debug)]
+pub enum ScramError {
+    /// The client sent a message that does not follow the protocol.
+    Malformed(&'static str),
+    /// The client requested something the server does not support.
+    Unsupported(&'static str),
+    /// The channel binding the client sent does not match the connection.
+    ChannelBindingMismatch,
+    /// The client proved no knowledge of the password.
+    InvalidProof,
+    /// OpenSSL failed to compute a digest.
+    Ssl(ErrorStack),
+}
+
+impl From<ErrorStack> for ScramError {
+    fn from(e: ErrorStack) -> ScramError {
+        ScramError::Ssl(e)
+    }
+}
+
+impl fmt::Display for ScramError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScramError::Malformed(reason) => write!(f, "malformed SCRAM message: {reason}"),
+            ScramError::Unsupported(what) => write!(f, "SCRAM {what} not supported"),
+            ScramError::ChannelBindingMismatch => f.write_str("SCRAM channel binding check failed"),
+            ScramError::InvalidProof => f.write_str("invalid SCRAM client proof"),
+            ScramError::Ssl(e) => write!(f, "SCRAM failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ScramError {}
+
+/// The server's side of a SCRAM-SHA-256 exchange, before the client's first
+/// message.
+#[derive(Debug)]
+pub struct ScramServer {
+    verifier: ScramVerifier,
+    /// The channel binding data of the connection, if it supports channel
+    /// binding.
+    channel_binding: Option<Vec<u8>>,
+}
+
+impl ScramServer {
+    /// Starts an exchange that checks the client's password against
+    /// `verifier`.
+    ///
+    /// `channel_binding` holds the `tls-server-end-point` data of the
+    /// connection, if it is encrypted. In that case the server offers
+    /// [`SCRAM_SHA_256_PLUS`] and refuses clients that claim to support
+    /// channel binding but don't use it, as that indicates a downgrade attack.
+    pub fn new(verifier: ScramVerifier, channel_binding: Option<Vec<u8>>) -> ScramServer {
+        ScramServer {
+            verifier,
+            channel_binding,
+        }
+    }
+
+    /// Returns the SASL mechanisms the server offers, in order of preference.
+    pub fn mechanisms(&self) -> &'static [&'static str] {
+        if self.channel_binding.is_some() {
+            &[SCRAM_SHA_256_PLUS, SCRAM_SHA_256]
+        } else {
+            &[SCRAM_SHA_256]
+        }
+    }
+
+    /// Handles the client's first message, sent along with the `mechanism` it
+    /// selected.
+    pub fn handle_client_first(
+        self,
+        mechanism: &str,
+        message: &[u8],
+    ) -> Result<ScramServerFirst, ScramError> {
+        let mut nonce = [0; NONCE_LEN];
+        rand::rand_bytes(&mut nonce)?;
+        self.handle_client_first_with_nonce(mechanism, message, &base64::encode_block(&nonce))
+    }
+
+    fn handle_client_first_with_nonce(
+        self,
+        mechanism: &str,
+        message: &[u8],
+        server_nonce: &str,
+    ) -> Result<ScramServerFirst, ScramError> {
+        let message = std::str::from_utf8(message)
+            .map_err(|_| ScramError::Malformed("client-first-message is not valid UTF-8"))?;
+
+        // gs2-header = gs2-cbind-flag "," [ authzid ] ","
+        let (cbind_flag, rest) = message
+            .split_once(',')
+            .ok_or(ScramError::Malformed("missing channel binding flag"))?;
+        let (authzid, client_first_bare) = rest
+            .split_once(',')
+            .ok_or(ScramError::Malformed("missing authorization identity"))?;
+        if !authzid.is_empty() {
+            return Err(ScramError::Unsupported("authorization identities"));
+        }
+        let gs2_header = &message[..message.len() - client_first_bare.len()];
+
+        let mut channel_binding = gs2_header.as_bytes().to_vec();
+        match (mechanism, cbind_flag) {
+            (SCRAM_SHA_256_PLUS, flag) => {
+                let Some(data) = &self.channel_binding else {
+                    return Err(ScramError::Unsupported(
+                        "channel binding on this connection",
+                    ));
+                };
+                match flag.strip_prefix("p=") {
+                    Some(CHANNEL_BINDING_TYPE) => channel_binding.extend(data),
+                    Some(_) => return Err(ScramError::Unsupported("channel binding type")),
+                    None => {
+                        return Err(ScramError::Malformed(
+                            "SCRAM-SHA-256-PLUS selected without channel binding",
+                        ))
+                    }
+                }
+            }
+            (SCRAM_SHA_256, "n") => (),
+            (SCRAM_SHA_256, "y") => {
+                // The client supports channel binding but thinks the server
+                // doesn't. If the server does, something in between removed
+                // SCRAM-SHA-256-PLUS from the offered mechanisms.
+                if self.channel_binding.is_some() {
+                    return Err(ScramError::ChannelBindingMismatch);
+                }
+            }
+            (SCRAM_SHA_256, _) => {
+                return Err(ScramError::Malformed(
+                    "channel binding requested without SCRAM-SHA-256-PLUS",
+                ))
+            }
+            _ => return Err(ScramError::Unsupported("mechanism")),
+        }
+
+        // client-first-message-bare = [reserved-mext ","] username "," nonce ["," extensions]
+        //
+        // Like PostgreSQL, the username is ignored in favor of the user in the
+        // startup message.
+        let mut attributes = client_first_bare.split(',');
+        match attributes.next() {
+            Some(attr) if attr.starts_with("m=") => {
+                return Err(ScramError::Unsupported("mandatory extensions"))
+            }
+            Some(attr) if attr.starts_with("n=") => (),
+            _ => return Err(ScramError::Malformed("missing username")),
+        }
+        let client_nonce = attributes
+            .next()
+            .and_then(|attr| attr.strip_prefix("r="))
+            .ok_or(ScramError::Malformed("missing nonce"))?;
+        if client_nonce.is_empty()
+            || !client_nonce
+                .bytes()
+                .all(|b| b.is_ascii_graphic() && b != b',')
+        {
+            return Err(ScramError::Malformed("invalid nonce"));
+        }
+
+        let nonce = format!("{client_nonce}{server_nonce}");
+        let server_first = format!(
+            "r={nonce},s={},i={}",
+            base64::encode_block(&self.verifier.salt),
+            self.verifier.iterations,
+        );
+        Ok(ScramServerFirst {
+            verifier: self.verifier,
+            channel_binding,
+            nonce,
+            client_first_bare: client_first_bare.into(),
+            server_first,
+        })
+    }
+}
+
+/// The server's side of a SCRAM-SHA-256 exchange, after the client's first
+/// message.
+#[derive(Debug)]
+pub struct ScramServerFirst {
+    verifier: ScramVerifier,
+    /// The channel binding the client must send: its GS2 header, followed by
+    /// the binding data if it selected [`SCRAM_SHA_256_PLUS`].
+    channel_binding: Vec<u8>,
+    nonce: String,
+    client_first_bare: String,
+    server_first: String,
+}
+
+impl ScramServerFirst {
+    /// Returns the message to send in response to the client's first message.
+    pub fn message(&self) -> &[u8] {
+        self.server_first.as_bytes()
+    }
+
+    /// Handles the client's final message. If the client proves knowledge of
+    /// the password, returns the final message to send to the client, which
+    /// proves knowledge of the verifier in turn.
+    pub fn handle_client_final(self, message: &[u8]) -> Result<Vec<u8>, ScramError> {
+        let message = std::str::from_utf8(message)
+            .map_err(|_| ScramError::Malformed("client-final-message is not valid UTF-8"))?;
+
+        // client-final-message = channel-binding "," nonce ["," extensions] "," proof
+        let (without_proof, proof) = message
+            .rsplit_once(",p=")
+            .ok_or(ScramError::Malformed("missing proof"))?;
+        let mut attributes = without_proof.split(',');
+        let channel_binding = attributes
+            .next()
+            .and_then(|attr| attr.strip_prefix("c="))
+            .and_then(|c| base64::decode_block(c).ok())
+            .ok_or(ScramError::Malformed("missing channel binding"))?;
+        if channel_binding != self.channel_binding {
+            return Err(ScramError::ChannelBindingMismatch);
+        }
+        let nonce = attributes
+            .next()
+            .and_then(|attr| attr.strip_prefix("r="))
+            .ok_or(ScramError::Malformed("missing nonce"))?;
+        if nonce != self.nonce {
+            return Err(ScramError::Malformed("nonce does not match"));
+        }
+        let proof: [u8; KEY_LEN] = base64::decode_block(proof)
+            .ok()
+            .and_then(|proof| proof.try_into().ok())
+            .ok_or(ScramError::Malformed("invalid proof"))?;
+
+        let auth_message = format!(
+            "{},{},{without_proof}",
+            self.client_first_bare, self.server_first
+        );
+        let client_signature = hmac(&self.verifier.stored_key, auth_message.as_bytes())?;
+        let mut client_key = proof;
+        for (k, s) in client_key.iter_mut().zip(client_signature) {
+            *k ^= s;
+        }
+        if !memcmp::eq(&sha::sha256(&client_key), &self.verifier.stored_key) {
+            return Err(ScramError::InvalidProof);
+        }
+
+        let server_signature = hmac(&self.verifier.server_key, auth_message.as_bytes())?;
+        Ok(format!("v={}", base64::encode_block(&server_signature)).into_bytes())
+    }
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Result<[u8; KEY_LEN], ErrorStack> {
+    let key = PKey::hmac(key)?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    signer.update(data)?;
+    let mut mac = [0; KEY_LEN];
+    signer.sign(&mut mac)?;
+    Ok(mac)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example exchange from section 3 of RFC 7677.
+    const SALT: &str = "W22ZaJ0SNY7soEsUEjb6gQ==";
+    const CLIENT_FIRST: &str = "n,,n=user,r=rOprNGfwEbeRWgbNEkqO";
+    const SERVER_NONCE: &str = "%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0";
+    const SERVER_FIRST: &str =
+        "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+    const CLIENT_FINAL: &str = "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=";
+    const SERVER_FINAL: &str = "v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=";
+
+    fn verifier(password: &str) -> ScramVerifier {
+        ScramVerifier::with_salt(password, base64::decode_block(SALT).unwrap(), 4096).unwrap()
+    }
+
+    fn server_first(
+        server: ScramServer,
+        mechanism: &str,
+        message: &str,
+    ) -> Result<ScramServerFirst, ScramError> {
+        server.handle_client_first_with_nonce(mechanism, message.as_bytes(), SERVER_NONCE)
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function
+    fn test_rfc_7677_exchange() {
+        let server = ScramServer::new(verifier("pencil"), None);
+        assert_eq!(server.mechanisms(), &[SCRAM_SHA_256]);
+        let first = server_first(server, SCRAM_SHA_256, CLIENT_FIRST).unwrap();
+        assert_eq!(first.message(), SERVER_FIRST.as_bytes());
+        let last = first.handle_client_final(CLIENT_FINAL.as_bytes()).unwrap();
+        assert_eq!(last, SERVER_FINAL.as_bytes());
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function
+    fn test_wrong_password() {
+        let server = ScramServer::new(verifier("pen"), None);
+        let first = server_first(server, SCRAM_SHA_256, CLIENT_FIRST).unwrap();
+        assert!(matches!(
+            first.handle_client_final(CLIENT_FINAL.as_bytes()),
+            Err(ScramError::InvalidProof)
+        ));
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function
+    fn test_channel_binding() {
+        let binding = b"certificate hash".to_vec();
+        let server = ScramServer::new(verifier("pencil"), Some(binding.clone()));
+        assert_eq!(server.mechanisms(), &[SCRAM_SHA_256_PLUS, SCRAM_SHA_256]);
+
+        // A client that supports channel binding must use it if the server
+        // offers it.
+        let server = ScramServer::new(verifier("pencil"), Some(binding.clone()));
+        assert!(matches!(
+            server_first(server, SCRAM_SHA_256, "y,,n=,r=abc"),
+            Err(ScramError::ChannelBindingMismatch)
+        ));
+
+        // The client must bind to the data of this connection.
+        let header = "p=tls-server-end-point,,";
+        let server = ScramServer::new(verifier("pencil"), Some(binding.clone()));
+        let first = server_first(server, SCRAM_SHA_256_PLUS, &format!("{header}n=,r=abc")).unwrap();
+        let wrong = base64::encode_block(format!("{header}other hash").as_bytes());
+        assert!(matches!(
+            first.handle_client_final(
+                format!(
+                    "c={wrong},r=abc{SERVER_NONCE},p={}",
+                    base64::encode_block(&[0; 32])
+                )
+                .as_bytes()
+            ),
+            Err(ScramError::ChannelBindingMismatch)
+        ));
+
+        // Unencrypted connections can't bind to a channel.
+        let server = ScramServer::new(verifier("pencil"), None);
+        assert!(matches!(
+            server_first(server, SCRAM_SHA_256_PLUS, &format!("{header}n=,r=abc")),
+            Err(ScramError::Unsupported(_))
+        ));
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function
+    fn test_verifier_round_trip() {
+        let verifier = ScramVerifier::new("pencil").unwrap();
+        let s = verifier.to_string();
+        assert!(s.starts_with("SCRAM-SHA-256$4096:"));
+        assert_eq!(ScramVerifier::parse(&s), Some(verifier));
+        assert_eq!(ScramVerifier::parse("md5abc"), None);
+    }
+}
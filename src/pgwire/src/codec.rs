@@ -24,7 +24,7 @@ use mz_ore::future::OreSinkExt;
 use mz_ore::netio::AsyncReady;
 use mz_pgwire_common::{
     input_err, parse_frame_len, Conn, Cursor, DecodeState, ErrorResponse, FrontendMessage, Pgbuf,
-    MAX_REQUEST_SIZE,
+    MAX_REQUEST_SIZE, STRUCTURED_FIELD_TYPE,
 };
 use tokio::io::{self, AsyncRead, AsyncWrite, Interest, Ready};
 use tokio::time::{self, Duration};
@@ -223,6 +223,9 @@ impl Encoder<BackendMessage> for Codec {
         let byte = match &msg {
             BackendMessage::AuthenticationOk => b'R',
             BackendMessage::AuthenticationCleartextPassword => b'R',
+            BackendMessage::AuthenticationSasl { .. } => b'R',
+            BackendMessage::AuthenticationSaslContinue(_) => b'R',
+            BackendMessage::AuthenticationSaslFinal(_) => b'R',
             BackendMessage::RowDescription(_) => b'T',
             BackendMessage::DataRow(_) => b'D',
             BackendMessage::CommandComplete { .. } => b'C',
@@ -280,6 +283,21 @@ impl Encoder<BackendMessage> for Codec {
             BackendMessage::AuthenticationCleartextPassword => {
                 dst.put_u32(3);
             }
+            BackendMessage::AuthenticationSasl { mechanisms } => {
+                dst.put_u32(10);
+                for mechanism in mechanisms {
+                    dst.put_string(mechanism);
+                }
+                dst.put_u8(b'\0');
+            }
+            BackendMessage::AuthenticationSaslContinue(data) => {
+                dst.put_u32(11);
+                dst.put_slice(&data);
+            }
+            BackendMessage::AuthenticationSaslFinal(data) => {
+                dst.put_u32(12);
+                dst.put_slice(&data);
+            }
             BackendMessage::RowDescription(fields) => {
                 dst.put_length_i16(fields.len())?;
                 for f in &fields {
@@ -349,6 +367,7 @@ impl Encoder<BackendMessage> for Codec {
                 detail,
                 hint,
                 position,
+                structured,
             }) => {
                 dst.put_u8(b'S');
                 dst.put_string(severity.as_str());
@@ -368,6 +387,10 @@ impl Encoder<BackendMessage> for Codec {
                     dst.put_u8(b'P');
                     dst.put_string(&position.to_string());
                 }
+                if let Some(structured) = &structured {
+                    dst.put_u8(STRUCTURED_FIELD_TYPE);
+                    dst.put_string(structured);
+                }
                 dst.put_u8(b'\0');
             }
         }
@@ -437,7 +460,7 @@ impl Decoder for Codec {
                         b'X' => decode_terminate(buf)?,
 
                         // Authentication.
-                        b'p' => decode_password(buf)?,
+                        b'p' => decode_authentication_response(buf, frame_len)?,
 
                         // Copy from flow.
                         b'f' => decode_copy_fail(buf)?,
@@ -466,10 +489,35 @@ fn decode_terminate(mut _buf: Cursor) -> Result<FrontendMessage, io::Error> {
     Ok(FrontendMessage::Terminate)
 }
 
-fn decode_password(mut buf: Cursor) -> Result<FrontendMessage, io::Error> {
-    Ok(FrontendMessage::Password {
-        password: buf.read_cstr()?.to_owned(),
-    })
+fn decode_authentication_response(
+    mut buf: Cursor,
+    frame_len: usize,
+) -> Result<FrontendMessage, io::Error> {
+    let mut data = Vec::with_capacity(frame_len);
+    for _ in 0..frame_len {
+        data.push(buf.read_byte()?);
+    }
+    Ok(FrontendMessage::AuthenticationResponse(data))
+}
+
+/// Decodes the contents of an authentication response to a request for a
+/// cleartext password.
+pub fn decode_password(data: &[u8]) -> Result<String, io::Error> {
+    Ok(Cursor::new(data).read_cstr()?.to_owned())
+}
+
+/// Decodes the contents of the first authentication response to a request for
+/// SASL authentication into the selected mechanism and its initial response.
+pub fn decode_sasl_initial_response(data: &[u8]) -> Result<(String, Vec<u8>), io::Error> {
+    let mut buf = Cursor::new(data);
+    let mechanism = buf.read_cstr()?.to_owned();
+    // A length of -1 indicates that there is no initial response.
+    let len = buf.read_i32()?;
+    let mut response = Vec::new();
+    for _ in 0..len {
+        response.push(buf.read_byte()?);
+    }
+    Ok((mechanism, response))
 }
 
 fn decode_query(mut buf: Cursor) -> Result<FrontendMessage, io::Error> {
@@ -10,6 +10,7 @@
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::future::Future;
+use std::net::IpAddr;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use std::{iter, mem};
@@ -32,6 +33,7 @@ use mz_ore::instrument;
 use mz_ore::netio::AsyncReady;
 use mz_ore::str::StrExt;
 use mz_pgcopy::{CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams};
+use mz_pgwire_common::scram::{ScramError, ScramServer, ScramVerifier};
 use mz_pgwire_common::{ErrorResponse, Format, FrontendMessage, Severity, VERSIONS, VERSION_3};
 use mz_repr::{
     Datum, GlobalId, RelationDesc, RelationType, RowArena, RowIterator, RowRef, ScalarType,
@@ -52,7 +54,7 @@ use tokio::time::{self};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, debug_span, warn, Instrument};
 
-use crate::codec::FramedConn;
+use crate::codec::{self, FramedConn};
 use crate::message::{self, BackendMessage};
 
 /// Reports whether the given stream begins with a pgwire handshake.
@@ -94,6 +96,8 @@ pub struct RunParams<'a, A> {
     pub internal: bool,
     /// Global connection limit and count
     pub active_connection_count: Arc<Mutex<ConnectionCounter>>,
+    /// The IP address of the client, if known.
+    pub client_ip: Option<IpAddr>,
 }
 
 /// Runs a pgwire connection to completion.
@@ -116,6 +120,7 @@ pub async fn run<'a, A>(
         frontegg,
         internal,
         active_connection_count,
+        client_ip,
     }: RunParams<'a, A>,
 ) -> Result<(), io::Error>
 where
@@ -154,12 +159,55 @@ where
         return conn.send(err).await;
     }
 
-    let (mut session, expired) = if let Some(frontegg) = frontegg {
+    let (mut session, expired) = if let Some(identity) = conn.inner().client_cert_identity() {
+        // The certificate was verified during the TLS handshake, so it
+        // authenticates the client in place of a password. The role it maps to
+        // must be the requested user, if the client requested one.
+        let role = match adapter_client
+            .map_client_certificate(identity.common_name, identity.subject_alt_names)
+            .await
+        {
+            Ok(role) if user.is_empty() || role == user => role,
+            Ok(role) => {
+                warn!(%role, %user, "pgwire client certificate maps to a different role");
+                let msg = format!(
+                    "certificate authentication failed for user {}",
+                    user.quoted()
+                );
+                return conn
+                    .send(ErrorResponse::fatal(
+                        SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
+                        msg,
+                    ))
+                    .await;
+            }
+            Err(err) => {
+                warn!(?err, "pgwire connection failed certificate authentication");
+                return conn.send(err.into_response(Severity::Fatal)).await;
+            }
+        };
+        if !internal && mz_adapter::catalog::is_reserved_role_name(role.as_str()) {
+            let msg = format!("unauthorized login to user '{role}'");
+            return conn
+                .send(ErrorResponse::fatal(SqlState::INSUFFICIENT_PRIVILEGE, msg))
+                .await;
+        }
+        let session = adapter_client.new_session(SessionConfig {
+            conn_id: conn.conn_id().clone(),
+            user: role,
+            external_metadata_rx: None,
+            client_ip,
+        });
+        // Certificates are only checked at connection time, so the auth session
+        // lasts indefinitely.
+        let auth_session = pending().right_future();
+        (session, auth_session)
+    } else if let Some(frontegg) = frontegg {
         conn.send(BackendMessage::AuthenticationCleartextPassword)
             .await?;
         conn.flush().await?;
         let password = match conn.recv().await? {
-            Some(FrontendMessage::Password { password }) => password,
+            Some(FrontendMessage::AuthenticationResponse(data)) => codec::decode_password(&data)?,
             _ => {
                 return conn
                     .send(ErrorResponse::fatal(
@@ -184,6 +232,7 @@ where
                     conn_id: conn.conn_id().clone(),
                     user: auth_session.user().into(),
                     external_metadata_rx: Some(auth_session.external_metadata_rx()),
+                    client_ip,
                 });
                 let expired = async move { auth_session.expired().await };
                 (session, expired.left_future())
@@ -198,11 +247,27 @@ where
                     .await;
             }
         }
+    } else if let Some(verifier) = adapter_client.scram_verifier(user.clone()).await {
+        // The role has a password, so the client must prove that it knows it.
+        if let Err(err) = authenticate_scram(conn, &user, &verifier).await? {
+            return conn.send(err).await;
+        }
+        let session = adapter_client.new_session(SessionConfig {
+            conn_id: conn.conn_id().clone(),
+            user,
+            external_metadata_rx: None,
+            client_ip,
+        });
+        // Passwords are only checked at connection time, so the auth session
+        // lasts indefinitely.
+        let auth_session = pending().right_future();
+        (session, auth_session)
     } else {
         let session = adapter_client.new_session(SessionConfig {
             conn_id: conn.conn_id().clone(),
             user,
             external_metadata_rx: None,
+            client_ip,
         });
         // No frontegg check, so auth session lasts indefinitely.
         let auth_session = pending().right_future();
@@ -266,12 +331,13 @@ where
         conn_id: adapter_client.session().conn_id().unhandled(),
         secret_key: adapter_client.session().secret_key(),
     });
+    let structured = adapter_client.session().vars().emit_structured_notices();
     buf.extend(
         adapter_client
             .session()
             .drain_notices()
             .into_iter()
-            .map(|notice| BackendMessage::ErrorResponse(notice.into_response())),
+            .map(|notice| BackendMessage::ErrorResponse(notice_response(notice, structured))),
     );
     buf.push(BackendMessage::ReadyForQuery(
         adapter_client.session().transaction().into(),
@@ -315,6 +381,83 @@ where
 ///
 /// From Postgres, see pg_split_opts in postinit.c and process_postgres_switches
 /// in postgres.c.
+/// Authenticates the client with SCRAM-SHA-256, given the SCRAM `verifier` of
+/// the password of `user`.
+///
+/// Returns the error to send to the client if authentication fails.
+async fn authenticate_scram<A>(
+    conn: &mut FramedConn<A>,
+    user: &str,
+    verifier: &str,
+) -> Result<Result<(), ErrorResponse>, io::Error>
+where
+    A: AsyncRead + AsyncWrite + Unpin,
+{
+    let Some(verifier) = ScramVerifier::parse(verifier) else {
+        warn!(%user, "role has an invalid SCRAM verifier");
+        return Ok(Err(password_authentication_failed(user)));
+    };
+    let server = ScramServer::new(verifier, conn.inner().tls_server_end_point());
+    conn.send(BackendMessage::AuthenticationSasl {
+        mechanisms: server.mechanisms(),
+    })
+    .await?;
+    conn.flush().await?;
+
+    let (mechanism, client_first) = match conn.recv().await? {
+        Some(FrontendMessage::AuthenticationResponse(data)) => {
+            codec::decode_sasl_initial_response(&data)?
+        }
+        _ => return Ok(Err(expected_sasl_response())),
+    };
+    let server_first = match server.handle_client_first(&mechanism, &client_first) {
+        Ok(server_first) => server_first,
+        Err(err) => return Ok(Err(scram_error_response(user, err))),
+    };
+    conn.send(BackendMessage::AuthenticationSaslContinue(
+        server_first.message().to_vec(),
+    ))
+    .await?;
+    conn.flush().await?;
+
+    let client_final = match conn.recv().await? {
+        Some(FrontendMessage::AuthenticationResponse(data)) => data,
+        _ => return Ok(Err(expected_sasl_response())),
+    };
+    match server_first.handle_client_final(&client_final) {
+        Ok(server_final) => {
+            // The caller flushes this along with `AuthenticationOk`.
+            conn.send(BackendMessage::AuthenticationSaslFinal(server_final))
+                .await?;
+            Ok(Ok(()))
+        }
+        Err(err) => Ok(Err(scram_error_response(user, err))),
+    }
+}
+
+fn expected_sasl_response() -> ErrorResponse {
+    ErrorResponse::fatal(SqlState::PROTOCOL_VIOLATION, "expected SASL response")
+}
+
+fn password_authentication_failed(user: &str) -> ErrorResponse {
+    ErrorResponse::fatal(
+        SqlState::INVALID_PASSWORD,
+        format!("password authentication failed for user {}", user.quoted()),
+    )
+}
+
+fn scram_error_response(user: &str, err: ScramError) -> ErrorResponse {
+    warn!(%err, "pgwire connection failed SCRAM authentication");
+    match err {
+        ScramError::Malformed(_) | ScramError::Unsupported(_) => {
+            ErrorResponse::fatal(SqlState::PROTOCOL_VIOLATION, err.to_string())
+        }
+        ScramError::ChannelBindingMismatch | ScramError::InvalidProof | ScramError::Ssl(_) => {
+            password_authentication_failed(user)
+        }
+    }
+}
+
 fn parse_options(value: &str) -> Result<Vec<(String, String)>, ()> {
     let opts = split_options(value);
     let mut pairs = Vec::with_capacity(opts.len());
@@ -411,6 +554,10 @@ enum SendRowsEndedReason {
     Canceled,
 }
 
+/// The number of bytes of `COPY FROM` data to accumulate before decoding the
+/// complete records among them and adding them to the transaction.
+const COPY_FROM_BATCH_SIZE: usize = 8 << 20;
+
 const ABORTED_TXN_MSG: &str =
     "current transaction is aborted, commands ignored until end of transaction block";
 
@@ -557,7 +704,8 @@ where
             Some(FrontendMessage::CopyData(_))
             | Some(FrontendMessage::CopyDone)
             | Some(FrontendMessage::CopyFail(_))
-            | Some(FrontendMessage::Password { .. }) => State::Drain,
+            | Some(FrontendMessage::Password { .. })
+            | Some(FrontendMessage::AuthenticationResponse(_)) => State::Drain,
             None => State::Done,
         };
 
@@ -1097,6 +1245,29 @@ where
                     }
                     result
                 }
+                // A FETCH that was suspended by an earlier Execute. Continue sending rows from
+                // its cursor, but no more than the FETCH has left.
+                PortalState::SuspendedFetch {
+                    cursor, remaining, ..
+                } => {
+                    let cursor = cursor.clone();
+                    let count = match (max_rows, *remaining) {
+                        (ExecuteCount::Count(max_rows), Some(remaining)) => {
+                            ExecuteCount::Count(max_rows.min(remaining))
+                        }
+                        (ExecuteCount::All, Some(remaining)) => ExecuteCount::Count(remaining),
+                        (max_rows, None) => max_rows,
+                    };
+                    self.execute(
+                        cursor,
+                        count,
+                        fetch_message,
+                        Some(portal_name),
+                        timeout,
+                        outer_ctx_extra,
+                    )
+                    .await
+                }
                 // FETCH is an awkward command for our current architecture. In Postgres it
                 // will extract <count> rows from the target portal, cache them, and return
                 // them to the user as requested. Its command tag is always FETCH <num rows
@@ -1233,49 +1404,49 @@ where
         // Figure out how many rows we should send back by looking at the various
         // combinations of the execute and fetch.
         //
-        // In Postgres, Fetch will cache <count> rows from the target portal and
-        // return those as requested (if, say, an Execute message was sent with a
-        // max_rows < the Fetch's count). We expect that case to be incredibly rare and
-        // so have chosen to not support it until users request it. This eases
-        // implementation difficulty since we don't have to be able to "send" rows to
-        // a buffer.
+        // If an Execute message was sent with a max_rows < the Fetch's count, only
+        // max_rows are sent and the Fetch's portal is suspended. Executing it again
+        // continues to send rows until the Fetch's count is exhausted.
         //
-        // TODO(mjibson): Test this somehow? Need to divide up the pgtest files in
-        // order to have some that are not Postgres compatible.
-        let count = match (max_rows, count) {
+        // In Postgres, Fetch will instead cache <count> rows from the target portal on
+        // its first execution and return those as requested. We send the remaining
+        // rows straight from the target portal, so they differ only if the target
+        // portal is also fetched from while the Fetch is suspended. We expect that case
+        // to be incredibly rare, and not caching saves us from having to buffer up to
+        // <count> rows.
+        let suspended_fetch = |remaining| PortalState::SuspendedFetch {
+            cursor: name.clone(),
+            remaining,
+            sent: 0,
+        };
+        let (count, suspended_fetch) = match (max_rows, count) {
             (ExecuteCount::Count(max_rows), FetchDirection::ForwardCount(count)) => {
                 let count = usize::cast_from(count);
                 if max_rows < count {
-                    let msg = "Execute with max_rows < a FETCH's count is not supported";
-                    self.adapter_client.retire_execute(
-                        ctx_extra,
-                        StatementEndedExecutionReason::Errored {
-                            error: msg.to_string(),
-                        },
-                    );
-                    return self
-                        .error(ErrorResponse::error(SqlState::FEATURE_NOT_SUPPORTED, msg))
-                        .await;
+                    (
+                        ExecuteCount::Count(max_rows),
+                        Some(suspended_fetch(Some(count))),
+                    )
+                } else {
+                    (ExecuteCount::Count(count), None)
                 }
-                ExecuteCount::Count(count)
             }
-            (ExecuteCount::Count(_), FetchDirection::ForwardAll) => {
-                let msg = "Execute with max_rows of a FETCH ALL is not supported";
-                self.adapter_client.retire_execute(
-                    ctx_extra,
-                    StatementEndedExecutionReason::Errored {
-                        error: msg.to_string(),
-                    },
-                );
-                return self
-                    .error(ErrorResponse::error(SqlState::FEATURE_NOT_SUPPORTED, msg))
-                    .await;
+            (ExecuteCount::Count(max_rows), FetchDirection::ForwardAll) => {
+                (ExecuteCount::Count(max_rows), Some(suspended_fetch(None)))
             }
-            (ExecuteCount::All, FetchDirection::ForwardAll) => ExecuteCount::All,
+            (ExecuteCount::All, FetchDirection::ForwardAll) => (ExecuteCount::All, None),
             (ExecuteCount::All, FetchDirection::ForwardCount(count)) => {
-                ExecuteCount::Count(usize::cast_from(count))
+                (ExecuteCount::Count(usize::cast_from(count)), None)
             }
         };
+        if let (Some(state), Some(fetch_portal_name)) = (suspended_fetch, &fetch_portal_name) {
+            let fetch_portal = self
+                .adapter_client
+                .session()
+                .get_portal_unverified_mut(fetch_portal_name)
+                .expect("valid fetch portal");
+            fetch_portal.state = state;
+        }
         let cursor_name = name.to_string();
         self.execute(
             cursor_name,
@@ -1356,7 +1527,12 @@ where
                         return Ok(rx);
                     }
                     notice = self.adapter_client.session().recv_notice() => {
-                        self.send(notice.into_response())
+                        let structured = self
+                            .adapter_client
+                            .session()
+                            .vars()
+                            .emit_structured_notices();
+                        self.send(notice_response(notice, structured))
                             .await?;
                         self.conn.flush().await?;
                     }
@@ -1480,6 +1656,35 @@ where
                 .await
                 .map(|(state, _)| state)
             }
+            ExecuteResponse::SendingRowsStreaming {
+                rows,
+                instance_id,
+                strategy,
+            } => {
+                let row_desc = row_desc
+                    .expect("missing row description for ExecuteResponse::SendingRowsStreaming");
+
+                let span = tracing::debug_span!("sending_rows_streaming");
+
+                self.send_rows(
+                    row_desc,
+                    portal_name,
+                    InProgressRows::new(RecordFirstRowStream::new(
+                        Box::new(rows),
+                        execute_started,
+                        &self.adapter_client,
+                        Some(instance_id),
+                        Some(strategy),
+                    )),
+                    max_rows,
+                    get_response,
+                    fetch_portal_name,
+                    timeout,
+                )
+                .instrument(span)
+                .await
+                .map(|(state, _)| state)
+            }
             ExecuteResponse::SetVariable { name, .. } => {
                 // This code is somewhat awkwardly structured because we
                 // can't hold `var` across an await point.
@@ -1682,7 +1887,9 @@ where
                     .await
             }
             ExecuteResponse::TransactionCommitted { params }
-            | ExecuteResponse::TransactionRolledBack { params } => {
+            | ExecuteResponse::TransactionRolledBack { params }
+            | ExecuteResponse::DiscardedAll { params }
+            | ExecuteResponse::ResetAllVariables { params } => {
                 let notify_set: mz_ore::collections::HashSet<String> = self
                     .adapter_client
                     .session()
@@ -1724,7 +1931,6 @@ where
             | ExecuteResponse::Comment
             | ExecuteResponse::Deallocate { .. }
             | ExecuteResponse::Deleted(..)
-            | ExecuteResponse::DiscardedAll
             | ExecuteResponse::DiscardedTemp
             | ExecuteResponse::DroppedObject(_)
             | ExecuteResponse::DroppedOwned
@@ -1738,8 +1944,7 @@ where
             | ExecuteResponse::RevokedPrivilege
             | ExecuteResponse::RevokedRole
             | ExecuteResponse::StartedTransaction { .. }
-            | ExecuteResponse::Updated(..)
-            | ExecuteResponse::ValidatedConnection => {
+            | ExecuteResponse::Updated(..) => {
                 command_complete!()
             }
         };
@@ -1872,7 +2077,12 @@ where
                     self.conn.flush().await?;
                 }
                 FetchResult::Notice(notice) => {
-                    self.send(notice.into_response()).await?;
+                    let structured = self
+                        .adapter_client
+                        .session()
+                        .vars()
+                        .emit_structured_notices();
+                    self.send(notice_response(notice, structured)).await?;
                     self.conn.flush().await?;
                 }
                 FetchResult::Error(text) => {
@@ -1967,7 +2177,7 @@ where
 
         if let CopyFormat::Binary = format {
             // 11-byte signature.
-            out.extend(b"PGCOPY\n\xFF\r\n\0");
+            out.extend(mz_pgcopy::BINARY_SIGNATURE);
             // 32-bit flags field.
             out.extend([0, 0, 0, 0]);
             // 32-bit header extension length field.
@@ -2003,7 +2213,8 @@ where
                     }
                 },
                 notice = self.adapter_client.session().recv_notice() => {
-                    self.send(notice.into_response())
+                    let structured = self.adapter_client.session().vars().emit_structured_notices();
+                    self.send(notice_response(notice, structured))
                         .await?;
                     self.conn.flush().await?;
                 }
@@ -2073,14 +2284,18 @@ where
         &mut self,
         id: GlobalId,
         columns: Vec<usize>,
-        params: CopyFormatParams<'_>,
+        mut params: CopyFormatParams<'_>,
         row_desc: RelationDesc,
         ctx_extra: &mut ExecuteContextExtra,
     ) -> Result<State, io::Error> {
         let typ = row_desc.typ();
-        let column_formats = vec![Format::Text; typ.column_types.len()];
+        let format = match params {
+            CopyFormatParams::Binary => Format::Binary,
+            CopyFormatParams::Text(_) | CopyFormatParams::Csv(_) => Format::Text,
+        };
+        let column_formats = vec![format; typ.column_types.len()];
         self.send(BackendMessage::CopyInResponse {
-            overall_format: Format::Text,
+            overall_format: format,
             column_formats,
         })
         .await?;
@@ -2096,13 +2311,23 @@ where
             .unwrap_or(usize::MAX);
         tracing::debug!("COPY FROM max buffer size: {max_size} bytes");
 
+        let column_types = typ
+            .column_types
+            .iter()
+            .map(|x| &x.scalar_type)
+            .map(mz_pgrepr::Type::from)
+            .collect::<Vec<mz_pgrepr::Type>>();
+
         let mut data = Vec::new();
+        let mut size = 0;
+        let mut count = 0;
         loop {
             let message = self.conn.recv().await?;
             match message {
                 Some(FrontendMessage::CopyData(buf)) => {
                     // Bail before we OOM.
-                    if (data.len() + buf.len()) > max_size {
+                    size += buf.len();
+                    if size > max_size {
                         return self
                             .error(ErrorResponse::error(
                                 SqlState::INSUFFICIENT_RESOURCES,
@@ -2110,7 +2335,25 @@ where
                             ))
                             .await;
                     }
-                    data.extend(buf)
+                    data.extend(buf);
+
+                    // Rather than buffering the entire payload, decode the
+                    // complete records received so far once there are enough
+                    // of them. We don't read more data until the batch has
+                    // been staged, which applies backpressure to the client.
+                    if data.len() >= COPY_FROM_BATCH_SIZE {
+                        if let Some(boundary) = mz_pgcopy::copy_record_boundary(&data, &params) {
+                            let rest = data.split_off(boundary);
+                            let batch = mem::replace(&mut data, rest);
+                            match self
+                                .copy_from_batch(id, &columns, &column_types, &mut params, &batch)
+                                .await
+                            {
+                                Ok(batch_count) => count += batch_count,
+                                Err(err) => return self.copy_from_error(err, ctx_extra).await,
+                            }
+                        }
+                    }
                 }
                 Some(FrontendMessage::CopyDone) => break,
                 Some(FrontendMessage::CopyFail(err)) => {
@@ -2144,46 +2387,17 @@ where
             }
         }
 
-        let column_types = typ
-            .column_types
-            .iter()
-            .map(|x| &x.scalar_type)
-            .map(mz_pgrepr::Type::from)
-            .collect::<Vec<mz_pgrepr::Type>>();
-
-        let rows = match mz_pgcopy::decode_copy_format(&data, &column_types, params) {
-            Ok(rows) => rows,
-            Err(e) => {
-                self.adapter_client.retire_execute(
-                    std::mem::take(ctx_extra),
-                    StatementEndedExecutionReason::Errored {
-                        error: e.to_string(),
-                    },
-                );
-                return self
-                    .error(ErrorResponse::error(
-                        SqlState::BAD_COPY_FILE_FORMAT,
-                        format!("{}", e),
-                    ))
-                    .await;
-            }
-        };
-
-        let count = rows.len();
-
-        if let Err(e) = self
-            .adapter_client
-            .insert_rows(id, columns, rows, std::mem::take(ctx_extra))
+        match self
+            .copy_from_batch(id, &columns, &column_types, &mut params, &data)
             .await
         {
-            self.adapter_client.retire_execute(
-                std::mem::take(ctx_extra),
-                StatementEndedExecutionReason::Errored {
-                    error: e.to_string(),
-                },
-            );
-            return self.error(e.into_response(Severity::Error)).await;
+            Ok(batch_count) => count += batch_count,
+            Err(err) => return self.copy_from_error(err, ctx_extra).await,
         }
+        self.adapter_client.retire_execute(
+            std::mem::take(ctx_extra),
+            (&ExecuteResponse::Inserted(count)).into(),
+        );
 
         let tag = format!("COPY {}", count);
         self.send(BackendMessage::CommandComplete { tag }).await?;
@@ -2191,14 +2405,58 @@ where
         Ok(State::Ready)
     }
 
+    /// Decodes a batch of complete `COPY FROM` records and adds the resulting
+    /// rows to the current transaction, returning the number of rows.
+    async fn copy_from_batch(
+        &mut self,
+        id: GlobalId,
+        columns: &[usize],
+        column_types: &[mz_pgrepr::Type],
+        params: &mut CopyFormatParams<'_>,
+        data: &[u8],
+    ) -> Result<usize, ErrorResponse> {
+        let rows = mz_pgcopy::decode_copy_format(data, column_types, params.clone())
+            .map_err(|e| ErrorResponse::error(SqlState::BAD_COPY_FILE_FORMAT, e.to_string()))?;
+        // Only the first batch can contain the CSV header.
+        if let CopyFormatParams::Csv(params) = params {
+            params.header = false;
+        }
+
+        let count = rows.len();
+        self.adapter_client
+            .stage_rows(id, columns.to_vec(), rows)
+            .await
+            .map_err(|e| e.into_response(Severity::Error))?;
+        Ok(count)
+    }
+
+    async fn copy_from_error(
+        &mut self,
+        err: ErrorResponse,
+        ctx_extra: &mut ExecuteContextExtra,
+    ) -> Result<State, io::Error> {
+        self.adapter_client.retire_execute(
+            std::mem::take(ctx_extra),
+            StatementEndedExecutionReason::Errored {
+                error: err.message.clone(),
+            },
+        );
+        self.error(err).await
+    }
+
     #[instrument(level = "debug")]
     async fn send_pending_notices(&mut self) -> Result<(), io::Error> {
+        let structured = self
+            .adapter_client
+            .session()
+            .vars()
+            .emit_structured_notices();
         let notices = self
             .adapter_client
             .session()
             .drain_notices()
             .into_iter()
-            .map(|notice| BackendMessage::ErrorResponse(notice.into_response()));
+            .map(|notice| BackendMessage::ErrorResponse(notice_response(notice, structured)));
         self.send_all(notices).await?;
         Ok(())
     }
@@ -2256,6 +2514,16 @@ where
     }
 }
 
+/// Converts `notice` into a `NoticeResponse`, attaching its JSON description if
+/// the session asked for `structured` notices.
+fn notice_response(notice: AdapterNotice, structured: bool) -> ErrorResponse {
+    if structured {
+        notice.into_structured_response()
+    } else {
+        notice.into_response()
+    }
+}
+
 fn pad_formats(formats: Vec<Format>, n: usize) -> Result<Vec<Format>, String> {
     match (formats.len(), n) {
         (0, e) => Ok(vec![Format::Text; e]),
@@ -2308,14 +2576,38 @@ fn portal_exec_message(
 
 // A GetResponse used by send_rows during FETCH queries.
 fn fetch_message(
-    _max_rows: ExecuteCount,
+    max_rows: ExecuteCount,
     total_sent_rows: usize,
     fetch_portal: Option<&mut Portal>,
 ) -> BackendMessage {
-    let tag = format!("FETCH {}", total_sent_rows);
-    if let Some(portal) = fetch_portal {
-        portal.state = PortalState::Completed(Some(tag.clone()));
-    }
+    let Some(portal) = fetch_portal else {
+        return BackendMessage::CommandComplete {
+            tag: format!("FETCH {}", total_sent_rows),
+        };
+    };
+    let sent = match &mut portal.state {
+        PortalState::SuspendedFetch {
+            remaining, sent, ..
+        } => {
+            *sent += total_sent_rows;
+            if let Some(remaining) = remaining {
+                *remaining = remaining.saturating_sub(total_sent_rows);
+            }
+            // Like portal_exec_message, stay suspended if max_rows were sent, unless
+            // that exhausted the FETCH's count.
+            match max_rows {
+                ExecuteCount::Count(max_rows)
+                    if max_rows <= total_sent_rows && *remaining != Some(0) =>
+                {
+                    return BackendMessage::PortalSuspended;
+                }
+                _ => *sent,
+            }
+        }
+        _ => total_sent_rows,
+    };
+    let tag = format!("FETCH {}", sent);
+    portal.state = PortalState::Completed(Some(tag.clone()));
     BackendMessage::CommandComplete { tag }
 }
 
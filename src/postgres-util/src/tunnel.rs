@@ -55,6 +55,9 @@ pub enum TunnelConfig {
     AwsPrivatelink {
         /// The ID of the AWS PrivateLink service.
         connection_id: GlobalId,
+        /// The availability zone of the AWS PrivateLink service endpoint to
+        /// connect to, if restricted to a single zone.
+        availability_zone: Option<String>,
     },
 }
 
@@ -240,14 +243,20 @@ impl Config {
                 });
                 Ok(client)
             }
-            TunnelConfig::AwsPrivatelink { connection_id } => {
+            TunnelConfig::AwsPrivatelink {
+                connection_id,
+                availability_zone,
+            } => {
                 // This section of code is somewhat subtle. We are overriding the host
                 // for the actual TCP connection to be the PrivateLink host, but leaving the host
                 // for TLS verification as the original host. Managing the
                 // `tokio_postgres::Config` to do this is somewhat confusing, and requires we edit
                 // the singular host in place.
 
-                let privatelink_host = mz_cloud_resources::vpc_endpoint_name(*connection_id);
+                let privatelink_host = mz_cloud_resources::vpc_endpoint_host(
+                    *connection_id,
+                    availability_zone.as_deref(),
+                );
                 // `net::lookup_host` requires a port to be specified, but the port has no effect
                 // on the lookup so use a dummy one
                 let privatelink_addrs = tokio::net::lookup_host((privatelink_host, 11111)).await?;
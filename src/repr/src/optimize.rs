@@ -16,7 +16,7 @@ use serde::{Deserialize, Serialize};
 /// A macro for feature flags managed by the optimizer.
 macro_rules! optimizer_feature_flags {
     ({ $($feature:ident: $type:ty,)* }) => {
-        #[derive(Clone, Debug, Default)]
+        #[derive(Clone, Debug, Default, PartialEq, Eq)]
         pub struct OptimizerFeatures {
             $(pub $feature: $type),*
         }
@@ -104,6 +104,14 @@ optimizer_feature_flags!({
     enable_new_outer_join_lowering: bool,
     // Bound from `SystemVars::enable_reduce_mfp_fusion`.
     enable_reduce_mfp_fusion: bool,
+    // Bound from `SystemVars::enable_incremental_window_functions`.
+    enable_incremental_window_functions: bool,
+    // Bound from `SystemVars::enable_range_join_bucketing`.
+    enable_range_join_bucketing: bool,
+    // Bound from `SystemVars::range_join_bucket_width`.
+    range_join_bucket_width: usize,
+    // Bound from `SystemVars::range_join_max_buckets`.
+    range_join_max_buckets: usize,
     // Enable joint HIR ⇒ MIR lowering of stacks of left joins.
     enable_variadic_left_join_lowering: bool,
     // Enable cardinality estimation
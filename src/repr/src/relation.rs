@@ -547,6 +547,18 @@ impl RelationDesc {
         }
     }
 
+    /// Returns whether data written with the `old` schema can be read with
+    /// `self`.
+    ///
+    /// This is the case when `self` is `old` with zero or more nullable
+    /// columns appended. Rows written with `old` read back with nulls in the
+    /// appended columns.
+    pub fn is_backward_compatible_with(&self, old: &RelationDesc) -> bool {
+        self.arity() >= old.arity()
+            && self.iter().zip(old.iter()).all(|(new, old)| new == old)
+            && self.iter_types().skip(old.arity()).all(|typ| typ.nullable)
+    }
+
     /// Verifies that `d` meets all of the constraints for the `i`th column of `self`.
     ///
     /// n.b. The only constraint MZ currently supports in NOT NULL, but this
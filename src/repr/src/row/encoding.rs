@@ -829,6 +829,19 @@ impl Schema<Row> for RelationDesc {
         let ((), encoder) = self.encoder(part)?;
         Ok(encoder)
     }
+
+    fn backfill(&self, row: &mut Row) {
+        // Rows written before nullable columns were appended to the schema
+        // (see [RelationDesc::is_backward_compatible_with]) are missing them
+        // at the end, so fill them in with nulls.
+        let arity = row.iter().count();
+        if arity < self.arity() {
+            let mut packer = RowPacker::for_existing_row(row);
+            for _ in arity..self.arity() {
+                packer.push(Datum::Null);
+            }
+        }
+    }
 }
 
 impl<'a> From<Datum<'a>> for ProtoDatum {
@@ -1113,6 +1126,7 @@ impl RustType<ProtoRow> for Row {
 #[cfg(test)]
 mod tests {
     use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+    use mz_persist_types::columnar::Schema;
     use mz_persist_types::Codec;
     use proptest::prelude::*;
     use uuid::Uuid;
@@ -1305,4 +1319,42 @@ mod tests {
             scalar_type_columnar_roundtrip(scalar_type)
         });
     }
+
+    #[mz_ore::test]
+    fn backfill_added_columns() {
+        let old_desc = RelationDesc::empty()
+            .with_column("a", ScalarType::Int64.nullable(false))
+            .with_column("b", ScalarType::String.nullable(true));
+        let new_desc = old_desc
+            .clone()
+            .with_column("c", ScalarType::Bool.nullable(true));
+        assert!(new_desc.is_backward_compatible_with(&old_desc));
+        assert!(new_desc.is_backward_compatible_with(&new_desc));
+        // Columns can't be removed...
+        assert!(!old_desc.is_backward_compatible_with(&new_desc));
+        // ...and added columns must be nullable.
+        let non_null_desc = old_desc
+            .clone()
+            .with_column("c", ScalarType::Bool.nullable(false));
+        assert!(!non_null_desc.is_backward_compatible_with(&old_desc));
+
+        // Rows written with the old schema read back with nulls in the added
+        // columns.
+        let old_row = Row::pack_slice(&[Datum::Int64(1), Datum::String("b")]);
+        let mut encoded = Vec::new();
+        old_row.encode(&mut encoded);
+        let mut row = Row::decode(&encoded).unwrap();
+        Schema::<Row>::backfill(&new_desc, &mut row);
+        assert_eq!(
+            row,
+            Row::pack_slice(&[Datum::Int64(1), Datum::String("b"), Datum::Null])
+        );
+
+        // Rows written with the new schema are unchanged.
+        Schema::<Row>::backfill(&new_desc, &mut row);
+        assert_eq!(
+            row,
+            Row::pack_slice(&[Datum::Int64(1), Datum::String("b"), Datum::Null])
+        );
+    }
 }
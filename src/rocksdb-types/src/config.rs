@@ -42,7 +42,7 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use mz_ore::cast::CastFrom;
-use mz_proto::{IntoRustIfSome, RustType, TryFromProtoError};
+use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
@@ -310,40 +310,72 @@ impl std::fmt::Display for CompressionType {
     }
 }
 
+impl RustType<proto_rocks_db_tuning_parameters::ProtoCompactionStyle> for CompactionStyle {
+    fn into_proto(&self) -> proto_rocks_db_tuning_parameters::ProtoCompactionStyle {
+        use proto_rocks_db_tuning_parameters::proto_compaction_style::Kind;
+        proto_rocks_db_tuning_parameters::ProtoCompactionStyle {
+            kind: Some(match self {
+                CompactionStyle::Level => Kind::Level(()),
+                CompactionStyle::Universal => Kind::Universal(()),
+            }),
+        }
+    }
+
+    fn from_proto(
+        proto: proto_rocks_db_tuning_parameters::ProtoCompactionStyle,
+    ) -> Result<Self, TryFromProtoError> {
+        use proto_rocks_db_tuning_parameters::proto_compaction_style::Kind;
+        match proto.kind {
+            Some(Kind::Level(())) => Ok(CompactionStyle::Level),
+            Some(Kind::Universal(())) => Ok(CompactionStyle::Universal),
+            None => Err(TryFromProtoError::missing_field(
+                "ProtoCompactionStyle::kind",
+            )),
+        }
+    }
+}
+
+impl RustType<proto_rocks_db_tuning_parameters::ProtoCompressionType> for CompressionType {
+    fn into_proto(&self) -> proto_rocks_db_tuning_parameters::ProtoCompressionType {
+        use proto_rocks_db_tuning_parameters::proto_compression_type::Kind;
+        proto_rocks_db_tuning_parameters::ProtoCompressionType {
+            kind: Some(match self {
+                CompressionType::Zstd => Kind::Zstd(()),
+                CompressionType::Snappy => Kind::Snappy(()),
+                CompressionType::Lz4 => Kind::Lz4(()),
+                CompressionType::None => Kind::None(()),
+            }),
+        }
+    }
+
+    fn from_proto(
+        proto: proto_rocks_db_tuning_parameters::ProtoCompressionType,
+    ) -> Result<Self, TryFromProtoError> {
+        use proto_rocks_db_tuning_parameters::proto_compression_type::Kind;
+        match proto.kind {
+            Some(Kind::Zstd(())) => Ok(CompressionType::Zstd),
+            Some(Kind::Snappy(())) => Ok(CompressionType::Snappy),
+            Some(Kind::Lz4(())) => Ok(CompressionType::Lz4),
+            Some(Kind::None(())) => Ok(CompressionType::None),
+            None => Err(TryFromProtoError::missing_field(
+                "ProtoCompressionType::kind",
+            )),
+        }
+    }
+}
+
 impl RustType<ProtoRocksDbTuningParameters> for RocksDBTuningParameters {
     fn into_proto(&self) -> ProtoRocksDbTuningParameters {
-        use proto_rocks_db_tuning_parameters::{
-            proto_compaction_style, proto_compression_type, ProtoCompactionStyle,
-            ProtoCompressionType,
-        };
-
-        fn compression_into_proto(compression_type: &CompressionType) -> ProtoCompressionType {
-            ProtoCompressionType {
-                kind: Some(match compression_type {
-                    CompressionType::Zstd => proto_compression_type::Kind::Zstd(()),
-                    CompressionType::Snappy => proto_compression_type::Kind::Snappy(()),
-                    CompressionType::Lz4 => proto_compression_type::Kind::Lz4(()),
-                    CompressionType::None => proto_compression_type::Kind::None(()),
-                }),
-            }
-        }
         ProtoRocksDbTuningParameters {
-            compaction_style: Some(ProtoCompactionStyle {
-                kind: Some(match self.compaction_style {
-                    CompactionStyle::Level => proto_compaction_style::Kind::Level(()),
-                    CompactionStyle::Universal => proto_compaction_style::Kind::Universal(()),
-                }),
-            }),
+            compaction_style: Some(self.compaction_style.into_proto()),
             optimize_compaction_memtable_budget: u64::cast_from(
                 self.optimize_compaction_memtable_budget,
             ),
             level_compaction_dynamic_level_bytes: self.level_compaction_dynamic_level_bytes,
             universal_compaction_target_ratio: self.universal_compaction_target_ratio,
             parallelism: self.parallelism,
-            compression_type: Some(compression_into_proto(&self.compression_type)),
-            bottommost_compression_type: Some(compression_into_proto(
-                &self.bottommost_compression_type,
-            )),
+            compression_type: Some(self.compression_type.into_proto()),
+            bottommost_compression_type: Some(self.bottommost_compression_type.into_proto()),
             batch_size: u64::cast_from(self.batch_size),
             retry_max_duration: Some(self.retry_max_duration.into_proto()),
             stats_log_interval_seconds: self.stats_log_interval_seconds,
@@ -359,62 +391,22 @@ impl RustType<ProtoRocksDbTuningParameters> for RocksDBTuningParameters {
     }
 
     fn from_proto(proto: ProtoRocksDbTuningParameters) -> Result<Self, TryFromProtoError> {
-        use proto_rocks_db_tuning_parameters::{
-            proto_compaction_style, proto_compression_type, ProtoCompactionStyle,
-            ProtoCompressionType,
-        };
-
-        fn compression_from_proto(
-            compression_type: Option<ProtoCompressionType>,
-        ) -> Result<CompressionType, TryFromProtoError> {
-            match compression_type {
-                Some(ProtoCompressionType {
-                    kind: Some(proto_compression_type::Kind::Zstd(())),
-                }) => Ok(CompressionType::Zstd),
-                Some(ProtoCompressionType {
-                    kind: Some(proto_compression_type::Kind::Snappy(())),
-                }) => Ok(CompressionType::Snappy),
-                Some(ProtoCompressionType {
-                    kind: Some(proto_compression_type::Kind::Lz4(())),
-                }) => Ok(CompressionType::Lz4),
-                Some(ProtoCompressionType {
-                    kind: Some(proto_compression_type::Kind::None(())),
-                }) => Ok(CompressionType::None),
-                Some(ProtoCompressionType { kind: None }) => Err(TryFromProtoError::MissingField(
-                    "ProtoRocksDbTuningParameters::compression_type::kind".into(),
-                )),
-                None => Err(TryFromProtoError::MissingField(
-                    "ProtoRocksDbTuningParameters::compression_type".into(),
-                )),
-            }
-        }
         Ok(Self {
-            compaction_style: match proto.compaction_style {
-                Some(ProtoCompactionStyle {
-                    kind: Some(proto_compaction_style::Kind::Level(())),
-                }) => CompactionStyle::Level,
-                Some(ProtoCompactionStyle {
-                    kind: Some(proto_compaction_style::Kind::Universal(())),
-                }) => CompactionStyle::Universal,
-                Some(ProtoCompactionStyle { kind: None }) => {
-                    return Err(TryFromProtoError::MissingField(
-                        "ProtoRocksDbTuningParameters::compaction_style::kind".into(),
-                    ))
-                }
-                None => {
-                    return Err(TryFromProtoError::MissingField(
-                        "ProtoRocksDbTuningParameters::compaction_style".into(),
-                    ))
-                }
-            },
+            compaction_style: proto
+                .compaction_style
+                .into_rust_if_some("ProtoRocksDbTuningParameters::compaction_style")?,
             optimize_compaction_memtable_budget: usize::cast_from(
                 proto.optimize_compaction_memtable_budget,
             ),
             level_compaction_dynamic_level_bytes: proto.level_compaction_dynamic_level_bytes,
             universal_compaction_target_ratio: proto.universal_compaction_target_ratio,
             parallelism: proto.parallelism,
-            compression_type: compression_from_proto(proto.compression_type)?,
-            bottommost_compression_type: compression_from_proto(proto.bottommost_compression_type)?,
+            compression_type: proto
+                .compression_type
+                .into_rust_if_some("ProtoRocksDbTuningParameters::compression_type")?,
+            bottommost_compression_type: proto
+                .bottommost_compression_type
+                .into_rust_if_some("ProtoRocksDbTuningParameters::bottommost_compression_type")?,
             batch_size: usize::cast_from(proto.batch_size),
             retry_max_duration: proto
                 .retry_max_duration
@@ -432,6 +424,36 @@ impl RustType<ProtoRocksDbTuningParameters> for RocksDBTuningParameters {
     }
 }
 
+/// Overrides of the cluster-wide `RocksDBTuningParameters` for a single `UPSERT` source. Fields
+/// that are `None` keep the cluster-wide value.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug, Default, Arbitrary)]
+pub struct RocksDBTuningOverrides {
+    /// Overrides `RocksDBTuningParameters::compaction_style`.
+    pub compaction_style: Option<CompactionStyle>,
+    /// Overrides `RocksDBTuningParameters::compression_type`.
+    pub compression_type: Option<CompressionType>,
+    /// Overrides `RocksDBTuningParameters::point_lookup_block_cache_size_mb`.
+    pub point_lookup_block_cache_size_mb: Option<u32>,
+}
+
+impl RustType<ProtoRocksDbTuningOverrides> for RocksDBTuningOverrides {
+    fn into_proto(&self) -> ProtoRocksDbTuningOverrides {
+        ProtoRocksDbTuningOverrides {
+            compaction_style: self.compaction_style.into_proto(),
+            compression_type: self.compression_type.into_proto(),
+            point_lookup_block_cache_size_mb: self.point_lookup_block_cache_size_mb,
+        }
+    }
+
+    fn from_proto(proto: ProtoRocksDbTuningOverrides) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            compaction_style: proto.compaction_style.into_rust()?,
+            compression_type: proto.compression_type.into_rust()?,
+            point_lookup_block_cache_size_mb: proto.point_lookup_block_cache_size_mb,
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RocksDbWriteBufferManagerConfig {
     /// Optional write buffer manager bytes. This needs to be set to enable write buffer manager
@@ -542,4 +564,14 @@ mod tests {
 
         });
     }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // too slow
+    fn rocksdb_tuning_overrides_roundtrip() {
+        proptest!(|(expect in any::<RocksDBTuningOverrides>())| {
+            let actual = protobuf_roundtrip::<_, ProtoRocksDbTuningOverrides>(&expect);
+            assert!(actual.is_ok());
+            assert_eq!(actual.unwrap(), expect);
+        });
+    }
 }
@@ -171,6 +171,27 @@ impl RocksDBConfig {
         // SeqCst is probably not required here, but its the easiest to reason about
         self.dynamic.batch_size.store(batch_size, Ordering::SeqCst);
     }
+
+    /// Returns a copy of this config with the per-instance `overrides` applied on top.
+    pub fn with_overrides(&self, overrides: &RocksDBTuningOverrides) -> Self {
+        let RocksDBTuningOverrides {
+            compaction_style,
+            compression_type,
+            point_lookup_block_cache_size_mb,
+        } = overrides;
+
+        let mut config = self.clone();
+        if let Some(compaction_style) = compaction_style {
+            config.compaction_style = *compaction_style;
+        }
+        if let Some(compression_type) = compression_type {
+            config.compression_type = *compression_type;
+        }
+        if let Some(size_mb) = point_lookup_block_cache_size_mb {
+            config.point_lookup_block_cache_size_mb = Some(*size_mb);
+        }
+        config
+    }
 }
 
 #[derive(Clone, Default)]
@@ -21,7 +21,9 @@ use anyhow::bail;
 use futures::stream::{BoxStream, Stream, StreamExt};
 use mz_ore::error::ErrorExt;
 use mz_ore::task::JoinSetExt;
-use openssl::ssl::{SslAcceptor, SslContext, SslFiletype, SslMethod};
+use openssl::nid::Nid;
+use openssl::ssl::{SslAcceptor, SslContext, SslFiletype, SslMethod, SslRef, SslVerifyMode};
+use openssl::x509::{X509Name, X509VerifyResult};
 use socket2::{SockRef, TcpKeepalive};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::oneshot;
@@ -214,6 +216,11 @@ pub struct TlsCertConfig {
     pub cert: PathBuf,
     /// The path to the TLS key.
     pub key: PathBuf,
+    /// The path to the certificate authorities that sign client certificates.
+    ///
+    /// If set, clients must present a certificate signed by one of these
+    /// authorities.
+    pub client_ca: Option<PathBuf>,
 }
 
 impl TlsCertConfig {
@@ -228,6 +235,11 @@ impl TlsCertConfig {
         let mut builder = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls())?;
         builder.set_certificate_chain_file(&self.cert)?;
         builder.set_private_key_file(&self.key, SslFiletype::PEM)?;
+        if let Some(client_ca) = &self.client_ca {
+            builder.set_ca_file(client_ca)?;
+            builder.set_client_ca_list(X509Name::load_client_ca_file(client_ca)?);
+            builder.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+        }
         Ok(builder.build().into_context())
     }
 
@@ -304,6 +316,45 @@ pub fn cert_reload_never_reload() -> ReloadTrigger {
     ticker
 }
 
+/// The names that identify a client in a verified TLS client certificate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCertIdentity {
+    /// The common name (CN) of the certificate's subject.
+    pub common_name: Option<String>,
+    /// The certificate's DNS, email, and URI subject alternative names.
+    pub subject_alt_names: Vec<String>,
+}
+
+impl ClientCertIdentity {
+    /// Returns the identity in the certificate the peer of `ssl` presented, or
+    /// `None` if the peer did not present a certificate that was verified.
+    pub fn from_ssl(ssl: &SslRef) -> Option<ClientCertIdentity> {
+        let cert = ssl.peer_certificate()?;
+        if ssl.verify_result() != X509VerifyResult::OK {
+            return None;
+        }
+        let common_name = cert
+            .subject_name()
+            .entries_by_nid(Nid::COMMONNAME)
+            .next()
+            .and_then(|entry| entry.data().as_utf8().ok())
+            .map(|cn| cn.to_string());
+        let subject_alt_names = cert
+            .subject_alt_names()
+            .into_iter()
+            .flatten()
+            .filter_map(|name| {
+                let name = name.dnsname().or(name.email()).or(name.uri())?;
+                Some(name.to_string())
+            })
+            .collect();
+        Some(ClientCertIdentity {
+            common_name,
+            subject_alt_names,
+        })
+    }
+}
+
 /// Command line arguments for TLS.
 #[derive(Debug, Clone, clap::Parser)]
 pub struct TlsCliArgs {
@@ -344,6 +395,18 @@ pub struct TlsCliArgs {
         value_name = "PATH"
     )]
     tls_key: Option<PathBuf>,
+    /// Certificate authorities for verifying TLS client certificates.
+    ///
+    /// If set, clients must present a certificate signed by one of these
+    /// authorities, and log in as the role that the certificate's names map
+    /// to under the `tls_client_cert_role_mapping` system variable.
+    #[clap(
+        long,
+        env = "TLS_CLIENT_CA",
+        requires = "tls-cert",
+        value_name = "PATH"
+    )]
+    tls_client_ca: Option<PathBuf>,
 }
 
 impl TlsCliArgs {
@@ -356,11 +419,19 @@ impl TlsCliArgs {
             if self.tls_key.is_some() {
                 bail!("cannot specify --tls-mode=disable and --tls-key simultaneously");
             }
+            if self.tls_client_ca.is_some() {
+                bail!("cannot specify --tls-mode=disable and --tls-client-ca simultaneously");
+            }
             Ok(None)
         } else {
             let cert = self.tls_cert.unwrap();
             let key = self.tls_key.unwrap();
-            Ok(Some(TlsCertConfig { cert, key }))
+            let client_ca = self.tls_client_ca;
+            Ok(Some(TlsCertConfig {
+                cert,
+                key,
+                client_ca,
+            }))
         }
     }
 }
@@ -84,7 +84,7 @@ impl Keyword {
             self,
             // These keywords are ambiguous when used as a table alias, as they
             // conflict with the syntax for joins.
-            ON | JOIN | INNER | CROSS | FULL | LEFT | RIGHT | NATURAL | USING |
+            ON | JOIN | INNER | CROSS | FULL | LEFT | RIGHT | NATURAL | USING | ASOF |
             // Needed for UPDATE.
             SET |
             // `OUTER` is not strictly ambiguous, but it prevents `a OUTER JOIN
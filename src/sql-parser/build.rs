@@ -49,7 +49,11 @@ mod simple_option_names {
     use mz_walkabout::ir;
 
     // TODO: we might want to identify these enums using an attribute.
-    const ENUMS: [&str; 2] = ["ExplainPlanOptionName", "ClusterFeatureName"];
+    const ENUMS: [&str; 3] = [
+        "ExplainPlanOptionName",
+        "ClusterFeatureName",
+        "ClusterAlterOptionName",
+    ];
 
     /// Generate `Parser` methods.
     pub fn gen_display(ir: &ir::Ir) -> String {
@@ -32,6 +32,8 @@ use crate::ast::{
 pub enum MaterializedViewOptionName {
     /// The `ASSERT NOT NULL [=] <ident>` option.
     AssertNotNull,
+    /// The `ASSERT APPEND ONLY [[=] <bool>]` option.
+    AssertAppendOnly,
     RetainHistory,
     /// The `REFRESH [=] ...` option.
     Refresh,
@@ -41,6 +43,7 @@ impl AstDisplay for MaterializedViewOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             MaterializedViewOptionName::AssertNotNull => f.write_str("ASSERT NOT NULL"),
+            MaterializedViewOptionName::AssertAppendOnly => f.write_str("ASSERT APPEND ONLY"),
             MaterializedViewOptionName::RetainHistory => f.write_str("RETAIN HISTORY"),
             MaterializedViewOptionName::Refresh => f.write_str("REFRESH"),
         }
@@ -56,6 +59,7 @@ impl WithOptionName for MaterializedViewOptionName {
     fn redact_value(&self) -> bool {
         match self {
             MaterializedViewOptionName::AssertNotNull
+            | MaterializedViewOptionName::AssertAppendOnly
             | MaterializedViewOptionName::RetainHistory
             | MaterializedViewOptionName::Refresh => false,
         }
@@ -87,12 +91,18 @@ impl_display!(Schema);
 pub enum AvroSchemaOptionName {
     /// The `CONFLUENT WIRE FORMAT [=] <bool>` option.
     ConfluentWireFormat,
+    /// The `UNION DISCRIMINANT [=] <bool>` option.
+    UnionDiscriminant,
+    /// The `UNION TYPE NAMES [=] <bool>` option.
+    UnionTypeNames,
 }
 
 impl AstDisplay for AvroSchemaOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             AvroSchemaOptionName::ConfluentWireFormat => f.write_str("CONFLUENT WIRE FORMAT"),
+            AvroSchemaOptionName::UnionDiscriminant => f.write_str("UNION DISCRIMINANT"),
+            AvroSchemaOptionName::UnionTypeNames => f.write_str("UNION TYPE NAMES"),
         }
     }
 }
@@ -105,7 +115,7 @@ impl WithOptionName for AvroSchemaOptionName {
     /// on the conservative side and return `true`.
     fn redact_value(&self) -> bool {
         match self {
-            Self::ConfluentWireFormat => false,
+            Self::ConfluentWireFormat | Self::UnionDiscriminant | Self::UnionTypeNames => false,
         }
     }
 }
@@ -151,6 +161,43 @@ impl<T: AstInfo> AstDisplay for AvroSchema<T> {
 }
 impl_display_t!(AvroSchema);
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtobufSchemaOptionName {
+    /// The `WELL KNOWN TYPES [=] <bool>` option.
+    WellKnownTypes,
+    /// The `UNPACK ANY [=] <bool>` option.
+    UnpackAny,
+}
+
+impl AstDisplay for ProtobufSchemaOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            ProtobufSchemaOptionName::WellKnownTypes => f.write_str("WELL KNOWN TYPES"),
+            ProtobufSchemaOptionName::UnpackAny => f.write_str("UNPACK ANY"),
+        }
+    }
+}
+
+impl WithOptionName for ProtobufSchemaOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            Self::WellKnownTypes | Self::UnpackAny => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProtobufSchemaOption<T: AstInfo> {
+    pub name: ProtobufSchemaOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(ProtobufSchemaOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ProtobufSchema<T: AstInfo> {
     Csr {
@@ -159,6 +206,7 @@ pub enum ProtobufSchema<T: AstInfo> {
     InlineSchema {
         message_name: String,
         schema: Schema,
+        with_options: Vec<ProtobufSchemaOption<T>>,
     },
 }
 
@@ -171,11 +219,17 @@ impl<T: AstInfo> AstDisplay for ProtobufSchema<T> {
             Self::InlineSchema {
                 message_name,
                 schema,
+                with_options,
             } => {
                 f.write_str("MESSAGE '");
                 f.write_node(&display::escape_single_quote_string(message_name));
                 f.write_str("' USING ");
                 f.write_str(schema);
+                if !with_options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(with_options));
+                    f.write_str(")");
+                }
             }
         }
     }
@@ -190,6 +244,7 @@ pub enum CsrConfigOptionName<T: AstInfo> {
     AvroDocOn(AvroDocOn<T>),
     KeyCompatibilityLevel,
     ValueCompatibilityLevel,
+    SchemaReferences,
 }
 
 impl<T: AstInfo> WithOptionName for CsrConfigOptionName<T> {
@@ -205,7 +260,8 @@ impl<T: AstInfo> WithOptionName for CsrConfigOptionName<T> {
             | Self::NullDefaults
             | Self::AvroDocOn(_)
             | Self::KeyCompatibilityLevel
-            | Self::ValueCompatibilityLevel => false,
+            | Self::ValueCompatibilityLevel
+            | Self::SchemaReferences => false,
         }
     }
 }
@@ -260,6 +316,7 @@ impl<T: AstInfo> AstDisplay for CsrConfigOptionName<T> {
             CsrConfigOptionName::ValueCompatibilityLevel => {
                 f.write_str("VALUE COMPATIBILITY LEVEL")
             }
+            CsrConfigOptionName::SchemaReferences => f.write_str("SCHEMA REFERENCES"),
         }
     }
 }
@@ -430,20 +487,129 @@ impl_display_t!(CreateSourceFormat);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Format<T: AstInfo> {
-    Bytes,
+    Bytes {
+        with_options: Vec<FramingOption<T>>,
+    },
     Avro(AvroSchema<T>),
     Protobuf(ProtobufSchema<T>),
     Regex(String),
     Csv {
         columns: CsvColumns,
-        delimiter: char,
+        delimiter: String,
+        with_options: Vec<CsvFormatOption<T>>,
     },
     Json {
         array: bool,
     },
-    Text,
+    /// `JSON SHRED (<path> AS <name> <type>, ...)`
+    JsonShred {
+        columns: Vec<JsonShredColumnDef<T>>,
+    },
+    Text {
+        with_options: Vec<FramingOption<T>>,
+    },
+}
+
+/// An option that controls how `FORMAT TEXT` and `FORMAT BYTES` split each
+/// message into records.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FramingOptionName {
+    /// The `DELIMITER [=] <string>` option.
+    Delimiter,
+    /// The `LENGTH PREFIX [=] <width>` option.
+    LengthPrefix,
+}
+
+impl AstDisplay for FramingOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            FramingOptionName::Delimiter => f.write_str("DELIMITER"),
+            FramingOptionName::LengthPrefix => f.write_str("LENGTH PREFIX"),
+        }
+    }
 }
 
+impl WithOptionName for FramingOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            Self::Delimiter | Self::LengthPrefix => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FramingOption<T: AstInfo> {
+    pub name: FramingOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(FramingOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CsvFormatOptionName {
+    /// The `QUOTE [=] <char>` option.
+    Quote,
+    /// The `ESCAPE [=] <char>` option.
+    Escape,
+    /// The `EMBEDDED NEWLINES [=] <bool>` option.
+    EmbeddedNewlines,
+}
+
+impl AstDisplay for CsvFormatOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            CsvFormatOptionName::Quote => f.write_str("QUOTE"),
+            CsvFormatOptionName::Escape => f.write_str("ESCAPE"),
+            CsvFormatOptionName::EmbeddedNewlines => f.write_str("EMBEDDED NEWLINES"),
+        }
+    }
+}
+
+impl WithOptionName for CsvFormatOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            Self::Quote | Self::Escape | Self::EmbeddedNewlines => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CsvFormatOption<T: AstInfo> {
+    pub name: CsvFormatOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(CsvFormatOption);
+
+/// A column extracted from a JSON value by `FORMAT JSON SHRED`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JsonShredColumnDef<T: AstInfo> {
+    /// The JSONPath expression that selects the column's value.
+    pub path: String,
+    pub name: Ident,
+    pub data_type: T::DataType,
+}
+
+impl<T: AstInfo> AstDisplay for JsonShredColumnDef<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("'");
+        f.write_node(&display::escape_single_quote_string(&self.path));
+        f.write_str("' AS ");
+        f.write_node(&self.name);
+        f.write_str(" ");
+        f.write_node(&self.data_type);
+    }
+}
+impl_display_t!(JsonShredColumnDef);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CsvColumns {
     /// `WITH count COLUMNS`
@@ -479,6 +645,9 @@ pub enum SourceIncludeMetadata {
     Timestamp {
         alias: Option<Ident>,
     },
+    TimestampType {
+        alias: Option<Ident>,
+    },
     Partition {
         alias: Option<Ident>,
     },
@@ -488,6 +657,9 @@ pub enum SourceIncludeMetadata {
     Headers {
         alias: Option<Ident>,
     },
+    HeadersMap {
+        alias: Option<Ident>,
+    },
     Header {
         key: String,
         alias: Ident,
@@ -513,6 +685,10 @@ impl AstDisplay for SourceIncludeMetadata {
                 f.write_str("TIMESTAMP");
                 print_alias(f, alias);
             }
+            SourceIncludeMetadata::TimestampType { alias } => {
+                f.write_str("TIMESTAMP TYPE");
+                print_alias(f, alias);
+            }
             SourceIncludeMetadata::Partition { alias } => {
                 f.write_str("PARTITION");
                 print_alias(f, alias);
@@ -525,6 +701,10 @@ impl AstDisplay for SourceIncludeMetadata {
                 f.write_str("HEADERS");
                 print_alias(f, alias);
             }
+            SourceIncludeMetadata::HeadersMap { alias } => {
+                f.write_str("HEADERS MAP");
+                print_alias(f, alias);
+            }
             SourceIncludeMetadata::Header {
                 alias,
                 key,
@@ -639,7 +819,14 @@ impl_display_t!(SubscribeOutput);
 impl<T: AstInfo> AstDisplay for Format<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
-            Self::Bytes => f.write_str("BYTES"),
+            Self::Bytes { with_options } => {
+                f.write_str("BYTES");
+                if !with_options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(with_options));
+                    f.write_str(")");
+                }
+            }
             Self::Avro(inner) => {
                 f.write_str("AVRO ");
                 f.write_node(inner);
@@ -653,15 +840,24 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                 f.write_node(&display::escape_single_quote_string(regex));
                 f.write_str("'");
             }
-            Self::Csv { columns, delimiter } => {
+            Self::Csv {
+                columns,
+                delimiter,
+                with_options,
+            } => {
                 f.write_str("CSV WITH ");
                 f.write_node(columns);
 
-                if *delimiter != ',' {
+                if delimiter != "," {
                     f.write_str(" DELIMITED BY '");
-                    f.write_node(&display::escape_single_quote_string(&delimiter.to_string()));
+                    f.write_node(&display::escape_single_quote_string(delimiter));
                     f.write_str("'");
                 }
+                if !with_options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(with_options));
+                    f.write_str(")");
+                }
             }
             Self::Json { array } => {
                 f.write_str("JSON");
@@ -669,7 +865,19 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                     f.write_str(" ARRAY");
                 }
             }
-            Self::Text => f.write_str("TEXT"),
+            Self::JsonShred { columns } => {
+                f.write_str("JSON SHRED (");
+                f.write_node(&display::comma_separated(columns));
+                f.write_str(")");
+            }
+            Self::Text { with_options } => {
+                f.write_str("TEXT");
+                if !with_options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(with_options));
+                    f.write_str(")");
+                }
+            }
         }
     }
 }
@@ -874,22 +1082,26 @@ impl_display_t!(CreateConnectionOption);
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum KafkaSourceConfigOptionName {
     GroupIdPrefix,
+    MaxIngestRate,
     Topic,
     TopicMetadataRefreshInterval,
     StartTimestamp,
     StartOffset,
+    ValueDecryptionKey,
 }
 
 impl AstDisplay for KafkaSourceConfigOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(match self {
             KafkaSourceConfigOptionName::GroupIdPrefix => "GROUP ID PREFIX",
+            KafkaSourceConfigOptionName::MaxIngestRate => "MAX INGEST RATE",
             KafkaSourceConfigOptionName::Topic => "TOPIC",
             KafkaSourceConfigOptionName::TopicMetadataRefreshInterval => {
                 "TOPIC METADATA REFRESH INTERVAL"
             }
             KafkaSourceConfigOptionName::StartOffset => "START OFFSET",
             KafkaSourceConfigOptionName::StartTimestamp => "START TIMESTAMP",
+            KafkaSourceConfigOptionName::ValueDecryptionKey => "VALUE DECRYPTION KEY",
         })
     }
 }
@@ -904,10 +1116,12 @@ impl WithOptionName for KafkaSourceConfigOptionName {
     fn redact_value(&self) -> bool {
         match self {
             KafkaSourceConfigOptionName::GroupIdPrefix
+            | KafkaSourceConfigOptionName::MaxIngestRate
             | KafkaSourceConfigOptionName::Topic
             | KafkaSourceConfigOptionName::TopicMetadataRefreshInterval
             | KafkaSourceConfigOptionName::StartOffset
-            | KafkaSourceConfigOptionName::StartTimestamp => false,
+            | KafkaSourceConfigOptionName::StartTimestamp
+            | KafkaSourceConfigOptionName::ValueDecryptionKey => false,
         }
     }
 }
@@ -923,6 +1137,8 @@ impl_display_t!(KafkaSourceConfigOption);
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum KafkaSinkConfigOptionName {
     CompressionType,
+    DeliveryGuarantee,
+    PartitionBy,
     ProgressGroupIdPrefix,
     Topic,
     TransactionalIdPrefix,
@@ -931,12 +1147,15 @@ pub enum KafkaSinkConfigOptionName {
     TopicPartitionCount,
     TopicReplicationFactor,
     ProgressTopicReplicationFactor,
+    TransactionMetadataTopic,
 }
 
 impl AstDisplay for KafkaSinkConfigOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(match self {
             KafkaSinkConfigOptionName::CompressionType => "COMPRESSION TYPE",
+            KafkaSinkConfigOptionName::DeliveryGuarantee => "DELIVERY GUARANTEE",
+            KafkaSinkConfigOptionName::PartitionBy => "PARTITION BY",
             KafkaSinkConfigOptionName::ProgressGroupIdPrefix => "PROGRESS GROUP ID PREFIX",
             KafkaSinkConfigOptionName::Topic => "TOPIC",
             KafkaSinkConfigOptionName::TransactionalIdPrefix => "TRANSACTIONAL ID PREFIX",
@@ -947,6 +1166,7 @@ impl AstDisplay for KafkaSinkConfigOptionName {
             KafkaSinkConfigOptionName::ProgressTopicReplicationFactor => {
                 "PROGRESS TOPIC REPLICATION FACTOR"
             }
+            KafkaSinkConfigOptionName::TransactionMetadataTopic => "TRANSACTION METADATA TOPIC",
         })
     }
 }
@@ -961,6 +1181,8 @@ impl WithOptionName for KafkaSinkConfigOptionName {
     fn redact_value(&self) -> bool {
         match self {
             KafkaSinkConfigOptionName::CompressionType
+            | KafkaSinkConfigOptionName::DeliveryGuarantee
+            | KafkaSinkConfigOptionName::PartitionBy
             | KafkaSinkConfigOptionName::ProgressGroupIdPrefix
             | KafkaSinkConfigOptionName::Topic
             | KafkaSinkConfigOptionName::TransactionalIdPrefix
@@ -968,7 +1190,8 @@ impl WithOptionName for KafkaSinkConfigOptionName {
             | KafkaSinkConfigOptionName::TopicConfig
             | KafkaSinkConfigOptionName::TopicPartitionCount
             | KafkaSinkConfigOptionName::TopicReplicationFactor
-            | KafkaSinkConfigOptionName::ProgressTopicReplicationFactor => false,
+            | KafkaSinkConfigOptionName::ProgressTopicReplicationFactor
+            | KafkaSinkConfigOptionName::TransactionMetadataTopic => false,
         }
     }
 }
@@ -1154,6 +1377,7 @@ pub enum LoadGenerator {
     Datums,
     Tpch,
     KeyValue,
+    Custom,
 }
 
 impl AstDisplay for LoadGenerator {
@@ -1165,6 +1389,7 @@ impl AstDisplay for LoadGenerator {
             Self::Datums => f.write_str("DATUMS"),
             Self::Tpch => f.write_str("TPCH"),
             Self::KeyValue => f.write_str("KEY VALUE"),
+            Self::Custom => f.write_str("CUSTOM"),
         }
     }
 }
@@ -1184,6 +1409,8 @@ pub enum LoadGeneratorOptionName {
     Seed,
     Partitions,
     BatchSize,
+    Columns,
+    RowsPerSecond,
 }
 
 impl AstDisplay for LoadGeneratorOptionName {
@@ -1201,6 +1428,8 @@ impl AstDisplay for LoadGeneratorOptionName {
             LoadGeneratorOptionName::Seed => "SEED",
             LoadGeneratorOptionName::Partitions => "PARTITIONS",
             LoadGeneratorOptionName::BatchSize => "BATCH SIZE",
+            LoadGeneratorOptionName::Columns => "COLUMNS",
+            LoadGeneratorOptionName::RowsPerSecond => "ROWS PER SECOND",
         })
     }
 }
@@ -1225,6 +1454,8 @@ impl WithOptionName for LoadGeneratorOptionName {
             | LoadGeneratorOptionName::ValueSize
             | LoadGeneratorOptionName::Partitions
             | LoadGeneratorOptionName::BatchSize
+            | LoadGeneratorOptionName::Columns
+            | LoadGeneratorOptionName::RowsPerSecond
             | LoadGeneratorOptionName::Seed => false,
         }
     }
@@ -1400,6 +1631,9 @@ pub enum CreateSourceOptionName {
     Timeline,
     TimestampInterval,
     RetainHistory,
+    RocksdbBlockCacheSize,
+    RocksdbCompactionStyle,
+    RocksdbCompressionType,
 }
 
 impl AstDisplay for CreateSourceOptionName {
@@ -1409,6 +1643,9 @@ impl AstDisplay for CreateSourceOptionName {
             CreateSourceOptionName::Timeline => "TIMELINE",
             CreateSourceOptionName::TimestampInterval => "TIMESTAMP INTERVAL",
             CreateSourceOptionName::RetainHistory => "RETAIN HISTORY",
+            CreateSourceOptionName::RocksdbBlockCacheSize => "ROCKSDB BLOCK CACHE SIZE",
+            CreateSourceOptionName::RocksdbCompactionStyle => "ROCKSDB COMPACTION STYLE",
+            CreateSourceOptionName::RocksdbCompressionType => "ROCKSDB COMPRESSION TYPE",
         })
     }
 }
@@ -1425,7 +1662,10 @@ impl WithOptionName for CreateSourceOptionName {
             CreateSourceOptionName::IgnoreKeys
             | CreateSourceOptionName::Timeline
             | CreateSourceOptionName::TimestampInterval
-            | CreateSourceOptionName::RetainHistory => false,
+            | CreateSourceOptionName::RetainHistory
+            | CreateSourceOptionName::RocksdbBlockCacheSize
+            | CreateSourceOptionName::RocksdbCompactionStyle
+            | CreateSourceOptionName::RocksdbCompressionType => false,
         }
     }
 }
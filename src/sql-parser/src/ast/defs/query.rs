@@ -324,11 +324,13 @@ impl<T: AstInfo> AstDisplay for Distinct<T> {
 /// A block of common table expressions (CTEs).
 ///
 /// The block can either be entirely "simple" (traditional SQL `WITH` block),
-/// or "mutually recursive", which introduce their bindings before the block
-/// and may result in mutually recursive definitions.
+/// "recursive" (standard SQL `WITH RECURSIVE` block), whose CTEs may refer to
+/// themselves, or "mutually recursive", which introduce their bindings before
+/// the block and may result in mutually recursive definitions.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum CteBlock<T: AstInfo> {
     Simple(Vec<Cte<T>>),
+    Recursive(Vec<Cte<T>>),
     MutuallyRecursive(MutRecBlock<T>),
 }
 
@@ -346,7 +348,7 @@ impl<T: AstInfo> CteBlock<T> {
     /// True if there are no bindings in the block.
     pub fn is_empty(&self) -> bool {
         match self {
-            CteBlock::Simple(list) => list.is_empty(),
+            CteBlock::Simple(list) | CteBlock::Recursive(list) => list.is_empty(),
             CteBlock::MutuallyRecursive(list) => list.ctes.is_empty(),
         }
     }
@@ -354,7 +356,7 @@ impl<T: AstInfo> CteBlock<T> {
     pub fn bound_identifiers(&self) -> impl Iterator<Item = &Ident> {
         let mut names = Vec::new();
         match self {
-            CteBlock::Simple(list) => {
+            CteBlock::Simple(list) | CteBlock::Recursive(list) => {
                 for cte in list.iter() {
                     names.push(&cte.alias.name);
                 }
@@ -377,6 +379,10 @@ impl<T: AstInfo> AstDisplay for CteBlock<T> {
                     f.write_str("WITH ");
                     f.write_node(&display::comma_separated(list));
                 }
+                CteBlock::Recursive(list) => {
+                    f.write_str("WITH RECURSIVE ");
+                    f.write_node(&display::comma_separated(list));
+                }
                 CteBlock::MutuallyRecursive(MutRecBlock { options, ctes }) => {
                     f.write_str("WITH MUTUALLY RECURSIVE ");
                     if !options.is_empty() {
@@ -743,6 +749,20 @@ impl<T: AstInfo> AstDisplay for Join<T> {
                 f.write_node(&self.relation);
                 f.write_node(&suffix(constraint));
             }
+            JoinOperator::AsOf(constraint) => {
+                f.write_str(" ");
+                f.write_str(prefix(constraint));
+                f.write_str("ASOF JOIN ");
+                f.write_node(&self.relation);
+                f.write_node(&suffix(constraint));
+            }
+            JoinOperator::LeftAsOf(constraint) => {
+                f.write_str(" ");
+                f.write_str(prefix(constraint));
+                f.write_str("ASOF LEFT JOIN ");
+                f.write_node(&self.relation);
+                f.write_node(&suffix(constraint));
+            }
             JoinOperator::CrossJoin => {
                 f.write_str(" CROSS JOIN ");
                 f.write_node(&self.relation);
@@ -758,6 +778,11 @@ pub enum JoinOperator<T: AstInfo> {
     LeftOuter(JoinConstraint<T>),
     RightOuter(JoinConstraint<T>),
     FullOuter(JoinConstraint<T>),
+    /// `ASOF JOIN`: matches each left row with the right row that satisfies
+    /// the join condition and is closest according to its inequality.
+    AsOf(JoinConstraint<T>),
+    /// `ASOF LEFT JOIN`: like `ASOF JOIN`, but keeps left rows without a match.
+    LeftAsOf(JoinConstraint<T>),
     CrossJoin,
 }
 
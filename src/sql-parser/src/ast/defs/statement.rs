@@ -77,19 +77,21 @@ pub enum Statement<T: AstInfo> {
     AlterSystemResetAll(AlterSystemResetAllStatement),
     AlterConnection(AlterConnectionStatement<T>),
     AlterRole(AlterRoleStatement<T>),
+    AlterDatabase(AlterDatabaseStatement<T>),
     Discard(DiscardStatement),
     DropObjects(DropObjectsStatement),
     DropOwned(DropOwnedStatement<T>),
     SetVariable(SetVariableStatement),
     ResetVariable(ResetVariableStatement),
     Show(ShowStatement<T>),
-    StartTransaction(StartTransactionStatement),
+    StartTransaction(StartTransactionStatement<T>),
     SetTransaction(SetTransactionStatement),
     Commit(CommitStatement),
     Rollback(RollbackStatement),
     Subscribe(SubscribeStatement<T>),
     ExplainPlan(ExplainPlanStatement<T>),
     ExplainPushdown(ExplainPushdownStatement<T>),
+    ExplainAnalyze(ExplainAnalyzeStatement<T>),
     ExplainTimestamp(ExplainTimestampStatement<T>),
     ExplainSinkSchema(ExplainSinkSchemaStatement<T>),
     Declare(DeclareStatement<T>),
@@ -148,6 +150,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::AlterSystemResetAll(stmt) => f.write_node(stmt),
             Statement::AlterConnection(stmt) => f.write_node(stmt),
             Statement::AlterRole(stmt) => f.write_node(stmt),
+            Statement::AlterDatabase(stmt) => f.write_node(stmt),
             Statement::Discard(stmt) => f.write_node(stmt),
             Statement::DropObjects(stmt) => f.write_node(stmt),
             Statement::DropOwned(stmt) => f.write_node(stmt),
@@ -161,6 +164,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::Subscribe(stmt) => f.write_node(stmt),
             Statement::ExplainPlan(stmt) => f.write_node(stmt),
             Statement::ExplainPushdown(stmt) => f.write_node(stmt),
+            Statement::ExplainAnalyze(stmt) => f.write_node(stmt),
             Statement::ExplainTimestamp(stmt) => f.write_node(stmt),
             Statement::ExplainSinkSchema(stmt) => f.write_node(stmt),
             Statement::Declare(stmt) => f.write_node(stmt),
@@ -213,6 +217,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::AlterObjectSwap => "alter_object_swap",
         StatementKind::AlterIndex => "alter_index",
         StatementKind::AlterRole => "alter_role",
+        StatementKind::AlterDatabase => "alter_database",
         StatementKind::AlterSecret => "alter_secret",
         StatementKind::AlterSetCluster => "alter_set_cluster",
         StatementKind::AlterSink => "alter_sink",
@@ -235,6 +240,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::Subscribe => "subscribe",
         StatementKind::ExplainPlan => "explain_plan",
         StatementKind::ExplainPushdown => "explain_pushdown",
+        StatementKind::ExplainAnalyze => "explain_analyze",
         StatementKind::ExplainTimestamp => "explain_timestamp",
         StatementKind::ExplainSinkSchema => "explain_sink_schema",
         StatementKind::Declare => "declare",
@@ -359,6 +365,7 @@ pub enum CopyOptionName {
     Header,
     AwsConnection,
     MaxFileSize,
+    Manifest,
 }
 
 impl AstDisplay for CopyOptionName {
@@ -372,6 +379,7 @@ impl AstDisplay for CopyOptionName {
             CopyOptionName::Header => "HEADER",
             CopyOptionName::AwsConnection => "AWS CONNECTION",
             CopyOptionName::MaxFileSize => "MAX FILE SIZE",
+            CopyOptionName::Manifest => "MANIFEST",
         })
     }
 }
@@ -391,7 +399,8 @@ impl WithOptionName for CopyOptionName {
             | CopyOptionName::Quote
             | CopyOptionName::Header
             | CopyOptionName::AwsConnection
-            | CopyOptionName::MaxFileSize => false,
+            | CopyOptionName::MaxFileSize
+            | CopyOptionName::Manifest => false,
         }
     }
 }
@@ -556,16 +565,16 @@ impl_display!(CreateSchemaStatement);
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ConnectionDefaultAwsPrivatelink<T: AstInfo> {
     pub connection: T::ItemName,
-    // TODO port should be switched to a vec of options similar to KafkaBrokerAwsPrivatelink if ever support more than port
-    pub port: Option<u16>,
+    /// Accepts the same options as a Kafka broker's PrivateLink tunnel.
+    pub options: Vec<KafkaBrokerAwsPrivatelinkOption<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for ConnectionDefaultAwsPrivatelink<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_node(&self.connection);
-        if let Some(port) = self.port {
-            f.write_str(" (PORT ");
-            f.write_node(&display::escape_single_quote_string(&port.to_string()));
+        if !self.options.is_empty() {
+            f.write_str(" (");
+            f.write_node(&display::comma_separated(&self.options));
             f.write_str(")");
         }
     }
@@ -1603,6 +1612,8 @@ pub enum RoleAttribute {
     Inherit,
     /// The `NOINHERIT` option.
     NoInherit,
+    /// The `PASSWORD` option. `None` removes the role's password.
+    Password(Option<String>),
     // The following are not supported, but included to give helpful error messages.
     Login,
     NoLogin,
@@ -1631,6 +1642,17 @@ impl AstDisplay for RoleAttribute {
             RoleAttribute::NoCreateDB => f.write_str("NOCREATEDB"),
             RoleAttribute::CreateRole => f.write_str("CREATEROLE"),
             RoleAttribute::NoCreateRole => f.write_str("NOCREATEROLE"),
+            RoleAttribute::Password(None) => f.write_str("PASSWORD NULL"),
+            RoleAttribute::Password(Some(password)) => {
+                f.write_str("PASSWORD ");
+                if f.redacted() {
+                    f.write_str("'<REDACTED>'");
+                } else {
+                    f.write_str("'");
+                    f.write_node(&display::escape_single_quote_string(password));
+                    f.write_str("'");
+                }
+            }
         }
     }
 }
@@ -1734,6 +1756,10 @@ impl_display_t!(CreateTypeStatement);
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ClusterOptionName {
+    /// The `AUTOSCALE MAX SIZE [[=] <size>]` option.
+    AutoscaleMaxSize,
+    /// The `AUTOSCALE MIN SIZE [[=] <size>]` option.
+    AutoscaleMinSize,
     /// The `AVAILABILITY ZONES [[=] '[' <values> ']' ]` option.
     AvailabilityZones,
     /// The `DISK` option.
@@ -1757,6 +1783,8 @@ pub enum ClusterOptionName {
 impl AstDisplay for ClusterOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
+            ClusterOptionName::AutoscaleMaxSize => f.write_str("AUTOSCALE MAX SIZE"),
+            ClusterOptionName::AutoscaleMinSize => f.write_str("AUTOSCALE MIN SIZE"),
             ClusterOptionName::AvailabilityZones => f.write_str("AVAILABILITY ZONES"),
             ClusterOptionName::Disk => f.write_str("DISK"),
             ClusterOptionName::IntrospectionDebugging => f.write_str("INTROSPECTION DEBUGGING"),
@@ -1778,7 +1806,9 @@ impl WithOptionName for ClusterOptionName {
     /// on the conservative side and return `true`.
     fn redact_value(&self) -> bool {
         match self {
-            ClusterOptionName::AvailabilityZones
+            ClusterOptionName::AutoscaleMaxSize
+            | ClusterOptionName::AutoscaleMinSize
+            | ClusterOptionName::AvailabilityZones
             | ClusterOptionName::Disk
             | ClusterOptionName::IntrospectionDebugging
             | ClusterOptionName::IntrospectionInterval
@@ -1883,9 +1913,41 @@ impl<T: AstInfo> AstDisplay for ReplicaDefinition<T> {
 }
 impl_display_t!(ReplicaDefinition);
 
+// Note: the `AstDisplay` implementation and `Parser::parse_` method for this
+// enum are generated automatically by this crate's `build.rs`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ClusterAlterOptionName {
+    Graceful,
+}
+
+impl WithOptionName for ClusterAlterOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            Self::Graceful => false,
+        }
+    }
+}
+
+/// An option that controls how an `ALTER CLUSTER .. SET` is applied, as
+/// opposed to what it changes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ClusterAlterOption<T: AstInfo> {
+    pub name: ClusterAlterOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(ClusterAlterOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AlterClusterAction<T: AstInfo> {
-    SetOptions(Vec<ClusterOption<T>>),
+    SetOptions {
+        options: Vec<ClusterOption<T>>,
+        with_options: Vec<ClusterAlterOption<T>>,
+    },
     ResetOptions(Vec<ClusterOptionName>),
 }
 
@@ -1909,10 +1971,18 @@ impl<T: AstInfo> AstDisplay for AlterClusterStatement<T> {
         f.write_node(&self.name);
         f.write_str(" ");
         match &self.action {
-            AlterClusterAction::SetOptions(options) => {
+            AlterClusterAction::SetOptions {
+                options,
+                with_options,
+            } => {
                 f.write_str("SET (");
                 f.write_node(&display::comma_separated(options));
                 f.write_str(")");
+                if !with_options.is_empty() {
+                    f.write_str(" WITH (");
+                    f.write_node(&display::comma_separated(with_options));
+                    f.write_str(")");
+                }
             }
             AlterClusterAction::ResetOptions(options) => {
                 f.write_str("RESET (");
@@ -2360,6 +2430,9 @@ pub enum AlterSourceAction<T: AstInfo> {
         cascade: bool,
         names: Vec<UnresolvedItemName>,
     },
+    SwapConnection {
+        connection: T::ItemName,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for AlterSourceAction<T> {
@@ -2408,6 +2481,10 @@ impl<T: AstInfo> AstDisplay for AlterSourceAction<T> {
                     f.write_str(")");
                 }
             }
+            AlterSourceAction::SwapConnection { connection } => {
+                f.write_str("SWAP CONNECTION ");
+                f.write_node(connection);
+            }
         }
     }
 }
@@ -2596,6 +2673,25 @@ impl AstDisplay for AlterRoleOption {
 }
 impl_display!(AlterRoleOption);
 
+/// `ALTER DATABASE ... [ SET | RESET ] ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterDatabaseStatement<T: AstInfo> {
+    /// The specified database.
+    pub name: T::DatabaseName,
+    /// The variable that we want to provide a default value for in this database.
+    pub variable: SetRoleVar,
+}
+
+impl<T: AstInfo> AstDisplay for AlterDatabaseStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER DATABASE ");
+        f.write_node(&self.name);
+        f.write_str(" ");
+        f.write_node(&self.variable);
+    }
+}
+impl_display_t!(AlterDatabaseStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DiscardStatement {
     pub target: DiscardTarget,
@@ -2964,6 +3060,29 @@ impl<T: AstInfo> AstDisplay for ShowColumnsStatement<T> {
 }
 impl_display_t!(ShowColumnsStatement);
 
+/// `SHOW EFFECTIVE PRIVILEGES FOR ROLE <role> ON <object>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShowEffectivePrivilegesStatement<T: AstInfo> {
+    /// The role whose privileges are shown.
+    pub role: T::RoleName,
+    /// The type of the object.
+    pub object_type: ObjectType,
+    /// The object whose privileges are shown.
+    pub name: T::ObjectName,
+}
+
+impl<T: AstInfo> AstDisplay for ShowEffectivePrivilegesStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW EFFECTIVE PRIVILEGES FOR ROLE ");
+        f.write_node(&self.role);
+        f.write_str(" ON ");
+        f.write_node(&self.object_type);
+        f.write_str(" ");
+        f.write_node(&self.name);
+    }
+}
+impl_display_t!(ShowEffectivePrivilegesStatement);
+
 /// `SHOW CREATE VIEW <view>`
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ShowCreateViewStatement<T: AstInfo> {
@@ -3060,22 +3179,64 @@ impl<T: AstInfo> AstDisplay for ShowCreateConnectionStatement<T> {
     }
 }
 
-/// `{ BEGIN [ TRANSACTION | WORK ] | START TRANSACTION } ...`
+/// The container whose objects `SHOW CREATE ALL OBJECTS` shows.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ShowCreateAllObjectsIn<T: AstInfo> {
+    Schema(T::SchemaName),
+    Database(T::DatabaseName),
+}
+
+impl<T: AstInfo> AstDisplay for ShowCreateAllObjectsIn<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            ShowCreateAllObjectsIn::Schema(name) => {
+                f.write_str("SCHEMA ");
+                f.write_node(name);
+            }
+            ShowCreateAllObjectsIn::Database(name) => {
+                f.write_str("DATABASE ");
+                f.write_node(name);
+            }
+        }
+    }
+}
+impl_display_t!(ShowCreateAllObjectsIn);
+
+/// `SHOW CREATE ALL OBJECTS IN { SCHEMA <schema> | DATABASE <database> }`
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct StartTransactionStatement {
+pub struct ShowCreateAllObjectsStatement<T: AstInfo> {
+    pub in_container: ShowCreateAllObjectsIn<T>,
+}
+
+impl<T: AstInfo> AstDisplay for ShowCreateAllObjectsStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW CREATE ALL OBJECTS IN ");
+        f.write_node(&self.in_container);
+    }
+}
+impl_display_t!(ShowCreateAllObjectsStatement);
+
+/// `{ BEGIN [ TRANSACTION | WORK ] | START TRANSACTION } ... [AS OF ...]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StartTransactionStatement<T: AstInfo> {
     pub modes: Vec<TransactionMode>,
+    pub as_of: Option<AsOf<T>>,
 }
 
-impl AstDisplay for StartTransactionStatement {
+impl<T: AstInfo> AstDisplay for StartTransactionStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str("START TRANSACTION");
         if !self.modes.is_empty() {
             f.write_str(" ");
             f.write_node(&display::comma_separated(&self.modes));
         }
+        if let Some(as_of) = &self.as_of {
+            f.write_str(" ");
+            f.write_node(as_of);
+        }
     }
 }
-impl_display!(StartTransactionStatement);
+impl_display_t!(StartTransactionStatement);
 
 /// `SET TRANSACTION ...`
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -3379,6 +3540,19 @@ impl<T: AstInfo> AstDisplay for ExplainPushdownStatement<T> {
 }
 impl_display_t!(ExplainPushdownStatement);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExplainAnalyzeStatement<T: AstInfo> {
+    pub explainee: Explainee<T>,
+}
+
+impl<T: AstInfo> AstDisplay for ExplainAnalyzeStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("EXPLAIN ANALYZE FOR ");
+        f.write_node(&self.explainee);
+    }
+}
+impl_display_t!(ExplainAnalyzeStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExplainTimestampStatement<T: AstInfo> {
     pub format: Option<ExplainFormat>,
@@ -3543,6 +3717,7 @@ pub enum WithOptionValue<T: AstInfo> {
     RetainHistoryFor(Value),
     Refresh(RefreshOptionValue<T>),
     ClusterScheduleOptionValue(ClusterScheduleOptionValue),
+    Expr(Expr<T>),
 }
 
 impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
@@ -3555,7 +3730,8 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
                 | WithOptionValue::Sequence(_)
                 | WithOptionValue::Map(_)
                 | WithOptionValue::RetainHistoryFor(_)
-                | WithOptionValue::Refresh(_) => {
+                | WithOptionValue::Refresh(_)
+                | WithOptionValue::Expr(_) => {
                     // These are redact-aware.
                 }
                 WithOptionValue::Secret(_) | WithOptionValue::ConnectionKafkaBroker(_) => {
@@ -3617,6 +3793,7 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
             }
             WithOptionValue::Refresh(opt) => f.write_node(opt),
             WithOptionValue::ClusterScheduleOptionValue(value) => f.write_node(value),
+            WithOptionValue::Expr(expr) => f.write_node(expr),
         }
     }
 }
@@ -3678,6 +3855,10 @@ pub enum ClusterScheduleOptionValue {
     Refresh {
         rehydration_time_estimate: Option<IntervalValue>,
     },
+    /// The cluster is Off during the minutes matched by any of the given cron-like windows.
+    OffDuring {
+        windows: Vec<String>,
+    },
 }
 
 impl Default for ClusterScheduleOptionValue {
@@ -3703,6 +3884,18 @@ impl AstDisplay for ClusterScheduleOptionValue {
                     f.write_str(")");
                 }
             }
+            ClusterScheduleOptionValue::OffDuring { windows } => {
+                f.write_str("OFF DURING (");
+                for (i, window) in windows.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ");
+                    }
+                    f.write_str("'");
+                    f.write_node(&display::escape_single_quote_string(window));
+                    f.write_str("'");
+                }
+                f.write_str(")");
+            }
         }
     }
 }
@@ -4303,6 +4496,7 @@ impl_display_t!(AsOf);
 pub enum ShowStatement<T: AstInfo> {
     ShowObjects(ShowObjectsStatement<T>),
     ShowColumns(ShowColumnsStatement<T>),
+    ShowEffectivePrivileges(ShowEffectivePrivilegesStatement<T>),
     ShowCreateView(ShowCreateViewStatement<T>),
     ShowCreateMaterializedView(ShowCreateMaterializedViewStatement<T>),
     ShowCreateSource(ShowCreateSourceStatement<T>),
@@ -4310,6 +4504,7 @@ pub enum ShowStatement<T: AstInfo> {
     ShowCreateSink(ShowCreateSinkStatement<T>),
     ShowCreateIndex(ShowCreateIndexStatement<T>),
     ShowCreateConnection(ShowCreateConnectionStatement<T>),
+    ShowCreateAllObjects(ShowCreateAllObjectsStatement<T>),
     ShowVariable(ShowVariableStatement),
     InspectShard(InspectShardStatement),
 }
@@ -4319,6 +4514,7 @@ impl<T: AstInfo> AstDisplay for ShowStatement<T> {
         match self {
             ShowStatement::ShowObjects(stmt) => f.write_node(stmt),
             ShowStatement::ShowColumns(stmt) => f.write_node(stmt),
+            ShowStatement::ShowEffectivePrivileges(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateView(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateMaterializedView(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateSource(stmt) => f.write_node(stmt),
@@ -4326,6 +4522,7 @@ impl<T: AstInfo> AstDisplay for ShowStatement<T> {
             ShowStatement::ShowCreateSink(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateIndex(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateConnection(stmt) => f.write_node(stmt),
+            ShowStatement::ShowCreateAllObjects(stmt) => f.write_node(stmt),
             ShowStatement::ShowVariable(stmt) => f.write_node(stmt),
             ShowStatement::InspectShard(stmt) => f.write_node(stmt),
         }
@@ -1936,21 +1936,43 @@ impl<'a> Parser<'a> {
             };
             let delimiter = if self.parse_keywords(&[DELIMITED, BY]) {
                 let s = self.parse_literal_string()?;
-                match s.len() {
-                    1 => Ok(s.chars().next().unwrap()),
-                    _ => self.expected(self.peek_pos(), "one-character string", self.peek_token()),
-                }?
+                if s.is_empty() {
+                    return self.expected(self.peek_pos(), "non-empty string", self.peek_token());
+                }
+                s
             } else {
-                ','
+                ",".into()
             };
-            Format::Csv { columns, delimiter }
+            let with_options = if self.consume_token(&Token::LParen) {
+                let with_options = self.parse_comma_separated(Parser::parse_csv_format_option)?;
+                self.expect_token(&Token::RParen)?;
+                with_options
+            } else {
+                vec![]
+            };
+            Format::Csv {
+                columns,
+                delimiter,
+                with_options,
+            }
         } else if self.parse_keyword(JSON) {
-            let array = self.parse_keyword(ARRAY);
-            Format::Json { array }
+            if self.parse_keyword(SHRED) {
+                self.expect_token(&Token::LParen)?;
+                let columns = self.parse_comma_separated(Parser::parse_json_shred_column_def)?;
+                self.expect_token(&Token::RParen)?;
+                Format::JsonShred { columns }
+            } else {
+                let array = self.parse_keyword(ARRAY);
+                Format::Json { array }
+            }
         } else if self.parse_keyword(TEXT) {
-            Format::Text
+            Format::Text {
+                with_options: self.parse_framing_options()?,
+            }
         } else if self.parse_keyword(BYTES) {
-            Format::Bytes
+            Format::Bytes {
+                with_options: self.parse_framing_options()?,
+            }
         } else {
             return self.expected(
                 self.peek_pos(),
@@ -1961,6 +1983,59 @@ impl<'a> Parser<'a> {
         Ok(format)
     }
 
+    fn parse_json_shred_column_def(&mut self) -> Result<JsonShredColumnDef<Raw>, ParserError> {
+        let path = self.parse_literal_string()?;
+        self.expect_keyword(AS)?;
+        let name = self.parse_identifier()?;
+        let data_type = self.parse_data_type()?;
+        Ok(JsonShredColumnDef {
+            path,
+            name,
+            data_type,
+        })
+    }
+
+    fn parse_framing_options(&mut self) -> Result<Vec<FramingOption<Raw>>, ParserError> {
+        if self.consume_token(&Token::LParen) {
+            let with_options = self.parse_comma_separated(Parser::parse_framing_option)?;
+            self.expect_token(&Token::RParen)?;
+            Ok(with_options)
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    fn parse_framing_option(&mut self) -> Result<FramingOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[DELIMITER, LENGTH])? {
+            DELIMITER => FramingOptionName::Delimiter,
+            LENGTH => {
+                self.expect_keyword(PREFIX)?;
+                FramingOptionName::LengthPrefix
+            }
+            _ => unreachable!(),
+        };
+        Ok(FramingOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_csv_format_option(&mut self) -> Result<CsvFormatOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[QUOTE, ESCAPE, EMBEDDED])? {
+            QUOTE => CsvFormatOptionName::Quote,
+            ESCAPE => CsvFormatOptionName::Escape,
+            EMBEDDED => {
+                self.expect_keyword(NEWLINES)?;
+                CsvFormatOptionName::EmbeddedNewlines
+            }
+            _ => unreachable!(),
+        };
+        Ok(CsvFormatOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_avro_schema(&mut self) -> Result<AvroSchema<Raw>, ParserError> {
         let avro_schema = if self.parse_keywords(&[CONFLUENT, SCHEMA, REGISTRY]) {
             let csr_connection = self.parse_csr_connection_avro()?;
@@ -1993,9 +2068,23 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_avro_schema_option(&mut self) -> Result<AvroSchemaOption<Raw>, ParserError> {
-        self.expect_keywords(&[CONFLUENT, WIRE, FORMAT])?;
+        let name = match self.expect_one_of_keywords(&[CONFLUENT, UNION])? {
+            CONFLUENT => {
+                self.expect_keywords(&[WIRE, FORMAT])?;
+                AvroSchemaOptionName::ConfluentWireFormat
+            }
+            UNION => match self.expect_one_of_keywords(&[DISCRIMINANT, TYPE])? {
+                DISCRIMINANT => AvroSchemaOptionName::UnionDiscriminant,
+                TYPE => {
+                    self.expect_keyword(NAMES)?;
+                    AvroSchemaOptionName::UnionTypeNames
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
         Ok(AvroSchemaOption {
-            name: AvroSchemaOptionName::ConfluentWireFormat,
+            name,
             value: self.parse_optional_option_value()?,
         })
     }
@@ -2011,9 +2100,18 @@ impl<'a> Parser<'a> {
             let schema = Schema {
                 schema: self.parse_literal_string()?,
             };
+            let with_options = if self.consume_token(&Token::LParen) {
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_protobuf_schema_option)?;
+                self.expect_token(&Token::RParen)?;
+                with_options
+            } else {
+                vec![]
+            };
             Ok(ProtobufSchema::InlineSchema {
                 message_name,
                 schema,
+                with_options,
             })
         } else {
             self.expected(
@@ -2024,6 +2122,24 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_protobuf_schema_option(&mut self) -> Result<ProtobufSchemaOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[WELL, UNPACK])? {
+            WELL => {
+                self.expect_keywords(&[KNOWN, TYPES])?;
+                ProtobufSchemaOptionName::WellKnownTypes
+            }
+            UNPACK => {
+                self.expect_keyword(ANY)?;
+                ProtobufSchemaOptionName::UnpackAny
+            }
+            _ => unreachable!(),
+        };
+        Ok(ProtobufSchemaOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_csr_connection_reference(&mut self) -> Result<CsrConnection<Raw>, ParserError> {
         self.expect_keyword(CONNECTION)?;
         let connection = self.parse_raw_name()?;
@@ -2043,7 +2159,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_csr_config_option(&mut self) -> Result<CsrConfigOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[AVRO, NULL, KEY, VALUE, DOC])? {
+        let name = match self.expect_one_of_keywords(&[AVRO, NULL, KEY, VALUE, DOC, SCHEMA])? {
             AVRO => {
                 let name = match self.expect_one_of_keywords(&[KEY, VALUE])? {
                     KEY => CsrConfigOptionName::AvroKeyFullname,
@@ -2095,6 +2211,10 @@ impl<'a> Parser<'a> {
                     for_schema: DocOnSchema::All,
                 })
             }
+            SCHEMA => {
+                self.expect_keyword(REFERENCES)?;
+                CsrConfigOptionName::SchemaReferences
+            }
             _ => unreachable!(),
         };
         Ok(CsrConfigOption {
@@ -2318,19 +2438,19 @@ impl<'a> Parser<'a> {
     fn parse_default_aws_privatelink(&mut self) -> Result<WithOptionValue<Raw>, ParserError> {
         let _ = self.consume_token(&Token::Eq);
         let connection = self.parse_raw_name()?;
-        let port = if self.consume_token(&Token::LParen) {
-            self.expect_keyword(PORT)?;
-            let pos = self.peek_pos();
-            let Ok(port) = u16::try_from(self.parse_literal_int()?) else {
-                return parser_err!(self, pos, "Could not parse value into port");
-            };
+        let options = if self.consume_token(&Token::LParen) {
+            let options =
+                self.parse_comma_separated(Parser::parse_kafka_broker_aws_privatelink_option)?;
             self.expect_token(&Token::RParen)?;
-            Some(port)
+            options
         } else {
-            None
+            vec![]
         };
         Ok(WithOptionValue::ConnectionAwsPrivatelink(
-            ConnectionDefaultAwsPrivatelink { connection, port },
+            ConnectionDefaultAwsPrivatelink {
+                connection,
+                options,
+            },
         ))
     }
 
@@ -2390,11 +2510,15 @@ impl<'a> Parser<'a> {
     fn parse_kafka_source_config_option(
         &mut self,
     ) -> Result<KafkaSourceConfigOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[GROUP, START, TOPIC])? {
+        let name = match self.expect_one_of_keywords(&[GROUP, MAX, START, TOPIC, VALUE])? {
             GROUP => {
                 self.expect_keywords(&[ID, PREFIX])?;
                 KafkaSourceConfigOptionName::GroupIdPrefix
             }
+            MAX => {
+                self.expect_keywords(&[INGEST, RATE])?;
+                KafkaSourceConfigOptionName::MaxIngestRate
+            }
             START => match self.expect_one_of_keywords(&[OFFSET, TIMESTAMP])? {
                 OFFSET => KafkaSourceConfigOptionName::StartOffset,
                 TIMESTAMP => KafkaSourceConfigOptionName::StartTimestamp,
@@ -2408,6 +2532,10 @@ impl<'a> Parser<'a> {
                     KafkaSourceConfigOptionName::Topic
                 }
             }
+            VALUE => {
+                self.expect_keywords(&[DECRYPTION, KEY])?;
+                KafkaSourceConfigOptionName::ValueDecryptionKey
+            }
             _ => unreachable!(),
         };
         Ok(KafkaSourceConfigOption {
@@ -2421,15 +2549,30 @@ impl<'a> Parser<'a> {
     ) -> Result<KafkaSinkConfigOption<Raw>, ParserError> {
         let name = match self.expect_one_of_keywords(&[
             COMPRESSION,
+            DELIVERY,
+            PARTITION,
             PROGRESS,
             TOPIC,
             LEGACY,
+            TRANSACTION,
             TRANSACTIONAL,
         ])? {
             COMPRESSION => {
                 self.expect_keyword(TYPE)?;
                 KafkaSinkConfigOptionName::CompressionType
             }
+            DELIVERY => {
+                self.expect_keyword(GUARANTEE)?;
+                KafkaSinkConfigOptionName::DeliveryGuarantee
+            }
+            PARTITION => {
+                self.expect_keyword(BY)?;
+                let _ = self.consume_token(&Token::Eq);
+                return Ok(KafkaSinkConfigOption {
+                    name: KafkaSinkConfigOptionName::PartitionBy,
+                    value: Some(WithOptionValue::Expr(self.parse_expr()?)),
+                });
+            }
             PROGRESS => match self.expect_one_of_keywords(&[GROUP, TOPIC])? {
                 GROUP => {
                     self.expect_keywords(&[ID, PREFIX])?;
@@ -2460,6 +2603,10 @@ impl<'a> Parser<'a> {
                 self.expect_keywords(&[ID, PREFIX])?;
                 KafkaSinkConfigOptionName::TransactionalIdPrefix
             }
+            TRANSACTION => {
+                self.expect_keywords(&[METADATA, TOPIC])?;
+                KafkaSinkConfigOptionName::TransactionMetadataTopic
+            }
             LEGACY => {
                 self.expect_keywords(&[IDS])?;
                 KafkaSinkConfigOptionName::LegacyIds
@@ -2819,22 +2966,38 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_source_option_name(&mut self) -> Result<CreateSourceOptionName, ParserError> {
-        let name = match self.expect_one_of_keywords(&[IGNORE, TIMELINE, TIMESTAMP, RETAIN])? {
-            IGNORE => {
-                self.expect_keyword(KEYS)?;
-                CreateSourceOptionName::IgnoreKeys
-            }
-            TIMELINE => CreateSourceOptionName::Timeline,
-            TIMESTAMP => {
-                self.expect_keyword(INTERVAL)?;
-                CreateSourceOptionName::TimestampInterval
-            }
-            RETAIN => {
-                self.expect_keyword(HISTORY)?;
-                CreateSourceOptionName::RetainHistory
-            }
-            _ => unreachable!(),
-        };
+        let name =
+            match self.expect_one_of_keywords(&[IGNORE, TIMELINE, TIMESTAMP, RETAIN, ROCKSDB])? {
+                IGNORE => {
+                    self.expect_keyword(KEYS)?;
+                    CreateSourceOptionName::IgnoreKeys
+                }
+                TIMELINE => CreateSourceOptionName::Timeline,
+                TIMESTAMP => {
+                    self.expect_keyword(INTERVAL)?;
+                    CreateSourceOptionName::TimestampInterval
+                }
+                RETAIN => {
+                    self.expect_keyword(HISTORY)?;
+                    CreateSourceOptionName::RetainHistory
+                }
+                ROCKSDB => match self.expect_one_of_keywords(&[BLOCK, COMPACTION, COMPRESSION])? {
+                    BLOCK => {
+                        self.expect_keywords(&[CACHE, SIZE])?;
+                        CreateSourceOptionName::RocksdbBlockCacheSize
+                    }
+                    COMPACTION => {
+                        self.expect_keyword(STYLE)?;
+                        CreateSourceOptionName::RocksdbCompactionStyle
+                    }
+                    COMPRESSION => {
+                        self.expect_keyword(TYPE)?;
+                        CreateSourceOptionName::RocksdbCompressionType
+                    }
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            };
         Ok(name)
     }
 
@@ -2872,8 +3035,12 @@ impl<'a> Parser<'a> {
                 let array = self.parse_keyword(ARRAY);
                 Format::Json { array }
             }
-            TEXT => Format::Text,
-            BYTES => Format::Bytes,
+            TEXT => Format::Text {
+                with_options: vec![],
+            },
+            BYTES => Format::Bytes {
+                with_options: vec![],
+            },
             _ => unreachable!(),
         };
 
@@ -3132,9 +3299,9 @@ impl<'a> Parser<'a> {
             }
             LOAD => {
                 self.expect_keyword(GENERATOR)?;
-                let generator = match self
-                    .expect_one_of_keywords(&[COUNTER, MARKETING, AUCTION, TPCH, DATUMS, KEY])?
-                {
+                let generator = match self.expect_one_of_keywords(&[
+                    COUNTER, MARKETING, AUCTION, TPCH, DATUMS, KEY, CUSTOM,
+                ])? {
                     COUNTER => LoadGenerator::Counter,
                     AUCTION => LoadGenerator::Auction,
                     TPCH => LoadGenerator::Tpch,
@@ -3144,6 +3311,7 @@ impl<'a> Parser<'a> {
                         self.expect_keyword(VALUE)?;
                         LoadGenerator::KeyValue
                     }
+                    CUSTOM => LoadGenerator::Custom,
                     _ => unreachable!(),
                 };
                 let options = if self.consume_token(&Token::LParen) {
@@ -3259,6 +3427,8 @@ impl<'a> Parser<'a> {
             SEED,
             PARTITIONS,
             BATCH,
+            COLUMNS,
+            ROWS,
         ])? {
             AS => {
                 self.expect_keyword(OF)?;
@@ -3299,6 +3469,11 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(SIZE)?;
                 LoadGeneratorOptionName::BatchSize
             }
+            COLUMNS => LoadGeneratorOptionName::Columns,
+            ROWS => {
+                self.expect_keywords(&[PER, SECOND])?;
+                LoadGeneratorOptionName::RowsPerSecond
+            }
             _ => unreachable!(),
         };
 
@@ -3442,10 +3617,17 @@ impl<'a> Parser<'a> {
     ) -> Result<MaterializedViewOptionName, ParserError> {
         let option = self.expect_one_of_keywords(&[ASSERT, RETAIN, REFRESH])?;
         let name = match option {
-            ASSERT => {
-                self.expect_keywords(&[NOT, NULL])?;
-                MaterializedViewOptionName::AssertNotNull
-            }
+            ASSERT => match self.expect_one_of_keywords(&[APPEND, NOT])? {
+                APPEND => {
+                    self.expect_keyword(ONLY)?;
+                    MaterializedViewOptionName::AssertAppendOnly
+                }
+                NOT => {
+                    self.expect_keyword(NULL)?;
+                    MaterializedViewOptionName::AssertNotNull
+                }
+                _ => unreachable!(),
+            },
             RETAIN => {
                 self.expect_keyword(HISTORY)?;
                 MaterializedViewOptionName::RetainHistory
@@ -3640,11 +3822,11 @@ impl<'a> Parser<'a> {
         self.expect_keyword(ROLE)?;
         let name = self.parse_identifier()?;
         let _ = self.parse_keyword(WITH);
-        let options = self.parse_role_attributes();
+        let options = self.parse_role_attributes()?;
         Ok(Statement::CreateRole(CreateRoleStatement { name, options }))
     }
 
-    fn parse_role_attributes(&mut self) -> Vec<RoleAttribute> {
+    fn parse_role_attributes(&mut self) -> Result<Vec<RoleAttribute>, ParserError> {
         let mut options = vec![];
         loop {
             match self.parse_one_of_keywords(&[
@@ -3660,6 +3842,7 @@ impl<'a> Parser<'a> {
                 NOCREATEDB,
                 CREATEROLE,
                 NOCREATEROLE,
+                PASSWORD,
             ]) {
                 None => break,
                 Some(SUPERUSER) => options.push(RoleAttribute::SuperUser),
@@ -3674,10 +3857,18 @@ impl<'a> Parser<'a> {
                 Some(NOCREATEDB) => options.push(RoleAttribute::NoCreateDB),
                 Some(CREATEROLE) => options.push(RoleAttribute::CreateRole),
                 Some(NOCREATEROLE) => options.push(RoleAttribute::NoCreateRole),
+                Some(PASSWORD) => {
+                    let password = if self.parse_keyword(NULL) {
+                        None
+                    } else {
+                        Some(self.parse_literal_string()?)
+                    };
+                    options.push(RoleAttribute::Password(password))
+                }
                 Some(_) => unreachable!(),
             }
         }
-        options
+        Ok(options)
     }
 
     fn parse_create_secret(&mut self) -> Result<Statement<Raw>, ParserError> {
@@ -3784,6 +3975,7 @@ impl<'a> Parser<'a> {
 
     fn parse_cluster_option_name(&mut self) -> Result<ClusterOptionName, ParserError> {
         let option = self.expect_one_of_keywords(&[
+            AUTOSCALE,
             AVAILABILITY,
             DISK,
             INTROSPECTION,
@@ -3794,6 +3986,15 @@ impl<'a> Parser<'a> {
             SCHEDULE,
         ])?;
         let name = match option {
+            AUTOSCALE => {
+                let bound = self.expect_one_of_keywords(&[MAX, MIN])?;
+                self.expect_keyword(SIZE)?;
+                match bound {
+                    MAX => ClusterOptionName::AutoscaleMaxSize,
+                    MIN => ClusterOptionName::AutoscaleMinSize,
+                    _ => unreachable!(),
+                }
+            }
             AVAILABILITY => {
                 self.expect_keyword(ZONES)?;
                 ClusterOptionName::AvailabilityZones
@@ -3854,9 +4055,21 @@ impl<'a> Parser<'a> {
 
     fn parse_cluster_option_schedule(&mut self) -> Result<ClusterOption<Raw>, ParserError> {
         let _ = self.consume_token(&Token::Eq);
-        let kw = self.expect_one_of_keywords(&[MANUAL, ON])?;
+        let kw = self.expect_one_of_keywords(&[MANUAL, OFF, ON])?;
         let value = match kw {
             MANUAL => ClusterScheduleOptionValue::Manual,
+            OFF => {
+                self.expect_keyword(DURING)?;
+                // Parse either a single window or a parenthesized list of windows.
+                let windows = if self.consume_token(&Token::LParen) {
+                    let windows = self.parse_comma_separated(Parser::parse_literal_string)?;
+                    self.expect_token(&Token::RParen)?;
+                    windows
+                } else {
+                    vec![self.parse_literal_string()?]
+                };
+                ClusterScheduleOptionValue::OffDuring { windows }
+            }
             ON => {
                 self.expect_keyword(REFRESH)?;
                 // Parse optional `(REHYDRATION TIME ESTIMATE ...)`
@@ -3941,6 +4154,13 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_cluster_alter_option(&mut self) -> Result<ClusterAlterOption<Raw>, ParserError> {
+        Ok(ClusterAlterOption {
+            name: self.parse_cluster_alter_option_name()?,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_create_cluster_replica(&mut self) -> Result<Statement<Raw>, ParserError> {
         self.next_token();
         let of_cluster = self.parse_identifier()?;
@@ -3994,18 +4214,34 @@ impl<'a> Parser<'a> {
                     KEY => SourceIncludeMetadata::Key {
                         alias: parser.parse_alias()?,
                     },
-                    TIMESTAMP => SourceIncludeMetadata::Timestamp {
-                        alias: parser.parse_alias()?,
-                    },
+                    TIMESTAMP => {
+                        if parser.parse_keyword(TYPE) {
+                            SourceIncludeMetadata::TimestampType {
+                                alias: parser.parse_alias()?,
+                            }
+                        } else {
+                            SourceIncludeMetadata::Timestamp {
+                                alias: parser.parse_alias()?,
+                            }
+                        }
+                    }
                     PARTITION => SourceIncludeMetadata::Partition {
                         alias: parser.parse_alias()?,
                     },
                     OFFSET => SourceIncludeMetadata::Offset {
                         alias: parser.parse_alias()?,
                     },
-                    HEADERS => SourceIncludeMetadata::Headers {
-                        alias: parser.parse_alias()?,
-                    },
+                    HEADERS => {
+                        if parser.parse_keyword(MAP) {
+                            SourceIncludeMetadata::HeadersMap {
+                                alias: parser.parse_alias()?,
+                            }
+                        } else {
+                            SourceIncludeMetadata::Headers {
+                                alias: parser.parse_alias()?,
+                            }
+                        }
+                    }
                     HEADER => {
                         let key: String = parser.parse_literal_string()?;
                         parser.expect_keyword(AS)?;
@@ -4573,26 +4809,7 @@ impl<'a> Parser<'a> {
                     _ => unreachable!(),
                 }
             }
-            ObjectType::Database => {
-                let if_exists = self
-                    .parse_if_exists()
-                    .map_parser_err(StatementKind::AlterOwner)?;
-                let name = UnresolvedObjectName::Database(
-                    self.parse_database_name()
-                        .map_parser_err(StatementKind::AlterOwner)?,
-                );
-                self.expect_keywords(&[OWNER, TO])
-                    .map_parser_err(StatementKind::AlterOwner)?;
-                let new_owner = self
-                    .parse_identifier()
-                    .map_parser_err(StatementKind::AlterOwner)?;
-                Ok(Statement::AlterOwner(AlterOwnerStatement {
-                    object_type,
-                    if_exists,
-                    name,
-                    new_owner,
-                }))
-            }
+            ObjectType::Database => self.parse_alter_database(object_type),
             ObjectType::Schema => self.parse_alter_schema(object_type),
             ObjectType::Func | ObjectType::Subsource => parser_err!(
                 self,
@@ -4663,10 +4880,25 @@ impl<'a> Parser<'a> {
                     .map_parser_err(StatementKind::AlterCluster)?;
                 self.expect_token(&Token::RParen)
                     .map_parser_err(StatementKind::AlterCluster)?;
+                let with_options = if self.parse_keyword(WITH) {
+                    self.expect_token(&Token::LParen)
+                        .map_parser_err(StatementKind::AlterCluster)?;
+                    let with_options = self
+                        .parse_comma_separated(Parser::parse_cluster_alter_option)
+                        .map_parser_err(StatementKind::AlterCluster)?;
+                    self.expect_token(&Token::RParen)
+                        .map_parser_err(StatementKind::AlterCluster)?;
+                    with_options
+                } else {
+                    vec![]
+                };
                 Ok(Statement::AlterCluster(AlterClusterStatement {
                     if_exists,
                     name,
-                    action: AlterClusterAction::SetOptions(options),
+                    action: AlterClusterAction::SetOptions {
+                        options,
+                        with_options,
+                    },
                 }))
             }
             SWAP => {
@@ -4692,7 +4924,7 @@ impl<'a> Parser<'a> {
 
         Ok(
             match self
-                .expect_one_of_keywords(&[ADD, DROP, RESET, SET, RENAME, OWNER])
+                .expect_one_of_keywords(&[ADD, DROP, RESET, SET, RENAME, OWNER, SWAP])
                 .map_no_statement_parser_err()?
             {
                 ADD => {
@@ -4790,6 +5022,18 @@ impl<'a> Parser<'a> {
                         action: AlterSourceAction::SetOptions(set_options),
                     })
                 }
+                SWAP => {
+                    self.expect_keyword(CONNECTION)
+                        .map_parser_err(StatementKind::AlterSource)?;
+                    let connection = self
+                        .parse_raw_name()
+                        .map_parser_err(StatementKind::AlterSource)?;
+                    Statement::AlterSource(AlterSourceStatement {
+                        source_name,
+                        if_exists,
+                        action: AlterSourceAction::SwapConnection { connection },
+                    })
+                }
                 RENAME => {
                     self.expect_keyword(TO)
                         .map_parser_err(StatementKind::AlterObjectRename)?;
@@ -5211,6 +5455,65 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_alter_database(
+        &mut self,
+        object_type: ObjectType,
+    ) -> Result<Statement<Raw>, ParserStatementError> {
+        let if_exists = self.parse_if_exists().map_no_statement_parser_err()?;
+        let name = self.parse_database_name().map_no_statement_parser_err()?;
+        let action = self
+            .expect_one_of_keywords(&[OWNER, RESET, SET])
+            .map_no_statement_parser_err()?;
+        match action {
+            OWNER => {
+                self.expect_keyword(TO)
+                    .map_parser_err(StatementKind::AlterOwner)?;
+                let new_owner = self
+                    .parse_identifier()
+                    .map_parser_err(StatementKind::AlterOwner)?;
+                Ok(Statement::AlterOwner(AlterOwnerStatement {
+                    object_type,
+                    if_exists,
+                    name: UnresolvedObjectName::Database(name),
+                    new_owner,
+                }))
+            }
+            SET | RESET if if_exists => parser_err!(
+                self,
+                self.peek_prev_pos(),
+                format!("IF EXISTS is not supported for ALTER DATABASE ... {action}")
+            )
+            .map_parser_err(StatementKind::AlterDatabase),
+            SET => {
+                let var_name = self
+                    .parse_identifier()
+                    .map_parser_err(StatementKind::AlterDatabase)?;
+                self.expect_keyword_or_token(TO, &Token::Eq)
+                    .map_parser_err(StatementKind::AlterDatabase)?;
+                let value = self
+                    .parse_set_variable_to()
+                    .map_parser_err(StatementKind::AlterDatabase)?;
+                Ok(Statement::AlterDatabase(AlterDatabaseStatement {
+                    name,
+                    variable: SetRoleVar::Set {
+                        name: var_name,
+                        value,
+                    },
+                }))
+            }
+            RESET => {
+                let var_name = self
+                    .parse_identifier()
+                    .map_parser_err(StatementKind::AlterDatabase)?;
+                Ok(Statement::AlterDatabase(AlterDatabaseStatement {
+                    name,
+                    variable: SetRoleVar::Reset { name: var_name },
+                }))
+            }
+            _ => unreachable!(),
+        }
+    }
+
     fn parse_alter_role(&mut self) -> Result<Statement<Raw>, ParserError> {
         let name = self.parse_identifier()?;
 
@@ -5229,7 +5532,7 @@ impl<'a> Parser<'a> {
             }
             Some(WITH) | None => {
                 let _ = self.parse_keyword(WITH);
-                let attrs = self.parse_role_attributes();
+                let attrs = self.parse_role_attributes()?;
                 AlterRoleOption::Attributes(attrs)
             }
             Some(k) => unreachable!("unmatched keyword: {k}"),
@@ -5546,9 +5849,9 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_copy_option(&mut self) -> Result<CopyOption<Raw>, ParserError> {
-        let name = match self
-            .expect_one_of_keywords(&[FORMAT, DELIMITER, NULL, ESCAPE, QUOTE, HEADER, AWS, MAX])?
-        {
+        let name = match self.expect_one_of_keywords(&[
+            FORMAT, DELIMITER, NULL, ESCAPE, QUOTE, HEADER, AWS, MAX, MANIFEST,
+        ])? {
             FORMAT => CopyOptionName::Format,
             DELIMITER => CopyOptionName::Delimiter,
             NULL => CopyOptionName::Null,
@@ -5566,6 +5869,7 @@ impl<'a> Parser<'a> {
                 self.expect_keywords(&[FILE, SIZE])?;
                 CopyOptionName::MaxFileSize
             }
+            MANIFEST => CopyOptionName::Manifest,
             _ => unreachable!(),
         };
         Ok(CopyOption {
@@ -6239,8 +6543,17 @@ impl<'a> Parser<'a> {
                         options,
                         ctes: parser.parse_comma_separated(Parser::parse_cte_mut_rec)?,
                     })
+                } else if parser.peek_keyword(RECURSIVE)
+                    && !matches!(
+                        parser.peek_nth_token(1),
+                        Some(Token::Keyword(AS)) | Some(Token::LParen)
+                    )
+                {
+                    // `RECURSIVE` followed by `AS` or `(` is a CTE named
+                    // "recursive".
+                    parser.expect_keyword(RECURSIVE)?;
+                    CteBlock::Recursive(parser.parse_comma_separated(Parser::parse_cte)?)
                 } else {
-                    // TODO: optional RECURSIVE
                     CteBlock::Simple(parser.parse_comma_separated(Parser::parse_cte)?)
                 }
             } else {
@@ -6870,6 +7183,8 @@ impl<'a> Parser<'a> {
             self.parse_show_privileges()
         } else if self.parse_keywords(&[DEFAULT, PRIVILEGES]) {
             self.parse_show_default_privileges()
+        } else if self.parse_keywords(&[EFFECTIVE, PRIVILEGES]) {
+            self.parse_show_effective_privileges()
         } else if self.parse_keyword(ROLE) {
             self.expect_keyword(MEMBERSHIP)?;
             let role = if self.parse_keyword(FOR) {
@@ -6914,6 +7229,15 @@ impl<'a> Parser<'a> {
                     connection_name: self.parse_raw_name()?,
                 },
             ))
+        } else if self.parse_keywords(&[CREATE, ALL, OBJECTS, IN]) {
+            let in_container = match self.expect_one_of_keywords(&[SCHEMA, DATABASE])? {
+                SCHEMA => ShowCreateAllObjectsIn::Schema(self.parse_schema_name()?),
+                DATABASE => ShowCreateAllObjectsIn::Database(self.parse_database_name()?),
+                _ => unreachable!(),
+            };
+            Ok(ShowStatement::ShowCreateAllObjects(
+                ShowCreateAllObjectsStatement { in_container },
+            ))
         } else {
             let variable = if self.parse_keywords(&[TRANSACTION, ISOLATION, LEVEL]) {
                 ident!("transaction_isolation")
@@ -6973,6 +7297,21 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_show_effective_privileges(&mut self) -> Result<ShowStatement<Raw>, ParserError> {
+        self.expect_keywords(&[FOR, ROLE])?;
+        let role = self.parse_identifier()?;
+        self.expect_keyword(ON)?;
+        let object_type = self.expect_grant_revoke_object_type("SHOW EFFECTIVE PRIVILEGES")?;
+        let name = self.parse_object_name(object_type)?;
+        Ok(ShowStatement::ShowEffectivePrivileges(
+            ShowEffectivePrivilegesStatement {
+                role,
+                object_type,
+                name,
+            },
+        ))
+    }
+
     fn parse_show_default_privileges(&mut self) -> Result<ShowStatement<Raw>, ParserError> {
         let object_type = if self.parse_keyword(ON) {
             Some(self.expect_plural_object_type_for_privileges()?)
@@ -7036,6 +7375,19 @@ impl<'a> Parser<'a> {
                             _ => unreachable!(),
                         }
                     }
+                    Some(ASOF) => {
+                        let _ = self.next_token();
+                        let left = self.parse_keyword(LEFT);
+                        if left {
+                            let _ = self.parse_keyword(OUTER);
+                        }
+                        self.expect_keyword(JOIN)?;
+                        if left {
+                            JoinOperator::LeftAsOf
+                        } else {
+                            JoinOperator::AsOf
+                        }
+                    }
                     Some(OUTER) => {
                         return self.expected(
                             self.peek_pos(),
@@ -7422,6 +7774,7 @@ impl<'a> Parser<'a> {
         self.expect_keyword(TRANSACTION)?;
         Ok(Statement::StartTransaction(StartTransactionStatement {
             modes: self.parse_transaction_modes(false)?,
+            as_of: self.parse_optional_as_of()?,
         }))
     }
 
@@ -7429,6 +7782,7 @@ impl<'a> Parser<'a> {
         let _ = self.parse_one_of_keywords(&[TRANSACTION, WORK]);
         Ok(Statement::StartTransaction(StartTransactionStatement {
             modes: self.parse_transaction_modes(false)?,
+            as_of: self.parse_optional_as_of()?,
         }))
     }
 
@@ -7573,6 +7927,9 @@ impl<'a> Parser<'a> {
         } else if self.parse_keywords(&[FILTER, PUSHDOWN]) {
             self.parse_explain_pushdown()
                 .map_parser_err(StatementKind::ExplainPushdown)
+        } else if self.parse_keyword(ANALYZE) {
+            self.parse_explain_analyze()
+                .map_parser_err(StatementKind::ExplainAnalyze)
         } else if self.peek_keyword(KEY) || self.peek_keyword(VALUE) {
             self.parse_explain_schema()
                 .map_parser_err(StatementKind::ExplainSinkSchema)
@@ -7763,6 +8120,18 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    /// Parse an `EXPLAIN ANALYZE` statement, assuming that the `EXPLAIN
+    /// ANALYZE` tokens have already been consumed.
+    fn parse_explain_analyze(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(FOR)?;
+
+        let explainee = self.parse_explainee()?;
+
+        Ok(Statement::ExplainAnalyze(ExplainAnalyzeStatement {
+            explainee,
+        }))
+    }
+
     /// Parse an `EXPLAIN TIMESTAMP` statement, assuming that the `EXPLAIN
     /// TIMESTAMP` tokens have already been consumed.
     fn parse_explain_timestamp(&mut self) -> Result<Statement<Raw>, ParserError> {
@@ -256,6 +256,9 @@ fn doc_query<T: AstInfo>(v: &Query<T>) -> RcDoc {
     if !v.ctes.is_empty() {
         match &v.ctes {
             CteBlock::Simple(ctes) => docs.push(title_comma_separate("WITH", doc_cte, ctes)),
+            CteBlock::Recursive(ctes) => {
+                docs.push(title_comma_separate("WITH RECURSIVE", doc_cte, ctes))
+            }
             CteBlock::MutuallyRecursive(mutrec) => {
                 let mut doc = RcDoc::text("WITH MUTUALLY RECURSIVE");
                 if !mutrec.options.is_empty() {
@@ -378,6 +381,8 @@ fn doc_join<T: AstInfo>(v: &Join<T>) -> RcDoc {
         JoinOperator::FullOuter(constraint) => (constraint, "FULL JOIN"),
         JoinOperator::LeftOuter(constraint) => (constraint, "LEFT JOIN"),
         JoinOperator::RightOuter(constraint) => (constraint, "RIGHT JOIN"),
+        JoinOperator::AsOf(constraint) => (constraint, "ASOF JOIN"),
+        JoinOperator::LeftAsOf(constraint) => (constraint, "ASOF LEFT JOIN"),
         _ => return doc_display(v, "join operator"),
     };
     let constraint = match constraint {
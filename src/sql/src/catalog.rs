@@ -405,6 +405,9 @@ pub trait CatalogDatabase {
 
     /// Returns the privileges associated with the database.
     fn privileges(&self) -> &PrivilegeMap;
+
+    /// Returns all variables that this database has a default value stored for.
+    fn vars(&self) -> &BTreeMap<String, OwnedVarInput>;
 }
 
 /// A schema in a [`SessionCatalog`].
@@ -436,6 +439,11 @@ pub trait CatalogSchema {
 pub struct RoleAttributes {
     /// Indicates whether the role has inheritance of privileges.
     pub inherit: bool,
+    /// The SCRAM-SHA-256 verifier of the role's password, if the role has a
+    /// password. Clients must prove knowledge of the password to log in as a
+    /// role with a password.
+    #[serde(skip)]
+    pub scram_verifier: Option<String>,
     // Force use of constructor.
     _private: (),
 }
@@ -445,6 +453,7 @@ impl RoleAttributes {
     pub const fn new() -> RoleAttributes {
         RoleAttributes {
             inherit: true,
+            scram_verifier: None,
             _private: (),
         }
     }
@@ -462,16 +471,22 @@ impl RoleAttributes {
 }
 
 impl From<PlannedRoleAttributes> for RoleAttributes {
-    fn from(PlannedRoleAttributes { inherit }: PlannedRoleAttributes) -> RoleAttributes {
+    fn from(
+        PlannedRoleAttributes {
+            inherit,
+            scram_verifier,
+        }: PlannedRoleAttributes,
+    ) -> RoleAttributes {
         let default_attributes = RoleAttributes::new();
         RoleAttributes {
             inherit: inherit.unwrap_or(default_attributes.inherit),
+            scram_verifier: scram_verifier.unwrap_or(default_attributes.scram_verifier),
             _private: (),
         }
     }
 }
 
-/// Default variable values for a [`CatalogRole`].
+/// Default variable values for a [`CatalogRole`] or a [`CatalogDatabase`].
 #[derive(Default, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct RoleVars {
     /// Map of variable names to their value.
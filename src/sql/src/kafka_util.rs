@@ -16,10 +16,10 @@ use mz_kafka_util::client::DEFAULT_TOPIC_METADATA_REFRESH_INTERVAL;
 use mz_ore::task;
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
-    KafkaSinkConfigOption, KafkaSinkConfigOptionName, KafkaSourceConfigOption,
+    Expr, KafkaSinkConfigOption, KafkaSinkConfigOptionName, KafkaSourceConfigOption,
     KafkaSourceConfigOptionName,
 };
-use mz_storage_types::sinks::KafkaSinkCompressionType;
+use mz_storage_types::sinks::{KafkaSinkCompressionType, KafkaSinkDeliveryGuarantee};
 use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
 use rdkafka::{Offset, TopicPartitionList};
 use tokio::time::Duration;
@@ -27,12 +27,13 @@ use tokio::time::Duration;
 use crate::ast::Value;
 use crate::names::Aug;
 use crate::normalize::generate_extracted_config;
-use crate::plan::with_options::{ImpliedValue, TryFromValue};
+use crate::plan::with_options::{self, ImpliedValue, TryFromValue};
 use crate::plan::PlanError;
 
 generate_extracted_config!(
     KafkaSourceConfigOption,
     (GroupIdPrefix, String),
+    (MaxIngestRate, u64),
     (Topic, String),
     (
         TopicMetadataRefreshInterval,
@@ -40,7 +41,8 @@ generate_extracted_config!(
         Default(DEFAULT_TOPIC_METADATA_REFRESH_INTERVAL)
     ),
     (StartTimestamp, i64),
-    (StartOffset, Vec<i64>)
+    (StartOffset, Vec<i64>),
+    (ValueDecryptionKey, with_options::Secret)
 );
 
 generate_extracted_config!(
@@ -50,6 +52,12 @@ generate_extracted_config!(
         KafkaSinkCompressionType,
         Default(KafkaSinkCompressionType::None)
     ),
+    (
+        DeliveryGuarantee,
+        KafkaSinkDeliveryGuarantee,
+        Default(KafkaSinkDeliveryGuarantee::ExactlyOnce)
+    ),
+    (PartitionBy, Expr<Aug>),
     (ProgressGroupIdPrefix, String),
     (TransactionalIdPrefix, String),
     (LegacyIds, bool),
@@ -57,7 +65,8 @@ generate_extracted_config!(
     (TopicConfig, BTreeMap<String, String>),
     (TopicPartitionCount, i32),
     (TopicReplicationFactor, i32),
-    (ProgressTopicReplicationFactor, i32)
+    (ProgressTopicReplicationFactor, i32),
+    (TransactionMetadataTopic, String)
 );
 
 impl TryFromValue<Value> for KafkaSinkCompressionType {
@@ -88,6 +97,31 @@ impl ImpliedValue for KafkaSinkCompressionType {
     }
 }
 
+impl TryFromValue<Value> for KafkaSinkDeliveryGuarantee {
+    fn try_from_value(v: Value) -> Result<Self, PlanError> {
+        match v {
+            Value::String(v) => match v.to_lowercase().as_str() {
+                "exactly-once" => Ok(KafkaSinkDeliveryGuarantee::ExactlyOnce),
+                "at-least-once" => Ok(KafkaSinkDeliveryGuarantee::AtLeastOnce),
+                // The caller will add context, resulting in an error like
+                // "invalid DELIVERY GUARANTEE: <bad-delivery-guarantee>".
+                _ => sql_bail!("{}", v),
+            },
+            _ => sql_bail!("delivery guarantee must be a string"),
+        }
+    }
+
+    fn name() -> String {
+        "Kafka sink delivery guarantee".to_string()
+    }
+}
+
+impl ImpliedValue for KafkaSinkDeliveryGuarantee {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a delivery guarantee value")
+    }
+}
+
 /// Returns start offsets for the partitions of `topic` and the provided
 /// `START TIMESTAMP` option.
 ///
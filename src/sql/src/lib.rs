@@ -126,6 +126,9 @@ pub const DEFAULT_SCHEMA: &str = "public";
 /// The number of concurrent requests we allow at once for webhook sources.
 pub const WEBHOOK_CONCURRENCY_LIMIT: usize = 500;
 
+/// The maximum number of rows a single request to a webhook source may append.
+pub const WEBHOOK_ROWS_PER_REQUEST_LIMIT: usize = 10_000;
+
 pub mod ast;
 pub mod catalog;
 pub mod func;
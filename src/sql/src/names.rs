@@ -1502,6 +1502,28 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
                 }
                 CteBlock::Simple(result_ctes)
             }
+            CteBlock::Recursive(ctes) => {
+                let mut result_ctes = Vec::<Cte<Aug>>::new();
+
+                let initial_id = self.ctes.len();
+
+                // Unlike simple CTEs, each recursive CTE is in scope within its
+                // own definition.
+                for (offset, cte) in ctes.into_iter().enumerate() {
+                    let cte_name = normalize::ident(cte.alias.name.clone());
+                    let local_id = LocalId::new(u64::cast_from(initial_id + offset));
+
+                    let shadowed_id = self.ctes.insert(cte_name.clone(), local_id);
+                    shadowed_cte_ids.push((cte_name, shadowed_id));
+
+                    result_ctes.push(Cte {
+                        alias: cte.alias,
+                        id: local_id,
+                        query: self.fold_query(cte.query),
+                    });
+                }
+                CteBlock::Recursive(result_ctes)
+            }
             CteBlock::MutuallyRecursive(MutRecBlock { options, ctes }) => {
                 let mut result_ctes = Vec::<CteMutRec<Aug>>::new();
 
@@ -1826,6 +1848,7 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
             RetainHistoryFor(value) => RetainHistoryFor(self.fold_value(value)),
             Refresh(refresh) => Refresh(self.fold_refresh_option_value(refresh)),
             ClusterScheduleOptionValue(value) => ClusterScheduleOptionValue(value),
+            Expr(expr) => Expr(self.fold_expr(expr)),
         }
     }
 
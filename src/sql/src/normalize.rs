@@ -201,7 +201,7 @@ pub fn create_statement(
         fn visit_query_mut(&mut self, query: &'ast mut Query<Aug>) {
             let n = self.ctes.len();
             match &query.ctes {
-                CteBlock::Simple(ctes) => {
+                CteBlock::Simple(ctes) | CteBlock::Recursive(ctes) => {
                     for cte in ctes.iter() {
                         self.ctes.push(cte.alias.name.clone());
                     }
@@ -63,10 +63,11 @@ use crate::catalog::{
     RoleAttributes,
 };
 use crate::names::{
-    Aug, CommentObjectId, FullItemName, ObjectId, QualifiedItemName, ResolvedDatabaseSpecifier,
-    ResolvedIds, SchemaSpecifier, SystemObjectId,
+    Aug, CommentObjectId, DatabaseId, FullItemName, ObjectId, QualifiedItemName,
+    ResolvedDatabaseSpecifier, ResolvedIds, SchemaSpecifier, SystemObjectId,
 };
 
+pub(crate) mod cron;
 pub(crate) mod error;
 pub(crate) mod explain;
 pub(crate) mod expr;
@@ -85,6 +86,7 @@ pub(crate) mod with_options;
 
 use crate::plan;
 use crate::plan::with_options::OptionalDuration;
+pub use cron::CronSchedule;
 pub use error::PlanError;
 pub use explain::normalize_subqueries;
 pub use expr::{
@@ -137,6 +139,7 @@ pub enum Plan {
     InspectShard(InspectShardPlan),
     SetVariable(SetVariablePlan),
     ResetVariable(ResetVariablePlan),
+    ResetAllVariables,
     SetTransaction(SetTransactionPlan),
     StartTransaction(StartTransactionPlan),
     CommitTransaction(CommitTransactionPlan),
@@ -168,6 +171,7 @@ pub enum Plan {
     AlterSystemReset(AlterSystemResetPlan),
     AlterSystemResetAll(AlterSystemResetAllPlan),
     AlterRole(AlterRolePlan),
+    AlterDatabase(AlterDatabasePlan),
     AlterOwner(AlterOwnerPlan),
     Declare(DeclarePlan),
     Fetch(FetchPlan),
@@ -211,6 +215,7 @@ impl Plan {
                 PlanKind::AlterNoop,
             ],
             StatementKind::AlterRole => &[PlanKind::AlterRole],
+            StatementKind::AlterDatabase => &[PlanKind::AlterDatabase],
             StatementKind::AlterSecret => &[PlanKind::AlterNoop, PlanKind::AlterSecret],
             StatementKind::AlterSetCluster => &[PlanKind::AlterNoop, PlanKind::AlterSetCluster],
             StatementKind::AlterSink => &[PlanKind::AlterNoop, PlanKind::AlterSink],
@@ -259,6 +264,7 @@ impl Plan {
             StatementKind::Execute => &[PlanKind::Execute],
             StatementKind::ExplainPlan => &[PlanKind::ExplainPlan],
             StatementKind::ExplainPushdown => &[PlanKind::ExplainPushdown],
+            StatementKind::ExplainAnalyze => &[PlanKind::Select],
             StatementKind::ExplainTimestamp => &[PlanKind::ExplainTimestamp],
             StatementKind::ExplainSinkSchema => &[PlanKind::ExplainSinkSchema],
             StatementKind::Fetch => &[PlanKind::Fetch],
@@ -268,7 +274,7 @@ impl Plan {
             StatementKind::Prepare => &[PlanKind::Prepare],
             StatementKind::Raise => &[PlanKind::Raise],
             StatementKind::ReassignOwned => &[PlanKind::ReassignOwned],
-            StatementKind::ResetVariable => &[PlanKind::ResetVariable],
+            StatementKind::ResetVariable => &[PlanKind::ResetVariable, PlanKind::ResetAllVariables],
             StatementKind::RevokePrivileges => &[PlanKind::RevokePrivileges],
             StatementKind::RevokeRole => &[PlanKind::RevokeRole],
             StatementKind::Rollback => &[PlanKind::AbortTransaction],
@@ -338,6 +344,7 @@ impl Plan {
             Plan::InspectShard(_) => "inspect shard",
             Plan::SetVariable(_) => "set variable",
             Plan::ResetVariable(_) => "reset variable",
+            Plan::ResetAllVariables => "reset all variables",
             Plan::SetTransaction(_) => "set transaction",
             Plan::StartTransaction(_) => "start transaction",
             Plan::CommitTransaction(_) => "commit",
@@ -385,6 +392,7 @@ impl Plan {
             Plan::AlterSystemReset(_) => "alter system",
             Plan::AlterSystemResetAll(_) => "alter system",
             Plan::AlterRole(_) => "alter role",
+            Plan::AlterDatabase(_) => "alter database",
             Plan::AlterOwner(plan) => match plan.object_type {
                 ObjectType::Table => "alter table owner",
                 ObjectType::View => "alter view owner",
@@ -431,6 +439,10 @@ impl Plan {
 pub struct StartTransactionPlan {
     pub access: Option<TransactionAccessMode>,
     pub isolation_level: Option<TransactionIsolationLevel>,
+    /// The timestamp that the reads of the transaction are pinned to, if any.
+    ///
+    /// The expression may have any type.
+    pub as_of: Option<MirScalarExpr>,
 }
 
 #[derive(Debug)]
@@ -504,6 +516,7 @@ pub struct CreateClusterManagedPlan {
     pub disk: bool,
     pub optimizer_feature_overrides: OptimizerFeatureOverrides,
     pub schedule: ClusterSchedule,
+    pub autoscale: Option<ClusterAutoscale>,
 }
 
 #[derive(Debug)]
@@ -555,6 +568,9 @@ pub enum ClusterSchedule {
     /// `rehydration_time_estimate` determines how much time before a refresh to turn the
     /// cluster On, so that it can rehydrate already before the refresh time.
     Refresh { rehydration_time_estimate: Duration },
+    /// The cluster will be Off during the minutes matched by any of the `windows`, and On
+    /// otherwise.
+    OffDuring { windows: Vec<CronSchedule> },
 }
 
 impl Default for ClusterSchedule {
@@ -564,6 +580,15 @@ impl Default for ClusterSchedule {
     }
 }
 
+/// The bounds within which the cluster autoscaler may resize a managed cluster.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord)]
+pub struct ClusterAutoscale {
+    /// The smallest size the autoscaler may shrink the cluster to.
+    pub min_size: String,
+    /// The largest size the autoscaler may grow the cluster to.
+    pub max_size: String,
+}
+
 #[derive(Debug)]
 pub struct CreateSourcePlan {
     pub name: QualifiedItemName,
@@ -812,6 +837,8 @@ pub struct CopyToPlan {
     pub connection_id: GlobalId,
     pub format: S3SinkFormat,
     pub max_file_size: u64,
+    /// Whether to write a manifest listing the uploaded files.
+    pub manifest: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -1019,6 +1046,9 @@ pub enum AlterSourceAction {
         subsources: Vec<CreateSourcePlanBundle>,
         options: Vec<AlterSourceAddSubsourceOption<Aug>>,
     },
+    /// Points the source at a different connection while retaining its
+    /// progress.
+    SwapConnection { connection_id: GlobalId },
 }
 
 #[derive(Debug)]
@@ -1040,6 +1070,20 @@ pub struct AlterClusterPlan {
     pub id: ClusterId,
     pub name: String,
     pub options: PlanClusterOption,
+    pub strategy: AlterClusterPlanStrategy,
+}
+
+/// How the replicas of a managed cluster are replaced when an `ALTER CLUSTER`
+/// changes their configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlterClusterPlanStrategy {
+    /// Drop the existing replicas and create their replacements at once.
+    #[default]
+    None,
+    /// Create the replacement replicas next to the existing ones, and only
+    /// retire the existing replicas once the replacements have hydrated and
+    /// caught up.
+    Graceful,
 }
 
 #[derive(Debug)]
@@ -1125,6 +1169,13 @@ pub struct AlterRolePlan {
     pub option: PlannedAlterRoleOption,
 }
 
+#[derive(Debug)]
+pub struct AlterDatabasePlan {
+    pub id: DatabaseId,
+    pub name: String,
+    pub variable: PlannedRoleVariable,
+}
+
 #[derive(Debug)]
 pub struct AlterOwnerPlan {
     pub id: ObjectId,
@@ -1258,10 +1309,22 @@ pub struct Table {
     pub create_sql: String,
     pub desc: RelationDesc,
     pub defaults: Vec<Expr<Aug>>,
+    pub checks: Vec<TableCheck>,
     pub temporary: bool,
     pub compaction_window: Option<CompactionWindow>,
 }
 
+/// A `CHECK` constraint on a table, enforced whenever rows are written to the
+/// table.
+#[derive(Clone, Debug)]
+pub struct TableCheck {
+    /// The name of the constraint.
+    pub name: String,
+    /// The constraint's condition, over the columns of the table. A row
+    /// violates the constraint if the condition evaluates to `false`.
+    pub expr: MirScalarExpr,
+}
+
 #[derive(Clone, Debug)]
 pub struct Source {
     pub create_sql: String,
@@ -1434,6 +1497,8 @@ pub struct MaterializedView {
     pub column_names: Vec<ColumnName>,
     pub cluster_id: ClusterId,
     pub non_null_assertions: Vec<usize>,
+    /// Whether the materialized view is asserted to never retract rows.
+    pub append_only: bool,
     pub compaction_window: Option<CompactionWindow>,
     pub refresh_schedule: Option<RefreshSchedule>,
     pub as_of: Option<Timestamp>,
@@ -1570,6 +1635,7 @@ pub struct PlanClusterOption {
     pub size: AlterOptionParameter,
     pub disk: AlterOptionParameter<bool>,
     pub schedule: AlterOptionParameter<ClusterSchedule>,
+    pub autoscale: AlterOptionParameter<ClusterAutoscale>,
 }
 
 impl Default for PlanClusterOption {
@@ -1584,6 +1650,7 @@ impl Default for PlanClusterOption {
             size: AlterOptionParameter::Unchanged,
             disk: AlterOptionParameter::Unchanged,
             schedule: AlterOptionParameter::Unchanged,
+            autoscale: AlterOptionParameter::Unchanged,
         }
     }
 }
@@ -0,0 +1,214 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Cron-like schedules, used by `SCHEDULE = OFF DURING` to describe the windows during which a
+//! cluster is turned off.
+
+use std::fmt;
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use mz_ore::cast::CastFrom;
+use serde::{Deserialize, Serialize};
+
+/// A cron-like schedule that matches a set of minutes, in UTC.
+///
+/// A schedule consists of five whitespace-separated fields: minute (0-59), hour (0-23), day of
+/// month (1-31), month (1-12), and day of week (0-7, where both 0 and 7 are Sunday). Each field is
+/// a comma-separated list of `*`, single values, or ranges `a-b`, each optionally followed by a
+/// step `/n`. As in cron, if both the day of month and the day of week are restricted, a day
+/// matches if it matches either of them.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord, Hash)]
+pub struct CronSchedule {
+    /// The normalized text of the schedule.
+    text: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    days_of_month_restricted: bool,
+    days_of_week_restricted: bool,
+}
+
+struct CronField {
+    name: &'static str,
+    min: u32,
+    max: u32,
+}
+
+const CRON_FIELDS: [CronField; 5] = [
+    CronField {
+        name: "minute",
+        min: 0,
+        max: 59,
+    },
+    CronField {
+        name: "hour",
+        min: 0,
+        max: 23,
+    },
+    CronField {
+        name: "day of month",
+        min: 1,
+        max: 31,
+    },
+    CronField {
+        name: "month",
+        min: 1,
+        max: 12,
+    },
+    CronField {
+        name: "day of week",
+        min: 0,
+        max: 7,
+    },
+];
+
+impl CronSchedule {
+    /// Parses a cron-like schedule.
+    pub fn parse(text: &str) -> Result<CronSchedule, String> {
+        let parts: Vec<_> = text.split_whitespace().collect();
+        if parts.len() != CRON_FIELDS.len() {
+            return Err(format!(
+                "expected {} fields (minute, hour, day of month, month, day of week), got {}",
+                CRON_FIELDS.len(),
+                parts.len()
+            ));
+        }
+        let mut masks = [0; 5];
+        for ((part, field), mask) in parts.iter().zip(CRON_FIELDS.iter()).zip(masks.iter_mut()) {
+            *mask = parse_cron_field(part, field)?;
+        }
+        let [minutes, hours, days_of_month, months, mut days_of_week] = masks;
+        // Sunday can be written as either 0 or 7.
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+        Ok(CronSchedule {
+            text: parts.join(" "),
+            minutes,
+            hours,
+            days_of_month,
+            months,
+            days_of_week,
+            days_of_month_restricted: !parts[2].starts_with('*'),
+            days_of_week_restricted: !parts[4].starts_with('*'),
+        })
+    }
+
+    /// Reports whether the minute containing `time` is matched by the schedule.
+    pub fn matches(&self, time: DateTime<Utc>) -> bool {
+        let contains = |mask: u64, value: u32| mask & (1 << value) != 0;
+        let day_of_month = contains(self.days_of_month, time.day());
+        let day_of_week = contains(self.days_of_week, time.weekday().num_days_from_sunday());
+        let day = if self.days_of_month_restricted && self.days_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        };
+        day && contains(self.minutes, time.minute())
+            && contains(self.hours, time.hour())
+            && contains(self.months, time.month())
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// Parses one field of a cron-like schedule into a bitmask of the matched values.
+fn parse_cron_field(text: &str, field: &CronField) -> Result<u64, String> {
+    let parse_value = |value: &str| match value.parse::<u32>() {
+        Ok(value) if (field.min..=field.max).contains(&value) => Ok(value),
+        _ => Err(format!(
+            "invalid {} {:?}: must be between {} and {}",
+            field.name, value, field.min, field.max
+        )),
+    };
+
+    let mut mask = 0;
+    for item in text.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, step),
+                _ => return Err(format!("invalid step {:?} in {} field", step, field.name)),
+            },
+            None => (item, 1),
+        };
+        let (start, end) = if range == "*" {
+            (field.min, field.max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start)?, parse_value(end)?)
+        } else {
+            // As in cron, `a/n` means every n-th value starting at `a`.
+            let start = parse_value(range)?;
+            (start, if step > 1 { field.max } else { start })
+        };
+        if start > end {
+            return Err(format!("invalid range {:?} in {} field", range, field.name));
+        }
+        for value in (start..=end).step_by(usize::cast_from(step)) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, 0))
+            .expect("valid date")
+            .and_utc()
+    }
+
+    #[mz_ore::test]
+    fn test_cron_schedule() {
+        // 2024-06-03 is a Monday, 2024-06-09 is a Sunday.
+        let nights = CronSchedule::parse("*  0-7,20-23 * * 1-5").unwrap();
+        assert_eq!(nights.to_string(), "* 0-7,20-23 * * 1-5");
+        assert!(nights.matches(at(2024, 6, 3, 21, 30)));
+        assert!(nights.matches(at(2024, 6, 3, 7, 59)));
+        assert!(!nights.matches(at(2024, 6, 3, 8, 0)));
+        assert!(!nights.matches(at(2024, 6, 9, 21, 30)));
+
+        let weekends = CronSchedule::parse("* * * * 6,7").unwrap();
+        assert!(weekends.matches(at(2024, 6, 9, 12, 0)));
+        assert!(!weekends.matches(at(2024, 6, 3, 12, 0)));
+
+        let steps = CronSchedule::parse("*/15 12 1 */2 *").unwrap();
+        assert!(steps.matches(at(2024, 7, 1, 12, 45)));
+        assert!(!steps.matches(at(2024, 7, 1, 12, 46)));
+        assert!(!steps.matches(at(2024, 6, 1, 12, 45)));
+
+        // Restricting both the day of month and the day of week matches either.
+        let either = CronSchedule::parse("* * 1 * 0").unwrap();
+        assert!(either.matches(at(2024, 6, 1, 0, 0)));
+        assert!(either.matches(at(2024, 6, 9, 0, 0)));
+        assert!(!either.matches(at(2024, 6, 3, 0, 0)));
+
+        for invalid in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "* 5-2 * * *",
+            "*/0 * * * *",
+            "a * * * *",
+        ] {
+            assert!(CronSchedule::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -141,6 +141,7 @@ pub enum PlanError {
     AlterViewOnMaterializedView(String),
     ShowCreateViewOnMaterializedView(String),
     ExplainViewOnMaterializedView(String),
+    ExplainAnalyzeOnSelect,
     UnacceptableTimelineName(String),
     FetchingCsrSchemaFailed {
         schema_lookup: String,
@@ -357,6 +358,10 @@ impl PlanError {
             Self::ExplainViewOnMaterializedView(_) => {
                 Some("Use EXPLAIN [...] MATERIALIZED VIEW to explain a materialized view.".into())
             }
+            Self::ExplainAnalyzeOnSelect => Some(
+                "Create an index or materialized view for the query and EXPLAIN ANALYZE it instead."
+                    .into(),
+            ),
             Self::UnacceptableTimelineName(_) => {
                 Some("The prefix \"mz_\" is reserved for system timelines.".into())
             }
@@ -569,6 +574,10 @@ impl fmt::Display for PlanError {
             | Self::AlterViewOnMaterializedView(name)
             | Self::ShowCreateViewOnMaterializedView(name)
             | Self::ExplainViewOnMaterializedView(name) => write!(f, "{name} is not a view"),
+            Self::ExplainAnalyzeOnSelect => f.write_str(
+                "EXPLAIN ANALYZE is not supported for SELECT statements, \
+                 whose dataflows are dropped as soon as they return results",
+            ),
             Self::FetchingCsrSchemaFailed { schema_lookup, .. } => {
                 write!(f, "failed to fetch schema {schema_lookup} from schema registry")
             }
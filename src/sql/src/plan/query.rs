@@ -58,7 +58,7 @@ use mz_sql_parser::ast::visit::Visit;
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
     visit, AsOf, Assignment, AstInfo, CreateWebhookSourceBody, CreateWebhookSourceCheck,
-    CreateWebhookSourceHeader, CreateWebhookSourceSecret, CteBlock, DeleteStatement, Distinct,
+    CreateWebhookSourceHeader, CreateWebhookSourceSecret, Cte, CteBlock, DeleteStatement, Distinct,
     Expr, Function, FunctionArgs, HomogenizingFunction, Ident, InsertSource, IsExprConstruct, Join,
     JoinConstraint, JoinOperator, Limit, MapEntry, MutRecBlock, MutRecBlockOption,
     MutRecBlockOptionName, OrderByExpr, Query, Select, SelectItem, SelectOption, SelectOptionName,
@@ -1217,6 +1217,67 @@ pub fn plan_index_exprs<'a>(
     Ok(out)
 }
 
+/// Plans the expression of a `CHECK` constraint against the columns of the table
+/// it constrains.
+pub fn plan_check_expr(
+    scx: &StatementContext,
+    on_desc: &RelationDesc,
+    mut expr: Expr<Aug>,
+) -> Result<mz_expr::MirScalarExpr, PlanError> {
+    let scope = Scope::from_source(None, on_desc.iter_names());
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "CHECK constraint",
+        scope: &scope,
+        relation_type: on_desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    transform_ast::transform(scx, &mut expr)?;
+    let mut expr = plan_expr(ecx, &expr)?
+        .type_as(ecx, &ScalarType::Bool)?
+        .lower_uncorrelated()?;
+    // The constraint is evaluated by the adapter when rows are written, so it
+    // must only depend on the row itself.
+    if expr.contains_unmaterializable() {
+        sql_bail!("CHECK constraint cannot call functions that depend on the session or the time");
+    }
+    expr.reduce(&on_desc.typ().column_types);
+    Ok(expr)
+}
+
+/// Plans the `PARTITION BY` expression of a Kafka sink against the sink's key columns. The
+/// expression is cast to `uint8` so that it can be reduced modulo the topic's partition count.
+pub fn plan_kafka_sink_partition_by(
+    scx: &StatementContext,
+    key_desc: &RelationDesc,
+    mut expr: Expr<Aug>,
+) -> Result<mz_expr::MirScalarExpr, PlanError> {
+    let scope = Scope::from_source(None, key_desc.iter_names());
+    let qcx = QueryContext::root(scx, QueryLifetime::Index);
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "PARTITION BY",
+        scope: &scope,
+        relation_type: key_desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    transform_ast::transform(scx, &mut expr)?;
+    let mut expr = plan_expr(ecx, &expr)?
+        .cast_to(ecx, CastContext::Assignment, &ScalarType::UInt64)?
+        .lower_uncorrelated()?;
+    expr.reduce(&key_desc.typ().column_types);
+    Ok(expr)
+}
+
 fn plan_expr_or_col_index(ecx: &ExprContext, e: &Expr<Aug>) -> Result<HirScalarExpr, PlanError> {
     match check_col_index(ecx.name, e, ecx.relation_type.column_types.len())? {
         Some(column) => Ok(HirScalarExpr::column(column)),
@@ -1356,7 +1417,7 @@ fn plan_query_inner(qcx: &mut QueryContext, q: &Query<Aug>) -> Result<PlannedQue
         }
     };
 
-    // Both introduce `Let` bindings atop `result` and re-install shadowed bindings.
+    // All introduce `Let` or `LetRec` bindings atop `result` and re-install shadowed bindings.
     match &q.ctes {
         CteBlock::Simple(_) => {
             for (id, value, shadowed_val) in cte_bindings.into_iter().rev() {
@@ -1373,13 +1434,18 @@ fn plan_query_inner(qcx: &mut QueryContext, q: &Query<Aug>) -> Result<PlannedQue
                 }
             }
         }
-        CteBlock::MutuallyRecursive(MutRecBlock { options, ctes: _ }) => {
+        CteBlock::Recursive(_) | CteBlock::MutuallyRecursive(_) => {
+            // Standard `WITH RECURSIVE` blocks have no options.
+            let options = match &q.ctes {
+                CteBlock::MutuallyRecursive(MutRecBlock { options, ctes: _ }) => options.clone(),
+                _ => vec![],
+            };
             let MutRecBlockOptionExtracted {
                 recursion_limit,
                 return_at_recursion_limit,
                 error_at_recursion_limit,
                 seen: _,
-            } = MutRecBlockOptionExtracted::try_from(options.clone())?;
+            } = MutRecBlockOptionExtracted::try_from(options)?;
             let limit = match (recursion_limit, return_at_recursion_limit, error_at_recursion_limit) {
                 (None, None, None) => None,
                 (Some(max_iters), None, None) => Some((max_iters, LetRecLimit::RETURN_AT_LIMIT_DEFAULT)),
@@ -1500,68 +1566,376 @@ pub fn plan_ctes(
             // Plan all CTEs and validate the proposed types.
             for cte in ctes.iter() {
                 let (val, _scope) = plan_nested_query(qcx, &cte.query)?;
+                let val = cast_recursive_cte(qcx, &cte.name, cte.id, val)?;
+                result.push((cte.id, val, shadowed_descs.remove(&cte.id)));
+            }
+        }
+        CteBlock::Recursive(ctes) => {
+            for cte in ctes.iter() {
+                let cte_name = normalize::ident(cte.alias.name.clone());
 
-                let proposed_typ = qcx.ctes[&cte.id].desc.typ();
-
-                if proposed_typ.column_types.iter().any(|c| !c.nullable) {
-                    // Once WMR CTEs support NOT NULL constraints, check that
-                    // nullability of derived column types are compatible.
-                    sql_bail!("[internal error]: WMR CTEs do not support NOT NULL constraints on proposed column types");
+                let mut checker = RecursiveReferenceChecker::new(cte.id, &cte_name);
+                checker.visit_query(&cte.query);
+                if checker.references == 0 {
+                    // CTEs that don't refer to themselves are planned as in a
+                    // simple `WITH` block.
+                    let (val, scope) = plan_nested_query(qcx, &cte.query)?;
+                    let typ = qcx.relation_type(&val);
+                    let mut desc = RelationDesc::new(typ, scope.column_names());
+                    plan_utils::maybe_rename_columns(
+                        format!("CTE {}", cte.alias.name),
+                        &mut desc,
+                        &cte.alias.columns,
+                    )?;
+                    let shadowed = qcx.ctes.insert(
+                        cte.id,
+                        CteDesc {
+                            name: cte_name,
+                            desc,
+                        },
+                    );
+                    result.push((cte.id, val, shadowed));
+                    continue;
                 }
 
-                if !proposed_typ.keys.is_empty() {
-                    // Once WMR CTEs support keys, check that keys exactly
-                    // overlap.
-                    sql_bail!("[internal error]: WMR CTEs do not support keys");
-                }
+                let non_recursive_term = recursive_cte_non_recursive_term(cte, &cte_name)?;
 
-                // Validate that the derived and proposed types are the same.
-                let derived_typ = qcx.relation_type(&val);
+                // As in PostgreSQL, the non-recursive term determines the
+                // column names and types of the CTE.
+                let (val, scope) = plan_nested_query(
+                    qcx,
+                    &Query {
+                        ctes: CteBlock::empty(),
+                        body: non_recursive_term.clone(),
+                        order_by: vec![],
+                        limit: None,
+                        offset: None,
+                    },
+                )?;
+                let typ = qcx.relation_type(&val);
+                let mut desc = RelationDesc::from_names_and_types(
+                    scope.column_names().cloned().zip(
+                        typ.column_types
+                            .into_iter()
+                            .map(|ty| ty.scalar_type.nullable(true)),
+                    ),
+                );
+                plan_utils::maybe_rename_columns(
+                    format!("CTE {}", cte.alias.name),
+                    &mut desc,
+                    &cte.alias.columns,
+                )?;
+                let shadowed = qcx.ctes.insert(
+                    cte.id,
+                    CteDesc {
+                        name: cte_name.clone(),
+                        desc,
+                    },
+                );
 
-                let type_err = |proposed_typ: &RelationType, derived_typ: RelationType| {
-                    let cte_name = normalize::ident(cte.name.clone());
-                    let proposed_typ = proposed_typ
-                        .column_types
-                        .iter()
-                        .map(|ty| qcx.humanize_scalar_type(&ty.scalar_type))
-                        .collect::<Vec<_>>();
-                    let inferred_typ = derived_typ
-                        .column_types
-                        .iter()
-                        .map(|ty| qcx.humanize_scalar_type(&ty.scalar_type))
-                        .collect::<Vec<_>>();
-                    Err(PlanError::RecursiveTypeMismatch(
-                        cte_name,
-                        proposed_typ,
-                        inferred_typ,
-                    ))
-                };
+                let (val, _scope) = plan_nested_query(qcx, &cte.query)?;
+                check_recursive_term(&val, cte.id, &cte_name)?;
+                let val = cast_recursive_cte(qcx, &cte.alias.name, cte.id, val)?;
+                result.push((cte.id, val, shadowed));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Casts the planned value of the recursive CTE `id` to the column types
+/// proposed for it in `qcx.ctes`, or errors if the types are incompatible.
+fn cast_recursive_cte(
+    qcx: &QueryContext,
+    name: &Ident,
+    id: LocalId,
+    val: HirRelationExpr,
+) -> Result<HirRelationExpr, PlanError> {
+    let proposed_typ = qcx.ctes[&id].desc.typ();
+
+    if proposed_typ.column_types.iter().any(|c| !c.nullable) {
+        // Once WMR CTEs support NOT NULL constraints, check that
+        // nullability of derived column types are compatible.
+        sql_bail!("[internal error]: WMR CTEs do not support NOT NULL constraints on proposed column types");
+    }
+
+    if !proposed_typ.keys.is_empty() {
+        // Once WMR CTEs support keys, check that keys exactly
+        // overlap.
+        sql_bail!("[internal error]: WMR CTEs do not support keys");
+    }
+
+    // Validate that the derived and proposed types are the same.
+    let derived_typ = qcx.relation_type(&val);
+
+    let type_err = |proposed_typ: &RelationType, derived_typ: RelationType| {
+        let cte_name = normalize::ident(name.clone());
+        let proposed_typ = proposed_typ
+            .column_types
+            .iter()
+            .map(|ty| qcx.humanize_scalar_type(&ty.scalar_type))
+            .collect::<Vec<_>>();
+        let inferred_typ = derived_typ
+            .column_types
+            .iter()
+            .map(|ty| qcx.humanize_scalar_type(&ty.scalar_type))
+            .collect::<Vec<_>>();
+        Err(PlanError::RecursiveTypeMismatch(
+            cte_name,
+            proposed_typ,
+            inferred_typ,
+        ))
+    };
+
+    if derived_typ.column_types.len() != proposed_typ.column_types.len() {
+        return type_err(proposed_typ, derived_typ);
+    }
+
+    // Cast dervied types to proposed types or error.
+    match cast_relation(
+        qcx,
+        // Choose `CastContext::Assignment`` because the user has
+        // been explicit about the types they expect. Choosing
+        // `CastContext::Implicit` is not "strong" enough to impose
+        // typmods from proposed types onto values.
+        CastContext::Assignment,
+        val,
+        proposed_typ.column_types.iter().map(|c| &c.scalar_type),
+    ) {
+        Ok(val) => Ok(val),
+        Err(_) => type_err(proposed_typ, derived_typ),
+    }
+}
+
+/// Returns the non-recursive term of a self-referencing CTE in a
+/// `WITH RECURSIVE` block, after validating that the CTE has the form
+/// `non_recursive_term UNION [ALL] recursive_term`.
+fn recursive_cte_non_recursive_term<'a>(
+    cte: &'a Cte<Aug>,
+    cte_name: &str,
+) -> Result<&'a SetExpr<Aug>, PlanError> {
+    let query = &cte.query;
+    if !query.ctes.is_empty() {
+        bail_unsupported!("WITH in a recursive query");
+    }
+    if !query.order_by.is_empty() {
+        bail_unsupported!("ORDER BY in a recursive query");
+    }
+    if query.limit.is_some() {
+        bail_unsupported!("LIMIT in a recursive query");
+    }
+    if query.offset.is_some() {
+        bail_unsupported!("OFFSET in a recursive query");
+    }
+    let SetExpr::SetOperation {
+        op: SetOperator::Union,
+        all: _,
+        left,
+        right,
+    } = &query.body
+    else {
+        sql_bail!(
+            "recursive query {} does not have the form non-recursive-term UNION [ALL] recursive-term",
+            cte_name.quoted()
+        );
+    };
+
+    let mut checker = RecursiveReferenceChecker::new(cte.id, cte_name);
+    checker.visit_set_expr(left);
+    if checker.references > 0 {
+        sql_bail!(
+            "recursive reference to query {} must not appear within its non-recursive term",
+            cte_name.quoted()
+        );
+    }
+
+    let mut checker = RecursiveReferenceChecker::new(cte.id, cte_name);
+    checker.visit_set_expr(right);
+    if let Some(err) = checker.error {
+        return Err(err);
+    }
+
+    Ok(left)
+}
 
-                if derived_typ.column_types.len() != proposed_typ.column_types.len() {
-                    return type_err(proposed_typ, derived_typ);
+/// Rejects recursive terms that refer to the recursive CTE `id` from within
+/// an aggregation or a `TopK`, which don't distribute over the iterations of
+/// the recursion.
+fn check_recursive_term(
+    val: &HirRelationExpr,
+    id: LocalId,
+    cte_name: &str,
+) -> Result<(), PlanError> {
+    let references = |expr: &HirRelationExpr| {
+        let mut found = false;
+        mz_expr::visit::Visit::visit_pre(expr, &mut |expr: &HirRelationExpr| {
+            if let HirRelationExpr::Get {
+                id: Id::Local(get_id),
+                ..
+            } = expr
+            {
+                found |= *get_id == id;
+            }
+        })
+        .map(|()| found)
+    };
+    let mut error = None;
+    mz_expr::visit::Visit::try_visit_pre(val, &mut |expr: &HirRelationExpr| {
+        if error.is_none() {
+            match expr {
+                HirRelationExpr::Reduce { input, .. } if references(input)? => {
+                    error = Some(sql_err!(
+                        "aggregate functions are not allowed in a recursive query's recursive term"
+                    ));
                 }
+                HirRelationExpr::TopK { input, .. } if references(input)? => {
+                    error = Some(sql_err!(
+                        "recursive reference to query {} must not appear within LIMIT, OFFSET, or DISTINCT ON",
+                        cte_name.quoted()
+                    ));
+                }
+                _ => {}
+            }
+        }
+        Ok::<_, PlanError>(())
+    })?;
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
 
-                // Cast dervied types to proposed types or error.
-                let val = match cast_relation(
-                    qcx,
-                    // Choose `CastContext::Assignment`` because the user has
-                    // been explicit about the types they expect. Choosing
-                    // `CastContext::Implicit` is not "strong" enough to impose
-                    // typmods from proposed types onto values.
-                    CastContext::Assignment,
-                    val,
-                    proposed_typ.column_types.iter().map(|c| &c.scalar_type),
-                ) {
-                    Ok(val) => val,
-                    Err(_) => return type_err(proposed_typ, derived_typ),
-                };
+/// Counts the references that a query makes to the recursive CTE `id`, and
+/// records an error if the recursive term of the CTE refers to it in a way
+/// that makes the recursion non-linear, which we, like PostgreSQL, don't
+/// support.
+struct RecursiveReferenceChecker<'a> {
+    id: LocalId,
+    cte_name: &'a str,
+    /// The number of references to the CTE.
+    references: usize,
+    /// The enclosing construct, if any, that must not contain a reference to
+    /// the CTE.
+    context: Option<&'static str>,
+    /// The first error found.
+    error: Option<PlanError>,
+}
 
-                result.push((cte.id, val, shadowed_descs.remove(&cte.id)));
+impl<'a> RecursiveReferenceChecker<'a> {
+    fn new(id: LocalId, cte_name: &'a str) -> Self {
+        RecursiveReferenceChecker {
+            id,
+            cte_name,
+            references: 0,
+            context: None,
+            error: None,
+        }
+    }
+
+    fn within<F>(&mut self, context: &'static str, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let outer = self.context.replace(context);
+        f(self);
+        self.context = outer;
+    }
+}
+
+impl<'a, 'ast> Visit<'ast, Aug> for RecursiveReferenceChecker<'a> {
+    fn visit_item_name(&mut self, item_name: &'ast ResolvedItemName) {
+        let ResolvedItemName::Cte { id, .. } = item_name else {
+            return;
+        };
+        if *id != self.id {
+            return;
+        }
+        self.references += 1;
+        if self.error.is_some() {
+            return;
+        }
+        if self.references > 1 {
+            self.error = Some(sql_err!(
+                "recursive reference to query {} must not appear more than once",
+                self.cte_name.quoted()
+            ));
+        } else if let Some(context) = self.context {
+            self.error = Some(sql_err!(
+                "recursive reference to query {} must not appear within {}",
+                self.cte_name.quoted(),
+                context
+            ));
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr<Aug>) {
+        match expr {
+            Expr::Exists(_)
+            | Expr::Subquery(_)
+            | Expr::InSubquery { .. }
+            | Expr::AnySubquery { .. }
+            | Expr::AllSubquery { .. }
+            | Expr::ArraySubquery(_)
+            | Expr::ListSubquery(_)
+            | Expr::MapSubquery(_) => {
+                self.within("a subquery", |this| visit::visit_expr(this, expr))
             }
+            _ => visit::visit_expr(self, expr),
         }
     }
 
-    Ok(result)
+    fn visit_set_expr(&mut self, set_expr: &'ast SetExpr<Aug>) {
+        match set_expr {
+            SetExpr::SetOperation {
+                op: SetOperator::Except,
+                ..
+            } => self.within("EXCEPT", |this| visit::visit_set_expr(this, set_expr)),
+            SetExpr::SetOperation {
+                op: SetOperator::Intersect,
+                ..
+            } => self.within("INTERSECT", |this| visit::visit_set_expr(this, set_expr)),
+            _ => visit::visit_set_expr(self, set_expr),
+        }
+    }
+
+    fn visit_table_with_joins(&mut self, table_with_joins: &'ast TableWithJoins<Aug>) {
+        let TableWithJoins { relation, joins } = table_with_joins;
+        // A relation is on the nullable side of an outer join if it is the
+        // right-hand side of a `LEFT` or `FULL` join, or precedes a `RIGHT` or
+        // `FULL` join.
+        let nullable = |i: usize| {
+            let preceding = i > 0
+                && matches!(
+                    joins[i - 1].join_operator,
+                    JoinOperator::LeftOuter(_)
+                        | JoinOperator::FullOuter(_)
+                        | JoinOperator::LeftAsOf(_)
+                );
+            let following = joins[i..].iter().any(|join| {
+                matches!(
+                    join.join_operator,
+                    JoinOperator::RightOuter(_) | JoinOperator::FullOuter(_)
+                )
+            });
+            preceding || following
+        };
+        for i in 0..=joins.len() {
+            let factor = if i == 0 {
+                relation
+            } else {
+                &joins[i - 1].relation
+            };
+            if nullable(i) {
+                self.within("an outer join", |this| this.visit_table_factor(factor));
+            } else {
+                self.visit_table_factor(factor);
+            }
+            if i > 0 {
+                self.visit_join_operator(&joins[i - 1].join_operator);
+            }
+        }
+    }
 }
 
 pub fn plan_nested_query(
@@ -1790,6 +2164,11 @@ fn plan_set_expr(
                 ShowStatement::ShowColumns(stmt) => {
                     show::show_columns(qcx.scx, stmt)?.plan_hir(qcx)
                 }
+                ShowStatement::ShowEffectivePrivileges(stmt) => {
+                    let (expr, desc) = show::show_effective_privileges(qcx.scx, stmt)?;
+                    let scope = Scope::from_source(None, desc.iter_names());
+                    Ok((expr, scope))
+                }
                 ShowStatement::ShowCreateConnection(stmt) => to_hirscope(
                     show::plan_show_create_connection(qcx.scx, stmt.clone())?,
                     show::describe_show_create_connection(qcx.scx, stmt)?,
@@ -1798,6 +2177,11 @@ fn plan_set_expr(
                     show::plan_show_create_index(qcx.scx, stmt.clone())?,
                     show::describe_show_create_index(qcx.scx, stmt)?,
                 ),
+                ShowStatement::ShowCreateAllObjects(stmt) => {
+                    let (expr, desc) = show::show_create_all_objects(qcx.scx, stmt)?;
+                    let scope = Scope::from_source(None, desc.iter_names());
+                    Ok((expr, scope))
+                }
                 ShowStatement::ShowCreateSink(stmt) => to_hirscope(
                     show::plan_show_create_sink(qcx.scx, stmt.clone())?,
                     show::describe_show_create_sink(qcx.scx, stmt)?,
@@ -3059,14 +3443,30 @@ fn plan_table_function_internal(
     };
 
     if with_ordinality {
-        expr = expr.map(vec![HirScalarExpr::Windowing(WindowExpr {
-            func: WindowExprType::Scalar(ScalarWindowExpr {
-                func: ScalarWindowFunc::RowNumber,
+        expr = match expr {
+            // Number the rows inside the table function call itself. This keeps
+            // the call stateless, so that lowering can apply it directly to
+            // each row of a correlated input rather than decorrelating it
+            // through a distinct and a join, and the rows are numbered in the
+            // order in which the function produces them.
+            HirRelationExpr::CallTable { func, exprs } => HirRelationExpr::CallTable {
+                func: mz_expr::TableFunc::WithOrdinality {
+                    inner: Box::new(func),
+                },
+                exprs,
+            },
+            // Table functions that are implemented in SQL can plan to
+            // arbitrary expressions, so fall back to numbering their rows
+            // with a window function.
+            expr => expr.map(vec![HirScalarExpr::Windowing(WindowExpr {
+                func: WindowExprType::Scalar(ScalarWindowExpr {
+                    func: ScalarWindowFunc::RowNumber,
+                    order_by: vec![],
+                }),
+                partition_by: vec![],
                 order_by: vec![],
-            }),
-            partition_by: vec![],
-            order_by: vec![],
-        })]);
+            })]),
+        };
         scope
             .items
             .push(ScopeItem::from_name(scope_name, "ordinality"));
@@ -3391,6 +3791,26 @@ fn plan_join(
         JoinOperator::LeftOuter(constraint) => (JoinKind::LeftOuter, constraint),
         JoinOperator::RightOuter(constraint) => (JoinKind::RightOuter, constraint),
         JoinOperator::FullOuter(constraint) => (JoinKind::FullOuter, constraint),
+        JoinOperator::AsOf(constraint) => {
+            return plan_asof_join(
+                left_qcx,
+                left,
+                left_scope,
+                join,
+                constraint,
+                JoinKind::Inner,
+            )
+        }
+        JoinOperator::LeftAsOf(constraint) => {
+            return plan_asof_join(
+                left_qcx,
+                left,
+                left_scope,
+                join,
+                constraint,
+                JoinKind::LeftOuter,
+            )
+        }
     };
 
     let mut right_qcx = left_qcx.derived_context(left_scope.clone(), left_qcx.relation_type(&left));
@@ -3470,6 +3890,166 @@ fn plan_join(
     Ok((expr, scope))
 }
 
+/// Plans an `ASOF JOIN`, which matches each left row with the right row that
+/// satisfies the `ON` clause and is closest to the left row according to the
+/// clause's single inequality between a right and a left expression. For
+/// example, `ON l.k = r.k AND r.ts <= l.ts` matches each left row with the
+/// right row with the same key and the latest `ts` that is not after the left
+/// row's `ts`.
+///
+/// The join is planned as a lateral join against the right relation, filtered
+/// by the `ON` clause and limited to its first row by the inequality, which
+/// decorrelates into a join against a `TopK` grouped by the referenced left
+/// columns and so is maintained incrementally.
+fn plan_asof_join(
+    left_qcx: &QueryContext,
+    left: HirRelationExpr,
+    left_scope: Scope,
+    join: &Join<Aug>,
+    constraint: &JoinConstraint<Aug>,
+    kind: JoinKind,
+) -> Result<(HirRelationExpr, Scope), PlanError> {
+    let JoinConstraint::On(on) = constraint else {
+        sql_bail!("ASOF JOIN requires an ON clause");
+    };
+
+    let left_type = left_qcx.relation_type(&left);
+    let left_arity = left_type.arity();
+    let mut right_qcx = left_qcx.derived_context(left_scope.clone(), left_type.clone());
+    for item in &mut right_qcx.outer_scopes[0].items {
+        item.lateral_error_if_referenced = true;
+    }
+    let (right, right_scope) = plan_table_factor(&right_qcx, &join.relation)?;
+    let right_type = right_qcx.relation_type(&right);
+    let right_arity = right_type.arity();
+
+    let product_scope = left_scope.product(right_scope)?;
+    let ecx = &ExprContext {
+        qcx: left_qcx,
+        name: "ASOF JOIN ON clause",
+        scope: &product_scope,
+        relation_type: &RelationType::new(
+            left_type
+                .column_types
+                .into_iter()
+                .chain(right_type.column_types)
+                .collect(),
+        ),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: true,
+        allow_windows: false,
+    };
+
+    // Find the inequality that orders the matching right rows. Its right-hand
+    // relation side determines the order: for `r.ts <= l.ts` the match is the
+    // right row with the greatest `ts`, for `r.ts >= l.ts` the one with the
+    // least `ts`.
+    fn conjuncts<'a>(expr: &'a Expr<Aug>, out: &mut Vec<&'a Expr<Aug>>) {
+        match expr {
+            Expr::And { left, right } => {
+                conjuncts(left, out);
+                conjuncts(right, out);
+            }
+            Expr::Nested(expr) => conjuncts(expr, out),
+            _ => out.push(expr),
+        }
+    }
+    let mut on_conjuncts = vec![];
+    conjuncts(on, &mut on_conjuncts);
+
+    let side = |expr: &HirScalarExpr| {
+        let (mut any_left, mut any_right) = (false, false);
+        #[allow(deprecated)]
+        expr.visit_columns(0, &mut |depth, col| {
+            if col.level == depth {
+                if col.column < left_arity {
+                    any_left = true;
+                } else {
+                    any_right = true;
+                }
+            }
+        });
+        (any_left, any_right)
+    };
+    let mut order = None;
+    for conjunct in on_conjuncts {
+        let Expr::Op {
+            op,
+            expr1,
+            expr2: Some(expr2),
+        } = conjunct
+        else {
+            continue;
+        };
+        // The right relation's side should be greatest if it is bounded from
+        // above, and least if it is bounded from below.
+        let desc_if_right_first = match op.op.as_str() {
+            "<" | "<=" => true,
+            ">" | ">=" => false,
+            _ => continue,
+        };
+        let expr1 = plan_expr(ecx, expr1)?.type_as_any(ecx)?;
+        let expr2 = plan_expr(ecx, expr2)?.type_as_any(ecx)?;
+        let (right_expr, desc) = match (side(&expr1), side(&expr2)) {
+            ((false, true), (true, false)) => (expr1, desc_if_right_first),
+            ((true, false), (false, true)) => (expr2, !desc_if_right_first),
+            _ => continue,
+        };
+        if order.is_some() {
+            sql_bail!(
+                "ASOF JOIN ON clause must contain exactly one inequality between the left and \
+                 right relations"
+            );
+        }
+        order = Some((right_expr, desc));
+    }
+    let Some((mut order_expr, desc)) = order else {
+        sql_bail!(
+            "ASOF JOIN ON clause must contain exactly one inequality between the left and right \
+             relations"
+        );
+    };
+    let mut on = plan_expr(ecx, on)?.type_as(ecx, &ScalarType::Bool)?;
+
+    // Rewrite the expressions from the scope of the join's product to the
+    // scope of the right relation, with the left relation as its outer scope.
+    let lateralize = |expr: &mut HirScalarExpr| {
+        #[allow(deprecated)]
+        expr.visit_columns_mut(0, &mut |depth, col| {
+            if col.level > depth {
+                col.level += 1;
+            } else if col.level == depth {
+                if col.column < left_arity {
+                    col.level += 1;
+                } else {
+                    col.column -= left_arity;
+                }
+            }
+        });
+    };
+    lateralize(&mut on);
+    lateralize(&mut order_expr);
+
+    let matches = right
+        .filter(vec![on])
+        .map(vec![order_expr])
+        .top_k(
+            vec![],
+            vec![ColumnOrder {
+                column: right_arity,
+                desc,
+                nulls_last: true,
+            }],
+            Some(HirScalarExpr::literal(Datum::Int64(1), ScalarType::Int64)),
+            0,
+            None,
+        )
+        .project((0..right_arity).collect());
+    let joined = left.join(matches, HirScalarExpr::literal_true(), kind);
+    Ok((joined, product_scope))
+}
+
 // See page 440 of ANSI SQL 2016 spec for details on scoping of using/natural joins
 #[allow(clippy::too_many_arguments)]
 fn plan_using_constraint(
@@ -31,26 +31,30 @@ use mz_ore::num::NonNeg;
 use mz_ore::soft_panic_or_log;
 use mz_ore::str::StrExt;
 use mz_ore::vec::VecExt;
+use mz_pgwire_common::scram::ScramVerifier;
 use mz_proto::RustType;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::mz_acl_item::{MzAclItem, PrivilegeMap};
 use mz_repr::adt::system::Oid;
+use mz_repr::bytes::ByteSize;
 use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::refresh_schedule::{RefreshEvery, RefreshSchedule};
 use mz_repr::role_id::RoleId;
 use mz_repr::{
     strconv, ColumnName, ColumnType, GlobalId, RelationDesc, RelationType, ScalarType, Timestamp,
 };
+use mz_rocksdb_types::config::{CompactionStyle, CompressionType, RocksDBTuningOverrides};
 use mz_sql_parser::ast::display::comma_separated;
 use mz_sql_parser::ast::{
     self, AlterClusterAction, AlterClusterStatement, AlterConnectionAction, AlterConnectionOption,
-    AlterConnectionOptionName, AlterConnectionStatement, AlterIndexAction, AlterIndexStatement,
-    AlterObjectRenameStatement, AlterObjectSwapStatement, AlterRetainHistoryStatement,
-    AlterRoleOption, AlterRoleStatement, AlterSecretStatement, AlterSetClusterStatement,
-    AlterSinkAction, AlterSinkStatement, AlterSourceAction, AlterSourceAddSubsourceOption,
-    AlterSourceAddSubsourceOptionName, AlterSourceStatement, AlterSystemResetAllStatement,
-    AlterSystemResetStatement, AlterSystemSetStatement, AvroSchema, AvroSchemaOption,
-    AvroSchemaOptionName, ClusterFeature, ClusterFeatureName, ClusterOption, ClusterOptionName,
+    AlterConnectionOptionName, AlterConnectionStatement, AlterDatabaseStatement, AlterIndexAction,
+    AlterIndexStatement, AlterObjectRenameStatement, AlterObjectSwapStatement,
+    AlterRetainHistoryStatement, AlterRoleOption, AlterRoleStatement, AlterSecretStatement,
+    AlterSetClusterStatement, AlterSinkAction, AlterSinkStatement, AlterSourceAction,
+    AlterSourceAddSubsourceOption, AlterSourceAddSubsourceOptionName, AlterSourceStatement,
+    AlterSystemResetAllStatement, AlterSystemResetStatement, AlterSystemSetStatement, AvroSchema,
+    AvroSchemaOption, AvroSchemaOptionName, ClusterAlterOption, ClusterAlterOptionName,
+    ClusterFeature, ClusterFeatureName, ClusterOption, ClusterOptionName,
     ClusterScheduleOptionValue, ColumnOption, CommentObjectType, CommentStatement,
     CreateClusterReplicaStatement, CreateClusterStatement, CreateConnectionOption,
     CreateConnectionOptionName, CreateConnectionStatement, CreateConnectionType,
@@ -62,12 +66,13 @@ use mz_sql_parser::ast::{
     CreateTableStatement, CreateTypeAs, CreateTypeListOption, CreateTypeListOptionName,
     CreateTypeMapOption, CreateTypeMapOptionName, CreateTypeStatement, CreateViewStatement,
     CreateWebhookSourceStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
-    CsrConnectionAvro, CsrConnectionProtobuf, CsrSeedProtobuf, CsvColumns, DeferredItemName,
-    DocOnIdentifier, DocOnSchema, DropObjectsStatement, DropOwnedStatement, Expr, Format, Ident,
-    IfExistsBehavior, IndexOption, IndexOptionName, KafkaSinkConfigOption, KeyConstraint,
-    LoadGeneratorOption, LoadGeneratorOptionName, MaterializedViewOption,
-    MaterializedViewOptionName, MySqlConfigOption, MySqlConfigOptionName, PgConfigOption,
-    PgConfigOptionName, ProtobufSchema, QualifiedReplica, RefreshAtOptionValue,
+    CsrConnectionAvro, CsrConnectionProtobuf, CsrSeedProtobuf, CsvColumns, CsvFormatOption,
+    CsvFormatOptionName, DeferredItemName, DocOnIdentifier, DocOnSchema, DropObjectsStatement,
+    DropOwnedStatement, Expr, Format, FramingOption, FramingOptionName, Ident, IfExistsBehavior,
+    IndexOption, IndexOptionName, KafkaSinkConfigOption, KeyConstraint, LoadGeneratorOption,
+    LoadGeneratorOptionName, MaterializedViewOption, MaterializedViewOptionName, MySqlConfigOption,
+    MySqlConfigOptionName, PgConfigOption, PgConfigOptionName, ProtobufSchema,
+    ProtobufSchemaOption, ProtobufSchemaOptionName, QualifiedReplica, RefreshAtOptionValue,
     RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition, ReplicaOption,
     ReplicaOptionName, RoleAttribute, SetRoleVar, SourceIncludeMetadata, Statement,
     TableConstraint, TableOption, TableOptionName, UnresolvedDatabaseName, UnresolvedItemName,
@@ -78,20 +83,20 @@ use mz_sql_parser::parser::StatementParseResult;
 use mz_storage_types::connections::inline::{ConnectionAccess, ReferencedConnection};
 use mz_storage_types::connections::Connection;
 use mz_storage_types::sinks::{
-    KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat, KafkaSinkTopicOptions, SinkEnvelope,
-    StorageSinkConnection,
+    KafkaIdStyle, KafkaSinkConnection, KafkaSinkDeliveryGuarantee, KafkaSinkFormat,
+    KafkaSinkTopicOptions, SinkEnvelope, StorageSinkConnection,
 };
 use mz_storage_types::sources::encoding::{
-    included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, ProtobufEncoding,
-    RegexEncoding, SourceDataEncoding,
+    included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, Framing, JsonPath,
+    JsonShredColumn, JsonShredEncoding, ProtobufEncoding, RegexEncoding, SourceDataEncoding,
 };
 use mz_storage_types::sources::envelope::{
     KeyEnvelope, SourceEnvelope, UnplannedSourceEnvelope, UpsertStyle,
 };
 use mz_storage_types::sources::kafka::{KafkaMetadataKind, KafkaSourceConnection};
 use mz_storage_types::sources::load_generator::{
-    KeyValueLoadGenerator, LoadGenerator, LoadGeneratorSourceConnection,
-    LOAD_GENERATOR_KEY_VALUE_OFFSET_DEFAULT,
+    CustomLoadGenerator, CustomLoadGeneratorColumn, KeyValueLoadGenerator, LoadGenerator,
+    LoadGeneratorSourceConnection, ValueDistribution, LOAD_GENERATOR_KEY_VALUE_OFFSET_DEFAULT,
 };
 use mz_storage_types::sources::mysql::{
     MySqlSourceConnection, MySqlSourceDetails, ProtoMySqlSourceDetails,
@@ -114,7 +119,8 @@ use crate::kafka_util::{KafkaSinkConfigOptionExtracted, KafkaSourceConfigOptionE
 use crate::names::{
     Aug, CommentObjectId, DatabaseId, ObjectId, PartialItemName, QualifiedItemName,
     RawDatabaseSpecifier, ResolvedClusterName, ResolvedColumnReference, ResolvedDataType,
-    ResolvedDatabaseSpecifier, ResolvedItemName, SchemaSpecifier, SystemObjectId,
+    ResolvedDatabaseName, ResolvedDatabaseSpecifier, ResolvedItemName, SchemaSpecifier,
+    SystemObjectId,
 };
 use crate::normalize::{self, ident};
 use crate::plan::error::PlanError;
@@ -126,24 +132,28 @@ use crate::plan::statement::{scl, StatementContext, StatementDesc};
 use crate::plan::typeconv::{plan_cast, CastContext};
 use crate::plan::with_options::{OptionalDuration, TryFromValue};
 use crate::plan::{
-    plan_utils, query, transform_ast, AlterClusterPlan, AlterClusterRenamePlan,
-    AlterClusterReplicaRenamePlan, AlterClusterSwapPlan, AlterConnectionPlan, AlterItemRenamePlan,
-    AlterNoopPlan, AlterOptionParameter, AlterRetainHistoryPlan, AlterRolePlan,
-    AlterSchemaRenamePlan, AlterSchemaSwapPlan, AlterSecretPlan, AlterSetClusterPlan,
-    AlterSystemResetAllPlan, AlterSystemResetPlan, AlterSystemSetPlan, ClusterSchedule,
-    CommentPlan, ComputeReplicaConfig, ComputeReplicaIntrospectionConfig, CreateClusterManagedPlan,
-    CreateClusterPlan, CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant,
-    CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan,
-    CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
-    CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc, DropObjectsPlan,
-    DropOwnedPlan, FullItemName, HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan,
-    PlanClusterOption, PlanNotice, QueryContext, ReplicaConfig, Secret, Sink, Source, Table, Type,
-    VariableValue, View, WebhookBodyFormat, WebhookHeaderFilters, WebhookHeaders,
+    plan_utils, query, transform_ast, AlterClusterPlan, AlterClusterPlanStrategy,
+    AlterClusterRenamePlan, AlterClusterReplicaRenamePlan, AlterClusterSwapPlan,
+    AlterConnectionPlan, AlterDatabasePlan, AlterItemRenamePlan, AlterNoopPlan,
+    AlterOptionParameter, AlterRetainHistoryPlan, AlterRolePlan, AlterSchemaRenamePlan,
+    AlterSchemaSwapPlan, AlterSecretPlan, AlterSetClusterPlan, AlterSourcePlan,
+    AlterSystemResetAllPlan, AlterSystemResetPlan, AlterSystemSetPlan, ClusterAutoscale,
+    ClusterSchedule, CommentPlan, ComputeReplicaConfig, ComputeReplicaIntrospectionConfig,
+    CreateClusterManagedPlan, CreateClusterPlan, CreateClusterReplicaPlan,
+    CreateClusterUnmanagedPlan, CreateClusterVariant, CreateConnectionPlan, CreateDatabasePlan,
+    CreateIndexPlan, CreateMaterializedViewPlan, CreateRolePlan, CreateSchemaPlan,
+    CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
+    CreateViewPlan, DataSourceDesc, DropObjectsPlan, DropOwnedPlan, FullItemName, HirScalarExpr,
+    Index, Ingestion, MaterializedView, Params, Plan, PlanClusterOption, PlanNotice, QueryContext,
+    ReplicaConfig, Secret, Sink, Source, Table, TableCheck, Type, VariableValue, View,
+    WebhookBodyFormat, WebhookHeaderFilters, WebhookHeaders,
 };
-use crate::plan::{AlterSinkPlan, WebhookValidation};
+use crate::plan::{AlterSinkPlan, CronSchedule, WebhookValidation};
 use crate::session::vars;
 use crate::session::vars::{
-    ENABLE_CLUSTER_SCHEDULE_REFRESH, ENABLE_KAFKA_SINK_HEADERS, ENABLE_REFRESH_EVERY_MVS,
+    ENABLE_APPEND_ONLY_MVS, ENABLE_CLUSTER_AUTOSCALING, ENABLE_CLUSTER_SCHEDULE_OFF_DURING,
+    ENABLE_CLUSTER_SCHEDULE_REFRESH, ENABLE_GRACEFUL_CLUSTER_RECONFIGURATION,
+    ENABLE_KAFKA_SINK_HEADERS, ENABLE_REFRESH_EVERY_MVS,
 };
 
 mod connection;
@@ -250,6 +260,9 @@ pub fn plan_create_table(
     let mut column_types = Vec::with_capacity(columns.len());
     let mut defaults = Vec::with_capacity(columns.len());
     let mut keys = Vec::new();
+    // `CHECK` constraints, along with the column they were declared on, if
+    // any. They are planned once the table's columns are known.
+    let mut check_defs = Vec::new();
 
     for (i, c) in columns.into_iter().enumerate() {
         let aug_data_type = &c.data_type;
@@ -273,6 +286,9 @@ pub fn plan_create_table(
                         nullable = false;
                     }
                 }
+                ColumnOption::Check(expr) => {
+                    check_defs.push((option.name.as_ref(), Some(i), expr));
+                }
                 other => {
                     bail_unsupported!(format!("CREATE TABLE with column constraint: {}", other))
                 }
@@ -282,6 +298,16 @@ pub fn plan_create_table(
         defaults.push(default);
     }
 
+    for constraint in constraints {
+        if let TableConstraint::Check {
+            name: constraint_name,
+            expr,
+        } = constraint
+        {
+            check_defs.push((constraint_name.as_ref(), None, &**expr));
+        }
+    }
+
     let mut seen_primary = false;
     'c: for constraint in constraints {
         match constraint {
@@ -337,13 +363,15 @@ pub fn plan_create_table(
                 scx.require_feature_flag(&vars::ENABLE_TABLE_FOREIGN_KEY)?
             }
             TableConstraint::Check { .. } => {
-                // Check constraints are not presently enforced. We allow them
-                // with feature flags for sqllogictest's sake.
-                scx.require_feature_flag(&vars::ENABLE_TABLE_CHECK_CONSTRAINT)?
+                // Collected into `check_defs` above.
             }
         }
     }
 
+    if !check_defs.is_empty() {
+        scx.require_feature_flag(&vars::ENABLE_TABLE_CHECK_CONSTRAINT)?
+    }
+
     if !keys.is_empty() {
         // Unique constraints are not presently enforced. We allow them with feature flags for
         // sqllogictest's sake.
@@ -376,6 +404,44 @@ pub fn plan_create_table(
 
     let desc = RelationDesc::new(typ, names);
 
+    // Unnamed constraints are named like in PostgreSQL: after the table, and
+    // the column for column constraints, with a numeric suffix to make the
+    // name unique if necessary.
+    let mut checks: Vec<TableCheck> = Vec::with_capacity(check_defs.len());
+    for (constraint_name, column, expr) in check_defs {
+        let constraint_name = match constraint_name {
+            Some(constraint_name) => {
+                let constraint_name = normalize::ident(constraint_name.clone());
+                if checks.iter().any(|check| check.name == constraint_name) {
+                    sql_bail!(
+                        "constraint {} for relation {} already exists",
+                        constraint_name.quoted(),
+                        name.item.quoted()
+                    );
+                }
+                constraint_name
+            }
+            None => {
+                let base = match column {
+                    Some(i) => format!("{}_{}_check", name.item, desc.get_name(i)),
+                    None => format!("{}_check", name.item),
+                };
+                let mut constraint_name = base.clone();
+                let mut suffix = 0;
+                while checks.iter().any(|check| check.name == constraint_name) {
+                    suffix += 1;
+                    constraint_name = format!("{base}{suffix}");
+                }
+                constraint_name
+            }
+        };
+        let expr = query::plan_check_expr(scx, &desc, expr.clone())?;
+        checks.push(TableCheck {
+            name: constraint_name,
+            expr,
+        });
+    }
+
     let create_sql = normalize::create_statement(scx, Statement::CreateTable(stmt.clone()))?;
 
     let options = plan_table_options(scx, with_options.clone())?;
@@ -392,6 +458,7 @@ pub fn plan_create_table(
         create_sql,
         desc,
         defaults,
+        checks,
         temporary,
         compaction_window,
     };
@@ -428,7 +495,10 @@ generate_extracted_config!(
     (IgnoreKeys, bool),
     (Timeline, String),
     (TimestampInterval, Duration),
-    (RetainHistory, OptionalDuration)
+    (RetainHistory, OptionalDuration),
+    (RocksdbBlockCacheSize, ByteSize),
+    (RocksdbCompactionStyle, CompactionStyle),
+    (RocksdbCompressionType, CompressionType)
 );
 
 generate_extracted_config!(
@@ -483,9 +553,9 @@ pub fn plan_create_webhook_source(
     }
 
     let body_format = match body_format {
-        Format::Bytes => WebhookBodyFormat::Bytes,
+        Format::Bytes { .. } => WebhookBodyFormat::Bytes,
         Format::Json { array } => WebhookBodyFormat::Json { array },
-        Format::Text => WebhookBodyFormat::Text,
+        Format::Text { .. } => WebhookBodyFormat::Text,
         // TODO(parkmycar): Make an issue to support more types, or change this to NeverSupported.
         ty => {
             return Err(PlanError::Unsupported {
@@ -631,6 +701,9 @@ pub fn plan_create_source(
     let allowed_with_options = vec![
         CreateSourceOptionName::TimestampInterval,
         CreateSourceOptionName::RetainHistory,
+        CreateSourceOptionName::RocksdbBlockCacheSize,
+        CreateSourceOptionName::RocksdbCompactionStyle,
+        CreateSourceOptionName::RocksdbCompressionType,
     ];
     if let Some(op) = with_options
         .iter()
@@ -647,9 +720,12 @@ pub fn plan_create_source(
     }
 
     if !matches!(connection, CreateSourceConnection::Kafka { .. })
-        && include_metadata
-            .iter()
-            .any(|sic| matches!(sic, SourceIncludeMetadata::Headers { .. }))
+        && include_metadata.iter().any(|sic| {
+            matches!(
+                sic,
+                SourceIncludeMetadata::Headers { .. } | SourceIncludeMetadata::HeadersMap { .. }
+            )
+        })
     {
         // TODO(guswynn): should this be `bail_unsupported!`?
         sql_bail!("INCLUDE HEADERS with non-Kafka sources not supported");
@@ -677,10 +753,12 @@ pub fn plan_create_source(
 
             let KafkaSourceConfigOptionExtracted {
                 group_id_prefix,
+                max_ingest_rate,
                 topic,
                 topic_metadata_refresh_interval,
                 start_timestamp: _, // purified into `start_offset`
                 start_offset,
+                value_decryption_key,
                 seen: _,
             }: KafkaSourceConfigOptionExtracted = options.clone().try_into()?;
 
@@ -706,6 +784,10 @@ pub fn plan_create_source(
                 sql_bail!("TOPIC METADATA REFRESH INTERVAL cannot be greater than 1 hour");
             }
 
+            if max_ingest_rate == Some(0) {
+                sql_bail!("MAX INGEST RATE must be greater than zero");
+            }
+
             if !include_metadata.is_empty()
                 && !matches!(
                     envelope,
@@ -728,6 +810,13 @@ pub fn plan_create_source(
                         };
                         Some((name, KafkaMetadataKind::Timestamp))
                     }
+                    SourceIncludeMetadata::TimestampType { alias } => {
+                        let name = match alias {
+                            Some(name) => name.to_string(),
+                            None => "timestamp_type".to_owned(),
+                        };
+                        Some((name, KafkaMetadataKind::TimestampType))
+                    }
                     SourceIncludeMetadata::Partition { alias } => {
                         let name = match alias {
                             Some(name) => name.to_string(),
@@ -749,6 +838,13 @@ pub fn plan_create_source(
                         };
                         Some((name, KafkaMetadataKind::Headers))
                     }
+                    SourceIncludeMetadata::HeadersMap { alias } => {
+                        let name = match alias {
+                            Some(name) => name.to_string(),
+                            None => "headers".to_owned(),
+                        };
+                        Some((name, KafkaMetadataKind::HeadersMap))
+                    }
                     SourceIncludeMetadata::Header {
                         alias,
                         key,
@@ -775,6 +871,8 @@ pub fn plan_create_source(
                 group_id_prefix,
                 topic_metadata_refresh_interval,
                 metadata_columns,
+                max_ingest_rate,
+                value_decryption_key: value_decryption_key.map(|key| key.into()),
             };
 
             GenericSourceConnection::Kafka(connection)
@@ -1089,6 +1187,9 @@ pub fn plan_create_source(
         timestamp_interval,
         ignore_keys,
         retain_history,
+        rocksdb_block_cache_size,
+        rocksdb_compaction_style,
+        rocksdb_compression_type,
         seen: _,
     } = CreateSourceOptionExtracted::try_from(with_options.clone())?;
 
@@ -1236,7 +1337,26 @@ pub fn plan_create_source(
 
     let metadata_columns = external_connection.metadata_columns();
     let metadata_desc = included_column_desc(metadata_columns.clone());
-    let (envelope, mut desc) = envelope.desc(key_desc, value_desc, metadata_desc)?;
+    let (mut envelope, mut desc) = envelope.desc(key_desc, value_desc, metadata_desc)?;
+
+    let point_lookup_block_cache_size_mb = match rocksdb_block_cache_size {
+        Some(size) => match u32::try_from(size.as_bytes() / (1024 * 1024)) {
+            Ok(size_mb) if size_mb > 0 => Some(size_mb),
+            _ => sql_bail!("ROCKSDB BLOCK CACHE SIZE must be between 1MB and 4PB"),
+        },
+        None => None,
+    };
+    let rocksdb_tuning = RocksDBTuningOverrides {
+        compaction_style: rocksdb_compaction_style,
+        compression_type: rocksdb_compression_type,
+        point_lookup_block_cache_size_mb,
+    };
+    if rocksdb_tuning != RocksDBTuningOverrides::default() {
+        match &mut envelope {
+            SourceEnvelope::Upsert(upsert) => upsert.rocksdb_tuning = rocksdb_tuning,
+            _ => sql_bail!("ROCKSDB options require ENVELOPE UPSERT or ENVELOPE DEBEZIUM"),
+        }
+    }
 
     if ignore_keys.unwrap_or(false) {
         desc = desc.without_keys();
@@ -1574,7 +1694,9 @@ generate_extracted_config!(
     (ValueSize, u64),
     (Seed, u64),
     (Partitions, u64),
-    (BatchSize, u64)
+    (BatchSize, u64),
+    (Columns, Vec<String>),
+    (RowsPerSecond, u64)
 );
 
 impl LoadGeneratorOptionExtracted {
@@ -1602,6 +1724,7 @@ impl LoadGeneratorOptionExtracted {
                 Partitions,
                 BatchSize,
             ],
+            ast::LoadGenerator::Custom => &[AsOf, UpTo, Columns, Keys, Seed, RowsPerSecond],
         };
 
         for o in permitted_options {
@@ -1760,6 +1883,54 @@ pub(crate) fn load_generator_ast_to_generator(
 
             LoadGenerator::KeyValue(lgkv)
         }
+        ast::LoadGenerator::Custom => {
+            scx.require_feature_flag(&vars::ENABLE_LOAD_GENERATOR_CUSTOM)?;
+            let LoadGeneratorOptionExtracted {
+                columns,
+                keys,
+                seed,
+                rows_per_second,
+                ..
+            } = extracted;
+
+            let columns = columns
+                .ok_or_else(|| sql_err!("LOAD GENERATOR CUSTOM requires COLUMNS"))?
+                .iter()
+                .map(|spec| plan_custom_load_generator_column(spec))
+                .collect::<Result<Vec<_>, _>>()?;
+            if columns.is_empty() {
+                sql_bail!("COLUMNS must not be empty");
+            }
+            let mut names = BTreeSet::new();
+            for column in &columns {
+                if !names.insert(&column.name) {
+                    sql_bail!("column {} specified more than once", column.name.quoted());
+                }
+            }
+
+            if keys == Some(0) {
+                sql_bail!("KEYS must be larger than 0");
+            }
+            if keys.is_none()
+                && columns
+                    .iter()
+                    .any(|c| c.distribution == ValueDistribution::Key)
+            {
+                sql_bail!("key columns require KEYS");
+            }
+
+            let rows_per_second = rows_per_second.unwrap_or(1);
+            if rows_per_second == 0 {
+                sql_bail!("ROWS PER SECOND must be larger than 0");
+            }
+
+            LoadGenerator::Custom(CustomLoadGenerator {
+                columns,
+                keys,
+                rows_per_second,
+                seed: seed.unwrap_or(0),
+            })
+        }
     };
 
     let mut available_subsources = BTreeMap::new();
@@ -1785,6 +1956,60 @@ pub(crate) fn load_generator_ast_to_generator(
     Ok((load_generator, available_subsources))
 }
 
+/// Plans a `COLUMNS` entry of a `LOAD GENERATOR CUSTOM`, which has the form
+/// `<name> <distribution>`, e.g. `'amount uniform(1, 100)'`.
+fn plan_custom_load_generator_column(spec: &str) -> Result<CustomLoadGeneratorColumn, PlanError> {
+    let invalid = || {
+        sql_err!(
+            "invalid COLUMNS entry {}: expected '<name> <distribution>'",
+            spec.quoted()
+        )
+    };
+    let (name, distribution) = spec
+        .trim()
+        .split_once(char::is_whitespace)
+        .ok_or_else(invalid)?;
+    let distribution = distribution.trim();
+    let (kind, args) = match distribution.split_once('(') {
+        Some((kind, args)) => {
+            let args = args
+                .strip_suffix(')')
+                .ok_or_else(invalid)?
+                .split(',')
+                .map(|arg| arg.trim().parse::<i64>().map_err(|_| invalid()))
+                .collect::<Result<Vec<_>, _>>()?;
+            (kind.trim(), args)
+        }
+        None => (distribution, vec![]),
+    };
+
+    let distribution = match (kind.to_lowercase().as_str(), args.as_slice()) {
+        ("sequence", []) => ValueDistribution::Sequence,
+        ("key", []) => ValueDistribution::Key,
+        ("uniform", [min, max]) if min <= max => ValueDistribution::Uniform {
+            min: *min,
+            max: *max,
+        },
+        ("normal", [mean, stddev]) if *stddev >= 0 => ValueDistribution::Normal {
+            mean: *mean,
+            stddev: *stddev,
+        },
+        ("text", [len]) if *len >= 0 => ValueDistribution::Text {
+            len: u64::try_from(*len).expect("known to be non-negative"),
+        },
+        _ => sql_bail!(
+            "unsupported distribution {} for column {}",
+            distribution.quoted(),
+            name.quoted()
+        ),
+    };
+
+    Ok(CustomLoadGeneratorColumn {
+        name: name.to_string(),
+        distribution,
+    })
+}
+
 fn typecheck_debezium(value_desc: &RelationDesc) -> Result<(Option<usize>, usize), PlanError> {
     let before = value_desc.get_by_name(&"before".into());
     let (after_idx, after_ty) = value_desc
@@ -1830,6 +2055,21 @@ fn get_encoding(
         sql_bail!("ENVELOPE [DEBEZIUM] UPSERT requires that KEY FORMAT be specified");
     };
 
+    // Splitting messages into several records would give each record the
+    // message's key, so framing is only meaningful for values without an
+    // envelope that interprets keys.
+    if encoding
+        .key
+        .as_ref()
+        .is_some_and(|key| *key.framing() != Framing::None)
+    {
+        sql_bail!("DELIMITER and LENGTH PREFIX are not supported in KEY FORMAT");
+    }
+    if *encoding.value.framing() != Framing::None && !matches!(envelope, ast::SourceEnvelope::None)
+    {
+        sql_bail!("DELIMITER and LENGTH PREFIX require ENVELOPE NONE");
+    }
+
     Ok(encoding)
 }
 
@@ -1862,7 +2102,27 @@ fn source_sink_cluster_config(
     Ok(cluster.id())
 }
 
-generate_extracted_config!(AvroSchemaOption, (ConfluentWireFormat, bool, Default(true)));
+generate_extracted_config!(
+    AvroSchemaOption,
+    (ConfluentWireFormat, bool, Default(true)),
+    (UnionDiscriminant, bool, Default(false)),
+    (UnionTypeNames, bool, Default(false))
+);
+
+generate_extracted_config!(
+    CsvFormatOption,
+    (Quote, String, Default("\"".to_string())),
+    (Escape, String),
+    (EmbeddedNewlines, bool, Default(true))
+);
+
+generate_extracted_config!(FramingOption, (Delimiter, String), (LengthPrefix, u16));
+
+generate_extracted_config!(
+    ProtobufSchemaOption,
+    (WellKnownTypes, bool, Default(false)),
+    (UnpackAny, bool, Default(false))
+);
 
 #[derive(Debug)]
 pub struct Schema {
@@ -1870,6 +2130,8 @@ pub struct Schema {
     pub value_schema: String,
     pub csr_connection: Option<<ReferencedConnection as ConnectionAccess>::Csr>,
     pub confluent_wire_format: bool,
+    pub union_discriminant: bool,
+    pub union_type_names: bool,
 }
 
 fn get_encoding_inner(
@@ -1877,13 +2139,15 @@ fn get_encoding_inner(
     format: &Format<Aug>,
 ) -> Result<SourceDataEncoding<ReferencedConnection>, PlanError> {
     let value = match format {
-        Format::Bytes => DataEncoding::Bytes,
+        Format::Bytes { with_options } => DataEncoding::Bytes(get_framing(with_options)?),
         Format::Avro(schema) => {
             let Schema {
                 key_schema,
                 value_schema,
                 csr_connection,
                 confluent_wire_format,
+                union_discriminant,
+                union_type_names,
             } = match schema {
                 // TODO(jldlaughlin): we need a way to pass in primary key information
                 // when building a source from a string or file.
@@ -1893,6 +2157,8 @@ fn get_encoding_inner(
                 } => {
                     let AvroSchemaOptionExtracted {
                         confluent_wire_format,
+                        union_discriminant,
+                        union_type_names,
                         ..
                     } = with_options.clone().try_into()?;
 
@@ -1901,6 +2167,8 @@ fn get_encoding_inner(
                         value_schema: schema.clone(),
                         csr_connection: None,
                         confluent_wire_format,
+                        union_discriminant,
+                        union_type_names,
                     }
                 }
                 AvroSchema::Csr {
@@ -1932,6 +2200,8 @@ fn get_encoding_inner(
                             value_schema: seed.value_schema.clone(),
                             csr_connection: Some(csr_connection),
                             confluent_wire_format: true,
+                            union_discriminant: false,
+                            union_type_names: false,
                         }
                     } else {
                         unreachable!("CSR seed resolution should already have been called: Avro")
@@ -1945,11 +2215,15 @@ fn get_encoding_inner(
                         schema: key_schema,
                         csr_connection: csr_connection.clone(),
                         confluent_wire_format,
+                        union_discriminant,
+                        union_type_names,
                     })),
                     value: DataEncoding::Avro(AvroEncoding {
                         schema: value_schema,
                         csr_connection,
                         confluent_wire_format,
+                        union_discriminant,
+                        union_type_names,
                     }),
                 });
             } else {
@@ -1957,6 +2231,8 @@ fn get_encoding_inner(
                     schema: value_schema,
                     csr_connection,
                     confluent_wire_format,
+                    union_discriminant,
+                    union_type_names,
                 })
             }
         }
@@ -1995,6 +2271,8 @@ fn get_encoding_inner(
                         descriptors: strconv::parse_bytes(&value.schema)?,
                         message_name: value.message_name.clone(),
                         confluent_wire_format: true,
+                        well_known_types: false,
+                        unpack_any: false,
                     });
                     if let Some(key) = key {
                         return Ok(SourceDataEncoding {
@@ -2002,6 +2280,8 @@ fn get_encoding_inner(
                                 descriptors: strconv::parse_bytes(&key.schema)?,
                                 message_name: key.message_name.clone(),
                                 confluent_wire_format: true,
+                                well_known_types: false,
+                                unpack_any: false,
                             })),
                             value,
                         });
@@ -2014,13 +2294,21 @@ fn get_encoding_inner(
             ProtobufSchema::InlineSchema {
                 message_name,
                 schema: ast::Schema { schema },
+                with_options,
             } => {
                 let descriptors = strconv::parse_bytes(schema)?;
+                let ProtobufSchemaOptionExtracted {
+                    well_known_types,
+                    unpack_any,
+                    ..
+                } = with_options.clone().try_into()?;
 
                 DataEncoding::Protobuf(ProtobufEncoding {
                     descriptors,
                     message_name: message_name.to_owned(),
                     confluent_wire_format: false,
+                    well_known_types,
+                    unpack_any,
                 })
             }
         },
@@ -2028,7 +2316,11 @@ fn get_encoding_inner(
             regex: mz_repr::adt::regex::Regex::new(regex.clone(), false)
                 .map_err(|e| sql_err!("parsing regex: {e}"))?,
         }),
-        Format::Csv { columns, delimiter } => {
+        Format::Csv {
+            columns,
+            delimiter,
+            with_options,
+        } => {
             let columns = match columns {
                 CsvColumns::Header { names } => {
                     if names.is_empty() {
@@ -2040,19 +2332,95 @@ fn get_encoding_inner(
                 }
                 CsvColumns::Count(n) => ColumnSpec::Count(usize::cast_from(*n)),
             };
+            let CsvFormatOptionExtracted {
+                quote,
+                escape,
+                embedded_newlines,
+                ..
+            } = with_options.clone().try_into()?;
+            let extract_byte = |v: String, option_name: &str| match v.as_bytes() {
+                [b] if b.is_ascii() => Ok(*b),
+                _ => sql_bail!("CSV {} must be a single ASCII character", option_name),
+            };
+            let quote = extract_byte(quote, "QUOTE")?;
+            let escape = escape.map(|e| extract_byte(e, "ESCAPE")).transpose()?;
+            let delimiter = delimiter.as_bytes().to_vec();
+            if delimiter.is_empty() {
+                sql_bail!("CSV delimiter must not be empty");
+            }
+            if delimiter.contains(&quote) {
+                sql_bail!("CSV delimiter must not contain the QUOTE character");
+            }
+            if let Some(escape) = escape {
+                if delimiter.contains(&escape) {
+                    sql_bail!("CSV delimiter must not contain the ESCAPE character");
+                }
+            }
+            if delimiter.iter().any(|b| matches!(b, b'\n' | b'\r')) {
+                sql_bail!("CSV delimiter must not contain newline characters");
+            }
             DataEncoding::Csv(CsvEncoding {
                 columns,
-                delimiter: u8::try_from(*delimiter)
-                    .map_err(|_| sql_err!("CSV delimiter must be an ASCII character"))?,
+                delimiter,
+                quote,
+                escape,
+                embedded_newlines,
             })
         }
         Format::Json { array: false } => DataEncoding::Json,
         Format::Json { array: true } => bail_unsupported!("JSON ARRAY format in sources"),
-        Format::Text => DataEncoding::Text,
+        Format::JsonShred { columns } => {
+            let mut names = BTreeSet::new();
+            let columns = columns
+                .iter()
+                .map(|column| {
+                    let name = normalize::ident(column.name.clone());
+                    if !names.insert(name.clone()) {
+                        sql_bail!("column {} specified more than once", name.quoted());
+                    }
+                    let path = JsonPath::parse(&column.path).map_err(|e| sql_err!("{e}"))?;
+                    let typ = query::scalar_type_from_sql(scx, &column.data_type)?;
+                    if !JsonShredColumn::supports_type(&typ) {
+                        sql_bail!(
+                            "JSON SHRED does not support columns of type {}",
+                            scx.humanize_scalar_type(&typ)
+                        );
+                    }
+                    Ok(JsonShredColumn { name, path, typ })
+                })
+                .collect::<Result<_, PlanError>>()?;
+            DataEncoding::JsonShred(JsonShredEncoding { columns })
+        }
+        Format::Text { with_options } => DataEncoding::Text(get_framing(with_options)?),
     };
     Ok(SourceDataEncoding { key: None, value })
 }
 
+/// Plans the options that split `TEXT` and `BYTES` messages into records.
+fn get_framing(with_options: &[FramingOption<Aug>]) -> Result<Framing, PlanError> {
+    let FramingOptionExtracted {
+        delimiter,
+        length_prefix,
+        ..
+    } = with_options.to_vec().try_into()?;
+    match (delimiter, length_prefix) {
+        (None, None) => Ok(Framing::None),
+        (Some(delimiter), None) => {
+            if delimiter.is_empty() {
+                sql_bail!("DELIMITER must not be empty");
+            }
+            Ok(Framing::Delimited(delimiter.into_bytes()))
+        }
+        (None, Some(width @ (1 | 2 | 4 | 8))) => Ok(Framing::LengthPrefixed {
+            width: u8::try_from(width).expect("width is at most 8"),
+        }),
+        (None, Some(width)) => {
+            sql_bail!("LENGTH PREFIX must be 1, 2, 4, or 8 bytes, got {width}")
+        }
+        (Some(_), Some(_)) => sql_bail!("cannot specify both DELIMITER and LENGTH PREFIX"),
+    }
+}
+
 /// Extract the key envelope, if it is requested
 fn get_key_envelope(
     included_items: &[SourceIncludeMetadata],
@@ -2094,10 +2462,11 @@ fn get_unnamed_key_envelope(
     //
     // Otherwise it gets the names of the columns in the type
     let is_composite = match key {
-        Some(DataEncoding::Bytes | DataEncoding::Json | DataEncoding::Text) => false,
+        Some(DataEncoding::Bytes(_) | DataEncoding::Json | DataEncoding::Text(_)) => false,
         Some(
             DataEncoding::Avro(_)
             | DataEncoding::Csv(_)
+            | DataEncoding::JsonShred(_)
             | DataEncoding::Protobuf(_)
             | DataEncoding::Regex { .. },
         ) => true,
@@ -2302,11 +2671,16 @@ pub fn plan_create_materialized_view(
 
     let MaterializedViewOptionExtracted {
         assert_not_null,
+        assert_append_only,
         retain_history,
         refresh,
         seen: _,
     }: MaterializedViewOptionExtracted = stmt.with_options.try_into()?;
 
+    if assert_append_only {
+        scx.require_feature_flag(&ENABLE_APPEND_ONLY_MVS)?;
+    }
+
     let refresh_schedule = {
         let mut refresh_schedule = RefreshSchedule::empty();
         let mut on_commits_seen = 0;
@@ -2518,6 +2892,7 @@ pub fn plan_create_materialized_view(
             column_names,
             cluster_id,
             non_null_assertions,
+            append_only: assert_append_only,
             compaction_window,
             refresh_schedule,
             as_of,
@@ -2532,6 +2907,7 @@ pub fn plan_create_materialized_view(
 generate_extracted_config!(
     MaterializedViewOption,
     (AssertNotNull, Ident, AllowMultiple),
+    (AssertAppendOnly, bool, Default(false)),
     (RetainHistory, OptionalDuration),
     (Refresh, RefreshOptionValue<Aug>, AllowMultiple)
 );
@@ -2820,6 +3196,7 @@ pub struct CsrConfigOptionExtracted {
     pub(crate) key_doc_options: BTreeMap<DocTarget, String>,
     pub(crate) key_compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
     pub(crate) value_compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
+    pub(crate) schema_references: bool,
 }
 
 impl std::convert::TryFrom<Vec<CsrConfigOption<Aug>>> for CsrConfigOptionExtracted {
@@ -2903,6 +3280,10 @@ impl std::convert::TryFrom<Vec<CsrConfigOption<Aug>>> for CsrConfigOptionExtract
                 CsrConfigOptionName::ValueCompatibilityLevel => {
                     extracted.value_compatibility_level = to_compatibility_level(option.value)?;
                 }
+                CsrConfigOptionName::SchemaReferences => {
+                    extracted.schema_references =
+                        <bool>::try_from_value(option.value).map_err(better_error)?;
+                }
             }
         }
 
@@ -2944,6 +3325,7 @@ fn kafka_sink_builder(
     let KafkaSinkConfigOptionExtracted {
         topic,
         compression_type,
+        delivery_guarantee,
         progress_group_id_prefix,
         transactional_id_prefix,
         legacy_ids,
@@ -2951,9 +3333,17 @@ fn kafka_sink_builder(
         topic_partition_count,
         topic_replication_factor,
         progress_topic_replication_factor,
+        transaction_metadata_topic,
+        partition_by,
         seen: _,
     }: KafkaSinkConfigOptionExtracted = options.try_into()?;
 
+    if delivery_guarantee == KafkaSinkDeliveryGuarantee::AtLeastOnce
+        && transactional_id_prefix.is_some()
+    {
+        sql_bail!("TRANSACTIONAL ID PREFIX cannot be used with DELIVERY GUARANTEE 'at-least-once'");
+    }
+
     let transactional_id = match (transactional_id_prefix, legacy_ids) {
         (Some(_), Some(true)) => {
             sql_bail!("LEGACY IDS cannot be used at the same time as TRANSACTIONAL ID PREFIX")
@@ -2972,6 +3362,31 @@ fn kafka_sink_builder(
 
     let topic_name = topic.ok_or_else(|| sql_err!("KAFKA CONNECTION must specify TOPIC"))?;
 
+    if let Some(transaction_metadata_topic) = &transaction_metadata_topic {
+        if !matches!(envelope, SinkEnvelope::Debezium) {
+            sql_bail!("TRANSACTION METADATA TOPIC requires ENVELOPE DEBEZIUM");
+        }
+        if *transaction_metadata_topic == topic_name {
+            sql_bail!("TRANSACTION METADATA TOPIC must differ from TOPIC");
+        }
+    }
+
+    let partition_by = match partition_by {
+        Some(partition_by) => {
+            // Partitioning on the key guarantees that all updates to a key, including its
+            // deletion, land in the same partition.
+            let Some((key_desc, _indices)) = &key_desc_and_indices else {
+                sql_bail!("PARTITION BY requires a KEY");
+            };
+            let partition_by = query::plan_kafka_sink_partition_by(scx, key_desc, partition_by)?;
+            if partition_by.contains_unmaterializable() || partition_by.contains_temporal() {
+                sql_bail!("PARTITION BY expression must be deterministic");
+            }
+            Some(partition_by)
+        }
+        None => None,
+    };
+
     let assert_positive = |val: Option<i32>, name: &str| {
         if let Some(val) = val {
             if val <= 0 {
@@ -3036,6 +3451,7 @@ fn kafka_sink_builder(
                 value_doc_options,
                 key_compatibility_level,
                 value_compatibility_level,
+                schema_references,
                 seen: _,
             } = options.try_into()?;
 
@@ -3054,6 +3470,7 @@ fn kafka_sink_builder(
                 avro_value_fullname,
                 set_null_defaults: null_defaults,
                 is_debezium: matches!(envelope, SinkEnvelope::Debezium),
+                debezium_transaction: transaction_metadata_topic.is_some(),
                 sink_from: Some(sink_from),
                 value_doc_options,
                 key_doc_options,
@@ -3077,6 +3494,7 @@ fn kafka_sink_builder(
                 csr_connection,
                 key_compatibility_level,
                 value_compatibility_level,
+                schema_references,
             }
         }
         Some(Format::Json { array: false }) => KafkaSinkFormat::Json,
@@ -3095,8 +3513,11 @@ fn kafka_sink_builder(
         headers_index,
         value_desc,
         compression_type,
+        delivery_guarantee,
         progress_group_id,
         transactional_id,
+        transaction_metadata_topic,
+        partition_by,
         topic_options: KafkaSinkTopicOptions {
             partition_count: topic_partition_count,
             replication_factor: topic_replication_factor,
@@ -3411,10 +3832,16 @@ pub enum PlannedAlterRoleOption {
 #[derive(Debug)]
 pub struct PlannedRoleAttributes {
     pub inherit: Option<bool>,
+    /// The SCRAM verifier of the role's new password, if the password is
+    /// changed. `Some(None)` removes the role's password.
+    pub scram_verifier: Option<Option<String>>,
 }
 
 fn plan_role_attributes(options: Vec<RoleAttribute>) -> Result<PlannedRoleAttributes, PlanError> {
-    let mut planned_attributes = PlannedRoleAttributes { inherit: None };
+    let mut planned_attributes = PlannedRoleAttributes {
+        inherit: None,
+        scram_verifier: None,
+    };
 
     for option in options {
         match option {
@@ -3429,6 +3856,9 @@ fn plan_role_attributes(options: Vec<RoleAttribute>) -> Result<PlannedRoleAttrib
             {
                 sql_bail!("conflicting or redundant options");
             }
+            RoleAttribute::Password(_) if planned_attributes.scram_verifier.is_some() => {
+                sql_bail!("conflicting or redundant options");
+            }
             RoleAttribute::CreateCluster | RoleAttribute::NoCreateCluster => {
                 bail_never_supported!(
                     "CREATECLUSTER attribute",
@@ -3453,6 +3883,17 @@ fn plan_role_attributes(options: Vec<RoleAttribute>) -> Result<PlannedRoleAttrib
 
             RoleAttribute::Inherit => planned_attributes.inherit = Some(true),
             RoleAttribute::NoInherit => planned_attributes.inherit = Some(false),
+            RoleAttribute::Password(None) => planned_attributes.scram_verifier = Some(None),
+            RoleAttribute::Password(Some(password)) => {
+                if password.is_empty() {
+                    sql_bail!("empty string is not a valid password");
+                }
+                // Only a verifier of the password is stored, so that the
+                // password can't be recovered from the catalog.
+                let verifier = ScramVerifier::new(&password)
+                    .map_err(|e| sql_err!("failed to hash password: {}", e))?;
+                planned_attributes.scram_verifier = Some(Some(verifier.to_string()));
+            }
         }
     }
     if planned_attributes.inherit == Some(false) {
@@ -3522,6 +3963,8 @@ pub fn describe_create_cluster(
 // to ALTER CLUSTER would always reset the value of that option to the default.
 generate_extracted_config!(
     ClusterOption,
+    (AutoscaleMaxSize, String),
+    (AutoscaleMinSize, String),
     (AvailabilityZones, Vec<String>),
     (Disk, bool),
     (IntrospectionDebugging, bool),
@@ -3542,6 +3985,8 @@ generate_extracted_config!(
     (EnableLetrecFixpointAnalysis, Option<bool>, Default(None))
 );
 
+generate_extracted_config!(ClusterAlterOption, (Graceful, bool, Default(false)));
+
 pub fn plan_create_cluster(
     scx: &StatementContext,
     CreateClusterStatement {
@@ -3551,6 +3996,8 @@ pub fn plan_create_cluster(
     }: CreateClusterStatement<Aug>,
 ) -> Result<Plan, PlanError> {
     let ClusterOptionExtracted {
+        autoscale_max_size,
+        autoscale_min_size,
         availability_zones,
         introspection_debugging,
         introspection_interval,
@@ -3592,7 +4039,7 @@ pub fn plan_create_cluster(
         let replication_factor = if matches!(schedule, ClusterScheduleOptionValue::Manual) {
             replication_factor.unwrap_or(1)
         } else {
-            scx.require_feature_flag(&ENABLE_CLUSTER_SCHEDULE_REFRESH)?;
+            require_cluster_schedule_feature_flag(scx, &schedule)?;
             if replication_factor.is_some() {
                 sql_bail!("REPLICATION FACTOR cannot be given together with any SCHEDULE other than MANUAL");
             }
@@ -3642,6 +4089,7 @@ pub fn plan_create_cluster(
         };
 
         let schedule = plan_cluster_schedule(schedule)?;
+        let autoscale = plan_cluster_autoscale(scx, autoscale_min_size, autoscale_max_size)?;
 
         Ok(Plan::CreateCluster(CreateClusterPlan {
             name: normalize::ident(name),
@@ -3653,6 +4101,7 @@ pub fn plan_create_cluster(
                 disk,
                 optimizer_feature_overrides,
                 schedule,
+                autoscale,
             }),
         }))
     } else {
@@ -3685,6 +4134,9 @@ pub fn plan_create_cluster(
                 "cluster schedules other than MANUAL are not supported for unmanaged clusters"
             );
         }
+        if autoscale_min_size.is_some() || autoscale_max_size.is_some() {
+            sql_bail!("AUTOSCALE not supported for unmanaged clusters");
+        }
 
         let mut replicas = vec![];
         for ReplicaDefinition { name, options } in replica_defs {
@@ -3871,11 +4323,56 @@ fn plan_compute_replica_config(
     Ok(compute)
 }
 
+/// Plans the `AUTOSCALE MIN SIZE` and `AUTOSCALE MAX SIZE` options, which have to be given
+/// together. Whether the sizes exist and are ordered correctly is checked when sequencing, where
+/// the available replica sizes are known.
+fn plan_cluster_autoscale(
+    scx: &StatementContext,
+    min_size: Option<String>,
+    max_size: Option<String>,
+) -> Result<Option<ClusterAutoscale>, PlanError> {
+    match (min_size, max_size) {
+        (None, None) => Ok(None),
+        (Some(min_size), Some(max_size)) => {
+            scx.require_feature_flag(&ENABLE_CLUSTER_AUTOSCALING)?;
+            Ok(Some(ClusterAutoscale { min_size, max_size }))
+        }
+        _ => sql_bail!("AUTOSCALE MIN SIZE and AUTOSCALE MAX SIZE must be specified together"),
+    }
+}
+
+/// Ensures that the feature flag gating the given cluster schedule is enabled.
+fn require_cluster_schedule_feature_flag(
+    scx: &StatementContext,
+    schedule: &ClusterScheduleOptionValue,
+) -> Result<(), PlanError> {
+    match schedule {
+        ClusterScheduleOptionValue::Manual => Ok(()),
+        ClusterScheduleOptionValue::Refresh { .. } => {
+            scx.require_feature_flag(&ENABLE_CLUSTER_SCHEDULE_REFRESH)
+        }
+        ClusterScheduleOptionValue::OffDuring { .. } => {
+            scx.require_feature_flag(&ENABLE_CLUSTER_SCHEDULE_OFF_DURING)
+        }
+    }
+}
+
 fn plan_cluster_schedule(
     schedule: ClusterScheduleOptionValue,
 ) -> Result<ClusterSchedule, PlanError> {
     Ok(match schedule {
         ClusterScheduleOptionValue::Manual => ClusterSchedule::Manual,
+        ClusterScheduleOptionValue::OffDuring { windows } => {
+            let windows = windows
+                .iter()
+                .map(|window| {
+                    CronSchedule::parse(window).map_err(|e| {
+                        sql_err!("invalid OFF DURING window {}: {}", window.quoted(), e)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            ClusterSchedule::OffDuring { windows }
+        }
         // If `REHYDRATION TIME ESTIMATE` is not explicitly given, we default to 0.
         ClusterScheduleOptionValue::Refresh {
             rehydration_time_estimate: None,
@@ -4744,10 +5241,23 @@ pub fn plan_alter_cluster(
     };
 
     let mut options: PlanClusterOption = Default::default();
+    let mut strategy = AlterClusterPlanStrategy::None;
 
     match action {
-        AlterClusterAction::SetOptions(set_options) => {
+        AlterClusterAction::SetOptions {
+            options: set_options,
+            with_options,
+        } => {
+            let ClusterAlterOptionExtracted { graceful, seen: _ } =
+                ClusterAlterOptionExtracted::try_from(with_options)?;
+            if graceful {
+                scx.require_feature_flag(&ENABLE_GRACEFUL_CLUSTER_RECONFIGURATION)?;
+                strategy = AlterClusterPlanStrategy::Graceful;
+            }
+
             let ClusterOptionExtracted {
+                autoscale_max_size,
+                autoscale_min_size,
                 availability_zones,
                 introspection_debugging,
                 introspection_interval,
@@ -4765,10 +5275,8 @@ pub fn plan_alter_cluster(
                     if replica_defs.is_some() {
                         sql_bail!("REPLICAS not supported for managed clusters");
                     }
-                    if schedule.is_some()
-                        && !matches!(schedule, Some(ClusterScheduleOptionValue::Manual))
-                    {
-                        scx.require_feature_flag(&ENABLE_CLUSTER_SCHEDULE_REFRESH)?;
+                    if let Some(schedule) = &schedule {
+                        require_cluster_schedule_feature_flag(scx, schedule)?;
                     }
 
                     if let Some(replication_factor) = replication_factor {
@@ -4819,6 +5327,9 @@ pub fn plan_alter_cluster(
                     if disk.is_some() {
                         sql_bail!("DISK not supported for unmanaged clusters");
                     }
+                    if autoscale_min_size.is_some() || autoscale_max_size.is_some() {
+                        sql_bail!("AUTOSCALE not supported for unmanaged clusters");
+                    }
                     if schedule.is_some()
                         && !matches!(schedule, Some(ClusterScheduleOptionValue::Manual))
                     {
@@ -4904,12 +5415,18 @@ pub fn plan_alter_cluster(
             if let Some(schedule) = schedule {
                 options.schedule = AlterOptionParameter::Set(plan_cluster_schedule(schedule)?);
             }
+            if let Some(autoscale) =
+                plan_cluster_autoscale(scx, autoscale_min_size, autoscale_max_size)?
+            {
+                options.autoscale = AlterOptionParameter::Set(autoscale);
+            }
         }
         AlterClusterAction::ResetOptions(reset_options) => {
             use AlterOptionParameter::Reset;
             use ClusterOptionName::*;
             for option in reset_options {
                 match option {
+                    AutoscaleMaxSize | AutoscaleMinSize => options.autoscale = Reset,
                     AvailabilityZones => options.availability_zones = Reset,
                     Disk => options.disk = Reset,
                     IntrospectionInterval => options.introspection_interval = Reset,
@@ -4927,6 +5444,7 @@ pub fn plan_alter_cluster(
         id: cluster.id(),
         name: cluster.name().to_string(),
         options,
+        strategy,
     }))
 }
 
@@ -5747,14 +6265,16 @@ pub fn plan_alter_source(
     } = stmt;
     let object_type = ObjectType::Source;
 
-    if resolve_item_or_type(scx, object_type, source_name.clone(), if_exists)?.is_none() {
+    let Some(source) = resolve_item_or_type(scx, object_type, source_name.clone(), if_exists)?
+    else {
         scx.catalog.add_notice(PlanNotice::ObjectDoesNotExist {
             name: source_name.to_string(),
             object_type,
         });
 
         return Ok(Plan::AlterNoop(AlterNoopPlan { object_type }));
-    }
+    };
+    let source_id = source.id();
 
     match action {
         AlterSourceAction::SetOptions(options) => {
@@ -5802,7 +6322,18 @@ pub fn plan_alter_source(
         AlterSourceAction::AddSubsources { .. } => {
             unreachable!("ALTER SOURCE...ADD SUBSOURCE must be purified")
         }
-    };
+        AlterSourceAction::SwapConnection { connection } => {
+            // Purification has verified that the source and connection are
+            // both Kafka.
+            let connection = scx.get_item_by_resolved_name(&connection)?;
+            Ok(Plan::AlterSource(AlterSourcePlan {
+                id: source_id,
+                action: crate::plan::AlterSourceAction::SwapConnection {
+                    connection_id: connection.id(),
+                },
+            }))
+        }
+    }
 }
 
 pub fn describe_alter_system_set(
@@ -5881,6 +6412,40 @@ pub fn plan_alter_role(
     }))
 }
 
+pub fn describe_alter_database(
+    _: &StatementContext,
+    _: AlterDatabaseStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_database(
+    _scx: &StatementContext,
+    AlterDatabaseStatement { name, variable }: AlterDatabaseStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let (id, name) = match name {
+        ResolvedDatabaseName::Database { id, name } => (id, name),
+        ResolvedDatabaseName::Error => unreachable!("should have been handled in name resolution"),
+    };
+    let variable = plan_role_variable(variable)?;
+    // The database of a session is chosen before its database defaults are known.
+    if variable.name().eq_ignore_ascii_case(vars::DATABASE.name()) {
+        sql_bail!("cannot set a default for parameter \"database\" in a database");
+    }
+    if vars::is_role_limit_var(variable.name()) {
+        sql_bail!(
+            "parameter {} can only be set with ALTER ROLE",
+            variable.name().quoted()
+        );
+    }
+
+    Ok(Plan::AlterDatabase(AlterDatabasePlan {
+        id,
+        name,
+        variable,
+    }))
+}
+
 pub fn describe_comment(
     _: &StatementContext,
     _: CommentStatement<Aug>,
@@ -14,6 +14,7 @@ use std::collections::{BTreeMap, BTreeSet};
 use array_concat::concat_arrays;
 use itertools::Itertools;
 use mz_ore::str::StrExt;
+use mz_repr::GlobalId;
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::ConnectionOptionName::*;
 use mz_sql_parser::ast::{
@@ -270,8 +271,11 @@ impl ConnectionOptionExtracted {
 
                 Connection::Kafka(KafkaConnection {
                     brokers: self.get_brokers(scx)?,
-                    default_tunnel: scx
-                        .build_tunnel_definition(self.ssh_tunnel, self.aws_privatelink)?,
+                    default_tunnel: build_tunnel_definition(
+                        scx,
+                        self.ssh_tunnel,
+                        self.aws_privatelink,
+                    )?,
                     progress_topic: self.progress_topic,
                     options: BTreeMap::new(),
                     tls,
@@ -305,13 +309,13 @@ impl ConnectionOptionExtracted {
                     password: self.password.map(|secret| secret.into()),
                 });
 
+                let tunnel = build_tunnel_definition(scx, self.ssh_tunnel, self.aws_privatelink)?;
                 // TODO we should move to self.port being unsupported if aws_privatelink is some, see <https://github.com/MaterializeInc/materialize/issues/24712#issuecomment-1925443977>
-                if let Some(privatelink) = self.aws_privatelink.as_ref() {
-                    if privatelink.port.is_some() {
-                        sql_bail!("invalid CONNECTION: PORT in AWS PRIVATELINK is only supported for kafka")
-                    }
+                if let Tunnel::AwsPrivatelink(AwsPrivatelink { port: Some(_), .. }) = &tunnel {
+                    sql_bail!(
+                        "invalid CONNECTION: PORT in AWS PRIVATELINK is only supported for kafka"
+                    )
                 }
-                let tunnel = scx.build_tunnel_definition(self.ssh_tunnel, self.aws_privatelink)?;
 
                 Connection::Csr(CsrConnection {
                     url,
@@ -345,13 +349,13 @@ impl ConnectionOptionExtracted {
                     Some(m) => sql_bail!("invalid CONNECTION: unknown SSL MODE {}", m.quoted()),
                 };
 
+                let tunnel = build_tunnel_definition(scx, self.ssh_tunnel, self.aws_privatelink)?;
                 // TODO we should move to self.port being unsupported if aws_privatelink is some, see <https://github.com/MaterializeInc/materialize/issues/24712#issuecomment-1925443977>
-                if let Some(privatelink) = self.aws_privatelink.as_ref() {
-                    if privatelink.port.is_some() {
-                        sql_bail!("invalid CONNECTION: PORT in AWS PRIVATELINK is only supported for kafka")
-                    }
+                if let Tunnel::AwsPrivatelink(AwsPrivatelink { port: Some(_), .. }) = &tunnel {
+                    sql_bail!(
+                        "invalid CONNECTION: PORT in AWS PRIVATELINK is only supported for kafka"
+                    )
                 }
-                let tunnel = scx.build_tunnel_definition(self.ssh_tunnel, self.aws_privatelink)?;
 
                 Connection::Postgres(PostgresConnection {
                     database: self
@@ -417,13 +421,13 @@ impl ConnectionOptionExtracted {
                     Some(m) => sql_bail!("invalid CONNECTION: unknown SSL MODE {}", m.quoted()),
                 };
 
+                let tunnel = build_tunnel_definition(scx, self.ssh_tunnel, self.aws_privatelink)?;
                 // TODO we should move to self.port being unsupported if aws_privatelink is some, see <https://github.com/MaterializeInc/materialize/issues/24712#issuecomment-1925443977>
-                if let Some(privatelink) = self.aws_privatelink.as_ref() {
-                    if privatelink.port.is_some() {
-                        sql_bail!("invalid CONNECTION: PORT in AWS PRIVATELINK is only supported for kafka")
-                    }
+                if let Tunnel::AwsPrivatelink(AwsPrivatelink { port: Some(_), .. }) = &tunnel {
+                    sql_bail!(
+                        "invalid CONNECTION: PORT in AWS PRIVATELINK is only supported for kafka"
+                    )
                 }
-                let tunnel = scx.build_tunnel_definition(self.ssh_tunnel, self.aws_privatelink)?;
 
                 Connection::MySql(MySqlConnection {
                     password: self.password.map(|password| password.into()),
@@ -474,43 +478,11 @@ Instead, specify BROKERS using multiple strings, e.g. BROKERS ('kafka:9092', 'ka
 
             let tunnel = match &broker.tunnel {
                 KafkaBrokerTunnel::Direct => Tunnel::Direct,
-                KafkaBrokerTunnel::AwsPrivatelink(aws_privatelink) => {
-                    let KafkaBrokerAwsPrivatelinkOptionExtracted {
-                        availability_zone,
-                        port,
-                        seen: _,
-                    } = KafkaBrokerAwsPrivatelinkOptionExtracted::try_from(
-                        aws_privatelink.options.clone(),
-                    )?;
-
-                    let id = match &aws_privatelink.connection {
-                        ResolvedItemName::Item { id, .. } => id,
-                        _ => sql_bail!(
-                            "internal error: Kafka PrivateLink connection was not resolved"
-                        ),
-                    };
-                    let entry = scx.catalog.get_item(id);
-                    match entry.connection()? {
-                        Connection::AwsPrivatelink(connection) => {
-                            if let Some(az) = &availability_zone {
-                                if !connection.availability_zones.contains(az) {
-                                    sql_bail!("AWS PrivateLink availability zone {} does not match any of the \
-                                      availability zones on the AWS PrivateLink connection {}",
-                                      az.quoted(),
-                                        scx.catalog.resolve_full_name(entry.name()).to_string().quoted())
-                                }
-                            }
-                            Tunnel::AwsPrivatelink(AwsPrivatelink {
-                                connection_id: *id,
-                                availability_zone,
-                                port,
-                            })
-                        }
-                        _ => {
-                            sql_bail!("{} is not an AWS PRIVATELINK connection", entry.name().item)
-                        }
-                    }
-                }
+                KafkaBrokerTunnel::AwsPrivatelink(aws_privatelink) => plan_aws_privatelink_tunnel(
+                    scx,
+                    &aws_privatelink.connection,
+                    aws_privatelink.options.clone(),
+                )?,
                 KafkaBrokerTunnel::SshTunnel(ssh) => {
                     let id = match &ssh {
                         ResolvedItemName::Item { id, .. } => id,
@@ -541,6 +513,79 @@ Instead, specify BROKERS using multiple strings, e.g. BROKERS ('kafka:9092', 'ka
     }
 }
 
+fn build_tunnel_definition(
+    scx: &StatementContext,
+    ssh_tunnel: Option<with_options::Object>,
+    aws_privatelink: Option<ConnectionDefaultAwsPrivatelink<Aug>>,
+) -> Result<Tunnel<ReferencedConnection>, PlanError> {
+    match (ssh_tunnel, aws_privatelink) {
+        (None, None) => Ok(Tunnel::Direct),
+        (Some(ssh_tunnel), None) => {
+            let id = GlobalId::from(ssh_tunnel);
+            let ssh_tunnel = scx.catalog.get_item(&id);
+            match ssh_tunnel.connection()? {
+                Connection::Ssh(_connection) => Ok(Tunnel::Ssh(SshTunnel {
+                    connection_id: id,
+                    connection: id,
+                })),
+                _ => sql_bail!("{} is not an SSH connection", ssh_tunnel.name().item),
+            }
+        }
+        (None, Some(aws_privatelink)) => {
+            plan_aws_privatelink_tunnel(scx, &aws_privatelink.connection, aws_privatelink.options)
+        }
+        (Some(_), Some(_)) => {
+            sql_bail!("cannot specify both SSH TUNNEL and AWS PRIVATELINK");
+        }
+    }
+}
+
+/// Plans a tunnel through the AWS PrivateLink connection named by `connection`.
+///
+/// If an `AVAILABILITY ZONE` is specified, it must be one of the availability
+/// zones of the PrivateLink connection; otherwise, the tunnel uses the
+/// endpoint spanning all of the connection's availability zones.
+fn plan_aws_privatelink_tunnel(
+    scx: &StatementContext,
+    connection: &ResolvedItemName,
+    options: Vec<KafkaBrokerAwsPrivatelinkOption<Aug>>,
+) -> Result<Tunnel<ReferencedConnection>, PlanError> {
+    let KafkaBrokerAwsPrivatelinkOptionExtracted {
+        availability_zone,
+        port,
+        seen: _,
+    } = KafkaBrokerAwsPrivatelinkOptionExtracted::try_from(options)?;
+
+    let id = match connection {
+        ResolvedItemName::Item { id, .. } => id,
+        _ => sql_bail!("internal error: PrivateLink connection was not resolved"),
+    };
+    let entry = scx.catalog.get_item(id);
+    match entry.connection()? {
+        Connection::AwsPrivatelink(connection) => {
+            if let Some(az) = &availability_zone {
+                if !connection.availability_zones.contains(az) {
+                    sql_bail!(
+                        "AWS PrivateLink availability zone {} does not match any of the \
+                        availability zones on the AWS PrivateLink connection {}",
+                        az.quoted(),
+                        scx.catalog
+                            .resolve_full_name(entry.name())
+                            .to_string()
+                            .quoted()
+                    )
+                }
+            }
+            Ok(Tunnel::AwsPrivatelink(AwsPrivatelink {
+                connection_id: *id,
+                availability_zone,
+                port,
+            }))
+        }
+        _ => sql_bail!("{} is not an AWS PRIVATELINK connection", entry.name().item),
+    }
+}
+
 fn plan_kafka_security(
     v: &ConnectionOptionExtracted,
 ) -> Result<(Option<KafkaTlsConfig>, Option<KafkaSaslConfig>), PlanError> {
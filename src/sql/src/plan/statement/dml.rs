@@ -28,9 +28,10 @@ use mz_repr::explain::{ExplainConfig, ExplainFormat};
 use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::{Datum, GlobalId, RelationDesc, ScalarType};
 use mz_sql_parser::ast::{
-    CteBlock, ExplainPlanOption, ExplainPlanOptionName, ExplainPushdownStatement,
-    ExplainSinkSchemaFor, ExplainSinkSchemaStatement, ExplainTimestampStatement, Expr,
-    IfExistsBehavior, OrderByExpr, SetExpr, SubscribeOutput, UnresolvedItemName,
+    CteBlock, ExplainAnalyzeStatement, ExplainPlanOption, ExplainPlanOptionName,
+    ExplainPushdownStatement, ExplainSinkSchemaFor, ExplainSinkSchemaStatement,
+    ExplainTimestampStatement, Expr, IfExistsBehavior, OrderByExpr, SetExpr, SubscribeOutput,
+    UnresolvedItemName,
 };
 use mz_sql_parser::ident;
 use mz_storage_types::sinks::{
@@ -50,6 +51,7 @@ use crate::names::{Aug, ResolvedItemName};
 use crate::normalize;
 use crate::plan::query::{plan_expr, plan_up_to, ExprContext, QueryLifetime};
 use crate::plan::scope::Scope;
+use crate::plan::statement::show::ShowSelect;
 use crate::plan::statement::{ddl, StatementContext, StatementDesc};
 use crate::plan::with_options::{self, TryFromValue};
 use crate::plan::{
@@ -627,6 +629,82 @@ pub fn plan_explain_pushdown(
     Ok(Plan::ExplainPushdown(ExplainPushdownPlan { explainee }))
 }
 
+/// Rewrites an `EXPLAIN ANALYZE` statement into a query that renders the
+/// physical plan of an index or materialized view alongside the rows produced
+/// and time spent by the dataflow operators implementing each plan node.
+///
+/// The metrics come from the introspection relations of the active cluster
+/// replica, so the statement must run on the cluster hosting the explainee.
+pub fn explain_analyze<'a>(
+    scx: &'a StatementContext<'a>,
+    statement: ExplainAnalyzeStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let (export_id, build_id) = match statement.explainee {
+        Explainee::Index(name) => {
+            let item = scx.get_item_by_resolved_name(&name)?;
+            let item_type = item.item_type();
+            if item_type != CatalogItemType::Index {
+                sql_bail!("Expected {name} to be an index, not a {item_type}");
+            }
+            // The index dataflow builds the indexed object under its own ID.
+            let (_, on_id) = item.index_details().expect("item is an index");
+            (item.id(), on_id)
+        }
+        Explainee::MaterializedView(name) => {
+            let item = scx.get_item_by_resolved_name(&name)?;
+            let item_type = item.item_type();
+            if item_type != CatalogItemType::MaterializedView {
+                sql_bail!("Expected {name} to be a materialized view, not a {item_type}");
+            }
+            (item.id(), item.id())
+        }
+        // The dataflow backing a one-off `SELECT`, and with it its
+        // introspection data, is dropped as soon as the peek completes.
+        Explainee::Select(..) => return Err(PlanError::ExplainAnalyzeOnSelect),
+        _ => bail_unsupported!("EXPLAIN ANALYZE for this explainee"),
+    };
+
+    // Each LIR node is implemented by the operators whose IDs lie in
+    // `[operator_id_start, operator_id_end)`. A node's output is what flows
+    // over channels leaving that range.
+    let query = format!(
+        "SELECT
+            repeat(' ', 2 * lm.nesting::int4) || lm.operator AS operator,
+            (
+                SELECT pg_catalog.sum(mc.sent)
+                FROM
+                    mz_internal.mz_dataflow_channel_operators AS dco
+                    JOIN mz_internal.mz_message_counts AS mc ON dco.id = mc.channel_id
+                WHERE
+                    dco.from_operator_id >= lm.operator_id_start
+                    AND dco.from_operator_id < lm.operator_id_end
+                    AND (
+                        dco.to_operator_id < lm.operator_id_start
+                        OR dco.to_operator_id >= lm.operator_id_end
+                    )
+            ) AS rows_produced,
+            (
+                SELECT pg_catalog.sum(se.elapsed_ns) / 1000 * '1 MICROSECONDS'::interval
+                FROM mz_internal.mz_scheduling_elapsed AS se
+                WHERE se.id >= lm.operator_id_start AND se.id < lm.operator_id_end
+            ) AS elapsed,
+            lm.lir_id,
+            lm.operator_id_start
+        FROM
+            mz_internal.mz_lir_mapping AS lm
+            JOIN mz_internal.mz_compute_exports AS ce ON lm.dataflow_id = ce.dataflow_id
+        WHERE lm.global_id = '{build_id}' AND ce.export_id = '{export_id}'"
+    );
+
+    ShowSelect::new(
+        scx,
+        query,
+        None,
+        Some("operator_id_start DESC, lir_id DESC"),
+        Some(&["operator", "rows_produced", "elapsed"]),
+    )
+}
+
 pub fn plan_explain_timestamp(
     scx: &StatementContext,
     explain: ExplainTimestampStatement<Aug>,
@@ -1061,6 +1139,7 @@ fn plan_copy_to_expr(
         connection_id: conn_id,
         format,
         max_file_size: options.max_file_size.as_bytes(),
+        manifest: options.manifest,
     }))
 }
 
@@ -1106,7 +1185,18 @@ fn plan_copy_from(
                 .map_err(|e| sql_err!("{}", e))?,
             )
         }
-        CopyFormat::Binary => bail_unsupported!("FORMAT BINARY"),
+        CopyFormat::Binary => {
+            only_available_with_csv(options.quote, "quote")?;
+            only_available_with_csv(options.escape, "escape")?;
+            only_available_with_csv(options.header, "HEADER")?;
+            if options.delimiter.is_some() {
+                sql_bail!("cannot specify DELIMITER in BINARY mode");
+            }
+            if options.null.is_some() {
+                sql_bail!("cannot specify NULL in BINARY mode");
+            }
+            CopyFormatParams::Binary
+        }
         CopyFormat::Parquet => bail_unsupported!("FORMAT PARQUET"),
     };
 
@@ -1135,7 +1225,8 @@ generate_extracted_config!(
     (Quote, String),
     (Header, bool),
     (AwsConnection, with_options::Object),
-    (MaxFileSize, ByteSize, Default(ByteSize::mb(256)))
+    (MaxFileSize, ByteSize, Default(ByteSize::mb(256))),
+    (Manifest, bool, Default(false))
 );
 
 pub fn plan_copy(
@@ -94,9 +94,13 @@ pub fn plan_reset_variable(
     _: &StatementContext,
     ResetVariableStatement { variable }: ResetVariableStatement,
 ) -> Result<Plan, PlanError> {
-    Ok(Plan::ResetVariable(ResetVariablePlan {
-        name: variable.to_string(),
-    }))
+    if variable.as_str() == UncasedStr::new("ALL") {
+        Ok(Plan::ResetAllVariables)
+    } else {
+        Ok(Plan::ResetVariable(ResetVariablePlan {
+            name: variable.to_string(),
+        }))
+    }
 }
 
 pub fn describe_show_variable(
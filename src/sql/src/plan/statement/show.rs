@@ -13,14 +13,18 @@
 //! `SHOW CREATE TABLE` and `SHOW VIEWS`. Note that `SHOW <var>` is considered
 //! an SCL statement.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Write;
 
+use mz_expr::{ColumnOrder, RowSetFinishing};
+use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
+use mz_repr::role_id::RoleId;
 use mz_repr::{Datum, GlobalId, RelationDesc, Row, ScalarType};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
-    CreateSourceSubsource, ObjectType, ReferencedSubsources, ShowCreateConnectionStatement,
+    CreateSourceSubsource, ObjectType, ReferencedSubsources, ShowCreateAllObjectsIn,
+    ShowCreateAllObjectsStatement, ShowCreateConnectionStatement,
     ShowCreateMaterializedViewStatement, ShowObjectType, SystemObjectType, UnresolvedItemName,
     WithOptionValue,
 };
@@ -30,19 +34,21 @@ use crate::ast::visit_mut::VisitMut;
 use crate::ast::{
     SelectStatement, ShowColumnsStatement, ShowCreateIndexStatement, ShowCreateSinkStatement,
     ShowCreateSourceStatement, ShowCreateTableStatement, ShowCreateViewStatement,
-    ShowObjectsStatement, ShowStatementFilter, Statement, Value,
+    ShowEffectivePrivilegesStatement, ShowObjectsStatement, ShowStatementFilter, Statement, Value,
 };
-use crate::catalog::{CatalogItemType, SessionCatalog};
+use crate::catalog::{CatalogItemType, ErrorMessageObjectDescription, SessionCatalog};
 use crate::names::{
-    self, Aug, NameSimplifier, ResolvedClusterName, ResolvedDatabaseName, ResolvedIds,
-    ResolvedItemName, ResolvedRoleName, ResolvedSchemaName,
+    self, Aug, NameSimplifier, ObjectId, ResolvedClusterName, ResolvedDatabaseName, ResolvedIds,
+    ResolvedItemName, ResolvedRoleName, ResolvedSchemaName, PUBLIC_ROLE_NAME,
 };
 use crate::parse;
 use crate::plan::scope::Scope;
 use crate::plan::statement::{dml, StatementContext, StatementDesc};
 use crate::plan::{
-    query, transform_ast, HirRelationExpr, Params, Plan, PlanError, ShowColumnsPlan, ShowCreatePlan,
+    query, transform_ast, HirRelationExpr, Params, Plan, PlanError, QueryWhen, SelectPlan,
+    ShowColumnsPlan, ShowCreatePlan,
 };
+use crate::rbac;
 
 pub fn describe_show_create_view(
     _: &StatementContext,
@@ -205,6 +211,147 @@ pub fn plan_show_create_connection(
     plan_show_create(scx, &connection_name, CatalogItemType::Connection)
 }
 
+pub fn describe_show_create_all_objects(
+    _: &StatementContext,
+    _: ShowCreateAllObjectsStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(Some(show_create_all_objects_desc())))
+}
+
+fn show_create_all_objects_desc() -> RelationDesc {
+    RelationDesc::empty()
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("type", ScalarType::String.nullable(false))
+        .with_column("create_sql", ScalarType::String.nullable(false))
+}
+
+/// Collects the definitions shown by `SHOW CREATE ALL OBJECTS` into a constant
+/// relation.
+///
+/// The relation has an additional leading column with the position of each
+/// definition in dependency order, i.e., every object comes after the objects
+/// it references.
+fn show_create_all_objects_in_order(
+    scx: &StatementContext,
+    ShowCreateAllObjectsStatement { in_container }: ShowCreateAllObjectsStatement<Aug>,
+) -> Result<HirRelationExpr, PlanError> {
+    let schemas = match &in_container {
+        ShowCreateAllObjectsIn::Schema(name) => {
+            let schema = scx
+                .catalog
+                .get_schema(name.database_spec(), name.schema_spec());
+            vec![schema]
+        }
+        ShowCreateAllObjectsIn::Database(name) => {
+            scx.catalog.get_database(name.database_id()).schemas()
+        }
+    };
+
+    // Subsources and progress collections are recreated by the statement that
+    // creates their source, so they are not shown on their own.
+    let ids: BTreeSet<_> = schemas
+        .iter()
+        .flat_map(|schema| schema.item_ids())
+        .filter(|id| {
+            let item = scx.catalog.get_item(id);
+            id.is_user() && item.subsource_details().is_none() && !item.is_progress_source()
+        })
+        .collect();
+    // References to a subsource are references to the source that creates it.
+    let dependency = |id: &GlobalId| match scx.catalog.get_item(id).subsource_details() {
+        Some((ingestion_id, _)) => ingestion_id,
+        None => *id,
+    };
+
+    // Order the objects depth-first by their references, visiting objects in
+    // the order in which they were created.
+    let mut ordered = Vec::with_capacity(ids.len());
+    let mut visited = BTreeSet::new();
+    for id in &ids {
+        let mut stack = vec![(*id, false)];
+        while let Some((id, references_visited)) = stack.pop() {
+            if references_visited {
+                ordered.push(id);
+                continue;
+            }
+            if !visited.insert(id) {
+                continue;
+            }
+            stack.push((id, true));
+            let references: BTreeSet<_> = scx
+                .catalog
+                .get_item(&id)
+                .references()
+                .0
+                .iter()
+                .map(dependency)
+                .filter(|reference| ids.contains(reference) && !visited.contains(reference))
+                .collect();
+            stack.extend(
+                references
+                    .into_iter()
+                    .rev()
+                    .map(|reference| (reference, false)),
+            );
+        }
+    }
+
+    let mut rows = Vec::with_capacity(ordered.len());
+    for (position, id) in ordered.into_iter().enumerate() {
+        let item = scx.catalog.get_item(&id);
+        let name = scx.catalog.resolve_full_name(item.name()).to_string();
+        let create_sql = humanize_sql_for_show_create(scx.catalog, id, item.create_sql())?;
+        rows.push(Row::pack_slice(&[
+            Datum::UInt64(u64::cast_from(position)),
+            Datum::String(&name),
+            Datum::String(&item.item_type().to_string()),
+            Datum::String(&create_sql),
+        ]));
+    }
+
+    let desc = RelationDesc::empty()
+        .with_column("position", ScalarType::UInt64.nullable(false))
+        .concat(show_create_all_objects_desc());
+    Ok(HirRelationExpr::Constant {
+        rows,
+        typ: desc.typ().clone(),
+    })
+}
+
+/// Plans `SHOW CREATE ALL OBJECTS` as a constant relation, without the
+/// dependency order, for use in queries.
+pub fn show_create_all_objects(
+    scx: &StatementContext,
+    stmt: ShowCreateAllObjectsStatement<Aug>,
+) -> Result<(HirRelationExpr, RelationDesc), PlanError> {
+    let desc = show_create_all_objects_desc();
+    let expr = show_create_all_objects_in_order(scx, stmt)?.project((1..=desc.arity()).collect());
+    Ok((expr, desc))
+}
+
+pub fn plan_show_create_all_objects(
+    scx: &StatementContext,
+    stmt: ShowCreateAllObjectsStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let source = show_create_all_objects_in_order(scx, stmt)?;
+    let arity = show_create_all_objects_desc().arity();
+    Ok(Plan::Select(SelectPlan {
+        source,
+        when: QueryWhen::Immediately,
+        finishing: RowSetFinishing {
+            order_by: vec![ColumnOrder {
+                column: 0,
+                desc: false,
+                nulls_last: false,
+            }],
+            limit: None,
+            offset: 0,
+            project: (1..=arity).collect(),
+        },
+        copy_to: None,
+    }))
+}
+
 pub fn show_databases<'a>(
     scx: &'a StatementContext<'a>,
     filter: Option<ShowStatementFilter<Aug>>,
@@ -784,6 +931,100 @@ pub fn show_role_membership<'a>(
     )
 }
 
+pub fn describe_show_effective_privileges(
+    _: &StatementContext,
+    _: ShowEffectivePrivilegesStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(Some(show_effective_privileges_desc())))
+}
+
+fn show_effective_privileges_desc() -> RelationDesc {
+    RelationDesc::empty()
+        .with_column("source", ScalarType::String.nullable(false))
+        .with_column("object_type", ScalarType::String.nullable(false))
+        .with_column("object_name", ScalarType::String.nullable(true))
+        .with_column("grantor", ScalarType::String.nullable(false))
+        .with_column("grantee", ScalarType::String.nullable(false))
+        .with_column("privilege_type", ScalarType::String.nullable(false))
+}
+
+/// Resolves the privileges shown by `SHOW EFFECTIVE PRIVILEGES` into a constant relation.
+pub fn show_effective_privileges(
+    scx: &StatementContext,
+    ShowEffectivePrivilegesStatement {
+        role,
+        object_type: _,
+        name,
+    }: ShowEffectivePrivilegesStatement<Aug>,
+) -> Result<(HirRelationExpr, RelationDesc), PlanError> {
+    let object_id: ObjectId = name
+        .try_into()
+        .expect("name resolution should handle invalid objects");
+    let role_name = |id: &RoleId| match id {
+        RoleId::Public => PUBLIC_ROLE_NAME.as_str().to_string(),
+        id => scx.catalog.get_role(id).name().to_string(),
+    };
+
+    let mut rows = Vec::new();
+    for privilege in rbac::effective_privileges(scx.catalog, role.id, &object_id) {
+        let source = privilege.source.to_string();
+        let object_type = scx
+            .catalog
+            .get_system_object_type(&privilege.object_id)
+            .to_string()
+            .to_lowercase();
+        let object_name =
+            match ErrorMessageObjectDescription::from_sys_id(&privilege.object_id, scx.catalog) {
+                ErrorMessageObjectDescription::Object { object_name, .. } => object_name,
+                ErrorMessageObjectDescription::System => None,
+            };
+        let grantor = role_name(&privilege.grantor);
+        let grantee = role_name(&privilege.grantee);
+        for privilege_type in privilege.acl_mode.explode() {
+            rows.push(Row::pack_slice(&[
+                Datum::String(&source),
+                Datum::String(&object_type),
+                Datum::from(object_name.as_deref()),
+                Datum::String(&grantor),
+                Datum::String(&grantee),
+                Datum::String(privilege_type),
+            ]));
+        }
+    }
+
+    let desc = show_effective_privileges_desc();
+    let expr = HirRelationExpr::Constant {
+        rows,
+        typ: desc.typ().clone(),
+    };
+    Ok((expr, desc))
+}
+
+pub fn plan_show_effective_privileges(
+    scx: &StatementContext,
+    stmt: ShowEffectivePrivilegesStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let (source, desc) = show_effective_privileges(scx, stmt)?;
+    let arity = desc.arity();
+    Ok(Plan::Select(SelectPlan {
+        source,
+        when: QueryWhen::Immediately,
+        finishing: RowSetFinishing {
+            order_by: (0..arity)
+                .map(|column| ColumnOrder {
+                    column,
+                    desc: false,
+                    nulls_last: false,
+                })
+                .collect(),
+            limit: None,
+            offset: 0,
+            project: (0..arity).collect(),
+        },
+        copy_to: None,
+    }))
+}
+
 /// An intermediate result when planning a `SHOW` query.
 ///
 /// Can be interrogated for its columns, or converted into a proper [`Plan`].
@@ -801,7 +1042,7 @@ impl<'a> ShowSelect<'a> {
     /// may implicitly reference this column. Any `ORDER BY` in the query is
     /// ignored. `ShowSelects`s are always ordered in ascending order by all
     /// columns from left to right unless an order field is supplied.
-    fn new(
+    pub(crate) fn new(
         scx: &'a StatementContext,
         query: String,
         filter: Option<ShowStatementFilter<Aug>>,
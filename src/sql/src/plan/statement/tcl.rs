@@ -15,9 +15,11 @@
 use mz_sql_parser::ast::TransactionIsolationLevel;
 
 use crate::ast::{
-    CommitStatement, RollbackStatement, SetTransactionStatement, StartTransactionStatement,
+    AsOf, CommitStatement, RollbackStatement, SetTransactionStatement, StartTransactionStatement,
     TransactionAccessMode, TransactionMode,
 };
+use crate::names::Aug;
+use crate::plan::query;
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::{
     AbortTransactionPlan, CommitTransactionPlan, Plan, PlanError, SetTransactionPlan,
@@ -26,19 +28,31 @@ use crate::plan::{
 
 pub fn describe_start_transaction(
     _: &StatementContext,
-    _: StartTransactionStatement,
+    _: StartTransactionStatement<Aug>,
 ) -> Result<StatementDesc, PlanError> {
     Ok(StatementDesc::new(None))
 }
 
 pub fn plan_start_transaction(
-    _: &StatementContext,
-    StartTransactionStatement { modes }: StartTransactionStatement,
+    scx: &StatementContext,
+    StartTransactionStatement { modes, as_of }: StartTransactionStatement<Aug>,
 ) -> Result<Plan, PlanError> {
-    let (access, isolation_level) = verify_transaction_modes(modes)?;
+    let (mut access, isolation_level) = verify_transaction_modes(modes)?;
+    let as_of = match as_of {
+        Some(AsOf::AtLeast(_)) => bail_unsupported!("AS OF AT LEAST in BEGIN"),
+        as_of => query::plan_as_of(scx, as_of)?.advance_to_timestamp(),
+    };
+    // Transactions pinned to a historical timestamp can only read.
+    if as_of.is_some() {
+        if access == Some(TransactionAccessMode::ReadWrite) {
+            sql_bail!("BEGIN ... AS OF requires a READ ONLY transaction");
+        }
+        access = Some(TransactionAccessMode::ReadOnly);
+    }
     Ok(Plan::StartTransaction(StartTransactionPlan {
         access,
         isolation_level,
+        as_of,
     }))
 }
 
@@ -9,6 +9,8 @@
 
 //! Queries that validate CONNECTION objects.
 
+use mz_repr::{RelationDesc, ScalarType};
+
 use crate::ast::ValidateConnectionStatement;
 use crate::names::Aug;
 use crate::plan::statement::{StatementContext, StatementDesc};
@@ -19,7 +21,13 @@ pub fn describe_validate_connection(
     _: &StatementContext,
     _: ValidateConnectionStatement<Aug>,
 ) -> Result<StatementDesc, PlanError> {
-    Ok(StatementDesc::new(None))
+    Ok(StatementDesc::new(Some(
+        RelationDesc::empty()
+            .with_column("check", ScalarType::String.nullable(false))
+            .with_column("target", ScalarType::String.nullable(true))
+            .with_column("status", ScalarType::String.nullable(false))
+            .with_column("detail", ScalarType::String.nullable(true)),
+    )))
 }
 
 pub fn plan_validate_connection(
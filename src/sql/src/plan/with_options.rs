@@ -15,8 +15,9 @@ use std::time::Duration;
 use mz_repr::adt::interval::Interval;
 use mz_repr::bytes::ByteSize;
 use mz_repr::{strconv, GlobalId};
+use mz_rocksdb_types::config::{CompactionStyle, CompressionType};
 use mz_sql_parser::ast::{
-    ClusterScheduleOptionValue, ConnectionDefaultAwsPrivatelink, Ident, KafkaBroker,
+    ClusterScheduleOptionValue, ConnectionDefaultAwsPrivatelink, Expr, Ident, KafkaBroker,
     RefreshOptionValue, ReplicaDefinition,
 };
 use mz_storage_types::connections::StringOrSecret;
@@ -209,6 +210,42 @@ impl ImpliedValue for ByteSize {
     }
 }
 
+impl TryFromValue<Value> for CompactionStyle {
+    fn try_from_value(v: Value) -> Result<Self, PlanError> {
+        match v {
+            Value::String(value) => Ok(value.parse().map_err(|e| sql_err!("{e}"))?),
+            _ => sql_bail!("cannot use value as compaction style"),
+        }
+    }
+    fn name() -> String {
+        "compaction style".to_string()
+    }
+}
+
+impl ImpliedValue for CompactionStyle {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a compaction style value")
+    }
+}
+
+impl TryFromValue<Value> for CompressionType {
+    fn try_from_value(v: Value) -> Result<Self, PlanError> {
+        match v {
+            Value::String(value) => Ok(value.parse().map_err(|e| sql_err!("{e}"))?),
+            _ => sql_bail!("cannot use value as compression type"),
+        }
+    }
+    fn name() -> String {
+        "compression type".to_string()
+    }
+}
+
+impl ImpliedValue for CompressionType {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a compression type value")
+    }
+}
+
 impl TryFromValue<Value> for Interval {
     fn try_from_value(v: Value) -> Result<Self, PlanError> {
         match v {
@@ -479,7 +516,8 @@ impl<V: TryFromValue<Value>, T: AstInfo + std::fmt::Debug> TryFromValue<WithOpti
             | WithOptionValue::ConnectionKafkaBroker(_)
             | WithOptionValue::ConnectionAwsPrivatelink(_)
             | WithOptionValue::Refresh(_)
-            | WithOptionValue::ClusterScheduleOptionValue(_) => sql_bail!(
+            | WithOptionValue::ClusterScheduleOptionValue(_)
+            | WithOptionValue::Expr(_) => sql_bail!(
                 "incompatible value types: cannot convert {} to {}",
                 match v {
                     // The first few are unreachable because they are handled at the top of the outer match.
@@ -496,6 +534,7 @@ impl<V: TryFromValue<Value>, T: AstInfo + std::fmt::Debug> TryFromValue<WithOpti
                     WithOptionValue::ConnectionAwsPrivatelink(_) => "connection kafka brokers",
                     WithOptionValue::Refresh(_) => "refresh option values",
                     WithOptionValue::ClusterScheduleOptionValue(_) => "cluster schedule",
+                    WithOptionValue::Expr(_) => "expressions",
                 },
                 V::name()
             ),
@@ -623,6 +662,26 @@ impl TryFromValue<WithOptionValue<Aug>> for ClusterScheduleOptionValue {
     }
 }
 
+impl ImpliedValue for Expr<Aug> {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide an expression")
+    }
+}
+
+impl TryFromValue<WithOptionValue<Aug>> for Expr<Aug> {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        if let WithOptionValue::Expr(e) = v {
+            Ok(e)
+        } else {
+            sql_bail!("cannot use value `{}` for an expression", v)
+        }
+    }
+
+    fn name() -> String {
+        "expression".to_string()
+    }
+}
+
 impl<V: ImpliedValue> ImpliedValue for BTreeMap<String, V> {
     fn implied_value() -> Result<Self, PlanError> {
         sql_bail!("must provide a map of key-value pairs")
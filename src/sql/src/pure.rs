@@ -497,12 +497,13 @@ async fn purify_create_sink(
                     .map_err(|e| CsrPurificationError::ListSubjectsError(Arc::new(e)))?;
             }
             Format::Avro(AvroSchema::InlineSchema { .. })
-            | Format::Bytes
+            | Format::Bytes { .. }
             | Format::Csv { .. }
             | Format::Json { .. }
+            | Format::JsonShred { .. }
             | Format::Protobuf(ProtobufSchema::InlineSchema { .. })
             | Format::Regex(..)
-            | Format::Text => {}
+            | Format::Text { .. } => {}
         }
     }
 
@@ -1219,6 +1220,57 @@ async fn purify_alter_source(
     };
     let connection_name = desc.connection.name();
 
+    if let AlterSourceAction::SwapConnection { connection } = &action {
+        let GenericSourceConnection::Kafka(kafka_connection) = &desc.connection else {
+            sql_bail!(
+                "{} is a {} source, which does not support ALTER SOURCE...SWAP CONNECTION",
+                scx.catalog.minimal_qualification(name),
+                connection_name,
+            );
+        };
+
+        let item = scx.get_item_by_resolved_name(connection)?;
+        let new_connection = match item.connection()? {
+            Connection::Kafka(connection) => connection.clone().into_inline_connection(&catalog),
+            _ => Err(KafkaSourcePurificationError::NotKafkaConnection(
+                scx.catalog.resolve_full_name(item.name()),
+            ))?,
+        };
+
+        // The source resumes from its existing progress, so the new cluster
+        // must at least serve the source's topic.
+        let consumer = new_connection
+            .create_with_context(
+                storage_configuration,
+                MzClientContext::default(),
+                &BTreeMap::new(),
+                InTask::No,
+            )
+            .await
+            .map_err(|e| {
+                KafkaSourcePurificationError::KafkaConsumerError(
+                    e.display_with_causes().to_string(),
+                )
+            })?;
+        kafka_util::ensure_topic_exists(
+            Arc::new(consumer),
+            &kafka_connection.topic,
+            storage_configuration
+                .parameters
+                .kafka_timeout_config
+                .fetch_metadata_timeout,
+        )
+        .await?;
+
+        return Ok(PurifiedStatement::PurifiedAlterSource {
+            alter_source_stmt: AlterSourceStatement {
+                source_name: unresolved_source_name,
+                action,
+                if_exists,
+            },
+        });
+    }
+
     // Validate this is a source that can be altered.
     match desc.connection {
         GenericSourceConnection::Postgres(_) => {}
@@ -1561,10 +1613,11 @@ async fn purify_source_format_single(
             }
             ProtobufSchema::InlineSchema { .. } => {}
         },
-        Format::Bytes
+        Format::Bytes { .. }
         | Format::Regex(_)
         | Format::Json { .. }
-        | Format::Text
+        | Format::JsonShred { .. }
+        | Format::Text { .. }
         | Format::Csv { .. } => (),
     }
     Ok(())
@@ -1724,17 +1777,80 @@ async fn get_remote_csr_schema(
     topic: String,
 ) -> Result<Schema, PlanError> {
     let value_schema_name = format!("{}-value", topic);
+    let check_value_schema = value_strategy != ReaderSchemaSelectionStrategy::Latest;
     let value_schema =
         get_schema_with_strategy(ccsr_client, value_strategy, &value_schema_name).await?;
     let value_schema = value_schema.ok_or_else(|| anyhow!("No value schema found"))?;
+    if check_value_schema {
+        check_reader_schema_compatibility(ccsr_client, &value_schema_name, &value_schema).await?;
+    }
+    report_schema_aliases(&value_schema_name, &value_schema);
     let subject = format!("{}-key", topic);
+    let check_key_schema = key_strategy != ReaderSchemaSelectionStrategy::Latest;
     let key_schema = get_schema_with_strategy(ccsr_client, key_strategy, &subject).await?;
+    if let (true, Some(key_schema)) = (check_key_schema, &key_schema) {
+        check_reader_schema_compatibility(ccsr_client, &subject, key_schema).await?;
+    }
+    if let Some(key_schema) = &key_schema {
+        report_schema_aliases(&subject, key_schema);
+    }
     Ok(Schema {
         key_schema,
         value_schema,
     })
 }
 
+/// Logs the aliases declared by the reader schema for `subject`, through which
+/// the source reads data that was written before types or fields were
+/// renamed.
+fn report_schema_aliases(subject: &str, reader_schema: &str) {
+    // Schemas that fail to parse are reported when the source is planned.
+    let Ok(reader_schema) = mz_interchange::avro::parse_schema(reader_schema) else {
+        return;
+    };
+    for alias in reader_schema.aliases() {
+        tracing::info!("reader schema for subject {}: {alias}", subject.quoted());
+    }
+}
+
+/// Verifies that a reader schema that was not selected as the latest schema
+/// of `subject` can read data written with that latest schema, so that every
+/// incompatible field is reported when the source is created rather than the
+/// first one failing at runtime.
+async fn check_reader_schema_compatibility(
+    client: &Client,
+    subject: &str,
+    reader_schema: &str,
+) -> Result<(), PlanError> {
+    let writer_schema = match client.get_schema_by_subject(subject).await {
+        Ok(CcsrSchema { raw, .. }) => raw,
+        // Nothing has been written to the subject yet.
+        Err(GetBySubjectError::SubjectNotFound) | Err(GetBySubjectError::VersionNotFound(_)) => {
+            return Ok(())
+        }
+        Err(e) => {
+            return Err(PlanError::FetchingCsrSchemaFailed {
+                schema_lookup: format!("subject {}", subject.quoted()),
+                cause: Arc::new(e),
+            })
+        }
+    };
+    // Schemas that fail to parse are reported when the source is planned.
+    let (Ok(reader_schema), Ok(writer_schema)) = (
+        mz_interchange::avro::parse_schema(reader_schema),
+        mz_interchange::avro::parse_schema(&writer_schema),
+    ) else {
+        return Ok(());
+    };
+    if let Err(incompatibilities) = reader_schema.compatible_with(&writer_schema) {
+        Err(CsrPurificationError::IncompatibleReaderSchema {
+            subject: subject.to_string(),
+            incompatibilities: incompatibilities.iter().map(|i| i.to_string()).collect(),
+        })?;
+    }
+    Ok(())
+}
+
 /// Collect protobuf message descriptor from CSR and compile the descriptor.
 async fn compile_proto(
     subject_name: &String,
@@ -119,6 +119,11 @@ impl PgSourcePurificationError {
                 "you might be able to wait for other sources to finish snapshotting and try again".into()
             ),
             Self::ReplicationDisabled => Some("set max_wal_senders to a value > 0".into()),
+            Self::NotTablesWReplicaIdentityFull { .. } => Some(
+                "run ALTER TABLE ... REPLICA IDENTITY FULL in the upstream database for each \
+                referenced item"
+                    .into(),
+            ),
             _ => None,
         }
     }
@@ -212,6 +217,13 @@ pub enum CsrPurificationError {
     ClientError(Arc<CsrConnectError>),
     #[error("list subjects failed")]
     ListSubjectsError(Arc<ListError>),
+    #[error(
+        "reader schema for subject \"{subject}\" cannot read data written with its latest schema"
+    )]
+    IncompatibleReaderSchema {
+        subject: String,
+        incompatibilities: Vec<String>,
+    },
 }
 
 impl CsrPurificationError {
@@ -219,6 +231,12 @@ impl CsrPurificationError {
         match self {
             Self::ClientError(e) => Some(e.to_string_with_causes()),
             Self::ListSubjectsError(e) => Some(e.to_string_with_causes()),
+            Self::IncompatibleReaderSchema {
+                incompatibilities, ..
+            } => Some(format!(
+                "incompatibilities:\n{}",
+                incompatibilities.join("\n")
+            )),
             _ => None,
         }
     }
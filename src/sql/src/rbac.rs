@@ -8,7 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::{BTreeMap, BTreeSet};
-use std::iter;
+use std::{fmt, iter};
 
 use itertools::Itertools;
 use maplit::btreeset;
@@ -27,7 +27,7 @@ use crate::catalog::{
 };
 use crate::names::{
     CommentObjectId, ObjectId, QualifiedItemName, ResolvedDatabaseSpecifier, ResolvedIds,
-    SystemObjectId,
+    SchemaSpecifier, SystemObjectId,
 };
 use crate::plan::{self, PlanKind};
 use crate::plan::{
@@ -35,7 +35,7 @@ use crate::plan::{
 };
 use crate::session::metadata::SessionMetadata;
 use crate::session::user::{MZ_SUPPORT_ROLE_ID, MZ_SYSTEM_ROLE_ID, SUPPORT_USER, SYSTEM_USER};
-use crate::session::vars::SystemVars;
+use crate::session::vars::{is_role_limit_var, SystemVars};
 
 /// Common checks that need to be performed before we can start checking a role's privileges.
 fn rbac_check_preamble(
@@ -804,6 +804,7 @@ fn generate_rbac_requirements(
             connection_id: _,
             format: _,
             max_file_size: _,
+            manifest: _,
         }) => {
             let mut privileges = generate_read_privileges(
                 catalog,
@@ -947,6 +948,7 @@ fn generate_rbac_requirements(
             id,
             name: _,
             options: _,
+            strategy: _,
         }) => RbacRequirements {
             ownership: vec![ObjectId::Cluster(*id)],
             item_usage: &CREATE_ITEM_USAGE,
@@ -1107,8 +1109,11 @@ fn generate_rbac_requirements(
             name: _,
             option,
         }) => match option {
-            // Roles are allowed to change their own variables.
-            plan::PlannedAlterRoleOption::Variable(_) if role_id == *id => {
+            // Roles are allowed to change their own variables, except for those that limit
+            // their resources.
+            plan::PlannedAlterRoleOption::Variable(variable)
+                if role_id == *id && !is_role_limit_var(variable.name()) =>
+            {
                 RbacRequirements::default()
             }
             // Otherwise to ALTER a role, you need to have the CREATE_ROLE privilege.
@@ -1118,6 +1123,15 @@ fn generate_rbac_requirements(
                 ..Default::default()
             },
         },
+        Plan::AlterDatabase(plan::AlterDatabasePlan {
+            id,
+            name: _,
+            variable: _,
+        }) => RbacRequirements {
+            ownership: vec![ObjectId::Database(*id)],
+            item_usage: &CREATE_ITEM_USAGE,
+            ..Default::default()
+        },
         Plan::AlterOwner(plan::AlterOwnerPlan {
             id,
             object_type: _,
@@ -1383,6 +1397,7 @@ fn generate_rbac_requirements(
             local: _,
         })
         | Plan::ResetVariable(plan::ResetVariablePlan { name: _ })
+        | Plan::ResetAllVariables
         | Plan::SetTransaction(plan::SetTransactionPlan { local: _, modes: _ })
         | Plan::StartTransaction(plan::StartTransactionPlan {
             access: _,
@@ -1643,6 +1658,158 @@ fn check_object_privileges(
     Ok(())
 }
 
+/// Where a privilege that a role holds on an object, or on one of the objects containing it, comes
+/// from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PrivilegeSource {
+    /// Granted on the object itself.
+    Object,
+    /// Granted on the schema that contains the object.
+    Schema,
+    /// Granted on the database that contains the object.
+    Database,
+    /// Granted on the entire system.
+    System,
+    /// Granted to the object when it was created, by a default privilege.
+    DefaultPrivilege,
+}
+
+impl fmt::Display for PrivilegeSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PrivilegeSource::Object => f.write_str("object"),
+            PrivilegeSource::Schema => f.write_str("schema"),
+            PrivilegeSource::Database => f.write_str("database"),
+            PrivilegeSource::System => f.write_str("system"),
+            PrivilegeSource::DefaultPrivilege => f.write_str("default privilege"),
+        }
+    }
+}
+
+/// A privilege held by a role, either directly or through one of the roles it is a member of.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EffectivePrivilege {
+    /// Where the privilege comes from.
+    pub source: PrivilegeSource,
+    /// The object that the privilege is held on.
+    pub object_id: SystemObjectId,
+    /// The role that granted the privilege.
+    pub grantor: RoleId,
+    /// The role that the privilege was granted to.
+    pub grantee: RoleId,
+    /// The privileges granted.
+    pub acl_mode: AclMode,
+}
+
+/// Resolves all privileges that `role_id` holds that are relevant to accessing `object_id`.
+///
+/// Materialize does not inherit privileges from containing objects, so accessing an object
+/// usually requires privileges on the object itself, on the schema and database that contain it,
+/// and sometimes on the entire system. All of these are returned, along with the default
+/// privileges that applied to the object when it was created.
+pub fn effective_privileges(
+    catalog: &dyn SessionCatalog,
+    role_id: RoleId,
+    object_id: &ObjectId,
+) -> Vec<EffectivePrivilege> {
+    let role_membership = catalog.collect_role_membership(&role_id);
+    let mut effective_privileges = Vec::new();
+    let mut push_grants = |source: PrivilegeSource, object_id: SystemObjectId| {
+        let Some(privileges) = catalog.get_privileges(&object_id) else {
+            return;
+        };
+        for mz_acl_item in privileges.all_values() {
+            if role_membership.contains(&mz_acl_item.grantee) {
+                effective_privileges.push(EffectivePrivilege {
+                    source,
+                    object_id: object_id.clone(),
+                    grantor: mz_acl_item.grantor,
+                    grantee: mz_acl_item.grantee,
+                    acl_mode: mz_acl_item.acl_mode,
+                });
+            }
+        }
+    };
+
+    // The schema and database that contain the object, if any.
+    let (schema, database_id, schema_id) = match object_id {
+        ObjectId::Item(id) => {
+            let qualifiers = &catalog.get_item(id).name().qualifiers;
+            let database_id = match qualifiers.database_spec {
+                ResolvedDatabaseSpecifier::Id(database_id) => Some(database_id),
+                ResolvedDatabaseSpecifier::Ambient => None,
+            };
+            let schema_id = match qualifiers.schema_spec {
+                SchemaSpecifier::Id(schema_id) => Some(schema_id),
+                SchemaSpecifier::Temporary => None,
+            };
+            let schema = ObjectId::Schema((qualifiers.database_spec, qualifiers.schema_spec));
+            (Some(schema), database_id, schema_id)
+        }
+        ObjectId::Schema((ResolvedDatabaseSpecifier::Id(database_id), _)) => {
+            (None, Some(*database_id), None)
+        }
+        _ => (None, None, None),
+    };
+
+    push_grants(
+        PrivilegeSource::Object,
+        SystemObjectId::Object(object_id.clone()),
+    );
+    if let Some(schema) = schema {
+        push_grants(PrivilegeSource::Schema, SystemObjectId::Object(schema));
+    }
+    if let Some(database_id) = database_id {
+        push_grants(
+            PrivilegeSource::Database,
+            SystemObjectId::Object(ObjectId::Database(database_id)),
+        );
+    }
+    push_grants(PrivilegeSource::System, SystemObjectId::System);
+
+    // Mirror how default privileges are applied when an object is created: they match the owner
+    // of the object or all roles, and either the object's schema and database or any of them.
+    if let Some(owner_id) = catalog.get_owner_id(object_id) {
+        let object_type = catalog.get_object_type(object_id);
+        let privilege_object_type = if object_type.is_relation() {
+            ObjectType::Table
+        } else {
+            object_type
+        };
+        let valid_acl_mode = all_object_privileges(SystemObjectType::Object(object_type));
+        for (default_privilege_object, acl_items) in catalog.get_default_privileges() {
+            let applies = default_privilege_object.object_type == privilege_object_type
+                && (default_privilege_object.role_id == owner_id
+                    || default_privilege_object.role_id == RoleId::Public)
+                && default_privilege_object
+                    .database_id
+                    .map_or(true, |id| Some(id) == database_id)
+                && default_privilege_object
+                    .schema_id
+                    .map_or(true, |id| Some(id) == schema_id);
+            if !applies {
+                continue;
+            }
+            for acl_item in acl_items {
+                let acl_mode = acl_item.acl_mode.intersection(valid_acl_mode);
+                if role_membership.contains(&acl_item.grantee) && !acl_mode.is_empty() {
+                    effective_privileges.push(EffectivePrivilege {
+                        source: PrivilegeSource::DefaultPrivilege,
+                        object_id: SystemObjectId::Object(object_id.clone()),
+                        grantor: owner_id,
+                        grantee: acl_item.grantee,
+                        acl_mode,
+                    });
+                }
+            }
+        }
+    }
+
+    effective_privileges.sort();
+    effective_privileges.dedup();
+    effective_privileges
+}
+
 pub const fn all_object_privileges(object_type: SystemObjectType) -> AclMode {
     const TABLE_ACL_MODE: AclMode = AclMode::INSERT
         .union(AclMode::SELECT)
@@ -131,3 +131,114 @@ pub struct RoleMetadata {
     /// checks.
     pub current_role: RoleId,
 }
+
+/// A name in a TLS client certificate that a [`CertRoleMapping`] rule can
+/// match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CertField {
+    /// The common name (CN) of the certificate's subject.
+    CommonName,
+    /// Any of the certificate's DNS, email, or URI subject alternative names.
+    SubjectAltName,
+}
+
+/// A single rule of a [`CertRoleMapping`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CertRoleRule {
+    field: CertField,
+    /// The value the field must have, or `None` to match any value.
+    pattern: Option<String>,
+    /// The role to log in as, or `None` to log in as the matched value.
+    role: Option<String>,
+}
+
+/// Rules that map the names in a verified TLS client certificate to the role
+/// that the client logs in as.
+///
+/// The rules are written as a comma-separated list of `FIELD:PATTERN=ROLE`,
+/// where `FIELD` is `cn` or `san`. A `PATTERN` of `*` matches any value, and a
+/// `ROLE` of `*` logs in as the matched value itself. Rules are tried in order,
+/// and the first rule that matches determines the role. For example,
+///
+/// ```text
+/// cn:ops-bot=ops, san:loader.example.com=loader, cn:*=*
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CertRoleMapping {
+    rules: Vec<CertRoleRule>,
+}
+
+impl CertRoleMapping {
+    /// Parses a mapping from its textual representation.
+    pub fn parse(s: &str) -> Result<CertRoleMapping, String> {
+        let mut rules = vec![];
+        for rule in s.split(',').map(str::trim).filter(|rule| !rule.is_empty()) {
+            let invalid = || format!("invalid rule {rule:?}: expected FIELD:PATTERN=ROLE");
+            let (field, rest) = rule.split_once(':').ok_or_else(invalid)?;
+            let (pattern, role) = rest.split_once('=').ok_or_else(invalid)?;
+            let field = match field.trim().to_lowercase().as_str() {
+                "cn" => CertField::CommonName,
+                "san" => CertField::SubjectAltName,
+                other => {
+                    return Err(format!(
+                        "invalid rule {rule:?}: unknown field {other:?}, expected \"cn\" or \"san\""
+                    ))
+                }
+            };
+            let (pattern, role) = (pattern.trim(), role.trim());
+            if pattern.is_empty() || role.is_empty() {
+                return Err(invalid());
+            }
+            let wildcard = |s: &str| (s != "*").then(|| s.to_string());
+            rules.push(CertRoleRule {
+                field,
+                pattern: wildcard(pattern),
+                role: wildcard(role),
+            });
+        }
+        Ok(CertRoleMapping { rules })
+    }
+
+    /// Returns the role that a client presenting a certificate with the given
+    /// names logs in as, or `None` if no rule matches.
+    pub fn role(&self, common_name: Option<&str>, subject_alt_names: &[String]) -> Option<String> {
+        self.rules.iter().find_map(|rule| {
+            let mut values: Box<dyn Iterator<Item = &str>> = match rule.field {
+                CertField::CommonName => Box::new(common_name.into_iter()),
+                CertField::SubjectAltName => Box::new(subject_alt_names.iter().map(String::as_str)),
+            };
+            let value = values.find(|value| match &rule.pattern {
+                None => true,
+                Some(pattern) => pattern == value,
+            })?;
+            Some(rule.role.clone().unwrap_or_else(|| value.to_string()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_cert_role_mapping() {
+        let mapping =
+            CertRoleMapping::parse("cn:ops-bot=ops, san:loader.example.com=loader, cn:*=*")
+                .unwrap();
+        assert_eq!(mapping.role(Some("ops-bot"), &[]), Some("ops".into()));
+        assert_eq!(
+            mapping.role(Some("host"), &["loader.example.com".into()]),
+            Some("loader".into())
+        );
+        assert_eq!(mapping.role(Some("alice"), &[]), Some("alice".into()));
+        assert_eq!(mapping.role(None, &["other.example.com".into()]), None);
+
+        assert_eq!(
+            CertRoleMapping::parse("").unwrap().role(Some("alice"), &[]),
+            None
+        );
+        assert!(CertRoleMapping::parse("cn=alice").is_err());
+        assert!(CertRoleMapping::parse("ou:eng=alice").is_err());
+        assert!(CertRoleMapping::parse("cn:alice=").is_err());
+    }
+}
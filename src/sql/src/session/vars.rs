@@ -76,6 +76,7 @@ use im::OrdMap;
 use mz_build_info::BuildInfo;
 use mz_dyncfg::{ConfigSet, ConfigType, ConfigUpdates, ConfigVal};
 use mz_ore::cast::CastFrom;
+use mz_ore::cidr::IpNetwork;
 use mz_persist_client::cfg::{CRDB_CONNECT_TIMEOUT, CRDB_TCP_USER_TIMEOUT};
 use mz_repr::adt::numeric::Numeric;
 use mz_repr::adt::timestamp::CheckedTimestamp;
@@ -88,7 +89,7 @@ use serde::Serialize;
 use uncased::UncasedStr;
 
 use crate::ast::Ident;
-use crate::session::user::User;
+use crate::session::user::{CertRoleMapping, User};
 
 pub(crate) mod constraints;
 pub(crate) mod definitions;
@@ -380,6 +381,7 @@ impl SessionVars {
             &SQL_SAFE_UPDATES,
             &REAL_TIME_RECENCY,
             &EMIT_PLAN_INSIGHTS_NOTICE,
+            &EMIT_STRUCTURED_NOTICES,
             &EMIT_TIMESTAMP_NOTICE,
             &EMIT_TRACE_ID_NOTICE,
             &AUTO_ROUTE_CATALOG_QUERIES,
@@ -390,6 +392,9 @@ impl SessionVars {
             &EMIT_INTROSPECTION_QUERY_NOTICE,
             &UNSAFE_NEW_TRANSACTION_WALL_TIME,
             &WELCOME_MESSAGE,
+            &STATEMENT_TIMEOUT_LIMIT,
+            &MAX_QUERY_RESULT_SIZE_LIMIT,
+            &ALLOWED_CLUSTERS,
         ]
         .into_iter()
         .chain(SystemVars::SESSION_VARS.iter().map(|(_name, var)| *var))
@@ -460,11 +465,21 @@ impl SessionVars {
     }
 
     /// Resets all variables to their default value.
-    pub fn reset_all(&mut self) {
-        let names: Vec<_> = self.vars.keys().copied().collect();
-        for name in names {
-            self.vars[name].reset(false);
+    ///
+    /// Like [`SessionVars::reset`], the new values only become the session
+    /// values at the next call to [`SessionVars::end_transaction`]. Returns the
+    /// parameters whose values changed.
+    pub fn reset_all(&mut self) -> BTreeMap<&'static str, String> {
+        let mut changed = BTreeMap::new();
+        for var in self.vars.values_mut() {
+            let before = var.value();
+            var.reset(false);
+            let after = var.value();
+            if before != after {
+                changed.insert(var.name(), after);
+            }
         }
+        changed
     }
 
     /// Returns a [`Var`] representing the configuration parameter with the
@@ -532,6 +547,7 @@ impl SessionVars {
 
         let name = UncasedStr::new(name);
         self.check_read_only(name)?;
+        self.check_role_only(name)?;
 
         self.vars
             .get_mut(name)
@@ -582,6 +598,7 @@ impl SessionVars {
 
         let name = UncasedStr::new(name);
         self.check_read_only(name)?;
+        self.check_role_only(name)?;
 
         self.vars
             .get_mut(name)
@@ -609,6 +626,16 @@ impl SessionVars {
         }
     }
 
+    /// Returns an error if the variable corresponding to `name` limits the
+    /// resources of a role, and can thus only be configured with `ALTER ROLE`.
+    fn check_role_only(&self, name: &UncasedStr) -> Result<(), VarError> {
+        if is_role_limit_var(name.as_str()) {
+            let var = self.inspect(name.as_str())?;
+            return Err(VarError::ReadOnlyParameter(var.name()));
+        }
+        Ok(())
+    }
+
     /// Commits or rolls back configuration parameter updates made via
     /// [`SessionVars::set`] since the last call to `end_transaction`.
     ///
@@ -730,6 +757,25 @@ impl SessionVars {
         self.expect_value(&STATEMENT_TIMEOUT)
     }
 
+    /// Returns the value of the `statement_timeout_limit` configuration parameter.
+    pub fn statement_timeout_limit(&self) -> &Duration {
+        self.expect_value(&STATEMENT_TIMEOUT_LIMIT)
+    }
+
+    /// Returns the timeout to apply to operations subject to `statement_timeout`,
+    /// capped by `statement_timeout_limit`. A zero duration disables the timeout.
+    pub fn effective_statement_timeout(&self) -> Duration {
+        let timeout = *self.statement_timeout();
+        let limit = *self.statement_timeout_limit();
+        if limit.is_zero() {
+            timeout
+        } else if timeout.is_zero() {
+            limit
+        } else {
+            std::cmp::min(timeout, limit)
+        }
+    }
+
     /// Returns the value of the `idle_in_transaction_session_timeout` configuration parameter.
     pub fn idle_in_transaction_session_timeout(&self) -> &Duration {
         self.expect_value(&IDLE_IN_TRANSACTION_SESSION_TIMEOUT)
@@ -761,6 +807,11 @@ impl SessionVars {
         *self.expect_value(&EMIT_PLAN_INSIGHTS_NOTICE)
     }
 
+    /// Returns the value of `emit_structured_notices` configuration parameter.
+    pub fn emit_structured_notices(&self) -> bool {
+        *self.expect_value(&EMIT_STRUCTURED_NOTICES)
+    }
+
     /// Returns the value of `emit_timestamp_notice` configuration parameter.
     pub fn emit_timestamp_notice(&self) -> bool {
         *self.expect_value(&EMIT_TIMESTAMP_NOTICE)
@@ -802,6 +853,22 @@ impl SessionVars {
             .as_bytes()
     }
 
+    /// Returns the maximum size of a single query's result, which is the value of
+    /// `max_query_result_size` capped by `max_query_result_size_limit`.
+    pub fn effective_max_query_result_size(&self) -> u64 {
+        let size = self.max_query_result_size();
+        match self.expect_value::<Option<ByteSize>>(&MAX_QUERY_RESULT_SIZE_LIMIT) {
+            Some(limit) => std::cmp::min(size, limit.as_bytes()),
+            None => size,
+        }
+    }
+
+    /// Returns the value of the `allowed_clusters` configuration parameter.
+    pub fn allowed_clusters(&self) -> &[Ident] {
+        self.expect_value::<Vec<Ident>>(&ALLOWED_CLUSTERS)
+            .as_slice()
+    }
+
     /// Sets the external metadata associated with the user.
     pub fn set_external_user_metadata(&mut self, metadata: ExternalUserMetadata) {
         self.user.external_metadata = Some(metadata);
@@ -1202,6 +1269,12 @@ impl SystemVars {
             &STORAGE_SHRINK_UPSERT_UNUSED_BUFFERS_BY_RATIO,
             &STORAGE_RECORD_SOURCE_SINK_NAMESPACED_ERRORS,
             &PERSIST_FAST_PATH_LIMIT,
+            &RANGE_JOIN_BUCKET_WIDTH,
+            &RANGE_JOIN_MAX_BUCKETS,
+            &CURSOR_PAGE_SIZE,
+            &PEEK_RESULT_CACHE_SIZE,
+            &SUBSCRIBE_FAN_OUT_MAX_BUFFERED_ROWS,
+            &NETWORK_POLICY_INGRESS_ALLOWLIST,
             &TXN_WAL_TABLES,
             &METRICS_RETENTION,
             &UNSAFE_MOCK_AUDIT_EVENT_TIMESTAMP,
@@ -1223,6 +1296,8 @@ impl SystemVars {
             &SSH_CHECK_INTERVAL,
             &SSH_CONNECT_TIMEOUT,
             &SSH_KEEPALIVES_IDLE,
+            &SSH_RECONNECT_BACKOFF,
+            &SSH_RECONNECT_GRACE_PERIOD,
             &KAFKA_SOCKET_KEEPALIVE,
             &KAFKA_SOCKET_TIMEOUT,
             &KAFKA_TRANSACTION_TIMEOUT,
@@ -1236,6 +1311,7 @@ impl SystemVars {
             &KEEP_N_SOURCE_STATUS_HISTORY_ENTRIES,
             &KEEP_N_SINK_STATUS_HISTORY_ENTRIES,
             &KEEP_N_PRIVATELINK_STATUS_HISTORY_ENTRIES,
+            &KEEP_N_CLUSTER_REPLICA_EVENT_ENTRIES,
             &ARRANGEMENT_EXERT_PROPORTIONALITY,
             &ENABLE_STORAGE_SHARD_FINALIZATION,
             &ENABLE_CONSOLIDATE_AFTER_UNION_NEGATE,
@@ -1264,6 +1340,18 @@ impl SystemVars {
             &cluster_scheduling::CLUSTER_ALWAYS_USE_DISK,
             &cluster_scheduling::CLUSTER_CHECK_SCHEDULING_POLICIES_INTERVAL,
             &cluster_scheduling::CLUSTER_SECURITY_CONTEXT_ENABLED,
+            &cluster_scheduling::CLUSTER_REPLACE_CRASH_LOOPING_REPLICAS,
+            &cluster_scheduling::CLUSTER_CRASH_LOOP_THRESHOLD,
+            &cluster_scheduling::CLUSTER_CRASH_LOOP_WINDOW,
+            &cluster_scheduling::CLUSTER_CRASH_LOOP_REPLACEMENT_BACKOFF,
+            &cluster_scheduling::CLUSTER_CRASH_LOOP_REPLACEMENT_MAX_BACKOFF,
+            &cluster_scheduling::CLUSTER_REPLICA_HEARTBEAT_TIMEOUT,
+            &cluster_scheduling::CLUSTER_AUTOSCALING_SCALE_UP_MEMORY_PERCENT,
+            &cluster_scheduling::CLUSTER_AUTOSCALING_SCALE_DOWN_MEMORY_PERCENT,
+            &cluster_scheduling::CLUSTER_AUTOSCALING_MAX_FRONTIER_LAG,
+            &cluster_scheduling::CLUSTER_AUTOSCALING_COOLDOWN,
+            &cluster_scheduling::CLUSTER_GRACEFUL_RECONFIGURATION_TIMEOUT,
+            &cluster_scheduling::CLUSTER_GRACEFUL_RECONFIGURATION_MAX_LAG,
             &grpc_client::HTTP2_KEEP_ALIVE_TIMEOUT,
             &STATEMENT_LOGGING_MAX_SAMPLE_RATE,
             &STATEMENT_LOGGING_DEFAULT_SAMPLE_RATE,
@@ -1271,8 +1359,12 @@ impl SystemVars {
             &STATEMENT_LOGGING_MAX_DATA_CREDIT,
             &OPTIMIZER_STATS_TIMEOUT,
             &OPTIMIZER_ONESHOT_STATS_TIMEOUT,
+            &OPTIMIZER_STATISTICS_REFRESH_INTERVAL,
+            &STORAGE_SHARD_USAGE_REPORT_INTERVAL,
             &PRIVATELINK_STATUS_UPDATE_QUOTA_PER_MINUTE,
+            &TLS_CLIENT_CERT_ROLE_MAPPING,
             &WEBHOOK_CONCURRENT_REQUEST_LIMIT,
+            &WEBHOOK_REQUEST_ROW_LIMIT,
             &PG_TIMESTAMP_ORACLE_CONNECTION_POOL_MAX_SIZE,
             &PG_TIMESTAMP_ORACLE_CONNECTION_POOL_MAX_WAIT,
             &PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL,
@@ -1780,6 +1872,34 @@ impl SystemVars {
         *self.expect_value(&PERSIST_FAST_PATH_LIMIT)
     }
 
+    pub fn range_join_bucket_width(&self) -> usize {
+        *self.expect_value(&RANGE_JOIN_BUCKET_WIDTH)
+    }
+
+    pub fn range_join_max_buckets(&self) -> usize {
+        *self.expect_value(&RANGE_JOIN_MAX_BUCKETS)
+    }
+
+    pub fn cursor_page_size(&self) -> usize {
+        *self.expect_value(&CURSOR_PAGE_SIZE)
+    }
+
+    /// Returns the `peek_result_cache_size` configuration parameter, in bytes.
+    pub fn peek_result_cache_size(&self) -> usize {
+        usize::cast_from(
+            self.expect_value::<ByteSize>(&PEEK_RESULT_CACHE_SIZE)
+                .as_bytes(),
+        )
+    }
+
+    pub fn subscribe_fan_out_max_buffered_rows(&self) -> usize {
+        *self.expect_value(&SUBSCRIBE_FAN_OUT_MAX_BUFFERED_ROWS)
+    }
+
+    pub fn network_policy_ingress_allowlist(&self) -> &[IpNetwork] {
+        self.expect_value::<Vec<IpNetwork>>(&NETWORK_POLICY_INGRESS_ALLOWLIST)
+    }
+
     pub fn txn_wal_tables(&self) -> TxnWalTablesImpl {
         *self.expect_value(&TXN_WAL_TABLES)
     }
@@ -1867,6 +1987,16 @@ impl SystemVars {
         *self.expect_value(&SSH_KEEPALIVES_IDLE)
     }
 
+    /// Returns the `ssh_reconnect_backoff` configuration parameter.
+    pub fn ssh_reconnect_backoff(&self) -> Duration {
+        *self.expect_value(&SSH_RECONNECT_BACKOFF)
+    }
+
+    /// Returns the `ssh_reconnect_grace_period` configuration parameter.
+    pub fn ssh_reconnect_grace_period(&self) -> Duration {
+        *self.expect_value(&SSH_RECONNECT_GRACE_PERIOD)
+    }
+
     /// Returns the `kafka_socket_keepalive` configuration parameter.
     pub fn kafka_socket_keepalive(&self) -> bool {
         *self.expect_value(&KAFKA_SOCKET_KEEPALIVE)
@@ -2023,6 +2153,10 @@ impl SystemVars {
         *self.expect_value(&KEEP_N_PRIVATELINK_STATUS_HISTORY_ENTRIES)
     }
 
+    pub fn keep_n_cluster_replica_event_entries(&self) -> usize {
+        *self.expect_value(&KEEP_N_CLUSTER_REPLICA_EVENT_ENTRIES)
+    }
+
     /// Returns the `arrangement_exert_proportionality` configuration parameter.
     pub fn arrangement_exert_proportionality(&self) -> u32 {
         *self.expect_value(&ARRANGEMENT_EXERT_PROPORTIONALITY)
@@ -2144,6 +2278,54 @@ impl SystemVars {
         *self.expect_value(&cluster_scheduling::CLUSTER_SECURITY_CONTEXT_ENABLED)
     }
 
+    pub fn cluster_replace_crash_looping_replicas(&self) -> bool {
+        *self.expect_value(&cluster_scheduling::CLUSTER_REPLACE_CRASH_LOOPING_REPLICAS)
+    }
+
+    pub fn cluster_crash_loop_threshold(&self) -> usize {
+        *self.expect_value(&cluster_scheduling::CLUSTER_CRASH_LOOP_THRESHOLD)
+    }
+
+    pub fn cluster_crash_loop_window(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_CRASH_LOOP_WINDOW)
+    }
+
+    pub fn cluster_crash_loop_replacement_backoff(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_CRASH_LOOP_REPLACEMENT_BACKOFF)
+    }
+
+    pub fn cluster_crash_loop_replacement_max_backoff(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_CRASH_LOOP_REPLACEMENT_MAX_BACKOFF)
+    }
+
+    pub fn cluster_replica_heartbeat_timeout(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_REPLICA_HEARTBEAT_TIMEOUT)
+    }
+
+    pub fn cluster_autoscaling_scale_up_memory_percent(&self) -> u32 {
+        *self.expect_value(&cluster_scheduling::CLUSTER_AUTOSCALING_SCALE_UP_MEMORY_PERCENT)
+    }
+
+    pub fn cluster_autoscaling_scale_down_memory_percent(&self) -> u32 {
+        *self.expect_value(&cluster_scheduling::CLUSTER_AUTOSCALING_SCALE_DOWN_MEMORY_PERCENT)
+    }
+
+    pub fn cluster_autoscaling_max_frontier_lag(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_AUTOSCALING_MAX_FRONTIER_LAG)
+    }
+
+    pub fn cluster_autoscaling_cooldown(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_AUTOSCALING_COOLDOWN)
+    }
+
+    pub fn cluster_graceful_reconfiguration_timeout(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_GRACEFUL_RECONFIGURATION_TIMEOUT)
+    }
+
+    pub fn cluster_graceful_reconfiguration_max_lag(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_GRACEFUL_RECONFIGURATION_MAX_LAG)
+    }
+
     /// Returns the `privatelink_status_update_quota_per_minute` configuration parameter.
     pub fn privatelink_status_update_quota_per_minute(&self) -> u32 {
         *self.expect_value(&PRIVATELINK_STATUS_UPDATE_QUOTA_PER_MINUTE)
@@ -2177,11 +2359,33 @@ impl SystemVars {
         *self.expect_value(&OPTIMIZER_ONESHOT_STATS_TIMEOUT)
     }
 
+    /// Returns the `optimizer_statistics_refresh_interval` configuration parameter.
+    pub fn optimizer_statistics_refresh_interval(&self) -> Duration {
+        *self.expect_value(&OPTIMIZER_STATISTICS_REFRESH_INTERVAL)
+    }
+
+    /// Returns the `storage_shard_usage_report_interval` configuration parameter.
+    pub fn storage_shard_usage_report_interval(&self) -> Duration {
+        *self.expect_value(&STORAGE_SHARD_USAGE_REPORT_INTERVAL)
+    }
+
+    /// Returns the `tls_client_cert_role_mapping` configuration parameter.
+    pub fn tls_client_cert_role_mapping(&self) -> CertRoleMapping {
+        // Values are validated when set, but should one slip through, map no
+        // certificates rather than panic.
+        CertRoleMapping::parse(self.expect_value(&TLS_CLIENT_CERT_ROLE_MAPPING)).unwrap_or_default()
+    }
+
     /// Returns the `webhook_concurrent_request_limit` configuration parameter.
     pub fn webhook_concurrent_request_limit(&self) -> usize {
         *self.expect_value(&WEBHOOK_CONCURRENT_REQUEST_LIMIT)
     }
 
+    /// Returns the `webhook_request_row_limit` configuration parameter.
+    pub fn webhook_request_row_limit(&self) -> usize {
+        *self.expect_value(&WEBHOOK_REQUEST_ROW_LIMIT)
+    }
+
     /// Returns the `pg_timestamp_oracle_connection_pool_max_size` configuration parameter.
     pub fn pg_timestamp_oracle_connection_pool_max_size(&self) -> usize {
         *self.expect_value(&PG_TIMESTAMP_ORACLE_CONNECTION_POOL_MAX_SIZE)
@@ -2234,6 +2438,8 @@ impl SystemVars {
             || name == SSH_CHECK_INTERVAL.name()
             || name == SSH_CONNECT_TIMEOUT.name()
             || name == SSH_KEEPALIVES_IDLE.name()
+            || name == SSH_RECONNECT_BACKOFF.name()
+            || name == SSH_RECONNECT_GRACE_PERIOD.name()
             || name == KAFKA_SOCKET_KEEPALIVE.name()
             || name == KAFKA_SOCKET_TIMEOUT.name()
             || name == KAFKA_TRANSACTION_TIMEOUT.name()
@@ -2313,9 +2519,18 @@ pub fn is_cluster_scheduling_var(name: &str) -> bool {
         || name == cluster_scheduling::CLUSTER_ALWAYS_USE_DISK.name()
 }
 
+/// Returns whether the named variable limits the resources of a role, and can thus only be
+/// configured with `ALTER ROLE`.
+pub fn is_role_limit_var(name: &str) -> bool {
+    let name = UncasedStr::new(name);
+    name == STATEMENT_TIMEOUT_LIMIT.name
+        || name == MAX_QUERY_RESULT_SIZE_LIMIT.name
+        || name == ALLOWED_CLUSTERS.name
+}
+
 /// Returns whether the named variable is an HTTP server related config var.
 pub fn is_http_config_var(name: &str) -> bool {
-    name == WEBHOOK_CONCURRENT_REQUEST_LIMIT.name()
+    name == WEBHOOK_CONCURRENT_REQUEST_LIMIT.name() || name == WEBHOOK_REQUEST_ROW_LIMIT.name()
 }
 
 // Provides a wrapper to express that a particular `ServerVar` is meant to be used as a feature
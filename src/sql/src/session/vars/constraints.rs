@@ -15,12 +15,15 @@ use std::ops::{RangeBounds, RangeInclusive};
 use mz_repr::adt::numeric::Numeric;
 
 use super::{Value, Var, VarError};
+use crate::session::user::CertRoleMapping;
 
 pub static NUMERIC_NON_NEGATIVE: NumericNonNegNonNan = NumericNonNegNonNan;
 
 pub static NUMERIC_BOUNDED_0_1_INCLUSIVE: NumericInRange<RangeInclusive<f64>> =
     NumericInRange(0.0f64..=1.0);
 
+pub static VALID_CERT_ROLE_MAPPING: ValidCertRoleMapping = ValidCertRoleMapping;
+
 #[derive(Debug)]
 pub enum ValueConstraint {
     /// Variable is read-only and cannot be updated.
@@ -149,3 +152,22 @@ where
         }
     }
 }
+
+/// Requires a string to be a valid set of [`CertRoleMapping`] rules.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ValidCertRoleMapping;
+
+impl DomainConstraint for ValidCertRoleMapping {
+    type Value = String;
+
+    fn check(&self, var: &dyn Var, s: &String) -> Result<(), VarError> {
+        match CertRoleMapping::parse(s) {
+            Ok(_) => Ok(()),
+            Err(reason) => Err(VarError::InvalidParameterValue {
+                name: var.name(),
+                invalid_values: vec![s.clone()],
+                reason,
+            }),
+        }
+    }
+}
@@ -19,6 +19,7 @@ use mz_adapter_types::timestamp_oracle::{
     DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_TTL, DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_TTL_STAGGER,
 };
 use mz_ore::cast::{self, CastFrom};
+use mz_ore::cidr::IpNetwork;
 use mz_repr::adt::numeric::Numeric;
 use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::bytes::ByteSize;
@@ -39,6 +40,7 @@ use uncased::UncasedStr;
 use crate::session::user::{User, SUPPORT_USER, SYSTEM_USER};
 use crate::session::vars::constraints::{
     DomainConstraint, ValueConstraint, NUMERIC_BOUNDED_0_1_INCLUSIVE, NUMERIC_NON_NEGATIVE,
+    VALID_CERT_ROLE_MAPPING,
 };
 use crate::session::vars::errors::VarError;
 use crate::session::vars::polyfill::{lazy_value, value, LazyValueFn};
@@ -47,7 +49,7 @@ use crate::session::vars::value::{
     DEFAULT_DATE_STYLE,
 };
 use crate::session::vars::{FeatureFlag, Var, VarInput, VarParseError};
-use crate::{DEFAULT_SCHEMA, WEBHOOK_CONCURRENCY_LIMIT};
+use crate::{DEFAULT_SCHEMA, WEBHOOK_CONCURRENCY_LIMIT, WEBHOOK_ROWS_PER_REQUEST_LIMIT};
 
 /// Definition of a variable.
 #[derive(Clone, Derivative)]
@@ -367,6 +369,31 @@ pub static IDLE_IN_TRANSACTION_SESSION_TIMEOUT: VarDefinition = VarDefinition::n
     false,
 );
 
+pub static STATEMENT_TIMEOUT_LIMIT: VarDefinition = VarDefinition::new(
+    "statement_timeout_limit",
+    value!(Duration; Duration::ZERO),
+    "Sets the maximum allowed duration of queries and of operations subject to \
+    `statement_timeout`. Can only be configured with ALTER ROLE. If this value is specified \
+    without units, it is taken as milliseconds. A value of zero disables the limit (Materialize).",
+    false,
+);
+
+pub static MAX_QUERY_RESULT_SIZE_LIMIT: VarDefinition = VarDefinition::new(
+    "max_query_result_size_limit",
+    value!(Option<ByteSize>; None),
+    "Sets the maximum size in bytes for a single query's result, regardless of \
+    `max_query_result_size`. Can only be configured with ALTER ROLE (Materialize).",
+    false,
+);
+
+pub static ALLOWED_CLUSTERS: VarDefinition = VarDefinition::new_lazy(
+    "allowed_clusters",
+    lazy_value!(Vec<Ident>; || vec![]),
+    "Sets the clusters that queries may run on. Can only be configured with ALTER ROLE. \
+    An empty list allows all clusters (Materialize).",
+    false,
+);
+
 pub static SERVER_VERSION: VarDefinition = VarDefinition::new_lazy(
     "server_version",
     lazy_value!(String; || {
@@ -592,6 +619,56 @@ pub static PERSIST_FAST_PATH_LIMIT: VarDefinition = VarDefinition::new(
     true,
 );
 
+pub static RANGE_JOIN_BUCKET_WIDTH: VarDefinition = VarDefinition::new(
+    "range_join_bucket_width",
+    value!(usize; 3600),
+    "The width of the buckets that range joins are rewritten to join on, in units of the joined \
+    values, or in seconds for timestamps. Only used if `enable_range_join_bucketing` is on.",
+    true,
+);
+
+pub static RANGE_JOIN_MAX_BUCKETS: VarDefinition = VarDefinition::new(
+    "range_join_max_buckets",
+    value!(usize; 1024),
+    "The maximum number of buckets a range of a rewritten range join is replicated to. Wider \
+    ranges, including unbounded ones, are compared to every record of the other input instead. \
+    Only used if `enable_range_join_bucketing` is on.",
+    true,
+);
+
+pub static CURSOR_PAGE_SIZE: VarDefinition = VarDefinition::new(
+    "cursor_page_size",
+    value!(usize; 10_000),
+    "The number of rows that are read at a time from indexes for the rows of cursors. \
+    Only used if `enable_paged_cursors` is on.",
+    true,
+);
+
+pub static PEEK_RESULT_CACHE_SIZE: VarDefinition = VarDefinition::new(
+    "peek_result_cache_size",
+    value!(ByteSize; ByteSize::mib(64)),
+    "The maximum total size of the results of fast-path peeks that are cached for reuse by \
+    identical peeks at the same timestamp. Only used if `enable_peek_result_cache` is on.",
+    true,
+);
+
+pub static SUBSCRIBE_FAN_OUT_MAX_BUFFERED_ROWS: VarDefinition = VarDefinition::new(
+    "subscribe_fan_out_max_buffered_rows",
+    value!(usize; 1_000_000),
+    "The maximum number of rows buffered for a SUBSCRIBE sharing a dataflow that its client has \
+    not yet read. SUBSCRIBEs exceeding it are terminated. Only used if \
+    `enable_subscribe_fan_out` is on.",
+    true,
+);
+
+pub static NETWORK_POLICY_INGRESS_ALLOWLIST: VarDefinition = VarDefinition::new(
+    "network_policy_ingress_allowlist",
+    value!(Vec<IpNetwork>; Vec::new()),
+    "The IP networks, in CIDR notation, that user sessions may connect from. An empty list \
+    allows connections from any address (Materialize).",
+    false,
+);
+
 pub static TXN_WAL_TABLES: VarDefinition = VarDefinition::new(
     // The actual name is kept as "persist_txn_tables" instead of "txn_wal_tables" for historical
     // reasons.
@@ -1064,6 +1141,22 @@ pub static SSH_KEEPALIVES_IDLE: VarDefinition = VarDefinition::new(
     true,
 );
 
+/// Controls the initial backoff between reconnection attempts for dropped SSH tunnels.
+pub static SSH_RECONNECT_BACKOFF: VarDefinition = VarDefinition::new(
+    "ssh_reconnect_backoff",
+    value!(Duration; mz_ssh_util::tunnel::DEFAULT_RECONNECT_BACKOFF),
+    "Controls the initial backoff between reconnection attempts for dropped SSH tunnels via `mz_ssh_util`.",
+    true,
+);
+
+/// Controls how long a dropped SSH tunnel may spend reconnecting before it is reported as errored.
+pub static SSH_RECONNECT_GRACE_PERIOD: VarDefinition = VarDefinition::new(
+    "ssh_reconnect_grace_period",
+    value!(Duration; mz_ssh_util::tunnel::DEFAULT_RECONNECT_GRACE_PERIOD),
+    "Controls how long a dropped SSH tunnel may spend reconnecting before it is reported as errored via `mz_ssh_util`.",
+    true,
+);
+
 /// Enables `socket.keepalive.enable` for rdkafka client connections. Defaults to true.
 pub static KAFKA_SOCKET_KEEPALIVE: VarDefinition = VarDefinition::new(
     "kafka_socket_keepalive",
@@ -1244,6 +1337,13 @@ pub static EMIT_PLAN_INSIGHTS_NOTICE: VarDefinition = VarDefinition::new(
     false,
 );
 
+pub static EMIT_STRUCTURED_NOTICES: VarDefinition = VarDefinition::new(
+    "emit_structured_notices",
+    value!(bool; false),
+    "Boolean flag indicating whether to attach a JSON description with a stable name and machine-readable fields to each NOTICE (Materialize).",
+    false,
+);
+
 pub static EMIT_TIMESTAMP_NOTICE: VarDefinition = VarDefinition::new(
     "emit_timestamp_notice",
     value!(bool; false),
@@ -1315,6 +1415,22 @@ pub static OPTIMIZER_ONESHOT_STATS_TIMEOUT: VarDefinition = VarDefinition::new(
     true,
 );
 
+pub static OPTIMIZER_STATISTICS_REFRESH_INTERVAL: VarDefinition = VarDefinition::new(
+    "optimizer_statistics_refresh_interval",
+    value!(Duration; Duration::from_secs(5 * 60)),
+    "Sets the interval at which the column statistics used by the optimizer are \
+        recollected from storage (Materialize).",
+    true,
+);
+
+pub static STORAGE_SHARD_USAGE_REPORT_INTERVAL: VarDefinition = VarDefinition::new(
+    "storage_shard_usage_report_interval",
+    value!(Duration; Duration::from_secs(60 * 60)),
+    "Sets the interval at which the usage and fragmentation of storage shards is \
+        reported in mz_storage_shard_usage (Materialize).",
+    true,
+);
+
 pub static PRIVATELINK_STATUS_UPDATE_QUOTA_PER_MINUTE: VarDefinition = VarDefinition::new(
     "privatelink_status_update_quota_per_minute",
     value!(u32; 20),
@@ -1424,6 +1540,15 @@ pub static KEEP_N_PRIVATELINK_STATUS_HISTORY_ENTRIES: VarDefinition = VarDefinit
     true,
 );
 
+/// Controls [`mz_storage_types::parameters::StorageParameters::keep_n_cluster_replica_event_entries`].
+pub static KEEP_N_CLUSTER_REPLICA_EVENT_ENTRIES: VarDefinition = VarDefinition::new(
+    "keep_n_cluster_replica_event_entries",
+    value!(usize; 20),
+    "On reboot, truncate all but the last n entries per replica in the mz_cluster_replica_events \
+        collection (Materialize).",
+    true,
+);
+
 pub static ENABLE_STORAGE_SHARD_FINALIZATION: VarDefinition = VarDefinition::new(
     "enable_storage_shard_finalization",
     value!(bool; true),
@@ -1452,6 +1577,15 @@ pub static MAX_TIMESTAMP_INTERVAL: VarDefinition = VarDefinition::new(
     true,
 );
 
+pub static TLS_CLIENT_CERT_ROLE_MAPPING: VarDefinition = VarDefinition::new(
+    "tls_client_cert_role_mapping",
+    value!(String; String::new()),
+    "Rules that map the common name or subject alternative names of a verified TLS client \
+    certificate to the role the client logs in as (Materialize).",
+    true,
+)
+.with_constraint(&VALID_CERT_ROLE_MAPPING);
+
 pub static WEBHOOK_CONCURRENT_REQUEST_LIMIT: VarDefinition = VarDefinition::new(
     "webhook_concurrent_request_limit",
     value!(usize; WEBHOOK_CONCURRENCY_LIMIT),
@@ -1459,6 +1593,13 @@ pub static WEBHOOK_CONCURRENT_REQUEST_LIMIT: VarDefinition = VarDefinition::new(
     true,
 );
 
+pub static WEBHOOK_REQUEST_ROW_LIMIT: VarDefinition = VarDefinition::new(
+    "webhook_request_row_limit",
+    value!(usize; WEBHOOK_ROWS_PER_REQUEST_LIMIT),
+    "Maximum number of rows a single request may append to a webhook source.",
+    true,
+);
+
 pub static USER_STORAGE_MANAGED_COLLECTIONS_BATCH_DURATION: VarDefinition = VarDefinition::new(
     "user_storage_managed_collections_batch_duration",
     value!(Duration; STORAGE_MANAGED_COLLECTIONS_BATCH_DURATION_DEFAULT),
@@ -1586,6 +1727,118 @@ pub mod cluster_scheduling {
         "Enables SecurityContext for clusterd instances, restricting capabilities to improve security.",
         true,
     );
+
+    pub static CLUSTER_REPLACE_CRASH_LOOPING_REPLICAS: VarDefinition = VarDefinition::new(
+        "cluster_replace_crash_looping_replicas",
+        value!(bool; false),
+        "Whether to automatically replace replicas of managed clusters that are crash looping \
+            (Materialize).",
+        true,
+    );
+
+    pub static CLUSTER_CRASH_LOOP_THRESHOLD: VarDefinition = VarDefinition::new(
+        "cluster_crash_loop_threshold",
+        value!(usize; 5),
+        "The number of times a replica must go from ready to not ready within \
+            `cluster_crash_loop_window` to be considered crash looping (Materialize).",
+        true,
+    );
+
+    const DEFAULT_CRASH_LOOP_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+    pub static CLUSTER_CRASH_LOOP_WINDOW: VarDefinition = VarDefinition::new(
+        "cluster_crash_loop_window",
+        value!(Duration; DEFAULT_CRASH_LOOP_WINDOW),
+        "The window over which replica crashes are counted to detect crash loops (Materialize).",
+        true,
+    );
+
+    const DEFAULT_CRASH_LOOP_REPLACEMENT_BACKOFF: Duration = Duration::from_secs(60);
+
+    pub static CLUSTER_CRASH_LOOP_REPLACEMENT_BACKOFF: VarDefinition = VarDefinition::new(
+        "cluster_crash_loop_replacement_backoff",
+        value!(Duration; DEFAULT_CRASH_LOOP_REPLACEMENT_BACKOFF),
+        "How long to wait before replacing a crash looping replica again after replacing a \
+            replica of the same name. Doubles with every consecutive replacement (Materialize).",
+        true,
+    );
+
+    const DEFAULT_CRASH_LOOP_REPLACEMENT_MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+    pub static CLUSTER_CRASH_LOOP_REPLACEMENT_MAX_BACKOFF: VarDefinition = VarDefinition::new(
+        "cluster_crash_loop_replacement_max_backoff",
+        value!(Duration; DEFAULT_CRASH_LOOP_REPLACEMENT_MAX_BACKOFF),
+        "The maximum time to wait between replacements of crash looping replicas of the same \
+            name (Materialize).",
+        true,
+    );
+
+    const DEFAULT_REPLICA_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(60);
+
+    pub static CLUSTER_REPLICA_HEARTBEAT_TIMEOUT: VarDefinition = VarDefinition::new(
+        "cluster_replica_heartbeat_timeout",
+        value!(Duration; DEFAULT_REPLICA_HEARTBEAT_TIMEOUT),
+        "How long a replica with introspection enabled may go without responding to the \
+            controller before it is considered unresponsive. Zero disables the check \
+            (Materialize).",
+        true,
+    );
+
+    pub static CLUSTER_AUTOSCALING_SCALE_UP_MEMORY_PERCENT: VarDefinition = VarDefinition::new(
+        "cluster_autoscaling_scale_up_memory_percent",
+        value!(u32; 85),
+        "The memory utilization, in percent of the memory limit, of any replica process above \
+            which the autoscaler grows a cluster (Materialize).",
+        true,
+    );
+
+    pub static CLUSTER_AUTOSCALING_SCALE_DOWN_MEMORY_PERCENT: VarDefinition = VarDefinition::new(
+        "cluster_autoscaling_scale_down_memory_percent",
+        value!(u32; 30),
+        "The memory utilization, in percent of the memory limit, of all replica processes below \
+            which the autoscaler shrinks a cluster (Materialize).",
+        true,
+    );
+
+    const DEFAULT_AUTOSCALING_MAX_FRONTIER_LAG: Duration = Duration::from_secs(5 * 60);
+
+    pub static CLUSTER_AUTOSCALING_MAX_FRONTIER_LAG: VarDefinition = VarDefinition::new(
+        "cluster_autoscaling_max_frontier_lag",
+        value!(Duration; DEFAULT_AUTOSCALING_MAX_FRONTIER_LAG),
+        "How far the write frontier of a dataflow may lag behind the current time before the \
+            autoscaler grows its cluster (Materialize).",
+        true,
+    );
+
+    const DEFAULT_AUTOSCALING_COOLDOWN: Duration = Duration::from_secs(15 * 60);
+
+    pub static CLUSTER_AUTOSCALING_COOLDOWN: VarDefinition = VarDefinition::new(
+        "cluster_autoscaling_cooldown",
+        value!(Duration; DEFAULT_AUTOSCALING_COOLDOWN),
+        "The minimum time between two resizes of the same cluster by the autoscaler, which gives \
+            new replicas time to hydrate (Materialize).",
+        true,
+    );
+
+    const DEFAULT_GRACEFUL_RECONFIGURATION_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+    pub static CLUSTER_GRACEFUL_RECONFIGURATION_TIMEOUT: VarDefinition = VarDefinition::new(
+        "cluster_graceful_reconfiguration_timeout",
+        value!(Duration; DEFAULT_GRACEFUL_RECONFIGURATION_TIMEOUT),
+        "How long a graceful `ALTER CLUSTER` waits for the new replicas to hydrate and catch up \
+            before giving up and dropping them again (Materialize).",
+        true,
+    );
+
+    const DEFAULT_GRACEFUL_RECONFIGURATION_MAX_LAG: Duration = Duration::from_secs(1);
+
+    pub static CLUSTER_GRACEFUL_RECONFIGURATION_MAX_LAG: VarDefinition = VarDefinition::new(
+        "cluster_graceful_reconfiguration_max_lag",
+        value!(Duration; DEFAULT_GRACEFUL_RECONFIGURATION_MAX_LAG),
+        "How far the write frontiers of new replicas may trail those of the existing replicas \
+            for a graceful `ALTER CLUSTER` to consider them caught up (Materialize).",
+        true,
+    );
 }
 
 /// Macro to simplify creating feature flags, i.e. boolean flags that we use to toggle the
@@ -1995,6 +2248,13 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_load_generator_custom,
+        desc: "Create a LOAD GENERATOR CUSTOM",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
     {
         name: enable_expressions_in_limit_syntax,
         desc: "LIMIT <expr> syntax",
@@ -2038,6 +2298,27 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: true,
     },
+    {
+        name: enable_cluster_schedule_off_during,
+        desc: "`SCHEDULE = OFF DURING` cluster option",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_cluster_autoscaling,
+        desc: "cluster autoscaling",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_graceful_cluster_reconfiguration,
+        desc: "ALTER CLUSTER ... WITH (GRACEFUL)",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
     {
         name: enable_reduce_mfp_fusion,
         desc: "fusion of MFPs in reductions",
@@ -2045,6 +2326,42 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_incremental_window_functions,
+        desc: "incremental rendering of ROW_NUMBER, LAG, and LEAD over append-only inputs, \
+            which retains every input record in memory",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_range_join_bucketing,
+        desc: "rewriting range joins into equi-joins on buckets of the range",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_paged_cursors,
+        desc: "reading the rows of cursors over indexes in pages, as they are fetched",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_peek_result_cache,
+        desc: "reusing the results of identical fast-path peeks at the same timestamp",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_append_only_mvs,
+        desc: "ASSERT APPEND ONLY materialized views",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
     {
         name: enable_worker_core_affinity,
         desc: "set core affinity for replica worker threads",
@@ -2101,6 +2418,27 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: true,
     },
+    {
+        name: enable_optimizer_statistics_collection,
+        desc: "periodic collection of column statistics for the optimizer from persisted collections",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_storage_shard_usage_report,
+        desc: "periodic reporting of the blob usage and fragmentation of storage shards",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_subscribe_fan_out,
+        desc: "sharing a single dataflow between identical SUBSCRIBEs",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
     {
         name: enable_unlimited_retain_history,
         desc: "Disable limits on RETAIN HISTORY (below 1s default, and 0 disables compaction).",
@@ -2117,6 +2455,10 @@ impl From<&super::SystemVars> for OptimizerFeatures {
             enable_eager_delta_joins: vars.enable_eager_delta_joins(),
             enable_new_outer_join_lowering: vars.enable_new_outer_join_lowering(),
             enable_reduce_mfp_fusion: vars.enable_reduce_mfp_fusion(),
+            enable_incremental_window_functions: vars.enable_incremental_window_functions(),
+            enable_range_join_bucketing: vars.enable_range_join_bucketing(),
+            range_join_bucket_width: vars.range_join_bucket_width(),
+            range_join_max_buckets: vars.range_join_max_buckets(),
             enable_variadic_left_join_lowering: vars.enable_variadic_left_join_lowering(),
             enable_letrec_fixpoint_analysis: vars.enable_letrec_fixpoint_analysis(),
             enable_cardinality_estimates: vars.enable_cardinality_estimates(),
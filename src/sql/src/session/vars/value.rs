@@ -15,6 +15,7 @@ use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
+use mz_ore::cidr::IpNetwork;
 use mz_pgwire_common::Severity;
 use mz_repr::adt::numeric::Numeric;
 use mz_repr::adt::timestamp::CheckedTimestamp;
@@ -513,6 +514,41 @@ impl Value for Vec<SerializableDirective> {
     }
 }
 
+impl Value for Vec<IpNetwork> {
+    fn type_name() -> Cow<'static, str>
+    where
+        Self: Sized,
+    {
+        "IP network list".into()
+    }
+
+    fn parse(input: VarInput<'_>) -> Result<Self, VarParseError>
+    where
+        Self: Sized,
+    {
+        let values = input.to_vec();
+        let networks: Result<_, _> = values
+            .iter()
+            .flat_map(|i| i.split(','))
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(IpNetwork::from_str)
+            .collect();
+        networks.map_err(|e| VarParseError::InvalidParameterValue {
+            invalid_values: values.to_vec(),
+            reason: e.to_string().into(),
+        })
+    }
+
+    fn box_clone(&self) -> Box<dyn Value> {
+        Box::new(self.clone())
+    }
+
+    fn format(&self) -> String {
+        self.iter().map(|n| n.to_string()).join(", ")
+    }
+}
+
 // This unorthodox design lets us escape complex errors from value parsing.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Failpoints;
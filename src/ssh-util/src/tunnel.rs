@@ -15,7 +15,7 @@ use std::net::{Ipv4Addr, SocketAddr};
 use std::os::unix::fs::PermissionsExt;
 use std::sync::atomic::{AtomicU16, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::bail;
 use itertools::Itertools;
@@ -40,6 +40,13 @@ pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
 /// is comfortably beneath that threshold without being overly chatty.
 pub const DEFAULT_KEEPALIVES_IDLE: Duration = Duration::from_secs(10);
 
+/// The initial delay between attempts to re-establish a dropped SSH tunnel.
+pub const DEFAULT_RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Bastions are routinely restarted or fail over; give a dropped tunnel a
+/// minute to come back before surfacing an error to its users.
+pub const DEFAULT_RECONNECT_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
 /// Configuration of Ssh session and tunnel timeouts.
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SshTimeoutConfig {
@@ -51,6 +58,13 @@ pub struct SshTimeoutConfig {
     /// keepalive packet to the SSH server to determine whether the server is
     /// still alive.
     pub keepalives_idle: Duration,
+    /// The initial delay between attempts to re-establish a dropped SSH
+    /// tunnel. The delay doubles after each failed attempt, up to
+    /// `check_interval`.
+    pub reconnect_backoff: Duration,
+    /// How long a dropped SSH tunnel may spend reconnecting before it is
+    /// reported as errored.
+    pub reconnect_grace_period: Duration,
 }
 
 impl Default for SshTimeoutConfig {
@@ -59,6 +73,8 @@ impl Default for SshTimeoutConfig {
             check_interval: DEFAULT_CHECK_INTERVAL,
             connect_timeout: DEFAULT_CONNECT_TIMEOUT,
             keepalives_idle: DEFAULT_KEEPALIVES_IDLE,
+            reconnect_backoff: DEFAULT_RECONNECT_BACKOFF,
+            reconnect_grace_period: DEFAULT_RECONNECT_GRACE_PERIOD,
         }
     }
 }
@@ -162,30 +178,47 @@ impl SshTunnelConfig {
                 interval.tick().await;
                 loop {
                     interval.tick().await;
-                    if let Err(e) = session.check().await {
-                        warn!(%tunnel_id, "ssh tunnel unhealthy: {}", e.display_with_causes());
-                        let s = match connect(&config, timeout_config).await {
-                            Ok(s) => s,
-                            Err(e) => {
-                                warn!(%tunnel_id, "reconnection to ssh tunnel failed: {}", e.display_with_causes());
-                                *status.lock().expect("poisoned") =
-                                    SshTunnelStatus::Errored(e.to_string_with_causes());
-                                continue;
-                            }
+                    let Err(e) = session.check().await else {
+                        continue;
+                    };
+                    warn!(%tunnel_id, "ssh tunnel unhealthy: {}", e.display_with_causes());
+
+                    // Re-establish the tunnel, backing off between attempts.
+                    // Users of the tunnel keep seeing it as running until
+                    // we've been unable to reconnect for the grace period, so
+                    // that brief bastion restarts don't error dependent
+                    // sources and sinks.
+                    let unhealthy_since = Instant::now();
+                    let mut backoff = timeout_config.reconnect_backoff;
+                    loop {
+                        let reconnected = match connect(&config, timeout_config).await {
+                            Ok(s) => port_forward(&s, &remote_host, remote_port)
+                                .await
+                                .map(|lp| (s, lp)),
+                            Err(e) => Err(e),
                         };
-                        let lp = match port_forward(&s, &remote_host, remote_port).await {
-                            Ok(lp) => lp,
+                        match reconnected {
+                            Ok((s, lp)) => {
+                                info!(%tunnel_id, local_port = %lp, "reconnected to ssh tunnel");
+                                session = s;
+                                local_port.store(lp, Ordering::SeqCst);
+                                *status.lock().expect("poisoned") = SshTunnelStatus::Running;
+                                break;
+                            }
                             Err(e) => {
                                 warn!(%tunnel_id, "reconnection to ssh tunnel failed: {}", e.display_with_causes());
-                                *status.lock().expect("poisoned") =
-                                    SshTunnelStatus::Errored(e.to_string_with_causes());
-                                continue;
+                                if unhealthy_since.elapsed()
+                                    >= timeout_config.reconnect_grace_period
+                                {
+                                    *status.lock().expect("poisoned") =
+                                        SshTunnelStatus::Errored(e.to_string_with_causes());
+                                }
                             }
-                        };
-                        session = s;
-                        local_port.store(lp, Ordering::SeqCst);
-                        *status.lock().expect("poisoned") = SshTunnelStatus::Running;
+                        }
+                        time::sleep(backoff).await;
+                        backoff = std::cmp::min(backoff * 2, timeout_config.check_interval);
                     }
+                    interval.reset();
                 }
             }
         });
@@ -87,9 +87,18 @@ pub enum IntrospectionType {
     ComputeHydrationStatus,
     ComputeOperatorHydrationStatus,
     ComputeMaterializedViewRefreshes,
+    ComputeReapedDataflows,
 
     // Written by the Adapter for tracking AWS PrivateLink Connection Status History
     PrivatelinkConnectionStatusHistory,
+
+    // Written by the storage controller as it finalizes the shards of dropped
+    // collections.
+    StorageShardFinalizations,
+
+    // Written by the controller as the orchestrator reports events for
+    // replica processes.
+    ReplicaEvents,
 }
 
 /// Describes how data is written to the collection.
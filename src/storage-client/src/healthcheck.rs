@@ -122,3 +122,15 @@ pub static MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC: Lazy<RelationDesc>
             .with_column("connection_id", ScalarType::String.nullable(false))
             .with_column("status", ScalarType::String.nullable(false))
     });
+
+pub static MZ_CLUSTER_REPLICA_EVENTS_DESC: Lazy<RelationDesc> = Lazy::new(|| {
+    RelationDesc::empty()
+        .with_column("replica_id", ScalarType::String.nullable(false))
+        .with_column("process_id", ScalarType::UInt64.nullable(false))
+        .with_column("event_type", ScalarType::String.nullable(false))
+        .with_column("message", ScalarType::String.nullable(true))
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+});
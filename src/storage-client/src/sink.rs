@@ -7,11 +7,11 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::time::Duration;
 
 use anyhow::{anyhow, bail, Context};
-use mz_ccsr::GetSubjectConfigError;
+use mz_ccsr::{CheckCompatibilityError, GetSubjectConfigError};
 use mz_kafka_util::client::MzClientContext;
 use mz_ore::collections::CollectionExt;
 use mz_ore::future::{InTask, OreFutureExt};
@@ -226,6 +226,10 @@ pub async fn ensure_kafka_topic(
 /// Publish value and optional key schemas for a given topic, and set
 /// compatibility levels for the schemas if applicable.
 ///
+/// If `schema_references` is set, named types nested within Avro schemas are
+/// registered under their own subjects and referenced from the key and value
+/// schemas. See [`split_avro_schema`].
+///
 /// TODO(benesch): do we need to delete the Kafka topic if publishing the
 /// schema fails?
 pub async fn publish_kafka_schemas(
@@ -237,6 +241,7 @@ pub async fn publish_kafka_schemas(
     value_schema_type: mz_ccsr::SchemaType,
     key_compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
     value_compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
+    schema_references: bool,
 ) -> Result<(Option<i32>, i32), anyhow::Error> {
     let key_subject = format!("{}-key", topic);
     let value_subject = format!("{}-value", topic);
@@ -305,8 +310,14 @@ pub async fn publish_kafka_schemas(
             key_schema_type.ok_or_else(|| anyhow!("expected schema type for key schema"))?;
         Some(
             async move {
-                ccsr.publish_schema(&key_subject, &key_schema, key_schema_type, &[])
-                    .await
+                publish_schema_with_references(
+                    &ccsr,
+                    &key_subject,
+                    &key_schema,
+                    key_schema_type,
+                    schema_references,
+                )
+                .await
             }
             .run_in_task(|| "publish_kafka_key_schema".to_string())
             .await
@@ -317,8 +328,14 @@ pub async fn publish_kafka_schemas(
     };
 
     let value_schema_id = async move {
-        ccsr.publish_schema(&value_subject, &value_schema, value_schema_type, &[])
-            .await
+        publish_schema_with_references(
+            &ccsr,
+            &value_subject,
+            &value_schema,
+            value_schema_type,
+            schema_references,
+        )
+        .await
     }
     .run_in_task(|| "publish_kafka_value_schema".to_string())
     .await
@@ -326,3 +343,324 @@ pub async fn publish_kafka_schemas(
 
     Ok((key_schema_id, value_schema_id))
 }
+
+/// Publishes `schema` under `subject`.
+///
+/// If `split_references` is set and the schema is an Avro schema, the named
+/// types nested within it are first published under subjects of the form
+/// `{subject}-{fullname}`, and the top-level schema is published with
+/// references to them.
+async fn publish_schema_with_references(
+    ccsr: &mz_ccsr::Client,
+    subject: &str,
+    schema: &str,
+    schema_type: mz_ccsr::SchemaType,
+    split_references: bool,
+) -> Result<i32, anyhow::Error> {
+    if !split_references || !matches!(schema_type, mz_ccsr::SchemaType::Avro) {
+        return check_and_publish_schema(ccsr, subject, schema, schema_type, &[]).await;
+    }
+
+    let split = split_avro_schema(schema)?;
+
+    // Maps the fullname of each published named type to the subject and
+    // version it was published under.
+    let mut published: BTreeMap<String, (String, i32)> = BTreeMap::new();
+    let to_references = |published: &BTreeMap<String, (String, i32)>, names: &[String]| {
+        names
+            .iter()
+            .map(|name| {
+                let (subject, version) = published
+                    .get(name)
+                    .ok_or_else(|| anyhow!("reference to unpublished avro type {name}"))?;
+                Ok(mz_ccsr::SchemaReference {
+                    name: name.clone(),
+                    subject: subject.clone(),
+                    version: *version,
+                })
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()
+    };
+
+    for named in split.named_types {
+        let references = to_references(&published, &named.references)?;
+        let named_subject = format!("{subject}-{}", named.fullname);
+        check_and_publish_schema(
+            ccsr,
+            &named_subject,
+            &named.schema,
+            mz_ccsr::SchemaType::Avro,
+            &references,
+        )
+        .await
+        .with_context(|| format!("publishing avro type {}", named.fullname))?;
+        let version = ccsr.get_subject_latest(&named_subject).await?.version;
+        published.insert(named.fullname, (named_subject, version));
+    }
+
+    let references = to_references(&published, &split.references)?;
+    check_and_publish_schema(
+        ccsr,
+        subject,
+        &split.schema,
+        mz_ccsr::SchemaType::Avro,
+        &references,
+    )
+    .await
+}
+
+/// Publishes `schema` under `subject`, after checking that it is compatible
+/// with the latest schema registered under `subject`.
+///
+/// The schema registry also rejects incompatible schemas at publication, but
+/// without explaining why. Checking first allows reporting the specific
+/// incompatibilities. Failures to perform the check itself are not fatal, as
+/// publication will surface any persistent problem with the registry.
+async fn check_and_publish_schema(
+    ccsr: &mz_ccsr::Client,
+    subject: &str,
+    schema: &str,
+    schema_type: mz_ccsr::SchemaType,
+    references: &[mz_ccsr::SchemaReference],
+) -> Result<i32, anyhow::Error> {
+    match ccsr
+        .check_compatibility(subject, schema, schema_type, references)
+        .await
+    {
+        Ok(())
+        | Err(CheckCompatibilityError::SubjectNotFound)
+        | Err(CheckCompatibilityError::VersionNotFound(_)) => (),
+        Err(CheckCompatibilityError::IncompatibleSchema { messages }) => {
+            let mut msg = format!(
+                "schema is incompatible with the latest schema registered under subject {subject:?}"
+            );
+            if !messages.is_empty() {
+                msg.push_str(": ");
+                msg.push_str(&messages.join("; "));
+            }
+            bail!(
+                "{msg} (either make the sink's schema compatible, or change the \
+                compatibility level of the subject in the schema registry)"
+            );
+        }
+        Err(e) => warn!("unable to check compatibility of schema for subject {subject:?}: {e}"),
+    }
+    Ok(ccsr
+        .publish_schema(subject, schema, schema_type, references)
+        .await?)
+}
+
+/// An Avro schema whose nested named types have been split out into
+/// standalone schemas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitAvroSchema {
+    /// The top-level schema, in which every nested named type has been
+    /// replaced by its fullname.
+    pub schema: String,
+    /// The fullnames of the named types that `schema` references.
+    pub references: Vec<String>,
+    /// The nested named types, ordered such that every type appears after the
+    /// types it references.
+    pub named_types: Vec<NamedAvroType>,
+}
+
+/// A named Avro type (record, enum, or fixed) split out of a larger schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedAvroType {
+    /// The fullname of the type.
+    pub fullname: String,
+    /// The standalone schema of the type.
+    pub schema: String,
+    /// The fullnames of the named types that `schema` references.
+    pub references: Vec<String>,
+}
+
+/// Splits every named type nested within the Avro schema `schema` into a
+/// standalone schema, so that each can be registered under its own subject
+/// and referenced by name from the schemas that use it.
+pub fn split_avro_schema(schema: &str) -> Result<SplitAvroSchema, anyhow::Error> {
+    let mut root: serde_json::Value =
+        serde_json::from_str(schema).context("parsing avro schema")?;
+    let mut named_types = vec![];
+    let mut references = BTreeSet::new();
+    split_avro_node(&mut root, "", true, &mut named_types, &mut references)?;
+    Ok(SplitAvroSchema {
+        schema: root.to_string(),
+        references: references.into_iter().collect(),
+        named_types,
+    })
+}
+
+fn split_avro_node(
+    node: &mut serde_json::Value,
+    namespace: &str,
+    is_root: bool,
+    named_types: &mut Vec<NamedAvroType>,
+    references: &mut BTreeSet<String>,
+) -> Result<(), anyhow::Error> {
+    use serde_json::Value;
+
+    const PRIMITIVES: [&str; 8] = [
+        "null", "boolean", "int", "long", "float", "double", "bytes", "string",
+    ];
+
+    match node {
+        Value::String(name) => {
+            if !PRIMITIVES.contains(&name.as_str()) {
+                let fullname = avro_fullname(name, None, namespace);
+                references.insert(fullname.clone());
+                *name = fullname;
+            }
+        }
+        Value::Array(union) => {
+            for variant in union {
+                split_avro_node(variant, namespace, false, named_types, references)?;
+            }
+        }
+        Value::Object(object) => {
+            let ty = match object.get_mut("type") {
+                Some(Value::String(ty)) => ty.clone(),
+                Some(ty) => return split_avro_node(ty, namespace, false, named_types, references),
+                None => bail!("avro schema object is missing a type"),
+            };
+            match ty.as_str() {
+                "record" | "error" | "enum" | "fixed" => {
+                    let name = match object.get("name") {
+                        Some(Value::String(name)) => name.clone(),
+                        _ => bail!("avro {ty} is missing a name"),
+                    };
+                    let explicit_namespace = match object.get("namespace") {
+                        Some(Value::String(namespace)) => Some(namespace.clone()),
+                        _ => None,
+                    };
+                    let fullname = avro_fullname(&name, explicit_namespace.as_deref(), namespace);
+                    let type_namespace = fullname
+                        .rsplit_once('.')
+                        .map(|(namespace, _)| namespace)
+                        .unwrap_or("");
+
+                    let mut type_references = BTreeSet::new();
+                    if let Some(Value::Array(fields)) = object.get_mut("fields") {
+                        for field in fields {
+                            if let Some(field_type) = field.get_mut("type") {
+                                split_avro_node(
+                                    field_type,
+                                    type_namespace,
+                                    false,
+                                    named_types,
+                                    &mut type_references,
+                                )?;
+                            }
+                        }
+                    }
+
+                    // Recursive references are resolved within the type's
+                    // own schema.
+                    type_references.remove(&fullname);
+
+                    if is_root {
+                        references.extend(type_references);
+                    } else {
+                        object.insert("name".into(), Value::String(fullname.clone()));
+                        object.remove("namespace");
+                        named_types.push(NamedAvroType {
+                            fullname: fullname.clone(),
+                            schema: Value::Object(object.clone()).to_string(),
+                            references: type_references.into_iter().collect(),
+                        });
+                        references.insert(fullname.clone());
+                        *node = Value::String(fullname);
+                    }
+                }
+                "array" => {
+                    if let Some(items) = object.get_mut("items") {
+                        split_avro_node(items, namespace, false, named_types, references)?;
+                    }
+                }
+                "map" => {
+                    if let Some(values) = object.get_mut("values") {
+                        split_avro_node(values, namespace, false, named_types, references)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        _ => bail!("invalid avro schema node: {node}"),
+    }
+    Ok(())
+}
+
+/// Resolves the fullname of an Avro named type, per the Avro specification.
+fn avro_fullname(
+    name: &str,
+    explicit_namespace: Option<&str>,
+    enclosing_namespace: &str,
+) -> String {
+    if name.contains('.') {
+        return name.to_string();
+    }
+    match explicit_namespace.unwrap_or(enclosing_namespace) {
+        "" => name.to_string(),
+        namespace => format!("{namespace}.{name}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_split_avro_schema() {
+        let schema = r#"{
+            "type": "record",
+            "name": "envelope",
+            "fields": [
+                {
+                    "name": "before",
+                    "type": [
+                        "null",
+                        {
+                            "type": "record",
+                            "name": "row",
+                            "namespace": "com.materialize.sink",
+                            "fields": [
+                                {
+                                    "name": "a",
+                                    "type": {"type": "fixed", "name": "uint8", "size": 8}
+                                },
+                                {"name": "b", "type": "long"}
+                            ]
+                        }
+                    ]
+                },
+                {"name": "after", "type": ["null", "com.materialize.sink.row"]},
+                {"name": "c", "type": {"type": "array", "items": "com.materialize.sink.uint8"}}
+            ]
+        }"#;
+        let split = split_avro_schema(schema).unwrap();
+
+        assert_eq!(
+            split.references,
+            vec!["com.materialize.sink.row", "com.materialize.sink.uint8"]
+        );
+        let names: Vec<_> = split.named_types.iter().map(|t| &t.fullname).collect();
+        assert_eq!(
+            names,
+            vec!["com.materialize.sink.uint8", "com.materialize.sink.row"]
+        );
+        assert!(split.named_types[0].references.is_empty());
+        assert_eq!(
+            split.named_types[1].references,
+            vec!["com.materialize.sink.uint8"]
+        );
+
+        let root: serde_json::Value = serde_json::from_str(&split.schema).unwrap();
+        assert_eq!(
+            root["fields"][0]["type"],
+            serde_json::json!(["null", "com.materialize.sink.row"])
+        );
+        let row: serde_json::Value = serde_json::from_str(&split.named_types[1].schema).unwrap();
+        assert_eq!(row["name"], "com.materialize.sink.row");
+        assert_eq!(row["fields"][0]["type"], "com.materialize.sink.uint8");
+    }
+}
@@ -32,6 +32,7 @@ use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::critical::SinceHandle;
 use mz_persist_client::read::ReadHandle;
 use mz_persist_client::stats::{SnapshotPartsStats, SnapshotStats};
+use mz_persist_client::usage::StorageUsageClient;
 use mz_persist_client::write::WriteHandle;
 use mz_persist_client::{Diagnostics, PersistClient, PersistLocation, ShardId};
 use mz_persist_types::codec_impls::UnitSchema;
@@ -291,6 +292,20 @@ pub trait StorageCollections: Debug {
         &self,
         updates: &mut BTreeMap<GlobalId, ChangeBatch<Self::Timestamp>>,
     );
+
+    /// Returns the shards that were finalized since the last call, along with
+    /// the blob storage reclaimed by finalizing them.
+    fn drain_shard_finalizations(&self) -> Vec<ShardFinalization>;
+}
+
+/// A shard of a dropped collection that was finalized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShardFinalization {
+    /// The finalized shard.
+    pub shard_id: ShardId,
+    /// The size of the data that the shard referenced when it was finalized,
+    /// which is deleted from blob storage by finalizing it.
+    pub reclaimed_bytes: u64,
 }
 
 /// Frontiers of the collection identified by `id`.
@@ -354,6 +369,11 @@ pub struct StorageCollectionsImpl<
     /// [StorageCollections::prepare_state].
     finalized_shards: Arc<std::sync::Mutex<BTreeSet<ShardId>>>,
 
+    /// Shards that we have finalized, with the storage that was reclaimed,
+    /// until they are reported via
+    /// [StorageCollections::drain_shard_finalizations].
+    shard_finalizations: Arc<std::sync::Mutex<Vec<ShardFinalization>>>,
+
     /// Collections maintained by this [StorageCollections].
     collections: Arc<std::sync::Mutex<BTreeMap<GlobalId, CollectionState<T>>>>,
 
@@ -469,6 +489,7 @@ where
         let collections = Arc::new(std::sync::Mutex::new(BTreeMap::default()));
         let finalizable_shards = Arc::new(std::sync::Mutex::new(BTreeSet::default()));
         let finalized_shards = Arc::new(std::sync::Mutex::new(BTreeSet::default()));
+        let shard_finalizations = Arc::new(std::sync::Mutex::new(Vec::new()));
         let config = Arc::new(Mutex::new(StorageConfiguration::new(
             connection_context,
             mz_dyncfgs::all_dyncfgs(),
@@ -487,6 +508,7 @@ where
             collections: Arc::clone(&collections),
             finalizable_shards: Arc::clone(&finalizable_shards),
             finalized_shards: Arc::clone(&finalized_shards),
+            shard_finalizations: Arc::clone(&shard_finalizations),
             shard_by_id: BTreeMap::new(),
             since_handles: BTreeMap::new(),
             txns_handle: Some(txns_write),
@@ -501,6 +523,7 @@ where
         Self {
             finalizable_shards,
             finalized_shards,
+            shard_finalizations,
             collections,
             txns,
             txns_init_run: Arc::new(AtomicBool::new(false)),
@@ -1850,6 +1873,10 @@ where
             updates,
         );
     }
+
+    fn drain_shard_finalizations(&self) -> Vec<ShardFinalization> {
+        std::mem::take(&mut *self.shard_finalizations.lock().expect("lock poisoned"))
+    }
 }
 
 /// State maintained about individual collections.
@@ -1927,6 +1954,7 @@ struct BackgroundTask<T: TimelyTimestamp + Lattice + Codec64> {
     holds_rx: mpsc::UnboundedReceiver<(GlobalId, ChangeBatch<T>)>,
     finalizable_shards: Arc<std::sync::Mutex<BTreeSet<ShardId>>>,
     finalized_shards: Arc<std::sync::Mutex<BTreeSet<ShardId>>>,
+    shard_finalizations: Arc<std::sync::Mutex<Vec<ShardFinalization>>>,
     collections: Arc<std::sync::Mutex<BTreeMap<GlobalId, CollectionState<T>>>>,
     // So we know what shard ID corresponds to what global ID, which we need
     // when re-enqueing futures for determining the next upper update.
@@ -2340,6 +2368,7 @@ where
             .get(self.config.lock().expect("lock poisoned").config_set());
 
         let epoch = &PersistEpoch::from(self.envd_epoch);
+        let shard_finalizations = &self.shard_finalizations;
 
         use futures::stream::StreamExt;
         let finalized_shards: BTreeSet<ShardId> = futures::stream::iter(finalizable_shards.clone())
@@ -2347,6 +2376,7 @@ where
                 let persist_client = persist_client.clone();
                 let diagnostics = diagnostics.clone();
                 let epoch = epoch.clone();
+                let shard_finalizations = Arc::clone(shard_finalizations);
 
                 let is_finalized = persist_client
                     .is_finalized::<SourceData, (), T, Diff>(shard_id, diagnostics)
@@ -2436,12 +2466,28 @@ where
                                 // since_handle.expire().await;
                             }
 
+                            // Finalizing deletes everything the shard
+                            // references, so measure that beforehand.
+                            let reclaimed_bytes = StorageUsageClient::open(persist_client.clone())
+                                .shard_usage_referenced(shard_id)
+                                .await
+                                .size_bytes();
+
                             persist_client
                                 .finalize_shard::<SourceData, (), T, Diff>(
                                     shard_id,
                                     Diagnostics::from_purpose("finalizing shards"),
                                 )
-                                .await
+                                .await?;
+
+                            shard_finalizations
+                                .lock()
+                                .expect("lock poisoned")
+                                .push(ShardFinalization {
+                                    shard_id,
+                                    reclaimed_bytes,
+                                });
+                            Ok(())
                         };
 
                         match finalize().await {
@@ -2673,6 +2719,7 @@ mod tests {
                 holds_rx,
                 finalizable_shards: Arc::new(Mutex::new(BTreeSet::new())),
                 finalized_shards: Arc::new(Mutex::new(BTreeSet::new())),
+                shard_finalizations: Arc::new(Mutex::new(Vec::new())),
                 collections: Arc::new(Mutex::new(BTreeMap::new())),
                 shard_by_id: BTreeMap::new(),
                 since_handles: BTreeMap::new(),
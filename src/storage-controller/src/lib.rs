@@ -983,12 +983,27 @@ where
                             // truncation above.
                             self.collection_manager.register_append_only_collection(id);
                         }
+                        IntrospectionType::ReplicaEvents => {
+                            self.partially_truncate_status_history(
+                                IntrospectionType::ReplicaEvents,
+                            )
+                            .await;
+
+                            // Only register afterwards, see above.
+                            self.collection_manager.register_append_only_collection(id);
+                        }
+                        IntrospectionType::StorageShardFinalizations => {
+                            // Shards are finalized at most once, so this
+                            // grows only as fast as collections are dropped.
+                            self.collection_manager.register_append_only_collection(id);
+                        }
 
                         // Truncate compute-maintained collections.
                         IntrospectionType::ComputeDependencies
                         | IntrospectionType::ComputeHydrationStatus
                         | IntrospectionType::ComputeOperatorHydrationStatus
-                        | IntrospectionType::ComputeMaterializedViewRefreshes => {
+                        | IntrospectionType::ComputeMaterializedViewRefreshes
+                        | IntrospectionType::ComputeReapedDataflows => {
                             self.collection_manager.register_differential_collection(id, read_handle_fn);
                             // Differential collections start with an empty
                             // desired state. No need to manually reset.
@@ -2104,6 +2119,8 @@ where
             .append_updates(dropped_sinks, IntrospectionType::SinkStatusHistory)
             .await;
 
+        self.record_shard_finalizations().await;
+
         Ok(updated_frontiers)
     }
 
@@ -2292,8 +2309,10 @@ where
     /// - `off` -> `off`: If `B`` manages to append `t_1` before A appends `t_0`
     ///    then the `t_0` append will panic and we won't acknowledge the write
     ///   to the user (or similarly `t_2` and `t_1`). Before txn-wal,
-    ///   appends are not atomic, so we might get a partial append. This is fine
-    ///   because we only support single table transactions.
+    ///   appends are not atomic, so we might get a partial append. This was
+    ///   fine because only single table transactions were supported. Write
+    ///   transactions that touch multiple tables rely on txn-wal, which is
+    ///   now always on, for atomicity.
     /// - `on` -> `on`: The txn-shard is meant to correctly handle two writers
     ///   so this should be fine. Note it's possible that we have two
     ///   Coordinators interleaving write transactions without the leadership
@@ -3140,6 +3159,17 @@ where
                     .expect("schema has not changed")
                     .0,
             ),
+            IntrospectionType::ReplicaEvents => (
+                self.config.parameters.keep_n_cluster_replica_event_entries,
+                collection_status::MZ_CLUSTER_REPLICA_EVENTS_DESC
+                    .get_by_name(&ColumnName::from("occurred_at"))
+                    .expect("schema has not changed")
+                    .0,
+                collection_status::MZ_CLUSTER_REPLICA_EVENTS_DESC
+                    .get_by_name(&ColumnName::from("replica_id"))
+                    .expect("schema has not changed")
+                    .0,
+            ),
             _ => unreachable!(),
         };
 
@@ -3578,6 +3608,42 @@ where
 
         Ok(())
     }
+
+    /// Records the shards that [StorageCollections] finalized since the last
+    /// call in `mz_storage_shard_finalizations`.
+    async fn record_shard_finalizations(&mut self) {
+        // Leave finalizations queued until the collection is registered.
+        if !self
+            .introspection_ids
+            .lock()
+            .expect("poisoned")
+            .contains_key(&IntrospectionType::StorageShardFinalizations)
+        {
+            return;
+        }
+
+        let finalizations = self.storage_collections.drain_shard_finalizations();
+        if finalizations.is_empty() {
+            return;
+        }
+
+        let finalized_at = mz_ore::now::to_datetime((self.now)())
+            .try_into()
+            .expect("must fit");
+        let updates = finalizations
+            .into_iter()
+            .map(|finalization| {
+                let row = Row::pack_slice(&[
+                    Datum::String(&finalization.shard_id.to_string()),
+                    Datum::UInt64(finalization.reclaimed_bytes),
+                    Datum::TimestampTz(finalized_at),
+                ]);
+                (row, 1)
+            })
+            .collect();
+        self.append_introspection_updates(IntrospectionType::StorageShardFinalizations, updates)
+            .await;
+    }
 }
 
 /// State maintained about individual collections.
@@ -47,7 +47,8 @@ mod pgcopy;
 /// This renders 3 operators used to coordinate the upload:
 ///   - initialization: confirms the S3 path is empty and writes any sentinel files
 ///   - upload: uploads data to S3
-///   - completion: removes the sentinel file and calls the `worker_callback`
+///   - completion: optionally writes a manifest, removes the sentinel file and
+///     calls the `worker_callback`
 pub fn copy_to<G, F>(
     input_collection: Collection<G, ((Row, u64), ()), Diff>,
     err_stream: Stream<G, (((DataflowError, u64), ()), G::Timestamp, Diff)>,
@@ -66,6 +67,7 @@ pub fn copy_to<G, F>(
     let scope = input_collection.scope();
 
     let s3_key_manager = S3KeyManager::new(&sink_id, &connection_details.uri);
+    let write_manifest = connection_details.manifest;
 
     let start_stream = render_initialization_operator(
         scope.clone(),
@@ -113,6 +115,7 @@ pub fn copy_to<G, F>(
         sink_id,
         s3_key_manager,
         completion_stream,
+        write_manifest,
         worker_callback,
     );
 }
@@ -287,7 +290,10 @@ where
 /// the callback.
 ///
 /// This cleanup work removes the INCOMPLETE sentinel file (see description
-/// of `render_initialization_operator` for more details).
+/// of `render_initialization_operator` for more details). If `write_manifest`
+/// is set, it first writes a MANIFEST file listing the URIs of all data files
+/// written by the sink, one per line, so that the manifest is guaranteed to be
+/// present once the sentinel is gone.
 fn render_completion_operator<G, F>(
     scope: G,
     connection_context: ConnectionContext,
@@ -296,6 +302,7 @@ fn render_completion_operator<G, F>(
     sink_id: GlobalId,
     s3_key_manager: S3KeyManager,
     completion_stream: Stream<G, Result<u64, String>>,
+    write_manifest: bool,
     worker_callback: F,
 ) where
     G: Scope<Timestamp = Timestamp>,
@@ -341,8 +348,42 @@ fn render_completion_operator<G, F>(
                 // This will race against other replicas who are completing the same uploads,
                 // such that the first replica to complete its uploads will delete the sentinel
                 // and the subsequent replicas shouldn't error if the object is already deleted.
-                // TODO: Should we also write a manifest of all the files uploaded?
                 mz_ore::task::spawn(|| "copytos3:completion", async move {
+                    if write_manifest {
+                        // Data file keys are deterministic, so racing replicas write
+                        // identical manifests.
+                        let manifest_key = s3_key_manager.manifest_key();
+                        let mut data_keys = mz_aws_util::s3::list_bucket_path(
+                            &client,
+                            &bucket,
+                            s3_key_manager.path_prefix(),
+                        )
+                        .await?
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|key| {
+                            s3_key_manager.is_sink_object(key)
+                                && key != &incomplete_sentinel_key
+                                && key != &manifest_key
+                        })
+                        .collect::<Vec<_>>();
+                        data_keys.sort();
+
+                        let mut manifest = String::new();
+                        for key in data_keys {
+                            manifest.push_str(&format!("s3://{}/{}\n", bucket, key));
+                        }
+
+                        debug!(%sink_id, %worker_id, "uploading MANIFEST file");
+                        client
+                            .put_object()
+                            .bucket(&bucket)
+                            .key(manifest_key)
+                            .body(manifest.into_bytes().into())
+                            .send()
+                            .await?;
+                    }
+
                     debug!(%sink_id, %worker_id, "removing INCOMPLETE sentinel file");
                     client
                         .delete_object()
@@ -537,6 +578,11 @@ impl S3KeyManager {
         format!("{}INCOMPLETE", self.object_key_prefix)
     }
 
+    /// The S3 key to use for the manifest file
+    fn manifest_key(&self) -> String {
+        format!("{}MANIFEST", self.object_key_prefix)
+    }
+
     /// Whether the given object key belongs to this sink instance
     fn is_sink_object(&self, object_key: &str) -> bool {
         object_key.starts_with(&self.object_key_prefix)
@@ -223,6 +223,7 @@ mod tests {
                 max_file_size: ByteSize::b(6).as_u64(),
                 desc,
                 format: S3SinkFormat::PgCopy(CopyFormatParams::Csv(Default::default())),
+                manifest: false,
             },
             &sink_id,
             batch,
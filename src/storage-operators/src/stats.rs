@@ -9,9 +9,11 @@
 
 //! Types and traits that connect up our mz-repr types with the stats that persist maintains.
 
+use mz_expr::{MapFilterProject, ResultSpec};
 use mz_persist_client::metrics::Metrics;
 use mz_persist_client::read::{Cursor, LazyPartStats, ReadHandle, Since};
-use mz_repr::{Diff, RelationDesc, Row, Timestamp};
+use mz_persist_types::stats::{PartStats, PartStatsMetrics};
+use mz_repr::{Datum, Diff, RelationDesc, Row, Timestamp};
 use mz_storage_types::controller::TxnsCodecRow;
 use mz_storage_types::errors::DataflowError;
 use mz_storage_types::sources::SourceData;
@@ -25,7 +27,8 @@ use timely::progress::Antichain;
 /// This is necessary so that errors are presented before data, which matches our usual
 /// lookup semantics. To avoid being ludicrously inefficient, this pushes down a filter
 /// on the stats. (In particular, in the common case of no errors, we don't do any extra
-/// fetching.)
+/// fetching.) The predicates of the given `MapFilterProject` are pushed down as well, so
+/// that data parts whose stats prove that no row could pass them are never fetched.
 pub struct StatsCursor {
     errors: Cursor<SourceData, (), Timestamp, Diff>,
     data: Cursor<SourceData, (), Timestamp, Diff>,
@@ -40,6 +43,7 @@ impl StatsCursor {
         txns_read: Option<&mut TxnsCache<Timestamp, TxnsCodecRow>>,
         metrics: &Metrics,
         desc: &RelationDesc,
+        mfp: &MapFilterProject,
         as_of: Antichain<Timestamp>,
     ) -> Result<StatsCursor, Since<Timestamp>> {
         let should_fetch = |name: &'static str, count: fn(&RelationPartStats) -> Option<usize>| {
@@ -51,13 +55,20 @@ impl StatsCursor {
                 count(&relation_stats).map_or(true, |n| n > 0)
             }
         };
+        let mz_now = as_of.as_option().copied();
+        let should_fetch_data = |stats: Option<&LazyPartStats>| {
+            let Some(stats) = stats else { return true };
+            let stats = stats.decode();
+            let metrics = &metrics.pushdown.part_stats;
+            data_part_may_match(metrics, desc, mz_now, mfp, &stats)
+        };
         let (errors, data) = match txns_read {
             None => {
                 let errors = handle
                     .snapshot_cursor(as_of.clone(), should_fetch("errors", |s| s.err_count()))
                     .await?;
                 let data = handle
-                    .snapshot_cursor(as_of.clone(), should_fetch("data", |s| s.ok_count()))
+                    .snapshot_cursor(as_of.clone(), should_fetch_data)
                     .await?;
                 (errors, data)
             }
@@ -72,7 +83,7 @@ impl StatsCursor {
                     .snapshot_cursor(handle, should_fetch("errors", |s| s.err_count()))
                     .await?;
                 let data = data_snapshot
-                    .snapshot_cursor(handle, should_fetch("data", |s| s.ok_count()))
+                    .snapshot_cursor(handle, should_fetch_data)
                     .await?;
                 (errors, data)
             }
@@ -112,3 +123,74 @@ impl StatsCursor {
         }
     }
 }
+
+/// Returns whether a data part with the given stats may contain rows that pass
+/// the predicates of `mfp`, with `mz_now()` evaluated at `mz_now` if it's known.
+fn data_part_may_match(
+    metrics: &PartStatsMetrics,
+    desc: &RelationDesc,
+    mz_now: Option<Timestamp>,
+    mfp: &MapFilterProject,
+    stats: &PartStats,
+) -> bool {
+    let relation_stats = RelationPartStats::new("data", metrics, desc, stats);
+    let time_range = match mz_now {
+        Some(mz_now) => ResultSpec::value(Datum::MzTimestamp(mz_now)),
+        None => ResultSpec::anything(),
+    };
+    relation_stats.ok_count().map_or(true, |n| n > 0)
+        && relation_stats.may_match_mfp(time_range, mfp)
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_expr::{BinaryFunc, MirScalarExpr, UnmaterializableFunc};
+    use mz_ore::metrics::MetricsRegistry;
+    use mz_persist_types::codec_impls::UnitSchema;
+    use mz_persist_types::part::PartBuilder;
+    use mz_repr::ScalarType;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_data_part_may_match() {
+        let desc = RelationDesc::empty()
+            .with_column("a", ScalarType::Int64.nullable(false))
+            .with_column("b", ScalarType::MzTimestamp.nullable(false));
+        let mut builder = PartBuilder::new(&desc, &UnitSchema).expect("success");
+        for (a, b) in [(1, 10u64), (5, 20)] {
+            let row = Row::pack_slice(&[Datum::Int64(a), Datum::MzTimestamp(Timestamp::from(b))]);
+            builder.push(&SourceData(Ok(row)), &(), 1u64, 1i64);
+        }
+        let part = builder.finish();
+        let stats = PartStats {
+            key: part.key_stats().expect("valid stats"),
+        };
+        let metrics = PartStatsMetrics::new(&MetricsRegistry::new());
+
+        let may_match = |predicate: MirScalarExpr, mz_now: Option<u64>| {
+            let mfp = MapFilterProject::new(desc.arity()).filter([predicate]);
+            let mz_now = mz_now.map(Timestamp::from);
+            data_part_may_match(&metrics, &desc, mz_now, &mfp, &stats)
+        };
+        let a_gt = |n| {
+            MirScalarExpr::column(0).call_binary(
+                MirScalarExpr::literal_ok(Datum::Int64(n), ScalarType::Int64),
+                BinaryFunc::Gt,
+            )
+        };
+        let mz_now_lte_b = || {
+            MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::MzNow)
+                .call_binary(MirScalarExpr::column(1), BinaryFunc::Lte)
+        };
+
+        // Parts whose values can't pass the predicates are skipped.
+        assert!(may_match(a_gt(3), None));
+        assert!(!may_match(a_gt(100), None));
+
+        // Temporal filters are evaluated at `mz_now`, if it's known.
+        assert!(may_match(mz_now_lte_b(), Some(15)));
+        assert!(!may_match(mz_now_lte_b(), Some(30)));
+        assert!(may_match(mz_now_lte_b(), None));
+    }
+}
@@ -12,6 +12,7 @@
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::{anyhow, Context};
@@ -51,6 +52,7 @@ use url::Url;
 
 use crate::configuration::StorageConfiguration;
 use crate::connections::aws::{AwsConnection, AwsConnectionValidationError};
+use crate::connections::validation::ValidationCheck;
 use crate::controller::AlterError;
 use crate::dyncfgs::{ENFORCE_EXTERNAL_ADDRESSES, KAFKA_CLIENT_ID_ENRICHMENT_RULES};
 use crate::errors::{ContextCreationError, CsrConnectError};
@@ -58,6 +60,7 @@ use crate::AlterCompatible;
 
 pub mod aws;
 pub mod inline;
+pub mod validation;
 
 include!(concat!(env!("OUT_DIR"), "/mz_storage_types.connections.rs"));
 
@@ -195,6 +198,9 @@ pub struct ConnectionContext {
     pub cloud_resource_reader: Option<Arc<dyn CloudResourceReader>>,
     /// A manager for SSH tunnels.
     pub ssh_tunnel_manager: SshTunnelManager,
+    /// A directory in which schema registry clients cache the schemas they
+    /// fetch by ID, if any.
+    pub ccsr_schema_cache_directory: Option<PathBuf>,
 }
 
 impl ConnectionContext {
@@ -212,6 +218,7 @@ impl ConnectionContext {
         aws_connection_role_arn: Option<String>,
         secrets_reader: Arc<dyn SecretsReader>,
         cloud_resource_reader: Option<Arc<dyn CloudResourceReader>>,
+        ccsr_schema_cache_directory: Option<PathBuf>,
     ) -> ConnectionContext {
         ConnectionContext {
             environment_id,
@@ -224,6 +231,7 @@ impl ConnectionContext {
             secrets_reader,
             cloud_resource_reader,
             ssh_tunnel_manager: SshTunnelManager::default(),
+            ccsr_schema_cache_directory,
         }
     }
 
@@ -237,6 +245,7 @@ impl ConnectionContext {
             secrets_reader,
             cloud_resource_reader: None,
             ssh_tunnel_manager: SshTunnelManager::default(),
+            ccsr_schema_cache_directory: None,
         }
     }
 }
@@ -345,6 +354,13 @@ pub enum ConnectionValidationError {
     Aws(#[from] AwsConnectionValidationError),
     #[error("{}", .0.display_with_causes())]
     Other(#[from] anyhow::Error),
+    /// A check performed by [`Connection::validate_checks`] failed.
+    #[error("{source}")]
+    Check {
+        check: ValidationCheck,
+        target: Option<String>,
+        source: Box<ConnectionValidationError>,
+    },
 }
 
 impl ConnectionValidationError {
@@ -353,6 +369,14 @@ impl ConnectionValidationError {
         match self {
             ConnectionValidationError::Aws(e) => e.detail(),
             ConnectionValidationError::Other(_) => None,
+            ConnectionValidationError::Check {
+                check,
+                target,
+                source,
+            } => source.detail().or_else(|| match target {
+                Some(target) => Some(format!("the {check} check for {target} failed")),
+                None => Some(format!("the {check} check failed")),
+            }),
         }
     }
 
@@ -361,6 +385,7 @@ impl ConnectionValidationError {
         match self {
             ConnectionValidationError::Aws(e) => e.hint(),
             ConnectionValidationError::Other(_) => None,
+            ConnectionValidationError::Check { source, .. } => source.hint(),
         }
     }
 }
@@ -1001,6 +1026,15 @@ impl CsrConnection {
         in_task: InTask,
     ) -> Result<mz_ccsr::Client, CsrConnectError> {
         let mut client_config = mz_ccsr::ClientConfig::new(self.url.clone());
+        if let Some(dir) = &storage_configuration
+            .connection_context
+            .ccsr_schema_cache_directory
+        {
+            // Schema IDs are only unique within a registry, so each registry
+            // gets its own cache directory, named after its URL.
+            let registry_dir = base64::encode_config(self.url.as_str(), base64::URL_SAFE_NO_PAD);
+            client_config = client_config.schema_cache_directory(dir.join(registry_dir));
+        }
         if let Some(root_cert) = &self.tls_root_cert {
             let root_cert = root_cert
                 .get_string(
@@ -1453,6 +1487,7 @@ impl PostgresConnection<InlinedConnection> {
                 assert!(connection.port.is_none());
                 mz_postgres_util::TunnelConfig::AwsPrivatelink {
                     connection_id: connection.connection_id,
+                    availability_zone: connection.availability_zone.clone(),
                 }
             }
         };
@@ -1832,6 +1867,7 @@ impl MySqlConnection<InlinedConnection> {
                 assert!(connection.port.is_none());
                 mz_mysql_util::TunnelConfig::AwsPrivatelink {
                     connection_id: connection.connection_id,
+                    availability_zone: connection.availability_zone.clone(),
                 }
             }
         };
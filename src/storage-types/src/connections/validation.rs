@@ -0,0 +1,493 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Step-by-step validation of connections, as reported by `VALIDATE CONNECTION`.
+//!
+//! [`Connection::validate`] only reports whether a connection works. The checks
+//! here additionally probe each network endpoint a connection reaches directly
+//! (DNS, TCP, and TLS), so that a failure can be attributed to the layer it
+//! happened in.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::net::SocketAddr;
+
+use anyhow::{anyhow, bail, Context};
+use mz_ore::future::InTask;
+use mz_ore::netio::resolve_address;
+use mz_repr::GlobalId;
+use openssl::pkey::PKey;
+use openssl::ssl::{SslConnector, SslMethod};
+use openssl::x509::X509;
+use tokio::net::TcpStream;
+use tokio_postgres::config::SslMode;
+
+use crate::configuration::StorageConfiguration;
+use crate::connections::{
+    Connection, ConnectionValidationError, KafkaConnection, MySqlConnection, MySqlSslMode,
+    PostgresConnection, SecretsReaderExt, StringOrSecret, TlsIdentity, Tunnel,
+};
+use crate::dyncfgs::{CONNECTION_VALIDATION_PROBE_TIMEOUT, ENFORCE_EXTERNAL_ADDRESSES};
+
+/// A check performed by [`Connection::validate_checks`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValidationCheck {
+    /// Resolving the host of an endpoint.
+    Dns,
+    /// Opening a TCP connection to an endpoint.
+    Tcp,
+    /// Completing a TLS handshake with an endpoint.
+    Tls,
+    /// Authenticating with the upstream system.
+    Auth,
+    /// Verifying that the authenticated user holds the privileges Materialize
+    /// requires.
+    Authz,
+}
+
+impl ValidationCheck {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValidationCheck::Dns => "dns",
+            ValidationCheck::Tcp => "tcp",
+            ValidationCheck::Tls => "tls",
+            ValidationCheck::Auth => "auth",
+            ValidationCheck::Authz => "authz",
+        }
+    }
+}
+
+impl fmt::Display for ValidationCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The outcome of a [`ValidationCheck`] that did not fail.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationStatus {
+    /// The check succeeded.
+    Ok,
+    /// The check does not apply, for the given reason.
+    Skipped(String),
+}
+
+/// The result of a [`ValidationCheck`] that did not fail.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationCheckResult {
+    pub check: ValidationCheck,
+    /// The `host:port` the check was performed against, if the check concerns
+    /// a single endpoint.
+    pub target: Option<String>,
+    pub status: ValidationStatus,
+}
+
+/// A network endpoint that a connection reaches.
+struct Endpoint {
+    /// The `host:port` of the endpoint, as configured by the user.
+    target: String,
+    /// How Materialize reaches the endpoint.
+    route: Route,
+    /// How TLS is established with the endpoint, if at all.
+    tls: Option<TlsProbe>,
+}
+
+/// How Materialize reaches an [`Endpoint`].
+enum Route {
+    /// Directly.
+    Direct { host: String, port: u16 },
+    /// Through the SSH bastion at the given address.
+    Ssh { host: String, port: u16 },
+    /// Through an AWS PrivateLink endpoint.
+    AwsPrivatelink,
+}
+
+/// How TLS is established with an [`Endpoint`].
+enum TlsProbe {
+    /// TLS is established right after connecting, and is verified using the
+    /// given root certificate and client identity.
+    Handshake {
+        root_cert: Option<StringOrSecret>,
+        identity: Option<TlsIdentity>,
+    },
+    /// TLS is negotiated by the upstream system's protocol, and is verified as
+    /// part of authenticating.
+    InProtocol,
+}
+
+impl Endpoint {
+    fn new(host: &str, port: u16, tunnel: &Tunnel, tls: Option<TlsProbe>) -> Self {
+        let route = match tunnel {
+            Tunnel::Direct => Route::Direct {
+                host: host.to_string(),
+                port,
+            },
+            Tunnel::Ssh(ssh) => Route::Ssh {
+                host: ssh.connection.host.clone(),
+                port: ssh.connection.port,
+            },
+            Tunnel::AwsPrivatelink(_) => Route::AwsPrivatelink,
+        };
+        Endpoint {
+            target: format!("{host}:{port}"),
+            route,
+            tls,
+        }
+    }
+}
+
+impl Connection {
+    /// Validates this connection by attempting to connect to the upstream
+    /// system, reporting every check that was performed.
+    ///
+    /// Checks run in order, and the first failed check is reported as an error.
+    /// Unlike [`Connection::validate`], this also verifies that the
+    /// authenticated user holds the privileges required by sources, where the
+    /// upstream system allows checking them.
+    pub async fn validate_checks(
+        &self,
+        id: GlobalId,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<Vec<ValidationCheckResult>, ConnectionValidationError> {
+        let mut results = vec![];
+
+        let mut seen = BTreeSet::new();
+        for endpoint in self.endpoints() {
+            let Endpoint { target, route, tls } = endpoint;
+            if !seen.insert(target.clone()) {
+                continue;
+            }
+            probe_endpoint(&target, route, tls, storage_configuration, &mut results).await?;
+        }
+
+        self.validate(id, storage_configuration)
+            .await
+            .map_err(|e| check_failed(ValidationCheck::Auth, None, e))?;
+        push_ok(&mut results, ValidationCheck::Auth, None);
+
+        match self {
+            Connection::Postgres(conn) => {
+                validate_postgres_privileges(conn, storage_configuration)
+                    .await
+                    .map_err(|e| check_failed(ValidationCheck::Authz, None, e.into()))?;
+                push_ok(&mut results, ValidationCheck::Authz, None);
+            }
+            Connection::MySql(conn) => {
+                validate_mysql_privileges(conn, storage_configuration)
+                    .await
+                    .map_err(|e| check_failed(ValidationCheck::Authz, None, e.into()))?;
+                push_ok(&mut results, ValidationCheck::Authz, None);
+            }
+            Connection::Kafka(_)
+            | Connection::Csr(_)
+            | Connection::Ssh(_)
+            | Connection::Aws(_)
+            | Connection::AwsPrivatelink(_) => {}
+        }
+
+        Ok(results)
+    }
+
+    /// Returns the network endpoints this connection reaches.
+    fn endpoints(&self) -> Vec<Endpoint> {
+        match self {
+            Connection::Kafka(conn) => kafka_endpoints(conn),
+            Connection::Csr(conn) => {
+                let host = conn.url.host_str().unwrap_or_default();
+                let port = conn.url.port_or_known_default().unwrap_or(80);
+                let tls = (conn.url.scheme() == "https").then(|| TlsProbe::Handshake {
+                    root_cert: conn.tls_root_cert.clone(),
+                    identity: conn.tls_identity.clone(),
+                });
+                vec![Endpoint::new(host, port, &conn.tunnel, tls)]
+            }
+            Connection::Postgres(conn) => {
+                let tls = (conn.tls_mode != SslMode::Disable).then_some(TlsProbe::InProtocol);
+                vec![Endpoint::new(&conn.host, conn.port, &conn.tunnel, tls)]
+            }
+            Connection::MySql(conn) => {
+                let tls = (conn.tls_mode != MySqlSslMode::Disabled).then_some(TlsProbe::InProtocol);
+                vec![Endpoint::new(&conn.host, conn.port, &conn.tunnel, tls)]
+            }
+            Connection::Ssh(conn) => {
+                vec![Endpoint::new(&conn.host, conn.port, &Tunnel::Direct, None)]
+            }
+            // AWS connections only talk to AWS APIs, and PrivateLink connections
+            // are validated by inspecting the state of their VPC endpoint.
+            Connection::Aws(_) | Connection::AwsPrivatelink(_) => vec![],
+        }
+    }
+}
+
+fn kafka_endpoints(conn: &KafkaConnection) -> Vec<Endpoint> {
+    let tls = || {
+        conn.tls.as_ref().map(|tls| TlsProbe::Handshake {
+            root_cert: tls.root_cert.clone(),
+            identity: tls.identity.clone(),
+        })
+    };
+    conn.brokers
+        .iter()
+        .map(|broker| {
+            let (host, port) = match broker.address.split_once(':') {
+                Some((host, port)) => (host, port.parse().unwrap_or(9092)),
+                None => (broker.address.as_str(), 9092),
+            };
+            let tunnel = match &broker.tunnel {
+                Tunnel::Direct => &conn.default_tunnel,
+                tunnel => tunnel,
+            };
+            Endpoint::new(host, port, tunnel, tls())
+        })
+        .collect()
+}
+
+/// Runs the network checks against a single endpoint.
+async fn probe_endpoint(
+    target: &str,
+    route: Route,
+    tls: Option<TlsProbe>,
+    storage_configuration: &StorageConfiguration,
+    results: &mut Vec<ValidationCheckResult>,
+) -> Result<(), ConnectionValidationError> {
+    let target = Some(target.to_string());
+    let (host, port) = match route {
+        Route::Direct { host, port } => (host, port),
+        Route::Ssh { host, port } => {
+            // The endpoint itself is only reachable through the bastion, so
+            // check that the bastion is reachable instead.
+            let bastion = Some(format!("{host}:{port}"));
+            probe_tcp(&host, port, &bastion, storage_configuration, results).await?;
+            if tls.is_some() {
+                let reason = "negotiated through the SSH tunnel, checked by auth";
+                push_skipped(results, ValidationCheck::Tls, target, reason);
+            }
+            return Ok(());
+        }
+        Route::AwsPrivatelink => {
+            let reason = "reached through AWS PrivateLink";
+            push_skipped(results, ValidationCheck::Dns, target.clone(), reason);
+            push_skipped(results, ValidationCheck::Tcp, target.clone(), reason);
+            if tls.is_some() {
+                push_skipped(results, ValidationCheck::Tls, target, reason);
+            }
+            return Ok(());
+        }
+    };
+
+    let addrs = probe_tcp(&host, port, &target, storage_configuration, results).await?;
+    match tls {
+        None => {}
+        Some(TlsProbe::InProtocol) => {
+            let reason = "negotiated by the upstream protocol, checked by auth";
+            push_skipped(results, ValidationCheck::Tls, target, reason);
+        }
+        Some(TlsProbe::Handshake {
+            root_cert,
+            identity,
+        }) => {
+            tls_handshake(&host, &addrs, root_cert, identity, storage_configuration)
+                .await
+                .map_err(|e| check_failed(ValidationCheck::Tls, target.clone(), e.into()))?;
+            push_ok(results, ValidationCheck::Tls, target);
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that `host` resolves and that `port` accepts TCP connections on one
+/// of its addresses, which are returned.
+async fn probe_tcp(
+    host: &str,
+    port: u16,
+    target: &Option<String>,
+    storage_configuration: &StorageConfiguration,
+    results: &mut Vec<ValidationCheckResult>,
+) -> Result<Vec<SocketAddr>, ConnectionValidationError> {
+    let config_set = storage_configuration.config_set();
+    let timeout = CONNECTION_VALIDATION_PROBE_TIMEOUT.get(config_set);
+
+    let ips = resolve_address(host, ENFORCE_EXTERNAL_ADDRESSES.get(config_set))
+        .await
+        .map_err(|e| check_failed(ValidationCheck::Dns, target.clone(), anyhow!(e).into()))?;
+    push_ok(results, ValidationCheck::Dns, target.clone());
+
+    let addrs: Vec<_> = ips
+        .into_iter()
+        .map(|ip| SocketAddr::new(ip, port))
+        .collect();
+    match tokio::time::timeout(timeout, TcpStream::connect(&addrs[..])).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => {
+            return Err(check_failed(
+                ValidationCheck::Tcp,
+                target.clone(),
+                anyhow!(e).into(),
+            ));
+        }
+        Err(_) => {
+            let e = anyhow!("timed out after {timeout:?}");
+            return Err(check_failed(ValidationCheck::Tcp, target.clone(), e.into()));
+        }
+    }
+    push_ok(results, ValidationCheck::Tcp, target.clone());
+
+    Ok(addrs)
+}
+
+/// Completes a TLS handshake with `host`, verifying its certificate.
+async fn tls_handshake(
+    host: &str,
+    addrs: &[SocketAddr],
+    root_cert: Option<StringOrSecret>,
+    identity: Option<TlsIdentity>,
+    storage_configuration: &StorageConfiguration,
+) -> Result<(), anyhow::Error> {
+    let secrets_reader = &storage_configuration.connection_context.secrets_reader;
+    let mut builder = SslConnector::builder(SslMethod::tls_client())?;
+    if let Some(root_cert) = root_cert {
+        let root_cert = root_cert.get_string(InTask::No, secrets_reader).await?;
+        for cert in X509::stack_from_pem(root_cert.as_bytes())? {
+            builder.cert_store_mut().add_cert(cert)?;
+        }
+    }
+    if let Some(identity) = identity {
+        let cert = identity.cert.get_string(InTask::No, secrets_reader).await?;
+        let key = secrets_reader
+            .read_string_in_task_if(InTask::No, identity.key)
+            .await?;
+        builder.set_certificate(&*X509::from_pem(cert.as_bytes())?)?;
+        builder.set_private_key(&*PKey::private_key_from_pem(key.as_bytes())?)?;
+    }
+    let connector = builder.build();
+
+    let host = host.to_string();
+    let addrs = addrs.to_vec();
+    let timeout = CONNECTION_VALIDATION_PROBE_TIMEOUT.get(storage_configuration.config_set());
+    // `openssl` only offers a blocking handshake.
+    mz_ore::task::spawn_blocking(
+        || "connection_validation_tls_handshake",
+        move || {
+            let Some(addr) = addrs.first() else {
+                bail!("no addresses to connect to");
+            };
+            let stream = std::net::TcpStream::connect_timeout(addr, timeout)?;
+            stream.set_read_timeout(Some(timeout))?;
+            stream.set_write_timeout(Some(timeout))?;
+            connector
+                .connect(&host, stream)
+                .map_err(|e| anyhow!("{e}"))
+                .context("TLS handshake failed")?;
+            Ok(())
+        },
+    )
+    .await?
+}
+
+/// Checks that the user of a PostgreSQL connection may create replication
+/// slots, which PostgreSQL sources require.
+async fn validate_postgres_privileges(
+    conn: &PostgresConnection,
+    storage_configuration: &StorageConfiguration,
+) -> Result<(), anyhow::Error> {
+    let client = conn
+        .config(
+            &storage_configuration.connection_context.secrets_reader,
+            storage_configuration,
+            InTask::No,
+        )
+        .await?
+        .connect(
+            "connection validation",
+            &storage_configuration.connection_context.ssh_tunnel_manager,
+        )
+        .await?;
+    // Amazon RDS grants replication privileges through the `rds_replication`
+    // role rather than the `REPLICATION` attribute.
+    let row = mz_postgres_util::simple_query_opt(
+        &client,
+        "SELECT r.rolname, r.rolsuper OR r.rolreplication OR EXISTS (
+            SELECT 1 FROM pg_roles m
+            WHERE m.rolname = 'rds_replication' AND pg_has_role(r.oid, m.oid, 'MEMBER')
+        )
+        FROM pg_roles r WHERE r.rolname = current_user",
+    )
+    .await?
+    .context("current user not found in pg_roles")?;
+    if row.get(1) != Some("t") {
+        bail!(
+            "user {} lacks the REPLICATION attribute, which PostgreSQL sources require",
+            row.get(0).unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// Checks that the user of a MySQL connection holds the global privileges
+/// MySQL sources require.
+async fn validate_mysql_privileges(
+    conn: &MySqlConnection,
+    storage_configuration: &StorageConfiguration,
+) -> Result<(), anyhow::Error> {
+    let mut conn = conn
+        .config(
+            &storage_configuration.connection_context.secrets_reader,
+            storage_configuration,
+            InTask::No,
+        )
+        .await?
+        .connect(
+            "connection validation",
+            &storage_configuration.connection_context.ssh_tunnel_manager,
+        )
+        .await?;
+    // Privileges on tables are checked when creating a source, as only then
+    // are the tables known.
+    mz_mysql_util::validate_source_privileges(&mut conn, &[]).await?;
+    conn.disconnect().await?;
+    Ok(())
+}
+
+fn check_failed(
+    check: ValidationCheck,
+    target: Option<String>,
+    source: ConnectionValidationError,
+) -> ConnectionValidationError {
+    ConnectionValidationError::Check {
+        check,
+        target,
+        source: Box::new(source),
+    }
+}
+
+fn push_ok(
+    results: &mut Vec<ValidationCheckResult>,
+    check: ValidationCheck,
+    target: Option<String>,
+) {
+    results.push(ValidationCheckResult {
+        check,
+        target,
+        status: ValidationStatus::Ok,
+    });
+}
+
+fn push_skipped(
+    results: &mut Vec<ValidationCheckResult>,
+    check: ValidationCheck,
+    target: Option<String>,
+    reason: &str,
+) {
+    results.push(ValidationCheckResult {
+        check,
+        target,
+        status: ValidationStatus::Skipped(reason.to_string()),
+    });
+}
@@ -128,6 +128,13 @@ pub const ENFORCE_EXTERNAL_ADDRESSES: Config<bool> = Config::new(
           (not private or local) when resolving them",
 );
 
+/// The timeout for each network check performed by `VALIDATE CONNECTION`.
+pub const CONNECTION_VALIDATION_PROBE_TIMEOUT: Config<Duration> = Config::new(
+    "storage_connection_validation_probe_timeout",
+    Duration::from_secs(10),
+    "The timeout for each network check performed by `VALIDATE CONNECTION`.",
+);
+
 // Upsert
 
 /// Whether or not to prevent buffering the entire _upstream_ snapshot in
@@ -190,6 +197,7 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&PG_FETCH_SLOT_RESUME_LSN_INTERVAL)
         .add(&PG_OFFSET_KNOWN_INTERVAL)
         .add(&ENFORCE_EXTERNAL_ADDRESSES)
+        .add(&CONNECTION_VALIDATION_PROBE_TIMEOUT)
         .add(&STORAGE_UPSERT_PREVENT_SNAPSHOT_BUFFERING)
         .add(&STORAGE_ROCKSDB_USE_MERGE_OPERATOR)
         .add(&STORAGE_UPSERT_MAX_SNAPSHOT_BATCH_BUFFERING)
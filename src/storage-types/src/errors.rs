@@ -543,6 +543,7 @@ mod columnation {
                         | e @ EvalError::NegLimit
                         | e @ EvalError::NullCharacterNotPermitted
                         | e @ EvalError::KeyCannotBeNull
+                        | e @ EvalError::AppendOnlyViolated
                         | e @ EvalError::UnterminatedLikeEscapeSequence
                         | e @ EvalError::MultipleRowsFromSubquery
                         | e @ EvalError::LikePatternTooLong
@@ -65,6 +65,7 @@ pub struct StorageParameters {
     pub keep_n_source_status_history_entries: usize,
     pub keep_n_sink_status_history_entries: usize,
     pub keep_n_privatelink_status_history_entries: usize,
+    pub keep_n_cluster_replica_event_entries: usize,
     /// A set of parameters used to tune RocksDB when used with `UPSERT` sources.
     pub upsert_rocksdb_tuning_config: mz_rocksdb_types::RocksDBTuningParameters,
     /// Whether or not to allow shard finalization to occur. Note that this will
@@ -130,6 +131,7 @@ impl Default for StorageParameters {
             keep_n_source_status_history_entries: Default::default(),
             keep_n_sink_status_history_entries: Default::default(),
             keep_n_privatelink_status_history_entries: Default::default(),
+            keep_n_cluster_replica_event_entries: Default::default(),
             upsert_rocksdb_tuning_config: Default::default(),
             finalize_shards: Default::default(),
             tracing: Default::default(),
@@ -234,6 +236,7 @@ impl StorageParameters {
             keep_n_source_status_history_entries,
             keep_n_sink_status_history_entries,
             keep_n_privatelink_status_history_entries,
+            keep_n_cluster_replica_event_entries,
             upsert_rocksdb_tuning_config,
             finalize_shards,
             tracing,
@@ -263,6 +266,7 @@ impl StorageParameters {
         self.keep_n_source_status_history_entries = keep_n_source_status_history_entries;
         self.keep_n_sink_status_history_entries = keep_n_sink_status_history_entries;
         self.keep_n_privatelink_status_history_entries = keep_n_privatelink_status_history_entries;
+        self.keep_n_cluster_replica_event_entries = keep_n_cluster_replica_event_entries;
         self.upsert_rocksdb_tuning_config = upsert_rocksdb_tuning_config;
         self.finalize_shards = finalize_shards;
         self.tracing.update(tracing);
@@ -308,6 +312,9 @@ impl RustType<ProtoStorageParameters> for StorageParameters {
             keep_n_privatelink_status_history_entries: u64::cast_from(
                 self.keep_n_privatelink_status_history_entries,
             ),
+            keep_n_cluster_replica_event_entries: u64::cast_from(
+                self.keep_n_cluster_replica_event_entries,
+            ),
             upsert_rocksdb_tuning_config: Some(self.upsert_rocksdb_tuning_config.into_proto()),
             finalize_shards: self.finalize_shards,
             tracing: Some(self.tracing.into_proto()),
@@ -361,6 +368,9 @@ impl RustType<ProtoStorageParameters> for StorageParameters {
             keep_n_privatelink_status_history_entries: usize::cast_from(
                 proto.keep_n_privatelink_status_history_entries,
             ),
+            keep_n_cluster_replica_event_entries: usize::cast_from(
+                proto.keep_n_cluster_replica_event_entries,
+            ),
             upsert_rocksdb_tuning_config: proto
                 .upsert_rocksdb_tuning_config
                 .into_rust_if_some("ProtoStorageParameters::upsert_rocksdb_tuning_config")?,
@@ -532,6 +542,8 @@ impl RustType<ProtoSshTimeoutConfig> for SshTimeoutConfig {
             check_interval: Some(self.check_interval.into_proto()),
             connect_timeout: Some(self.connect_timeout.into_proto()),
             keepalives_idle: Some(self.keepalives_idle.into_proto()),
+            reconnect_backoff: Some(self.reconnect_backoff.into_proto()),
+            reconnect_grace_period: Some(self.reconnect_grace_period.into_proto()),
         }
     }
 
@@ -546,6 +558,12 @@ impl RustType<ProtoSshTimeoutConfig> for SshTimeoutConfig {
             keepalives_idle: proto
                 .keepalives_idle
                 .into_rust_if_some("ProtoSshTimeoutConfig::keepalives_idle")?,
+            reconnect_backoff: proto
+                .reconnect_backoff
+                .into_rust_if_some("ProtoSshTimeoutConfig::reconnect_backoff")?,
+            reconnect_grace_period: proto
+                .reconnect_grace_period
+                .into_rust_if_some("ProtoSshTimeoutConfig::reconnect_grace_period")?,
         })
     }
 }
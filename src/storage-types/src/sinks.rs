@@ -14,6 +14,7 @@ use std::collections::BTreeMap;
 use std::fmt::Debug;
 
 use mz_dyncfg::ConfigSet;
+use mz_expr::MirScalarExpr;
 use mz_ore::num::NonNeg;
 use mz_persist_types::ShardId;
 use mz_pgcopy::CopyFormatParams;
@@ -384,6 +385,29 @@ impl KafkaSinkCompressionType {
     }
 }
 
+/// The guarantee a Kafka sink makes about how often each update is written.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaSinkDeliveryGuarantee {
+    /// Updates and progress records are committed together in Kafka
+    /// transactions, so each update is written exactly once.
+    ExactlyOnce,
+    /// Updates are written without transactions and progress is recorded only
+    /// after they are acknowledged. This lowers end-to-end latency, but
+    /// updates may be written more than once if the sink restarts.
+    AtLeastOnce,
+}
+
+impl KafkaSinkDeliveryGuarantee {
+    /// Returns the name of the delivery guarantee, as accepted by the
+    /// `DELIVERY GUARANTEE` option.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KafkaSinkDeliveryGuarantee::ExactlyOnce => "exactly-once",
+            KafkaSinkDeliveryGuarantee::AtLeastOnce => "at-least-once",
+        }
+    }
+}
+
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
 pub struct KafkaSinkTopicOptions {
     /// The replication factor for the topic.
@@ -432,8 +456,18 @@ pub struct KafkaSinkConnection<C: ConnectionAccess = InlinedConnection> {
     /// Options to use when creating the progress topic if it doesn't already exist.
     pub progress_topic_options: KafkaSinkTopicOptions,
     pub compression_type: KafkaSinkCompressionType,
+    pub delivery_guarantee: KafkaSinkDeliveryGuarantee,
     pub progress_group_id: KafkaIdStyle,
     pub transactional_id: KafkaIdStyle,
+    /// The topic to which Debezium-compatible transaction metadata is written, if any.
+    ///
+    /// When set, each timestamp is framed by `BEGIN` and `END` markers in this topic and the
+    /// Debezium envelope of each data record carries the ID of its transaction.
+    pub transaction_metadata_topic: Option<String>,
+    /// An expression over the key columns that selects the partition of each message, if any.
+    ///
+    /// The expression produces a `uint8` that is reduced modulo the topic's partition count.
+    pub partition_by: Option<MirScalarExpr>,
 }
 
 impl KafkaSinkConnection {
@@ -522,10 +556,13 @@ impl<C: ConnectionAccess> KafkaSinkConnection<C> {
             value_desc,
             topic,
             compression_type,
+            delivery_guarantee,
             progress_group_id,
             transactional_id,
             topic_options,
             progress_topic_options,
+            transaction_metadata_topic,
+            partition_by,
         } = self;
 
         let compatibility_checks = [
@@ -550,6 +587,10 @@ impl<C: ConnectionAccess> KafkaSinkConnection<C> {
                 compression_type == &other.compression_type,
                 "compression_type",
             ),
+            (
+                delivery_guarantee == &other.delivery_guarantee,
+                "delivery_guarantee",
+            ),
             (
                 progress_group_id == &other.progress_group_id,
                 "progress_group_id",
@@ -563,6 +604,11 @@ impl<C: ConnectionAccess> KafkaSinkConnection<C> {
                 progress_topic_options == &other.progress_topic_options,
                 "progress_topic_options",
             ),
+            (
+                transaction_metadata_topic == &other.transaction_metadata_topic,
+                "transaction_metadata_topic",
+            ),
+            (partition_by == &other.partition_by, "partition_by"),
         ];
         for (compatible, field) in compatibility_checks {
             if !compatible {
@@ -594,10 +640,13 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkConnection, R>
             value_desc,
             topic,
             compression_type,
+            delivery_guarantee,
             progress_group_id,
             transactional_id,
             topic_options,
             progress_topic_options,
+            transaction_metadata_topic,
+            partition_by,
         } = self;
         KafkaSinkConnection {
             connection_id,
@@ -609,10 +658,13 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkConnection, R>
             value_desc,
             topic,
             compression_type,
+            delivery_guarantee,
             progress_group_id,
             transactional_id,
             topic_options,
             progress_topic_options,
+            transaction_metadata_topic,
+            partition_by,
         }
     }
 }
@@ -655,7 +707,7 @@ impl RustType<ProtoKafkaIdStyle> for KafkaIdStyle {
 
 impl RustType<ProtoKafkaSinkConnectionV2> for KafkaSinkConnection {
     fn into_proto(&self) -> ProtoKafkaSinkConnectionV2 {
-        use crate::sinks::proto_kafka_sink_connection_v2::CompressionType;
+        use crate::sinks::proto_kafka_sink_connection_v2::{CompressionType, DeliveryGuarantee};
         ProtoKafkaSinkConnectionV2 {
             connection_id: Some(self.connection_id.into_proto()),
             connection: Some(self.connection.into_proto()),
@@ -676,11 +728,17 @@ impl RustType<ProtoKafkaSinkConnectionV2> for KafkaSinkConnection {
             transactional_id: Some(self.transactional_id.into_proto()),
             topic_options: Some(self.topic_options.into_proto()),
             progress_topic_options: Some(self.progress_topic_options.into_proto()),
+            transaction_metadata_topic: self.transaction_metadata_topic.clone(),
+            partition_by: self.partition_by.into_proto(),
+            delivery_guarantee: Some(match self.delivery_guarantee {
+                KafkaSinkDeliveryGuarantee::ExactlyOnce => DeliveryGuarantee::ExactlyOnce(()),
+                KafkaSinkDeliveryGuarantee::AtLeastOnce => DeliveryGuarantee::AtLeastOnce(()),
+            }),
         }
     }
 
     fn from_proto(proto: ProtoKafkaSinkConnectionV2) -> Result<Self, TryFromProtoError> {
-        use crate::sinks::proto_kafka_sink_connection_v2::CompressionType;
+        use crate::sinks::proto_kafka_sink_connection_v2::{CompressionType, DeliveryGuarantee};
         Ok(KafkaSinkConnection {
             connection_id: proto
                 .connection_id
@@ -724,6 +782,14 @@ impl RustType<ProtoKafkaSinkConnectionV2> for KafkaSinkConnection {
                 Some(progress_topic_options) => progress_topic_options.into_rust()?,
                 None => Default::default(),
             },
+            transaction_metadata_topic: proto.transaction_metadata_topic,
+            partition_by: proto.partition_by.into_rust()?,
+            delivery_guarantee: match proto.delivery_guarantee {
+                Some(DeliveryGuarantee::ExactlyOnce(())) | None => {
+                    KafkaSinkDeliveryGuarantee::ExactlyOnce
+                }
+                Some(DeliveryGuarantee::AtLeastOnce(())) => KafkaSinkDeliveryGuarantee::AtLeastOnce,
+            },
         })
     }
 }
@@ -736,6 +802,9 @@ pub enum KafkaSinkFormat<C: ConnectionAccess = InlinedConnection> {
         csr_connection: C::Csr,
         key_compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
         value_compatibility_level: Option<mz_ccsr::CompatibilityLevel>,
+        /// Whether nested named types are registered as separate subjects and
+        /// referenced from the top-level schemas.
+        schema_references: bool,
     },
     Json,
 }
@@ -761,6 +830,7 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
                     csr_connection,
                     key_compatibility_level: _,
                     value_compatibility_level: _,
+                    schema_references,
                 },
                 Self::Avro {
                     key_schema: other_key_schema,
@@ -768,6 +838,7 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
                     csr_connection: other_csr_connection,
                     key_compatibility_level: _,
                     value_compatibility_level: _,
+                    schema_references: other_schema_references,
                 },
             ) => {
                 let compatibility_checks = [
@@ -779,6 +850,10 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
                             .is_ok(),
                         "csr_connection",
                     ),
+                    (
+                        schema_references == other_schema_references,
+                        "schema_references",
+                    ),
                 ];
                 for (compatible, field) in compatibility_checks {
                     if !compatible {
@@ -819,12 +894,14 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkFormat, R>
                 csr_connection,
                 key_compatibility_level,
                 value_compatibility_level,
+                schema_references,
             } => KafkaSinkFormat::Avro {
                 key_schema,
                 value_schema,
                 csr_connection: r.resolve_connection(csr_connection).unwrap_csr(),
                 key_compatibility_level,
                 value_compatibility_level,
+                schema_references,
             },
             Self::Json => KafkaSinkFormat::Json,
         }
@@ -878,12 +955,14 @@ impl RustType<ProtoKafkaSinkFormat> for KafkaSinkFormat {
                     csr_connection,
                     key_compatibility_level,
                     value_compatibility_level,
+                    schema_references,
                 } => Kind::Avro(proto_kafka_sink_format::ProtoKafkaSinkAvroFormat {
                     key_schema: key_schema.clone(),
                     value_schema: value_schema.clone(),
                     csr_connection: Some(csr_connection.into_proto()),
                     key_compatibility_level: csr_compat_level_to_proto(key_compatibility_level),
                     value_compatibility_level: csr_compat_level_to_proto(value_compatibility_level),
+                    schema_references: *schema_references,
                 }),
                 Self::Json => Kind::Json(()),
             }),
@@ -907,6 +986,7 @@ impl RustType<ProtoKafkaSinkFormat> for KafkaSinkFormat {
                 value_compatibility_level: csr_compat_level_from_proto(
                     proto.value_compatibility_level,
                 ),
+                schema_references: proto.schema_references,
             },
             Kind::Json(()) => Self::Json,
         })
@@ -956,6 +1036,9 @@ pub struct S3UploadInfo {
     pub desc: RelationDesc,
     /// The selected sink format.
     pub format: S3SinkFormat,
+    /// Whether to write a manifest listing all uploaded files once the upload
+    /// completes.
+    pub manifest: bool,
 }
 
 impl RustType<ProtoS3UploadInfo> for S3UploadInfo {
@@ -965,6 +1048,7 @@ impl RustType<ProtoS3UploadInfo> for S3UploadInfo {
             max_file_size: self.max_file_size,
             desc: Some(self.desc.into_proto()),
             format: Some(self.format.into_proto()),
+            manifest: self.manifest,
         }
     }
 
@@ -976,6 +1060,7 @@ impl RustType<ProtoS3UploadInfo> for S3UploadInfo {
             format: proto
                 .format
                 .into_rust_if_some("ProtoS3UploadInfo::format")?,
+            manifest: proto.manifest,
         })
     }
 }
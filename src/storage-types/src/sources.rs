@@ -1353,6 +1353,12 @@ impl Schema<SourceData> for RelationDesc {
             err,
         })
     }
+
+    fn backfill(&self, val: &mut SourceData) {
+        if let Ok(row) = val.0.as_mut() {
+            Schema::<Row>::backfill(self, row);
+        }
+    }
 }
 
 /// Describes how subsource references should be organized in a multi-level
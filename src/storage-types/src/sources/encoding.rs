@@ -11,6 +11,7 @@
 
 use anyhow::Context;
 use mz_interchange::{avro, protobuf};
+use mz_ore::str::StrExt;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::regex::any_regex;
 use mz_repr::{ColumnType, GlobalId, RelationDesc, ScalarType};
@@ -118,9 +119,10 @@ pub enum DataEncoding<C: ConnectionAccess = InlinedConnection> {
     Protobuf(ProtobufEncoding),
     Csv(CsvEncoding),
     Regex(RegexEncoding),
-    Bytes,
+    Bytes(Framing),
     Json,
-    Text,
+    JsonShred(JsonShredEncoding),
+    Text(Framing),
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<DataEncoding, R>
@@ -132,9 +134,10 @@ impl<R: ConnectionResolver> IntoInlineConnection<DataEncoding, R>
             Self::Protobuf(conn) => DataEncoding::Protobuf(conn),
             Self::Csv(conn) => DataEncoding::Csv(conn),
             Self::Regex(conn) => DataEncoding::Regex(conn),
-            Self::Bytes => DataEncoding::Bytes,
+            Self::Bytes(framing) => DataEncoding::Bytes(framing),
             Self::Json => DataEncoding::Json,
-            Self::Text => DataEncoding::Text,
+            Self::JsonShred(conn) => DataEncoding::JsonShred(conn),
+            Self::Text(framing) => DataEncoding::Text(framing),
         }
     }
 }
@@ -148,9 +151,10 @@ impl RustType<ProtoDataEncoding> for DataEncoding {
                 DataEncoding::Protobuf(e) => Kind::Protobuf(e.into_proto()),
                 DataEncoding::Csv(e) => Kind::Csv(e.into_proto()),
                 DataEncoding::Regex(e) => Kind::Regex(e.into_proto()),
-                DataEncoding::Bytes => Kind::Bytes(()),
-                DataEncoding::Text => Kind::Text(()),
+                DataEncoding::Bytes(f) => Kind::Bytes(f.into_proto()),
+                DataEncoding::Text(f) => Kind::Text(f.into_proto()),
                 DataEncoding::Json => Kind::Json(()),
+                DataEncoding::JsonShred(e) => Kind::JsonShred(e.into_proto()),
             }),
         }
     }
@@ -165,9 +169,10 @@ impl RustType<ProtoDataEncoding> for DataEncoding {
             Kind::Protobuf(e) => DataEncoding::Protobuf(e.into_rust()?),
             Kind::Csv(e) => DataEncoding::Csv(e.into_rust()?),
             Kind::Regex(e) => DataEncoding::Regex(e.into_rust()?),
-            Kind::Bytes(()) => DataEncoding::Bytes,
-            Kind::Text(()) => DataEncoding::Text,
+            Kind::Bytes(f) => DataEncoding::Bytes(f.into_rust()?),
+            Kind::Text(f) => DataEncoding::Text(f.into_rust()?),
             Kind::Json(()) => DataEncoding::Json,
+            Kind::JsonShred(e) => DataEncoding::JsonShred(e.into_rust()?),
         })
     }
 }
@@ -188,9 +193,17 @@ impl<C: ConnectionAccess> DataEncoding<C> {
             Self::Protobuf(_) => "protobuf",
             Self::Csv(_) => "csv",
             Self::Regex(_) => "regex",
-            Self::Bytes => "bytes",
-            Self::Json => "json",
-            Self::Text => "text",
+            Self::Bytes(_) => "bytes",
+            Self::Json | Self::JsonShred(_) => "json",
+            Self::Text(_) => "text",
+        }
+    }
+
+    /// How each message is split into records before decoding.
+    pub fn framing(&self) -> &Framing {
+        match self {
+            Self::Bytes(framing) | Self::Text(framing) => framing,
+            _ => &Framing::None,
         }
     }
 
@@ -199,26 +212,33 @@ impl<C: ConnectionAccess> DataEncoding<C> {
     fn desc(&self) -> Result<RelationDesc, anyhow::Error> {
         // Add columns for the data, based on the encoding format.
         Ok(match self {
-            Self::Bytes => {
+            Self::Bytes(_) => {
                 RelationDesc::empty().with_column("data", ScalarType::Bytes.nullable(false))
             }
             Self::Json => {
                 RelationDesc::empty().with_column("data", ScalarType::Jsonb.nullable(false))
             }
-            Self::Avro(AvroEncoding { schema, .. }) => {
-                let parsed_schema = avro::parse_schema(schema).context("validating avro schema")?;
-                avro::schema_to_relationdesc(parsed_schema).context("validating avro schema")?
+            Self::JsonShred(JsonShredEncoding { columns }) => {
+                columns.iter().fold(RelationDesc::empty(), |desc, column| {
+                    desc.with_column(column.name.as_str(), column.typ.clone().nullable(true))
+                })
             }
-            Self::Protobuf(ProtobufEncoding {
-                descriptors,
-                message_name,
-                confluent_wire_format: _,
-            }) => protobuf::DecodedDescriptors::from_bytes(descriptors, message_name.to_owned())?
-                .columns()
-                .iter()
-                .fold(RelationDesc::empty(), |desc, (name, ty)| {
-                    desc.with_column(name, ty.clone())
-                }),
+            Self::Avro(encoding) => {
+                let parsed_schema =
+                    avro::parse_schema(&encoding.schema).context("validating avro schema")?;
+                avro::schema_to_relationdesc(parsed_schema, encoding.decode_options())
+                    .context("validating avro schema")?
+            }
+            Self::Protobuf(encoding) => protobuf::DecodedDescriptors::from_bytes(
+                &encoding.descriptors,
+                encoding.message_name.to_owned(),
+                encoding.decode_options(),
+            )?
+            .columns()
+            .iter()
+            .fold(RelationDesc::empty(), |desc, (name, ty)| {
+                desc.with_column(name, ty.clone())
+            }),
             Self::Regex(RegexEncoding { regex }) => regex
                 .capture_names()
                 .enumerate()
@@ -246,7 +266,7 @@ impl<C: ConnectionAccess> DataEncoding<C> {
                         desc.with_column(name, ScalarType::String.nullable(false))
                     }),
             },
-            Self::Text => {
+            Self::Text(_) => {
                 RelationDesc::empty().with_column("text", ScalarType::String.nullable(false))
             }
         })
@@ -254,13 +274,14 @@ impl<C: ConnectionAccess> DataEncoding<C> {
 
     pub fn op_name(&self) -> &'static str {
         match self {
-            Self::Bytes => "Bytes",
+            Self::Bytes(_) => "Bytes",
             Self::Json => "Json",
+            Self::JsonShred(_) => "JsonShred",
             Self::Avro(_) => "Avro",
             Self::Protobuf(_) => "Protobuf",
             Self::Regex { .. } => "Regex",
             Self::Csv(_) => "Csv",
-            Self::Text => "Text",
+            Self::Text(_) => "Text",
         }
     }
 }
@@ -298,6 +319,19 @@ pub struct AvroEncoding<C: ConnectionAccess = InlinedConnection> {
     pub schema: String,
     pub csr_connection: Option<C::Csr>,
     pub confluent_wire_format: bool,
+    /// Whether multi-variant unions get a column holding the present variant.
+    pub union_discriminant: bool,
+    /// Whether multi-variant union columns are named after the variant types.
+    pub union_type_names: bool,
+}
+
+impl<C: ConnectionAccess> AvroEncoding<C> {
+    pub fn decode_options(&self) -> avro::DecodeOptions {
+        avro::DecodeOptions {
+            union_discriminant: self.union_discriminant,
+            union_type_names: self.union_type_names,
+        }
+    }
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<AvroEncoding, R>
@@ -308,11 +342,15 @@ impl<R: ConnectionResolver> IntoInlineConnection<AvroEncoding, R>
             schema,
             csr_connection,
             confluent_wire_format,
+            union_discriminant,
+            union_type_names,
         } = self;
         AvroEncoding {
             schema,
             csr_connection: csr_connection.map(|csr| r.resolve_connection(csr).unwrap_csr()),
             confluent_wire_format,
+            union_discriminant,
+            union_type_names,
         }
     }
 }
@@ -327,6 +365,8 @@ impl<C: ConnectionAccess> AlterCompatible for AvroEncoding<C> {
             schema,
             csr_connection,
             confluent_wire_format,
+            union_discriminant,
+            union_type_names,
         } = self;
 
         let compatibility_checks = [
@@ -342,6 +382,14 @@ impl<C: ConnectionAccess> AlterCompatible for AvroEncoding<C> {
                 confluent_wire_format == &other.confluent_wire_format,
                 "confluent_wire_format",
             ),
+            (
+                union_discriminant == &other.union_discriminant,
+                "union_discriminant",
+            ),
+            (
+                union_type_names == &other.union_type_names,
+                "union_type_names",
+            ),
         ];
 
         for (compatible, field) in compatibility_checks {
@@ -366,6 +414,8 @@ impl RustType<ProtoAvroEncoding> for AvroEncoding {
             schema: self.schema.clone(),
             csr_connection: self.csr_connection.into_proto(),
             confluent_wire_format: self.confluent_wire_format,
+            union_discriminant: self.union_discriminant,
+            union_type_names: self.union_type_names,
         }
     }
 
@@ -374,6 +424,8 @@ impl RustType<ProtoAvroEncoding> for AvroEncoding {
             schema: proto.schema,
             csr_connection: proto.csr_connection.into_rust()?,
             confluent_wire_format: proto.confluent_wire_format,
+            union_discriminant: proto.union_discriminant,
+            union_type_names: proto.union_type_names,
         })
     }
 }
@@ -384,6 +436,20 @@ pub struct ProtobufEncoding {
     pub descriptors: Vec<u8>,
     pub message_name: String,
     pub confluent_wire_format: bool,
+    /// Whether well-known types are decoded into native types.
+    pub well_known_types: bool,
+    /// Whether `google.protobuf.Any` fields are unpacked into JSON.
+    pub unpack_any: bool,
+}
+
+impl ProtobufEncoding {
+    /// The options with which to decode messages in this encoding.
+    pub fn decode_options(&self) -> protobuf::DecodeOptions {
+        protobuf::DecodeOptions {
+            well_known_types: self.well_known_types,
+            unpack_any: self.unpack_any,
+        }
+    }
 }
 
 impl RustType<ProtoProtobufEncoding> for ProtobufEncoding {
@@ -392,6 +458,8 @@ impl RustType<ProtoProtobufEncoding> for ProtobufEncoding {
             descriptors: self.descriptors.clone(),
             message_name: self.message_name.clone(),
             confluent_wire_format: self.confluent_wire_format,
+            well_known_types: self.well_known_types,
+            unpack_any: self.unpack_any,
         }
     }
 
@@ -400,6 +468,48 @@ impl RustType<ProtoProtobufEncoding> for ProtobufEncoding {
             descriptors: proto.descriptors,
             message_name: proto.message_name,
             confluent_wire_format: proto.confluent_wire_format,
+            well_known_types: proto.well_known_types,
+            unpack_any: proto.unpack_any,
+        })
+    }
+}
+
+/// How the `TEXT` and `BYTES` formats split each message into records.
+#[derive(Arbitrary, Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub enum Framing {
+    /// Each message is a single record.
+    #[default]
+    None,
+    /// Records are terminated by the delimiter, which may be more than one
+    /// byte. The final record need not be terminated.
+    Delimited(Vec<u8>),
+    /// Each record is preceded by its length in bytes, as a big-endian
+    /// unsigned integer of `width` bytes.
+    LengthPrefixed { width: u8 },
+}
+
+impl RustType<ProtoFraming> for Framing {
+    fn into_proto(&self) -> ProtoFraming {
+        use proto_framing::Kind;
+        ProtoFraming {
+            kind: match self {
+                Framing::None => None,
+                Framing::Delimited(delimiter) => Some(Kind::Delimiter(delimiter.clone())),
+                Framing::LengthPrefixed { width } => {
+                    Some(Kind::LengthPrefixWidth(width.into_proto()))
+                }
+            },
+        }
+    }
+
+    fn from_proto(proto: ProtoFraming) -> Result<Self, TryFromProtoError> {
+        use proto_framing::Kind;
+        Ok(match proto.kind {
+            None => Framing::None,
+            Some(Kind::Delimiter(delimiter)) => Framing::Delimited(delimiter),
+            Some(Kind::LengthPrefixWidth(width)) => Framing::LengthPrefixed {
+                width: width.into_rust()?,
+            },
         })
     }
 }
@@ -408,14 +518,24 @@ impl RustType<ProtoProtobufEncoding> for ProtobufEncoding {
 #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct CsvEncoding {
     pub columns: ColumnSpec,
-    pub delimiter: u8,
+    /// The delimiter between fields, which may be more than one byte.
+    pub delimiter: Vec<u8>,
+    pub quote: u8,
+    /// The character that escapes quotes within quoted fields, in addition
+    /// to doubling them.
+    pub escape: Option<u8>,
+    /// Whether quoted fields may contain newlines.
+    pub embedded_newlines: bool,
 }
 
 impl RustType<ProtoCsvEncoding> for CsvEncoding {
     fn into_proto(&self) -> ProtoCsvEncoding {
         ProtoCsvEncoding {
             columns: Some(self.columns.into_proto()),
-            delimiter: self.delimiter.into_proto(),
+            delimiter: self.delimiter.clone(),
+            quote: self.quote.into_proto(),
+            escape: self.escape.into_proto(),
+            embedded_newlines: self.embedded_newlines,
         }
     }
 
@@ -424,7 +544,10 @@ impl RustType<ProtoCsvEncoding> for CsvEncoding {
             columns: proto
                 .columns
                 .into_rust_if_some("ProtoCsvEncoding::columns")?,
-            delimiter: proto.delimiter.into_rust()?,
+            delimiter: proto.delimiter,
+            quote: proto.quote.into_rust()?,
+            escape: proto.escape.into_rust()?,
+            embedded_newlines: proto.embedded_newlines,
         })
     }
 }
@@ -501,3 +624,188 @@ impl RustType<ProtoRegexEncoding> for RegexEncoding {
         })
     }
 }
+
+/// Encoding in JSON format, with values extracted into typed columns.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct JsonShredEncoding {
+    pub columns: Vec<JsonShredColumn>,
+}
+
+impl RustType<ProtoJsonShredEncoding> for JsonShredEncoding {
+    fn into_proto(&self) -> ProtoJsonShredEncoding {
+        ProtoJsonShredEncoding {
+            columns: self.columns.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoJsonShredEncoding) -> Result<Self, TryFromProtoError> {
+        Ok(JsonShredEncoding {
+            columns: proto.columns.into_rust()?,
+        })
+    }
+}
+
+/// A column of a [`JsonShredEncoding`].
+///
+/// The column is `NULL` if the path does not exist in a decoded value, or
+/// refers to a JSON `null`.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct JsonShredColumn {
+    pub name: String,
+    pub path: JsonPath,
+    pub typ: ScalarType,
+}
+
+impl JsonShredColumn {
+    /// Whether JSON values can be decoded into a column of type `typ`.
+    pub fn supports_type(typ: &ScalarType) -> bool {
+        matches!(
+            typ,
+            ScalarType::Bool
+                | ScalarType::Int16
+                | ScalarType::Int32
+                | ScalarType::Int64
+                | ScalarType::Float32
+                | ScalarType::Float64
+                | ScalarType::Numeric { .. }
+                | ScalarType::String
+                | ScalarType::Jsonb
+                | ScalarType::Date
+                | ScalarType::Timestamp { .. }
+                | ScalarType::TimestampTz { .. }
+                | ScalarType::Uuid
+        )
+    }
+}
+
+impl RustType<ProtoJsonShredColumn> for JsonShredColumn {
+    fn into_proto(&self) -> ProtoJsonShredColumn {
+        ProtoJsonShredColumn {
+            name: self.name.clone(),
+            path: self.path.0.into_proto(),
+            typ: Some(self.typ.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoJsonShredColumn) -> Result<Self, TryFromProtoError> {
+        Ok(JsonShredColumn {
+            name: proto.name,
+            path: JsonPath(proto.path.into_rust()?),
+            typ: proto.typ.into_rust_if_some("ProtoJsonShredColumn::typ")?,
+        })
+    }
+}
+
+/// A JSONPath expression that selects a single value.
+///
+/// Supports the root (`$`), member accessors (`.name`, `['name']`, and
+/// `["name"]`), and array subscripts (`[0]`).
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct JsonPath(pub Vec<JsonPathElement>);
+
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum JsonPathElement {
+    Field(String),
+    Index(usize),
+}
+
+impl RustType<ProtoJsonPathElement> for JsonPathElement {
+    fn into_proto(&self) -> ProtoJsonPathElement {
+        use proto_json_path_element::Kind;
+        ProtoJsonPathElement {
+            kind: Some(match self {
+                JsonPathElement::Field(name) => Kind::Field(name.clone()),
+                JsonPathElement::Index(i) => Kind::Index(i.into_proto()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoJsonPathElement) -> Result<Self, TryFromProtoError> {
+        use proto_json_path_element::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoJsonPathElement::kind"))?;
+        Ok(match kind {
+            Kind::Field(name) => JsonPathElement::Field(name),
+            Kind::Index(i) => JsonPathElement::Index(i.into_rust()?),
+        })
+    }
+}
+
+impl JsonPath {
+    /// Parses a JSONPath expression.
+    pub fn parse(s: &str) -> Result<JsonPath, anyhow::Error> {
+        let mut chars = s.trim().chars().peekable();
+        if chars.next() != Some('$') {
+            anyhow::bail!("JSON path {} must start with $", s.quoted());
+        }
+        let mut elements = vec![];
+        while let Some(c) = chars.next() {
+            match c {
+                '.' => {
+                    let mut name = String::new();
+                    while let Some(c) = chars.next_if(|c| *c != '.' && *c != '[') {
+                        name.push(c);
+                    }
+                    if name.is_empty() {
+                        anyhow::bail!("JSON path {} has an empty member name", s.quoted());
+                    }
+                    elements.push(JsonPathElement::Field(name));
+                }
+                '[' => match chars.next() {
+                    Some(quote @ ('\'' | '"')) => {
+                        let mut name = String::new();
+                        loop {
+                            match chars.next() {
+                                Some('\\') => match chars.next() {
+                                    Some(c) => name.push(c),
+                                    None => break,
+                                },
+                                Some(c) if c == quote => break,
+                                Some(c) => name.push(c),
+                                None => anyhow::bail!(
+                                    "JSON path {} has an unterminated string",
+                                    s.quoted()
+                                ),
+                            }
+                        }
+                        if chars.next() != Some(']') {
+                            anyhow::bail!("JSON path {} is missing a ]", s.quoted());
+                        }
+                        elements.push(JsonPathElement::Field(name));
+                    }
+                    Some(c) => {
+                        let mut index = String::from(c);
+                        while let Some(c) = chars.next_if(|c| *c != ']') {
+                            index.push(c);
+                        }
+                        if chars.next() != Some(']') {
+                            anyhow::bail!("JSON path {} is missing a ]", s.quoted());
+                        }
+                        let index = index.trim().parse().map_err(|_| {
+                            anyhow::anyhow!(
+                                "JSON path {} has an invalid array subscript {}",
+                                s.quoted(),
+                                index.quoted()
+                            )
+                        })?;
+                        elements.push(JsonPathElement::Index(index));
+                    }
+                    None => anyhow::bail!("JSON path {} is missing a ]", s.quoted()),
+                },
+                c => anyhow::bail!("JSON path {} has unexpected character {c:?}", s.quoted()),
+            }
+        }
+        Ok(JsonPath(elements))
+    }
+
+    /// Returns the value at this path within `value`, if any.
+    pub fn lookup<'a>(&self, value: &'a serde_json::Value) -> Option<&'a serde_json::Value> {
+        self.0
+            .iter()
+            .try_fold(value, |value, element| match element {
+                JsonPathElement::Field(name) => value.as_object()?.get(name),
+                JsonPathElement::Index(i) => value.as_array()?.get(*i),
+            })
+    }
+}
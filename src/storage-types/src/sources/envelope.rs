@@ -12,6 +12,7 @@
 use anyhow::{anyhow, bail};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::{ColumnType, RelationDesc, RelationType, ScalarType};
+use mz_rocksdb_types::config::RocksDBTuningOverrides;
 use proptest::prelude::any;
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
@@ -116,6 +117,8 @@ pub struct UpsertEnvelope {
     /// to deduplicate data in `upsert_core`
     #[proptest(strategy = "proptest::collection::vec(any::<usize>(), 0..4)")]
     pub key_indices: Vec<usize>,
+    /// Overrides of the cluster-wide RocksDB tuning for this source's upsert state.
+    pub rocksdb_tuning: RocksDBTuningOverrides,
 }
 
 impl RustType<ProtoUpsertEnvelope> for UpsertEnvelope {
@@ -124,6 +127,7 @@ impl RustType<ProtoUpsertEnvelope> for UpsertEnvelope {
             source_arity: self.source_arity.into_proto(),
             style: Some(self.style.into_proto()),
             key_indices: self.key_indices.into_proto(),
+            rocksdb_tuning: Some(self.rocksdb_tuning.into_proto()),
         }
     }
 
@@ -134,6 +138,10 @@ impl RustType<ProtoUpsertEnvelope> for UpsertEnvelope {
                 .style
                 .into_rust_if_some("ProtoUpsertEnvelope::style")?,
             key_indices: proto.key_indices.into_rust()?,
+            rocksdb_tuning: match proto.rocksdb_tuning {
+                Some(rocksdb_tuning) => rocksdb_tuning.into_rust()?,
+                None => Default::default(),
+            },
         })
     }
 }
@@ -227,6 +235,7 @@ impl UnplannedSourceEnvelope {
                     "into_source_envelope to be passed \
                     correct parameters for UnplannedSourceEnvelope::Upsert",
                 ),
+                rocksdb_tuning: RocksDBTuningOverrides::default(),
             }),
             UnplannedSourceEnvelope::None(key_envelope) => SourceEnvelope::None(NoneEnvelope {
                 key_envelope,
@@ -58,6 +58,12 @@ pub struct KafkaSourceConnection<C: ConnectionAccess = InlinedConnection> {
     #[proptest(strategy = "proptest::collection::vec(any::<(String, KafkaMetadataKind)>(), 0..4)")]
     pub metadata_columns: Vec<(String, KafkaMetadataKind)>,
     pub topic_metadata_refresh_interval: Duration,
+    /// The maximum number of records per second that the source may ingest,
+    /// summed across all workers. `None` means ingestion is unthrottled.
+    pub max_ingest_rate: Option<u64>,
+    /// The secret holding the AES-GCM key with which message values are
+    /// decrypted before they are decoded, if any.
+    pub value_decryption_key: Option<GlobalId>,
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<KafkaSourceConnection, R>
@@ -72,6 +78,8 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSourceConnection, R>
             group_id_prefix,
             metadata_columns,
             topic_metadata_refresh_interval,
+            max_ingest_rate,
+            value_decryption_key,
         } = self;
         KafkaSourceConnection {
             connection: r.resolve_connection(connection).unwrap_kafka(),
@@ -81,6 +89,8 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSourceConnection, R>
             group_id_prefix,
             metadata_columns,
             topic_metadata_refresh_interval,
+            max_ingest_rate,
+            value_decryption_key,
         }
     }
 }
@@ -219,6 +229,7 @@ impl<C: ConnectionAccess> SourceConnection for KafkaSourceConnection<C> {
                     KafkaMetadataKind::Timestamp => {
                         ScalarType::Timestamp { precision: None }.nullable(false)
                     }
+                    KafkaMetadataKind::TimestampType => ScalarType::String.nullable(true),
                     KafkaMetadataKind::Header {
                         use_bytes: true, ..
                     } => ScalarType::Bytes.nullable(true),
@@ -248,6 +259,11 @@ impl<C: ConnectionAccess> SourceConnection for KafkaSourceConnection<C> {
                         custom_id: None,
                     }
                     .nullable(false),
+                    KafkaMetadataKind::HeadersMap => ScalarType::Map {
+                        value_type: Box::new(ScalarType::Bytes),
+                        custom_id: None,
+                    }
+                    .nullable(false),
                 };
                 (&**name, typ)
             })
@@ -273,6 +289,8 @@ impl<C: ConnectionAccess> crate::AlterCompatible for KafkaSourceConnection<C> {
             group_id_prefix,
             metadata_columns,
             topic_metadata_refresh_interval,
+            max_ingest_rate,
+            value_decryption_key,
         } = self;
 
         let compatibility_checks = [
@@ -292,6 +310,11 @@ impl<C: ConnectionAccess> crate::AlterCompatible for KafkaSourceConnection<C> {
                 topic_metadata_refresh_interval == &other.topic_metadata_refresh_interval,
                 "topic_metadata_refresh_interval",
             ),
+            (max_ingest_rate == &other.max_ingest_rate, "max_ingest_rate"),
+            (
+                value_decryption_key == &other.value_decryption_key,
+                "value_decryption_key",
+            ),
         ];
 
         for (compatible, field) in compatibility_checks {
@@ -329,6 +352,8 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection<InlinedConne
             topic_metadata_refresh_interval: Some(
                 self.topic_metadata_refresh_interval.into_proto(),
             ),
+            max_ingest_rate: self.max_ingest_rate,
+            value_decryption_key: self.value_decryption_key.into_proto(),
         }
     }
 
@@ -353,6 +378,8 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection<InlinedConne
             topic_metadata_refresh_interval: proto
                 .topic_metadata_refresh_interval
                 .into_rust_if_some("ProtoKafkaSourceConnection::topic_metadata_refresh_interval")?,
+            max_ingest_rate: proto.max_ingest_rate,
+            value_decryption_key: proto.value_decryption_key.into_rust()?,
         })
     }
 }
@@ -516,8 +543,15 @@ pub enum KafkaMetadataKind {
     Partition,
     Offset,
     Timestamp,
+    /// Whether the timestamp was set by the producer or the broker.
+    TimestampType,
     Headers,
-    Header { key: String, use_bytes: bool },
+    /// All headers as a map, keeping the last value of repeated keys.
+    HeadersMap,
+    Header {
+        key: String,
+        use_bytes: bool,
+    },
 }
 
 impl RustType<ProtoKafkaMetadataKind> for KafkaMetadataKind {
@@ -528,7 +562,9 @@ impl RustType<ProtoKafkaMetadataKind> for KafkaMetadataKind {
                 KafkaMetadataKind::Partition => Kind::Partition(()),
                 KafkaMetadataKind::Offset => Kind::Offset(()),
                 KafkaMetadataKind::Timestamp => Kind::Timestamp(()),
+                KafkaMetadataKind::TimestampType => Kind::TimestampType(()),
                 KafkaMetadataKind::Headers => Kind::Headers(()),
+                KafkaMetadataKind::HeadersMap => Kind::HeadersMap(()),
                 KafkaMetadataKind::Header { key, use_bytes } => Kind::Header(ProtoKafkaHeader {
                     key: key.clone(),
                     use_bytes: *use_bytes,
@@ -546,7 +582,9 @@ impl RustType<ProtoKafkaMetadataKind> for KafkaMetadataKind {
             Kind::Partition(()) => KafkaMetadataKind::Partition,
             Kind::Offset(()) => KafkaMetadataKind::Offset,
             Kind::Timestamp(()) => KafkaMetadataKind::Timestamp,
+            Kind::TimestampType(()) => KafkaMetadataKind::TimestampType,
             Kind::Headers(()) => KafkaMetadataKind::Headers,
+            Kind::HeadersMap(()) => KafkaMetadataKind::HeadersMap,
             Kind::Header(ProtoKafkaHeader { key, use_bytes }) => {
                 KafkaMetadataKind::Header { key, use_bytes }
             }
@@ -108,6 +108,25 @@ impl SourceConnection for LoadGeneratorSourceConnection {
                 }
                 desc
             }
+            LoadGenerator::Custom(custom) => {
+                let mut desc = RelationDesc::empty();
+                for column in &custom.columns {
+                    desc =
+                        desc.with_column(column.name.as_str(), column.distribution.column_type());
+                }
+                // Every key is present at most once when the keyspace is
+                // bounded.
+                if custom.keys.is_some() {
+                    if let Some(key) = custom
+                        .columns
+                        .iter()
+                        .position(|c| c.distribution == ValueDistribution::Key)
+                    {
+                        desc = desc.with_key(vec![key]);
+                    }
+                }
+                desc
+            }
         }
     }
 
@@ -156,6 +175,7 @@ pub enum LoadGenerator {
         count_clerk: i64,
     },
     KeyValue(KeyValueLoadGenerator),
+    Custom(CustomLoadGenerator),
 }
 
 pub const LOAD_GENERATOR_DATABASE_NAME: &str = "mz_load_generators";
@@ -169,6 +189,7 @@ impl LoadGenerator {
             LoadGenerator::Datums => "datums",
             LoadGenerator::Tpch { .. } => "tpch",
             LoadGenerator::KeyValue { .. } => "key_value",
+            LoadGenerator::Custom(_) => "custom",
         }
     }
 
@@ -412,6 +433,7 @@ impl LoadGenerator {
                 ]
             }
             LoadGenerator::KeyValue(_) => vec![],
+            LoadGenerator::Custom(_) => vec![],
         }
     }
 
@@ -426,6 +448,7 @@ impl LoadGenerator {
             LoadGenerator::Datums => true,
             LoadGenerator::Tpch { .. } => false,
             LoadGenerator::KeyValue(_) => true,
+            LoadGenerator::Custom(CustomLoadGenerator { keys, .. }) => keys.is_none(),
         }
     }
 }
@@ -467,6 +490,7 @@ impl RustType<ProtoLoadGeneratorSourceConnection> for LoadGeneratorSourceConnect
                 }),
                 LoadGenerator::Datums => Kind::Datums(()),
                 LoadGenerator::KeyValue(kv) => Kind::KeyValue(kv.into_proto()),
+                LoadGenerator::Custom(custom) => Kind::Custom(custom.into_proto()),
             }),
             tick_micros: self.tick_micros,
             as_of: self.as_of,
@@ -501,6 +525,7 @@ impl RustType<ProtoLoadGeneratorSourceConnection> for LoadGeneratorSourceConnect
                 },
                 Kind::Datums(()) => LoadGenerator::Datums,
                 Kind::KeyValue(kv) => LoadGenerator::KeyValue(kv.into_rust()?),
+                Kind::Custom(custom) => LoadGenerator::Custom(custom.into_rust()?),
             },
             tick_micros: proto.tick_micros,
             as_of: proto.as_of,
@@ -586,3 +611,125 @@ impl RustType<ProtoKeyValueLoadGenerator> for KeyValueLoadGenerator {
         })
     }
 }
+
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CustomLoadGenerator {
+    /// The columns to generate, in order.
+    pub columns: Vec<CustomLoadGeneratorColumn>,
+    /// The keyspace of the source, if bounded. When set, each new row retracts
+    /// the row emitted `keys` rows before it, so the collection holds at most
+    /// `keys` rows.
+    pub keys: Option<u64>,
+    /// The number of rows to produce per second.
+    pub rows_per_second: u64,
+    /// A per-source seed.
+    pub seed: u64,
+}
+
+impl CustomLoadGenerator {
+    /// The number of rows emitted at each offset and the interval between
+    /// offsets, chosen such that `rows_per_second` rows are produced each
+    /// second without ticking more than once per millisecond.
+    pub fn batch_size_and_tick(&self) -> (u64, Duration) {
+        let rows_per_second = self.rows_per_second.max(1);
+        let batch_size = rows_per_second.div_ceil(1_000);
+        let tick_micros = 1_000_000 * batch_size / rows_per_second;
+        (batch_size, Duration::from_micros(tick_micros))
+    }
+}
+
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CustomLoadGeneratorColumn {
+    pub name: String,
+    pub distribution: ValueDistribution,
+}
+
+/// How the values of a [`CustomLoadGeneratorColumn`] are produced.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ValueDistribution {
+    /// The sequence number of the row.
+    Sequence,
+    /// The key of the row, i.e. its sequence number modulo the keyspace.
+    Key,
+    /// An integer drawn uniformly from `[min, max]`.
+    Uniform { min: i64, max: i64 },
+    /// A float drawn from a normal distribution.
+    Normal { mean: i64, stddev: i64 },
+    /// A random alphanumeric string of the given length.
+    Text { len: u64 },
+}
+
+impl ValueDistribution {
+    pub fn column_type(&self) -> ColumnType {
+        match self {
+            ValueDistribution::Sequence
+            | ValueDistribution::Key
+            | ValueDistribution::Uniform { .. } => ScalarType::Int64.nullable(false),
+            ValueDistribution::Normal { .. } => ScalarType::Float64.nullable(false),
+            ValueDistribution::Text { .. } => ScalarType::String.nullable(false),
+        }
+    }
+}
+
+impl RustType<ProtoCustomLoadGenerator> for CustomLoadGenerator {
+    fn into_proto(&self) -> ProtoCustomLoadGenerator {
+        ProtoCustomLoadGenerator {
+            columns: self.columns.into_proto(),
+            keys: self.keys,
+            rows_per_second: self.rows_per_second,
+            seed: self.seed,
+        }
+    }
+
+    fn from_proto(proto: ProtoCustomLoadGenerator) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            columns: proto.columns.into_rust()?,
+            keys: proto.keys,
+            rows_per_second: proto.rows_per_second,
+            seed: proto.seed,
+        })
+    }
+}
+
+impl RustType<ProtoCustomLoadGeneratorColumn> for CustomLoadGeneratorColumn {
+    fn into_proto(&self) -> ProtoCustomLoadGeneratorColumn {
+        use proto_custom_load_generator_column::{Distribution, ProtoNormal, ProtoUniform};
+        ProtoCustomLoadGeneratorColumn {
+            name: self.name.clone(),
+            distribution: Some(match &self.distribution {
+                ValueDistribution::Sequence => Distribution::Sequence(()),
+                ValueDistribution::Key => Distribution::Key(()),
+                ValueDistribution::Uniform { min, max } => Distribution::Uniform(ProtoUniform {
+                    min: *min,
+                    max: *max,
+                }),
+                ValueDistribution::Normal { mean, stddev } => Distribution::Normal(ProtoNormal {
+                    mean: *mean,
+                    stddev: *stddev,
+                }),
+                ValueDistribution::Text { len } => Distribution::Text(*len),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoCustomLoadGeneratorColumn) -> Result<Self, TryFromProtoError> {
+        use proto_custom_load_generator_column::{Distribution, ProtoNormal, ProtoUniform};
+        let distribution = proto.distribution.ok_or_else(|| {
+            TryFromProtoError::missing_field("ProtoCustomLoadGeneratorColumn::distribution")
+        })?;
+        Ok(Self {
+            name: proto.name,
+            distribution: match distribution {
+                Distribution::Sequence(()) => ValueDistribution::Sequence,
+                Distribution::Key(()) => ValueDistribution::Key,
+                Distribution::Uniform(ProtoUniform { min, max }) => {
+                    ValueDistribution::Uniform { min, max }
+                }
+                Distribution::Normal(ProtoNormal { mean, stddev }) => {
+                    ValueDistribution::Normal { mean, stddev }
+                }
+                Distribution::Text(len) => ValueDistribution::Text { len },
+            },
+        })
+    }
+}
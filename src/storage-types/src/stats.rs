@@ -208,6 +208,53 @@ impl RelationPartStats<'_> {
         num_oks.map(|num_oks| num_results - num_oks)
     }
 
+    /// Returns the number of `NULL`s in column `idx` of this part, and the bounds of its non-null
+    /// values, if it has any.
+    ///
+    /// Returns `None` if there are no stats for the column.
+    pub fn col_bounds<'a>(
+        &'a self,
+        idx: usize,
+        arena: &'a RowArena,
+    ) -> Option<(usize, Option<(Datum<'a>, Datum<'a>)>)> {
+        type Bounds<'a> = Option<(usize, Option<(Datum<'a>, Datum<'a>)>)>;
+        struct ColBounds<'a>(
+            &'a PartStatsMetrics,
+            &'a str,
+            &'a str,
+            &'a dyn DynStats,
+            &'a RowArena,
+        );
+        impl<'a> DatumToPersistFn<Bounds<'a>> for ColBounds<'a> {
+            fn call<T: DatumToPersist>(self) -> Bounds<'a> {
+                let ColBounds(metrics, name, col_name, stats, arena) = self;
+                let stats = downcast_stats::<T::Data>(metrics, name, col_name, stats)?;
+                let make_datum = |lower| arena.make_datum(|packer| T::decode(lower, packer));
+                let bounds = match (stats.lower().map(make_datum), stats.upper().map(make_datum)) {
+                    (Some(lower), Some(upper)) => Some((lower, upper)),
+                    _ => None,
+                };
+                Some((stats.none_count(), bounds))
+            }
+        }
+
+        let name = self.desc.get_name(idx);
+        let typ = &self.desc.typ().column_types[idx];
+        let ok_stats = self
+            .stats
+            .key
+            .col::<Option<DynStruct>>("ok")
+            .expect("ok column should be a struct")?;
+        let stats = ok_stats.some.cols.get(name.as_str())?;
+        typ.to_persist(ColBounds(
+            self.metrics,
+            self.name,
+            name.as_str(),
+            stats.as_ref(),
+            arena,
+        ))
+    }
+
     fn col_values<'a>(&'a self, idx: usize, arena: &'a RowArena) -> Option<ResultSpec> {
         struct ColValues<'a>(
             &'a PartStatsMetrics,
@@ -25,7 +25,7 @@ use mz_ore::future::InTask;
 use mz_repr::{Datum, Diff, Row};
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::errors::{CsrConnectError, DecodeError, DecodeErrorKind};
-use mz_storage_types::sources::encoding::{AvroEncoding, DataEncoding, RegexEncoding};
+use mz_storage_types::sources::encoding::{AvroEncoding, DataEncoding, Framing, RegexEncoding};
 use mz_timely_util::builder_async::{
     Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
 };
@@ -40,6 +40,7 @@ use tracing::error;
 
 use crate::decode::avro::AvroDecoderState;
 use crate::decode::csv::CsvDecoderState;
+use crate::decode::json::JsonShredDecoderState;
 use crate::decode::protobuf::ProtobufDecoderState;
 use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
 use crate::metrics::decode::DecodeMetricDefs;
@@ -47,6 +48,7 @@ use crate::source::types::{DecodeResult, SourceOutput};
 
 mod avro;
 mod csv;
+mod json;
 mod protobuf;
 
 /// Decode delimited CDCv2 messages.
@@ -210,6 +212,7 @@ pub(crate) enum PreDelimitedFormat {
     Bytes,
     Text,
     Json,
+    JsonShred(JsonShredDecoderState),
     Regex(Regex, Row),
     Protobuf(ProtobufDecoderState),
 }
@@ -232,6 +235,7 @@ impl PreDelimitedFormat {
                 })?;
                 Ok(Some(j.into_row()))
             }
+            PreDelimitedFormat::JsonShred(json) => json.decode(bytes),
             PreDelimitedFormat::Text => {
                 let s = std::str::from_utf8(bytes)
                     .map_err(|_| DecodeErrorKind::Text("Failed to decode UTF-8".to_string()))?;
@@ -351,11 +355,14 @@ async fn get_decoder(
     storage_configuration: &StorageConfiguration,
 ) -> Result<DataDecoder, CsrConnectError> {
     let decoder = match encoding {
-        DataEncoding::Avro(AvroEncoding {
-            schema,
-            csr_connection,
-            confluent_wire_format,
-        }) => {
+        DataEncoding::Avro(encoding) => {
+            let options = encoding.decode_options();
+            let AvroEncoding {
+                schema,
+                csr_connection,
+                confluent_wire_format,
+                ..
+            } = encoding;
             let csr_client = match csr_connection {
                 None => None,
                 Some(csr_connection) => {
@@ -370,6 +377,7 @@ async fn get_decoder(
                 csr_client,
                 debug_name.to_string(),
                 confluent_wire_format,
+                options,
             )
             .expect("Failed to create avro decoder, even though we validated ccsr client creation in purification.");
             DataDecoder {
@@ -377,9 +385,10 @@ async fn get_decoder(
                 metrics,
             }
         }
-        DataEncoding::Text
-        | DataEncoding::Bytes
+        DataEncoding::Text(_)
+        | DataEncoding::Bytes(_)
         | DataEncoding::Json
+        | DataEncoding::JsonShred(_)
         | DataEncoding::Protobuf(_)
         | DataEncoding::Regex(_) => {
             let after_delimiting = match encoding {
@@ -392,9 +401,12 @@ async fn get_decoder(
                                     client creation in purification.",
                     ))
                 }
-                DataEncoding::Bytes => PreDelimitedFormat::Bytes,
+                DataEncoding::Bytes(_) => PreDelimitedFormat::Bytes,
                 DataEncoding::Json => PreDelimitedFormat::Json,
-                DataEncoding::Text => PreDelimitedFormat::Text,
+                DataEncoding::JsonShred(encoding) => {
+                    PreDelimitedFormat::JsonShred(JsonShredDecoderState::new(encoding))
+                }
+                DataEncoding::Text(_) => PreDelimitedFormat::Text,
                 _ => unreachable!(),
             };
             let inner = if is_connection_delimited {
@@ -447,6 +459,94 @@ async fn decode_delimited(
     }))
 }
 
+/// Splits a message into the records it contains according to `framing`.
+///
+/// A record that is truncated by the end of the message is returned as an
+/// error, after which no further records are returned.
+fn split_records<'a>(
+    framing: &'a Framing,
+    mut buf: &'a [u8],
+) -> impl Iterator<Item = Result<&'a [u8], DecodeErrorKind>> + 'a {
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        match framing {
+            Framing::None => {
+                done = true;
+                Some(Ok(buf))
+            }
+            // A message that ends with a delimiter does not contain a final
+            // empty record.
+            _ if buf.is_empty() => None,
+            Framing::Delimited(delimiter) => {
+                match buf
+                    .windows(delimiter.len())
+                    .position(|window| window == delimiter.as_slice())
+                {
+                    Some(idx) => {
+                        let record = &buf[..idx];
+                        buf = &buf[idx + delimiter.len()..];
+                        Some(Ok(record))
+                    }
+                    None => {
+                        done = true;
+                        Some(Ok(buf))
+                    }
+                }
+            }
+            Framing::LengthPrefixed { width } => {
+                let width = usize::from(*width);
+                if buf.len() < width {
+                    done = true;
+                    return Some(Err(DecodeErrorKind::Bytes(format!(
+                        "Truncated length prefix: expected {width} bytes, found {}",
+                        buf.len()
+                    ))));
+                }
+                let (prefix, rest) = buf.split_at(width);
+                let len = prefix
+                    .iter()
+                    .fold(0u64, |len, byte| (len << 8) | u64::from(*byte));
+                match usize::try_from(len).ok().filter(|len| *len <= rest.len()) {
+                    Some(len) => {
+                        buf = &rest[len..];
+                        Some(Ok(&rest[..len]))
+                    }
+                    None => {
+                        done = true;
+                        Some(Err(DecodeErrorKind::Bytes(format!(
+                            "Truncated record: length prefix is {len} bytes, but only {} bytes remain",
+                            rest.len()
+                        ))))
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Decodes each of the records in a message, as split by [`split_records`].
+async fn decode_framed(
+    decoder: &mut DataDecoder,
+    framing: &Framing,
+    buf: &[u8],
+) -> Result<Vec<Option<Result<Row, DecodeError>>>, CsrConnectError> {
+    let mut values = vec![];
+    for record in split_records(framing, buf) {
+        let value = match record {
+            Ok(record) => decode_delimited(decoder, record).await?.transpose(),
+            Err(kind) => Some(Err(DecodeError {
+                kind,
+                raw: buf.to_vec(),
+            })),
+        };
+        values.push(value);
+    }
+    Ok(values)
+}
+
 /// Decode already delimited records of data.
 ///
 /// Precondition: each record in the stream has at most one key and at most one value.
@@ -454,6 +554,10 @@ async fn decode_delimited(
 /// that have already separated the stream into records/messages/etc. before we
 /// decode them.
 ///
+/// If the value encoding specifies a [`Framing`], each message is first split
+/// into the records it contains, and each record produces its own result with
+/// a copy of the message's key and metadata.
+///
 /// Because we expect the upstream connection to have already delimited the data,
 /// we return an error here if the decoder does not consume all the bytes. This
 /// often lets us, for example, detect when Avro decoding has gone off the rails
@@ -479,6 +583,7 @@ pub fn render_decode_delimited<G: Scope, FromTime: Timestamp>(
         value_encoding.op_name()
     );
     let dist = |(x, _, _): &(SourceOutput<FromTime>, _, _)| x.value.hashed();
+    let framing = value_encoding.framing().clone();
 
     let mut builder = AsyncOperatorBuilder::new(op_name, input.scope());
 
@@ -533,27 +638,29 @@ pub fn render_decode_delimited<G: Scope, FromTime: Timestamp>(
                                 None => None,
                             };
 
-                            let value = match output.value.unpack_first() {
+                            let values = match output.value.unpack_first() {
                                 Datum::Bytes(buf) => {
-                                    decode_delimited(&mut value_decoder, buf).await?.transpose()
+                                    decode_framed(&mut value_decoder, &framing, buf).await?
                                 }
-                                Datum::Null => None,
+                                Datum::Null => vec![None],
                                 d => unreachable!("invalid datum: {d}"),
                             };
 
-                            if matches!(&key, Some(Err(_))) || matches!(&value, Some(Err(_))) {
-                                n_errors += 1;
-                            } else if matches!(&value, Some(Ok(_))) {
-                                n_successes += 1;
-                            }
+                            for value in values {
+                                if matches!(&key, Some(Err(_))) || matches!(&value, Some(Err(_))) {
+                                    n_errors += 1;
+                                } else if matches!(&value, Some(Ok(_))) {
+                                    n_successes += 1;
+                                }
 
-                            let result = DecodeResult {
-                                key,
-                                value,
-                                metadata: output.metadata.clone(),
-                                from_time: output.from_time.clone(),
-                            };
-                            output_container.push((result, ts.clone(), *diff));
+                                let result = DecodeResult {
+                                    key: key.clone(),
+                                    value,
+                                    metadata: output.metadata.clone(),
+                                    from_time: output.from_time.clone(),
+                                };
+                                output_container.push((result, ts.clone(), *diff));
+                            }
                         }
 
                         // Matching historical practice, we only log metrics on the value decoder.
@@ -591,3 +698,42 @@ pub fn render_decode_delimited<G: Scope, FromTime: Timestamp>(
 
     (output.as_collection(), health)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(framing: &Framing, buf: &[u8]) -> Vec<Result<Vec<u8>, DecodeErrorKind>> {
+        split_records(framing, buf)
+            .map(|record| record.map(|r| r.to_vec()))
+            .collect()
+    }
+
+    #[mz_ore::test]
+    fn test_split_records() {
+        assert_eq!(split(&Framing::None, b"a\nb"), vec![Ok(b"a\nb".to_vec())]);
+
+        let delimited = Framing::Delimited(b"||".to_vec());
+        assert_eq!(
+            split(&delimited, b"a||||b|c||"),
+            vec![Ok(b"a".to_vec()), Ok(b"".to_vec()), Ok(b"b|c".to_vec())]
+        );
+        assert_eq!(
+            split(&delimited, b"a||b"),
+            vec![Ok(b"a".to_vec()), Ok(b"b".to_vec())]
+        );
+        assert_eq!(split(&delimited, b""), vec![]);
+
+        let length_prefixed = Framing::LengthPrefixed { width: 2 };
+        assert_eq!(
+            split(&length_prefixed, b"\x00\x01a\x00\x00\x00\x03bcd"),
+            vec![Ok(b"a".to_vec()), Ok(b"".to_vec()), Ok(b"bcd".to_vec())]
+        );
+        let truncated = split(&length_prefixed, b"\x00\x01a\x00\x05bc");
+        assert_eq!(truncated.len(), 2);
+        assert!(truncated[1].is_err());
+        let truncated = split(&length_prefixed, b"\x00\x01a\x00");
+        assert_eq!(truncated.len(), 2);
+        assert!(truncated[1].is_err());
+    }
+}
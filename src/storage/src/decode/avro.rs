@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use mz_interchange::avro::Decoder;
+use mz_interchange::avro::{DecodeOptions, Decoder};
 use mz_ore::error::ErrorExt;
 use mz_repr::Row;
 use mz_storage_types::errors::DecodeErrorKind;
@@ -24,9 +24,16 @@ impl AvroDecoderState {
         ccsr_client: Option<mz_ccsr::Client>,
         debug_name: String,
         confluent_wire_format: bool,
+        options: DecodeOptions,
     ) -> Result<Self, anyhow::Error> {
         Ok(AvroDecoderState {
-            decoder: Decoder::new(value_schema, ccsr_client, debug_name, confluent_wire_format)?,
+            decoder: Decoder::new(
+                value_schema,
+                ccsr_client,
+                debug_name,
+                confluent_wire_format,
+                options,
+            )?,
             events_success: 0,
         })
     }
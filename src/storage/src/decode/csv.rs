@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use csv_core::ReadRecordResult;
 use mz_repr::{Datum, Row};
 use mz_storage_types::errors::DecodeErrorKind;
 use mz_storage_types::sources::encoding::CsvEncoding;
@@ -20,7 +21,8 @@ pub struct CsvDecoderState {
     output_cursor: usize,
     ends: Vec<usize>,
     ends_cursor: usize,
-    csv_reader: csv_core::Reader,
+    csv_reader: CsvReader,
+    embedded_newlines: bool,
     row_buf: Row,
     events_error: usize,
     events_success: usize,
@@ -32,8 +34,24 @@ impl CsvDecoderState {
     }
 
     pub fn new(format: CsvEncoding) -> Self {
-        let CsvEncoding { columns, delimiter } = format;
+        let CsvEncoding {
+            columns,
+            delimiter,
+            quote,
+            escape,
+            embedded_newlines,
+        } = format;
         let n_cols = columns.arity();
+        let csv_reader = match delimiter[..] {
+            [delimiter] => CsvReader::Core(
+                csv_core::ReaderBuilder::new()
+                    .delimiter(delimiter)
+                    .quote(quote)
+                    .escape(escape)
+                    .build(),
+            ),
+            _ => CsvReader::MultiByte(MultiByteReader::new(delimiter, quote, escape)),
+        };
 
         let header_names = columns.into_header_names();
         Self {
@@ -44,7 +62,8 @@ impl CsvDecoderState {
             output_cursor: 0,
             ends: vec![0],
             ends_cursor: 1,
-            csv_reader: csv_core::ReaderBuilder::new().delimiter(delimiter).build(),
+            csv_reader,
+            embedded_newlines,
             row_buf: Row::default(),
             events_error: 0,
             events_success: 0,
@@ -69,17 +88,17 @@ impl CsvDecoderState {
             self.ends_cursor += n_ends;
             match result {
                 // Error cases
-                csv_core::ReadRecordResult::InputEmpty => break Ok(None),
-                csv_core::ReadRecordResult::OutputFull => {
+                ReadRecordResult::InputEmpty => break Ok(None),
+                ReadRecordResult::OutputFull => {
                     let length = self.output.len();
                     self.output.extend(std::iter::repeat(0).take(length));
                 }
-                csv_core::ReadRecordResult::OutputEndsFull => {
+                ReadRecordResult::OutputEndsFull => {
                     let length = self.ends.len();
                     self.ends.extend(std::iter::repeat(0).take(length));
                 }
                 // Success cases
-                csv_core::ReadRecordResult::Record | csv_core::ReadRecordResult::End => {
+                ReadRecordResult::Record | ReadRecordResult::End => {
                     let result = {
                         let ends_valid = self.ends_cursor - 1;
                         if ends_valid == 0 {
@@ -95,6 +114,20 @@ impl CsvDecoderState {
                             )))
                         } else {
                             match std::str::from_utf8(&self.output[0..self.output_cursor]) {
+                                // Outside of quotes, newlines end the record,
+                                // so any newline in the output was quoted.
+                                Ok(output)
+                                    if !self.embedded_newlines && output.contains(['\n', '\r']) =>
+                                {
+                                    self.events_error += 1;
+                                    self.output_cursor = 0;
+                                    self.ends_cursor = 1;
+                                    Err(DecodeErrorKind::Text(format!(
+                                        "CSV error at record number {}: \
+                                         field contains an embedded newline",
+                                        self.total_events(),
+                                    )))
+                                }
                                 Ok(output) => {
                                     self.events_success += 1;
                                     let mut row_packer = self.row_buf.packer();
@@ -151,3 +184,311 @@ impl CsvDecoderState {
         }
     }
 }
+
+/// Splits CSV input into records of fields.
+#[derive(Debug)]
+enum CsvReader {
+    Core(csv_core::Reader),
+    MultiByte(MultiByteReader),
+}
+
+impl CsvReader {
+    fn read_record(
+        &mut self,
+        input: &[u8],
+        output: &mut [u8],
+        ends: &mut [usize],
+    ) -> (ReadRecordResult, usize, usize, usize) {
+        match self {
+            CsvReader::Core(reader) => reader.read_record(input, output, ends),
+            CsvReader::MultiByte(reader) => reader.read_record(input, output, ends),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MultiByteState {
+    /// Between records, skipping blank lines.
+    StartRecord,
+    StartField,
+    InField,
+    InQuotedField,
+    /// Just after an escape character in a quoted field.
+    EscapeInQuotedField,
+    /// Just after a quote character in a quoted field, which either closes
+    /// the field or, if doubled, is a literal quote.
+    QuoteInQuotedField,
+}
+
+/// A CSV reader for delimiters longer than one byte, which `csv_core` does
+/// not support.
+///
+/// It follows the same calling convention as [`csv_core::Reader::read_record`]
+/// and mirrors its default parsing rules: records end at `\r`, `\n` or
+/// `\r\n`, blank lines are skipped, and quotes within quoted fields are
+/// escaped by doubling them or by the escape character, if any.
+#[derive(Debug)]
+struct MultiByteReader {
+    delimiter: Vec<u8>,
+    quote: u8,
+    escape: Option<u8>,
+    state: MultiByteState,
+    /// How many bytes of the delimiter have been matched in an unquoted
+    /// field. These bytes have not yet been written to the output.
+    matched: usize,
+    /// How many bytes of the current record have been written to the
+    /// output, across calls.
+    record_len: usize,
+}
+
+impl MultiByteReader {
+    fn new(delimiter: Vec<u8>, quote: u8, escape: Option<u8>) -> Self {
+        assert!(!delimiter.is_empty(), "CSV delimiter must not be empty");
+        MultiByteReader {
+            delimiter,
+            quote,
+            escape,
+            state: MultiByteState::StartRecord,
+            matched: 0,
+            record_len: 0,
+        }
+    }
+
+    fn read_record(
+        &mut self,
+        input: &[u8],
+        output: &mut [u8],
+        ends: &mut [usize],
+    ) -> (ReadRecordResult, usize, usize, usize) {
+        use MultiByteState::*;
+
+        let (mut n_in, mut n_out, mut n_ends) = (0, 0, 0);
+        if input.is_empty() {
+            // As for `csv_core`, empty input signals the end of the input,
+            // which ends any record in progress.
+            if self.state == StartRecord {
+                return (ReadRecordResult::End, 0, 0, 0);
+            }
+            if output.len() < self.matched || ends.is_empty() {
+                return self.full(output, ends, 0, 0, 0);
+            }
+            self.flush_matched(output, &mut n_out);
+            self.end_field(ends, &mut n_ends);
+            self.end_record();
+            return (ReadRecordResult::Record, 0, n_out, n_ends);
+        }
+
+        while n_in < input.len() {
+            // Leave room for the largest amount of output a single byte can
+            // produce, so that no byte is ever partially processed.
+            if output.len() - n_out < self.delimiter.len() || ends.len() - n_ends < 1 {
+                return self.full(output, ends, n_in, n_out, n_ends);
+            }
+            let b = input[n_in];
+            match self.state {
+                StartRecord => {
+                    if b == b'\n' || b == b'\r' {
+                        n_in += 1;
+                    } else {
+                        self.state = StartField;
+                    }
+                }
+                StartField => {
+                    if b == self.quote {
+                        self.state = InQuotedField;
+                        n_in += 1;
+                    } else {
+                        self.state = InField;
+                    }
+                }
+                InField => {
+                    if b == b'\n' || b == b'\r' {
+                        self.flush_matched(output, &mut n_out);
+                        self.end_field(ends, &mut n_ends);
+                        self.end_record();
+                        return (ReadRecordResult::Record, n_in + 1, n_out, n_ends);
+                    }
+                    if b == self.delimiter[self.matched] {
+                        n_in += 1;
+                        self.matched += 1;
+                        if self.matched == self.delimiter.len() {
+                            self.matched = 0;
+                            self.end_field(ends, &mut n_ends);
+                            self.state = StartField;
+                        }
+                    } else if self.matched > 0 {
+                        self.rematch(output, &mut n_out);
+                    } else {
+                        self.write(output, &mut n_out, b);
+                        n_in += 1;
+                    }
+                }
+                InQuotedField => {
+                    if Some(b) == self.escape {
+                        self.state = EscapeInQuotedField;
+                    } else if b == self.quote {
+                        self.state = QuoteInQuotedField;
+                    } else {
+                        self.write(output, &mut n_out, b);
+                    }
+                    n_in += 1;
+                }
+                EscapeInQuotedField => {
+                    self.write(output, &mut n_out, b);
+                    self.state = InQuotedField;
+                    n_in += 1;
+                }
+                QuoteInQuotedField => {
+                    if b == self.quote {
+                        self.write(output, &mut n_out, b);
+                        self.state = InQuotedField;
+                        n_in += 1;
+                    } else {
+                        // The field was closed. Anything up to the next
+                        // delimiter is unquoted data.
+                        self.state = InField;
+                    }
+                }
+            }
+        }
+        (ReadRecordResult::InputEmpty, n_in, n_out, n_ends)
+    }
+
+    fn full(
+        &self,
+        output: &[u8],
+        ends: &[usize],
+        n_in: usize,
+        n_out: usize,
+        n_ends: usize,
+    ) -> (ReadRecordResult, usize, usize, usize) {
+        let result = if ends.len() - n_ends < 1 {
+            ReadRecordResult::OutputEndsFull
+        } else {
+            debug_assert!(output.len() - n_out < self.delimiter.len());
+            ReadRecordResult::OutputFull
+        };
+        (result, n_in, n_out, n_ends)
+    }
+
+    fn write(&mut self, output: &mut [u8], n_out: &mut usize, b: u8) {
+        output[*n_out] = b;
+        *n_out += 1;
+        self.record_len += 1;
+    }
+
+    /// Handles a mismatch after a partially matched delimiter. The matched
+    /// bytes were data after all: the first is output, and the rest may begin
+    /// another delimiter, so they are matched again.
+    fn rematch(&mut self, output: &mut [u8], n_out: &mut usize) {
+        let matched = std::mem::take(&mut self.matched);
+        self.write(output, n_out, self.delimiter[0]);
+        for i in 1..matched {
+            // These bytes are fewer than the delimiter, so they cannot
+            // complete it.
+            let b = self.delimiter[i];
+            while b != self.delimiter[self.matched] && self.matched > 0 {
+                self.rematch(output, n_out);
+            }
+            if b == self.delimiter[self.matched] {
+                self.matched += 1;
+            } else {
+                self.write(output, n_out, b);
+            }
+        }
+    }
+
+    /// Writes out the bytes of a partially matched delimiter.
+    fn flush_matched(&mut self, output: &mut [u8], n_out: &mut usize) {
+        for i in 0..std::mem::take(&mut self.matched) {
+            self.write(output, n_out, self.delimiter[i]);
+        }
+    }
+
+    fn end_field(&mut self, ends: &mut [usize], n_ends: &mut usize) {
+        ends[*n_ends] = self.record_len;
+        *n_ends += 1;
+    }
+
+    fn end_record(&mut self) {
+        self.state = MultiByteState::StartRecord;
+        self.matched = 0;
+        self.record_len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads all records from `input`, feeding it in chunks of `chunk` bytes
+    /// and growing the output buffers from a single element, to exercise
+    /// resumption.
+    fn read_all(reader: &mut MultiByteReader, input: &[u8], chunk: usize) -> Vec<Vec<String>> {
+        let mut records = vec![];
+        let mut output = vec![0; 1];
+        let mut ends = vec![0; 1];
+        let (mut n_out, mut n_ends) = (0, 0);
+        let mut chunks = input.chunks(chunk);
+        let mut current: &[u8] = &[];
+        loop {
+            // Once the chunks run out, the empty input signals the end.
+            if current.is_empty() {
+                current = chunks.next().unwrap_or_default();
+            }
+            let (result, n_in, o, e) =
+                reader.read_record(current, &mut output[n_out..], &mut ends[n_ends..]);
+            current = &current[n_in..];
+            n_out += o;
+            n_ends += e;
+            match result {
+                ReadRecordResult::InputEmpty => {}
+                ReadRecordResult::OutputFull => output.resize(output.len() * 2, 0),
+                ReadRecordResult::OutputEndsFull => ends.resize(ends.len() * 2, 0),
+                ReadRecordResult::Record => {
+                    let mut start = 0;
+                    let record = ends[..n_ends]
+                        .iter()
+                        .map(|&end| {
+                            let field = String::from_utf8(output[start..end].to_vec()).unwrap();
+                            start = end;
+                            field
+                        })
+                        .collect();
+                    records.push(record);
+                    (n_out, n_ends) = (0, 0);
+                }
+                ReadRecordResult::End => return records,
+            }
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_multi_byte_delimiter() {
+        let cases: &[(&str, &[u8], &[&[&str]])] = &[
+            ("||", b"a||b||c\n", &[&["a", "b", "c"]]),
+            ("||", b"a|b||c", &[&["a|b", "c"]]),
+            ("||", b"a|||b", &[&["a", "|b"]]),
+            ("||", b"||\r\n\nx", &[&["", ""], &["x"]]),
+            ("aab", b"aaab1aab2", &[&["a", "1", "2"]]),
+            ("abab", b"abababx", &[&["", "abx"]]),
+            (
+                "::",
+                b"\"a::b\"::\"c\"\"d\"::\"e\nf\"\n",
+                &[&["a::b", "c\"d", "e\nf"]],
+            ),
+            ("::", b"\"a\\\"b\"::c", &[&["a\"b", "c"]]),
+        ];
+        for (delimiter, input, expected) in cases {
+            for chunk in 1..=input.len() {
+                let mut reader =
+                    MultiByteReader::new(delimiter.as_bytes().to_vec(), b'"', Some(b'\\'));
+                let actual = read_all(&mut reader, input, chunk);
+                assert_eq!(
+                    actual, *expected,
+                    "delimiter {delimiter:?}, input {input:?}, chunk {chunk}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::adt::numeric;
+use mz_repr::{strconv, Datum, Row, RowPacker, ScalarType};
+use mz_storage_types::errors::DecodeErrorKind;
+use mz_storage_types::sources::encoding::{JsonShredColumn, JsonShredEncoding};
+
+/// Decodes JSON values, extracting the values at a set of JSONPath
+/// expressions into typed columns.
+#[derive(Debug)]
+pub struct JsonShredDecoderState {
+    columns: Vec<JsonShredColumn>,
+    row_buf: Row,
+}
+
+impl JsonShredDecoderState {
+    pub fn new(encoding: JsonShredEncoding) -> Self {
+        JsonShredDecoderState {
+            columns: encoding.columns,
+            row_buf: Row::default(),
+        }
+    }
+
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Option<Row>, DecodeErrorKind> {
+        let value: serde_json::Value = serde_json::from_slice(bytes)
+            .map_err(|e| DecodeErrorKind::Bytes(format!("Failed to decode JSON: {e}")))?;
+        let mut packer = self.row_buf.packer();
+        for column in &self.columns {
+            match column.path.lookup(&value) {
+                None | Some(serde_json::Value::Null) => packer.push(Datum::Null),
+                Some(v) => push_value(&mut packer, v, &column.typ).map_err(|e| {
+                    DecodeErrorKind::Text(format!(
+                        "Failed to decode JSON column {}: {e}",
+                        column.name
+                    ))
+                })?,
+            }
+        }
+        Ok(Some(self.row_buf.clone()))
+    }
+}
+
+/// Packs `value` as a datum of type `typ`.
+///
+/// JSON strings are converted using their contents, and all other JSON
+/// values using their JSON text, following the rules for casting from `text`.
+fn push_value(
+    packer: &mut RowPacker,
+    value: &serde_json::Value,
+    typ: &ScalarType,
+) -> Result<(), anyhow::Error> {
+    let text = match value {
+        serde_json::Value::String(s) => s.clone(),
+        v => v.to_string(),
+    };
+    let datum = match typ {
+        ScalarType::Jsonb => {
+            JsonbPacker::new(packer).pack_serde_json(value.clone())?;
+            return Ok(());
+        }
+        ScalarType::String => {
+            packer.push(Datum::String(&text));
+            return Ok(());
+        }
+        ScalarType::Bool => Datum::from(strconv::parse_bool(&text)?),
+        ScalarType::Int16 => Datum::from(strconv::parse_int16(&text)?),
+        ScalarType::Int32 => Datum::from(strconv::parse_int32(&text)?),
+        ScalarType::Int64 => Datum::from(strconv::parse_int64(&text)?),
+        ScalarType::Float32 => Datum::from(strconv::parse_float32(&text)?),
+        ScalarType::Float64 => Datum::from(strconv::parse_float64(&text)?),
+        ScalarType::Numeric { max_scale } => {
+            let mut n = strconv::parse_numeric(&text)?;
+            if let Some(scale) = max_scale {
+                numeric::rescale(&mut n.0, scale.into_u8())?;
+            }
+            Datum::from(n)
+        }
+        ScalarType::Date => Datum::from(strconv::parse_date(&text)?),
+        ScalarType::Timestamp { precision } => {
+            Datum::Timestamp(strconv::parse_timestamp(&text)?.round_to_precision(*precision)?)
+        }
+        ScalarType::TimestampTz { precision } => {
+            Datum::TimestampTz(strconv::parse_timestamptz(&text)?.round_to_precision(*precision)?)
+        }
+        ScalarType::Uuid => Datum::from(strconv::parse_uuid(&text)?),
+        typ => anyhow::bail!("unsupported type {typ:?}"),
+    };
+    packer.push(datum);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_storage_types::sources::encoding::JsonPath;
+
+    use super::*;
+
+    fn column(name: &str, path: &str, typ: ScalarType) -> JsonShredColumn {
+        JsonShredColumn {
+            name: name.into(),
+            path: JsonPath::parse(path).unwrap(),
+            typ,
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_json_shred() {
+        let mut decoder = JsonShredDecoderState::new(JsonShredEncoding {
+            columns: vec![
+                column("id", "$.id", ScalarType::Int64),
+                column("name", "$.user['full name']", ScalarType::String),
+                column("tag", "$.tags[1]", ScalarType::String),
+                column("active", "$.active", ScalarType::Bool),
+                column("extra", "$.extra", ScalarType::Jsonb),
+                column("missing", "$.missing.field", ScalarType::Int32),
+            ],
+        });
+
+        let input = br#"{"id": "42", "user": {"full name": "ann"}, "tags": ["a", 7],
+            "active": true, "extra": null}"#;
+        let row = decoder.decode(input).unwrap().unwrap();
+        assert_eq!(
+            row.unpack(),
+            vec![
+                Datum::Int64(42),
+                Datum::String("ann"),
+                Datum::String("7"),
+                Datum::True,
+                Datum::Null,
+                Datum::Null,
+            ]
+        );
+
+        let err = decoder.decode(br#"{"id": "forty-two"}"#).unwrap_err();
+        assert!(err.to_string().contains("JSON column id"), "{err}");
+        assert!(decoder.decode(b"not json").is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_json_path_parse() {
+        use mz_storage_types::sources::encoding::JsonPathElement::*;
+
+        assert_eq!(JsonPath::parse("$").unwrap(), JsonPath(vec![]));
+        assert_eq!(
+            JsonPath::parse(r#"$.a["b.c"][2]['d\'e']"#).unwrap(),
+            JsonPath(vec![
+                Field("a".into()),
+                Field("b.c".into()),
+                Index(2),
+                Field("d'e".into()),
+            ])
+        );
+        for invalid in ["a.b", "$.", "$..a", "$[x]", "$[1", "$['a'", "$ a"] {
+            assert!(JsonPath::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+}
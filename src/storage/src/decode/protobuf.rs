@@ -21,14 +21,15 @@ pub struct ProtobufDecoderState {
 }
 
 impl ProtobufDecoderState {
-    pub fn new(
-        ProtobufEncoding {
+    pub fn new(encoding: ProtobufEncoding) -> Result<Self, anyhow::Error> {
+        let options = encoding.decode_options();
+        let ProtobufEncoding {
             descriptors,
             message_name,
             confluent_wire_format,
-        }: ProtobufEncoding,
-    ) -> Result<Self, anyhow::Error> {
-        let descriptors = DecodedDescriptors::from_bytes(&descriptors, message_name)
+            ..
+        } = encoding;
+        let descriptors = DecodedDescriptors::from_bytes(&descriptors, message_name, options)
             .expect("descriptors provided to protobuf source are pre-validated");
         Ok(ProtobufDecoderState {
             decoder: Decoder::new(descriptors, confluent_wire_format)?,
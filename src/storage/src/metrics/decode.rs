@@ -40,7 +40,7 @@ impl DecodeMetricDefs {
             DataDecoderInner::DelimitedBytes { format, .. }
             | DataDecoderInner::PreDelimited(format) => match format {
                 PreDelimitedFormat::Bytes => "raw",
-                PreDelimitedFormat::Json => "json",
+                PreDelimitedFormat::Json | PreDelimitedFormat::JsonShred(..) => "json",
                 PreDelimitedFormat::Text => "text",
                 PreDelimitedFormat::Regex(..) => "regex",
                 PreDelimitedFormat::Protobuf(..) => "protobuf",
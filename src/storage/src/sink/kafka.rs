@@ -57,6 +57,11 @@
 //! eventually call `commit_transaction` which will ensure that all queued messages are
 //! successfully delivered before the transaction is reported as committed.
 //!
+//! Sinks with an at-least-once delivery guarantee skip transactions altogether. Instead they flush
+//! the send queue, failing if any message was not delivered, before and after writing each
+//! progress record. Their updates become visible sooner, but a restarted sink rewrites any updates
+//! past the last progress record.
+//!
 //! The only error that is possible during sending is that the queue is full. We are purposefully
 //! NOT handling this error and simply configure `librdkafka` with a very large queue. The reason
 //! for this choice is that the only choice for hanlding such an error ourselves would be to queue
@@ -80,8 +85,12 @@ use std::time::Duration;
 use anyhow::{anyhow, bail, Context};
 use differential_dataflow::{AsCollection, Collection, Hashable};
 use maplit::btreemap;
-use mz_interchange::avro::{AvroEncoder, AvroSchemaGenerator, AvroSchemaOptions};
+use mz_interchange::avro::{
+    encode_debezium_transaction_unchecked, get_debezium_transaction_schema, AvroEncoder,
+    AvroSchemaGenerator, AvroSchemaOptions,
+};
 use mz_interchange::encode::Encode;
+use mz_interchange::envelopes::dbz_format_transaction;
 use mz_interchange::json::JsonEncoder;
 use mz_kafka_util::client::{
     GetPartitionsError, MzClientContext, TimeoutConfig, TunnelingClientContext,
@@ -90,14 +99,16 @@ use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_ore::error::ErrorExt;
 use mz_ore::future::InTask;
+use mz_ore::num::NonNeg;
 use mz_ore::task;
 use mz_ore::vec::VecExt;
-use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
+use mz_repr::{Datum, Diff, GlobalId, Row, RowArena, Timestamp};
 use mz_storage_client::sink::progress_key::ProgressKey;
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::errors::{ContextCreationError, ContextCreationErrorExt, DataflowError};
 use mz_storage_types::sinks::{
-    KafkaSinkConnection, KafkaSinkFormat, MetadataFilled, SinkEnvelope, StorageSinkDesc,
+    KafkaSinkConnection, KafkaSinkDeliveryGuarantee, KafkaSinkFormat, KafkaSinkTopicOptions,
+    MetadataFilled, SinkEnvelope, StorageSinkDesc,
 };
 use mz_timely_util::antichain::AntichainExt;
 use mz_timely_util::builder_async::{
@@ -222,6 +233,25 @@ struct TransactionalProducer {
     socket_timeout: Duration,
     /// The maximum duration of a transaction.
     transaction_timeout: Duration,
+    /// Whether updates are committed in transactions or merely flushed.
+    delivery_guarantee: KafkaSinkDeliveryGuarantee,
+    /// Where to write Debezium transaction metadata, if the sink was configured to do so.
+    transaction_metadata: Option<TransactionMetadata>,
+    /// The number of partitions of the data topic, once known. Used to map the partition hashes
+    /// of messages to partitions.
+    partition_count: Option<u64>,
+}
+
+/// The state required to frame each timestamp written to the data topic with Debezium `BEGIN` and
+/// `END` transaction markers.
+struct TransactionMetadata {
+    /// The topic where the transaction markers go.
+    topic: String,
+    /// The ID of the transaction metadata schema in the schema registry, for Avro sinks.
+    avro_schema_id: Option<i32>,
+    /// The time of the currently open Debezium transaction and the number of events sent in it so
+    /// far.
+    open: Option<(Timestamp, i64)>,
 }
 
 impl TransactionalProducer {
@@ -241,8 +271,6 @@ impl TransactionalProducer {
             &storage_configuration.connection_context,
             sink_id,
         );
-        let transactional_id =
-            connection.transactional_id(&storage_configuration.connection_context, sink_id);
 
         let timeout_config = &storage_configuration.parameters.kafka_timeout_config;
         let mut options = BTreeMap::new();
@@ -268,13 +296,17 @@ impl TransactionalProducer {
         options.insert("queue.buffering.max.messages", format!("{}", 10_000_000));
         // Make the Kafka producer wait at least 10 ms before sending out MessageSets
         options.insert("queue.buffering.max.ms", format!("{}", 10));
-        // Time out transactions after 60 seconds
-        options.insert(
-            "transaction.timeout.ms",
-            format!("{}", timeout_config.transaction_timeout.as_millis()),
-        );
-        // Use the transactional ID requested by the user.
-        options.insert("transactional.id", transactional_id);
+        if connection.delivery_guarantee == KafkaSinkDeliveryGuarantee::ExactlyOnce {
+            let transactional_id =
+                connection.transactional_id(&storage_configuration.connection_context, sink_id);
+            // Time out transactions after 60 seconds
+            options.insert(
+                "transaction.timeout.ms",
+                format!("{}", timeout_config.transaction_timeout.as_millis()),
+            );
+            // Use the transactional ID requested by the user.
+            options.insert("transactional.id", transactional_id);
+        }
         // Allow Kafka monitoring tools to identify this producer.
         options.insert("client.id", client_id);
         // We want to be notified regularly with statistics
@@ -308,12 +340,17 @@ impl TransactionalProducer {
             staged_bytes: 0,
             socket_timeout: timeout_config.socket_timeout,
             transaction_timeout: timeout_config.transaction_timeout,
+            delivery_guarantee: connection.delivery_guarantee,
+            transaction_metadata: None,
+            partition_count: None,
         };
 
-        let timeout = timeout_config.socket_timeout;
-        producer
-            .spawn_blocking(move |p| p.init_transactions(timeout))
-            .await?;
+        if producer.delivery_guarantee == KafkaSinkDeliveryGuarantee::ExactlyOnce {
+            let timeout = timeout_config.socket_timeout;
+            producer
+                .spawn_blocking(move |p| p.init_transactions(timeout))
+                .await?;
+        }
 
         Ok(producer)
     }
@@ -340,7 +377,120 @@ impl TransactionalProducer {
     }
 
     async fn begin_transaction(&mut self) -> Result<(), ContextCreationError> {
-        self.spawn_blocking(|p| p.begin_transaction()).await
+        match self.delivery_guarantee {
+            KafkaSinkDeliveryGuarantee::ExactlyOnce => {
+                self.spawn_blocking(|p| p.begin_transaction()).await
+            }
+            KafkaSinkDeliveryGuarantee::AtLeastOnce => Ok(()),
+        }
+    }
+
+    /// Waits until every queued message has been acknowledged by the brokers, failing if any of
+    /// them could not be delivered.
+    async fn flush(&self) -> Result<(), ContextCreationError> {
+        let timeout = self.transaction_timeout;
+        self.spawn_blocking(move |p| p.flush(timeout)).await?;
+        match self.producer.context().inner().take_delivery_error() {
+            Some(err) => Err(err.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Configures the producer to write Debezium transaction markers to `topic`. Markers are
+    /// Avro-encoded with the given schema ID if one is provided and JSON-encoded otherwise.
+    fn enable_transaction_metadata(&mut self, topic: String, avro_schema_id: Option<i32>) {
+        self.transaction_metadata = Some(TransactionMetadata {
+            topic,
+            avro_schema_id,
+            open: None,
+        });
+    }
+
+    /// Puts a Debezium transaction marker for the transaction identified by `time` to librdkafka's
+    /// send queue. `event_count` must be provided for `END` markers and omitted for `BEGIN`
+    /// markers.
+    async fn send_transaction_marker(
+        &self,
+        time: Timestamp,
+        status: &str,
+        event_count: Option<i64>,
+    ) -> Result<(), ContextCreationError> {
+        let Some(metadata) = &self.transaction_metadata else {
+            return Ok(());
+        };
+        let id = time.to_string();
+        let payload = match metadata.avro_schema_id {
+            Some(schema_id) => encode_debezium_transaction_unchecked(
+                schema_id,
+                &self.data_topic,
+                &id,
+                status,
+                event_count,
+            ),
+            None => {
+                let data_collections = event_count.map(|event_count| {
+                    serde_json::json!([{
+                        "data_collection": self.data_topic,
+                        "event_count": event_count,
+                    }])
+                });
+                let marker = serde_json::json!({
+                    "id": id,
+                    "status": status,
+                    "event_count": event_count,
+                    "data_collections": data_collections,
+                });
+                serde_json::to_vec(&marker).expect("infallible")
+            }
+        };
+        let record = BaseRecord::<(), _>::to(&metadata.topic).payload(&payload);
+        match self.producer.send(record) {
+            Ok(()) => Ok(()),
+            Err((err, record)) => match err.rdkafka_error_code() {
+                Some(RDKafkaErrorCode::QueueFull) => {
+                    // If the internal rdkafka queue is full we have no other option than to flush
+                    let timeout = self.transaction_timeout;
+                    self.spawn_blocking(move |p| p.flush(timeout)).await?;
+                    self.producer.send(record).map_err(|(err, _)| err.into())
+                }
+                _ => Err(err.into()),
+            },
+        }
+    }
+
+    /// Writes the `END` marker of the currently open Debezium transaction, if any.
+    async fn end_debezium_transaction(&mut self) -> Result<(), ContextCreationError> {
+        let open = self
+            .transaction_metadata
+            .as_mut()
+            .and_then(|metadata| metadata.open.take());
+        match open {
+            Some((time, event_count)) => {
+                self.send_transaction_marker(time, "END", Some(event_count))
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Records that a message at `time` is about to be sent, writing `END` and `BEGIN` markers as
+    /// necessary when `time` starts a new Debezium transaction.
+    async fn track_debezium_transaction(
+        &mut self,
+        time: Timestamp,
+    ) -> Result<(), ContextCreationError> {
+        let Some(metadata) = &self.transaction_metadata else {
+            return Ok(());
+        };
+        if metadata.open.map(|(open_time, _)| open_time) != Some(time) {
+            self.end_debezium_transaction().await?;
+            self.send_transaction_marker(time, "BEGIN", None).await?;
+        }
+        if let Some(metadata) = &mut self.transaction_metadata {
+            let (_, event_count) = metadata.open.get_or_insert((time, 0));
+            *event_count += 1;
+        }
+        Ok(())
     }
 
     /// Synchronously puts the provided message to librdkafka's send queue. This method only
@@ -355,6 +505,7 @@ impl TransactionalProducer {
         diff: Diff,
     ) -> Result<(), ContextCreationError> {
         assert_eq!(diff, 1, "invalid sink update");
+        self.track_debezium_transaction(time).await?;
 
         let mut headers = OwnedHeaders::new().insert(Header {
             key: "materialize-timestamp",
@@ -375,12 +526,18 @@ impl TransactionalProducer {
                 value: header.value.as_ref(),
             });
         }
+        let partition = message.hash.map(|hash| {
+            let partition_count = self
+                .partition_count
+                .expect("partition count known before sending messages");
+            i32::try_from(hash % partition_count).expect("partition count fits in i32")
+        });
         let record = BaseRecord {
             topic: &self.data_topic,
             key: message.key.as_ref(),
             payload: message.value.as_ref(),
             headers: Some(headers),
-            partition: None,
+            partition,
             timestamp: None,
             delivery_opaque: (),
         };
@@ -418,6 +575,16 @@ impl TransactionalProducer {
         &mut self,
         upper: Antichain<Timestamp>,
     ) -> Result<(), ContextCreationError> {
+        // Updates are sent in time order, so every Debezium transaction ends no later than the
+        // Kafka transaction that contains it.
+        self.end_debezium_transaction().await?;
+
+        if self.delivery_guarantee == KafkaSinkDeliveryGuarantee::AtLeastOnce {
+            // Without a transaction, the progress record must not be written until the updates
+            // it covers are durable. Otherwise a restart could skip over them.
+            self.flush().await?;
+        }
+
         let progress = ProgressRecord {
             frontier: upper.into(),
             version: self.sink_version,
@@ -441,6 +608,16 @@ impl TransactionalProducer {
             },
         }
 
+        if self.delivery_guarantee == KafkaSinkDeliveryGuarantee::AtLeastOnce {
+            self.flush().await?;
+            self.statistics
+                .inc_messages_committed_by(self.staged_messages);
+            self.statistics.inc_bytes_committed_by(self.staged_bytes);
+            self.staged_messages = 0;
+            self.staged_bytes = 0;
+            return Ok(());
+        }
+
         let timeout = self.socket_timeout;
         match self
             .spawn_blocking(move |p| p.commit_transaction(timeout))
@@ -528,6 +705,9 @@ struct KafkaMessage {
     value: Option<Vec<u8>>,
     /// Message headers.
     headers: Vec<KafkaHeader>,
+    /// The hash used to select the partition of the message. When absent, the partition is
+    /// chosen by the producer's default partitioner.
+    hash: Option<u64>,
 }
 
 /// A header to attach to a Kafka message.
@@ -589,7 +769,8 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
             )
             .await?;
             // Instantiating the transactional producer fences out all previous ones, making it
-            // safe to determine the resume upper.
+            // safe to determine the resume upper. At-least-once sinks are not fenced, which is
+            // fine because they tolerate rewriting updates that a previous instance wrote.
             let progress = determine_sink_progress(
                 sink_id,
                 &connection,
@@ -617,16 +798,59 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                         &connection.topic_options,
                     )
                     .await?;
+                    if let Some(topic) = &connection.transaction_metadata_topic {
+                        // Consumers rely on observing the markers in the same order as the data
+                        // so the transaction metadata topic MUST have a single partition.
+                        let options = KafkaSinkTopicOptions {
+                            partition_count: Some(NonNeg::try_from(1).expect("1 is positive")),
+                            replication_factor: connection.topic_options.replication_factor,
+                            topic_config: BTreeMap::new(),
+                        };
+                        mz_storage_client::sink::ensure_kafka_topic(
+                            &connection,
+                            &storage_configuration,
+                            topic,
+                            &options,
+                        )
+                        .await?;
+                    }
                     Antichain::from_elem(Timestamp::minimum())
                 }
             };
 
+            if let Some(topic) = &connection.transaction_metadata_topic {
+                let avro_schema_id = match &connection.format {
+                    KafkaSinkFormat::Avro { csr_connection, .. } => {
+                        let ccsr = csr_connection
+                            .connect(&storage_configuration, InTask::Yes)
+                            .await?;
+                        let (_, schema_id) = mz_storage_client::sink::publish_kafka_schemas(
+                            ccsr,
+                            topic.clone(),
+                            None,
+                            None,
+                            get_debezium_transaction_schema().to_string(),
+                            mz_ccsr::SchemaType::Avro,
+                            None,
+                            None,
+                            false,
+                        )
+                        .await
+                        .context("error publishing transaction metadata schema for sink")?;
+                        Some(schema_id)
+                    }
+                    KafkaSinkFormat::Json => None,
+                };
+                producer.enable_transaction_metadata(topic.clone(), avro_schema_id);
+            }
+
             // At this point the topic must exist and so we can query for its metadata.
             let meta = producer.fetch_metadata().await?;
             match meta.topics().iter().find(|t| t.name() == &connection.topic) {
                 Some(topic) => {
                     let partition_count = u64::cast_from(topic.partitions().len());
                     metrics.partition_count.set(partition_count);
+                    producer.partition_count = Some(partition_count);
                 }
                 None => return Err(anyhow!("sink data topic is missing").into()),
             }
@@ -1143,7 +1367,7 @@ fn parse_progress_record(payload: &[u8]) -> Result<ProgressRecord, anyhow::Error
 /// Encodes a stream of `(Option<Row>, Option<Row>)` updates using the specified encoder.
 ///
 /// Input [`Row`] updates must me compatible with the given implementor of [`Encode`].
-fn encode_collection<G: Scope>(
+fn encode_collection<G: Scope<Timestamp = Timestamp>>(
     name: String,
     input: &Collection<G, (Option<Row>, Option<Row>), Diff>,
     envelope: SinkEnvelope,
@@ -1167,6 +1391,7 @@ fn encode_collection<G: Scope>(
                 .as_ref()
                 .map(|(desc, _indices)| desc.clone());
             let value_desc = connection.value_desc;
+            let debezium_transaction = connection.transaction_metadata_topic.is_some();
 
             let encoder: Box<dyn Encode> = match connection.format {
                 KafkaSinkFormat::Avro {
@@ -1175,6 +1400,7 @@ fn encode_collection<G: Scope>(
                     csr_connection,
                     key_compatibility_level,
                     value_compatibility_level,
+                    schema_references,
                 } => {
                     // Ensure that schemas are registered with the schema registry.
                     //
@@ -1193,12 +1419,14 @@ fn encode_collection<G: Scope>(
                             mz_ccsr::SchemaType::Avro,
                             key_compatibility_level,
                             value_compatibility_level,
+                            schema_references,
                         )
                         .await
                         .context("error publishing kafka schemas for sink")?;
 
                     let options = AvroSchemaOptions {
                         is_debezium: matches!(envelope, SinkEnvelope::Debezium),
+                        debezium_transaction,
                         ..Default::default()
                     };
 
@@ -1214,6 +1442,7 @@ fn encode_collection<G: Scope>(
                     key_desc,
                     value_desc,
                     matches!(envelope, SinkEnvelope::Debezium),
+                    debezium_transaction,
                 )),
             };
 
@@ -1224,6 +1453,7 @@ fn encode_collection<G: Scope>(
             // TODO(petrosagg): Make the fallible async operator safe
             *capset = CapabilitySet::new();
 
+            let mut row_buf = Row::default();
             while let Some(event) = input.next().await {
                 if let Event::Data(cap, rows) = event {
                     for ((key, value), time, diff) in rows {
@@ -1231,12 +1461,37 @@ fn encode_collection<G: Scope>(
                             (Some(i), Some(v)) => encode_headers(v.iter().nth(i).unwrap()),
                             _ => vec![],
                         };
+                        let hash = match (&connection.partition_by, &key) {
+                            (Some(partition_by), Some(key)) => {
+                                let arena = RowArena::new();
+                                let datums: Vec<_> = key.iter().collect();
+                                match partition_by.eval(&datums, &arena) {
+                                    Ok(Datum::UInt64(hash)) => Some(hash),
+                                    // NULLs and evaluation errors fall back to the default
+                                    // partitioner.
+                                    _ => None,
+                                }
+                            }
+                            _ => None,
+                        };
                         let key = key.map(|key| encoder.encode_key_unchecked(key));
-                        let value = value.map(|value| encoder.encode_value_unchecked(value));
+                        let value = value.map(|value| {
+                            if debezium_transaction {
+                                // Each timestamp is a Debezium transaction, identified by the
+                                // timestamp itself.
+                                let mut packer = row_buf.packer();
+                                packer.extend_by_row(&value);
+                                dbz_format_transaction(&mut packer, &time.to_string());
+                                encoder.encode_value_unchecked(row_buf.clone())
+                            } else {
+                                encoder.encode_value_unchecked(value)
+                            }
+                        });
                         let message = KafkaMessage {
                             key,
                             value,
                             headers,
+                            hash,
                         };
                         output.give(&cap, (message, time, diff)).await;
                     }
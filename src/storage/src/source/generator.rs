@@ -28,6 +28,7 @@ use crate::source::{RawSourceCreationConfig, SourceMessage, SourceReaderError};
 
 mod auction;
 mod counter;
+mod custom;
 mod datums;
 mod key_value;
 mod marketing;
@@ -35,6 +36,7 @@ mod tpch;
 
 pub use auction::Auction;
 pub use counter::Counter;
+pub use custom::Custom;
 pub use datums::Datums;
 pub use tpch::Tpch;
 
@@ -112,6 +114,19 @@ impl GeneratorKind {
                 up_to,
                 required_exports,
             },
+            LoadGenerator::Custom(custom) => {
+                let (batch_size, tick) = custom.batch_size_and_tick();
+                GeneratorKind::Simple {
+                    generator: Box::new(Custom {
+                        generator: custom.clone(),
+                        batch_size,
+                    }),
+                    tick_micros: Some(u64::try_from(tick.as_micros()).expect("tick fits in u64")),
+                    as_of,
+                    up_to,
+                    required_exports,
+                }
+            }
             LoadGenerator::KeyValue(kv) => {
                 mz_ore::soft_assert_eq_or_log!(
                     required_exports,
@@ -0,0 +1,100 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use mz_ore::cast::{CastFrom, CastLossy};
+use mz_ore::now::NowFn;
+use mz_repr::{Datum, Row};
+use mz_storage_types::sources::load_generator::{
+    CustomLoadGenerator, Event, Generator, ValueDistribution,
+};
+use mz_storage_types::sources::MzOffset;
+use rand::distributions::{Alphanumeric, DistString};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+pub struct Custom {
+    pub generator: CustomLoadGenerator,
+    /// The number of rows emitted at each offset.
+    pub batch_size: u64,
+}
+
+impl Custom {
+    /// Produces the row with sequence number `n`.
+    ///
+    /// Rows are a pure function of the seed and `n`, which lets us reproduce
+    /// a row in order to retract it, and to resume at any offset.
+    fn row(&self, n: u64) -> Row {
+        let mut rng =
+            SmallRng::seed_from_u64(self.generator.seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ n);
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        for column in &self.generator.columns {
+            match &column.distribution {
+                ValueDistribution::Sequence => {
+                    packer.push(Datum::Int64(i64::try_from(n).unwrap()));
+                }
+                ValueDistribution::Key => {
+                    let keys = self.generator.keys.expect("validated during planning");
+                    packer.push(Datum::Int64(i64::try_from(n % keys).unwrap()));
+                }
+                ValueDistribution::Uniform { min, max } => {
+                    packer.push(Datum::Int64(rng.gen_range(*min..=*max)));
+                }
+                ValueDistribution::Normal { mean, stddev } => {
+                    // Box-Muller transform. `1 - gen()` lies in (0, 1], which
+                    // keeps the logarithm finite.
+                    let u1: f64 = 1.0 - rng.gen::<f64>();
+                    let u2: f64 = rng.gen();
+                    let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+                    let value = f64::cast_lossy(*mean) + f64::cast_lossy(*stddev) * z;
+                    packer.push(Datum::Float64(value.into()));
+                }
+                ValueDistribution::Text { len } => {
+                    let value = Alphanumeric.sample_string(&mut rng, usize::cast_from(*len));
+                    packer.push(Datum::String(&value));
+                }
+            }
+        }
+        row
+    }
+}
+
+impl Generator for Custom {
+    fn by_seed(
+        &self,
+        _now: NowFn,
+        _seed: Option<u64>,
+        resume_offset: MzOffset,
+    ) -> Box<(dyn Iterator<Item = (usize, Event<Option<MzOffset>, (Row, i64)>)>)> {
+        let custom = Custom {
+            generator: self.generator.clone(),
+            batch_size: self.batch_size,
+        };
+
+        Box::new((resume_offset.offset..).flat_map(move |offset| {
+            let mut events = Vec::new();
+            for i in 0..custom.batch_size {
+                let n = offset * custom.batch_size + i;
+                // Once the keyspace is exhausted, each row replaces the one
+                // emitted `keys` rows earlier. (If this retraction behavior is
+                // changed, `LoadGenerator::is_monotonic` must be updated.)
+                if let Some(keys) = custom.generator.keys {
+                    if n >= keys {
+                        let retraction = (custom.row(n - keys), -1);
+                        events.push((0, Event::Message(MzOffset::from(offset), retraction)));
+                    }
+                }
+                let insertion = (custom.row(n), 1);
+                events.push((0, Event::Message(MzOffset::from(offset), insertion)));
+            }
+            events.push((0, Event::Progress(Some(MzOffset::from(offset + 1)))));
+            events
+        }))
+    }
+}
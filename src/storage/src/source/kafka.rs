@@ -13,7 +13,7 @@ use std::convert::Infallible;
 use std::str::{self};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
 use chrono::{DateTime, NaiveDateTime};
@@ -21,12 +21,14 @@ use differential_dataflow::{AsCollection, Collection};
 use futures::StreamExt;
 use maplit::btreemap;
 use mz_kafka_util::client::{get_partitions, MzClientContext, PartitionId, TunnelingClientContext};
+use mz_ore::cast::CastLossy;
 use mz_ore::error::ErrorExt;
 use mz_ore::future::InTask;
 use mz_ore::thread::{JoinHandleExt, UnparkOnDropHandle};
 use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::{adt::jsonb::Jsonb, Datum, Diff, GlobalId, Row};
 use mz_ssh_util::tunnel::SshTunnelStatus;
+use mz_storage_types::connections::SecretsReaderExt;
 use mz_storage_types::errors::ContextCreationError;
 use mz_storage_types::sources::kafka::{
     KafkaMetadataKind, KafkaSourceConnection, KafkaTimestamp, RangeBound,
@@ -35,6 +37,7 @@ use mz_storage_types::sources::{MzOffset, SourceTimestamp};
 use mz_timely_util::antichain::AntichainExt;
 use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
 use mz_timely_util::order::Partitioned;
+use openssl::symm::{decrypt_aead, Cipher};
 use rdkafka::client::Client;
 use rdkafka::consumer::base_consumer::PartitionQueue;
 use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
@@ -50,7 +53,7 @@ use timely::progress::Antichain;
 use timely::progress::Timestamp;
 use timely::PartialOrder;
 use tokio::sync::Notify;
-use tracing::{error, info, trace, warn};
+use tracing::{debug, error, info, trace, warn};
 
 use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
 use crate::metrics::source::kafka::KafkaSourceMetrics;
@@ -106,6 +109,12 @@ pub struct KafkaSourceReader {
     partition_capabilities: BTreeMap<PartitionId, PartitionCapability>,
     /// Timeout when fast-forwarding the consumer.
     consumer_seek_timeout: Duration,
+    /// Throttles the rate at which messages are read from the partition
+    /// queues, if the user requested a `MAX INGEST RATE`.
+    rate_limiter: Option<IngestRateLimiter>,
+    /// Decrypts message values before they are emitted, if the user
+    /// requested a `VALUE DECRYPTION KEY`.
+    value_decryptor: Option<Arc<ValueDecryptor>>,
 }
 
 /// A partially-filled version of `ProgressStatisticsUpdate`. This allows us to
@@ -123,6 +132,109 @@ struct PartitionCapability {
     progress: Capability<KafkaTimestamp>,
 }
 
+/// A token bucket that limits the number of records a single worker ingests
+/// per second.
+///
+/// The bucket holds at most one second's worth of tokens, so a source that
+/// has been idle can burst up to its per-second rate but no further.
+struct IngestRateLimiter {
+    /// The number of records this worker may ingest per second.
+    records_per_sec: f64,
+    /// The number of records that may currently be ingested.
+    tokens: f64,
+    /// The last time `tokens` was refilled.
+    last_refill: Instant,
+}
+
+impl IngestRateLimiter {
+    /// Creates a limiter that enforces this worker's share of the
+    /// source-wide `max_ingest_rate`.
+    fn new(max_ingest_rate: u64, worker_count: usize) -> Self {
+        // A worker never gets less than one record per second, even if there
+        // are more workers than the rate allows for.
+        let records_per_sec =
+            (f64::cast_lossy(max_ingest_rate) / f64::cast_lossy(worker_count)).max(1.0);
+        IngestRateLimiter {
+            records_per_sec,
+            tokens: records_per_sec,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.records_per_sec).min(self.records_per_sec);
+        self.last_refill = now;
+    }
+
+    /// Takes a token for a single record, returning `false` if the worker
+    /// has exhausted its budget and must wait before ingesting more.
+    fn try_acquire(&mut self) -> bool {
+        self.refill();
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns how long the worker must wait until a token becomes available.
+    fn time_until_available(&self) -> Duration {
+        let missing = (1.0 - self.tokens).max(0.0);
+        Duration::from_secs_f64(missing / self.records_per_sec)
+    }
+}
+
+/// Decrypts message values that were encrypted with AES-GCM.
+///
+/// An encrypted value consists of a 12-byte nonce, followed by the ciphertext,
+/// followed by a 16-byte authentication tag. No additional authenticated data
+/// is used.
+struct ValueDecryptor {
+    cipher: Cipher,
+    key: Vec<u8>,
+}
+
+impl ValueDecryptor {
+    const NONCE_LEN: usize = 12;
+    const TAG_LEN: usize = 16;
+
+    /// Creates a decryptor for the given key, whose length selects between
+    /// AES-128, AES-192, and AES-256.
+    fn new(key: Vec<u8>) -> Result<Self, anyhow::Error> {
+        let cipher = match key.len() {
+            16 => Cipher::aes_128_gcm(),
+            24 => Cipher::aes_192_gcm(),
+            32 => Cipher::aes_256_gcm(),
+            len => anyhow::bail!(
+                "decryption key must be 16, 24, or 32 bytes long, but is {len} bytes long"
+            ),
+        };
+        Ok(ValueDecryptor { cipher, key })
+    }
+
+    fn decrypt(&self, value: &[u8]) -> Result<Vec<u8>, KafkaMessageError> {
+        if value.len() < Self::NONCE_LEN + Self::TAG_LEN {
+            return Err(KafkaMessageError::Decryption {
+                reason: format!(
+                    "value is {} bytes long, which is too short to contain a nonce and authentication tag",
+                    value.len()
+                ),
+            });
+        }
+        let (nonce, rest) = value.split_at(Self::NONCE_LEN);
+        let (ciphertext, tag) = rest.split_at(rest.len() - Self::TAG_LEN);
+        decrypt_aead(self.cipher, &self.key, Some(nonce), &[], ciphertext, tag).map_err(|_| {
+            // OpenSSL does not distinguish a wrong key from a tampered value.
+            KafkaMessageError::Decryption {
+                reason: "authentication failed".into(),
+            }
+        })
+    }
+}
+
 /// Represents the low and high watermark offsets of a Kafka partition.
 #[derive(Debug)]
 struct WatermarkOffsets {
@@ -190,6 +302,8 @@ impl SourceRender for KafkaSourceConnection {
                 topic_metadata_refresh_interval,
                 start_offsets,
                 metadata_columns,
+                max_ingest_rate,
+                value_decryption_key,
                 // Exhaustive match protects against forgetting to apply an
                 // option. Ignored fields are justified below.
                 connection_id: _,   // not needed here
@@ -329,6 +443,45 @@ impl SourceRender for KafkaSourceConnection {
                 }
             };
 
+            let value_decryptor = match value_decryption_key {
+                None => None,
+                Some(id) => {
+                    let decryptor = config
+                        .config
+                        .connection_context
+                        .secrets_reader
+                        .read_in_task_if(InTask::Yes, id)
+                        .await
+                        .and_then(ValueDecryptor::new);
+                    match decryptor {
+                        Ok(decryptor) => Some(Arc::new(decryptor)),
+                        Err(e) => {
+                            let update = HealthStatusUpdate::halting(
+                                format!(
+                                    "failed loading value decryption key: {}",
+                                    e.display_with_causes()
+                                ),
+                                None,
+                            );
+                            health_output
+                                .give(
+                                    &health_cap,
+                                    HealthStatusMessage {
+                                        index: 0,
+                                        namespace: Self::STATUS_NAMESPACE.clone(),
+                                        update,
+                                    },
+                                )
+                                .await;
+                            // IMPORTANT: wedge forever until the `SuspendAndRestart` is processed,
+                            // for the same reason as above.
+                            std::future::pending::<()>().await;
+                            unreachable!("pending future never returns");
+                        }
+                    }
+                }
+            };
+
             // Note that we wait for this AFTER we downgrade to the source `resume_upper`. This
             // allows downstream operators (namely, the `reclock_operator`) to downgrade to the
             // `resume_upper`, which is necessary for this basic form of backpressure to work.
@@ -468,6 +621,9 @@ impl SourceRender for KafkaSourceConnection {
                 partition_capabilities,
                 consumer_seek_timeout: mz_storage_types::dyncfgs::KAFKA_FAST_FORWARD_SEEK_TIMEOUT
                     .get(config.config.config_set()),
+                rate_limiter: max_ingest_rate
+                    .map(|rate| IngestRateLimiter::new(rate, config.worker_count)),
+                value_decryptor,
             };
 
             let offset_committer = KafkaResumeUpperProcessor {
@@ -651,8 +807,11 @@ impl SourceRender for KafkaSourceConnection {
                                 .await;
                         }
                         Ok(message) => {
-                            let (message, ts) =
-                                construct_source_message(&message, &reader.metadata_columns);
+                            let (message, ts) = construct_source_message(
+                                &message,
+                                &reader.metadata_columns,
+                                reader.value_decryptor.as_deref(),
+                            );
                             if let Some((msg, time, diff)) = reader.handle_message(message, ts) {
                                 let pid = time.interval().singleton().unwrap().unwrap_exact();
                                 let part_cap = &reader.partition_capabilities[pid].data;
@@ -668,8 +827,20 @@ impl SourceRender for KafkaSourceConnection {
 
                 // Take the consumers temporarily to get around borrow checker errors
                 let mut consumers = std::mem::take(&mut reader.partition_consumers);
+                let mut throttled = false;
                 for consumer in consumers.iter_mut() {
-                    while let Some(message) = consumer.get_next_message().transpose() {
+                    loop {
+                        // Messages we don't read stay buffered in librdkafka, which stops
+                        // fetching from the broker once its queues are full.
+                        if let Some(limiter) = reader.rate_limiter.as_mut() {
+                            if !limiter.try_acquire() {
+                                throttled = true;
+                                break;
+                            }
+                        }
+                        let Some(message) = consumer.get_next_message().transpose() else {
+                            break;
+                        };
                         let message = match message {
                             Ok((msg, ts)) => Ok(reader.handle_message(msg, ts)),
                             Err(err) => Err(err),
@@ -712,6 +883,19 @@ impl SourceRender for KafkaSourceConnection {
                         }
                     }
                 }
+                if throttled {
+                    // Start from a different partition next time so that the first partition
+                    // doesn't consume the entire budget.
+                    if !consumers.is_empty() {
+                        consumers.rotate_left(1);
+                    }
+                    debug!(
+                        source_id = config.id.to_string(),
+                        worker_id = config.worker_id,
+                        num_workers = config.worker_count,
+                        "kafka source throttled by MAX INGEST RATE"
+                    );
+                }
                 // We can now put them back
                 assert!(reader.partition_consumers.is_empty());
                 reader.partition_consumers = consumers;
@@ -824,11 +1008,22 @@ impl SourceRender for KafkaSourceConnection {
                     }
                 }
 
+                // If we ran out of ingest budget there are already messages waiting for us,
+                // so instead of waiting to be notified by librdkafka we sleep until the budget
+                // has been replenished.
+                let throttle_time = match (throttled, reader.rate_limiter.as_ref()) {
+                    (true, Some(limiter)) => Some(limiter.time_until_available()),
+                    _ => None,
+                };
+
                 // Wait to be notified while also making progress with offset committing
                 tokio::select! {
                     // TODO(petrosagg): remove the timeout and rely purely on librdkafka waking us
                     // up
-                    _  = tokio::time::timeout(max_wait_time, notificator.notified()) => {},
+                    _  = tokio::time::timeout(max_wait_time, notificator.notified()),
+                        if throttle_time.is_none() => {},
+                    _ = tokio::time::sleep(throttle_time.unwrap_or_default()),
+                        if throttle_time.is_some() => {},
                     // This future is not cancel safe but we are only passing a reference to it in
                     // the select! loop so the future stays on the stack and never gets cancelled
                     // until the end of the function.
@@ -961,6 +1156,7 @@ impl KafkaSourceReader {
             partition_id,
             partition_queue,
             self.metadata_columns.clone(),
+            self.value_decryptor.clone(),
         ));
         assert_eq!(
             self.consumer
@@ -1065,10 +1261,10 @@ impl KafkaSourceReader {
     /// past the expected offset and seeks the consumer if it is not.
     fn handle_message(
         &mut self,
-        message: Result<SourceMessage, KafkaHeaderParseError>,
+        message: Result<SourceMessage, KafkaMessageError>,
         (partition, offset): (PartitionId, MzOffset),
     ) -> Option<(
-        Result<SourceMessage, KafkaHeaderParseError>,
+        Result<SourceMessage, KafkaMessageError>,
         KafkaTimestamp,
         Diff,
     )> {
@@ -1124,8 +1320,9 @@ impl KafkaSourceReader {
 fn construct_source_message(
     msg: &BorrowedMessage<'_>,
     metadata_columns: &[KafkaMetadataKind],
+    value_decryptor: Option<&ValueDecryptor>,
 ) -> (
-    Result<SourceMessage, KafkaHeaderParseError>,
+    Result<SourceMessage, KafkaMessageError>,
     (PartitionId, MzOffset),
 ) {
     let pid = msg.partition();
@@ -1157,6 +1354,14 @@ fn construct_source_message(
                     .into();
                 packer.push(d)
             }
+            KafkaMetadataKind::TimestampType => {
+                let d = match msg.timestamp() {
+                    rdkafka::Timestamp::CreateTime(_) => Datum::String("create_time"),
+                    rdkafka::Timestamp::LogAppendTime(_) => Datum::String("log_append_time"),
+                    rdkafka::Timestamp::NotAvailable => Datum::Null,
+                };
+                packer.push(d)
+            }
             KafkaMetadataKind::Header { key, use_bytes } => {
                 match msg.headers() {
                     Some(headers) => {
@@ -1171,7 +1376,7 @@ fn construct_source_message(
                                     } else {
                                         match str::from_utf8(v) {
                                             Ok(str) => Ok(Datum::String(str)),
-                                            Err(_) => Err(KafkaHeaderParseError::Utf8Error {
+                                            Err(_) => Err(KafkaMessageError::Utf8Error {
                                                 key: key.clone(),
                                                 raw: v.to_vec(),
                                             }),
@@ -1180,9 +1385,7 @@ fn construct_source_message(
                                 }
                                 None => Ok(Datum::Null),
                             })
-                            .unwrap_or(Err(KafkaHeaderParseError::KeyNotFound {
-                                key: key.clone(),
-                            }));
+                            .unwrap_or(Err(KafkaMessageError::KeyNotFound { key: key.clone() }));
                         match d {
                             Ok(d) => packer.push(d),
                             //abort with a definite error when the header is not found or cannot be parsed correctly
@@ -1210,6 +1413,25 @@ fn construct_source_message(
                     }
                 });
             }
+            KafkaMetadataKind::HeadersMap => {
+                // Maps must be packed with sorted, unique keys. Later headers win, matching
+                // the behavior of `INCLUDE HEADER`.
+                let mut headers = BTreeMap::new();
+                if let Some(msg_headers) = msg.headers() {
+                    for header in msg_headers.iter() {
+                        headers.insert(header.key, header.value);
+                    }
+                }
+                packer.push_dict_with(|r| {
+                    for (key, value) in headers {
+                        r.push(Datum::String(key));
+                        r.push(match value {
+                            Some(v) => Datum::Bytes(v),
+                            None => Datum::Null,
+                        });
+                    }
+                });
+            }
         }
     }
 
@@ -1217,9 +1439,14 @@ fn construct_source_message(
         Some(bytes) => Row::pack([Datum::Bytes(bytes)]),
         None => Row::pack([Datum::Null]),
     };
-    let value = match msg.payload() {
-        Some(bytes) => Row::pack([Datum::Bytes(bytes)]),
-        None => Row::pack([Datum::Null]),
+    let value = match (msg.payload(), value_decryptor) {
+        (Some(bytes), Some(decryptor)) => match decryptor.decrypt(bytes) {
+            Ok(bytes) => Row::pack([Datum::Bytes(&bytes)]),
+            Err(err) => return (Err(err), (pid, offset.into())),
+        },
+        (Some(bytes), None) => Row::pack([Datum::Bytes(bytes)]),
+        // Tombstones are not encrypted.
+        (None, _) => Row::pack([Datum::Null]),
     };
     (
         Ok(SourceMessage {
@@ -1239,6 +1466,8 @@ struct PartitionConsumer {
     partition_queue: PartitionQueue<TunnelingClientContext<GlueConsumerContext>>,
     /// Additional metadata columns requested by the user
     metadata_columns: Vec<KafkaMetadataKind>,
+    /// Decrypts message values, if requested by the user
+    value_decryptor: Option<Arc<ValueDecryptor>>,
 }
 
 impl PartitionConsumer {
@@ -1247,11 +1476,13 @@ impl PartitionConsumer {
         pid: PartitionId,
         partition_queue: PartitionQueue<TunnelingClientContext<GlueConsumerContext>>,
         metadata_columns: Vec<KafkaMetadataKind>,
+        value_decryptor: Option<Arc<ValueDecryptor>>,
     ) -> Self {
         PartitionConsumer {
             pid,
             partition_queue,
             metadata_columns,
+            value_decryptor,
         }
     }
 
@@ -1265,14 +1496,18 @@ impl PartitionConsumer {
         &mut self,
     ) -> Result<
         Option<(
-            Result<SourceMessage, KafkaHeaderParseError>,
+            Result<SourceMessage, KafkaMessageError>,
             (PartitionId, MzOffset),
         )>,
         KafkaError,
     > {
         match self.partition_queue.poll(Duration::from_millis(0)) {
             Some(Ok(msg)) => {
-                let (msg, ts) = construct_source_message(&msg, &self.metadata_columns);
+                let (msg, ts) = construct_source_message(
+                    &msg,
+                    &self.metadata_columns,
+                    self.value_decryptor.as_deref(),
+                );
                 assert_eq!(ts.0, self.pid);
                 Ok(Some((msg, ts)))
             }
@@ -1442,9 +1677,11 @@ fn fetch_partition_info<C: ClientContext>(
 }
 
 #[derive(Debug, thiserror::Error)]
-pub enum KafkaHeaderParseError {
+pub enum KafkaMessageError {
     #[error("A header with key '{key}' was not found in the message headers")]
     KeyNotFound { key: String },
     #[error("Found ill-formed byte sequence in header '{key}' that cannot be decoded as valid utf-8 (original bytes: {raw:x?})")]
     Utf8Error { key: String, raw: Vec<u8> },
+    #[error("Failed to decrypt message value: {reason}")]
+    Decryption { reason: String },
 }
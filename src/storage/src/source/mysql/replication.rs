@@ -91,8 +91,8 @@ static REPL_READER: &str = "reader";
 
 /// A constant arbitrary offset to add to the source-id to
 /// produce a deterministic server-id for identifying Materialize
-/// as a replica on the upstream MySQL server.
-/// TODO(roshan): Add user-facing documentation for this
+/// as a replica on the upstream MySQL server. This is documented
+/// in the user-facing `CREATE SOURCE: MySQL` reference.
 static REPLICATION_SERVER_ID_OFFSET: u32 = 524000;
 
 /// Renders the replication dataflow. See the module documentation for more
@@ -254,7 +254,9 @@ where
     let thin_input = upsert_thinning(input);
 
     if let Some(scratch_directory) = instance_context.scratch_directory.as_ref() {
-        let tuning = dataflow_paramters.upsert_rocksdb_tuning_config.clone();
+        let tuning = dataflow_paramters
+            .upsert_rocksdb_tuning_config
+            .with_overrides(&upsert_envelope.rocksdb_tuning);
 
         let allow_auto_spill = storage_configuration
             .parameters
@@ -88,16 +88,7 @@ impl Transcoder {
             Transcoder::ConfluentAvro { schema, schema_id } => {
                 if let Some(val) = Self::decode_json(row)? {
                     let val = avro::from_json(&val, schema.top_node())?;
-                    let mut out = vec![];
-                    // The first byte is a magic byte (0) that indicates the Confluent
-                    // serialization format version, and the next four bytes are a
-                    // 32-bit schema ID.
-                    //
-                    // https://docs.confluent.io/3.3.0/schema-registry/docs/serializer-formatter.html#wire-format
-                    out.write_u8(0).unwrap();
-                    out.write_i32::<NetworkEndian>(*schema_id).unwrap();
-                    out.extend(avro::to_avro_datum(schema, val)?);
-                    Ok(Some(out))
+                    Ok(Some(avro::to_confluent_datum(*schema_id, schema, val)?))
                 } else {
                     Ok(None)
                 }
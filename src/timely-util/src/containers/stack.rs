@@ -10,13 +10,19 @@
 //! A chunked columnar container based on the columnation library. It stores the local
 //! portion in region-allocated data, too, which is different to the `TimelyStack` type.
 
+use std::cell::Cell;
 use std::collections::Bound;
 use std::ops::{Index, RangeBounds};
 use std::sync::atomic::AtomicBool;
 
+use differential_dataflow::logging::Logger;
 use differential_dataflow::trace::implementations::BatchContainer;
+use differential_dataflow::trace::{Batcher, Builder};
 use either::Either;
+use timely::communication::message::RefOrMut;
 use timely::container::PushInto;
+use timely::progress::frontier::AntichainRef;
+use timely::progress::Antichain;
 use timely::{
     container::columnation::{Columnation, Region, TimelyStack},
     Container,
@@ -26,11 +32,23 @@ use crate::containers::array::Array;
 
 static ENABLE_CHUNKED_STACK: AtomicBool = AtomicBool::new(false);
 
+thread_local! {
+    /// The runtime chunk size for [`ChunkedStack`]s created on this thread, in number of
+    /// elements. Zero selects the compile-time maximum of the stack.
+    static CHUNK_SIZE: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The default maximum chunk size of a [`ChunkedStack`], in number of elements.
+pub const DEFAULT_CHUNK: usize = 64 << 10;
+
 /// A runtime-configurable wrapper around timely stacks and chunked stacks.
+///
+/// The `CHUNK` parameter is the maximum chunk size of the chunked stack implementation, and
+/// must be a power of two.
 #[derive(Clone)]
-pub enum StackWrapper<T: Columnation> {
+pub enum StackWrapper<T: Columnation, const CHUNK: usize = DEFAULT_CHUNK> {
     Legacy(TimelyStack<T>),
-    Chunked(ChunkedStack<T>),
+    Chunked(ChunkedStack<T, CHUNK>),
 }
 
 /// Runtime switch to select the stack implementation. `true` to use [`ChunkedStack`],
@@ -39,7 +57,72 @@ pub fn use_chunked_stack(enable: bool) {
     ENABLE_CHUNKED_STACK.store(enable, std::sync::atomic::Ordering::Relaxed);
 }
 
-impl<T: Columnation> StackWrapper<T> {
+/// Runs `f` with the chunk size of the [`ChunkedStack`]s it creates set to `size`, in number
+/// of elements. The size is rounded up to the next power of two and capped at the stack's
+/// compile-time maximum. Zero selects the compile-time maximum.
+///
+/// Existing stacks retain their chunk size, and merges of stacks use the larger chunk size
+/// of their non-empty inputs.
+pub fn with_chunk_size<R>(size: usize, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous chunk size, even if `f` panics.
+    struct Restore(usize);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CHUNK_SIZE.set(self.0);
+        }
+    }
+
+    let _restore = Restore(CHUNK_SIZE.replace(size));
+    f()
+}
+
+/// Returns the chunk size selected by the innermost enclosing [`with_chunk_size`], or zero if
+/// there is none.
+pub fn chunk_size() -> usize {
+    CHUNK_SIZE.get()
+}
+
+/// A [`Batcher`] that builds its batches with the [`ChunkedStack`] chunk size that was
+/// selected when it was created.
+///
+/// Arrangements create their batcher while their dataflow is rendered, so rendering a
+/// dataflow within [`with_chunk_size`] selects the chunk size of its arrangements. Batches
+/// produced by merging retain the chunk size of their inputs.
+pub struct ChunkSizeBatcher<B> {
+    inner: B,
+    chunk_size: usize,
+}
+
+impl<B: Batcher> Batcher for ChunkSizeBatcher<B> {
+    type Input = B::Input;
+    type Output = B::Output;
+    type Time = B::Time;
+
+    fn new(logger: Option<Logger>, operator_id: usize) -> Self {
+        Self {
+            inner: B::new(logger, operator_id),
+            chunk_size: chunk_size(),
+        }
+    }
+
+    fn push_container(&mut self, batch: RefOrMut<Self::Input>) {
+        self.inner.push_container(batch)
+    }
+
+    fn seal<Bu: Builder<Input = Self::Output, Time = Self::Time>>(
+        &mut self,
+        upper: Antichain<Self::Time>,
+    ) -> Bu::Output {
+        let inner = &mut self.inner;
+        with_chunk_size(self.chunk_size, || inner.seal::<Bu>(upper))
+    }
+
+    fn frontier(&mut self) -> AntichainRef<Self::Time> {
+        self.inner.frontier()
+    }
+}
+
+impl<T: Columnation, const CHUNK: usize> StackWrapper<T, CHUNK> {
     #[inline]
     fn with_capacity(size: usize) -> Self {
         if ENABLE_CHUNKED_STACK.load(std::sync::atomic::Ordering::Relaxed) {
@@ -61,7 +144,9 @@ impl<T: Columnation> StackWrapper<T> {
 
 // The `ToOwned` requirement exists to satisfy `self.reserve_items`, who must for now
 // be presented with the actual contained type, rather than a type that borrows into it.
-impl<T: Ord + Columnation + Clone + 'static> BatchContainer for StackWrapper<T> {
+impl<T: Ord + Columnation + Clone + 'static, const CHUNK: usize> BatchContainer
+    for StackWrapper<T, CHUNK>
+{
     type Owned = T;
     type ReadItem<'a> = &'a Self::Owned;
 
@@ -79,9 +164,7 @@ impl<T: Ord + Columnation + Clone + 'static> BatchContainer for StackWrapper<T>
                 Self::Legacy(new)
             }
             (Chunked(cont1), Chunked(cont2)) => {
-                let mut new = ChunkedStack::with_capacity(cont1.len() + cont2.len());
-                new.reserve_regions(std::iter::once(cont1).chain(std::iter::once(cont2)));
-                Self::Chunked(new)
+                Self::Chunked(ChunkedStack::merge_capacity(cont1, cont2))
             }
             (cont1, cont2) => {
                 // We don't have a good way to estimate the result region size
@@ -121,7 +204,7 @@ impl<T: Ord + Columnation + Clone + 'static> BatchContainer for StackWrapper<T>
     }
 }
 
-impl<T: Clone + Columnation + 'static> Container for StackWrapper<T> {
+impl<T: Clone + Columnation + 'static, const CHUNK: usize> Container for StackWrapper<T, CHUNK> {
     type ItemRef<'a> = &'a T where Self: 'a;
     type Item<'a> = &'a T where Self: 'a;
 
@@ -146,8 +229,10 @@ impl<T: Clone + Columnation + 'static> Container for StackWrapper<T> {
         }
     }
 
-    type Iter<'a> =
-        Either<<TimelyStack<T> as Container>::Iter<'a>, <ChunkedStack<T> as Container>::Iter<'a>>;
+    type Iter<'a> = Either<
+        <TimelyStack<T> as Container>::Iter<'a>,
+        <ChunkedStack<T, CHUNK> as Container>::Iter<'a>,
+    >;
 
     fn iter(&self) -> Self::Iter<'_> {
         match self {
@@ -158,7 +243,7 @@ impl<T: Clone + Columnation + 'static> Container for StackWrapper<T> {
 
     type DrainIter<'a> = Either<
         <TimelyStack<T> as Container>::DrainIter<'a>,
-        <ChunkedStack<T> as Container>::DrainIter<'a>,
+        <ChunkedStack<T, CHUNK> as Container>::DrainIter<'a>,
     >;
 
     fn drain(&mut self) -> Self::DrainIter<'_> {
@@ -169,7 +254,9 @@ impl<T: Clone + Columnation + 'static> Container for StackWrapper<T> {
     }
 }
 
-impl<T: Ord + Columnation + ToOwned<Owned = T> + 'static> PushInto<T> for StackWrapper<T> {
+impl<T: Ord + Columnation + ToOwned<Owned = T> + 'static, const CHUNK: usize> PushInto<T>
+    for StackWrapper<T, CHUNK>
+{
     fn push_into(&mut self, item: T) {
         match self {
             StackWrapper::Legacy(stack) => stack.copy(&item),
@@ -178,7 +265,9 @@ impl<T: Ord + Columnation + ToOwned<Owned = T> + 'static> PushInto<T> for StackW
     }
 }
 
-impl<T: Ord + Columnation + ToOwned<Owned = T> + 'static> PushInto<&T> for StackWrapper<T> {
+impl<T: Ord + Columnation + ToOwned<Owned = T> + 'static, const CHUNK: usize> PushInto<&T>
+    for StackWrapper<T, CHUNK>
+{
     fn push_into(&mut self, item: &T) {
         match self {
             StackWrapper::Legacy(stack) => stack.copy(item),
@@ -187,7 +276,7 @@ impl<T: Ord + Columnation + ToOwned<Owned = T> + 'static> PushInto<&T> for Stack
     }
 }
 
-impl<T: Columnation> Default for StackWrapper<T> {
+impl<T: Columnation, const CHUNK: usize> Default for StackWrapper<T, CHUNK> {
     fn default() -> Self {
         Self::with_capacity(0)
     }
@@ -202,29 +291,59 @@ impl<T: Columnation> Default for StackWrapper<T> {
 /// taken when this type is dropped to ensure that the correct memory
 /// is returned (rather than the incorrect memory, from running the
 /// elements `Drop` implementations).
-pub struct ChunkedStack<T: Columnation> {
+///
+/// Elements are stored in chunks of a power-of-two size, selected at runtime through
+/// [`with_chunk_size`] and bounded by the compile-time maximum `CHUNK`.
+pub struct ChunkedStack<T: Columnation, const CHUNK: usize = DEFAULT_CHUNK> {
     local: Vec<Array<T>>,
     inner: T::InnerRegion,
     length: usize,
+    /// The log2 of the capacity of each individual chunk, in number of elements.
+    chunk_shift: u32,
 }
 
-impl<T: Columnation> ChunkedStack<T> {
-    /// The capacity of each individual chunk, in number of elements. Should be a power of two.
-    const CHUNK: usize = 64 << 10;
+impl<T: Columnation, const CHUNK: usize> ChunkedStack<T, CHUNK> {
+    /// Determine the chunk size of new stacks from the runtime configuration.
+    fn configured_chunk_shift() -> u32 {
+        debug_assert!(CHUNK.is_power_of_two(), "CHUNK must be a power of two");
+        let size = chunk_size();
+        if size == 0 {
+            CHUNK.trailing_zeros()
+        } else {
+            size.checked_next_power_of_two()
+                .unwrap_or(CHUNK)
+                .min(CHUNK)
+                .trailing_zeros()
+        }
+    }
 
     /// Construct a [`ChunkedStack`], reserving space for `capacity` elements
     ///
     /// Note that the associated region is not initialized to a specific capacity
     /// because we can't generally know how much space would be required.
     pub fn with_capacity(capacity: usize) -> Self {
-        let local = Vec::with_capacity((capacity + Self::CHUNK - 1) / Self::CHUNK);
+        Self::with_capacity_and_chunk_shift(capacity, Self::configured_chunk_shift())
+    }
+
+    /// Construct a [`ChunkedStack`] with chunks of `1 << chunk_shift` elements, reserving space
+    /// for `capacity` elements.
+    fn with_capacity_and_chunk_shift(capacity: usize, chunk_shift: u32) -> Self {
+        let chunk = 1 << chunk_shift;
+        let local = Vec::with_capacity((capacity + chunk - 1) / chunk);
         Self {
             local,
             inner: T::InnerRegion::default(),
             length: 0,
+            chunk_shift,
         }
     }
 
+    /// The capacity of each individual chunk, in number of elements.
+    #[inline(always)]
+    pub fn chunk_size(&self) -> usize {
+        1 << self.chunk_shift
+    }
+
     /// Ensures `Self` can absorb `items` without further allocations.
     ///
     /// The argument `items` may be cloned and iterated multiple times.
@@ -263,8 +382,9 @@ impl<T: Columnation> ChunkedStack<T> {
     /// Internal helper to push a copied item onto the local storage. The `item` must be allocated
     /// in the region, because it will not be dropped.
     fn push(&mut self, item: T) {
-        if Some(true) != self.local.last().map(|last| last.len() < Self::CHUNK) {
-            self.local.push(Array::with_capacity(Self::CHUNK));
+        let chunk_size = self.chunk_size();
+        if Some(true) != self.local.last().map(|last| last.len() < chunk_size) {
+            self.local.push(Array::with_capacity(chunk_size));
         }
         let chunk = self.local.last_mut().unwrap();
         chunk.push(item);
@@ -285,7 +405,7 @@ impl<T: Columnation> ChunkedStack<T> {
     /// Iterate over a range of elements. Panics if the range mentions non-existent elements,
     /// i.e., its end is past the last element of this container.
     #[inline(always)]
-    pub fn range(&self, r: impl RangeBounds<usize> + std::fmt::Debug) -> Iter<'_, T> {
+    pub fn range(&self, r: impl RangeBounds<usize> + std::fmt::Debug) -> Iter<'_, T, CHUNK> {
         let offset = match r.start_bound() {
             Bound::Included(x) => *x,
             Bound::Excluded(x) => x.checked_add(1).unwrap(),
@@ -313,8 +433,8 @@ impl<T: Columnation> ChunkedStack<T> {
     /// Lookup a specific element.
     #[inline(always)]
     fn index(&self, index: usize) -> &T {
-        let chunk = index / Self::CHUNK;
-        let offset = index & (Self::CHUNK - 1);
+        let chunk = index >> self.chunk_shift;
+        let offset = index & (self.chunk_size() - 1);
         &self.local[chunk][offset]
     }
 
@@ -349,7 +469,9 @@ impl<T: Columnation> ChunkedStack<T> {
 
 // The `ToOwned` requirement exists to satisfy `self.reserve_items`, who must for now
 // be presented with the actual contained type, rather than a type that borrows into it.
-impl<T: Ord + Columnation + ToOwned<Owned = T> + 'static> BatchContainer for ChunkedStack<T> {
+impl<T: Ord + Columnation + ToOwned<Owned = T> + 'static, const CHUNK: usize> BatchContainer
+    for ChunkedStack<T, CHUNK>
+{
     type Owned = T;
     type ReadItem<'a> = &'a Self::Owned;
 
@@ -359,7 +481,15 @@ impl<T: Ord + Columnation + ToOwned<Owned = T> + 'static> BatchContainer for Chu
     }
 
     fn merge_capacity(cont1: &Self, cont2: &Self) -> Self {
-        let mut new = Self::with_capacity(cont1.length + cont2.length);
+        // Empty stacks, e.g. those of empty batches, don't reflect the chunk size of the
+        // arrangement, so they don't contribute to the chunk size of the result.
+        let chunk_shift = [cont1, cont2]
+            .into_iter()
+            .filter(|cont| cont.length > 0)
+            .map(|cont| cont.chunk_shift)
+            .max()
+            .unwrap_or_else(Self::configured_chunk_shift);
+        let mut new = Self::with_capacity_and_chunk_shift(cont1.length + cont2.length, chunk_shift);
         new.reserve_regions(std::iter::once(cont1).chain(std::iter::once(cont2)));
         new
     }
@@ -379,7 +509,7 @@ impl<T: Ord + Columnation + ToOwned<Owned = T> + 'static> BatchContainer for Chu
     }
 }
 
-impl<T: Columnation + 'static> Container for ChunkedStack<T> {
+impl<T: Columnation + 'static, const CHUNK: usize> Container for ChunkedStack<T, CHUNK> {
     type ItemRef<'a> = &'a T where Self: 'a;
     type Item<'a> = &'a T where Self: 'a;
 
@@ -395,26 +525,26 @@ impl<T: Columnation + 'static> Container for ChunkedStack<T> {
         self.clear()
     }
 
-    type Iter<'a> = Iter<'a, T>;
+    type Iter<'a> = Iter<'a, T, CHUNK>;
 
     fn iter(&self) -> Self::Iter<'_> {
         self.range(..)
     }
 
-    type DrainIter<'a> = Iter<'a, T>;
+    type DrainIter<'a> = Iter<'a, T, CHUNK>;
 
     fn drain(&mut self) -> Self::DrainIter<'_> {
         self.range(..)
     }
 }
 
-impl<T: Columnation> PushInto<&T> for ChunkedStack<T> {
+impl<T: Columnation, const CHUNK: usize> PushInto<&T> for ChunkedStack<T, CHUNK> {
     fn push_into(&mut self, item: &T) {
         self.copy(item);
     }
 }
 
-impl<T: Columnation> Index<usize> for ChunkedStack<T> {
+impl<T: Columnation, const CHUNK: usize> Index<usize> for ChunkedStack<T, CHUNK> {
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -422,15 +552,15 @@ impl<T: Columnation> Index<usize> for ChunkedStack<T> {
     }
 }
 
-impl<T: Columnation> Default for ChunkedStack<T> {
+impl<T: Columnation, const CHUNK: usize> Default for ChunkedStack<T, CHUNK> {
     fn default() -> Self {
         Self::with_capacity(0)
     }
 }
 
-impl<T: Columnation> Clone for ChunkedStack<T> {
+impl<T: Columnation, const CHUNK: usize> Clone for ChunkedStack<T, CHUNK> {
     fn clone(&self) -> Self {
-        let mut new: Self = Default::default();
+        let mut new = Self::with_capacity_and_chunk_shift(self.length, self.chunk_shift);
         for item in self.range(..) {
             new.copy(item);
         }
@@ -446,22 +576,22 @@ impl<T: Columnation> Clone for ChunkedStack<T> {
 }
 
 /// An iterator of a [`ChunkedStack`].
-pub struct Iter<'a, T: Columnation> {
-    stack: &'a ChunkedStack<T>,
+pub struct Iter<'a, T: Columnation, const CHUNK: usize = DEFAULT_CHUNK> {
+    stack: &'a ChunkedStack<T, CHUNK>,
     offset: usize,
     limit: usize,
 }
 
-impl<'a, T: Columnation> Clone for Iter<'a, T> {
+impl<'a, T: Columnation, const CHUNK: usize> Clone for Iter<'a, T, CHUNK> {
     #[inline]
     fn clone(&self) -> Self {
         *self
     }
 }
 
-impl<'a, T: Columnation> Copy for Iter<'a, T> {}
+impl<'a, T: Columnation, const CHUNK: usize> Copy for Iter<'a, T, CHUNK> {}
 
-impl<'a, T: Columnation> Iterator for Iter<'a, T> {
+impl<'a, T: Columnation, const CHUNK: usize> Iterator for Iter<'a, T, CHUNK> {
     type Item = &'a T;
 
     #[inline]
@@ -476,8 +606,51 @@ impl<'a, T: Columnation> Iterator for Iter<'a, T> {
     }
 }
 
-impl<T: Columnation> Drop for ChunkedStack<T> {
+impl<T: Columnation, const CHUNK: usize> Drop for ChunkedStack<T, CHUNK> {
     fn drop(&mut self) {
         self.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn chunked_stack_small_chunks() {
+        let mut stack = ChunkedStack::<u64, 16>::with_capacity(0);
+        assert_eq!(stack.chunk_size(), 16);
+        for i in 0..100 {
+            stack.copy(&i);
+        }
+        assert_eq!(stack.len(), 100);
+        assert_eq!(stack.local.len(), 7);
+        for (index, item) in (0..100).enumerate() {
+            assert_eq!(stack[index], item);
+        }
+        let merged = ChunkedStack::merge_capacity(&stack, &ChunkedStack::default());
+        assert_eq!(merged.chunk_size(), 16);
+        let cloned = stack.clone();
+        assert!(cloned.range(..).copied().eq(0..100));
+    }
+
+    #[mz_ore::test]
+    fn chunked_stack_scoped_chunk_size() {
+        let small = with_chunk_size(3, || {
+            let inner = with_chunk_size(100, ChunkedStack::<u64, 64>::default);
+            assert_eq!(inner.chunk_size(), 64);
+            ChunkedStack::<u64, 64>::default()
+        });
+        assert_eq!(small.chunk_size(), 4);
+        assert_eq!(chunk_size(), 0);
+        assert_eq!(ChunkedStack::<u64, 64>::default().chunk_size(), 64);
+
+        // Merging with an empty stack of another chunk size keeps the chunk size of the
+        // non-empty input.
+        let mut small = small;
+        small.copy(&1);
+        let empty = ChunkedStack::<u64, 64>::default();
+        let merged = ChunkedStack::merge_capacity(&empty, &small);
+        assert_eq!(merged.chunk_size(), 4);
+    }
+}
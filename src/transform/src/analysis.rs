@@ -1208,29 +1208,46 @@ mod explain {
 /// Definition and helper structs for the [`Cardinality`] attribute.
 mod cardinality {
     use std::collections::{BTreeMap, BTreeSet};
+    use std::sync::Arc;
 
     use mz_expr::{
         BinaryFunc, Id, JoinImplementation, MirRelationExpr, MirScalarExpr, TableFunc, UnaryFunc,
         VariadicFunc,
     };
     use mz_ore::cast::{CastFrom, CastLossy, TryCastFrom};
-    use mz_repr::GlobalId;
+    use mz_repr::{Datum, GlobalId};
 
     use ordered_float::OrderedFloat;
 
     use super::{Analysis, Arity, SubtreeSize, UniqueKeys};
+    use crate::{ColumnStatistics, HistogramBucket};
 
     /// Compute the estimated cardinality of each subtree of a [MirRelationExpr] from the bottom up.
     #[allow(missing_debug_implementations)]
     pub struct Cardinality {
         /// Cardinalities for globally named entities
         pub stats: BTreeMap<GlobalId, usize>,
+        /// Column statistics for globally named entities
+        pub column_stats: BTreeMap<GlobalId, Arc<Vec<ColumnStatistics>>>,
     }
 
     impl Cardinality {
         /// A cardinality estimator with provided statistics for the given global identifiers
         pub fn with_stats(stats: BTreeMap<GlobalId, usize>) -> Self {
-            Cardinality { stats }
+            Cardinality {
+                stats,
+                column_stats: BTreeMap::new(),
+            }
+        }
+
+        /// Adds column statistics for the given global identifiers, used to estimate the selectivity
+        /// of filters.
+        pub fn with_column_stats(
+            mut self,
+            column_stats: BTreeMap<GlobalId, Arc<Vec<ColumnStatistics>>>,
+        ) -> Self {
+            self.column_stats = column_stats;
+            self
         }
     }
 
@@ -1238,6 +1255,7 @@ mod cardinality {
         fn default() -> Self {
             Cardinality {
                 stats: BTreeMap::new(),
+                column_stats: BTreeMap::new(),
             }
         }
     }
@@ -1389,6 +1407,142 @@ mod cardinality {
     /// But see also expr/src/scalar.rs for `FilterCharacteristics::worst_case_scaling_factor()` for a more nuanced take.
     pub const WORST_CASE_SELECTIVITY: OrderedFloat<f64> = OrderedFloat(0.1);
 
+    /// The statistics for `expr`, if it is a column that we have nonempty statistics for.
+    fn column_statistics<'a>(
+        column_stats: Option<&'a [ColumnStatistics]>,
+        expr: &MirScalarExpr,
+    ) -> Option<&'a ColumnStatistics> {
+        match expr {
+            MirScalarExpr::Column(col) => column_stats?
+                .get(*col)
+                .filter(|stats| stats.total_count() > 0),
+            _ => None,
+        }
+    }
+
+    /// Estimates the selectivity of `column <func> literal` (or `literal <func> column`, if
+    /// `flipped`) using the histogram in `stats`.
+    ///
+    /// Returns `None` if `func` is not a comparison.
+    fn histogram_selectivity(
+        stats: &ColumnStatistics,
+        func: &BinaryFunc,
+        literal: Datum,
+        flipped: bool,
+    ) -> Option<OrderedFloat<f64>> {
+        let func = match (func, flipped) {
+            (BinaryFunc::Lt, true) => BinaryFunc::Gt,
+            (BinaryFunc::Lte, true) => BinaryFunc::Gte,
+            (BinaryFunc::Gt, true) => BinaryFunc::Lt,
+            (BinaryFunc::Gte, true) => BinaryFunc::Lte,
+            (func, _) => func.clone(),
+        };
+        if !matches!(
+            func,
+            BinaryFunc::Eq
+                | BinaryFunc::NotEq
+                | BinaryFunc::Lt
+                | BinaryFunc::Lte
+                | BinaryFunc::Gt
+                | BinaryFunc::Gte
+        ) {
+            return None;
+        }
+        if literal.is_null() {
+            // Comparisons with `NULL` are never true.
+            return Some(OrderedFloat(0.0));
+        }
+
+        let mut matching = 0.0;
+        for bucket in &stats.buckets {
+            let below = bucket_fraction_below(bucket, literal);
+            let equal = bucket_fraction_equal(bucket, literal);
+            let fraction = match func {
+                BinaryFunc::Eq => equal,
+                BinaryFunc::NotEq => 1.0 - equal,
+                BinaryFunc::Lt => below,
+                BinaryFunc::Lte => below + equal,
+                BinaryFunc::Gt => 1.0 - below - equal,
+                BinaryFunc::Gte => 1.0 - below,
+                _ => unreachable!(),
+            };
+            matching += f64::cast_lossy(bucket.count) * fraction.clamp(0.0, 1.0);
+        }
+
+        let selectivity = matching / f64::cast_lossy(stats.total_count());
+        Some(OrderedFloat(selectivity.clamp(0.0, 1.0)))
+    }
+
+    /// Estimates the fraction of the values in `bucket` that are less than `literal`.
+    fn bucket_fraction_below(bucket: &HistogramBucket, literal: Datum) -> f64 {
+        let lower = bucket.lower.unpack_first();
+        let upper = bucket.upper.unpack_first();
+        if upper < literal {
+            1.0
+        } else if literal <= lower {
+            0.0
+        } else {
+            // Assume a uniform distribution within the bucket, if we can.
+            match (
+                datum_as_f64(lower),
+                datum_as_f64(upper),
+                datum_as_f64(literal),
+            ) {
+                (Some(lower), Some(upper), Some(literal)) if lower < upper => {
+                    (literal - lower) / (upper - lower)
+                }
+                _ => 0.5,
+            }
+        }
+    }
+
+    /// Estimates the fraction of the values in `bucket` that are equal to `literal`.
+    fn bucket_fraction_equal(bucket: &HistogramBucket, literal: Datum) -> f64 {
+        let lower = bucket.lower.unpack_first();
+        let upper = bucket.upper.unpack_first();
+        if literal < lower || upper < literal {
+            0.0
+        } else if lower == upper {
+            1.0
+        } else {
+            // For integers, assume every value in the bucket's range occurs equally often.
+            match (datum_as_f64(lower), datum_as_f64(upper)) {
+                (Some(lower_f64), Some(upper_f64)) if is_integral(lower) => {
+                    1.0 / (upper_f64 - lower_f64 + 1.0)
+                }
+                _ => WORST_CASE_SELECTIVITY.0,
+            }
+        }
+    }
+
+    fn is_integral(datum: Datum) -> bool {
+        matches!(
+            datum,
+            Datum::Int16(_)
+                | Datum::Int32(_)
+                | Datum::Int64(_)
+                | Datum::UInt8(_)
+                | Datum::UInt16(_)
+                | Datum::UInt32(_)
+                | Datum::UInt64(_)
+        )
+    }
+
+    fn datum_as_f64(datum: Datum) -> Option<f64> {
+        match datum {
+            Datum::Int16(i) => Some(f64::from(i)),
+            Datum::Int32(i) => Some(f64::from(i)),
+            Datum::Int64(i) => Some(f64::cast_lossy(i)),
+            Datum::UInt8(i) => Some(f64::from(i)),
+            Datum::UInt16(i) => Some(f64::from(i)),
+            Datum::UInt32(i) => Some(f64::from(i)),
+            Datum::UInt64(i) => Some(f64::cast_lossy(i)),
+            Datum::Float32(f) => Some(f64::from(*f)),
+            Datum::Float64(f) => Some(*f),
+            _ => None,
+        }
+    }
+
     // This section defines how we estimate cardinality for each syntactic construct.
     //
     // We split it up into functions to make it all a bit more tractable to work with.
@@ -1409,6 +1563,7 @@ mod cardinality {
             &self,
             predicate_expr: &MirScalarExpr,
             unique_columns: &BTreeSet<usize>,
+            column_stats: Option<&[ColumnStatistics]>,
         ) -> OrderedFloat<f64> {
             let index_selectivity = |expr: &MirScalarExpr| -> Option<OrderedFloat<f64>> {
                 match expr {
@@ -1429,10 +1584,17 @@ mod cardinality {
                 | MirScalarExpr::Literal(_, _)
                 | MirScalarExpr::CallUnmaterializable(_) => OrderedFloat(1.0),
                 MirScalarExpr::CallUnary { func, expr } => match func {
-                    UnaryFunc::Not(_) => OrderedFloat(1.0) - self.predicate(expr, unique_columns),
+                    UnaryFunc::Not(_) => {
+                        OrderedFloat(1.0) - self.predicate(expr, unique_columns, column_stats)
+                    }
                     UnaryFunc::IsTrue(_) | UnaryFunc::IsFalse(_) => OrderedFloat(0.5),
                     UnaryFunc::IsNull(_) => {
-                        if let Some(icard) = index_selectivity(expr) {
+                        if let Some(stats) = column_statistics(column_stats, expr) {
+                            OrderedFloat(
+                                f64::cast_lossy(stats.null_count)
+                                    / f64::cast_lossy(stats.total_count()),
+                            )
+                        } else if let Some(icard) = index_selectivity(expr) {
                             icard
                         } else {
                             WORST_CASE_SELECTIVITY
@@ -1441,6 +1603,19 @@ mod cardinality {
                     _ => WORST_CASE_SELECTIVITY,
                 },
                 MirScalarExpr::CallBinary { func, expr1, expr2 } => {
+                    // Comparisons of a column against a literal can be estimated using the
+                    // column's histogram, if we have one.
+                    let histogram_estimate = match (expr1.as_literal(), expr2.as_literal()) {
+                        (None, Some(Ok(literal))) => column_statistics(column_stats, expr1)
+                            .and_then(|stats| histogram_selectivity(stats, func, literal, false)),
+                        (Some(Ok(literal)), None) => column_statistics(column_stats, expr2)
+                            .and_then(|stats| histogram_selectivity(stats, func, literal, true)),
+                        _ => None,
+                    };
+                    if let Some(selectivity) = histogram_estimate {
+                        return selectivity;
+                    }
+
                     match func {
                         BinaryFunc::Eq => {
                             match (index_selectivity(expr1), index_selectivity(expr2)) {
@@ -1469,7 +1644,7 @@ mod cardinality {
                 MirScalarExpr::CallVariadic { func, exprs } => match func {
                     VariadicFunc::And => exprs
                         .iter()
-                        .map(|expr| self.predicate(expr, unique_columns))
+                        .map(|expr| self.predicate(expr, unique_columns, column_stats))
                         .product(),
                     VariadicFunc::Or => {
                         // TODO(mgree): BETWEEN will get compiled down to an AND of appropriate bounds---we could try to detect it and be clever
@@ -1480,13 +1655,13 @@ mod cardinality {
                         let mut expr1;
 
                         if let Some(first) = exprs.next() {
-                            expr1 = self.predicate(first, unique_columns);
+                            expr1 = self.predicate(first, unique_columns, column_stats);
                         } else {
                             return OrderedFloat(1.0);
                         }
 
                         for expr2 in exprs {
-                            let expr2 = self.predicate(expr2, unique_columns);
+                            let expr2 = self.predicate(expr2, unique_columns, column_stats);
                             expr1 = expr1 + expr2 - expr1 * expr2;
                         }
                         expr1
@@ -1494,8 +1669,8 @@ mod cardinality {
                     _ => OrderedFloat(1.0),
                 },
                 MirScalarExpr::If { cond: _, then, els } => std::cmp::max(
-                    self.predicate(then, unique_columns),
-                    self.predicate(els, unique_columns),
+                    self.predicate(then, unique_columns, column_stats),
+                    self.predicate(els, unique_columns, column_stats),
                 ),
             }
         }
@@ -1504,6 +1679,7 @@ mod cardinality {
             &self,
             predicates: &Vec<MirScalarExpr>,
             keys: &Vec<Vec<usize>>,
+            column_stats: Option<&[ColumnStatistics]>,
             input: CardinalityEstimate,
         ) -> CardinalityEstimate {
             // TODO(mgree): should we try to do something for indices built on multiple columns?
@@ -1516,7 +1692,7 @@ mod cardinality {
 
             let mut estimate = input;
             for expr in predicates {
-                let selectivity = self.predicate(expr, &unique_columns);
+                let selectivity = self.predicate(expr, &unique_columns, column_stats);
                 debug_assert!(
                     OrderedFloat(0.0) <= selectivity && selectivity <= OrderedFloat(1.0),
                     "predicate selectivity {selectivity} should be in the range [0,1]"
@@ -1706,11 +1882,26 @@ mod cardinality {
                     let input = results[index - 1];
                     self.flat_map(func, input)
                 }
-                Filter { predicates, .. } => {
+                Filter {
+                    input: filter_input,
+                    predicates,
+                } => {
                     let input = results[index - 1];
                     let keys = depends.results::<UniqueKeys>().expect("UniqueKeys missing");
                     let keys = &keys[index - 1];
-                    self.filter(predicates, keys, input)
+                    // Column statistics are only available when filtering a global collection
+                    // directly.
+                    let mut filter_input = &**filter_input;
+                    while let ArrangeBy { input, .. } = filter_input {
+                        filter_input = &**input;
+                    }
+                    let column_stats = match filter_input {
+                        Get {
+                            id: Id::Global(id), ..
+                        } => self.column_stats.get(id).map(|stats| stats.as_slice()),
+                        _ => None,
+                    };
+                    self.filter(predicates, keys, column_stats, input)
                 }
                 Join {
                     equivalences,
@@ -277,7 +277,10 @@ impl JoinImplementation {
                 if features.enable_cardinality_estimates {
                     let mut builder = DerivedBuilder::new(features);
                     // TODO(mgree): it would be good to not have to copy the statistics here
-                    builder.require(Cardinality::with_stats(stats.as_map()));
+                    builder.require(
+                        Cardinality::with_stats(stats.as_map())
+                            .with_column_stats(stats.column_statistics_map()),
+                    );
                     let derived = builder.visit(input);
 
                     let estimate = *derived.as_view().value::<Cardinality>().unwrap();
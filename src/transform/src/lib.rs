@@ -30,7 +30,7 @@ use mz_expr::{MirRelationExpr, MirScalarExpr};
 use mz_ore::id_gen::IdGen;
 use mz_ore::stack::RecursionLimitError;
 use mz_repr::optimize::OptimizerFeatures;
-use mz_repr::GlobalId;
+use mz_repr::{GlobalId, Row};
 use tracing::error;
 
 pub mod analysis;
@@ -56,6 +56,7 @@ pub mod normalize_ops;
 pub mod notice;
 pub mod ordering;
 pub mod predicate_pushdown;
+pub mod range_join;
 pub mod reduce_elision;
 pub mod reduction_pushdown;
 pub mod redundant_join;
@@ -253,6 +254,50 @@ pub trait StatisticsOracle: fmt::Debug + Send {
 
     /// Returns a map from identifiers to sizes
     fn as_map(&self) -> BTreeMap<GlobalId, usize>;
+
+    /// Returns statistics about the values of each column of the given identifier
+    ///
+    /// Returning `None` means that nothing is known about the columns of `id`
+    fn column_statistics(&self, _id: GlobalId) -> Option<Arc<Vec<ColumnStatistics>>> {
+        None
+    }
+
+    /// Returns a map from identifiers to the statistics about their columns
+    fn column_statistics_map(&self) -> BTreeMap<GlobalId, Arc<Vec<ColumnStatistics>>> {
+        BTreeMap::new()
+    }
+}
+
+/// Statistics about the values of a single column of a collection.
+///
+/// These are derived from the statistics persist keeps about the parts of a
+/// shard, and so count updates rather than consolidated rows.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColumnStatistics {
+    /// The number of updates in which the column is `NULL`.
+    pub null_count: usize,
+    /// A histogram of the non-null values of the column, ordered by lower bound.
+    ///
+    /// Buckets may overlap.
+    pub buckets: Vec<HistogramBucket>,
+}
+
+impl ColumnStatistics {
+    /// The number of updates these statistics describe, including `NULL`s.
+    pub fn total_count(&self) -> usize {
+        self.null_count + self.buckets.iter().map(|b| b.count).sum::<usize>()
+    }
+}
+
+/// A bucket of a [`ColumnStatistics`] histogram.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistogramBucket {
+    /// The smallest value in the bucket, as a single-datum row.
+    pub lower: Row,
+    /// The largest value in the bucket, as a single-datum row.
+    pub upper: Row,
+    /// The number of values in the bucket.
+    pub count: usize,
 }
 
 /// A [`StatisticsOracle`] that knows nothing and can give no estimates.
@@ -588,7 +633,7 @@ impl Optimizer {
     /// rendering.
     pub fn physical_optimizer(ctx: &mut TransformCtx) -> Self {
         // Implementation transformations
-        let transforms: Vec<Box<dyn Transform>> = vec![
+        let transforms: Vec<Box<dyn Transform>> = transforms![
             Box::new(
                 typecheck::Typecheck::new(ctx.typecheck())
                     .disallow_new_globals()
@@ -631,6 +676,12 @@ impl Optimizer {
                     Box::new(literal_lifting::LiteralLifting::default()),
                 ],
             }),
+            // Must run before JoinImplementation, as it only rewrites unimplemented joins.
+            Box::new(range_join::RangeJoinBucketing::new(
+                ctx.features.range_join_bucket_width,
+                ctx.features.range_join_max_buckets,
+            ));
+                if ctx.features.enable_range_join_bucketing,
             Box::new(literal_constraints::LiteralConstraints),
             Box::new(Fixpoint {
                 name: "fix_joins",
@@ -338,6 +338,14 @@ macro_rules! raw_optimizer_notices {
                         $(Self::$ty => stringify!($ty),)+
                     }
                 }
+
+                /// A stable, machine-readable name for this optimizer notice
+                /// kind.
+                pub fn name(&self) -> &'static str {
+                    match self {
+                        $(Self::$ty => stringify!([<$ty:snake>]),)+
+                    }
+                }
             }
 
             $(
@@ -0,0 +1,350 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Turns range joins into equi-joins on buckets of the range.
+//!
+//! A range (or band) join relates two inputs only by a predicate of the form
+//! `lo <= x AND x <= hi`, where `x` comes from one input and `lo` and `hi` come
+//! from the other one, e.g. `a.ts BETWEEN b.start AND b.end`. Without an
+//! equality between the inputs, such joins are planned as a cross join followed
+//! by a filter, which is quadratic in the size of the inputs.
+//!
+//! This transform maps each value to a bucket of a fixed width, such that
+//! `bucket` is monotone, and rewrites
+//!
+//! ```text
+//! Filter (lo <= x) AND (x <= hi)
+//!   Join (A, B)
+//! ```
+//!
+//! into
+//!
+//! ```text
+//! Filter (lo <= x) AND (x <= hi)
+//!   Project (<columns of A and B>)
+//!     Join on=(#a_wide = #b_wide AND #a_bucket = #b_bucket)
+//!       Map (case when (#a_wide = 0) then bucket(x) else 0 end)
+//!         FlatMap generate_series(0, 1, 1)
+//!           A
+//!       FlatMap generate_series(
+//!           case when (#b_wide = 1) then 0 else bucket(lo) end,
+//!           case when (#b_wide = 1) then 0 else bucket(hi) end,
+//!           1
+//!         )
+//!         Map (case when ((bucket(hi) - bucket(lo)) > max_buckets) then 1 else 0 end)
+//!           B
+//! ```
+//!
+//! Records of `B` are replicated once per bucket their range spans, so the
+//! bucket width trades off replication of `B` against the number of records
+//! of `A` each record of `B` is compared to. Ranges that span more than
+//! `max_buckets` buckets, e.g. ranges whose upper bound is a sentinel like
+//! `9999-12-31`, are marked as wide and are not replicated. Instead, every
+//! record of `A` is duplicated into a single wide bucket that all wide ranges
+//! join with, like they would in a cross join.
+//!
+//! Every pair of records that satisfies the range predicate agrees on exactly
+//! one pair of wideness and bucket, so the rewritten join produces each such
+//! pair exactly once. The original predicates are kept to discard pairs that
+//! only share a bucket.
+
+use mz_expr::visit::Visit;
+use mz_expr::{
+    func, BinaryFunc, JoinInputMapper, MirRelationExpr, MirScalarExpr, TableFunc, UnaryFunc,
+};
+use mz_repr::adt::datetime::DateTimeUnits;
+use mz_repr::adt::numeric::Numeric;
+use mz_repr::{ColumnType, Datum, ScalarType};
+
+use crate::TransformCtx;
+
+/// Turns range joins into equi-joins on buckets of the range.
+#[derive(Debug)]
+pub struct RangeJoinBucketing {
+    /// The width of the buckets. A width of zero disables the transform.
+    width: usize,
+    /// The maximum number of buckets a range is replicated to.
+    max_buckets: usize,
+}
+
+impl RangeJoinBucketing {
+    /// Creates a transform that uses buckets of the given `width`, and
+    /// replicates ranges to at most `max_buckets` buckets.
+    pub fn new(width: usize, max_buckets: usize) -> Self {
+        Self { width, max_buckets }
+    }
+}
+
+impl crate::Transform for RangeJoinBucketing {
+    #[mz_ore::instrument(
+        target = "optimizer",
+        level = "debug",
+        fields(path.segment = "range_join_bucketing")
+    )]
+    fn transform(
+        &self,
+        relation: &mut MirRelationExpr,
+        _: &mut TransformCtx,
+    ) -> Result<(), crate::TransformError> {
+        let width = i64::try_from(self.width).unwrap_or(i64::MAX);
+        if width > 0 {
+            relation.visit_mut_post(&mut |expr| Self::action(expr, width, self.max_buckets))?;
+        }
+        mz_repr::explain::trace_plan(&*relation);
+        Ok(())
+    }
+}
+
+/// A range predicate `lo <= x AND x <= hi` between two inputs of a join.
+struct RangePredicate {
+    /// The input that `x` references.
+    point_input: usize,
+    /// The input that `lo` and `hi` reference.
+    range_input: usize,
+    point: MirScalarExpr,
+    lo: MirScalarExpr,
+    hi: MirScalarExpr,
+    scalar_type: ScalarType,
+}
+
+impl RangeJoinBucketing {
+    /// Rewrites a `Filter` with a range predicate over an unimplemented `Join`
+    /// whose inputs are otherwise unrelated.
+    pub fn action(relation: &mut MirRelationExpr, width: i64, max_buckets: usize) {
+        let MirRelationExpr::Filter { input, predicates } = relation else {
+            return;
+        };
+        let MirRelationExpr::Join {
+            inputs,
+            equivalences,
+            implementation: mz_expr::JoinImplementation::Unimplemented,
+        } = &mut **input
+        else {
+            return;
+        };
+        if inputs.len() < 2 {
+            return;
+        }
+
+        let input_mapper = JoinInputMapper::new(inputs);
+        let column_types = inputs
+            .iter()
+            .flat_map(|input| input.typ().column_types)
+            .collect::<Vec<_>>();
+        let Some(range) =
+            find_range_predicate(predicates, equivalences, &input_mapper, &column_types)
+        else {
+            return;
+        };
+        let (Some(point_bucket), Some(lo_bucket), Some(hi_bucket)) = (
+            bucket(
+                input_mapper.map_expr_to_local(range.point.clone()),
+                &range.scalar_type,
+                width,
+            ),
+            bucket(
+                input_mapper.map_expr_to_local(range.lo.clone()),
+                &range.scalar_type,
+                width,
+            ),
+            bucket(
+                input_mapper.map_expr_to_local(range.hi.clone()),
+                &range.scalar_type,
+                width,
+            ),
+        ) else {
+            return;
+        };
+
+        // Append the wideness and the bucket columns to both inputs.
+        let int64 = |i| MirScalarExpr::literal_ok(Datum::Int64(i), ScalarType::Int64);
+        let point_arity = input_mapper.input_arity(range.point_input);
+        let range_arity = input_mapper.input_arity(range.range_input);
+        let point_input = inputs[range.point_input].take_dangerous();
+        inputs[range.point_input] = point_input
+            .flat_map(
+                TableFunc::GenerateSeriesInt64,
+                vec![int64(0), int64(1), int64(1)],
+            )
+            .map_one(MirScalarExpr::If {
+                cond: Box::new(
+                    MirScalarExpr::column(point_arity).call_binary(int64(0), BinaryFunc::Eq),
+                ),
+                then: Box::new(point_bucket),
+                els: Box::new(int64(0)),
+            });
+        // The span is computed on `numeric`s, which can't overflow.
+        let to_numeric = |expr: &MirScalarExpr| {
+            expr.clone()
+                .call_unary(UnaryFunc::CastInt64ToNumeric(func::CastInt64ToNumeric(
+                    None,
+                )))
+        };
+        let max_buckets = Numeric::from(u64::try_from(max_buckets).unwrap_or(u64::MAX));
+        let wide = to_numeric(&hi_bucket)
+            .call_binary(to_numeric(&lo_bucket), BinaryFunc::SubNumeric)
+            .call_binary(
+                MirScalarExpr::literal_ok(
+                    Datum::from(max_buckets),
+                    ScalarType::Numeric { max_scale: None },
+                ),
+                BinaryFunc::Gt,
+            );
+        let narrow_or_zero = |bucket| MirScalarExpr::If {
+            cond: Box::new(
+                MirScalarExpr::column(range_arity).call_binary(int64(1), BinaryFunc::Eq),
+            ),
+            then: Box::new(int64(0)),
+            els: Box::new(bucket),
+        };
+        let range_input = inputs[range.range_input].take_dangerous();
+        inputs[range.range_input] = range_input
+            .map_one(MirScalarExpr::If {
+                cond: Box::new(wide),
+                then: Box::new(int64(1)),
+                els: Box::new(int64(0)),
+            })
+            .flat_map(
+                TableFunc::GenerateSeriesInt64,
+                vec![
+                    narrow_or_zero(lo_bucket),
+                    narrow_or_zero(hi_bucket),
+                    int64(1),
+                ],
+            );
+
+        // Remap the existing equivalences, and relate the buckets.
+        let new_mapper = JoinInputMapper::new(inputs);
+        let permutation = (0..input_mapper.total_columns())
+            .map(|column| {
+                let (column, input) = input_mapper.map_column_to_local(column);
+                new_mapper.map_column_to_global(column, input)
+            })
+            .collect::<Vec<_>>();
+        for class in equivalences.iter_mut() {
+            for expr in class.iter_mut() {
+                expr.permute(&permutation);
+            }
+        }
+        for offset in 0..2 {
+            equivalences.push(vec![
+                MirScalarExpr::column(
+                    new_mapper.map_column_to_global(point_arity + offset, range.point_input),
+                ),
+                MirScalarExpr::column(
+                    new_mapper.map_column_to_global(range_arity + offset, range.range_input),
+                ),
+            ]);
+        }
+
+        // Restore the original columns for the predicates and the consumers.
+        let join = input.take_dangerous();
+        **input = join.project(permutation);
+    }
+}
+
+/// Finds a predicate `lo <= x AND x <= hi` in `predicates`, such that `x`
+/// references a single input, and `lo` and `hi` reference the same other
+/// input, which is not related to the first one by the `equivalences`.
+fn find_range_predicate(
+    predicates: &[MirScalarExpr],
+    equivalences: &[Vec<MirScalarExpr>],
+    input_mapper: &JoinInputMapper,
+    column_types: &[ColumnType],
+) -> Option<RangePredicate> {
+    // Each bound as `(smaller, larger)`.
+    let bounds = predicates
+        .iter()
+        .filter_map(|predicate| match predicate {
+            MirScalarExpr::CallBinary { func, expr1, expr2 } => match func {
+                BinaryFunc::Lt | BinaryFunc::Lte => Some((&**expr1, &**expr2)),
+                BinaryFunc::Gt | BinaryFunc::Gte => Some((&**expr2, &**expr1)),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for (lo, point) in bounds.iter() {
+        let (Some(point_input), Some(range_input)) = (
+            input_mapper.single_input(point),
+            input_mapper.single_input(lo),
+        ) else {
+            continue;
+        };
+        if point_input == range_input
+            || related(equivalences, input_mapper, point_input, range_input)
+        {
+            continue;
+        }
+        let Some((_, hi)) = bounds.iter().find(|(other_point, hi)| {
+            *other_point == *point && input_mapper.is_localized(hi, range_input)
+        }) else {
+            continue;
+        };
+        let scalar_type = point.typ(column_types).scalar_type;
+        if lo.typ(column_types).scalar_type.base_eq(&scalar_type)
+            && hi.typ(column_types).scalar_type.base_eq(&scalar_type)
+        {
+            return Some(RangePredicate {
+                point_input,
+                range_input,
+                point: (*point).clone(),
+                lo: (*lo).clone(),
+                hi: (*hi).clone(),
+                scalar_type,
+            });
+        }
+    }
+    None
+}
+
+/// Returns whether some equivalence class relates the two given inputs.
+fn related(
+    equivalences: &[Vec<MirScalarExpr>],
+    input_mapper: &JoinInputMapper,
+    a: usize,
+    b: usize,
+) -> bool {
+    equivalences.iter().any(|class| {
+        let inputs = class
+            .iter()
+            .flat_map(|expr| input_mapper.lookup_inputs(expr))
+            .collect::<Vec<_>>();
+        inputs.contains(&a) && inputs.contains(&b)
+    })
+}
+
+/// Returns an `int8` expression that maps `expr` of type `scalar_type` to its
+/// bucket of the given `width`, or `None` if the type is not supported.
+///
+/// Timestamps are bucketed by their number of seconds since the Unix epoch.
+fn bucket(expr: MirScalarExpr, scalar_type: &ScalarType, width: i64) -> Option<MirScalarExpr> {
+    let expr = match scalar_type {
+        ScalarType::Int16 => expr.call_unary(UnaryFunc::CastInt16ToInt64(func::CastInt16ToInt64)),
+        ScalarType::Int32 => expr.call_unary(UnaryFunc::CastInt32ToInt64(func::CastInt32ToInt64)),
+        ScalarType::Int64 => expr,
+        ScalarType::Timestamp { .. } => expr
+            .call_unary(UnaryFunc::ExtractTimestamp(func::ExtractTimestamp(
+                DateTimeUnits::Epoch,
+            )))
+            .call_unary(UnaryFunc::CastNumericToInt64(func::CastNumericToInt64)),
+        ScalarType::TimestampTz { .. } => expr
+            .call_unary(UnaryFunc::ExtractTimestampTz(func::ExtractTimestampTz(
+                DateTimeUnits::Epoch,
+            )))
+            .call_unary(UnaryFunc::CastNumericToInt64(func::CastNumericToInt64)),
+        _ => return None,
+    };
+    // Integer division is monotone, which is all we need from a bucket.
+    Some(expr.call_binary(
+        MirScalarExpr::literal_ok(Datum::Int64(width), ScalarType::Int64),
+        BinaryFunc::DivInt64,
+    ))
+}
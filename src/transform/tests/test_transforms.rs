@@ -213,6 +213,11 @@ fn handle_apply(
             let transform = NormalizeLets::new(false);
             apply_transform(transform, catalog, input)
         }
+        "range_join_bucketing" => {
+            use mz_transform::range_join::RangeJoinBucketing;
+            let transform = RangeJoinBucketing::new(10, 100);
+            apply_transform(transform, catalog, input)
+        }
         "reduction_pushdown" => {
             use mz_transform::reduction_pushdown::ReductionPushdown;
             let transform = ReductionPushdown;
@@ -0,0 +1,94 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+# Source definitions
+# ------------------
+
+# Define t0 source
+define
+DefSource name=t0
+  - c0: bigint
+  - c1: bigint
+----
+Source defined as t0
+
+# Define t1 source
+define
+DefSource name=t1
+  - c0: bigint
+  - c1: bigint
+----
+Source defined as t1
+
+
+# Cases that should be rewritten
+# ------------------------------
+
+# A range join is rewritten to join on buckets of width 10. Ranges that span
+# more than 100 buckets are marked as wide and joined with all points.
+apply pipeline=range_join_bucketing
+Filter (#0 >= #2) AND (#0 <= #3)
+  CrossJoin
+    Get t0
+    Get t1
+----
+Filter (#0 >= #2) AND (#0 <= #3)
+  Project (#0, #1, #4, #5)
+    Join on=(#2 = #6 AND #3 = #7)
+      Map (case when (#2 = 0) then (#0 / 10) else 0 end)
+        FlatMap generate_series(0, 1, 1)
+          Get t0
+      FlatMap generate_series(case when (#2 = 1) then 0 else (#0 / 10) end, case when (#2 = 1) then 0 else (#1 / 10) end, 1)
+        Map (case when ((bigint_to_numeric((#1 / 10)) - bigint_to_numeric((#0 / 10))) > 100) then 1 else 0 end)
+          Get t1
+
+# The range can come from the first input.
+apply pipeline=range_join_bucketing
+Filter (#0 < #3) AND (#3 < #1)
+  CrossJoin
+    Get t0
+    Get t1
+----
+Filter (#0 < #3) AND (#3 < #1)
+  Project (#0, #1, #4, #5)
+    Join on=(#6 = #2 AND #7 = #3)
+      FlatMap generate_series(case when (#2 = 1) then 0 else (#0 / 10) end, case when (#2 = 1) then 0 else (#1 / 10) end, 1)
+        Map (case when ((bigint_to_numeric((#1 / 10)) - bigint_to_numeric((#0 / 10))) > 100) then 1 else 0 end)
+          Get t0
+      Map (case when (#2 = 0) then (#1 / 10) else 0 end)
+        FlatMap generate_series(0, 1, 1)
+          Get t1
+
+
+# Cases that should not be rewritten
+# ----------------------------------
+
+# The inputs are already related by an equality.
+apply pipeline=range_join_bucketing
+Filter (#0 >= #2) AND (#0 <= #3)
+  Join on=(#1 = #3)
+    Get t0
+    Get t1
+----
+Filter (#0 >= #2) AND (#0 <= #3)
+  Join on=(#1 = #3)
+    Get t0
+    Get t1
+
+# The range is only bounded from one side.
+apply pipeline=range_join_bucketing
+Filter (#0 >= #2)
+  CrossJoin
+    Get t0
+    Get t1
+----
+Filter (#0 >= #2)
+  CrossJoin
+    Get t0
+    Get t1
@@ -228,6 +228,35 @@ def test_create_privatelink_connection(mz: MaterializeApplication) -> None:
             )
         )
 
+    with pytest.raises(
+        ProgrammingError,
+        match='AWS PrivateLink availability zone "use1-az3" does not match any of the availability zones on the AWS PrivateLink connection',
+    ):
+        mz.environmentd.sql(
+            dedent(
+                """\
+            CREATE CONNECTION mysql TO MYSQL (
+                HOST 'mysql',
+                USER root,
+                AWS PRIVATELINK privatelinkconn (AVAILABILITY ZONE 'use1-az3')
+            ) WITH (VALIDATE = false);
+            """
+            )
+        )
+
+    mz.environmentd.sql(
+        dedent(
+            """\
+            CREATE CONNECTION pg TO POSTGRES (
+                HOST 'postgres',
+                DATABASE postgres,
+                USER postgres,
+                AWS PRIVATELINK privatelinkconn (AVAILABILITY ZONE 'use1-az1')
+            ) WITH (VALIDATE = false);
+            """
+        )
+    )
+
     mz.environmentd.sql("DROP CONNECTION kafkaconn CASCADE")
     mz.environmentd.sql("DROP CONNECTION privatelinkconn CASCADE")
 
@@ -23,7 +23,11 @@ SERVICES = [
     Zookeeper(),
     Kafka(),
     SchemaRegistry(),
-    Materialized(),
+    # Shorten the reconnect grace period so that tests which kill the bastion
+    # don't wait a full minute for dependent objects to report errors.
+    Materialized(
+        additional_system_parameter_defaults={"ssh_reconnect_grace_period": "5s"}
+    ),
     Testdrive(consistent_seed=True),
     SshBastionHost(),
     Postgres(),